@@ -0,0 +1,127 @@
+use chrono::Utc;
+use tauri::{AppHandle, Listener, Manager};
+use crate::db::Database;
+use crate::services::{event_service, task_service};
+use crate::structs::dto::DateQuery;
+use crate::window_manager;
+use tracing::error;
+
+// Keep the dock/taskbar badge in sync with today's remaining task count
+pub fn init_badge(app: &AppHandle) {
+    for event in event_service::TASK_EVENTS {
+        let handle = app.clone();
+        app.listen_any(event, move |_| {
+            // Off the emitting thread: refreshing queries the database
+            let handle = handle.clone();
+            tauri::async_runtime::spawn_blocking(move || refresh_badge(&handle));
+        });
+    }
+    
+    refresh_badge(app);
+}
+
+// Recompute the remaining count and update the badge, or clear it when disabled
+pub fn refresh_badge(app: &AppHandle) {
+    let count = match remaining_count(app) {
+        Ok(count) => count,
+        Err(e) => {
+            error!("Failed to compute badge count: {}", e);
+            return;
+        }
+    };
+    
+    if let Some(window) = app.get_webview_window(window_manager::MAIN_WINDOW) {
+        if let Err(e) = set_badge(&window, count) {
+            error!("Failed to update badge: {}", e);
+        }
+    }
+}
+
+// None when the badge is turned off in settings
+fn remaining_count(app: &AppHandle) -> Result<Option<i64>, String> {
+    let db = app.try_state::<Database>()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    let show_badge = db.settings()
+        .map_err(|e| format!("Failed to fetch settings: {}", e))?
+        .show_task_badge;
+    
+    if !show_badge {
+        return Ok(None);
+    }
+    
+    let query = DateQuery {
+        date: Utc::now().to_rfc3339(),
+        utc_offset_minutes: None,
+    };
+    let overview = task_service::get_today_overview(query, &db)?;
+    Ok(Some(overview.remaining_count).filter(|count| *count > 0))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn set_badge(window: &tauri::WebviewWindow, count: Option<i64>) -> tauri::Result<()> {
+    window.set_badge_count(count)
+}
+
+// Windows has no badge count; draw the number into a taskbar overlay icon instead
+#[cfg(target_os = "windows")]
+fn set_badge(window: &tauri::WebviewWindow, count: Option<i64>) -> tauri::Result<()> {
+    let icon = count.map(|count| tauri::image::Image::new_owned(overlay_pixels(count), OVERLAY_SIZE, OVERLAY_SIZE));
+    window.set_overlay_icon(icon)
+}
+
+#[cfg(target_os = "windows")]
+const OVERLAY_SIZE: u32 = 16;
+
+// 3x5 bitmaps for digits 0-9, one row per entry, high bit on the left
+#[cfg(target_os = "windows")]
+const DIGITS: [[u8; 5]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b001, 0b001, 0b001],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+];
+
+// Red disc with the count in white; counts above 9 show a plain disc
+#[cfg(target_os = "windows")]
+fn overlay_pixels(count: i64) -> Vec<u8> {
+    let size = OVERLAY_SIZE as i32;
+    let center = (size - 1) as f32 / 2.0;
+    let mut rgba = vec![0u8; (size * size * 4) as usize];
+    
+    for y in 0..size {
+        for x in 0..size {
+            let distance = ((x as f32 - center).powi(2) + (y as f32 - center).powi(2)).sqrt();
+            if distance <= center {
+                let i = ((y * size + x) * 4) as usize;
+                rgba[i..i + 4].copy_from_slice(&[220, 38, 38, 255]);
+            }
+        }
+    }
+    
+    if let Some(digit) = DIGITS.get(count as usize) {
+        // Each bitmap pixel is drawn 2x2, giving a 6x10 glyph centered in the disc
+        let (left, top) = (5, 3);
+        for (row, bits) in digit.iter().enumerate() {
+            for col in 0..3 {
+                if bits & (0b100 >> col) == 0 {
+                    continue;
+                }
+                for (dx, dy) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+                    let x = left + col * 2 + dx;
+                    let y = top + row as i32 * 2 + dy;
+                    let i = ((y * size + x) * 4) as usize;
+                    rgba[i..i + 4].copy_from_slice(&[255, 255, 255, 255]);
+                }
+            }
+        }
+    }
+    
+    rgba
+}
@@ -0,0 +1,182 @@
+use std::path::PathBuf;
+use chrono::{Duration, Local, NaiveDate, TimeZone, Utc};
+use crate::db::{self, Database};
+use crate::helpers::datetime::deadline_on;
+use crate::services::{settings_service, task_service};
+use crate::structs::dto::DateQuery;
+use crate::structs::task_struct::{Task, TaskId};
+
+const USAGE: &str = "Usage:
+  myhandler add \"title\" [--due today|tomorrow|YYYY-MM-DD]
+  myhandler list [--today | --date YYYY-MM-DD]
+  myhandler complete <task id>";
+
+enum CliCommand {
+    Add { title: String, due: Option<NaiveDate> },
+    List { date: NaiveDate },
+    Complete { id: TaskId },
+    Help,
+}
+
+// Run a headless command if the arguments name one; returns the exit code,
+// or None to start the UI (plain launches, deep-link URLs, unknown arguments)
+pub fn run(identifier: &str) -> Option<i32> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = match parse(&args)? {
+        Ok(command) => command,
+        Err(e) => {
+            attach_console();
+            eprintln!("{}\n\n{}", e, USAGE);
+            return Some(2);
+        }
+    };
+    
+    attach_console();
+    match execute(command, identifier) {
+        Ok(()) => Some(0),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            Some(1)
+        }
+    }
+}
+
+fn parse(args: &[String]) -> Option<Result<CliCommand, String>> {
+    let (name, rest) = args.split_first()?;
+    
+    let command = match name.as_str() {
+        "add" => parse_add(rest),
+        "list" => parse_list(rest),
+        "complete" => match rest {
+            [id] => id.parse().map(|id| CliCommand::Complete { id }),
+            _ => Err("complete takes exactly one task id".to_string()),
+        },
+        "help" | "--help" | "-h" => Ok(CliCommand::Help),
+        _ => return None,
+    };
+    
+    Some(command)
+}
+
+fn parse_add(args: &[String]) -> Result<CliCommand, String> {
+    let mut title = None;
+    let mut due = None;
+    let mut args = args.iter();
+    
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--due" => {
+                let value = args.next().ok_or("--due needs a value")?;
+                due = Some(parse_day(value)?);
+            }
+            _ if title.is_none() => title = Some(arg.trim().to_string()),
+            _ => return Err(format!("Unexpected argument: {}", arg)),
+        }
+    }
+    
+    let title = title.filter(|t| !t.is_empty()).ok_or("add needs a task title")?;
+    Ok(CliCommand::Add { title, due })
+}
+
+fn parse_list(args: &[String]) -> Result<CliCommand, String> {
+    match args {
+        [] => Ok(CliCommand::List { date: Local::now().date_naive() }),
+        [flag] if flag == "--today" => Ok(CliCommand::List { date: Local::now().date_naive() }),
+        [flag, value] if flag == "--date" => Ok(CliCommand::List { date: parse_day(value)? }),
+        _ => Err("list takes --today or --date YYYY-MM-DD".to_string()),
+    }
+}
+
+fn parse_day(value: &str) -> Result<NaiveDate, String> {
+    let today = Local::now().date_naive();
+    match value {
+        "today" => Ok(today),
+        "tomorrow" => Ok(today + Duration::days(1)),
+        _ => NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .map_err(|_| format!("Invalid date '{}': use today, tomorrow or YYYY-MM-DD", value)),
+    }
+}
+
+fn execute(command: CliCommand, identifier: &str) -> Result<(), String> {
+    if let CliCommand::Help = command {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+    
+    // Same file the app uses; a running app picks up changes on its next refresh
+    let db = open_database(identifier)?;
+    
+    match command {
+        CliCommand::Add { title, due } => {
+            let deadline = match due {
+                Some(day) => Some(local_deadline(day, &db)?),
+                None => None,
+            };
+            let task = task_service::add_task(&title, Utc::now(), deadline, &db)?;
+            println!("{}", task.id);
+        }
+        CliCommand::List { date } => {
+            // Days of this machine's time zone; noon lands inside the right one
+            let noon = Local.from_local_datetime(&date.and_hms_opt(12, 0, 0).ok_or("Invalid date")?)
+                .earliest()
+                .ok_or_else(|| format!("Invalid local date: {}", date))?;
+            let query = DateQuery {
+                date: noon.to_rfc3339(),
+                utc_offset_minutes: Some(noon.offset().local_minus_utc() / 60),
+            };
+            for item in task_service::get_tasks_by_date(query, &db)? {
+                println!("{}", format_task(&item.task));
+            }
+        }
+        CliCommand::Complete { id } => {
+            let task = tauri::async_runtime::block_on(task_service::finish_task(id, None, &db, None))?;
+            println!("Completed: {}", task.title);
+        }
+        CliCommand::Help => {}
+    }
+    
+    Ok(())
+}
+
+// Mirrors Tauri's app data dir (<data dir>/<identifier>) without building an app
+fn open_database(identifier: &str) -> Result<Database, String> {
+    let path: PathBuf = dirs::data_dir()
+        .ok_or("Could not determine the data directory")?
+        .join(identifier)
+        .join(db::DB_FILE_NAME);
+    
+    if !path.exists() {
+        return Err(format!("No database at {:?}; start the app once first", path));
+    }
+    
+    let db = Database::open(&path).map_err(|e| e.to_string())?;
+    db::prepare_file_connection(&db.get_connection()).map_err(|e| e.to_string())?;
+    Ok(db)
+}
+
+// The default deadline time from settings, in this machine's time zone
+fn local_deadline(date: NaiveDate, db: &Database) -> Result<chrono::DateTime<Utc>, String> {
+    let time = settings_service::default_deadline_time(db)?;
+    deadline_on(date, time, &Local)
+        .ok_or_else(|| format!("Invalid local date: {}", date))
+}
+
+fn format_task(task: &Task) -> String {
+    let due = task.deadline
+        .map(|d| format!("  (due {})", d.with_timezone(&Local).format("%Y-%m-%d %H:%M")))
+        .unwrap_or_default();
+    format!("{}  {:<11}  {}{}", task.id, String::from(task.status.clone()), task.title, due)
+}
+
+// Release builds use the Windows GUI subsystem, so borrow the parent terminal for output
+#[cfg(target_os = "windows")]
+fn attach_console() {
+    use windows_sys::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+    // SAFETY: plain Win32 call; failure just means there is no parent console
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn attach_console() {}
@@ -1,19 +1,66 @@
-use tauri::State;
+use tauri::{AppHandle, State};
 use crate::db;
-use crate::services::calendar_service;
-use crate::structs::calendar::CalendarCredentials;
+use crate::services::{calendar_rule_service, calendar_service, work_session_service};
+use crate::structs::calendar::{CalendarCredentials, CalendarHealth, CalendarUsageQuery, CalendarUsageStats, DisconnectCalendarData};
+use crate::structs::calendar_rule::{
+    CalendarEventRule, CalendarEventRuleData, CalendarEventRuleId, CalendarEventRuleUpdate, CalendarPullReport,
+};
+use crate::structs::work_session::WorkSessionReport;
+use crate::perf;
 
 #[tauri::command]
-pub async fn start_calendar_auth(db: State<'_, db::Database>) -> Result<CalendarCredentials, String> {
-    calendar_service::start_oauth_flow(&db).await
+pub async fn start_calendar_auth(app: AppHandle, db: State<'_, db::Database>) -> Result<CalendarCredentials, String> {
+    perf::timed_async("start_calendar_auth", calendar_service::start_oauth_flow(&app, &db)).await
 }
 
 #[tauri::command]
 pub fn get_calendar_status(db: State<'_, db::Database>) -> Result<Option<CalendarCredentials>, String> {
-    calendar_service::get_credentials(&db)
+    perf::timed("get_calendar_status", || calendar_service::get_credentials(&db))
 }
 
 #[tauri::command]
-pub fn disconnect_calendar(db: State<'_, db::Database>) -> Result<(), String> {
-    calendar_service::disconnect_calendar(&db)
+pub async fn check_calendar_connection(db: State<'_, db::Database>) -> Result<CalendarHealth, String> {
+    perf::timed_async("check_calendar_connection", calendar_service::check_calendar_connection(&db)).await
+}
+
+#[tauri::command]
+pub fn disconnect_calendar(payload: Option<DisconnectCalendarData>, db: State<'_, db::Database>) -> Result<(), String> {
+    perf::timed("disconnect_calendar", || calendar_service::disconnect_calendar(&db, payload.unwrap_or_default()))
+}
+
+#[tauri::command]
+pub fn get_calendar_usage_stats(payload: CalendarUsageQuery, db: State<'_, db::Database>) -> Result<CalendarUsageStats, String> {
+    perf::timed("get_calendar_usage_stats", || calendar_service::get_calendar_usage_stats(&db, payload))
+}
+
+// Post finished work sessions now instead of waiting for the end-of-day job
+#[tauri::command]
+pub async fn post_work_sessions(app: AppHandle) -> Result<WorkSessionReport, String> {
+    perf::timed_async("post_work_sessions", work_session_service::post_work_sessions(&app)).await
+}
+
+#[tauri::command]
+pub fn get_calendar_event_rules(db: State<'_, db::Database>) -> Result<Vec<CalendarEventRule>, String> {
+    perf::timed("get_calendar_event_rules", || calendar_rule_service::list_rules(&db))
+}
+
+#[tauri::command]
+pub fn create_calendar_event_rule(payload: CalendarEventRuleData, db: State<'_, db::Database>) -> Result<CalendarEventRule, String> {
+    perf::timed("create_calendar_event_rule", || calendar_rule_service::create_rule(&db, payload))
+}
+
+#[tauri::command]
+pub fn update_calendar_event_rule(payload: CalendarEventRuleUpdate, db: State<'_, db::Database>) -> Result<CalendarEventRule, String> {
+    perf::timed("update_calendar_event_rule", || calendar_rule_service::update_rule(&db, payload))
+}
+
+#[tauri::command]
+pub fn delete_calendar_event_rule(payload: CalendarEventRuleId, db: State<'_, db::Database>) -> Result<(), String> {
+    perf::timed("delete_calendar_event_rule", || calendar_rule_service::delete_rule(&db, payload))
+}
+
+// Run the calendar event rules now instead of waiting for the pull job
+#[tauri::command]
+pub async fn pull_calendar_events(app: AppHandle) -> Result<CalendarPullReport, String> {
+    perf::timed_async("pull_calendar_events", calendar_rule_service::pull_calendar_events(&app)).await
 }
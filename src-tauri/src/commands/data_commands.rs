@@ -0,0 +1,99 @@
+use tauri::{AppHandle, State};
+use crate::db;
+use crate::services::{agenda_service, data_service, export_service, import_service, settings_service, snapshot_service, stats_service, sync_service, vault_service};
+use crate::structs::data_export::{ArchiveData, CsvExportData, PdfAgendaData, MarkdownSummary, MarkdownSummaryData, SharedDay, SharedDayData, TaskImportData, DataExportData, DataImportData, ImportMode, ImportSummary};
+use crate::{badge, window_manager};
+use crate::perf;
+
+#[tauri::command]
+pub fn export_data(payload: DataExportData, db: State<db::Database>, app: AppHandle) -> Result<(), String> {
+  perf::timed("export_data", || data_service::export_data(&db, &app, &payload.path))
+}
+
+// Imports run in the background, emit import-progress events and stop on cancel_import
+#[tauri::command]
+pub async fn import_data(payload: DataImportData, app: AppHandle) -> Result<ImportSummary, String> {
+  perf::timed_async("import_data", import_service::run_in_background(app, move |db, app| {
+    let summary = data_service::import_data(db, app, &payload.path, payload.mode)?;
+    after_import(db, app, payload.mode)?;
+    Ok(summary)
+  })).await
+}
+
+#[tauri::command]
+pub fn export_archive(payload: ArchiveData, db: State<db::Database>, app: AppHandle) -> Result<(), String> {
+  perf::timed("export_archive", || data_service::export_archive(&db, &app, &payload.path))
+}
+
+// Always replaces local data: archives are for moving to a new machine
+#[tauri::command]
+pub async fn import_archive(payload: ArchiveData, app: AppHandle) -> Result<ImportSummary, String> {
+  perf::timed_async("import_archive", import_service::run_in_background(app, move |db, app| {
+    let summary = data_service::import_archive(db, app, &payload.path)?;
+    after_import(db, app, ImportMode::Replace)?;
+    Ok(summary)
+  })).await
+}
+
+// Imports bypass task events, so refresh everything that listens to them
+fn after_import(db: &db::Database, app: &AppHandle, mode: ImportMode) -> Result<(), String> {
+  if mode == ImportMode::Replace {
+    // Settings came from the file; apply the ones that live outside the UI
+    let settings = settings_service::get_settings(db)?;
+    window_manager::register_quick_add_shortcut(app, &settings.quick_add_shortcut)?;
+  }
+  badge::refresh_badge(app);
+  vault_service::refresh_vault(app);
+  sync_service::queue_all_tasks(app)?;
+  stats_service::clear_cache();
+  Ok(())
+}
+
+#[tauri::command]
+pub fn export_csv(payload: CsvExportData, db: State<db::Database>) -> Result<usize, String> {
+  perf::timed("export_csv", || export_service::export_csv(&db, payload))
+}
+
+#[tauri::command]
+pub fn export_pdf_agenda(payload: PdfAgendaData, db: State<db::Database>) -> Result<usize, String> {
+  perf::timed("export_pdf_agenda", || agenda_service::export_pdf_agenda(&db, payload))
+}
+
+#[tauri::command]
+pub fn export_markdown_summary(payload: MarkdownSummaryData, db: State<db::Database>) -> Result<MarkdownSummary, String> {
+  perf::timed("export_markdown_summary", || export_service::export_markdown_summary(&db, payload))
+}
+
+#[tauri::command]
+pub fn export_shared_day(payload: SharedDayData, db: State<db::Database>) -> Result<SharedDay, String> {
+  perf::timed("export_shared_day", || export_service::export_shared_day(&db, payload))
+}
+
+#[tauri::command]
+pub async fn import_tasks(payload: TaskImportData, app: AppHandle) -> Result<ImportSummary, String> {
+  perf::timed_async("import_tasks", import_service::run_in_background(app, move |db, app| {
+    let summary = import_service::import_tasks(db, app, payload)?;
+    after_import(db, app, ImportMode::Merge)?;
+    Ok(summary)
+  })).await
+}
+
+// The macOS permission prompt and osascript also run on the background thread
+#[tauri::command]
+pub async fn import_apple_reminders(app: AppHandle) -> Result<ImportSummary, String> {
+  perf::timed_async("import_apple_reminders", import_service::run_in_background(app, |db, app| {
+    let summary = import_service::import_apple_reminders(db, app)?;
+    after_import(db, app, ImportMode::Merge)?;
+    Ok(summary)
+  })).await
+}
+
+#[tauri::command]
+pub fn cancel_import() -> Result<(), String> {
+  perf::timed("cancel_import", import_service::cancel_import)
+}
+
+#[tauri::command]
+pub fn open_snapshot_folder(app: AppHandle) -> Result<(), String> {
+  perf::timed("open_snapshot_folder", || snapshot_service::open_snapshot_folder(&app))
+}
@@ -0,0 +1,20 @@
+use tauri::State;
+use crate::db;
+use crate::services::dependency_service;
+use crate::structs::dependency::{DependencyGraph, DependencyGraphQuery, TaskDependency};
+use crate::perf;
+
+#[tauri::command]
+pub fn add_task_dependency(payload: TaskDependency, db: State<db::Database>) -> Result<(), String> {
+  perf::timed("add_task_dependency", || dependency_service::add_task_dependency(&db, payload))
+}
+
+#[tauri::command]
+pub fn remove_task_dependency(payload: TaskDependency, db: State<db::Database>) -> Result<(), String> {
+  perf::timed("remove_task_dependency", || dependency_service::remove_task_dependency(&db, payload))
+}
+
+#[tauri::command]
+pub fn get_dependency_graph(payload: DependencyGraphQuery, db: State<db::Database>) -> Result<DependencyGraph, String> {
+  perf::timed("get_dependency_graph", || dependency_service::get_dependency_graph(&db, payload))
+}
@@ -0,0 +1,28 @@
+use tauri::{AppHandle, State};
+use crate::db;
+use crate::services::app_info_service::{self, CommandList};
+use crate::services::diagnostics_service;
+use crate::structs::app_info::AppInfo;
+use crate::structs::dto::DiagnosticsExportData;
+use crate::structs::startup::StartupProgress;
+use crate::{perf, startup};
+
+#[tauri::command]
+pub fn export_diagnostics(payload: DiagnosticsExportData, db: State<db::Database>, app: AppHandle) -> Result<(), String> {
+  perf::timed("export_diagnostics", || diagnostics_service::export_diagnostics(&db, &app, &payload.path))
+}
+
+#[tauri::command]
+pub fn get_performance_stats() -> Vec<perf::CommandStats> {
+  perf::get_performance_stats()
+}
+
+#[tauri::command]
+pub fn get_app_info(db: State<db::Database>, app: AppHandle, commands: State<CommandList>) -> Result<AppInfo, String> {
+  perf::timed("get_app_info", || app_info_service::get_app_info(&db, &app, &commands))
+}
+
+#[tauri::command]
+pub fn get_startup_phase(app: AppHandle) -> Result<Option<StartupProgress>, String> {
+  perf::timed("get_startup_phase", || startup::get_startup_phase(&app))
+}
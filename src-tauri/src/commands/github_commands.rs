@@ -0,0 +1,30 @@
+use tauri::{AppHandle, State};
+use crate::db;
+use crate::services::github_service;
+use crate::structs::github::{GithubCloseIssuesData, GithubConnectData, GithubStatus, GithubSyncSummary};
+use crate::perf;
+
+#[tauri::command]
+pub async fn connect_github(payload: GithubConnectData, db: State<'_, db::Database>) -> Result<GithubStatus, String> {
+  perf::timed_async("connect_github", github_service::connect_github(&db, &payload.token, payload.close_issues_on_complete)).await
+}
+
+#[tauri::command]
+pub fn get_github_status(db: State<db::Database>) -> Result<Option<GithubStatus>, String> {
+  perf::timed("get_github_status", || github_service::get_github_status(&db))
+}
+
+#[tauri::command]
+pub fn disconnect_github(db: State<db::Database>) -> Result<(), String> {
+  perf::timed("disconnect_github", || github_service::disconnect_github(&db))
+}
+
+#[tauri::command]
+pub fn set_github_close_issues(payload: GithubCloseIssuesData, db: State<db::Database>) -> Result<GithubStatus, String> {
+  perf::timed("set_github_close_issues", || github_service::set_close_issues_on_complete(&db, payload.enabled))
+}
+
+#[tauri::command]
+pub async fn sync_github_issues(db: State<'_, db::Database>, app: AppHandle) -> Result<GithubSyncSummary, String> {
+  perf::timed_async("sync_github_issues", github_service::sync_github_issues(&db, &app)).await
+}
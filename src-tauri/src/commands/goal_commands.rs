@@ -0,0 +1,30 @@
+use tauri::State;
+use crate::db;
+use crate::services::goal_service;
+use crate::structs::goal::{Goal, GoalData, GoalId, GoalProgress, GoalProgressQuery, GoalUpdate};
+use crate::perf;
+
+#[tauri::command]
+pub fn create_goal(payload: GoalData, db: State<db::Database>) -> Result<Goal, String> {
+  perf::timed("create_goal", || goal_service::create_goal(&db, payload))
+}
+
+#[tauri::command]
+pub fn get_goals(db: State<db::Database>) -> Result<Vec<Goal>, String> {
+  perf::timed("get_goals", || goal_service::get_goals(&db))
+}
+
+#[tauri::command]
+pub fn update_goal(payload: GoalUpdate, db: State<db::Database>) -> Result<Goal, String> {
+  perf::timed("update_goal", || goal_service::update_goal(&db, payload))
+}
+
+#[tauri::command]
+pub fn delete_goal(payload: GoalId, db: State<db::Database>) -> Result<(), String> {
+  perf::timed("delete_goal", || goal_service::delete_goal(&db, payload))
+}
+
+#[tauri::command]
+pub fn get_goal_progress(payload: GoalProgressQuery, db: State<db::Database>) -> Result<Vec<GoalProgress>, String> {
+  perf::timed("get_goal_progress", || goal_service::get_goal_progress(&db, payload))
+}
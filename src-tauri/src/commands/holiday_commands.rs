@@ -0,0 +1,20 @@
+use tauri::State;
+use crate::db;
+use crate::services::holiday_service;
+use crate::structs::holiday::{HolidayImport, HolidayImportData, HolidayRangeQuery, ListedHoliday};
+use crate::perf;
+
+#[tauri::command]
+pub fn list_holidays(payload: HolidayRangeQuery, db: State<db::Database>) -> Result<Vec<ListedHoliday>, String> {
+  perf::timed("list_holidays", || holiday_service::list_holidays(&db, payload))
+}
+
+#[tauri::command]
+pub fn import_holidays(payload: HolidayImportData, db: State<db::Database>) -> Result<HolidayImport, String> {
+  perf::timed("import_holidays", || holiday_service::import_holidays(&db, payload))
+}
+
+#[tauri::command]
+pub fn clear_holidays(db: State<db::Database>) -> Result<usize, String> {
+  perf::timed("clear_holidays", || holiday_service::clear_holidays(&db))
+}
@@ -0,0 +1,26 @@
+use tauri::{AppHandle, State};
+use crate::db;
+use crate::services::inbox_service;
+use crate::structs::inbox::{CaptureData, InboxItem, InboxItemRef, TriageData};
+use crate::structs::task_struct::Task;
+use crate::perf;
+
+#[tauri::command]
+pub fn capture(payload: CaptureData, db: State<db::Database>) -> Result<InboxItem, String> {
+  perf::timed("capture", || inbox_service::capture(&db, payload))
+}
+
+#[tauri::command]
+pub fn list_inbox(db: State<db::Database>) -> Result<Vec<InboxItem>, String> {
+  perf::timed("list_inbox", || inbox_service::list_inbox(&db))
+}
+
+#[tauri::command]
+pub fn triage_inbox_item(payload: TriageData, app: AppHandle, db: State<db::Database>) -> Result<Task, String> {
+  perf::timed("triage_inbox_item", || inbox_service::triage_inbox_item(&db, &app, payload))
+}
+
+#[tauri::command]
+pub fn discard_inbox_item(payload: InboxItemRef, db: State<db::Database>) -> Result<(), String> {
+  perf::timed("discard_inbox_item", || inbox_service::discard_inbox_item(&db, payload))
+}
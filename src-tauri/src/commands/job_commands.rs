@@ -0,0 +1,15 @@
+use tauri::{AppHandle, State};
+use crate::db;
+use crate::structs::job::{Job, JobName};
+use crate::services::scheduler_service;
+use crate::perf;
+
+#[tauri::command]
+pub fn list_jobs(db: State<db::Database>) -> Result<Vec<Job>, String> {
+  perf::timed("list_jobs", || scheduler_service::list_jobs(&db))
+}
+
+#[tauri::command]
+pub async fn run_job_now(payload: JobName, app: AppHandle) -> Result<Job, String> {
+  perf::timed_async("run_job_now", scheduler_service::run_job_now(&app, &payload.name)).await
+}
@@ -0,0 +1,30 @@
+use tauri::AppHandle;
+use crate::services::lan_sync_service;
+use crate::structs::dto::{LanDeviceId, LanPairData};
+use crate::structs::sync::{LanDevice, LanPairingCode, SyncReport};
+use crate::perf;
+
+#[tauri::command]
+pub fn get_lan_devices(app: AppHandle) -> Result<Vec<LanDevice>, String> {
+  perf::timed("get_lan_devices", || lan_sync_service::get_lan_devices(&app))
+}
+
+#[tauri::command]
+pub fn start_lan_pairing(app: AppHandle) -> Result<LanPairingCode, String> {
+  perf::timed("start_lan_pairing", || lan_sync_service::start_lan_pairing(&app))
+}
+
+#[tauri::command]
+pub async fn pair_lan_device(payload: LanPairData, app: AppHandle) -> Result<LanDevice, String> {
+  perf::timed_async("pair_lan_device", lan_sync_service::pair_lan_device(&app, &payload.device_id, &payload.code)).await
+}
+
+#[tauri::command]
+pub fn unpair_lan_device(payload: LanDeviceId, app: AppHandle) -> Result<(), String> {
+  perf::timed("unpair_lan_device", || lan_sync_service::unpair_lan_device(&app, &payload.device_id))
+}
+
+#[tauri::command]
+pub async fn sync_lan_now(app: AppHandle) -> Result<SyncReport, String> {
+  perf::timed_async("sync_lan_now", lan_sync_service::sync_lan_now(&app)).await
+}
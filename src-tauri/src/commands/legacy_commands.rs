@@ -0,0 +1,33 @@
+use tauri::State;
+use crate::db;
+use crate::services::legacy_service;
+use crate::structs::legacy::{DateSection, LegacySearchQuery};
+use crate::structs::task_struct::Task;
+use crate::perf;
+
+// Old invoke names; see legacy_service
+
+#[tauri::command]
+pub fn get_ongoing_task(db: State<db::Database>) -> Result<Option<Task>, String> {
+  perf::timed("get_ongoing_task", || legacy_service::get_ongoing_task(&db))
+}
+
+#[tauri::command]
+pub fn search_tasks(payload: LegacySearchQuery, db: State<db::Database>) -> Result<Vec<Task>, String> {
+  perf::timed("search_tasks", || legacy_service::search_tasks(&db, payload))
+}
+
+#[tauri::command]
+pub fn get_completed_tasks(db: State<db::Database>) -> Result<Vec<Task>, String> {
+  perf::timed("get_completed_tasks", || legacy_service::get_completed_tasks(&db))
+}
+
+#[tauri::command]
+pub fn get_all_dates_with_tasks(db: State<db::Database>) -> Result<Vec<String>, String> {
+  perf::timed("get_all_dates_with_tasks", || legacy_service::get_all_dates_with_tasks(&db))
+}
+
+#[tauri::command]
+pub fn get_date_sections(db: State<db::Database>) -> Result<Vec<DateSection>, String> {
+  perf::timed("get_date_sections", || legacy_service::get_date_sections(&db))
+}
@@ -0,0 +1,30 @@
+use tauri::{AppHandle, State};
+use crate::db;
+use crate::services::lock_service;
+use crate::structs::app_lock::{AppLockData, LockStatus, UnlockData};
+use crate::perf;
+
+#[tauri::command]
+pub fn get_lock_status(db: State<db::Database>) -> Result<LockStatus, String> {
+  perf::timed("get_lock_status", || lock_service::get_lock_status(&db))
+}
+
+#[tauri::command]
+pub async fn unlock_app(payload: UnlockData, app: AppHandle) -> Result<LockStatus, String> {
+  perf::timed_async("unlock_app", lock_service::unlock_app(&app, payload)).await
+}
+
+#[tauri::command]
+pub fn lock_app(app: AppHandle) -> Result<LockStatus, String> {
+  perf::timed("lock_app", || lock_service::lock_app(&app))
+}
+
+#[tauri::command]
+pub async fn set_app_lock(payload: AppLockData, app: AppHandle) -> Result<LockStatus, String> {
+  perf::timed_async("set_app_lock", lock_service::set_app_lock(&app, payload)).await
+}
+
+#[tauri::command]
+pub async fn disable_app_lock(payload: UnlockData, app: AppHandle) -> Result<LockStatus, String> {
+  perf::timed_async("disable_app_lock", lock_service::disable_app_lock(&app, payload)).await
+}
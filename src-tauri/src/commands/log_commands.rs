@@ -0,0 +1,14 @@
+use tauri::AppHandle;
+use crate::logging;
+use crate::structs::dto::LogQuery;
+use crate::perf;
+
+#[tauri::command]
+pub fn get_recent_logs(payload: LogQuery, app: AppHandle) -> Result<Vec<String>, String> {
+  perf::timed("get_recent_logs", || logging::get_recent_logs(&app, payload.lines))
+}
+
+#[tauri::command]
+pub fn open_log_folder(app: AppHandle) -> Result<(), String> {
+  perf::timed("open_log_folder", || logging::open_log_folder(&app))
+}
@@ -0,0 +1,15 @@
+use tauri::State;
+use crate::db;
+use crate::services::metrics_service;
+use crate::structs::metrics::{UsageMetrics, UsageMetricsExportData, UsageMetricsQuery};
+use crate::perf;
+
+#[tauri::command]
+pub fn get_usage_metrics(payload: UsageMetricsQuery, db: State<db::Database>) -> Result<UsageMetrics, String> {
+  perf::timed("get_usage_metrics", || metrics_service::get_usage_metrics(&db, payload))
+}
+
+#[tauri::command]
+pub fn export_usage_metrics(payload: UsageMetricsExportData, db: State<db::Database>) -> Result<usize, String> {
+  perf::timed("export_usage_metrics", || metrics_service::export_usage_metrics(&db, payload))
+}
@@ -1,7 +1,59 @@
 pub mod task_commands;
 pub mod setting_commands;
 pub mod calendar_commands;
+pub mod notification_commands;
+pub mod job_commands;
+pub mod log_commands;
+pub mod diagnostics_commands;
+pub mod recovery_commands;
+pub mod window_commands;
+pub mod data_commands;
+pub mod github_commands;
+pub mod stats_commands;
+pub mod goal_commands;
+pub mod metrics_commands;
+pub mod sync_commands;
+pub mod lan_sync_commands;
+pub mod webhook_commands;
+pub mod rule_commands;
+pub mod inbox_commands;
+pub mod schedule_commands;
+pub mod query_commands;
+pub mod holiday_commands;
+pub mod lock_commands;
+pub mod project_commands;
+pub mod remembered_date_commands;
+pub mod legacy_commands;
+pub mod tag_commands;
+pub mod dependency_commands;
+pub mod recurrence_commands;
 
 pub use task_commands::*;
 pub use setting_commands::*;
-pub use calendar_commands::*;
\ No newline at end of file
+pub use calendar_commands::*;
+pub use notification_commands::*;
+pub use job_commands::*;
+pub use log_commands::*;
+pub use diagnostics_commands::*;
+pub use recovery_commands::*;
+pub use window_commands::*;
+pub use data_commands::*;
+pub use github_commands::*;
+pub use stats_commands::*;
+pub use goal_commands::*;
+pub use metrics_commands::*;
+pub use sync_commands::*;
+pub use lan_sync_commands::*;
+pub use webhook_commands::*;
+pub use rule_commands::*;
+pub use inbox_commands::*;
+pub use schedule_commands::*;
+pub use query_commands::*;
+pub use holiday_commands::*;
+pub use lock_commands::*;
+pub use project_commands::*;
+pub use remembered_date_commands::*;
+pub use legacy_commands::*;
+pub use tag_commands::*;
+pub use dependency_commands::*;
+pub use recurrence_commands::*;
\ No newline at end of file
@@ -0,0 +1,10 @@
+use tauri::{AppHandle, State};
+use crate::db;
+use crate::structs::dto::TaskRef;
+use crate::services::notification_service;
+use crate::perf;
+
+#[tauri::command]
+pub fn notify_pomodoro_finished(payload: TaskRef, app: AppHandle, db: State<db::Database>) -> Result<(), String> {
+  perf::timed("notify_pomodoro_finished", || notification_service::notify_pomodoro_finished(&app, &db, payload.id))
+}
@@ -0,0 +1,46 @@
+use tauri::{AppHandle, State};
+use crate::db;
+use crate::services::project_service;
+use crate::structs::project::{ArchiveProjectData, ArchiveProjectSummary, JoinProjectData, ProjectData, ProjectDetail, ProjectId, ProjectInvite, ProjectMembersData, ShareProjectData};
+use crate::structs::sync::SyncReport;
+use crate::perf;
+
+#[tauri::command]
+pub fn create_project(payload: ProjectData, db: State<db::Database>) -> Result<ProjectDetail, String> {
+  perf::timed("create_project", || project_service::create_project(&db, payload))
+}
+
+#[tauri::command]
+pub fn get_projects(db: State<db::Database>) -> Result<Vec<ProjectDetail>, String> {
+  perf::timed("get_projects", || project_service::get_projects(&db))
+}
+
+#[tauri::command]
+pub fn delete_project(payload: ProjectId, app: AppHandle, db: State<db::Database>) -> Result<(), String> {
+  perf::timed("delete_project", || project_service::delete_project(&db, &app, payload))
+}
+
+#[tauri::command]
+pub async fn archive_project(payload: ArchiveProjectData, app: AppHandle, db: State<'_, db::Database>) -> Result<ArchiveProjectSummary, String> {
+  perf::timed_async("archive_project", project_service::archive_project(&db, &app, payload)).await
+}
+
+#[tauri::command]
+pub async fn share_project(payload: ShareProjectData, app: AppHandle) -> Result<ProjectInvite, String> {
+  perf::timed_async("share_project", project_service::share_project(&app, payload)).await
+}
+
+#[tauri::command]
+pub async fn join_shared_project(payload: JoinProjectData, app: AppHandle) -> Result<ProjectDetail, String> {
+  perf::timed_async("join_shared_project", project_service::join_shared_project(&app, payload)).await
+}
+
+#[tauri::command]
+pub async fn set_project_members(payload: ProjectMembersData, app: AppHandle) -> Result<ProjectDetail, String> {
+  perf::timed_async("set_project_members", project_service::set_project_members(&app, payload)).await
+}
+
+#[tauri::command]
+pub async fn sync_shared_projects(app: AppHandle) -> Result<SyncReport, String> {
+  perf::timed_async("sync_shared_projects", project_service::sync_shared_projects(&app)).await
+}
@@ -0,0 +1,10 @@
+use tauri::State;
+use crate::db;
+use crate::services::query_service;
+use crate::structs::query::{QueryData, QueryResult};
+use crate::perf;
+
+#[tauri::command]
+pub fn run_readonly_query(payload: QueryData, db: State<db::Database>) -> Result<QueryResult, String> {
+  perf::timed("run_readonly_query", || query_service::run_readonly_query(&db, payload))
+}
@@ -0,0 +1,14 @@
+use tauri::AppHandle;
+use crate::services::recovery_service;
+use crate::structs::recovery::{RecoveryRequest, RecoveryStatus};
+use crate::perf;
+
+#[tauri::command]
+pub fn get_recovery_status(app: AppHandle) -> Result<Option<RecoveryStatus>, String> {
+  perf::timed("get_recovery_status", || recovery_service::get_recovery_status(&app))
+}
+
+#[tauri::command]
+pub fn recover_database(payload: RecoveryRequest, app: AppHandle) -> Result<(), String> {
+  perf::timed("recover_database", || recovery_service::recover_database(&app, payload.action))
+}
@@ -0,0 +1,26 @@
+use tauri::{AppHandle, State};
+use crate::db;
+use crate::services::recurrence_service;
+use crate::structs::dto::TaskRef;
+use crate::structs::recurrence::{RecurrenceData, RecurrenceException, RecurrenceExceptionData, RecurrenceView, TaskRecurrence};
+use crate::perf;
+
+#[tauri::command]
+pub fn set_task_recurrence(payload: RecurrenceData, db: State<db::Database>) -> Result<TaskRecurrence, String> {
+  perf::timed("set_task_recurrence", || recurrence_service::set_task_recurrence(&db, payload))
+}
+
+#[tauri::command]
+pub fn remove_task_recurrence(payload: TaskRef, db: State<db::Database>) -> Result<(), String> {
+  perf::timed("remove_task_recurrence", || recurrence_service::remove_task_recurrence(&db, payload))
+}
+
+#[tauri::command]
+pub fn get_task_recurrence(payload: TaskRef, db: State<db::Database>) -> Result<Option<RecurrenceView>, String> {
+  perf::timed("get_task_recurrence", || recurrence_service::get_task_recurrence(&db, payload))
+}
+
+#[tauri::command]
+pub async fn add_recurrence_exception(payload: RecurrenceExceptionData, app: AppHandle, db: State<'_, db::Database>) -> Result<RecurrenceException, String> {
+  perf::timed_async("add_recurrence_exception", recurrence_service::add_recurrence_exception(&db, &app, payload)).await
+}
@@ -0,0 +1,25 @@
+use tauri::State;
+use crate::db;
+use crate::services::remembered_date_service;
+use crate::structs::remembered_date::{RememberedDateData, RememberedDateId, RememberedDateUpdate, UpcomingDate};
+use crate::perf;
+
+#[tauri::command]
+pub fn list_remembered_dates(db: State<db::Database>) -> Result<Vec<UpcomingDate>, String> {
+  perf::timed("list_remembered_dates", || remembered_date_service::list_remembered_dates(&db))
+}
+
+#[tauri::command]
+pub fn add_remembered_date(payload: RememberedDateData, db: State<db::Database>) -> Result<UpcomingDate, String> {
+  perf::timed("add_remembered_date", || remembered_date_service::add_remembered_date(&db, payload))
+}
+
+#[tauri::command]
+pub fn update_remembered_date(payload: RememberedDateUpdate, db: State<db::Database>) -> Result<UpcomingDate, String> {
+  perf::timed("update_remembered_date", || remembered_date_service::update_remembered_date(&db, payload))
+}
+
+#[tauri::command]
+pub fn delete_remembered_date(payload: RememberedDateId, db: State<db::Database>) -> Result<(), String> {
+  perf::timed("delete_remembered_date", || remembered_date_service::delete_remembered_date(&db, payload))
+}
@@ -0,0 +1,25 @@
+use tauri::State;
+use crate::db;
+use crate::services::rule_service;
+use crate::structs::rule::{Rule, RuleData, RuleId, RuleUpdate};
+use crate::perf;
+
+#[tauri::command]
+pub fn create_rule(payload: RuleData, db: State<db::Database>) -> Result<Rule, String> {
+  perf::timed("create_rule", || rule_service::create_rule(&db, payload))
+}
+
+#[tauri::command]
+pub fn get_rules(db: State<db::Database>) -> Result<Vec<Rule>, String> {
+  perf::timed("get_rules", || rule_service::list_rules(&db))
+}
+
+#[tauri::command]
+pub fn update_rule(payload: RuleUpdate, db: State<db::Database>) -> Result<Rule, String> {
+  perf::timed("update_rule", || rule_service::update_rule(&db, payload))
+}
+
+#[tauri::command]
+pub fn delete_rule(payload: RuleId, db: State<db::Database>) -> Result<(), String> {
+  perf::timed("delete_rule", || rule_service::delete_rule(&db, payload))
+}
@@ -0,0 +1,16 @@
+use tauri::{AppHandle, State};
+use crate::db;
+use crate::services::schedule_service;
+use crate::structs::schedule::{ApplyScheduleData, SchedulePlan, ScheduleQuery};
+use crate::structs::task_struct::Task;
+use crate::perf;
+
+#[tauri::command]
+pub fn suggest_schedule(payload: ScheduleQuery, db: State<db::Database>) -> Result<SchedulePlan, String> {
+  perf::timed("suggest_schedule", || schedule_service::suggest_schedule(&db, payload))
+}
+
+#[tauri::command]
+pub fn apply_schedule(payload: ApplyScheduleData, app: AppHandle, db: State<db::Database>) -> Result<Vec<Task>, String> {
+  perf::timed("apply_schedule", || schedule_service::apply_schedule(&db, &app, payload))
+}
@@ -1,14 +1,85 @@
-use tauri::State;
+use tauri::{AppHandle, State};
 use crate::db;
+use crate::structs::network::{ConnectivityStatus, NetworkPermissions, NetworkPermissionsUpdate};
 use crate::structs::settings::{Settings, SettingsUpdateData};
-use crate::services::settings_service;
+use crate::structs::theme::{Theme, ThemeUpdateData};
+use crate::services::{connectivity_service, metrics_service, network_permission_service, scheduler_service, settings_service, sync_service, lan_sync_service, vault_service};
+use crate::{badge, http_api, window_manager};
+use crate::perf;
 
 #[tauri::command]
 pub fn get_settings(db: State<db::Database>) -> Result<Settings, String> {
-  settings_service::get_settings(&db)
+  perf::timed("get_settings", || settings_service::get_settings(&db))
 }
 
 #[tauri::command]
-pub fn update_settings(payload: SettingsUpdateData, db: State<db::Database>) -> Result<Settings, String> {
-  settings_service::update_settings(&db, payload)
-}
\ No newline at end of file
+pub fn update_settings(payload: SettingsUpdateData, app: AppHandle, db: State<db::Database>) -> Result<Settings, String> {
+  perf::timed("update_settings", || {
+    let shortcut_changed = payload.quick_add_shortcut.is_some();
+    let badge_changed = payload.show_task_badge.is_some();
+    let http_api_changed = payload.http_api_enabled.is_some() || payload.http_api_port.is_some();
+    let vault_changed = payload.vault_path.is_some() || payload.vault_layout.is_some();
+    let summary_time_changed = payload.daily_summary_time.is_some();
+    let sync_changed = payload.sync_folder.is_some()
+      || payload.sync_backend.is_some()
+      || payload.sync_webdav_url.is_some()
+      || payload.sync_webdav_username.is_some()
+      || payload.sync_webdav_password.is_some()
+      || payload.sync_passphrase.is_some();
+    let lan_sync_changed = payload.lan_sync_enabled.is_some() || payload.sync_device_name.is_some();
+    let settings = settings_service::update_settings(&db, payload)?;
+    if shortcut_changed {
+      window_manager::register_quick_add_shortcut(&app, &settings.quick_add_shortcut)?;
+    }
+    if badge_changed {
+      badge::refresh_badge(&app);
+    }
+    if http_api_changed {
+      http_api::apply_settings(&app)?;
+    }
+    if vault_changed {
+      vault_service::apply_settings(&app)?;
+    }
+    if summary_time_changed {
+      scheduler_service::set_daily_summary_time(&db, &settings.daily_summary_time)?;
+    }
+    if sync_changed {
+      sync_service::apply_settings(&app)?;
+    }
+    if lan_sync_changed {
+      lan_sync_service::apply_settings(&app)?;
+    }
+    metrics_service::set_enabled(settings.usage_metrics_enabled);
+    Ok(settings)
+  })
+}
+
+#[tauri::command]
+pub fn get_theme(db: State<db::Database>) -> Result<Theme, String> {
+  perf::timed("get_theme", || settings_service::get_theme(&db))
+}
+
+#[tauri::command]
+pub fn update_theme(payload: ThemeUpdateData, db: State<db::Database>) -> Result<Theme, String> {
+  perf::timed("update_theme", || settings_service::update_theme(&db, payload))
+}
+
+#[tauri::command]
+pub fn regenerate_http_api_token(app: AppHandle) -> Result<String, String> {
+  perf::timed("regenerate_http_api_token", || http_api::regenerate_token(&app))
+}
+
+#[tauri::command]
+pub fn get_connectivity(db: State<db::Database>) -> Result<ConnectivityStatus, String> {
+  perf::timed("get_connectivity", || connectivity_service::get_connectivity(&db))
+}
+
+#[tauri::command]
+pub fn get_network_permissions(db: State<db::Database>) -> Result<NetworkPermissions, String> {
+  perf::timed("get_network_permissions", || network_permission_service::get_network_permissions(&db))
+}
+
+#[tauri::command]
+pub fn set_network_permissions(payload: NetworkPermissionsUpdate, db: State<db::Database>) -> Result<NetworkPermissions, String> {
+  perf::timed("set_network_permissions", || network_permission_service::set_network_permissions(&db, payload))
+}
@@ -0,0 +1,56 @@
+use tauri::State;
+use crate::db;
+use crate::services::{analytics, deadline_service, stats_service};
+use crate::structs::dto::TaskRef;
+use crate::structs::stats::{CompletionHeatmap, ConsistencyQuery, ConsistencyScore, CycleTimeQuery, CycleTimeStats, DeadlineSuggestion, HeatmapQuery, PauseReasonQuery, PauseReasonStats, ProcrastinationStats, ProductivityBucket, ProductivityStatsQuery, TimeBreakdown, TimeBreakdownQuery, WeeklyReview, WeeklyReviewQuery, WorkloadForecast, WorkloadForecastQuery};
+use crate::perf;
+
+#[tauri::command]
+pub fn get_productivity_stats(payload: ProductivityStatsQuery, db: State<db::Database>) -> Result<Vec<ProductivityBucket>, String> {
+  perf::timed("get_productivity_stats", || stats_service::get_productivity_stats(&db, payload))
+}
+
+#[tauri::command]
+pub fn get_time_breakdown(payload: TimeBreakdownQuery, db: State<db::Database>) -> Result<TimeBreakdown, String> {
+  perf::timed("get_time_breakdown", || stats_service::get_time_breakdown(&db, payload))
+}
+
+#[tauri::command]
+pub fn get_pause_reasons(payload: PauseReasonQuery, db: State<db::Database>) -> Result<PauseReasonStats, String> {
+  perf::timed("get_pause_reasons", || stats_service::get_pause_reasons(&db, payload))
+}
+
+#[tauri::command]
+pub fn get_completion_heatmap(payload: HeatmapQuery, db: State<db::Database>) -> Result<CompletionHeatmap, String> {
+  perf::timed("get_completion_heatmap", || stats_service::get_completion_heatmap(&db, payload))
+}
+
+#[tauri::command]
+pub fn get_cycle_time_stats(payload: CycleTimeQuery, db: State<db::Database>) -> Result<CycleTimeStats, String> {
+  perf::timed("get_cycle_time_stats", || stats_service::get_cycle_time_stats(&db, payload))
+}
+
+#[tauri::command]
+pub fn generate_weekly_review(payload: WeeklyReviewQuery, db: State<db::Database>) -> Result<WeeklyReview, String> {
+  perf::timed("generate_weekly_review", || stats_service::generate_weekly_review(&db, payload))
+}
+
+#[tauri::command]
+pub fn get_procrastination_stats(db: State<db::Database>) -> Result<ProcrastinationStats, String> {
+  perf::timed("get_procrastination_stats", || stats_service::get_procrastination_stats(&db))
+}
+
+#[tauri::command]
+pub fn get_consistency_score(payload: ConsistencyQuery, db: State<db::Database>) -> Result<ConsistencyScore, String> {
+  perf::timed("get_consistency_score", || analytics::get_consistency_score(&db, payload))
+}
+
+#[tauri::command]
+pub fn get_workload_forecast(payload: WorkloadForecastQuery, db: State<db::Database>) -> Result<WorkloadForecast, String> {
+  perf::timed("get_workload_forecast", || stats_service::get_workload_forecast(&db, payload))
+}
+
+#[tauri::command]
+pub fn suggest_deadline(payload: TaskRef, db: State<db::Database>) -> Result<DeadlineSuggestion, String> {
+  perf::timed("suggest_deadline", || deadline_service::suggest_deadline(&db, payload))
+}
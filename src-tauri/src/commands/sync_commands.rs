@@ -0,0 +1,31 @@
+use tauri::{AppHandle, State};
+use crate::db;
+use crate::services::sync_service;
+use crate::structs::sync::{ConflictResolution, SyncConflictDetail, SyncReport, SyncStatus};
+use crate::structs::task_struct::Task;
+use crate::perf;
+
+#[tauri::command]
+pub async fn sync_now(app: AppHandle) -> Result<SyncReport, String> {
+  perf::timed_async("sync_now", sync_service::sync_now(&app)).await
+}
+
+#[tauri::command]
+pub fn get_sync_status(db: State<db::Database>) -> Result<SyncStatus, String> {
+  perf::timed("get_sync_status", || sync_service::get_sync_status(&db))
+}
+
+#[tauri::command]
+pub fn list_conflicts(db: State<db::Database>) -> Result<Vec<SyncConflictDetail>, String> {
+  perf::timed("list_conflicts", || sync_service::list_conflicts(&db))
+}
+
+#[tauri::command]
+pub async fn resolve_conflict(payload: ConflictResolution, app: AppHandle) -> Result<Task, String> {
+  perf::timed_async("resolve_conflict", sync_service::resolve_conflict(&app, payload)).await
+}
+
+#[tauri::command]
+pub fn clear_sync_conflicts(db: State<db::Database>) -> Result<usize, String> {
+  perf::timed("clear_sync_conflicts", || sync_service::clear_sync_conflicts(&db))
+}
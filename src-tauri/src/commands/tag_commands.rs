@@ -0,0 +1,15 @@
+use tauri::State;
+use crate::db;
+use crate::services::tag_service;
+use crate::structs::tag::{TagPrefix, TagStats, TagSuggestion};
+use crate::perf;
+
+#[tauri::command]
+pub fn suggest_tags(payload: TagPrefix, db: State<db::Database>) -> Result<Vec<TagSuggestion>, String> {
+  perf::timed("suggest_tags", || tag_service::suggest_tags(&db, payload))
+}
+
+#[tauri::command]
+pub fn get_tag_stats(db: State<db::Database>) -> Result<Vec<TagStats>, String> {
+  perf::timed("get_tag_stats", || tag_service::get_tag_stats(&db))
+}
@@ -1,56 +1,125 @@
-use tauri::State;
+use tauri::{AppHandle, State};
 use crate::db;
-use crate::structs::dto::{TaskData, DateQuery, TaskId};
+use crate::structs::dto::{TaskData, DateQuery, TaskRef, TaskIds, QuickAddData, IdleResolutionData, ContextQuery, MergeTasksData, PauseTaskData, CompleteTaskData, ApplyDefaultRemindersData, TaskWindowQuery};
 use crate::structs::task_update::TaskUpdate;
-use crate::structs::task_struct::Task;
-use crate::services::task_service;
+use crate::structs::task_struct::{Task, TaskFilter, TaskListItem};
+use crate::structs::overview::{DailySummary, TodayOverview};
+use crate::structs::reminder_frequency::DefaultRemindersReport;
+use crate::structs::changelog::RowChange;
+use crate::services::{changelog_service, idle_service, task_service};
+use crate::window_manager;
+use crate::perf;
 
 #[tauri::command]
-pub fn create_task(payload: TaskData, db: State<db::Database>) -> Result<Task, String> {
-  task_service::create_task(payload, &db)
+pub fn create_task(payload: TaskData, app: AppHandle, db: State<db::Database>) -> Result<Task, String> {
+  perf::timed("create_task", || task_service::create_task(payload, &db, &app))
 }
 
 #[tauri::command]
-pub fn get_tasks_by_date(payload: DateQuery, db: State<db::Database>) -> Result<Vec<Task>, String> {
-  task_service::get_tasks_by_date(payload, &db)
+pub fn quick_add_task(payload: QuickAddData, app: AppHandle, db: State<db::Database>) -> Result<Task, String> {
+  perf::timed("quick_add_task", || {
+    let task = task_service::quick_add_task(payload, &db, &app)?;
+    window_manager::hide_quick_add_window(&app);
+    Ok(task)
+  })
 }
 
 #[tauri::command]
-pub fn get_tasks_by_date_not_completed(payload: DateQuery, db: State<db::Database>) -> Result<Vec<Task>, String> {
-  task_service::get_tasks_by_date_not_completed(payload, &db)
+pub fn get_tasks_by_date(payload: DateQuery, db: State<db::Database>) -> Result<Vec<TaskListItem>, String> {
+  perf::timed("get_tasks_by_date", || task_service::get_tasks_by_date(payload, &db))
 }
 
 #[tauri::command]
-pub fn start_task(payload: TaskId, db: State<db::Database>) -> Result<Task, String> {
-  task_service::start_task(payload, &db)
+pub fn get_tasks_by_date_not_completed(payload: DateQuery, db: State<db::Database>) -> Result<Vec<TaskListItem>, String> {
+  perf::timed("get_tasks_by_date_not_completed", || task_service::get_tasks_by_date_not_completed(payload, &db))
 }
 
 #[tauri::command]
-pub fn pause_task(payload: TaskId, db: State<db::Database>) -> Result<Task, String> {
-  task_service::pause_task(payload, &db)
+pub fn get_tasks_for_context(payload: ContextQuery, db: State<db::Database>) -> Result<Vec<Task>, String> {
+  perf::timed("get_tasks_for_context", || task_service::get_tasks_for_context(payload, &db))
 }
 
 #[tauri::command]
-pub fn resume_task(payload: TaskId, db: State<db::Database>) -> Result<Task, String> {
-  task_service::resume_task(payload, &db)
+pub fn count_tasks(payload: TaskFilter, db: State<db::Database>) -> Result<i64, String> {
+  perf::timed("count_tasks", || task_service::count_tasks(payload, &db))
 }
 
 #[tauri::command]
-pub fn complete_task(payload: TaskId, db: State<db::Database>) -> Result<Task, String> {
-  task_service::complete_task(payload, &db)
+pub fn get_tasks_window(payload: TaskWindowQuery, db: State<db::Database>) -> Result<Vec<TaskListItem>, String> {
+  perf::timed("get_tasks_window", || task_service::get_tasks_window(payload, &db))
 }
 
 #[tauri::command]
-pub fn delete_task(payload: TaskId, db: State<db::Database>) -> Result<(), String> {
-  task_service::delete_task(payload, &db)
+pub fn get_today_overview(payload: DateQuery, db: State<db::Database>) -> Result<TodayOverview, String> {
+  perf::timed("get_today_overview", || task_service::get_today_overview(payload, &db))
 }
 
 #[tauri::command]
-pub fn get_task_by_id(payload: TaskId, db: State<db::Database>) -> Result<Task, String> {
-  task_service::get_task_by_id(payload, &db)
+pub fn get_daily_summary(payload: DateQuery, db: State<db::Database>) -> Result<DailySummary, String> {
+  perf::timed("get_daily_summary", || task_service::get_daily_summary(payload, &db))
 }
 
 #[tauri::command]
-pub async fn update_task(payload: TaskUpdate, db: State<'_, db::Database>) -> Result<Task, String> {
-  task_service::update_task(payload, &db).await
-}
\ No newline at end of file
+pub fn start_task(payload: TaskRef, app: AppHandle, db: State<db::Database>) -> Result<Task, String> {
+  perf::timed("start_task", || task_service::start_task(payload, &db, &app))
+}
+
+#[tauri::command]
+pub async fn pause_task(payload: PauseTaskData, app: AppHandle, db: State<'_, db::Database>) -> Result<Task, String> {
+  perf::timed_async("pause_task", task_service::pause_task(payload, &db, &app)).await
+}
+
+#[tauri::command]
+pub async fn resume_task(payload: TaskRef, app: AppHandle, db: State<'_, db::Database>) -> Result<Task, String> {
+  perf::timed_async("resume_task", task_service::resume_task(payload, &db, &app)).await
+}
+
+#[tauri::command]
+pub async fn complete_task(payload: CompleteTaskData, app: AppHandle, db: State<'_, db::Database>) -> Result<Task, String> {
+  perf::timed_async("complete_task", task_service::complete_task(payload, &db, &app)).await
+}
+
+#[tauri::command]
+pub async fn delete_task(payload: TaskRef, app: AppHandle, db: State<'_, db::Database>) -> Result<(), String> {
+  perf::timed_async("delete_task", task_service::delete_task(payload, &db, &app)).await
+}
+
+#[tauri::command]
+pub async fn merge_tasks(payload: MergeTasksData, app: AppHandle, db: State<'_, db::Database>) -> Result<Task, String> {
+  perf::timed_async("merge_tasks", task_service::merge_tasks(payload, &db, &app)).await
+}
+
+#[tauri::command]
+pub fn get_task_by_id(payload: TaskRef, db: State<db::Database>) -> Result<Task, String> {
+  perf::timed("get_task_by_id", || task_service::get_task_by_id(payload, &db))
+}
+
+#[tauri::command]
+pub fn get_tasks_by_ids(payload: TaskIds, db: State<db::Database>) -> Result<Vec<Task>, String> {
+  perf::timed("get_tasks_by_ids", || task_service::get_tasks_by_ids(payload, &db))
+}
+
+#[tauri::command]
+pub fn get_task_notes(payload: TaskRef, db: State<db::Database>) -> Result<Option<String>, String> {
+  perf::timed("get_task_notes", || task_service::get_task_notes(payload, &db))
+}
+
+#[tauri::command]
+pub async fn update_task(payload: TaskUpdate, app: AppHandle, db: State<'_, db::Database>) -> Result<Task, String> {
+  perf::timed_async("update_task", task_service::update_task(payload, &db, &app)).await
+}
+
+#[tauri::command]
+pub async fn apply_default_reminders(payload: ApplyDefaultRemindersData, app: AppHandle, db: State<'_, db::Database>) -> Result<DefaultRemindersReport, String> {
+  perf::timed_async("apply_default_reminders", task_service::apply_default_reminders(payload, &db, &app)).await
+}
+
+#[tauri::command]
+pub async fn resolve_idle_time(payload: IdleResolutionData, app: AppHandle, db: State<'_, db::Database>) -> Result<Vec<Task>, String> {
+  perf::timed_async("resolve_idle_time", idle_service::resolve_idle_time(payload, &db, &app)).await
+}
+
+#[tauri::command]
+pub fn get_task_history(payload: TaskRef, db: State<db::Database>) -> Result<Vec<RowChange>, String> {
+  perf::timed("get_task_history", || changelog_service::get_task_history(&db, payload.id))
+}
@@ -0,0 +1,30 @@
+use tauri::{AppHandle, State};
+use crate::db;
+use crate::services::webhook_service;
+use crate::structs::webhook::{Webhook, WebhookData, WebhookDelivery, WebhookDeliveryQuery, WebhookIdData};
+use crate::perf;
+
+#[tauri::command]
+pub fn list_webhooks(db: State<db::Database>) -> Result<Vec<Webhook>, String> {
+  perf::timed("list_webhooks", || webhook_service::list_webhooks(&db))
+}
+
+#[tauri::command]
+pub fn create_webhook(payload: WebhookData, db: State<db::Database>) -> Result<Webhook, String> {
+  perf::timed("create_webhook", || webhook_service::create_webhook(&db, payload))
+}
+
+#[tauri::command]
+pub fn delete_webhook(payload: WebhookIdData, db: State<db::Database>) -> Result<(), String> {
+  perf::timed("delete_webhook", || webhook_service::delete_webhook(&db, payload))
+}
+
+#[tauri::command]
+pub fn get_webhook_deliveries(payload: WebhookDeliveryQuery, db: State<db::Database>) -> Result<Vec<WebhookDelivery>, String> {
+  perf::timed("get_webhook_deliveries", || webhook_service::get_webhook_deliveries(&db, payload))
+}
+
+#[tauri::command]
+pub async fn retry_webhook_deliveries(app: AppHandle) -> Result<(), String> {
+  perf::timed_async("retry_webhook_deliveries", webhook_service::deliver_due(&app)).await
+}
@@ -0,0 +1,27 @@
+use tauri::{AppHandle, State};
+use crate::db;
+use crate::structs::dto::TaskRef;
+use crate::structs::window_state::AlwaysOnTopData;
+use crate::services::task_service;
+use crate::window_manager;
+use crate::perf;
+
+// Window-creating commands are async: building a window from a sync command deadlocks on Windows
+
+#[tauri::command]
+pub async fn open_focus_window(payload: TaskRef, app: AppHandle, db: State<'_, db::Database>) -> Result<(), String> {
+  perf::timed("open_focus_window", || {
+    let task = task_service::get_task_by_id(payload, &db)?;
+    window_manager::open_focus_window(&app, &task.id.to_string())
+  })
+}
+
+#[tauri::command]
+pub async fn open_today_widget(app: AppHandle) -> Result<(), String> {
+  perf::timed("open_today_widget", || window_manager::open_today_widget(&app))
+}
+
+#[tauri::command]
+pub fn set_window_always_on_top(payload: AlwaysOnTopData, app: AppHandle) -> Result<(), String> {
+  perf::timed("set_window_always_on_top", || window_manager::set_always_on_top(&app, &payload.label, payload.always_on_top))
+}
@@ -0,0 +1,2 @@
+-- Per-task opt-out for desktop notifications
+ALTER TABLE tasks ADD COLUMN notifications_enabled BOOLEAN NOT NULL DEFAULT 1;
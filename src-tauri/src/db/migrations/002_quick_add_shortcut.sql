@@ -0,0 +1,2 @@
+-- OS-wide hotkey that opens the quick-add window
+ALTER TABLE settings ADD COLUMN quick_add_shortcut VARCHAR(50) NOT NULL DEFAULT 'CommandOrControl+Shift+Space';
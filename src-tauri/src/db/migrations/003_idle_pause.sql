@@ -0,0 +1,2 @@
+-- Minutes of OS idle time before the running task is auto-paused; 0 disables
+ALTER TABLE settings ADD COLUMN idle_pause_minutes INTEGER NOT NULL DEFAULT 10;
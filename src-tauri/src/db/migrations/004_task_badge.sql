@@ -0,0 +1,2 @@
+-- Show today's remaining task count on the dock/taskbar icon
+ALTER TABLE settings ADD COLUMN show_task_badge BOOLEAN NOT NULL DEFAULT 1;
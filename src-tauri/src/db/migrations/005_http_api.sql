@@ -0,0 +1,4 @@
+-- Opt-in localhost REST API for external tools (launchers, Stream Deck)
+ALTER TABLE settings ADD COLUMN http_api_enabled BOOLEAN NOT NULL DEFAULT 0;
+ALTER TABLE settings ADD COLUMN http_api_port INTEGER NOT NULL DEFAULT 47615;
+ALTER TABLE settings ADD COLUMN http_api_token VARCHAR(64);
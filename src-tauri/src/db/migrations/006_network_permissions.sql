@@ -0,0 +1,6 @@
+-- Per-feature consent for outbound network calls; NULL means the user was never asked
+ALTER TABLE settings ADD COLUMN calendar_network_allowed BOOLEAN;
+
+-- Connecting a calendar before this existed already meant agreeing to talk to Google
+UPDATE settings SET calendar_network_allowed = 1
+WHERE EXISTS (SELECT 1 FROM calendar_credentials WHERE refresh_token != '');
@@ -0,0 +1,2 @@
+-- Consent for the GitHub issues integration; NULL means the user was never asked
+ALTER TABLE settings ADD COLUMN github_network_allowed BOOLEAN;
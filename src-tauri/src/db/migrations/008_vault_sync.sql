@@ -0,0 +1,3 @@
+-- Mirror tasks into a Markdown vault folder; NULL folder means the mirror is off
+ALTER TABLE settings ADD COLUMN vault_path TEXT;
+ALTER TABLE settings ADD COLUMN vault_layout VARCHAR(10) NOT NULL DEFAULT 'day' CHECK (vault_layout IN ('day', 'task'));
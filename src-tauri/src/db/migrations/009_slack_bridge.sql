@@ -0,0 +1,3 @@
+-- Slack slash commands through the local HTTP API, verified with the app's signing secret
+ALTER TABLE settings ADD COLUMN slack_enabled BOOLEAN NOT NULL DEFAULT 0;
+ALTER TABLE settings ADD COLUMN slack_signing_secret VARCHAR(128);
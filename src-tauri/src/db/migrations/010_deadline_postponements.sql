@@ -0,0 +1,3 @@
+-- How often a task's deadline was moved, and how many days it was pushed back in total
+ALTER TABLE tasks ADD COLUMN deadline_moves INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE tasks ADD COLUMN postponed_days REAL NOT NULL DEFAULT 0;
@@ -0,0 +1,9 @@
+-- Weekly targets, e.g. 15 completed tasks; last_met_week is the Monday of the last week the goal was met
+CREATE TABLE IF NOT EXISTS goals (
+    id TEXT PRIMARY KEY NOT NULL,
+    title TEXT NOT NULL,
+    metric VARCHAR(32) NOT NULL,
+    target INTEGER NOT NULL,
+    created_at TEXT NOT NULL,
+    last_met_week TEXT
+);
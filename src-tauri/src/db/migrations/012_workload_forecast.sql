@@ -0,0 +1,3 @@
+-- Estimated effort per task, and the working minutes per day the forecast plans against
+ALTER TABLE tasks ADD COLUMN estimate_minutes INTEGER;
+ALTER TABLE settings ADD COLUMN work_day_minutes INTEGER NOT NULL DEFAULT 480;
@@ -0,0 +1,11 @@
+-- One row per Google Calendar call; error_kind is NULL for calls that went through
+CREATE TABLE IF NOT EXISTS calendar_api_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    account VARCHAR(255) NOT NULL,
+    operation VARCHAR(20) NOT NULL,
+    error_kind VARCHAR(32),
+    message TEXT,
+    occurred_at DATETIME NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_calendar_api_log_occurred_at ON calendar_api_log(occurred_at);
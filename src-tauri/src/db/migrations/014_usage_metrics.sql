@@ -0,0 +1,10 @@
+-- Local-only usage counts per UTC day, collected only while the setting is on
+ALTER TABLE settings ADD COLUMN usage_metrics_enabled BOOLEAN NOT NULL DEFAULT 0;
+
+CREATE TABLE IF NOT EXISTS usage_metrics (
+    day TEXT NOT NULL,
+    kind VARCHAR(16) NOT NULL,
+    name VARCHAR(64) NOT NULL,
+    count INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (day, kind, name)
+);
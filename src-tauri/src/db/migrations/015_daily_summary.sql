@@ -0,0 +1,3 @@
+-- End-of-day summary: local time it is sent, and whether it also shows a desktop notification
+ALTER TABLE settings ADD COLUMN daily_summary_time VARCHAR(5) NOT NULL DEFAULT '18:00';
+ALTER TABLE settings ADD COLUMN daily_summary_notification BOOLEAN NOT NULL DEFAULT 1;
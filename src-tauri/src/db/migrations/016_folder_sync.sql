@@ -0,0 +1,30 @@
+-- Sync through a folder another tool (Dropbox, Syncthing) keeps in step across devices.
+-- Each device appends its task changes under changes/<device id>/ and replays the others'.
+ALTER TABLE settings ADD COLUMN sync_folder VARCHAR(1024);
+ALTER TABLE settings ADD COLUMN sync_device_id VARCHAR(36);
+ALTER TABLE settings ADD COLUMN sync_merged_at DATETIME;
+UPDATE settings SET sync_device_id = lower(hex(randomblob(16)));
+
+-- Last change applied from each other device
+CREATE TABLE IF NOT EXISTS sync_cursors (
+    device_id VARCHAR(36) PRIMARY KEY,
+    last_seq INTEGER NOT NULL
+);
+
+-- Tasks deleted here, so an older change from another device doesn't bring them back
+CREATE TABLE IF NOT EXISTS sync_tombstones (
+    task_id BLOB PRIMARY KEY,
+    deleted_at DATETIME NOT NULL
+);
+
+-- Both sides changed a task between merges; the newer change won
+CREATE TABLE IF NOT EXISTS sync_conflicts (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    task_id BLOB NOT NULL,
+    title TEXT NOT NULL,
+    remote_device VARCHAR(36) NOT NULL,
+    local_updated_at DATETIME NOT NULL,
+    remote_updated_at DATETIME NOT NULL,
+    winner VARCHAR(10) NOT NULL,
+    detected_at DATETIME NOT NULL
+);
@@ -0,0 +1,24 @@
+-- Where sync exchanges changes: the sync folder, or a WebDAV server at sync_webdav_url
+ALTER TABLE settings ADD COLUMN sync_backend VARCHAR(10) NOT NULL DEFAULT 'folder' CHECK (sync_backend IN ('folder', 'webdav'));
+ALTER TABLE settings ADD COLUMN sync_webdav_url VARCHAR(1024);
+ALTER TABLE settings ADD COLUMN sync_webdav_username VARCHAR(255);
+ALTER TABLE settings ADD COLUMN sync_webdav_password VARCHAR(255);
+-- Encrypts everything pushed; every device must use the same one. Required for WebDAV
+ALTER TABLE settings ADD COLUMN sync_passphrase VARCHAR(255);
+ALTER TABLE settings ADD COLUMN sync_device_name VARCHAR(255);
+-- Highest seq handed out, so a clock set back can't reuse one other devices already passed
+ALTER TABLE settings ADD COLUMN sync_last_seq INTEGER NOT NULL DEFAULT 0;
+-- Consent for syncing with a WebDAV server; NULL means the user was never asked
+ALTER TABLE settings ADD COLUMN sync_network_allowed BOOLEAN;
+
+-- Name other devices registered with
+ALTER TABLE sync_cursors ADD COLUMN device_name VARCHAR(255);
+
+-- Local changes not yet pushed, sent as one changeset
+CREATE TABLE IF NOT EXISTS sync_outbox (
+    seq INTEGER PRIMARY KEY,
+    record TEXT NOT NULL
+);
+
+-- The job now syncs with whichever backend is set
+UPDATE jobs SET name = 'sync' WHERE name = 'folder-sync';
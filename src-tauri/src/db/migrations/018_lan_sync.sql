@@ -0,0 +1,11 @@
+-- Advertise this device over mDNS and accept sync requests from paired devices
+ALTER TABLE settings ADD COLUMN lan_sync_enabled BOOLEAN NOT NULL DEFAULT 0;
+
+-- Devices paired over the local network; the secret encrypts everything exchanged with them
+CREATE TABLE IF NOT EXISTS lan_peers (
+    device_id VARCHAR(64) PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,
+    secret VARCHAR(64) NOT NULL,
+    paired_at DATETIME NOT NULL,
+    last_synced_at DATETIME
+);
@@ -0,0 +1,153 @@
+-- Row-level history of user data, written by triggers so no code path can skip it.
+-- Each entry holds only the columns that changed: their values before and after.
+-- Shared by undo, history views and sync; pruned and compacted by the maintenance job.
+CREATE TABLE IF NOT EXISTS changelog (
+    seq INTEGER PRIMARY KEY AUTOINCREMENT,
+    table_name VARCHAR(64) NOT NULL,
+    -- Primary key of the changed row, as lowercase hex for UUIDs
+    row_id VARCHAR(64) NOT NULL,
+    op VARCHAR(10) NOT NULL CHECK (op IN ('insert', 'update', 'delete')),
+    -- JSON objects of column values; NULL before an insert and after a delete
+    old_values TEXT,
+    new_values TEXT,
+    -- Logical clock: one higher than any change this device has seen
+    clock INTEGER NOT NULL,
+    -- Device the change was made on
+    device_id VARCHAR(64) NOT NULL,
+    changed_at DATETIME NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_changelog_row ON changelog(table_name, row_id);
+CREATE INDEX IF NOT EXISTS idx_changelog_changed_at ON changelog(changed_at);
+
+-- Survives pruning, so the clock never goes back
+CREATE TABLE IF NOT EXISTS changelog_clock (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    value INTEGER NOT NULL
+);
+INSERT OR IGNORE INTO changelog_clock (id, value) VALUES (1, 0);
+
+-- Device to credit while changes from another device are written; no row means this one
+CREATE TABLE IF NOT EXISTS changelog_origin (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    device_id VARCHAR(64) NOT NULL
+);
+
+CREATE TRIGGER IF NOT EXISTS changelog_advance_clock AFTER INSERT ON changelog
+BEGIN
+    UPDATE changelog_clock SET value = MAX(value, NEW.clock) WHERE id = 1;
+END;
+
+CREATE TRIGGER IF NOT EXISTS tasks_changelog_insert AFTER INSERT ON tasks
+BEGIN
+    INSERT INTO changelog (table_name, row_id, op, old_values, new_values, clock, device_id, changed_at)
+    VALUES ('tasks', lower(hex(NEW.id)), 'insert', NULL,
+        json_object(
+            'title', NEW.title, 'notes', NEW.notes, 'status', NEW.status,
+            'created_at', NEW.created_at, 'updated_at', NEW.updated_at, 'deadline', NEW.deadline,
+            'has_calendar_integration', NEW.has_calendar_integration, 'calendar_email', NEW.calendar_email,
+            'reminder_frequency', NEW.reminder_frequency, 'started_at', NEW.started_at,
+            'paused_at', NEW.paused_at, 'completed_at', NEW.completed_at,
+            'notifications_enabled', NEW.notifications_enabled, 'deadline_moves', NEW.deadline_moves,
+            'postponed_days', NEW.postponed_days, 'estimate_minutes', NEW.estimate_minutes
+        ),
+        (SELECT value + 1 FROM changelog_clock WHERE id = 1),
+        COALESCE((SELECT device_id FROM changelog_origin WHERE id = 1), (SELECT sync_device_id FROM settings WHERE id = 1), ''),
+        strftime('%Y-%m-%d %H:%M:%f', 'now'));
+END;
+
+-- Only columns whose value changed; an update that changes nothing is not logged
+CREATE TRIGGER IF NOT EXISTS tasks_changelog_update AFTER UPDATE ON tasks
+BEGIN
+    INSERT INTO changelog (table_name, row_id, op, old_values, new_values, clock, device_id, changed_at)
+    SELECT 'tasks', lower(hex(NEW.id)), 'update',
+        json_group_object(name, old_value), json_group_object(name, new_value),
+        (SELECT value + 1 FROM changelog_clock WHERE id = 1),
+        COALESCE((SELECT device_id FROM changelog_origin WHERE id = 1), (SELECT sync_device_id FROM settings WHERE id = 1), ''),
+        strftime('%Y-%m-%d %H:%M:%f', 'now')
+    FROM (
+        SELECT 'title' AS name, OLD.title AS old_value, NEW.title AS new_value
+        UNION ALL SELECT 'notes', OLD.notes, NEW.notes
+        UNION ALL SELECT 'status', OLD.status, NEW.status
+        UNION ALL SELECT 'created_at', OLD.created_at, NEW.created_at
+        UNION ALL SELECT 'updated_at', OLD.updated_at, NEW.updated_at
+        UNION ALL SELECT 'deadline', OLD.deadline, NEW.deadline
+        UNION ALL SELECT 'has_calendar_integration', OLD.has_calendar_integration, NEW.has_calendar_integration
+        UNION ALL SELECT 'calendar_email', OLD.calendar_email, NEW.calendar_email
+        UNION ALL SELECT 'reminder_frequency', OLD.reminder_frequency, NEW.reminder_frequency
+        UNION ALL SELECT 'started_at', OLD.started_at, NEW.started_at
+        UNION ALL SELECT 'paused_at', OLD.paused_at, NEW.paused_at
+        UNION ALL SELECT 'completed_at', OLD.completed_at, NEW.completed_at
+        UNION ALL SELECT 'notifications_enabled', OLD.notifications_enabled, NEW.notifications_enabled
+        UNION ALL SELECT 'deadline_moves', OLD.deadline_moves, NEW.deadline_moves
+        UNION ALL SELECT 'postponed_days', OLD.postponed_days, NEW.postponed_days
+        UNION ALL SELECT 'estimate_minutes', OLD.estimate_minutes, NEW.estimate_minutes
+    )
+    WHERE old_value IS NOT new_value
+    HAVING COUNT(*) > 0;
+END;
+
+CREATE TRIGGER IF NOT EXISTS tasks_changelog_delete AFTER DELETE ON tasks
+BEGIN
+    INSERT INTO changelog (table_name, row_id, op, old_values, new_values, clock, device_id, changed_at)
+    VALUES ('tasks', lower(hex(OLD.id)), 'delete',
+        json_object(
+            'title', OLD.title, 'notes', OLD.notes, 'status', OLD.status,
+            'created_at', OLD.created_at, 'updated_at', OLD.updated_at, 'deadline', OLD.deadline,
+            'has_calendar_integration', OLD.has_calendar_integration, 'calendar_email', OLD.calendar_email,
+            'reminder_frequency', OLD.reminder_frequency, 'started_at', OLD.started_at,
+            'paused_at', OLD.paused_at, 'completed_at', OLD.completed_at,
+            'notifications_enabled', OLD.notifications_enabled, 'deadline_moves', OLD.deadline_moves,
+            'postponed_days', OLD.postponed_days, 'estimate_minutes', OLD.estimate_minutes
+        ),
+        NULL,
+        (SELECT value + 1 FROM changelog_clock WHERE id = 1),
+        COALESCE((SELECT device_id FROM changelog_origin WHERE id = 1), (SELECT sync_device_id FROM settings WHERE id = 1), ''),
+        strftime('%Y-%m-%d %H:%M:%f', 'now'));
+END;
+
+CREATE TRIGGER IF NOT EXISTS goals_changelog_insert AFTER INSERT ON goals
+BEGIN
+    INSERT INTO changelog (table_name, row_id, op, old_values, new_values, clock, device_id, changed_at)
+    VALUES ('goals', lower(hex(NEW.id)), 'insert', NULL,
+        json_object(
+            'title', NEW.title, 'metric', NEW.metric, 'target', NEW.target,
+            'created_at', NEW.created_at, 'last_met_week', NEW.last_met_week
+        ),
+        (SELECT value + 1 FROM changelog_clock WHERE id = 1),
+        COALESCE((SELECT device_id FROM changelog_origin WHERE id = 1), (SELECT sync_device_id FROM settings WHERE id = 1), ''),
+        strftime('%Y-%m-%d %H:%M:%f', 'now'));
+END;
+
+CREATE TRIGGER IF NOT EXISTS goals_changelog_update AFTER UPDATE ON goals
+BEGIN
+    INSERT INTO changelog (table_name, row_id, op, old_values, new_values, clock, device_id, changed_at)
+    SELECT 'goals', lower(hex(NEW.id)), 'update',
+        json_group_object(name, old_value), json_group_object(name, new_value),
+        (SELECT value + 1 FROM changelog_clock WHERE id = 1),
+        COALESCE((SELECT device_id FROM changelog_origin WHERE id = 1), (SELECT sync_device_id FROM settings WHERE id = 1), ''),
+        strftime('%Y-%m-%d %H:%M:%f', 'now')
+    FROM (
+        SELECT 'title' AS name, OLD.title AS old_value, NEW.title AS new_value
+        UNION ALL SELECT 'metric', OLD.metric, NEW.metric
+        UNION ALL SELECT 'target', OLD.target, NEW.target
+        UNION ALL SELECT 'created_at', OLD.created_at, NEW.created_at
+        UNION ALL SELECT 'last_met_week', OLD.last_met_week, NEW.last_met_week
+    )
+    WHERE old_value IS NOT new_value
+    HAVING COUNT(*) > 0;
+END;
+
+CREATE TRIGGER IF NOT EXISTS goals_changelog_delete AFTER DELETE ON goals
+BEGIN
+    INSERT INTO changelog (table_name, row_id, op, old_values, new_values, clock, device_id, changed_at)
+    VALUES ('goals', lower(hex(OLD.id)), 'delete',
+        json_object(
+            'title', OLD.title, 'metric', OLD.metric, 'target', OLD.target,
+            'created_at', OLD.created_at, 'last_met_week', OLD.last_met_week
+        ),
+        NULL,
+        (SELECT value + 1 FROM changelog_clock WHERE id = 1),
+        COALESCE((SELECT device_id FROM changelog_origin WHERE id = 1), (SELECT sync_device_id FROM settings WHERE id = 1), ''),
+        strftime('%Y-%m-%d %H:%M:%f', 'now'));
+END;
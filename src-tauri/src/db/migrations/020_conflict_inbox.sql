@@ -0,0 +1,9 @@
+-- Both versions of a task renamed differently on two devices, kept until the user picks one
+ALTER TABLE sync_conflicts ADD COLUMN local_version TEXT;
+ALTER TABLE sync_conflicts ADD COLUMN remote_version TEXT;
+ALTER TABLE sync_conflicts ADD COLUMN resolved_at DATETIME;
+-- 'local' or 'remote' when the user chose; 'superseded' when a later edit replaced both
+ALTER TABLE sync_conflicts ADD COLUMN kept VARCHAR(10);
+
+-- Earlier conflicts were only reported, without versions to choose from
+UPDATE sync_conflicts SET resolved_at = detected_at, kept = winner;
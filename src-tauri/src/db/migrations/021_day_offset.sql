@@ -0,0 +1,2 @@
+-- Where task days start for callers that don't send their own offset; 0 keeps UTC days
+ALTER TABLE settings ADD COLUMN utc_offset_minutes INTEGER NOT NULL DEFAULT 0;
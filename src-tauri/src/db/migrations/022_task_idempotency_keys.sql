@@ -0,0 +1,7 @@
+-- Client-generated keys for create_task, so a retried or double-clicked submission returns the first task
+CREATE TABLE IF NOT EXISTS task_idempotency_keys (
+    idempotency_key TEXT PRIMARY KEY NOT NULL,
+    task_id BLOB NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
@@ -0,0 +1,6 @@
+-- Optional steps run on completion (comma-separated) and the URL the webhook step posts to
+ALTER TABLE settings ADD COLUMN completion_effects TEXT NOT NULL DEFAULT '';
+ALTER TABLE settings ADD COLUMN completion_webhook_url TEXT;
+
+-- Set by the archive completion effect; archived tasks are left out of day lists until reopened
+ALTER TABLE tasks ADD COLUMN archived_at DATETIME;
@@ -0,0 +1,19 @@
+-- Automation rules: when `trigger` happens to a task (optionally one with #tag), run `action` (JSON)
+CREATE TABLE IF NOT EXISTS rules (
+    id TEXT PRIMARY KEY NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    trigger VARCHAR(20) NOT NULL CHECK (trigger IN ('task-created', 'task-completed', 'deadline-passed')),
+    tag VARCHAR(64),
+    action TEXT NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL
+);
+
+-- Deadline-passed rules that already fired, so each fires once per task and deadline
+CREATE TABLE IF NOT EXISTS rule_runs (
+    rule_id TEXT NOT NULL,
+    task_id BLOB NOT NULL,
+    deadline DATETIME NOT NULL,
+    fired_at DATETIME NOT NULL,
+    PRIMARY KEY (rule_id, task_id, deadline)
+);
@@ -0,0 +1,6 @@
+-- Raw captures with no date yet; triage turns each into a scheduled task
+CREATE TABLE IF NOT EXISTS inbox_items (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    text TEXT NOT NULL,
+    captured_at DATETIME NOT NULL
+);
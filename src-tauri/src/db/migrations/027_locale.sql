@@ -0,0 +1,2 @@
+-- Date, time and week conventions for exports, vault notes and notifications
+ALTER TABLE settings ADD COLUMN locale TEXT NOT NULL DEFAULT 'en-US';
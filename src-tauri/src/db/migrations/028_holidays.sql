@@ -0,0 +1,12 @@
+-- Holidays imported from ICS files; bundled regional lists are computed, not stored
+CREATE TABLE IF NOT EXISTS holidays (
+    date DATE NOT NULL,
+    name TEXT NOT NULL,
+    imported_at DATETIME NOT NULL,
+    PRIMARY KEY (date, name)
+);
+
+-- Whether scheduling and date shifting avoid weekends and holidays, and which bundled list applies
+ALTER TABLE settings ADD COLUMN skip_weekends INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE settings ADD COLUMN skip_holidays INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE settings ADD COLUMN holiday_region TEXT;
@@ -0,0 +1,11 @@
+-- Optional PIN lock; a row exists while one is set. Kept out of settings, which the UI reads whole.
+CREATE TABLE IF NOT EXISTS app_lock (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    -- Argon2id hash in PHC string form
+    pin_hash TEXT NOT NULL,
+    -- Hex salt for the key that seals notes and credentials; not the one in pin_hash
+    key_salt TEXT NOT NULL,
+    -- Lock again after this long without keyboard or mouse input; 0 never
+    idle_minutes INTEGER NOT NULL,
+    created_at DATETIME NOT NULL
+);
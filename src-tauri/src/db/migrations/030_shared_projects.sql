@@ -0,0 +1,46 @@
+-- Projects shared with other people through their own sync location
+CREATE TABLE IF NOT EXISTS projects (
+    id BLOB PRIMARY KEY,
+    name TEXT NOT NULL,
+    -- Folder path or WebDAV URL the members sync the project through; NULL until shared
+    share_location TEXT,
+    -- Hex key every member's copy of the project is sealed with
+    share_key TEXT,
+    -- This person's role: owner, editor or viewer
+    role TEXT NOT NULL DEFAULT 'owner',
+    -- Seq of the last changeset pushed to the share location
+    last_seq INTEGER NOT NULL DEFAULT 0,
+    synced_at DATETIME,
+    created_at DATETIME NOT NULL
+);
+
+-- Who may change a shared project's tasks; written by the owner, read by everyone else
+CREATE TABLE IF NOT EXISTS project_members (
+    project_id BLOB NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    identity TEXT NOT NULL,
+    role TEXT NOT NULL,
+    PRIMARY KEY (project_id, identity)
+);
+
+-- Version of each task last pushed to or merged from a project's share location
+CREATE TABLE IF NOT EXISTS shared_project_tasks (
+    project_id BLOB NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    task_id BLOB NOT NULL,
+    version DATETIME NOT NULL,
+    PRIMARY KEY (project_id, task_id)
+);
+
+-- Last changeset merged from each member device, per project
+CREATE TABLE IF NOT EXISTS shared_project_cursors (
+    project_id BLOB NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    device_id TEXT NOT NULL,
+    last_seq INTEGER NOT NULL,
+    PRIMARY KEY (project_id, device_id)
+);
+
+ALTER TABLE tasks ADD COLUMN project_id BLOB;
+ALTER TABLE tasks ADD COLUMN assignee TEXT;
+CREATE INDEX IF NOT EXISTS idx_tasks_project ON tasks(project_id);
+
+-- Who this person is to the people they share projects with
+ALTER TABLE settings ADD COLUMN sync_identity TEXT;
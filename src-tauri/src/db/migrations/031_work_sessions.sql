@@ -0,0 +1,16 @@
+-- One row per stretch of work on a task: from start or resume to pause, completion or reset
+CREATE TABLE IF NOT EXISTS work_sessions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    task_id BLOB NOT NULL,
+    started_at DATETIME NOT NULL,
+    -- NULL while the task is ongoing
+    ended_at DATETIME,
+    -- Set once the session is handled for the calendar; no event ID means it was skipped
+    posted_at DATETIME,
+    google_event_id TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_work_sessions_task ON work_sessions(task_id, ended_at);
+
+-- Post finished work sessions to Google Calendar as past events, once a day
+ALTER TABLE settings ADD COLUMN calendar_work_sessions INTEGER NOT NULL DEFAULT 0;
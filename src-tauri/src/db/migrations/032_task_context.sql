@@ -0,0 +1,4 @@
+-- Where a task can be done ("@phone") and how much focus it takes (low, medium, high)
+ALTER TABLE tasks ADD COLUMN context TEXT;
+ALTER TABLE tasks ADD COLUMN energy TEXT;
+CREATE INDEX IF NOT EXISTS idx_tasks_context ON tasks(context);
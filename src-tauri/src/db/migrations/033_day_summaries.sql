@@ -0,0 +1,105 @@
+-- Per-day counts kept current by triggers on tasks, so stats read a row per day instead of scanning tasks.
+-- Days are in the settings offset; rebuild_day_summaries redoes them when the offset changes.
+CREATE TABLE IF NOT EXISTS day_summaries (
+    -- YYYY-MM-DD
+    day TEXT PRIMARY KEY,
+    -- Tasks planned for the day
+    created INTEGER NOT NULL DEFAULT 0,
+    -- Of those, the ones completed since
+    created_and_completed INTEGER NOT NULL DEFAULT 0,
+    -- Tasks completed on the day, whatever day they were planned for
+    completed INTEGER NOT NULL DEFAULT 0,
+    -- Start to completion of the day's completed tasks, as Task::time_spent measures it
+    tracked_minutes INTEGER NOT NULL DEFAULT 0
+);
+
+-- SQLite date modifier for the settings offset, e.g. '+120 minutes'
+CREATE VIEW IF NOT EXISTS day_summary_offset AS
+SELECT COALESCE((SELECT printf('%+d minutes', utc_offset_minutes) FROM settings WHERE id = 1), '+0 minutes') AS modifier;
+
+-- Each trigger adds the row's new contribution and takes away its old one
+CREATE TRIGGER IF NOT EXISTS tasks_day_summaries_insert AFTER INSERT ON tasks
+BEGIN
+    INSERT INTO day_summaries (day, created, created_and_completed, completed, tracked_minutes)
+    SELECT day, SUM(created), SUM(created_and_completed), SUM(completed), SUM(tracked_minutes)
+    FROM (
+        SELECT date(NEW.created_at, (SELECT modifier FROM day_summary_offset)) AS day,
+               1 AS created, NEW.status = 'completed' AS created_and_completed, 0 AS completed, 0 AS tracked_minutes
+        UNION ALL
+        SELECT date(NEW.completed_at, (SELECT modifier FROM day_summary_offset)), 0, 0, 1,
+               COALESCE(MAX(0, (strftime('%s', NEW.completed_at) - strftime('%s', NEW.started_at)) / 60), 0)
+        WHERE NEW.completed_at IS NOT NULL
+    )
+    WHERE day IS NOT NULL
+    GROUP BY day
+    ON CONFLICT(day) DO UPDATE SET
+        created = created + excluded.created,
+        created_and_completed = created_and_completed + excluded.created_and_completed,
+        completed = completed + excluded.completed,
+        tracked_minutes = tracked_minutes + excluded.tracked_minutes;
+END;
+
+CREATE TRIGGER IF NOT EXISTS tasks_day_summaries_update AFTER UPDATE OF created_at, started_at, completed_at, status ON tasks
+BEGIN
+    INSERT INTO day_summaries (day, created, created_and_completed, completed, tracked_minutes)
+    SELECT day, SUM(created), SUM(created_and_completed), SUM(completed), SUM(tracked_minutes)
+    FROM (
+        SELECT date(OLD.created_at, (SELECT modifier FROM day_summary_offset)) AS day,
+               -1 AS created, -(OLD.status = 'completed') AS created_and_completed, 0 AS completed, 0 AS tracked_minutes
+        UNION ALL
+        SELECT date(OLD.completed_at, (SELECT modifier FROM day_summary_offset)), 0, 0, -1,
+               -COALESCE(MAX(0, (strftime('%s', OLD.completed_at) - strftime('%s', OLD.started_at)) / 60), 0)
+        WHERE OLD.completed_at IS NOT NULL
+        UNION ALL
+        SELECT date(NEW.created_at, (SELECT modifier FROM day_summary_offset)),
+               1, NEW.status = 'completed', 0, 0
+        UNION ALL
+        SELECT date(NEW.completed_at, (SELECT modifier FROM day_summary_offset)), 0, 0, 1,
+               COALESCE(MAX(0, (strftime('%s', NEW.completed_at) - strftime('%s', NEW.started_at)) / 60), 0)
+        WHERE NEW.completed_at IS NOT NULL
+    )
+    WHERE day IS NOT NULL
+    GROUP BY day
+    ON CONFLICT(day) DO UPDATE SET
+        created = created + excluded.created,
+        created_and_completed = created_and_completed + excluded.created_and_completed,
+        completed = completed + excluded.completed,
+        tracked_minutes = tracked_minutes + excluded.tracked_minutes;
+END;
+
+CREATE TRIGGER IF NOT EXISTS tasks_day_summaries_delete AFTER DELETE ON tasks
+BEGIN
+    INSERT INTO day_summaries (day, created, created_and_completed, completed, tracked_minutes)
+    SELECT day, SUM(created), SUM(created_and_completed), SUM(completed), SUM(tracked_minutes)
+    FROM (
+        SELECT date(OLD.created_at, (SELECT modifier FROM day_summary_offset)) AS day,
+               -1 AS created, -(OLD.status = 'completed') AS created_and_completed, 0 AS completed, 0 AS tracked_minutes
+        UNION ALL
+        SELECT date(OLD.completed_at, (SELECT modifier FROM day_summary_offset)), 0, 0, -1,
+               -COALESCE(MAX(0, (strftime('%s', OLD.completed_at) - strftime('%s', OLD.started_at)) / 60), 0)
+        WHERE OLD.completed_at IS NOT NULL
+    )
+    WHERE day IS NOT NULL
+    GROUP BY day
+    ON CONFLICT(day) DO UPDATE SET
+        created = created + excluded.created,
+        created_and_completed = created_and_completed + excluded.created_and_completed,
+        completed = completed + excluded.completed,
+        tracked_minutes = tracked_minutes + excluded.tracked_minutes;
+END;
+
+-- Fill in the days of existing tasks
+INSERT INTO day_summaries (day, created, created_and_completed, completed, tracked_minutes)
+SELECT day, SUM(created), SUM(created_and_completed), SUM(completed), SUM(tracked_minutes)
+FROM (
+    SELECT date(created_at, (SELECT modifier FROM day_summary_offset)) AS day,
+           1 AS created, status = 'completed' AS created_and_completed, 0 AS completed, 0 AS tracked_minutes
+    FROM tasks
+    UNION ALL
+    SELECT date(completed_at, (SELECT modifier FROM day_summary_offset)), 0, 0, 1,
+           COALESCE(MAX(0, (strftime('%s', completed_at) - strftime('%s', started_at)) / 60), 0)
+    FROM tasks
+    WHERE completed_at IS NOT NULL
+)
+WHERE day IS NOT NULL
+GROUP BY day;
@@ -0,0 +1,11 @@
+-- Where each integration's tokens and signing secrets are kept. Keyring rows hold no value;
+-- database rows hold it encrypted with the device key from the file beside the database.
+-- Values still in calendar_credentials, github_credentials and webhooks move here at startup.
+CREATE TABLE IF NOT EXISTS secrets (
+    namespace VARCHAR(64) NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    backend VARCHAR(10) NOT NULL CHECK (backend IN ('keyring', 'database')),
+    value TEXT,
+    updated_at DATETIME NOT NULL,
+    PRIMARY KEY (namespace, name)
+);
@@ -0,0 +1,13 @@
+-- Birthdays, anniversaries and other dates that come around every year
+CREATE TABLE IF NOT EXISTS remembered_dates (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name VARCHAR(255) NOT NULL,
+    month INTEGER NOT NULL CHECK (month BETWEEN 1 AND 12),
+    day INTEGER NOT NULL CHECK (day BETWEEN 1 AND 31),
+    -- Year it started, for ages and anniversary counts; NULL when unknown
+    year INTEGER,
+    remind_days_before INTEGER NOT NULL,
+    -- Occurrence (YYYY-MM-DD) the reminder was last shown for, so it's shown once a year
+    reminded_for DATE,
+    created_at DATETIME NOT NULL
+);
@@ -0,0 +1,4 @@
+-- Tell the UI once a running task's tracked time reaches this share of its estimate; 0 turns it off
+ALTER TABLE settings ADD COLUMN estimate_reached_percent INTEGER NOT NULL DEFAULT 100;
+-- Also show a notification then
+ALTER TABLE settings ADD COLUMN estimate_reached_notification INTEGER NOT NULL DEFAULT 0;
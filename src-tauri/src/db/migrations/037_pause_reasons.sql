@@ -0,0 +1,2 @@
+-- Why the task was paused when this session ended; NULL when it wasn't a pause or no reason was given
+ALTER TABLE work_sessions ADD COLUMN pause_reason VARCHAR(16);
@@ -0,0 +1,4 @@
+-- What a task's calendar event shows: full, no-notes or busy; NULL follows settings
+ALTER TABLE tasks ADD COLUMN calendar_privacy TEXT;
+-- The default for tasks without their own
+ALTER TABLE settings ADD COLUMN calendar_privacy TEXT NOT NULL DEFAULT 'full';
@@ -0,0 +1,6 @@
+-- What was last sent to Google for each event, so updates that change nothing aren't sent
+ALTER TABLE calendar_events ADD COLUMN pushed_title TEXT;
+ALTER TABLE calendar_events ADD COLUMN pushed_deadline DATETIME;
+-- Of the description and reminder frequency
+ALTER TABLE calendar_events ADD COLUMN pushed_hash TEXT;
+ALTER TABLE calendar_events ADD COLUMN pushed_at DATETIME;
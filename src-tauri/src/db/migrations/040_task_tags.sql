@@ -0,0 +1,52 @@
+-- Tags (#words in a task's title or notes) of each task, for tag suggestions and stats.
+-- Notes may be sealed, so only the app can read tags out of them: triggers mark changed tasks
+-- stale and the app reads their tags again before the next tag query.
+CREATE TABLE IF NOT EXISTS task_tags (
+    task_id BLOB NOT NULL,
+    -- Lowercased, without the '#'
+    tag TEXT NOT NULL,
+    PRIMARY KEY (task_id, tag)
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_tags_tag ON task_tags(tag);
+
+-- Tasks using each tag, kept current by triggers on task_tags
+CREATE TABLE IF NOT EXISTS tag_usage (
+    tag TEXT PRIMARY KEY,
+    task_count INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE TABLE IF NOT EXISTS task_tags_stale (
+    task_id BLOB PRIMARY KEY
+);
+
+CREATE TRIGGER IF NOT EXISTS task_tags_usage_insert AFTER INSERT ON task_tags
+BEGIN
+    INSERT INTO tag_usage (tag, task_count) VALUES (NEW.tag, 1)
+    ON CONFLICT(tag) DO UPDATE SET task_count = task_count + 1;
+END;
+
+CREATE TRIGGER IF NOT EXISTS task_tags_usage_delete AFTER DELETE ON task_tags
+BEGIN
+    UPDATE tag_usage SET task_count = task_count - 1 WHERE tag = OLD.tag;
+    DELETE FROM tag_usage WHERE tag = OLD.tag AND task_count <= 0;
+END;
+
+CREATE TRIGGER IF NOT EXISTS tasks_tags_insert AFTER INSERT ON tasks
+BEGIN
+    INSERT OR IGNORE INTO task_tags_stale (task_id) VALUES (NEW.id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS tasks_tags_update AFTER UPDATE OF title, notes ON tasks
+BEGIN
+    INSERT OR IGNORE INTO task_tags_stale (task_id) VALUES (NEW.id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS tasks_tags_delete AFTER DELETE ON tasks
+BEGIN
+    DELETE FROM task_tags WHERE task_id = OLD.id;
+    DELETE FROM task_tags_stale WHERE task_id = OLD.id;
+END;
+
+-- Existing tasks are read on the first tag query
+INSERT OR IGNORE INTO task_tags_stale (task_id) SELECT id FROM tasks;
@@ -0,0 +1,2 @@
+-- Archived projects stay listed with their completed tasks; NULL while active
+ALTER TABLE projects ADD COLUMN archived_at DATETIME;
@@ -0,0 +1,44 @@
+-- How a task repeats. The task is the first occurrence; later ones are made as tasks of their own
+-- a few days before their day, with the task's settings and deadline time.
+CREATE TABLE IF NOT EXISTS task_recurrences (
+    task_id BLOB PRIMARY KEY NOT NULL,
+    frequency VARCHAR(16) NOT NULL,
+    -- Every `interval` days, weeks or months
+    interval INTEGER NOT NULL DEFAULT 1,
+    -- Local day of the task's own deadline
+    starts_on DATE NOT NULL,
+    -- Occurrences up to this day have been made
+    generated_through DATE NOT NULL,
+    created_at DATETIME NOT NULL
+);
+
+-- One occurrence handled apart from the rule: 'skip', 'reschedule' to `deadline`, or 'end-series' there
+CREATE TABLE IF NOT EXISTS recurrence_exceptions (
+    series_id BLOB NOT NULL,
+    occurrence_on DATE NOT NULL,
+    kind VARCHAR(16) NOT NULL,
+    deadline DATETIME,
+    created_at DATETIME NOT NULL,
+    PRIMARY KEY (series_id, occurrence_on)
+);
+
+-- The task each occurrence became, so an exception reaches occurrences already made
+CREATE TABLE IF NOT EXISTS recurrence_occurrences (
+    series_id BLOB NOT NULL,
+    occurrence_on DATE NOT NULL,
+    task_id BLOB NOT NULL,
+    PRIMARY KEY (series_id, occurrence_on)
+);
+
+CREATE TRIGGER IF NOT EXISTS task_recurrences_delete AFTER DELETE ON task_recurrences
+BEGIN
+    DELETE FROM recurrence_exceptions WHERE series_id = OLD.task_id;
+    DELETE FROM recurrence_occurrences WHERE series_id = OLD.task_id;
+END;
+
+-- Occurrences already made stay when the series' task is deleted
+CREATE TRIGGER IF NOT EXISTS tasks_recurrence_delete AFTER DELETE ON tasks
+BEGIN
+    DELETE FROM task_recurrences WHERE task_id = OLD.id;
+    DELETE FROM recurrence_occurrences WHERE task_id = OLD.id;
+END;
@@ -0,0 +1,3 @@
+-- Set when Google refused the refresh token for good (revoked or expired); calendar calls stop and
+-- local reminders stand in for calendar ones until the account is connected again
+ALTER TABLE calendar_credentials ADD COLUMN reauth_needed_at DATETIME;
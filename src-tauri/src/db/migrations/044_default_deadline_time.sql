@@ -0,0 +1,2 @@
+-- Local HH:MM a deadline sent as a bare date falls on
+ALTER TABLE settings ADD COLUMN default_deadline_time VARCHAR(5) NOT NULL DEFAULT '18:00';
@@ -0,0 +1,14 @@
+-- A task that can't be done before another one is: task_id is blocked by depends_on_id
+CREATE TABLE IF NOT EXISTS task_dependencies (
+    task_id BLOB NOT NULL,
+    depends_on_id BLOB NOT NULL,
+    created_at DATETIME NOT NULL,
+    PRIMARY KEY (task_id, depends_on_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_dependencies_depends_on ON task_dependencies(depends_on_id);
+
+CREATE TRIGGER IF NOT EXISTS tasks_dependencies_delete AFTER DELETE ON tasks
+BEGIN
+    DELETE FROM task_dependencies WHERE task_id = OLD.id OR depends_on_id = OLD.id;
+END;
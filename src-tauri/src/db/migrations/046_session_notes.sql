@@ -0,0 +1,2 @@
+-- What the user noted about the stretch of work when pausing or completing; NULL when they didn't
+ALTER TABLE work_sessions ADD COLUMN note TEXT;
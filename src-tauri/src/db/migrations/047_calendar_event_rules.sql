@@ -0,0 +1,15 @@
+-- Rules that turn events on the user's calendar into tasks; the calendar isn't read while there are none
+CREATE TABLE IF NOT EXISTS calendar_event_rules (
+    id TEXT PRIMARY KEY NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    -- Matched against the start of the event title, ignoring case, and cut from the task title
+    title_prefix VARCHAR(255) NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL
+);
+
+-- 'task' for events made for a task, 'calendar' for events a task was made from. The app never edits
+-- or deletes the latter, and their links outlive the task so the event isn't turned into a task again.
+ALTER TABLE calendar_events ADD COLUMN origin VARCHAR(16) NOT NULL DEFAULT 'task';
+-- Start of a 'calendar' event when its task's deadline was last set from it
+ALTER TABLE calendar_events ADD COLUMN event_start DATETIME;
@@ -0,0 +1,8 @@
+-- Reminder frequencies are read strictly now; values the old lenient reader let through become 'none'
+UPDATE tasks SET reminder_frequency = 'none'
+WHERE reminder_frequency IS NULL
+   OR reminder_frequency NOT IN ('none', 'hourly', 'every-3-hours', 'daily', 'escalating');
+
+UPDATE settings SET default_reminder_frequency = 'none'
+WHERE default_reminder_frequency IS NULL
+   OR default_reminder_frequency NOT IN ('none', 'hourly', 'every-3-hours', 'daily', 'escalating');
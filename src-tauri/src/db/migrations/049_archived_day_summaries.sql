@@ -0,0 +1,98 @@
+-- Open tasks of an archived project no longer count as planned for their day, so they stop
+-- showing as remaining; archived completed tasks still count as done
+DROP TRIGGER IF EXISTS tasks_day_summaries_insert;
+DROP TRIGGER IF EXISTS tasks_day_summaries_update;
+DROP TRIGGER IF EXISTS tasks_day_summaries_delete;
+
+-- Each trigger adds the row's new contribution and takes away its old one
+CREATE TRIGGER tasks_day_summaries_insert AFTER INSERT ON tasks
+BEGIN
+    INSERT INTO day_summaries (day, created, created_and_completed, completed, tracked_minutes)
+    SELECT day, SUM(created), SUM(created_and_completed), SUM(completed), SUM(tracked_minutes)
+    FROM (
+        SELECT date(NEW.created_at, (SELECT modifier FROM day_summary_offset)) AS day,
+               1 AS created, NEW.status = 'completed' AS created_and_completed, 0 AS completed, 0 AS tracked_minutes
+        WHERE NEW.archived_at IS NULL OR NEW.status = 'completed'
+        UNION ALL
+        SELECT date(NEW.completed_at, (SELECT modifier FROM day_summary_offset)), 0, 0, 1,
+               COALESCE(MAX(0, (strftime('%s', NEW.completed_at) - strftime('%s', NEW.started_at)) / 60), 0)
+        WHERE NEW.completed_at IS NOT NULL
+    )
+    WHERE day IS NOT NULL
+    GROUP BY day
+    ON CONFLICT(day) DO UPDATE SET
+        created = created + excluded.created,
+        created_and_completed = created_and_completed + excluded.created_and_completed,
+        completed = completed + excluded.completed,
+        tracked_minutes = tracked_minutes + excluded.tracked_minutes;
+END;
+
+CREATE TRIGGER tasks_day_summaries_update AFTER UPDATE OF created_at, started_at, completed_at, status, archived_at ON tasks
+BEGIN
+    INSERT INTO day_summaries (day, created, created_and_completed, completed, tracked_minutes)
+    SELECT day, SUM(created), SUM(created_and_completed), SUM(completed), SUM(tracked_minutes)
+    FROM (
+        SELECT date(OLD.created_at, (SELECT modifier FROM day_summary_offset)) AS day,
+               -1 AS created, -(OLD.status = 'completed') AS created_and_completed, 0 AS completed, 0 AS tracked_minutes
+        WHERE OLD.archived_at IS NULL OR OLD.status = 'completed'
+        UNION ALL
+        SELECT date(OLD.completed_at, (SELECT modifier FROM day_summary_offset)), 0, 0, -1,
+               -COALESCE(MAX(0, (strftime('%s', OLD.completed_at) - strftime('%s', OLD.started_at)) / 60), 0)
+        WHERE OLD.completed_at IS NOT NULL
+        UNION ALL
+        SELECT date(NEW.created_at, (SELECT modifier FROM day_summary_offset)),
+               1, NEW.status = 'completed', 0, 0
+        WHERE NEW.archived_at IS NULL OR NEW.status = 'completed'
+        UNION ALL
+        SELECT date(NEW.completed_at, (SELECT modifier FROM day_summary_offset)), 0, 0, 1,
+               COALESCE(MAX(0, (strftime('%s', NEW.completed_at) - strftime('%s', NEW.started_at)) / 60), 0)
+        WHERE NEW.completed_at IS NOT NULL
+    )
+    WHERE day IS NOT NULL
+    GROUP BY day
+    ON CONFLICT(day) DO UPDATE SET
+        created = created + excluded.created,
+        created_and_completed = created_and_completed + excluded.created_and_completed,
+        completed = completed + excluded.completed,
+        tracked_minutes = tracked_minutes + excluded.tracked_minutes;
+END;
+
+CREATE TRIGGER tasks_day_summaries_delete AFTER DELETE ON tasks
+BEGIN
+    INSERT INTO day_summaries (day, created, created_and_completed, completed, tracked_minutes)
+    SELECT day, SUM(created), SUM(created_and_completed), SUM(completed), SUM(tracked_minutes)
+    FROM (
+        SELECT date(OLD.created_at, (SELECT modifier FROM day_summary_offset)) AS day,
+               -1 AS created, -(OLD.status = 'completed') AS created_and_completed, 0 AS completed, 0 AS tracked_minutes
+        WHERE OLD.archived_at IS NULL OR OLD.status = 'completed'
+        UNION ALL
+        SELECT date(OLD.completed_at, (SELECT modifier FROM day_summary_offset)), 0, 0, -1,
+               -COALESCE(MAX(0, (strftime('%s', OLD.completed_at) - strftime('%s', OLD.started_at)) / 60), 0)
+        WHERE OLD.completed_at IS NOT NULL
+    )
+    WHERE day IS NOT NULL
+    GROUP BY day
+    ON CONFLICT(day) DO UPDATE SET
+        created = created + excluded.created,
+        created_and_completed = created_and_completed + excluded.created_and_completed,
+        completed = completed + excluded.completed,
+        tracked_minutes = tracked_minutes + excluded.tracked_minutes;
+END;
+
+-- Recount the days with archived open tasks left out
+DELETE FROM day_summaries;
+INSERT INTO day_summaries (day, created, created_and_completed, completed, tracked_minutes)
+SELECT day, SUM(created), SUM(created_and_completed), SUM(completed), SUM(tracked_minutes)
+FROM (
+    SELECT date(created_at, (SELECT modifier FROM day_summary_offset)) AS day,
+           1 AS created, status = 'completed' AS created_and_completed, 0 AS completed, 0 AS tracked_minutes
+    FROM tasks
+    WHERE archived_at IS NULL OR status = 'completed'
+    UNION ALL
+    SELECT date(completed_at, (SELECT modifier FROM day_summary_offset)), 0, 0, 1,
+           COALESCE(MAX(0, (strftime('%s', completed_at) - strftime('%s', started_at)) / 60), 0)
+    FROM tasks
+    WHERE completed_at IS NOT NULL
+)
+WHERE day IS NOT NULL
+GROUP BY day;
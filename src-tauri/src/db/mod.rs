@@ -1,11 +1,23 @@
-use rusqlite::Connection;
+use rusqlite::{Connection, OpenFlags};
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
-use std::sync::Mutex;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, RwLock};
+use std::time::Duration;
+use rusqlite::functions::FunctionFlags;
+use rusqlite::hooks::{Action, AuthAction, AuthContext, Authorization};
 use tauri::AppHandle;
 use tauri::Manager;
 use uuid::Uuid;
 use crate::error::{DbError, DbResult};
+use crate::helpers::crypto::{self, SecretKey, SEALED_PREFIX};
+use crate::secrets;
+use crate::structs::calendar::CalendarCredentials;
+use crate::structs::settings::Settings;
+use crate::structs::task_struct::TaskId;
+use tracing::{trace, debug, info, warn, error};
 
 // Trait for types that can be inserted into the database
 pub trait Insertable {
@@ -19,38 +31,345 @@ pub trait Updatable {
     fn update_columns_values(&self) -> Vec<(&'static str, &dyn rusqlite::ToSql)>;
 }
 
+pub const DB_FILE_NAME: &str = "myhandler.db";
+
+// Idle read-only connections kept open; more are opened under load and closed after
+const MAX_IDLE_READERS: usize = 4;
+// How long a reader waits on a checkpoint before giving up
+const READER_BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+// Read as NULL by user-written queries: tokens, passwords and signing secrets
+const SECRET_COLUMNS: [(&str, &str); 8] = [
+    ("calendar_credentials", "access_token"),
+    ("calendar_credentials", "refresh_token"),
+    ("github_credentials", "token"),
+    ("lan_peers", "secret"),
+    ("webhooks", "secret"),
+    ("app_lock", "pin_hash"),
+    ("projects", "share_key"),
+    ("secrets", "value"),
+];
+// Encrypted with the app lock's key while one is set: written through seal_text() and read through open_sealed()
+const SEALED_COLUMNS: [(&str, &str); 8] = [
+    ("tasks", "notes"),
+    ("calendar_credentials", "access_token"),
+    ("calendar_credentials", "refresh_token"),
+    ("github_credentials", "token"),
+    ("settings", "sync_webdav_password"),
+    ("settings", "sync_passphrase"),
+    ("projects", "share_key"),
+    ("secrets", "value"),
+];
+
 // Global database connection wrapped in Mutex for thread safety
 pub struct Database {
+    // The only connection that writes
     conn: Mutex<Connection>,
+    readers: Mutex<ReadPool>,
+    cache: Arc<Cache>,
+    sealing: Arc<Sealing>,
+}
+
+// Read-only connections for SELECT-only work. With WAL they read alongside a
+// write in progress, so a long sync doesn't hold up the UI's list queries.
+pub struct ReadPool {
+    // None for an in-memory database, which a second connection can't see
+    path: Option<PathBuf>,
+    idle: Vec<Connection>,
+    // Bumped when the file is swapped, so connections to the old one aren't reused
+    generation: u64,
+}
+
+// Connection for queries only: a pooled read-only one, or the main one when the
+// database has no file to open a second connection to
+pub enum ReadConnection<'a> {
+    Pooled {
+        pool: &'a Mutex<ReadPool>,
+        generation: u64,
+        conn: Option<Connection>,
+    },
+    Main(MutexGuard<'a, Connection>),
+}
+
+impl Deref for ReadConnection<'_> {
+    type Target = Connection;
+    
+    fn deref(&self) -> &Connection {
+        match self {
+            ReadConnection::Pooled { conn, .. } => conn.as_ref().expect("read connection used after release"),
+            ReadConnection::Main(conn) => conn,
+        }
+    }
+}
+
+impl Drop for ReadConnection<'_> {
+    fn drop(&mut self) {
+        if let ReadConnection::Pooled { pool, generation, conn } = self {
+            let mut pool = pool.lock().unwrap_or_else(|e| e.into_inner());
+            if pool.generation == *generation && pool.idle.len() < MAX_IDLE_READERS {
+                if let Some(conn) = conn.take() {
+                    pool.idle.push(conn);
+                }
+            }
+        }
+    }
+}
+
+fn open_reader(path: &Path) -> rusqlite::Result<Connection> {
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX)?;
+    conn.busy_timeout(READER_BUSY_TIMEOUT)?;
+    Ok(conn)
+}
+
+// Read-only connection for user-written queries. On top of the read-only flag, the authorizer
+// allows nothing but SELECTs, reads and function calls, and secret columns read as NULL
+fn open_query_reader(path: &Path) -> rusqlite::Result<Connection> {
+    let conn = open_reader(path)?;
+    conn.authorizer(Some(|ctx: AuthContext<'_>| match ctx.action {
+        AuthAction::Read { table_name, column_name }
+            if SECRET_COLUMNS.iter().any(|(table, column)| table.eq_ignore_ascii_case(table_name) && column.eq_ignore_ascii_case(column_name)) =>
+        {
+            Authorization::Ignore
+        }
+        AuthAction::Select | AuthAction::Read { .. } | AuthAction::Function { .. } | AuthAction::Recursive => Authorization::Allow,
+        _ => Authorization::Deny,
+    }))?;
+    Ok(conn)
+}
+
+// Rows read on nearly every call, kept in memory until their table is written
+#[derive(Default)]
+struct Cache {
+    settings: RwLock<Option<Settings>>,
+    // Outer None: not loaded yet; inner None: calendar not connected
+    calendar_credentials: RwLock<Option<Option<CalendarCredentials>>>,
+}
+
+impl Cache {
+    fn clear(&self) {
+        *self.settings.write().unwrap_or_else(|e| e.into_inner()) = None;
+        *self.calendar_credentials.write().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+    
+    // Hooks on the connection drop cached rows on any write to their table, whichever
+    // code path made it; a rollback drops everything, in case a value read mid-transaction was cached
+    fn watch(self: &Arc<Self>, conn: &Connection) -> rusqlite::Result<()> {
+        let cache = Arc::clone(self);
+        conn.update_hook(Some(move |_: Action, _: &str, table: &str, _: i64| match table {
+            "settings" => *cache.settings.write().unwrap_or_else(|e| e.into_inner()) = None,
+            "calendar_credentials" | "secrets" => *cache.calendar_credentials.write().unwrap_or_else(|e| e.into_inner()) = None,
+            _ => {}
+        }))?;
+        let cache = Arc::clone(self);
+        conn.rollback_hook(Some(move || cache.clear()))
+    }
+}
+
+// Key for sealed columns, shared by the SQL functions on every connection
+#[derive(Default)]
+struct Sealing {
+    // An app lock is set
+    enabled: AtomicBool,
+    // None while locked, or when there's no lock: values are then written and read as they are
+    key: RwLock<Option<Arc<SecretKey>>>,
+}
+
+impl Sealing {
+    fn key(&self) -> Option<Arc<SecretKey>> {
+        self.key.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+    
+    fn seal(&self, text: Option<String>) -> Result<Option<String>, String> {
+        match (text, self.key()) {
+            (Some(text), Some(key)) if !text.starts_with(SEALED_PREFIX) => crypto::seal_text(&key, &text).map(Some),
+            (text, _) => Ok(text),
+        }
+    }
+    
+    // A value that won't open stays sealed rather than failing the whole query
+    fn open(&self, text: String) -> String {
+        match self.key() {
+            Some(key) if text.starts_with(SEALED_PREFIX) => crypto::open_text(&key, &text).unwrap_or_else(|e| {
+                warn!("Failed to open sealed value: {}", e);
+                text
+            }),
+            _ => text,
+        }
+    }
+    
+    fn register(self: &Arc<Self>, conn: &Connection) -> rusqlite::Result<()> {
+        let sealing = Arc::clone(self);
+        conn.create_scalar_function("seal_text", 1, FunctionFlags::SQLITE_UTF8, move |ctx| {
+            sealing.seal(ctx.get(0)?).map_err(|e| rusqlite::Error::UserFunctionError(e.into()))
+        })?;
+        let sealing = Arc::clone(self);
+        conn.create_scalar_function("open_sealed", 1, FunctionFlags::SQLITE_UTF8, move |ctx| {
+            Ok(ctx.get::<Option<String>>(0)?.map(|text| sealing.open(text)))
+        })
+    }
+}
+
+// Placeholder for a column in generated INSERTs and UPDATEs
+fn placeholder(table: &str, column: &str) -> &'static str {
+    if SEALED_COLUMNS.iter().any(|(t, c)| *t == table && *c == column) {
+        "seal_text(?)"
+    } else {
+        "?"
+    }
 }
 
 impl Database {
     pub fn new(app: &AppHandle) -> DbResult<Self> {
-        let path = get_db_path(app)?;
-        
-        match Connection::open(&path) {
+        Self::open(&get_db_path(app)?)
+    }
+    
+    // Open a database file directly, without an AppHandle (CLI)
+    pub fn open(path: &Path) -> DbResult<Self> {
+        match Connection::open(path) {
             Ok(conn) => {
-                println!("Database connection opened");
-                Ok(Database {
-                    conn: Mutex::new(conn),
-                })
+                debug!("Database connection opened");
+                Self::with_connection(conn, Some(path.to_path_buf()))
             }
             Err(e) => {
-                eprintln!("Failed to open database at {:?}: {}", path, e);
+                error!("Failed to open database at {:?}: {}", path, e);
                 Err(DbError::Sqlite(e))
             }
         }
     }
-
-    pub fn get_connection(&self) -> std::sync::MutexGuard<'_, Connection> {
-        println!("Attempting to acquire database lock...");
+    
+    // Temporary database used while the real one can't be opened
+    pub fn open_in_memory() -> DbResult<Self> {
+        let conn = Connection::open_in_memory()?;
+        init_schema(&conn)?;
+        Self::with_connection(conn, None)
+    }
+    
+    fn with_connection(conn: Connection, path: Option<PathBuf>) -> DbResult<Self> {
+        let cache = Arc::new(Cache::default());
+        cache.watch(&conn)?;
+        let sealing = Arc::new(Sealing::default());
+        sealing.register(&conn)?;
+        Ok(Database {
+            conn: Mutex::new(conn),
+            readers: Mutex::new(ReadPool {
+                path,
+                idle: Vec::new(),
+                generation: 0,
+            }),
+            cache,
+            sealing,
+        })
+    }
+    
+    // Swap in a different connection, e.g. after recovering the database file
+    pub fn replace_connection(&self, conn: Connection, path: &Path) -> DbResult<()> {
+        self.cache.watch(&conn)?;
+        self.sealing.register(&conn)?;
+        let mut current = self.get_connection();
+        *current = conn;
+        self.cache.clear();
+        
+        let mut readers = self.readers.lock().unwrap_or_else(|e| e.into_inner());
+        readers.path = Some(path.to_path_buf());
+        readers.idle.clear();
+        readers.generation += 1;
+        Ok(())
+    }
+    
+    // Settings from memory; SQLite is only read again after a write to the settings table.
+    // Don't call while holding the connection: a cache miss locks it.
+    pub fn settings(&self) -> rusqlite::Result<Settings> {
+        if let Some(settings) = self.cache.settings.read().unwrap_or_else(|e| e.into_inner()).clone() {
+            return Ok(settings);
+        }
+        
+        // Stored before the lock is released, so no write can slip in between
+        let conn = self.get_connection();
+        let settings = get_settings(&conn)?;
+        *self.cache.settings.write().unwrap_or_else(|e| e.into_inner()) = Some(settings.clone());
+        Ok(settings)
+    }
+    
+    // Calendar credentials from memory, same rules as settings()
+    pub fn calendar_credentials(&self) -> rusqlite::Result<Option<CalendarCredentials>> {
+        if let Some(creds) = self.cache.calendar_credentials.read().unwrap_or_else(|e| e.into_inner()).clone() {
+            return Ok(creds);
+        }
+        
+        let conn = self.get_connection();
+        let creds = get_calendar_credentials(&conn)?;
+        *self.cache.calendar_credentials.write().unwrap_or_else(|e| e.into_inner()) = Some(creds.clone());
+        Ok(creds)
+    }
+    
+    // Connection for SELECT-only work; doesn't wait for the write lock.
+    // Falls back to the main connection if a read-only one can't be opened.
+    pub fn get_read_connection(&self) -> ReadConnection<'_> {
+        let (generation, path, idle) = {
+            let mut readers = self.readers.lock().unwrap_or_else(|e| e.into_inner());
+            (readers.generation, readers.path.clone(), readers.idle.pop())
+        };
+        
+        let conn = match (idle, path) {
+            (Some(conn), _) => Some(conn),
+            (None, Some(path)) => match open_reader(&path).and_then(|conn| self.sealing.register(&conn).map(|_| conn)) {
+                Ok(conn) => Some(conn),
+                Err(e) => {
+                    warn!("Failed to open read-only connection, using the main one: {}", e);
+                    None
+                }
+            },
+            (None, None) => None,
+        };
+        
+        match conn {
+            Some(conn) => ReadConnection::Pooled {
+                pool: &self.readers,
+                generation,
+                conn: Some(conn),
+            },
+            None => ReadConnection::Main(self.get_connection()),
+        }
+    }
+    
+    // An app lock is set and not unlocked: sealed columns can't be read
+    pub fn is_locked(&self) -> bool {
+        self.sealing.enabled.load(Ordering::SeqCst) && self.sealing.key().is_none()
+    }
+    
+    pub fn set_lock_enabled(&self, enabled: bool) {
+        self.sealing.enabled.store(enabled, Ordering::SeqCst);
+    }
+    
+    // Use `key` for sealed columns from now on, or none; returns the previous key.
+    // Cached rows were read with the old key, so they're dropped.
+    pub fn set_sealing_key(&self, key: Option<SecretKey>) -> Option<Arc<SecretKey>> {
+        let previous = std::mem::replace(&mut *self.sealing.key.write().unwrap_or_else(|e| e.into_inner()), key.map(Arc::new));
+        self.cache.clear();
+        previous
+    }
+    
+    // Put back a key returned by set_sealing_key
+    pub fn restore_sealing_key(&self, key: Option<Arc<SecretKey>>) {
+        *self.sealing.key.write().unwrap_or_else(|e| e.into_inner()) = key;
+        self.cache.clear();
+    }
+    
+    // Sandboxed connection for the query console, opened fresh and never pooled
+    pub fn open_query_connection(&self) -> DbResult<Connection> {
+        let path = self.readers.lock().unwrap_or_else(|e| e.into_inner()).path.clone()
+            .ok_or_else(|| DbError::PathError("The temporary database can't be queried".to_string()))?;
+        Ok(open_query_reader(&path)?)
+    }
+    
+    pub fn get_connection(&self) -> MutexGuard<'_, Connection> {
+        trace!("Attempting to acquire database lock...");
         match self.conn.lock() {
             Ok(guard) => {
-                println!("Database lock acquired successfully");
+                trace!("Database lock acquired successfully");
                 guard
             }
             Err(poisoned) => {
-                eprintln!("Database mutex poisoned, recovering...");
+                error!("Database mutex poisoned, recovering...");
                 poisoned.into_inner()
             }
         }
@@ -62,47 +381,171 @@ pub fn get_db_path(app: &AppHandle) -> DbResult<PathBuf> {
         .map_err(|e| DbError::PathError(format!("Failed to get app data directory: {:?}", e)))?;
     
     if let Err(e) = fs::create_dir_all(&app_dir) {
-        eprintln!("Failed to create app data directory: {}", e);
+        error!("Failed to create app data directory: {}", e);
         return Err(DbError::Io(e));
     }
     
-    let db_path = app_dir.join("myhandler.db");
-    println!("Database path: {:?}", db_path);
+    let db_path = app_dir.join(DB_FILE_NAME);
+    info!("Database path: {:?}", db_path);
     
     Ok(db_path)
 }
 
 pub fn init_db(app: &AppHandle) -> DbResult<()> {
-    println!("Initializing database...");
+    info!("Initializing database...");
     
     // Create global database connection
     let db = Database::new(app)?;
     
     // Initialize tables
     let conn = db.get_connection();
+    prepare_file_connection(&conn)?;
+    
+    // Drop the lock before storing in app state
+    drop(conn);
+    
+    // Store database in app state for global access
+    app.manage(db);
     
+    Ok(())
+}
+
+// Enable WAL and bring the schema up to date on a file-backed connection
+pub fn prepare_file_connection(conn: &rusqlite::Connection) -> DbResult<()> {
+    // WAL keeps readers from blocking on writes; checkpointed on shutdown
+    conn.execute_batch("PRAGMA journal_mode = WAL;")?;
+    init_schema(conn)?;
+    
+    // Not fatal: the old columns keep working as the source until a later start or unlock moves them
+    if let Err(e) = secrets::move_legacy(conn) {
+        warn!("Failed to move secrets into the secrets store: {}", e);
+    }
+    Ok(())
+}
+
+// Create tables and apply pending migrations
+pub fn init_schema(conn: &rusqlite::Connection) -> DbResult<()> {
     let table_sql_files = [
         ("tasks", include_str!("../db/tables/tasks.sql")),
         ("settings", include_str!("../db/tables/settings.sql")),
+        ("theme_settings", include_str!("../db/tables/theme_settings.sql")),
         ("calendar_credentials", include_str!("../db/tables/calendar_credentials.sql")),
         ("calendar_events", include_str!("../db/tables/calendar_events.sql")),
+        ("task_notifications", include_str!("../db/tables/task_notifications.sql")),
+        ("jobs", include_str!("../db/tables/jobs.sql")),
+        ("window_states", include_str!("../db/tables/window_states.sql")),
+        ("calendar_journal", include_str!("../db/tables/calendar_journal.sql")),
+        ("github_credentials", include_str!("../db/tables/github_credentials.sql")),
+        ("external_links", include_str!("../db/tables/external_links.sql")),
     ];
-
+    
     for (table_name, sql) in table_sql_files {
         match conn.execute_batch(sql) {
-            Ok(_) => println!("Table '{}' initialized", table_name),
+            Ok(_) => info!("Table '{}' initialized", table_name),
             Err(e) => {
-                eprintln!("Failed to initialize table '{}': {}", table_name, e);
+                error!("Failed to initialize table '{}': {}", table_name, e);
                 return Err(DbError::Sqlite(e));
             }
         }
     }
     
-    // Drop the lock before storing in app state
-    drop(conn);
+    run_migrations(conn)
+}
+
+// Fold the WAL back into the main database file
+pub fn checkpoint(conn: &rusqlite::Connection) -> rusqlite::Result<()> {
+    conn.execute_batch("PRAGMA wal_checkpoint(TRUNCATE);")
+}
+
+// Cheap structural check, used after an unclean shutdown
+pub fn quick_check(conn: &rusqlite::Connection) -> rusqlite::Result<String> {
+    conn.query_row("PRAGMA quick_check", [], |row| row.get(0))
+}
+
+// Whether the bundled SQLite was built with FTS5
+pub fn has_fts5(conn: &rusqlite::Connection) -> rusqlite::Result<bool> {
+    conn.query_row("SELECT sqlite_compileoption_used('ENABLE_FTS5')", [], |row| row.get(0))
+}
+
+// Schema migrations, applied in order and tracked with PRAGMA user_version
+const MIGRATIONS: &[(&str, &str)] = &[
+    ("001_task_notifications", include_str!("../db/migrations/001_task_notifications.sql")),
+    ("002_quick_add_shortcut", include_str!("../db/migrations/002_quick_add_shortcut.sql")),
+    ("003_idle_pause", include_str!("../db/migrations/003_idle_pause.sql")),
+    ("004_task_badge", include_str!("../db/migrations/004_task_badge.sql")),
+    ("005_http_api", include_str!("../db/migrations/005_http_api.sql")),
+    ("006_network_permissions", include_str!("../db/migrations/006_network_permissions.sql")),
+    ("007_github_network_permission", include_str!("../db/migrations/007_github_network_permission.sql")),
+    ("008_vault_sync", include_str!("../db/migrations/008_vault_sync.sql")),
+    ("009_slack_bridge", include_str!("../db/migrations/009_slack_bridge.sql")),
+    ("010_deadline_postponements", include_str!("../db/migrations/010_deadline_postponements.sql")),
+    ("011_goals", include_str!("../db/migrations/011_goals.sql")),
+    ("012_workload_forecast", include_str!("../db/migrations/012_workload_forecast.sql")),
+    ("013_calendar_api_log", include_str!("../db/migrations/013_calendar_api_log.sql")),
+    ("014_usage_metrics", include_str!("../db/migrations/014_usage_metrics.sql")),
+    ("015_daily_summary", include_str!("../db/migrations/015_daily_summary.sql")),
+    ("016_folder_sync", include_str!("../db/migrations/016_folder_sync.sql")),
+    ("017_sync_backends", include_str!("../db/migrations/017_sync_backends.sql")),
+    ("018_lan_sync", include_str!("../db/migrations/018_lan_sync.sql")),
+    ("019_changelog", include_str!("../db/migrations/019_changelog.sql")),
+    ("020_conflict_inbox", include_str!("../db/migrations/020_conflict_inbox.sql")),
+    ("021_day_offset", include_str!("../db/migrations/021_day_offset.sql")),
+    ("022_task_idempotency_keys", include_str!("../db/migrations/022_task_idempotency_keys.sql")),
+    ("023_completion_effects", include_str!("../db/migrations/023_completion_effects.sql")),
+    ("024_webhooks", include_str!("../db/migrations/024_webhooks.sql")),
+    ("025_rules", include_str!("../db/migrations/025_rules.sql")),
+    ("026_inbox", include_str!("../db/migrations/026_inbox.sql")),
+    ("027_locale", include_str!("../db/migrations/027_locale.sql")),
+    ("028_holidays", include_str!("../db/migrations/028_holidays.sql")),
+    ("029_app_lock", include_str!("../db/migrations/029_app_lock.sql")),
+    ("030_shared_projects", include_str!("../db/migrations/030_shared_projects.sql")),
+    ("031_work_sessions", include_str!("../db/migrations/031_work_sessions.sql")),
+    ("032_task_context", include_str!("../db/migrations/032_task_context.sql")),
+    ("033_day_summaries", include_str!("../db/migrations/033_day_summaries.sql")),
+    ("034_secrets", include_str!("../db/migrations/034_secrets.sql")),
+    ("035_remembered_dates", include_str!("../db/migrations/035_remembered_dates.sql")),
+    ("036_estimate_reached", include_str!("../db/migrations/036_estimate_reached.sql")),
+    ("037_pause_reasons", include_str!("../db/migrations/037_pause_reasons.sql")),
+    ("038_calendar_privacy", include_str!("../db/migrations/038_calendar_privacy.sql")),
+    ("039_calendar_push_cache", include_str!("../db/migrations/039_calendar_push_cache.sql")),
+    ("040_task_tags", include_str!("../db/migrations/040_task_tags.sql")),
+    ("041_project_archive", include_str!("../db/migrations/041_project_archive.sql")),
+    ("042_task_recurrence", include_str!("../db/migrations/042_task_recurrence.sql")),
+    ("043_calendar_reauth", include_str!("../db/migrations/043_calendar_reauth.sql")),
+    ("044_default_deadline_time", include_str!("../db/migrations/044_default_deadline_time.sql")),
+    ("045_task_dependencies", include_str!("../db/migrations/045_task_dependencies.sql")),
+    ("046_session_notes", include_str!("../db/migrations/046_session_notes.sql")),
+    ("047_calendar_event_rules", include_str!("../db/migrations/047_calendar_event_rules.sql")),
+    ("048_reminder_frequency_values", include_str!("../db/migrations/048_reminder_frequency_values.sql")),
+    ("049_archived_day_summaries", include_str!("../db/migrations/049_archived_day_summaries.sql")),
+];
+
+// Current schema version (number of applied migrations)
+pub fn get_schema_version(conn: &rusqlite::Connection) -> rusqlite::Result<i64> {
+    conn.query_row("PRAGMA user_version", [], |row| row.get(0))
+}
+
+// Row count for a table; only called with names from a fixed list, never user input
+pub fn count_rows(conn: &rusqlite::Connection, table_name: &str) -> rusqlite::Result<i64> {
+    let sql = format!("SELECT COUNT(*) FROM {}", table_name);
+    conn.query_row(&sql, [], |row| row.get(0))
+}
+
+fn run_migrations(conn: &rusqlite::Connection) -> DbResult<()> {
+    let current = get_schema_version(conn)? as usize;
     
-    // Store database in app state for global access
-    app.manage(db);
+    for (index, (name, sql)) in MIGRATIONS.iter().enumerate().skip(current) {
+        let tx = conn.unchecked_transaction()?;
+        
+        if let Err(e) = tx.execute_batch(sql) {
+            error!("Failed to apply migration '{}': {}", name, e);
+            return Err(DbError::Sqlite(e));
+        }
+        
+        tx.pragma_update(None, "user_version", (index + 1) as i64)?;
+        tx.commit()?;
+        info!("Migration '{}' applied", name);
+    }
     
     Ok(())
 }
@@ -112,14 +555,17 @@ pub fn insert<T: Insertable>(conn: &rusqlite::Connection, item: &T) -> rusqlite:
     let cols_vals = item.columns_values();
     let columns: Vec<&str> = cols_vals.iter().map(|(c, _)| *c).collect();
     let values: Vec<&dyn rusqlite::ToSql> = cols_vals.iter().map(|(_, v)| *v).collect();
-
+    
     let cols_str = columns.join(", ");
-    let placeholders = vec!["?"; columns.len()].join(", ");
+    let placeholders = columns.iter()
+        .map(|column| placeholder(T::table_name(), column))
+        .collect::<Vec<_>>()
+        .join(", ");
     let sql = format!("INSERT INTO {} ({}) VALUES ({})", T::table_name(), cols_str, placeholders);
-
+    
     conn.execute(&sql, &values[..]).map_err(|e| {
-        eprintln!("Failed to insert into {}: {}", T::table_name(), e);
-        eprintln!("SQL: {}", sql);
+        error!("Failed to insert into {}: {}", T::table_name(), e);
+        debug!("SQL: {}", sql);
         e
     })?;
     
@@ -136,30 +582,104 @@ pub fn query_tasks_by_date_range(
     use crate::structs::task_struct::Task;
     
     let mut stmt = conn.prepare(sql)?;
-    let task_iter = stmt.query_map([&start, &end], |row| Task::from_row(row))?;
+    let task_iter = stmt.query_map([&start, &end], Task::from_row)?;
     
     task_iter.collect()
 }
 
+// Day view rows in a date range, notes cut to `preview_chars`
+pub fn query_task_list_by_date_range(
+    conn: &rusqlite::Connection,
+    start: chrono::DateTime<chrono::Utc>,
+    end: chrono::DateTime<chrono::Utc>,
+    preview_chars: i64,
+    sql: &str,
+) -> rusqlite::Result<Vec<crate::structs::task_struct::TaskListItem>> {
+    use crate::structs::task_struct::TaskListItem;
+    
+    let mut stmt = conn.prepare(sql)?;
+    let item_iter = stmt.query_map(rusqlite::params![&start, &end, preview_chars], TaskListItem::from_row)?;
+    
+    item_iter.collect()
+}
+
+// Count open tasks in a date range
+pub fn count_tasks_by_date_not_completed(
+    conn: &rusqlite::Connection,
+    start: chrono::DateTime<chrono::Utc>,
+    end: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<i64> {
+    let sql = include_str!("../db/sql/count_tasks_by_date_not_completed.sql");
+    conn.query_row(sql, rusqlite::params![&start, &end], |row| row.get(0))
+}
+
+// Tasks a list filter matches
+pub fn count_tasks(
+    conn: &rusqlite::Connection,
+    filter: &crate::structs::task_struct::TaskFilter,
+) -> rusqlite::Result<i64> {
+    let sql = include_str!("../db/sql/count_tasks.sql");
+    conn.query_row(
+        sql,
+        rusqlite::params![filter.archived, filter.status, filter.project_id, filter.title_pattern()],
+        |row| row.get(0),
+    )
+}
+
+// One window of the tasks a list filter matches, newest first
+pub fn get_tasks_window(
+    conn: &rusqlite::Connection,
+    filter: &crate::structs::task_struct::TaskFilter,
+    preview_chars: i64,
+    offset: i64,
+    limit: i64,
+) -> rusqlite::Result<Vec<crate::structs::task_struct::TaskListItem>> {
+    use crate::structs::task_struct::TaskListItem;
+    
+    let sql = include_str!("../db/sql/get_tasks_window.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let item_iter = stmt.query_map(
+        rusqlite::params![filter.archived, filter.status, filter.project_id, filter.title_pattern(), preview_chars, limit, offset],
+        TaskListItem::from_row,
+    )?;
+    
+    item_iter.collect()
+}
+
+// Get the most relevant open tasks in a date range
+pub fn get_top_tasks_by_date(
+    conn: &rusqlite::Connection,
+    start: chrono::DateTime<chrono::Utc>,
+    end: chrono::DateTime<chrono::Utc>,
+    preview_chars: i64,
+    limit: i64,
+) -> rusqlite::Result<Vec<crate::structs::task_struct::TaskListItem>> {
+    use crate::structs::task_struct::TaskListItem;
+    
+    let sql = include_str!("../db/sql/get_top_tasks_by_date.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let item_iter = stmt.query_map(rusqlite::params![&start, &end, preview_chars, limit], TaskListItem::from_row)?;
+    
+    item_iter.collect()
+}
+
 // Delete Task by ID
 pub fn delete_task_by_id(
     conn: &rusqlite::Connection,
-    task_id: &str,
+    task_id: TaskId,
 ) -> rusqlite::Result<usize> {
-    let uuid = Uuid::parse_str(task_id)
-        .map_err(|e| rusqlite::Error::InvalidParameterName(format!("Invalid UUID: {}", e)))?;
-    
     let sql = include_str!("../db/sql/delete_task_by_id.sql");
     
-    let rows_affected = conn.execute(sql, [&uuid]).map_err(|e| {
-        eprintln!("Failed to delete task with ID {}: {}", task_id, e);
-        eprintln!("SQL: {}", sql);
+    let rows_affected = conn.execute(sql, [&task_id]).map_err(|e| {
+        error!("Failed to delete task with ID {}: {}", task_id, e);
+        debug!("SQL: {}", sql);
         e
     })?;
     
     if rows_affected == 0 {
-        eprintln!("Warning: No task found with ID {}", task_id);
+        warn!("No task found with ID {}", task_id);
     }
+    conn.execute(include_str!("../db/sql/delete_task_work_sessions.sql"), [&task_id])?;
     
     Ok(rows_affected)
 }
@@ -167,27 +687,31 @@ pub fn delete_task_by_id(
 // Get a single task by ID
 pub fn get_task_by_id(
     conn: &rusqlite::Connection,
-    task_id: &str,
+    task_id: TaskId,
 ) -> rusqlite::Result<crate::structs::task_struct::Task> {
     use crate::structs::task_struct::Task;
     
-    let uuid = Uuid::parse_str(task_id)
-        .map_err(|e| rusqlite::Error::InvalidParameterName(format!("Invalid UUID: {}", e)))?;
-    
     let sql = include_str!("../db/sql/get_task_by_id.sql");
     
-    conn.query_row(sql, [&uuid], |row| Task::from_row(row))
+    conn.query_row(sql, [&task_id], Task::from_row)
+}
+
+// Full notes of one task, which list queries only preview
+pub fn get_task_notes(
+    conn: &rusqlite::Connection,
+    task_id: TaskId,
+) -> rusqlite::Result<Option<String>> {
+    let sql = include_str!("../db/sql/get_task_notes.sql");
+    
+    conn.query_row(sql, [&task_id], |row| row.get(0))
 }
 
 // Update task fields
 pub fn update_task<T: Updatable>(
     conn: &rusqlite::Connection,
-    task_id: &str,
+    task_id: TaskId,
     update_data: &T,
 ) -> rusqlite::Result<crate::structs::task_struct::Task> {
-    let uuid = Uuid::parse_str(task_id)
-        .map_err(|e| rusqlite::Error::InvalidParameterName(format!("Invalid UUID: {}", e)))?;
-    
     let cols_vals = update_data.update_columns_values();
     
     if cols_vals.is_empty() {
@@ -196,7 +720,7 @@ pub fn update_task<T: Updatable>(
     }
     
     let set_clauses: Vec<String> = cols_vals.iter()
-        .map(|(col, _)| format!("{} = ?", col))
+        .map(|(col, _)| format!("{} = {}", col, placeholder(T::table_name(), col)))
         .collect();
     let values: Vec<&dyn rusqlite::ToSql> = cols_vals.iter()
         .map(|(_, v)| *v)
@@ -209,11 +733,11 @@ pub fn update_task<T: Updatable>(
     );
     
     let mut params = values;
-    params.push(&uuid);
+    params.push(&task_id);
     
     let rows_affected = conn.execute(&sql, &params[..]).map_err(|e| {
-        eprintln!("Failed to update task with ID {}: {}", task_id, e);
-        eprintln!("SQL: {}", sql);
+        error!("Failed to update task with ID {}: {}", task_id, e);
+        debug!("SQL: {}", sql);
         e
     })?;
     
@@ -227,14 +751,11 @@ pub fn update_task<T: Updatable>(
 // Handles: start, pause, resume, complete transitions
 pub fn update_task_status(
     conn: &rusqlite::Connection,
-    task_id: &str,
+    task_id: TaskId,
     new_status: crate::structs::task_struct::Status,
 ) -> rusqlite::Result<crate::structs::task_struct::Task> {
     use crate::structs::task_struct::Status;
     
-    let uuid = Uuid::parse_str(task_id)
-        .map_err(|e| rusqlite::Error::InvalidParameterName(format!("Invalid UUID: {}", e)))?;
-    
     let now = chrono::Utc::now();
     
     // Load SQL based on the status transition using include_str! macro
@@ -246,11 +767,11 @@ pub fn update_task_status(
     };
     
     let rows_affected = if new_status == Status::NotStarted {
-        conn.execute(sql, rusqlite::params![&new_status, &now, &uuid])
+        conn.execute(sql, rusqlite::params![&new_status, &now, &task_id])
     } else {
-        conn.execute(sql, rusqlite::params![&new_status, &now, &now, &uuid])
+        conn.execute(sql, rusqlite::params![&new_status, &now, &now, &task_id])
     }.map_err(|e| {
-        eprintln!("Failed to update task status to {:?} for ID {}: {}", new_status, task_id, e);
+        error!("Failed to update task status to {:?} for ID {}: {}", new_status, task_id, e);
         e
     })?;
     
@@ -258,162 +779,456 @@ pub fn update_task_status(
         return Err(rusqlite::Error::QueryReturnedNoRows);
     }
     
+    // Every other status ends the stretch of work
+    if new_status == Status::Ongoing {
+        start_work_session(conn, task_id, now)?;
+    } else {
+        end_work_session(conn, task_id, now, None, None)?;
+    }
+    
     // Fetch and return the updated task
     get_task_by_id(conn, task_id)
 }
 
-// Get settings from database
-pub fn get_settings(
+// Pause an ongoing task with an explicit pause time (e.g. when the user went idle)
+pub fn pause_task_at(
     conn: &rusqlite::Connection,
-) -> rusqlite::Result<crate::structs::settings::Settings> {
-    use crate::structs::settings::Settings;
+    task_id: TaskId,
+    paused_at: chrono::DateTime<chrono::Utc>,
+    reason: Option<crate::structs::work_session::PauseReason>,
+    note: Option<&str>,
+) -> rusqlite::Result<crate::structs::task_struct::Task> {
+    use crate::structs::task_struct::Status;
     
-    let sql = include_str!("../db/sql/get_settings.sql");
+    let sql = include_str!("../db/sql/update_status_paused.sql");
+    let rows_affected = conn.execute(sql, rusqlite::params![&Status::Paused, &paused_at, &chrono::Utc::now(), &task_id])?;
+    
+    if rows_affected == 0 {
+        return Err(rusqlite::Error::QueryReturnedNoRows);
+    }
+    end_work_session(conn, task_id, paused_at, reason, note)?;
     
-    conn.query_row(sql, [], |row| Settings::from_row(row))
+    get_task_by_id(conn, task_id)
 }
 
-// Update settings in database
-pub fn update_settings<T: Updatable>(
+pub fn start_work_session(
     conn: &rusqlite::Connection,
-    update_data: &T,
-) -> rusqlite::Result<crate::structs::settings::Settings> {
-    let cols_vals = update_data.update_columns_values();
+    task_id: TaskId,
+    started_at: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/start_work_session.sql");
+    conn.execute(sql, rusqlite::params![&task_id, &started_at])?;
     
-    if cols_vals.is_empty() {
-        // No fields to update, just return current settings
-        return get_settings(conn);
-    }
+    Ok(())
+}
+
+pub fn end_work_session(
+    conn: &rusqlite::Connection,
+    task_id: TaskId,
+    ended_at: chrono::DateTime<chrono::Utc>,
+    pause_reason: Option<crate::structs::work_session::PauseReason>,
+    note: Option<&str>,
+) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/end_work_session.sql");
+    conn.execute(sql, rusqlite::params![&task_id, &ended_at, &pause_reason, &note])?;
     
-    let set_clauses: Vec<String> = cols_vals.iter()
-        .map(|(col, _)| format!("{} = ?", col))
-        .collect();
-    let values: Vec<&dyn rusqlite::ToSql> = cols_vals.iter()
-        .map(|(_, v)| *v)
-        .collect();
+    Ok(())
+}
+
+// Hand a merged task's sessions to the task it was merged into
+pub fn move_task_work_sessions(conn: &rusqlite::Connection, from: TaskId, to: TaskId) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/move_task_work_sessions.sql");
+    conn.execute(sql, rusqlite::params![&from, &to])?;
+    Ok(())
+}
+
+// Finished sessions not yet handled for the calendar, oldest first
+pub fn get_unposted_work_sessions(
+    conn: &rusqlite::Connection,
+) -> rusqlite::Result<Vec<crate::structs::work_session::WorkSession>> {
+    use crate::structs::work_session::WorkSession;
     
-    let now = chrono::Utc::now();
-    let sql = format!(
-        "UPDATE {} SET {}, updated_at = ? WHERE id = 1",
-        T::table_name(),
-        set_clauses.join(", ")
-    );
+    let sql = include_str!("../db/sql/get_unposted_work_sessions.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let session_iter = stmt.query_map([], WorkSession::from_row)?;
     
-    let mut params = values;
-    params.push(&now);
+    session_iter.collect()
+}
+
+// Session notes in the range, oldest first
+pub fn get_session_notes(
+    conn: &rusqlite::Connection,
+    start: chrono::DateTime<chrono::Utc>,
+    end: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<Vec<crate::structs::work_session::SessionNote>> {
+    use crate::structs::work_session::SessionNote;
     
-    let rows_affected = conn.execute(&sql, &params[..]).map_err(|e| {
-        eprintln!("Failed to update settings: {}", e);
-        eprintln!("SQL: {}", sql);
-        e
-    })?;
+    let sql = include_str!("../db/sql/get_session_notes.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let note_iter = stmt.query_map([&start, &end], SessionNote::from_row)?;
     
-    if rows_affected == 0 {
-        Err(rusqlite::Error::QueryReturnedNoRows)
-    } else {
-        get_settings(conn)
-    }
+    note_iter.collect()
 }
 
-// Save calendar credentials to database
-pub fn save_calendar_credentials(
+// No event ID marks the session as skipped
+pub fn mark_work_session_posted(
     conn: &rusqlite::Connection,
-    creds: &crate::structs::calendar::CalendarCredentials,
+    session_id: i64,
+    posted_at: chrono::DateTime<chrono::Utc>,
+    event_id: Option<&str>,
 ) -> rusqlite::Result<()> {
-    let sql = include_str!("../db/sql/save_calendar_credentials.sql");
-    
-    conn.execute(
-        sql,
-        rusqlite::params![
-            &creds.email,
-            &creds.access_token,
-            &creds.refresh_token,
-            &creds.token_expiry,
-        ],
-    )?;
-    
-    // Enable calendar integration in settings
-    let enable_sql = include_str!("../db/sql/enable_calendar_integration.sql");
-    conn.execute(enable_sql, rusqlite::params![&creds.email])?;
+    let sql = include_str!("../db/sql/mark_work_session_posted.sql");
+    conn.execute(sql, rusqlite::params![&session_id, &posted_at, &event_id])?;
     
     Ok(())
 }
 
-// Get calendar credentials from database
-pub fn get_calendar_credentials(
+// Archive completion effect; reopening the task clears it
+pub fn archive_task(
     conn: &rusqlite::Connection,
-) -> rusqlite::Result<Option<crate::structs::calendar::CalendarCredentials>> {
+    task_id: TaskId,
+    archived_at: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/archive_task.sql");
+    conn.execute(sql, rusqlite::params![&archived_at, &task_id])?;
+    
+    Ok(())
+}
+
+// Open tasks due by `end`, overdue ones included
+pub fn get_open_tasks_due_by(
+    conn: &rusqlite::Connection,
+    end: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<Vec<crate::structs::task_struct::Task>> {
+    use crate::structs::task_struct::Task;
+    
+    let sql = include_str!("../db/sql/get_open_tasks_due_by.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let task_iter = stmt.query_map([&end], Task::from_row)?;
+    
+    task_iter.collect()
+}
+
+// Open, unarchived tasks planned for a day before `end`, earliest first
+pub fn get_open_tasks_planned_before(
+    conn: &rusqlite::Connection,
+    end: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<Vec<crate::structs::task_struct::Task>> {
+    use crate::structs::task_struct::Task;
+    
+    let sql = include_str!("../db/sql/get_open_tasks_planned_before.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let task_iter = stmt.query_map([&end], Task::from_row)?;
+    
+    task_iter.collect()
+}
+
+// Open tasks the default reminder frequency would change, oldest first
+pub fn get_open_tasks_for_reminder_default(
+    conn: &rusqlite::Connection,
+    frequency: &crate::structs::reminder_frequency::ReminderFrequency,
+    unset_only: bool,
+) -> rusqlite::Result<Vec<crate::structs::task_struct::Task>> {
+    use crate::structs::task_struct::Task;
+    
+    let sql = include_str!("../db/sql/get_open_tasks_for_reminder_default.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let task_iter = stmt.query_map(rusqlite::params![frequency, unset_only], Task::from_row)?;
+    
+    task_iter.collect()
+}
+
+pub fn set_task_reminder_frequency(
+    conn: &rusqlite::Connection,
+    task_id: TaskId,
+    frequency: &crate::structs::reminder_frequency::ReminderFrequency,
+    updated_at: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/set_task_reminder_frequency.sql");
+    conn.execute(sql, rusqlite::params![frequency, &updated_at, &task_id])?;
+    
+    Ok(())
+}
+
+// Move an open task to another day; tasks are planned by their created date
+pub fn reschedule_task(
+    conn: &rusqlite::Connection,
+    task_id: TaskId,
+    planned_at: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<crate::structs::task_struct::Task> {
+    let sql = include_str!("../db/sql/reschedule_task.sql");
+    let rows_affected = conn.execute(sql, rusqlite::params![&planned_at, &chrono::Utc::now(), &task_id])?;
+    
+    if rows_affected == 0 {
+        Err(rusqlite::Error::QueryReturnedNoRows)
+    } else {
+        get_task_by_id(conn, task_id)
+    }
+}
+
+// Open tasks for a context, energy and time budget; deadlines first
+pub fn get_tasks_for_context(
+    conn: &rusqlite::Connection,
+    context: Option<&str>,
+    max_energy: Option<crate::structs::task_struct::Energy>,
+    available_minutes: Option<i32>,
+    unestimated_minutes: i32,
+) -> rusqlite::Result<Vec<crate::structs::task_struct::Task>> {
+    use crate::structs::task_struct::Task;
+    
+    let sql = include_str!("../db/sql/get_tasks_for_context.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let task_iter = stmt.query_map(
+        rusqlite::params![context, max_energy, available_minutes, unestimated_minutes],
+        Task::from_row,
+    )?;
+    
+    task_iter.collect()
+}
+
+// Get all ongoing tasks
+pub fn get_ongoing_tasks(
+    conn: &rusqlite::Connection,
+) -> rusqlite::Result<Vec<crate::structs::task_struct::Task>> {
+    use crate::structs::task_struct::Task;
+    
+    let sql = include_str!("../db/sql/get_ongoing_tasks.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let task_iter = stmt.query_map([], Task::from_row)?;
+    
+    task_iter.collect()
+}
+
+// Get settings from database
+pub fn get_settings(
+    conn: &rusqlite::Connection,
+) -> rusqlite::Result<crate::structs::settings::Settings> {
+    use crate::structs::settings::Settings;
+    
+    let sql = include_str!("../db/sql/get_settings.sql");
+    
+    conn.query_row(sql, [], Settings::from_row)
+}
+
+// Update settings in database
+pub fn update_settings<T: Updatable>(
+    conn: &rusqlite::Connection,
+    update_data: &T,
+) -> rusqlite::Result<crate::structs::settings::Settings> {
+    let cols_vals = update_data.update_columns_values();
+    
+    if cols_vals.is_empty() {
+        // No fields to update, just return current settings
+        return get_settings(conn);
+    }
+    
+    let set_clauses: Vec<String> = cols_vals.iter()
+        .map(|(col, _)| format!("{} = {}", col, placeholder(T::table_name(), col)))
+        .collect();
+    let values: Vec<&dyn rusqlite::ToSql> = cols_vals.iter()
+        .map(|(_, v)| *v)
+        .collect();
+    
+    let now = chrono::Utc::now();
+    let sql = format!(
+        "UPDATE {} SET {}, updated_at = ? WHERE id = 1",
+        T::table_name(),
+        set_clauses.join(", ")
+    );
+    
+    let mut params = values;
+    params.push(&now);
+    
+    let rows_affected = conn.execute(&sql, &params[..]).map_err(|e| {
+        error!("Failed to update settings: {}", e);
+        debug!("SQL: {}", sql);
+        e
+    })?;
+    
+    if rows_affected == 0 {
+        Err(rusqlite::Error::QueryReturnedNoRows)
+    } else {
+        get_settings(conn)
+    }
+}
+
+// Get per-feature network consent (stored on the settings row)
+pub fn get_network_permissions(
+    conn: &rusqlite::Connection,
+) -> rusqlite::Result<crate::structs::network::NetworkPermissions> {
+    use crate::structs::network::NetworkPermissions;
+    
+    let sql = include_str!("../db/sql/get_network_permissions.sql");
+    
+    conn.query_row(sql, [], NetworkPermissions::from_row)
+}
+
+// Get theme from database
+pub fn get_theme(
+    conn: &rusqlite::Connection,
+) -> rusqlite::Result<crate::structs::theme::Theme> {
+    use crate::structs::theme::Theme;
+    
+    let sql = include_str!("../db/sql/get_theme.sql");
+    
+    conn.query_row(sql, [], Theme::from_row)
+}
+
+// Update theme in database
+pub fn update_theme<T: Updatable>(
+    conn: &rusqlite::Connection,
+    update_data: &T,
+) -> rusqlite::Result<crate::structs::theme::Theme> {
+    let cols_vals = update_data.update_columns_values();
+    
+    if cols_vals.is_empty() {
+        // No fields to update, just return current theme
+        return get_theme(conn);
+    }
+    
+    let set_clauses: Vec<String> = cols_vals.iter()
+        .map(|(col, _)| format!("{} = ?", col))
+        .collect();
+    let values: Vec<&dyn rusqlite::ToSql> = cols_vals.iter()
+        .map(|(_, v)| *v)
+        .collect();
+    
+    let now = chrono::Utc::now();
+    let sql = format!(
+        "UPDATE {} SET {}, updated_at = ? WHERE id = 1",
+        T::table_name(),
+        set_clauses.join(", ")
+    );
+    
+    let mut params = values;
+    params.push(&now);
+    
+    let rows_affected = conn.execute(&sql, &params[..]).map_err(|e| {
+        error!("Failed to update theme: {}", e);
+        debug!("SQL: {}", sql);
+        e
+    })?;
+    
+    if rows_affected == 0 {
+        Err(rusqlite::Error::QueryReturnedNoRows)
+    } else {
+        get_theme(conn)
+    }
+}
+
+// Save calendar credentials to database
+pub fn save_calendar_credentials(
+    conn: &rusqlite::Connection,
+    creds: &crate::structs::calendar::CalendarCredentials,
+) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/save_calendar_credentials.sql");
+    
+    conn.execute(
+        sql,
+        rusqlite::params![
+            &creds.email,
+            &creds.token_expiry,
+        ],
+    )?;
+    secrets::store(conn, secrets::GOOGLE, "access_token", &creds.access_token).map_err(secret_error)?;
+    secrets::store(conn, secrets::GOOGLE, "refresh_token", &creds.refresh_token).map_err(secret_error)?;
+    
+    // Enable calendar integration in settings
+    let enable_sql = include_str!("../db/sql/enable_calendar_integration.sql");
+    conn.execute(enable_sql, rusqlite::params![&creds.email])?;
+    
+    Ok(())
+}
+
+// Get calendar credentials from database
+pub fn get_calendar_credentials(
+    conn: &rusqlite::Connection,
+) -> rusqlite::Result<Option<crate::structs::calendar::CalendarCredentials>> {
     use crate::structs::calendar::CalendarCredentials;
     
-    println!("get_calendar_credentials: Loading SQL...");
+    debug!("get_calendar_credentials: Loading SQL...");
     let sql = include_str!("../db/sql/get_calendar_credentials.sql");
-    println!("get_calendar_credentials: SQL loaded, executing query...");
+    debug!("get_calendar_credentials: SQL loaded, executing query...");
     
     let result = conn.query_row(sql, [], |row| {
-        println!("get_calendar_credentials: Processing row...");
+        debug!("get_calendar_credentials: Processing row...");
         let email: String = row.get(0)?;
-        let access_token: String = row.get(1)?;
-        let refresh_token: String = row.get(2)?;
-        let token_expiry: chrono::DateTime<chrono::Utc> = row.get(3)?;
+        let token_expiry: chrono::DateTime<chrono::Utc> = row.get(1)?;
+        let reauth_needed_at: Option<chrono::DateTime<chrono::Utc>> = row.get(2)?;
         
-        println!("get_calendar_credentials: Row data retrieved");
+        debug!("get_calendar_credentials: Row data retrieved");
         
         // Check if credentials are actually set (not empty placeholder)
-        if email.is_empty() || access_token.is_empty() {
+        if email.is_empty() {
             return Err(rusqlite::Error::QueryReturnedNoRows);
         }
+        Ok((email, token_expiry, reauth_needed_at))
+    }).and_then(|(email, token_expiry, reauth_needed_at)| {
+        let access_token = secrets::get(conn, secrets::GOOGLE, "access_token").map_err(secret_error)?;
+        let refresh_token = secrets::get(conn, secrets::GOOGLE, "refresh_token").map_err(secret_error)?;
+        let Some(access_token) = access_token else {
+            return Err(rusqlite::Error::QueryReturnedNoRows);
+        };
         
         Ok(CalendarCredentials {
             email,
             access_token,
-            refresh_token,
+            refresh_token: refresh_token.unwrap_or_default(),
             token_expiry,
+            reauth_needed_at,
         })
     });
     
-    println!("get_calendar_credentials: Query executed, processing result...");
+    debug!("get_calendar_credentials: Query executed, processing result...");
     
     match result {
         Ok(creds) => {
-            println!("get_calendar_credentials: Credentials found");
+            debug!("get_calendar_credentials: Credentials found");
             Ok(Some(creds))
         }
         Err(rusqlite::Error::QueryReturnedNoRows) => {
-            println!("get_calendar_credentials: No credentials found (empty table)");
+            debug!("get_calendar_credentials: No credentials found (empty table)");
             Ok(None)
         }
         Err(e) => {
-            eprintln!("get_calendar_credentials: Error occurred: {}", e);
+            error!("get_calendar_credentials: Error occurred: {}", e);
             Err(e)
         }
     }
 }
 
-// Clear calendar credentials from database
-pub fn clear_calendar_credentials(conn: &rusqlite::Connection) -> rusqlite::Result<()> {
+// True the first time; later refusals keep the original time
+pub fn set_calendar_reauth_needed(conn: &rusqlite::Connection, at: chrono::DateTime<chrono::Utc>) -> rusqlite::Result<bool> {
+    let sql = include_str!("../db/sql/set_calendar_reauth_needed.sql");
+    Ok(conn.execute(sql, [&at])? == 1)
+}
+
+// Clear calendar credentials from database; event links stay unless asked to clear them, so
+// connecting the same account again can pick them up
+pub fn clear_calendar_credentials(conn: &rusqlite::Connection, clear_event_links: bool) -> rusqlite::Result<()> {
     let sql = include_str!("../db/sql/clear_calendar_credentials.sql");
     conn.execute(sql, [])?;
+    secrets::delete(conn, secrets::GOOGLE, "access_token").map_err(secret_error)?;
+    secrets::delete(conn, secrets::GOOGLE, "refresh_token").map_err(secret_error)?;
     
     // Disable calendar integration in settings
     let disable_sql = include_str!("../db/sql/disable_calendar_integration.sql");
     conn.execute(disable_sql, [])?;
     
-    // Clear all calendar events
-    clear_all_calendar_events(conn)?;
+    if clear_event_links {
+        clear_all_calendar_events(conn)?;
+    }
     
     Ok(())
 }
 // Update google_event_id for a task
 pub fn update_task_google_event_id(
     conn: &rusqlite::Connection,
-    task_id: &str,
+    task_id: TaskId,
     event_id: &str,
 ) -> rusqlite::Result<()> {
-    let uuid = Uuid::parse_str(task_id)
-        .map_err(|e| rusqlite::Error::InvalidParameterName(format!("Invalid UUID: {}", e)))?;
-    
     let sql = include_str!("../db/sql/upsert_calendar_event.sql");
-    conn.execute(sql, rusqlite::params![&uuid, event_id])?;
+    conn.execute(sql, rusqlite::params![&task_id, event_id])?;
     
     Ok(())
 }
@@ -421,13 +1236,10 @@ pub fn update_task_google_event_id(
 // Clear google_event_id for a task
 pub fn clear_task_google_event_id(
     conn: &rusqlite::Connection,
-    task_id: &str,
+    task_id: TaskId,
 ) -> rusqlite::Result<()> {
-    let uuid = Uuid::parse_str(task_id)
-        .map_err(|e| rusqlite::Error::InvalidParameterName(format!("Invalid UUID: {}", e)))?;
-    
     let sql = include_str!("../db/sql/delete_calendar_event_by_task.sql");
-    conn.execute(sql, rusqlite::params![&uuid])?;
+    conn.execute(sql, rusqlite::params![&task_id])?;
     
     Ok(())
 }
@@ -435,13 +1247,10 @@ pub fn clear_task_google_event_id(
 // Get google_event_id for a task
 pub fn get_task_google_event_id(
     conn: &rusqlite::Connection,
-    task_id: &str,
+    task_id: TaskId,
 ) -> rusqlite::Result<Option<String>> {
-    let uuid = Uuid::parse_str(task_id)
-        .map_err(|e| rusqlite::Error::InvalidParameterName(format!("Invalid UUID: {}", e)))?;
-    
     let sql = include_str!("../db/sql/get_calendar_event_by_task.sql");
-    let result = conn.query_row(sql, rusqlite::params![&uuid], |row| row.get(0));
+    let result = conn.query_row(sql, rusqlite::params![&task_id], |row| row.get(0));
     
     match result {
         Ok(event_id) => Ok(Some(event_id)),
@@ -450,6 +1259,48 @@ pub fn get_task_google_event_id(
     }
 }
 
+// None until an update for the event has been sent since it was cached
+pub fn get_pushed_calendar_event(
+    conn: &rusqlite::Connection,
+    event_id: &str,
+) -> rusqlite::Result<Option<crate::structs::calendar_event::PushedEvent>> {
+    use crate::structs::calendar_event::PushedEvent;
+    
+    let sql = include_str!("../db/sql/get_pushed_calendar_event.sql");
+    match conn.query_row(sql, [event_id], PushedEvent::from_row) {
+        Ok(pushed) => Ok(Some(pushed)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn save_pushed_calendar_event(
+    conn: &rusqlite::Connection,
+    task_id: TaskId,
+    event_id: &str,
+    pushed: &crate::structs::calendar_event::PushedEvent,
+    pushed_at: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/save_pushed_calendar_event.sql");
+    conn.execute(sql, rusqlite::params![&task_id, event_id, pushed.title, pushed.deadline, pushed.content_hash, pushed_at])?;
+    Ok(())
+}
+
+// None when the task follows settings, or no longer exists
+pub fn get_task_calendar_privacy(
+    conn: &rusqlite::Connection,
+    task_id: TaskId,
+) -> rusqlite::Result<Option<crate::structs::calendar_event::CalendarPrivacy>> {
+    let sql = include_str!("../db/sql/get_task_calendar_privacy.sql");
+    let result = conn.query_row(sql, rusqlite::params![&task_id], |row| row.get(0));
+    
+    match result {
+        Ok(privacy) => Ok(privacy),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
 // Clear all calendar events (when disconnecting calendar)
 pub fn clear_all_calendar_events(
     conn: &rusqlite::Connection,
@@ -458,3 +1309,2093 @@ pub fn clear_all_calendar_events(
     conn.execute(sql, [])?;
     Ok(())
 }
+
+// Get open tasks with notifications enabled and a deadline before the cutoff
+pub fn get_tasks_due_for_notification(
+    conn: &rusqlite::Connection,
+    cutoff: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<Vec<crate::structs::task_struct::Task>> {
+    use crate::structs::task_struct::Task;
+    
+    let sql = include_str!("../db/sql/get_tasks_due_for_notification.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let task_iter = stmt.query_map([&cutoff], Task::from_row)?;
+    
+    task_iter.collect()
+}
+
+// Tasks due after `now` that remind through local notifications
+pub fn get_tasks_with_reminders(
+    conn: &rusqlite::Connection,
+    now: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<Vec<crate::structs::task_struct::Task>> {
+    use crate::structs::task_struct::Task;
+    
+    let sql = include_str!("../db/sql/get_tasks_with_reminders.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let task_iter = stmt.query_map([&now], Task::from_row)?;
+    
+    task_iter.collect()
+}
+
+// Running tasks at or past `percent` of their estimate, with the minutes tracked on them
+pub fn get_tasks_past_estimate(
+    conn: &rusqlite::Connection,
+    now: chrono::DateTime<chrono::Utc>,
+    percent: i32,
+) -> rusqlite::Result<Vec<(crate::structs::task_struct::Task, i64)>> {
+    use crate::structs::task_struct::Task;
+    
+    let sql = include_str!("../db/sql/get_tasks_past_estimate.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map(rusqlite::params![&now, percent], |row| Ok((Task::from_row(row)?, row.get(20)?)))?;
+    
+    rows.collect()
+}
+
+// Check whether a notification kind was already delivered for a task
+pub fn has_task_notification(
+    conn: &rusqlite::Connection,
+    task_id: TaskId,
+    kind: &str,
+) -> rusqlite::Result<bool> {
+    let sql = include_str!("../db/sql/has_task_notification.sql");
+    let count: i64 = conn.query_row(sql, rusqlite::params![&task_id, kind], |row| row.get(0))?;
+    
+    Ok(count > 0)
+}
+
+// Record that a notification kind was delivered for a task
+pub fn record_task_notification(
+    conn: &rusqlite::Connection,
+    task_id: TaskId,
+    kind: &str,
+) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/record_task_notification.sql");
+    conn.execute(sql, rusqlite::params![&task_id, kind])?;
+    
+    Ok(())
+}
+
+// Forget delivered notifications for a task so they can fire again
+pub fn clear_task_notifications(
+    conn: &rusqlite::Connection,
+    task_id: TaskId,
+) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/clear_task_notifications.sql");
+    conn.execute(sql, rusqlite::params![&task_id])?;
+    
+    Ok(())
+}
+
+// Count a moved deadline against the task
+pub fn record_deadline_move(
+    conn: &rusqlite::Connection,
+    task_id: TaskId,
+    new_deadline: chrono::DateTime<chrono::Utc>,
+    old_deadline: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/record_deadline_move.sql");
+    conn.execute(sql, rusqlite::params![&task_id, &new_deadline, &old_deadline])?;
+    
+    Ok(())
+}
+
+// Add a job definition if it does not exist yet
+pub fn register_job(
+    conn: &rusqlite::Connection,
+    name: &str,
+    schedule: &str,
+    next_run_at: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/register_job.sql");
+    conn.execute(sql, rusqlite::params![name, schedule, &next_run_at])?;
+    Ok(())
+}
+
+// Get all scheduler jobs
+pub fn get_jobs(conn: &rusqlite::Connection) -> rusqlite::Result<Vec<crate::structs::job::Job>> {
+    use crate::structs::job::Job;
+    
+    let sql = include_str!("../db/sql/get_jobs.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let job_iter = stmt.query_map([], Job::from_row)?;
+    
+    job_iter.collect()
+}
+
+// Get enabled jobs that are due to run
+pub fn get_due_jobs(
+    conn: &rusqlite::Connection,
+    now: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<Vec<crate::structs::job::Job>> {
+    use crate::structs::job::Job;
+    
+    let sql = include_str!("../db/sql/get_due_jobs.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let job_iter = stmt.query_map([&now], Job::from_row)?;
+    
+    job_iter.collect()
+}
+
+// Get a single job by name
+pub fn get_job_by_name(
+    conn: &rusqlite::Connection,
+    name: &str,
+) -> rusqlite::Result<crate::structs::job::Job> {
+    use crate::structs::job::Job;
+    
+    let sql = include_str!("../db/sql/get_job_by_name.sql");
+    conn.query_row(sql, [name], Job::from_row)
+}
+
+// Record the outcome of a job run
+pub fn record_job_run(
+    conn: &rusqlite::Connection,
+    name: &str,
+    ran_at: chrono::DateTime<chrono::Utc>,
+    error: Option<&str>,
+    next_run_at: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/record_job_run.sql");
+    conn.execute(sql, rusqlite::params![&ran_at, error, &next_run_at, name])?;
+    Ok(())
+}
+
+pub fn set_job_schedule(
+    conn: &rusqlite::Connection,
+    name: &str,
+    schedule: &str,
+    next_run_at: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/set_job_schedule.sql");
+    conn.execute(sql, rusqlite::params![schedule, &next_run_at, name])?;
+    Ok(())
+}
+
+// Get the saved geometry for a window kind, if any
+pub fn get_window_state(
+    conn: &rusqlite::Connection,
+    kind: &str,
+) -> rusqlite::Result<Option<crate::structs::window_state::WindowState>> {
+    use crate::structs::window_state::WindowState;
+    
+    let sql = include_str!("../db/sql/get_window_state.sql");
+    
+    match conn.query_row(sql, [kind], WindowState::from_row) {
+        Ok(state) => Ok(Some(state)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+// Save the geometry for a window kind
+pub fn save_window_state(
+    conn: &rusqlite::Connection,
+    state: &crate::structs::window_state::WindowState,
+) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/save_window_state.sql");
+    conn.execute(sql, rusqlite::params![
+        &state.kind,
+        state.x,
+        state.y,
+        state.width,
+        state.height,
+        state.always_on_top,
+        &state.updated_at,
+    ])?;
+    Ok(())
+}
+
+// Journal a calendar side effect; returns the entry id
+pub fn record_calendar_op(
+    conn: &rusqlite::Connection,
+    task_id: TaskId,
+    operation: crate::structs::calendar_journal::JournalOperation,
+    event_id: Option<&str>,
+) -> rusqlite::Result<i64> {
+    let sql = include_str!("../db/sql/record_calendar_op.sql");
+    conn.execute(sql, rusqlite::params![&task_id, &operation, event_id, &chrono::Utc::now()])?;
+    Ok(conn.last_insert_rowid())
+}
+
+// Mark an entry and older pending ones of the same kind for the task as done
+pub fn complete_calendar_ops(
+    conn: &rusqlite::Connection,
+    task_id: TaskId,
+    operation: crate::structs::calendar_journal::JournalOperation,
+    up_to_id: i64,
+) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/complete_calendar_ops.sql");
+    conn.execute(sql, rusqlite::params![&chrono::Utc::now(), &task_id, &operation, up_to_id])?;
+    Ok(())
+}
+
+// Count a failed attempt at a journal entry
+pub fn fail_calendar_op(
+    conn: &rusqlite::Connection,
+    id: i64,
+    error: &str,
+) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/fail_calendar_op.sql");
+    conn.execute(sql, rusqlite::params![error, id])?;
+    Ok(())
+}
+
+// Unfinished journal entries, oldest first
+pub fn get_pending_calendar_ops(
+    conn: &rusqlite::Connection,
+    max_attempts: i32,
+) -> rusqlite::Result<Vec<crate::structs::calendar_journal::JournalEntry>> {
+    use crate::structs::calendar_journal::JournalEntry;
+    
+    let sql = include_str!("../db/sql/get_pending_calendar_ops.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let entry_iter = stmt.query_map([max_attempts], JournalEntry::from_row)?;
+    
+    entry_iter.collect()
+}
+
+pub fn count_pending_calendar_ops(conn: &rusqlite::Connection, max_attempts: i32) -> rusqlite::Result<i64> {
+    let sql = include_str!("../db/sql/count_pending_calendar_ops.sql");
+    conn.query_row(sql, [max_attempts], |row| row.get(0))
+}
+
+// Log a Google Calendar call; error_kind is None when it succeeded
+pub fn record_calendar_api_call(
+    conn: &rusqlite::Connection,
+    account: &str,
+    operation: &str,
+    error_kind: Option<&str>,
+    message: Option<&str>,
+) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/record_calendar_api_call.sql");
+    conn.execute(sql, rusqlite::params![account, operation, error_kind, message, &chrono::Utc::now()])?;
+    Ok(())
+}
+
+pub fn prune_calendar_api_log(
+    conn: &rusqlite::Connection,
+    before: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<usize> {
+    let sql = include_str!("../db/sql/prune_calendar_api_log.sql");
+    conn.execute(sql, [&before])
+}
+
+pub fn get_calendar_link_counts(
+    conn: &rusqlite::Connection,
+) -> rusqlite::Result<crate::structs::calendar::CalendarLinkCounts> {
+    use crate::structs::calendar::CalendarLinkCounts;
+    
+    let sql = include_str!("../db/sql/get_calendar_link_counts.sql");
+    conn.query_row(sql, [], CalendarLinkCounts::from_row)
+}
+
+pub fn get_calendar_errors_by_kind(
+    conn: &rusqlite::Connection,
+    since: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<Vec<crate::structs::calendar::CalendarErrorCount>> {
+    use crate::structs::calendar::CalendarErrorCount;
+    
+    let sql = include_str!("../db/sql/get_calendar_errors_by_kind.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([&since], CalendarErrorCount::from_row)?;
+    
+    rows.collect()
+}
+
+pub fn get_calendar_account_usage(
+    conn: &rusqlite::Connection,
+    since: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<Vec<crate::structs::calendar::CalendarAccountUsage>> {
+    use crate::structs::calendar::CalendarAccountUsage;
+    
+    let sql = include_str!("../db/sql/get_calendar_account_usage.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([&since], CalendarAccountUsage::from_row)?;
+    
+    rows.collect()
+}
+
+// Every task, oldest first (data export)
+pub fn get_all_tasks(conn: &rusqlite::Connection) -> rusqlite::Result<Vec<crate::structs::task_struct::Task>> {
+    use crate::structs::task_struct::Task;
+    
+    let sql = include_str!("../db/sql/get_all_tasks.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let task_iter = stmt.query_map([], Task::from_row)?;
+    
+    task_iter.collect()
+}
+
+// In no particular order; IDs without a task are left out
+pub fn get_tasks_by_ids(
+    conn: &rusqlite::Connection,
+    ids: &[TaskId],
+) -> rusqlite::Result<Vec<crate::structs::task_struct::Task>> {
+    use crate::structs::task_struct::Task;
+    
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let placeholders = (1..=ids.len()).map(|i| format!("?{}", i)).collect::<Vec<_>>().join(", ");
+    let sql = include_str!("../db/sql/get_tasks_by_ids.sql").replace("IN (?1)", &format!("IN ({})", placeholders));
+    let mut stmt = conn.prepare(&sql)?;
+    let task_iter = stmt.query_map(rusqlite::params_from_iter(ids), Task::from_row)?;
+    
+    task_iter.collect()
+}
+
+pub fn search_tasks(
+    conn: &rusqlite::Connection,
+    pattern: &str,
+    limit: i64,
+) -> rusqlite::Result<Vec<crate::structs::task_struct::Task>> {
+    use crate::structs::task_struct::Task;
+    
+    let sql = include_str!("../db/sql/search_tasks.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let task_iter = stmt.query_map(rusqlite::params![pattern, limit], Task::from_row)?;
+    
+    task_iter.collect()
+}
+
+pub fn get_completed_tasks(
+    conn: &rusqlite::Connection,
+    limit: i64,
+) -> rusqlite::Result<Vec<crate::structs::task_struct::Task>> {
+    use crate::structs::task_struct::Task;
+    
+    let sql = include_str!("../db/sql/get_completed_tasks.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let task_iter = stmt.query_map([limit], Task::from_row)?;
+    
+    task_iter.collect()
+}
+
+pub fn get_task_created_times(conn: &rusqlite::Connection) -> rusqlite::Result<Vec<chrono::DateTime<chrono::Utc>>> {
+    let sql = include_str!("../db/sql/get_task_created_times.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let time_iter = stmt.query_map([], |row| row.get(0))?;
+    
+    time_iter.collect()
+}
+
+// Every task-to-event link (data export)
+pub fn get_calendar_links(conn: &rusqlite::Connection) -> rusqlite::Result<Vec<crate::structs::data_export::CalendarLink>> {
+    use crate::structs::data_export::CalendarLink;
+    
+    let sql = include_str!("../db/sql/get_calendar_links.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let link_iter = stmt.query_map([], CalendarLink::from_row)?;
+    
+    link_iter.collect()
+}
+
+pub fn delete_calendar_link(conn: &rusqlite::Connection, event_id: &str) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/delete_calendar_link.sql");
+    conn.execute(sql, [event_id])?;
+    
+    Ok(())
+}
+
+// Link an imported task to its event unless the event is already linked
+pub fn insert_calendar_link(
+    conn: &rusqlite::Connection,
+    task_id: TaskId,
+    event_id: &str,
+    origin: crate::structs::calendar_event::EventOrigin,
+) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/insert_calendar_link.sql");
+    conn.execute(sql, rusqlite::params![&task_id, event_id, &origin])?;
+    Ok(())
+}
+
+// Delete all tasks with their calendar links and notification records (replace import)
+pub fn clear_all_tasks(conn: &rusqlite::Connection) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/clear_all_tasks.sql");
+    conn.execute_batch(sql)
+}
+
+// Visit tasks created in a range one row at a time instead of collecting them (CSV export)
+pub fn for_each_task_created_between(
+    conn: &rusqlite::Connection,
+    start: chrono::DateTime<chrono::Utc>,
+    end: chrono::DateTime<chrono::Utc>,
+    mut visit: impl FnMut(&crate::structs::task_struct::Task) -> std::io::Result<()>,
+) -> DbResult<usize> {
+    use crate::structs::task_struct::Task;
+    
+    let sql = include_str!("../db/sql/get_tasks_created_between.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let mut rows = stmt.query([&start, &end])?;
+    
+    let mut count = 0;
+    while let Some(row) = rows.next()? {
+        visit(&Task::from_row(row)?)?;
+        count += 1;
+    }
+    
+    Ok(count)
+}
+
+// Get the stored GitHub token, if connected
+pub fn get_github_credentials(
+    conn: &rusqlite::Connection,
+) -> rusqlite::Result<Option<crate::structs::github::GithubCredentials>> {
+    use crate::structs::github::GithubCredentials;
+    
+    let sql = include_str!("../db/sql/get_github_credentials.sql");
+    let creds = match conn.query_row(sql, [], GithubCredentials::from_row) {
+        Ok(creds) => creds,
+        Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    // Connected only while the token is still in the secrets store
+    let token = secrets::get(conn, secrets::GITHUB, "token").map_err(secret_error)?;
+    Ok(token.map(|token| GithubCredentials { token, ..creds }))
+}
+
+pub fn save_github_credentials(
+    conn: &rusqlite::Connection,
+    creds: &crate::structs::github::GithubCredentials,
+) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/save_github_credentials.sql");
+    conn.execute(sql, rusqlite::params![&creds.login, creds.close_issues_on_complete])?;
+    secrets::store(conn, secrets::GITHUB, "token", &creds.token).map_err(secret_error)
+}
+
+pub fn clear_github_credentials(conn: &rusqlite::Connection) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/clear_github_credentials.sql");
+    conn.execute(sql, [])?;
+    secrets::delete(conn, secrets::GITHUB, "token").map_err(secret_error)
+}
+
+pub fn set_github_close_issues(conn: &rusqlite::Connection, enabled: bool) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/set_github_close_issues.sql");
+    let updated = conn.execute(sql, [enabled])?;
+    
+    if updated == 0 {
+        Err(rusqlite::Error::QueryReturnedNoRows)
+    } else {
+        Ok(())
+    }
+}
+
+// Hand a merged task's links to the task it was merged into
+pub fn move_task_external_links(conn: &rusqlite::Connection, from: TaskId, to: TaskId) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/move_task_external_links.sql");
+    conn.execute(sql, rusqlite::params![&from, &to])?;
+    Ok(())
+}
+
+// Task linked to an item in another service
+pub fn get_task_id_by_external_id(
+    conn: &rusqlite::Connection,
+    source: &str,
+    external_id: &str,
+) -> rusqlite::Result<Option<TaskId>> {
+    let sql = include_str!("../db/sql/get_task_id_by_external_id.sql");
+    match conn.query_row(sql, [source, external_id], |row| row.get::<_, TaskId>(0)) {
+        Ok(task_id) => Ok(Some(task_id)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+// Task created earlier with this create_task idempotency key
+pub fn get_task_id_by_idempotency_key(
+    conn: &rusqlite::Connection,
+    key: &str,
+) -> rusqlite::Result<Option<TaskId>> {
+    let sql = include_str!("../db/sql/get_task_id_by_idempotency_key.sql");
+    match conn.query_row(sql, [key], |row| row.get::<_, TaskId>(0)) {
+        Ok(task_id) => Ok(Some(task_id)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn insert_task_idempotency_key(
+    conn: &rusqlite::Connection,
+    key: &str,
+    task_id: TaskId,
+) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/insert_task_idempotency_key.sql");
+    conn.execute(sql, rusqlite::params![key, &task_id])?;
+    
+    Ok(())
+}
+
+pub fn get_external_link_by_task(
+    conn: &rusqlite::Connection,
+    task_id: TaskId,
+    source: &str,
+) -> rusqlite::Result<Option<crate::structs::external_link::ExternalLink>> {
+    use crate::structs::external_link::ExternalLink;
+    
+    let sql = include_str!("../db/sql/get_external_link_by_task.sql");
+    match conn.query_row(sql, rusqlite::params![&task_id, source], ExternalLink::from_row) {
+        Ok(link) => Ok(Some(link)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn insert_external_link(
+    conn: &rusqlite::Connection,
+    task_id: TaskId,
+    source: &str,
+    external_id: &str,
+    url: Option<&str>,
+) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/insert_external_link.sql");
+    conn.execute(sql, rusqlite::params![&task_id, source, external_id, url])?;
+    Ok(())
+}
+
+// Activity counts per day/week/month bucket; buckets without activity are absent
+pub fn get_productivity_stats(
+    conn: &rusqlite::Connection,
+    start: chrono::DateTime<chrono::Utc>,
+    end: chrono::DateTime<chrono::Utc>,
+    granularity: crate::structs::stats::Granularity,
+    offset: chrono::FixedOffset,
+) -> rusqlite::Result<Vec<crate::structs::stats::ProductivityRow>> {
+    use crate::structs::stats::ProductivityRow;
+    
+    let sql = include_str!("../db/sql/get_productivity_stats.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map(
+        rusqlite::params![&start, &end, &granularity, &chrono::Utc::now(), offset_modifier(offset)],
+        ProductivityRow::from_row,
+    )?;
+    
+    rows.collect()
+}
+
+// Same rows as get_productivity_stats, read from day_summaries; only valid when `offset` is the settings offset
+pub fn get_productivity_from_summaries(
+    conn: &rusqlite::Connection,
+    start: chrono::DateTime<chrono::Utc>,
+    end: chrono::DateTime<chrono::Utc>,
+    first_day: chrono::NaiveDate,
+    last_day: chrono::NaiveDate,
+    granularity: crate::structs::stats::Granularity,
+    offset: chrono::FixedOffset,
+) -> rusqlite::Result<Vec<crate::structs::stats::ProductivityRow>> {
+    use crate::structs::stats::ProductivityRow;
+    
+    let sql = include_str!("../db/sql/get_productivity_from_summaries.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map(
+        rusqlite::params![&start, &end, &granularity, &chrono::Utc::now(), offset_modifier(offset), &first_day, &last_day],
+        ProductivityRow::from_row,
+    )?;
+    
+    rows.collect()
+}
+
+// Precomputed counts for the days from `first_day` to `last_day`, in the settings offset; days with no tasks are absent
+pub fn get_day_summaries(
+    conn: &rusqlite::Connection,
+    first_day: chrono::NaiveDate,
+    last_day: chrono::NaiveDate,
+) -> rusqlite::Result<Vec<crate::structs::stats::DaySummary>> {
+    use crate::structs::stats::DaySummary;
+    
+    let sql = include_str!("../db/sql/get_day_summaries.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([&first_day, &last_day], DaySummary::from_row)?;
+    
+    rows.collect()
+}
+
+// Recount day_summaries from tasks; needed when the settings offset moves the day boundaries
+pub fn rebuild_day_summaries(conn: &rusqlite::Connection) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/rebuild_day_summaries.sql");
+    conn.execute_batch(sql)
+}
+
+// Tracked minutes per completed task, measured from start to completion
+pub fn get_task_time(
+    conn: &rusqlite::Connection,
+    start: chrono::DateTime<chrono::Utc>,
+    end: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<Vec<crate::structs::stats::TaskTimeRow>> {
+    use crate::structs::stats::TaskTimeRow;
+    
+    let sql = include_str!("../db/sql/get_task_time.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([&start, &end], TaskTimeRow::from_row)?;
+    
+    rows.collect()
+}
+
+pub fn get_pause_reasons(
+    conn: &rusqlite::Connection,
+    start: chrono::DateTime<chrono::Utc>,
+    end: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<Vec<crate::structs::stats::PauseReasonRow>> {
+    use crate::structs::stats::PauseReasonRow;
+    
+    let sql = include_str!("../db/sql/get_pause_reasons.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([&start, &end], PauseReasonRow::from_row)?;
+    
+    rows.collect()
+}
+
+// Local days (YYYY-MM-DD) with at least one completion, in order
+pub fn get_completion_days(
+    conn: &rusqlite::Connection,
+    start: chrono::DateTime<chrono::Utc>,
+    end: chrono::DateTime<chrono::Utc>,
+    offset: chrono::FixedOffset,
+) -> rusqlite::Result<Vec<String>> {
+    let sql = include_str!("../db/sql/get_completion_days.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map(rusqlite::params![&start, &end, offset_modifier(offset)], |row| row.get(0))?;
+    
+    rows.collect()
+}
+
+// SQLite date modifier that moves UTC timestamps to the local day at `offset`
+fn offset_modifier(offset: chrono::FixedOffset) -> String {
+    format!("{:+} minutes", offset.local_minus_utc() / 60)
+}
+
+pub fn get_cycle_times(
+    conn: &rusqlite::Connection,
+    start: chrono::DateTime<chrono::Utc>,
+    end: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<Vec<crate::structs::stats::CycleTimeRow>> {
+    use crate::structs::stats::CycleTimeRow;
+    
+    let sql = include_str!("../db/sql/get_cycle_times.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([&start, &end], CycleTimeRow::from_row)?;
+    
+    rows.collect()
+}
+
+// Tasks whose deadline moved most often, most-moved first
+pub fn get_most_postponed(
+    conn: &rusqlite::Connection,
+    limit: i64,
+) -> rusqlite::Result<Vec<crate::structs::stats::PostponedTask>> {
+    use crate::structs::stats::PostponedTask;
+    
+    let sql = include_str!("../db/sql/get_most_postponed.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([limit], PostponedTask::from_row)?;
+    
+    rows.collect()
+}
+
+// Deadline moves of one task so far
+pub fn get_task_postponement(
+    conn: &rusqlite::Connection,
+    task_id: TaskId,
+) -> rusqlite::Result<crate::structs::stats::PostponedTask> {
+    use crate::structs::stats::PostponedTask;
+    
+    let sql = include_str!("../db/sql/get_task_postponement.sql");
+    conn.query_row(sql, [&task_id], PostponedTask::from_row)
+}
+
+pub fn get_postponement_totals(
+    conn: &rusqlite::Connection,
+) -> rusqlite::Result<crate::structs::stats::PostponementTotals> {
+    use crate::structs::stats::PostponementTotals;
+    
+    let sql = include_str!("../db/sql/get_postponement_totals.sql");
+    conn.query_row(sql, [], PostponementTotals::from_row)
+}
+
+pub fn get_goals(
+    conn: &rusqlite::Connection,
+) -> rusqlite::Result<Vec<crate::structs::goal::Goal>> {
+    use crate::structs::goal::Goal;
+    
+    let sql = include_str!("../db/sql/get_goals.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([], Goal::from_row)?;
+    
+    rows.collect()
+}
+
+pub fn get_goal_by_id(
+    conn: &rusqlite::Connection,
+    goal_id: &str,
+) -> rusqlite::Result<crate::structs::goal::Goal> {
+    use crate::structs::goal::Goal;
+    
+    let uuid = Uuid::parse_str(goal_id)
+        .map_err(|e| rusqlite::Error::InvalidParameterName(format!("Invalid UUID: {}", e)))?;
+    
+    let sql = include_str!("../db/sql/get_goal_by_id.sql");
+    conn.query_row(sql, [&uuid], Goal::from_row)
+}
+
+pub fn update_goal<T: Updatable>(
+    conn: &rusqlite::Connection,
+    goal_id: &str,
+    update_data: &T,
+) -> rusqlite::Result<crate::structs::goal::Goal> {
+    let uuid = Uuid::parse_str(goal_id)
+        .map_err(|e| rusqlite::Error::InvalidParameterName(format!("Invalid UUID: {}", e)))?;
+    
+    let cols_vals = update_data.update_columns_values();
+    if cols_vals.is_empty() {
+        return get_goal_by_id(conn, goal_id);
+    }
+    
+    let set_clauses: Vec<String> = cols_vals.iter()
+        .map(|(col, _)| format!("{} = ?", col))
+        .collect();
+    let mut params: Vec<&dyn rusqlite::ToSql> = cols_vals.iter()
+        .map(|(_, v)| *v)
+        .collect();
+    params.push(&uuid);
+    
+    let sql = format!(
+        "UPDATE {} SET {} WHERE id = ?",
+        T::table_name(),
+        set_clauses.join(", ")
+    );
+    
+    let rows_affected = conn.execute(&sql, &params[..]).map_err(|e| {
+        error!("Failed to update goal with ID {}: {}", goal_id, e);
+        debug!("SQL: {}", sql);
+        e
+    })?;
+    
+    if rows_affected == 0 {
+        Err(rusqlite::Error::QueryReturnedNoRows)
+    } else {
+        get_goal_by_id(conn, goal_id)
+    }
+}
+
+pub fn delete_goal_by_id(
+    conn: &rusqlite::Connection,
+    goal_id: &str,
+) -> rusqlite::Result<usize> {
+    let uuid = Uuid::parse_str(goal_id)
+        .map_err(|e| rusqlite::Error::InvalidParameterName(format!("Invalid UUID: {}", e)))?;
+    
+    let sql = include_str!("../db/sql/delete_goal_by_id.sql");
+    conn.execute(sql, [&uuid])
+}
+
+// Remember the week a goal was met so it is announced only once
+pub fn set_goal_met(
+    conn: &rusqlite::Connection,
+    goal_id: &Uuid,
+    week: &str,
+) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/set_goal_met.sql");
+    conn.execute(sql, rusqlite::params![goal_id, week])?;
+    Ok(())
+}
+
+pub fn get_week_totals(
+    conn: &rusqlite::Connection,
+    start: chrono::DateTime<chrono::Utc>,
+    end: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<crate::structs::goal::WeekTotals> {
+    use crate::structs::goal::WeekTotals;
+    
+    let sql = include_str!("../db/sql/get_week_totals.sql");
+    conn.query_row(sql, [&start, &end], WeekTotals::from_row)
+}
+
+// Add to a day's usage count
+pub fn add_usage_metric(
+    conn: &rusqlite::Connection,
+    day: &str,
+    kind: &str,
+    name: &str,
+    count: i64,
+) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/add_usage_metric.sql");
+    conn.execute(sql, rusqlite::params![day, kind, name, count])?;
+    Ok(())
+}
+
+// Usage totals per kind and name since `since_day` (YYYY-MM-DD), most used first
+pub fn get_usage_metric_totals(
+    conn: &rusqlite::Connection,
+    since_day: &str,
+) -> rusqlite::Result<Vec<crate::structs::metrics::UsageMetricTotal>> {
+    use crate::structs::metrics::UsageMetricTotal;
+    
+    let sql = include_str!("../db/sql/get_usage_metric_totals.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([since_day], UsageMetricTotal::from_row)?;
+    
+    rows.collect()
+}
+
+pub fn get_usage_metrics(
+    conn: &rusqlite::Connection,
+) -> rusqlite::Result<Vec<crate::structs::metrics::UsageMetricRow>> {
+    use crate::structs::metrics::UsageMetricRow;
+    
+    let sql = include_str!("../db/sql/get_usage_metrics.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([], UsageMetricRow::from_row)?;
+    
+    rows.collect()
+}
+
+// Write another device's copy of a task over the local row, keeping its calendar links
+pub fn overwrite_task(
+    conn: &rusqlite::Connection,
+    task: &crate::structs::task_struct::Task,
+) -> rusqlite::Result<usize> {
+    let sql = include_str!("../db/sql/overwrite_task.sql");
+    conn.execute(sql, rusqlite::params![
+        &task.id,
+        &task.title,
+        &task.notes,
+        &task.status,
+        &task.created_at,
+        &task.updated_at,
+        &task.deadline,
+        &task.has_calendar_integration,
+        &task.calendar_email,
+        &task.reminder_frequency,
+        &task.started_at,
+        &task.paused_at,
+        &task.completed_at,
+        &task.notifications_enabled,
+        &task.estimate_minutes,
+        &task.project_id,
+        &task.assignee,
+        &task.context,
+        &task.energy,
+        &task.calendar_privacy,
+    ])
+}
+
+// Last change applied from a sync device, if any
+pub fn get_sync_cursor(conn: &rusqlite::Connection, device_id: &str) -> rusqlite::Result<Option<i64>> {
+    let sql = include_str!("../db/sql/get_sync_cursor.sql");
+    match conn.query_row(sql, [device_id], |row| row.get(0)) {
+        Ok(value) => Ok(Some(value)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn set_sync_cursor(conn: &rusqlite::Connection, device_id: &str, last_seq: i64) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/set_sync_cursor.sql");
+    conn.execute(sql, rusqlite::params![device_id, last_seq])?;
+    Ok(())
+}
+
+pub fn record_sync_tombstone(
+    conn: &rusqlite::Connection,
+    task_id: &Uuid,
+    deleted_at: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/record_sync_tombstone.sql");
+    conn.execute(sql, rusqlite::params![task_id, &deleted_at])?;
+    Ok(())
+}
+
+// When the task was deleted on this device, if it was
+pub fn get_sync_tombstone(
+    conn: &rusqlite::Connection,
+    task_id: &Uuid,
+) -> rusqlite::Result<Option<chrono::DateTime<chrono::Utc>>> {
+    let sql = include_str!("../db/sql/get_sync_tombstone.sql");
+    match conn.query_row(sql, [task_id], |row| row.get(0)) {
+        Ok(value) => Ok(Some(value)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+// Every deleted task this device knows of, for a full snapshot
+pub fn get_sync_tombstones(
+    conn: &rusqlite::Connection,
+) -> rusqlite::Result<Vec<(Uuid, chrono::DateTime<chrono::Utc>)>> {
+    let sql = include_str!("../db/sql/get_sync_tombstones.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    
+    rows.collect()
+}
+
+pub fn set_sync_merged_at(conn: &rusqlite::Connection, merged_at: chrono::DateTime<chrono::Utc>) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/set_sync_merged_at.sql");
+    conn.execute(sql, [&merged_at])?;
+    Ok(())
+}
+
+pub fn insert_sync_conflict(
+    conn: &rusqlite::Connection,
+    local: &crate::structs::task_struct::Task,
+    remote: &crate::structs::task_struct::Task,
+    remote_device: &str,
+    winner: crate::structs::sync::ConflictWinner,
+) -> rusqlite::Result<()> {
+    let to_json = |task| serde_json::to_string(task)
+        .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)));
+    let sql = include_str!("../db/sql/insert_sync_conflict.sql");
+    conn.execute(sql, rusqlite::params![
+        &local.id,
+        &local.title,
+        remote_device,
+        &local.updated_at,
+        &remote.updated_at,
+        winner.as_str(),
+        &chrono::Utc::now(),
+        to_json(local)?,
+        to_json(remote)?,
+    ])?;
+    Ok(())
+}
+
+pub fn get_sync_conflict(
+    conn: &rusqlite::Connection,
+    id: i64,
+) -> rusqlite::Result<Option<crate::structs::sync::SyncConflict>> {
+    use crate::structs::sync::SyncConflict;
+    
+    let sql = include_str!("../db/sql/get_sync_conflict.sql");
+    match conn.query_row(sql, [id], SyncConflict::from_row) {
+        Ok(conflict) => Ok(Some(conflict)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+// `kept` is "local", "remote" or "superseded"
+pub fn resolve_sync_conflict(
+    conn: &rusqlite::Connection,
+    id: i64,
+    kept: &str,
+    resolved_at: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/resolve_sync_conflict.sql");
+    conn.execute(sql, rusqlite::params![id, &resolved_at, kept])?;
+    Ok(())
+}
+
+// Close a task's open conflicts found before `before`; a later edit replaced both versions
+pub fn supersede_sync_conflicts(
+    conn: &rusqlite::Connection,
+    task_id: &Uuid,
+    before: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<usize> {
+    let sql = include_str!("../db/sql/supersede_sync_conflicts.sql");
+    conn.execute(sql, rusqlite::params![task_id, &before, &chrono::Utc::now()])
+}
+
+// Open conflicts first, then resolved ones; newest first within each
+pub fn get_sync_conflicts(
+    conn: &rusqlite::Connection,
+) -> rusqlite::Result<Vec<crate::structs::sync::SyncConflict>> {
+    use crate::structs::sync::SyncConflict;
+    
+    let sql = include_str!("../db/sql/get_sync_conflicts.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([], SyncConflict::from_row)?;
+    
+    rows.collect()
+}
+
+// Only resolved ones; open conflicts stay until the user picks a version
+pub fn clear_sync_conflicts(conn: &rusqlite::Connection) -> rusqlite::Result<usize> {
+    let sql = include_str!("../db/sql/clear_sync_conflicts.sql");
+    conn.execute(sql, [])
+}
+
+// Other devices seen by sync, with the name each registered
+pub fn get_sync_devices(
+    conn: &rusqlite::Connection,
+) -> rusqlite::Result<Vec<crate::structs::sync::SyncDevice>> {
+    use crate::structs::sync::SyncDevice;
+    
+    let sql = include_str!("../db/sql/get_sync_devices.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([], SyncDevice::from_row)?;
+    
+    rows.collect()
+}
+
+pub fn set_sync_device_name(conn: &rusqlite::Connection, device_id: &str, name: &str) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/set_sync_device_name.sql");
+    conn.execute(sql, rusqlite::params![device_id, name])?;
+    Ok(())
+}
+
+// Queue a serialized change record for the next push
+pub fn add_sync_outbox(conn: &rusqlite::Connection, seq: i64, record: &str) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/add_sync_outbox.sql");
+    conn.execute(sql, rusqlite::params![seq, record])?;
+    Ok(())
+}
+
+// Queued change records, oldest first
+pub fn get_sync_outbox(conn: &rusqlite::Connection) -> rusqlite::Result<Vec<(i64, String)>> {
+    let sql = include_str!("../db/sql/get_sync_outbox.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    
+    rows.collect()
+}
+
+// Drop records up to `last_seq` once they are pushed
+pub fn clear_sync_outbox(conn: &rusqlite::Connection, last_seq: i64) -> rusqlite::Result<usize> {
+    let sql = include_str!("../db/sql/clear_sync_outbox.sql");
+    conn.execute(sql, [last_seq])
+}
+
+// Number of queued records and the highest seq (0 when empty)
+pub fn get_sync_outbox_stats(conn: &rusqlite::Connection) -> rusqlite::Result<(i64, i64)> {
+    let sql = include_str!("../db/sql/get_sync_outbox_stats.sql");
+    conn.query_row(sql, [], |row| Ok((row.get(0)?, row.get(1)?)))
+}
+
+pub fn get_sync_last_seq(conn: &rusqlite::Connection) -> rusqlite::Result<i64> {
+    let sql = include_str!("../db/sql/get_sync_last_seq.sql");
+    conn.query_row(sql, [], |row| row.get(0))
+}
+
+pub fn set_sync_last_seq(conn: &rusqlite::Connection, seq: i64) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/set_sync_last_seq.sql");
+    conn.execute(sql, [seq])?;
+    Ok(())
+}
+
+// Pair with a device, or replace the secret of one paired before
+pub fn save_lan_peer(
+    conn: &rusqlite::Connection,
+    device_id: &str,
+    name: &str,
+    secret: &str,
+    paired_at: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/save_lan_peer.sql");
+    conn.execute(sql, rusqlite::params![device_id, name, secret, &paired_at])?;
+    Ok(())
+}
+
+pub fn get_lan_peers(conn: &rusqlite::Connection) -> rusqlite::Result<Vec<crate::structs::sync::LanPeer>> {
+    use crate::structs::sync::LanPeer;
+    
+    let sql = include_str!("../db/sql/get_lan_peers.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([], LanPeer::from_row)?;
+    
+    rows.collect()
+}
+
+pub fn get_lan_peer(
+    conn: &rusqlite::Connection,
+    device_id: &str,
+) -> rusqlite::Result<Option<crate::structs::sync::LanPeer>> {
+    use crate::structs::sync::LanPeer;
+    
+    let sql = include_str!("../db/sql/get_lan_peer.sql");
+    match conn.query_row(sql, [device_id], LanPeer::from_row) {
+        Ok(peer) => Ok(Some(peer)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn delete_lan_peer(conn: &rusqlite::Connection, device_id: &str) -> rusqlite::Result<usize> {
+    let sql = include_str!("../db/sql/delete_lan_peer.sql");
+    conn.execute(sql, [device_id])
+}
+
+pub fn set_lan_peer_synced(
+    conn: &rusqlite::Connection,
+    device_id: &str,
+    synced_at: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/set_lan_peer_synced.sql");
+    conn.execute(sql, rusqlite::params![device_id, &synced_at])?;
+    Ok(())
+}
+
+// History of one row, oldest first
+pub fn get_row_changelog(
+    conn: &rusqlite::Connection,
+    table_name: &str,
+    row_id: &str,
+) -> rusqlite::Result<Vec<crate::structs::changelog::ChangelogEntry>> {
+    use crate::structs::changelog::ChangelogEntry;
+    
+    let sql = include_str!("../db/sql/get_row_changelog.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([table_name, row_id], ChangelogEntry::from_row)?;
+    
+    rows.collect()
+}
+
+// Entries older than `before`, oldest first
+pub fn get_changelog_before(
+    conn: &rusqlite::Connection,
+    before: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<Vec<crate::structs::changelog::ChangelogEntry>> {
+    use crate::structs::changelog::ChangelogEntry;
+    
+    let sql = include_str!("../db/sql/get_changelog_before.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([&before], ChangelogEntry::from_row)?;
+    
+    rows.collect()
+}
+
+// Write an entry back under its own seq, for compaction
+pub fn insert_changelog_entry(
+    conn: &rusqlite::Connection,
+    entry: &crate::structs::changelog::ChangelogEntry,
+) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/insert_changelog_entry.sql");
+    conn.execute(sql, rusqlite::params![
+        entry.seq,
+        entry.table_name,
+        entry.row_id,
+        entry.op,
+        entry.old_values,
+        entry.new_values,
+        entry.clock,
+        entry.device_id,
+        &entry.changed_at,
+    ])?;
+    Ok(())
+}
+
+pub fn delete_changelog_entry(conn: &rusqlite::Connection, seq: i64) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/delete_changelog_entry.sql");
+    conn.execute(sql, [seq])?;
+    Ok(())
+}
+
+pub fn prune_changelog(conn: &rusqlite::Connection, before: chrono::DateTime<chrono::Utc>) -> rusqlite::Result<usize> {
+    let sql = include_str!("../db/sql/prune_changelog.sql");
+    conn.execute(sql, [&before])
+}
+
+// Credit changes written from here on to another device, until cleared
+pub fn set_changelog_origin(conn: &rusqlite::Connection, device_id: &str) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/set_changelog_origin.sql");
+    conn.execute(sql, [device_id])?;
+    Ok(())
+}
+
+pub fn clear_changelog_origin(conn: &rusqlite::Connection) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/clear_changelog_origin.sql");
+    conn.execute(sql, [])?;
+    Ok(())
+}
+
+pub fn insert_webhook(
+    conn: &rusqlite::Connection,
+    url: &str,
+    secret: &str,
+    events: &crate::structs::webhook::WebhookEvents,
+) -> rusqlite::Result<i64> {
+    let sql = include_str!("../db/sql/insert_webhook.sql");
+    conn.execute(sql, rusqlite::params![url, events, &chrono::Utc::now()])?;
+    let id = conn.last_insert_rowid();
+    secrets::store(conn, secrets::WEBHOOK, &id.to_string(), secret).map_err(secret_error)?;
+    Ok(id)
+}
+
+pub fn get_webhooks(conn: &rusqlite::Connection) -> rusqlite::Result<Vec<crate::structs::webhook::Webhook>> {
+    use crate::structs::webhook::Webhook;
+    
+    let sql = include_str!("../db/sql/get_webhooks.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([], Webhook::from_row)?;
+    
+    rows.map(|webhook| webhook.and_then(|webhook| with_webhook_secret(conn, webhook))).collect()
+}
+
+pub fn get_webhook_by_id(conn: &rusqlite::Connection, id: i64) -> rusqlite::Result<crate::structs::webhook::Webhook> {
+    use crate::structs::webhook::Webhook;
+    
+    let sql = include_str!("../db/sql/get_webhook_by_id.sql");
+    let webhook = conn.query_row(sql, [id], Webhook::from_row)?;
+    with_webhook_secret(conn, webhook)
+}
+
+// A webhook whose secret is missing from the store sends deliveries signed with an empty key
+fn with_webhook_secret(
+    conn: &rusqlite::Connection,
+    webhook: crate::structs::webhook::Webhook,
+) -> rusqlite::Result<crate::structs::webhook::Webhook> {
+    let secret = secrets::get(conn, secrets::WEBHOOK, &webhook.id.to_string()).map_err(secret_error)?;
+    Ok(crate::structs::webhook::Webhook { secret: secret.unwrap_or_default(), ..webhook })
+}
+
+// Remove a webhook with its delivery log
+pub fn delete_webhook(conn: &rusqlite::Connection, id: i64) -> rusqlite::Result<usize> {
+    let deleted = conn.execute(include_str!("../db/sql/delete_webhook.sql"), [id])?;
+    conn.execute(include_str!("../db/sql/delete_webhook_deliveries.sql"), [id])?;
+    secrets::delete(conn, secrets::WEBHOOK, &id.to_string()).map_err(secret_error)?;
+    Ok(deleted)
+}
+
+// Queue an event for a webhook, due now
+pub fn insert_webhook_delivery(
+    conn: &rusqlite::Connection,
+    webhook_id: i64,
+    event: crate::structs::webhook::WebhookEvent,
+    payload: &str,
+) -> rusqlite::Result<i64> {
+    let sql = include_str!("../db/sql/insert_webhook_delivery.sql");
+    conn.execute(sql, rusqlite::params![webhook_id, &event, payload, &chrono::Utc::now()])?;
+    Ok(conn.last_insert_rowid())
+}
+
+// Pending deliveries of enabled webhooks whose next attempt is due, oldest first
+pub fn get_due_webhook_deliveries(
+    conn: &rusqlite::Connection,
+    now: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<Vec<crate::structs::webhook::WebhookDelivery>> {
+    use crate::structs::webhook::WebhookDelivery;
+    
+    let sql = include_str!("../db/sql/get_due_webhook_deliveries.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([&now], WebhookDelivery::from_row)?;
+    
+    rows.collect()
+}
+
+// Newest first; every webhook when webhook_id is None
+pub fn get_webhook_deliveries(
+    conn: &rusqlite::Connection,
+    webhook_id: Option<i64>,
+    limit: i64,
+) -> rusqlite::Result<Vec<crate::structs::webhook::WebhookDelivery>> {
+    use crate::structs::webhook::WebhookDelivery;
+    
+    let sql = include_str!("../db/sql/get_webhook_deliveries.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map(rusqlite::params![webhook_id, limit], WebhookDelivery::from_row)?;
+    
+    rows.collect()
+}
+
+// Store the outcome of one attempt; pending rows are retried at next_attempt_at
+pub fn record_webhook_attempt(
+    conn: &rusqlite::Connection,
+    id: i64,
+    status: crate::structs::webhook::DeliveryStatus,
+    response_status: Option<u16>,
+    error: Option<&str>,
+    next_attempt_at: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<()> {
+    use crate::structs::webhook::DeliveryStatus;
+    
+    let delivered_at = (status == DeliveryStatus::Delivered).then(chrono::Utc::now);
+    let sql = include_str!("../db/sql/record_webhook_attempt.sql");
+    conn.execute(sql, rusqlite::params![&status, response_status, error, &next_attempt_at, &delivered_at, id])?;
+    Ok(())
+}
+
+// Drop finished deliveries older than `before`; pending ones are kept
+pub fn prune_webhook_deliveries(
+    conn: &rusqlite::Connection,
+    before: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<usize> {
+    let sql = include_str!("../db/sql/prune_webhook_deliveries.sql");
+    conn.execute(sql, [&before])
+}
+
+// Every rule, oldest first; the order rules run in
+pub fn get_rules(conn: &rusqlite::Connection) -> rusqlite::Result<Vec<crate::structs::rule::Rule>> {
+    use crate::structs::rule::Rule;
+    
+    let sql = include_str!("../db/sql/get_rules.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([], Rule::from_row)?;
+    
+    rows.collect()
+}
+
+pub fn get_rule_by_id(conn: &rusqlite::Connection, rule_id: &Uuid) -> rusqlite::Result<crate::structs::rule::Rule> {
+    use crate::structs::rule::Rule;
+    
+    let sql = include_str!("../db/sql/get_rule_by_id.sql");
+    conn.query_row(sql, [rule_id], Rule::from_row)
+}
+
+pub fn update_rule<T: Updatable>(
+    conn: &rusqlite::Connection,
+    rule_id: &Uuid,
+    update_data: &T,
+) -> rusqlite::Result<crate::structs::rule::Rule> {
+    let cols_vals = update_data.update_columns_values();
+    if cols_vals.is_empty() {
+        return get_rule_by_id(conn, rule_id);
+    }
+    
+    let set_clauses: Vec<String> = cols_vals.iter()
+        .map(|(col, _)| format!("{} = ?", col))
+        .collect();
+    let mut params: Vec<&dyn rusqlite::ToSql> = cols_vals.iter()
+        .map(|(_, v)| *v)
+        .collect();
+    params.push(rule_id);
+    
+    let sql = format!(
+        "UPDATE {} SET {} WHERE id = ?",
+        T::table_name(),
+        set_clauses.join(", ")
+    );
+    
+    let rows_affected = conn.execute(&sql, &params[..]).map_err(|e| {
+        error!("Failed to update rule with ID {}: {}", rule_id, e);
+        debug!("SQL: {}", sql);
+        e
+    })?;
+    
+    if rows_affected == 0 {
+        Err(rusqlite::Error::QueryReturnedNoRows)
+    } else {
+        get_rule_by_id(conn, rule_id)
+    }
+}
+
+pub fn delete_rule_by_id(conn: &rusqlite::Connection, rule_id: &Uuid) -> rusqlite::Result<usize> {
+    let sql = include_str!("../db/sql/delete_rule_by_id.sql");
+    conn.execute(sql, [rule_id])
+}
+
+// Record a deadline-passed run; false when the rule already fired for this task and deadline
+pub fn claim_rule_run(
+    conn: &rusqlite::Connection,
+    rule_id: &Uuid,
+    task_id: TaskId,
+    deadline: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<bool> {
+    let sql = include_str!("../db/sql/claim_rule_run.sql");
+    let inserted = conn.execute(sql, rusqlite::params![rule_id, &task_id, &deadline, &chrono::Utc::now()])?;
+    Ok(inserted == 1)
+}
+
+pub fn insert_inbox_item(
+    conn: &rusqlite::Connection,
+    text: &str,
+    captured_at: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<crate::structs::inbox::InboxItem> {
+    let sql = include_str!("../db/sql/insert_inbox_item.sql");
+    conn.execute(sql, rusqlite::params![text, &captured_at])?;
+    
+    Ok(crate::structs::inbox::InboxItem {
+        id: conn.last_insert_rowid(),
+        text: text.to_string(),
+        captured_at,
+    })
+}
+
+// Oldest capture first, the order they get triaged in
+pub fn get_inbox_items(conn: &rusqlite::Connection) -> rusqlite::Result<Vec<crate::structs::inbox::InboxItem>> {
+    use crate::structs::inbox::InboxItem;
+    
+    let sql = include_str!("../db/sql/get_inbox_items.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([], InboxItem::from_row)?;
+    
+    rows.collect()
+}
+
+pub fn get_inbox_item(
+    conn: &rusqlite::Connection,
+    id: i64,
+) -> rusqlite::Result<Option<crate::structs::inbox::InboxItem>> {
+    use crate::structs::inbox::InboxItem;
+    
+    let sql = include_str!("../db/sql/get_inbox_item.sql");
+    match conn.query_row(sql, [id], InboxItem::from_row) {
+        Ok(item) => Ok(Some(item)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn delete_inbox_item(conn: &rusqlite::Connection, id: i64) -> rusqlite::Result<usize> {
+    let sql = include_str!("../db/sql/delete_inbox_item.sql");
+    conn.execute(sql, [id])
+}
+
+// Returns whether the holiday was new
+pub fn insert_holiday(
+    conn: &rusqlite::Connection,
+    date: chrono::NaiveDate,
+    name: &str,
+    imported_at: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<bool> {
+    let sql = include_str!("../db/sql/insert_holiday.sql");
+    conn.execute(sql, rusqlite::params![date, name, imported_at])
+        .map(|inserted| inserted > 0)
+}
+
+// Imported holidays from `from` to `to`, both included
+pub fn get_holidays_between(
+    conn: &rusqlite::Connection,
+    from: chrono::NaiveDate,
+    to: chrono::NaiveDate,
+) -> rusqlite::Result<Vec<crate::structs::holiday::Holiday>> {
+    use crate::structs::holiday::Holiday;
+    
+    let sql = include_str!("../db/sql/get_holidays_between.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map(rusqlite::params![from, to], Holiday::from_row)?;
+    
+    rows.collect()
+}
+
+pub fn delete_holidays(conn: &rusqlite::Connection) -> rusqlite::Result<usize> {
+    let sql = include_str!("../db/sql/delete_holidays.sql");
+    conn.execute(sql, [])
+}
+
+pub fn get_app_lock(conn: &rusqlite::Connection) -> rusqlite::Result<Option<crate::structs::app_lock::AppLock>> {
+    use crate::structs::app_lock::AppLock;
+    
+    let sql = include_str!("../db/sql/get_app_lock.sql");
+    match conn.query_row(sql, [], AppLock::from_row) {
+        Ok(lock) => Ok(Some(lock)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn save_app_lock(conn: &rusqlite::Connection, lock: &crate::structs::app_lock::AppLock) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/save_app_lock.sql");
+    conn.execute(sql, rusqlite::params![&lock.pin_hash, &lock.key_salt, lock.idle_minutes, &lock.created_at])?;
+    Ok(())
+}
+
+pub fn delete_app_lock(conn: &rusqlite::Connection) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/delete_app_lock.sql");
+    conn.execute(sql, [])?;
+    Ok(())
+}
+
+// Seal every sensitive value left in plain text with the current key
+pub fn seal_columns(conn: &rusqlite::Connection) -> rusqlite::Result<()> {
+    rewrite_sealed_columns(conn, include_str!("../db/sql/seal_columns.sql"))
+}
+
+// Store every sealed value in plain text again; needs the key that sealed them
+pub fn open_sealed_columns(conn: &rusqlite::Connection) -> rusqlite::Result<()> {
+    rewrite_sealed_columns(conn, include_str!("../db/sql/open_sealed_columns.sql"))
+}
+
+// Sealing changes how values are stored, not the values, so it's kept out of the changelog
+fn rewrite_sealed_columns(conn: &rusqlite::Connection, sql: &str) -> rusqlite::Result<()> {
+    let last_seq: i64 = conn.query_row(include_str!("../db/sql/get_changelog_last_seq.sql"), [], |row| row.get(0))?;
+    conn.execute_batch(sql)?;
+    conn.execute(include_str!("../db/sql/delete_changelog_after.sql"), [last_seq])?;
+    Ok(())
+}
+
+// Run one user-written SELECT, keeping at most `max_rows` rows; gives up once `timeout` has passed
+pub fn run_readonly_query(
+    conn: &rusqlite::Connection,
+    sql: &str,
+    max_rows: usize,
+    timeout: Duration,
+) -> rusqlite::Result<crate::structs::query::QueryResult> {
+    let started = std::time::Instant::now();
+    conn.progress_handler(1000, Some(move || started.elapsed() > timeout))?;
+    
+    let mut stmt = conn.prepare(sql)?;
+    if !stmt.readonly() {
+        return Err(rusqlite::Error::InvalidQuery);
+    }
+    let columns: Vec<String> = stmt.column_names().into_iter().map(String::from).collect();
+    
+    let mut rows = stmt.query([])?;
+    let mut values = Vec::new();
+    let mut truncated = false;
+    while let Some(row) = rows.next()? {
+        if values.len() == max_rows {
+            truncated = true;
+            break;
+        }
+        let row_values = (0..columns.len())
+            .map(|index| row.get_ref(index).map(query_value))
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        values.push(row_values);
+    }
+    
+    Ok(crate::structs::query::QueryResult { columns, rows: values, truncated })
+}
+
+// JSON for one result cell; 16-byte blobs are the UUID columns (task IDs) and show as UUIDs
+fn query_value(value: rusqlite::types::ValueRef<'_>) -> serde_json::Value {
+    use rusqlite::types::ValueRef;
+    
+    match value {
+        ValueRef::Null => serde_json::Value::Null,
+        ValueRef::Integer(i) => i.into(),
+        ValueRef::Real(f) => serde_json::Number::from_f64(f).map_or(serde_json::Value::Null, serde_json::Value::Number),
+        ValueRef::Text(text) => String::from_utf8_lossy(text).into_owned().into(),
+        ValueRef::Blob(bytes) => match Uuid::from_slice(bytes) {
+            Ok(uuid) => uuid.to_string().into(),
+            Err(_) => bytes.iter().map(|byte| format!("{:02x}", byte)).collect::<String>().into(),
+        },
+    }
+}
+
+pub fn get_projects(conn: &rusqlite::Connection) -> rusqlite::Result<Vec<crate::structs::project::Project>> {
+    use crate::structs::project::Project;
+    
+    let sql = include_str!("../db/sql/get_projects.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([], Project::from_row)?;
+    
+    rows.collect()
+}
+
+// Projects with a share location, for the shared project sync
+pub fn get_shared_projects(conn: &rusqlite::Connection) -> rusqlite::Result<Vec<crate::structs::project::Project>> {
+    use crate::structs::project::Project;
+    
+    let sql = include_str!("../db/sql/get_shared_projects.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([], Project::from_row)?;
+    
+    rows.collect()
+}
+
+pub fn get_project_by_id(conn: &rusqlite::Connection, id: &Uuid) -> rusqlite::Result<Option<crate::structs::project::Project>> {
+    use crate::structs::project::Project;
+    
+    let sql = include_str!("../db/sql/get_project_by_id.sql");
+    match conn.query_row(sql, [id], Project::from_row) {
+        Ok(project) => Ok(Some(project)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn set_project_share(conn: &rusqlite::Connection, id: &Uuid, location: &str, key: &str) -> rusqlite::Result<usize> {
+    let sql = include_str!("../db/sql/set_project_share.sql");
+    conn.execute(sql, rusqlite::params![id, location, key])
+}
+
+pub fn update_joined_project(
+    conn: &rusqlite::Connection,
+    id: &Uuid,
+    name: &str,
+    role: crate::structs::project::ProjectRole,
+) -> rusqlite::Result<usize> {
+    let sql = include_str!("../db/sql/update_joined_project.sql");
+    conn.execute(sql, rusqlite::params![id, name, role])
+}
+
+pub fn set_project_synced(
+    conn: &rusqlite::Connection,
+    id: &Uuid,
+    last_seq: i64,
+    synced_at: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/set_project_synced.sql");
+    conn.execute(sql, rusqlite::params![id, last_seq, synced_at])?;
+    Ok(())
+}
+
+// The project and everything kept about its sharing; its tasks are detached separately
+pub fn delete_project(conn: &rusqlite::Connection, id: &Uuid) -> rusqlite::Result<usize> {
+    let deleted = conn.execute(include_str!("../db/sql/delete_project.sql"), [id])?;
+    conn.execute(include_str!("../db/sql/delete_project_members.sql"), [id])?;
+    conn.execute(include_str!("../db/sql/delete_shared_project_versions.sql"), [id])?;
+    conn.execute(include_str!("../db/sql/delete_shared_project_cursors.sql"), [id])?;
+    Ok(deleted)
+}
+
+pub fn get_project_members(conn: &rusqlite::Connection, project_id: &Uuid) -> rusqlite::Result<Vec<crate::structs::project::ProjectMember>> {
+    use crate::structs::project::ProjectMember;
+    
+    let sql = include_str!("../db/sql/get_project_members.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([project_id], ProjectMember::from_row)?;
+    
+    rows.collect()
+}
+
+// Replace the member list
+pub fn set_project_members(
+    conn: &rusqlite::Connection,
+    project_id: &Uuid,
+    members: &[crate::structs::project::ProjectMember],
+) -> rusqlite::Result<()> {
+    conn.execute(include_str!("../db/sql/delete_project_members.sql"), [project_id])?;
+    let mut stmt = conn.prepare(include_str!("../db/sql/add_project_member.sql"))?;
+    for member in members {
+        stmt.execute(rusqlite::params![project_id, &member.identity, member.role])?;
+    }
+    Ok(())
+}
+
+pub fn count_project_tasks(conn: &rusqlite::Connection, project_id: &Uuid) -> rusqlite::Result<i64> {
+    let sql = include_str!("../db/sql/count_project_tasks.sql");
+    conn.query_row(sql, [project_id], |row| row.get(0))
+}
+
+pub fn get_project_tasks(conn: &rusqlite::Connection, project_id: &Uuid) -> rusqlite::Result<Vec<crate::structs::task_struct::Task>> {
+    use crate::structs::task_struct::Task;
+    
+    let sql = include_str!("../db/sql/get_project_tasks.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let task_iter = stmt.query_map([project_id], Task::from_row)?;
+    
+    task_iter.collect()
+}
+
+// Open tasks of a project, the ones archiving it has to decide about
+pub fn get_open_project_tasks(conn: &rusqlite::Connection, project_id: &Uuid) -> rusqlite::Result<Vec<crate::structs::task_struct::Task>> {
+    use crate::structs::task_struct::Task;
+    
+    let sql = include_str!("../db/sql/get_open_project_tasks.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let task_iter = stmt.query_map([project_id], Task::from_row)?;
+    
+    task_iter.collect()
+}
+
+pub fn archive_project(
+    conn: &rusqlite::Connection,
+    project_id: &Uuid,
+    archived_at: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<usize> {
+    let sql = include_str!("../db/sql/archive_project.sql");
+    conn.execute(sql, rusqlite::params![project_id, archived_at])
+}
+
+pub fn detach_project_tasks(
+    conn: &rusqlite::Connection,
+    project_id: &Uuid,
+    updated_at: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<usize> {
+    let sql = include_str!("../db/sql/detach_project_tasks.sql");
+    conn.execute(sql, rusqlite::params![project_id, updated_at])
+}
+
+// Version of each task last exchanged through a project's share location
+pub fn get_shared_project_versions(
+    conn: &rusqlite::Connection,
+    project_id: &Uuid,
+) -> rusqlite::Result<HashMap<Uuid, chrono::DateTime<chrono::Utc>>> {
+    let sql = include_str!("../db/sql/get_shared_project_versions.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([project_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    
+    rows.collect()
+}
+
+pub fn set_shared_project_version(
+    conn: &rusqlite::Connection,
+    project_id: &Uuid,
+    task_id: &Uuid,
+    version: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/set_shared_project_version.sql");
+    conn.execute(sql, rusqlite::params![project_id, task_id, version])?;
+    Ok(())
+}
+
+pub fn delete_shared_project_version(conn: &rusqlite::Connection, project_id: &Uuid, task_id: &Uuid) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/delete_shared_project_version.sql");
+    conn.execute(sql, rusqlite::params![project_id, task_id])?;
+    Ok(())
+}
+
+// Last changeset merged from a member device
+pub fn get_shared_project_cursor(conn: &rusqlite::Connection, project_id: &Uuid, device_id: &str) -> rusqlite::Result<Option<i64>> {
+    let sql = include_str!("../db/sql/get_shared_project_cursor.sql");
+    match conn.query_row(sql, rusqlite::params![project_id, device_id], |row| row.get(0)) {
+        Ok(value) => Ok(Some(value)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn set_shared_project_cursor(conn: &rusqlite::Connection, project_id: &Uuid, device_id: &str, last_seq: i64) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/set_shared_project_cursor.sql");
+    conn.execute(sql, rusqlite::params![project_id, device_id, last_seq])?;
+    Ok(())
+}
+
+// Errors from the secrets store, carried through rusqlite's error type
+fn secret_error(e: String) -> rusqlite::Error {
+    rusqlite::Error::UserFunctionError(e.into())
+}
+
+pub fn save_secret(
+    conn: &rusqlite::Connection,
+    namespace: &str,
+    name: &str,
+    backend: crate::structs::secret::SecretBackend,
+    value: Option<&str>,
+    now: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/save_secret.sql");
+    conn.execute(sql, rusqlite::params![namespace, name, backend, value, now])?;
+    Ok(())
+}
+
+pub fn get_secret(
+    conn: &rusqlite::Connection,
+    namespace: &str,
+    name: &str,
+) -> rusqlite::Result<Option<crate::structs::secret::StoredSecret>> {
+    use crate::structs::secret::StoredSecret;
+    
+    let sql = include_str!("../db/sql/get_secret.sql");
+    match conn.query_row(sql, rusqlite::params![namespace, name], StoredSecret::from_row) {
+        Ok(secret) => Ok(Some(secret)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn delete_secret(conn: &rusqlite::Connection, namespace: &str, name: &str) -> rusqlite::Result<usize> {
+    let sql = include_str!("../db/sql/delete_secret.sql");
+    conn.execute(sql, rusqlite::params![namespace, name])
+}
+
+pub fn get_legacy_secrets(conn: &rusqlite::Connection) -> rusqlite::Result<Vec<crate::structs::secret::LegacySecret>> {
+    use crate::structs::secret::LegacySecret;
+    
+    let sql = include_str!("../db/sql/get_legacy_secrets.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([], LegacySecret::from_row)?;
+    
+    rows.collect()
+}
+
+pub fn clear_legacy_secrets(conn: &rusqlite::Connection) -> rusqlite::Result<()> {
+    conn.execute_batch(include_str!("../db/sql/clear_legacy_secrets.sql"))
+}
+
+pub fn insert_remembered_date(
+    conn: &rusqlite::Connection,
+    data: &crate::structs::remembered_date::RememberedDateData,
+) -> rusqlite::Result<i64> {
+    let sql = include_str!("../db/sql/insert_remembered_date.sql");
+    conn.execute(
+        sql,
+        rusqlite::params![data.name, data.month, data.day, data.year, data.remind_days(), &chrono::Utc::now()],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+pub fn get_remembered_dates(conn: &rusqlite::Connection) -> rusqlite::Result<Vec<crate::structs::remembered_date::RememberedDate>> {
+    use crate::structs::remembered_date::RememberedDate;
+    
+    let sql = include_str!("../db/sql/get_remembered_dates.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([], RememberedDate::from_row)?;
+    
+    rows.collect()
+}
+
+pub fn get_remembered_date_by_id(conn: &rusqlite::Connection, id: i64) -> rusqlite::Result<crate::structs::remembered_date::RememberedDate> {
+    use crate::structs::remembered_date::RememberedDate;
+    
+    let sql = include_str!("../db/sql/get_remembered_date_by_id.sql");
+    conn.query_row(sql, [id], RememberedDate::from_row)
+}
+
+pub fn update_remembered_date(
+    conn: &rusqlite::Connection,
+    id: i64,
+    data: &crate::structs::remembered_date::RememberedDateData,
+) -> rusqlite::Result<usize> {
+    let sql = include_str!("../db/sql/update_remembered_date.sql");
+    conn.execute(sql, rusqlite::params![id, data.name, data.month, data.day, data.year, data.remind_days()])
+}
+
+pub fn delete_remembered_date(conn: &rusqlite::Connection, id: i64) -> rusqlite::Result<usize> {
+    conn.execute(include_str!("../db/sql/delete_remembered_date.sql"), [id])
+}
+
+pub fn set_remembered_date_reminded(conn: &rusqlite::Connection, id: i64, occurrence: chrono::NaiveDate) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/set_remembered_date_reminded.sql");
+    conn.execute(sql, rusqlite::params![id, occurrence])?;
+    Ok(())
+}
+
+pub fn get_stale_tag_tasks(conn: &rusqlite::Connection) -> rusqlite::Result<Vec<crate::structs::tag::StaleTagTask>> {
+    use crate::structs::tag::StaleTagTask;
+    
+    let sql = include_str!("../db/sql/get_stale_tag_tasks.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([], StaleTagTask::from_row)?;
+    
+    rows.collect()
+}
+
+pub fn replace_task_tags(conn: &rusqlite::Connection, task_id: TaskId, tags: &[String]) -> rusqlite::Result<()> {
+    conn.execute(include_str!("../db/sql/delete_task_tags.sql"), [&task_id])?;
+    let mut stmt = conn.prepare(include_str!("../db/sql/insert_task_tag.sql"))?;
+    for tag in tags {
+        stmt.execute(rusqlite::params![&task_id, tag])?;
+    }
+    Ok(())
+}
+
+pub fn clear_stale_tag_tasks(conn: &rusqlite::Connection) -> rusqlite::Result<()> {
+    conn.execute(include_str!("../db/sql/clear_stale_tag_tasks.sql"), [])?;
+    Ok(())
+}
+
+pub fn suggest_tags(conn: &rusqlite::Connection, pattern: &str, limit: i64) -> rusqlite::Result<Vec<crate::structs::tag::TagSuggestion>> {
+    use crate::structs::tag::TagSuggestion;
+    
+    let sql = include_str!("../db/sql/suggest_tags.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map(rusqlite::params![pattern, limit], TagSuggestion::from_row)?;
+    
+    rows.collect()
+}
+
+pub fn get_tag_stats(conn: &rusqlite::Connection) -> rusqlite::Result<Vec<crate::structs::tag::TagStats>> {
+    use crate::structs::tag::TagStats;
+    
+    let sql = include_str!("../db/sql/get_tag_stats.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([], TagStats::from_row)?;
+    
+    rows.collect()
+}
+
+// False when the link was already there
+pub fn add_task_dependency(
+    conn: &rusqlite::Connection,
+    task_id: TaskId,
+    depends_on_id: TaskId,
+    created_at: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<bool> {
+    let sql = include_str!("../db/sql/add_task_dependency.sql");
+    Ok(conn.execute(sql, rusqlite::params![&task_id, &depends_on_id, &created_at])? == 1)
+}
+
+pub fn remove_task_dependency(conn: &rusqlite::Connection, task_id: TaskId, depends_on_id: TaskId) -> rusqlite::Result<usize> {
+    let sql = include_str!("../db/sql/remove_task_dependency.sql");
+    conn.execute(sql, rusqlite::params![&task_id, &depends_on_id])
+}
+
+pub fn get_task_dependencies(conn: &rusqlite::Connection) -> rusqlite::Result<Vec<crate::structs::dependency::TaskDependency>> {
+    use crate::structs::dependency::TaskDependency;
+    
+    let sql = include_str!("../db/sql/get_task_dependencies.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([], TaskDependency::from_row)?;
+    
+    rows.collect()
+}
+
+pub fn get_project_task_ids(conn: &rusqlite::Connection, project_id: &Uuid) -> rusqlite::Result<Vec<TaskId>> {
+    let sql = include_str!("../db/sql/get_project_task_ids.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([project_id], |row| row.get(0))?;
+    
+    rows.collect()
+}
+
+pub fn get_calendar_event_rules(conn: &rusqlite::Connection) -> rusqlite::Result<Vec<crate::structs::calendar_rule::CalendarEventRule>> {
+    use crate::structs::calendar_rule::CalendarEventRule;
+    
+    let sql = include_str!("../db/sql/get_calendar_event_rules.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([], CalendarEventRule::from_row)?;
+    
+    rows.collect()
+}
+
+pub fn get_calendar_event_rule_by_id(
+    conn: &rusqlite::Connection,
+    rule_id: &Uuid,
+) -> rusqlite::Result<crate::structs::calendar_rule::CalendarEventRule> {
+    use crate::structs::calendar_rule::CalendarEventRule;
+    
+    let sql = include_str!("../db/sql/get_calendar_event_rule_by_id.sql");
+    conn.query_row(sql, [rule_id], CalendarEventRule::from_row)
+}
+
+pub fn update_calendar_event_rule<T: Updatable>(
+    conn: &rusqlite::Connection,
+    rule_id: &Uuid,
+    update_data: &T,
+) -> rusqlite::Result<crate::structs::calendar_rule::CalendarEventRule> {
+    let cols_vals = update_data.update_columns_values();
+    if cols_vals.is_empty() {
+        return get_calendar_event_rule_by_id(conn, rule_id);
+    }
+    
+    let set_clauses: Vec<String> = cols_vals.iter()
+        .map(|(col, _)| format!("{} = ?", col))
+        .collect();
+    let mut params: Vec<&dyn rusqlite::ToSql> = cols_vals.iter()
+        .map(|(_, v)| *v)
+        .collect();
+    params.push(rule_id);
+    
+    let sql = format!(
+        "UPDATE {} SET {} WHERE id = ?",
+        T::table_name(),
+        set_clauses.join(", ")
+    );
+    
+    let rows_affected = conn.execute(&sql, &params[..])?;
+    if rows_affected == 0 {
+        Err(rusqlite::Error::QueryReturnedNoRows)
+    } else {
+        get_calendar_event_rule_by_id(conn, rule_id)
+    }
+}
+
+pub fn delete_calendar_event_rule_by_id(conn: &rusqlite::Connection, rule_id: &Uuid) -> rusqlite::Result<usize> {
+    let sql = include_str!("../db/sql/delete_calendar_event_rule_by_id.sql");
+    conn.execute(sql, [rule_id])
+}
+
+// Every linked event by its Google ID, whichever side it came from
+pub fn get_pulled_event_links(
+    conn: &rusqlite::Connection,
+) -> rusqlite::Result<HashMap<String, crate::structs::calendar_event::PulledEventLink>> {
+    use crate::structs::calendar_event::PulledEventLink;
+    
+    let sql = include_str!("../db/sql/get_pulled_event_links.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([], PulledEventLink::from_row)?;
+    
+    rows.map(|row| row.map(|link| (link.google_event_id.clone(), link))).collect()
+}
+
+pub fn link_pulled_event(
+    conn: &rusqlite::Connection,
+    task_id: TaskId,
+    event_id: &str,
+    event_start: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/link_pulled_event.sql");
+    conn.execute(sql, rusqlite::params![&task_id, event_id, &event_start])?;
+    Ok(())
+}
+
+pub fn set_pulled_event_start(
+    conn: &rusqlite::Connection,
+    event_id: &str,
+    event_start: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/set_pulled_event_start.sql");
+    conn.execute(sql, rusqlite::params![event_id, &event_start])?;
+    Ok(())
+}
+
+pub fn set_task_recurrence(
+    conn: &rusqlite::Connection,
+    task_id: TaskId,
+    frequency: crate::structs::recurrence::RecurrenceFrequency,
+    interval: u32,
+    starts_on: chrono::NaiveDate,
+    created_at: chrono::DateTime<chrono::Utc>,
+) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/set_task_recurrence.sql");
+    conn.execute(sql, rusqlite::params![&task_id, &frequency, interval, &starts_on, &created_at])?;
+    Ok(())
+}
+
+pub fn get_task_recurrence(
+    conn: &rusqlite::Connection,
+    task_id: TaskId,
+) -> rusqlite::Result<Option<crate::structs::recurrence::TaskRecurrence>> {
+    use crate::structs::recurrence::TaskRecurrence;
+    
+    let sql = include_str!("../db/sql/get_task_recurrence.sql");
+    match conn.query_row(sql, [&task_id], TaskRecurrence::from_row) {
+        Ok(recurrence) => Ok(Some(recurrence)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn get_task_recurrences(conn: &rusqlite::Connection) -> rusqlite::Result<Vec<crate::structs::recurrence::TaskRecurrence>> {
+    use crate::structs::recurrence::TaskRecurrence;
+    
+    let sql = include_str!("../db/sql/get_task_recurrences.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([], TaskRecurrence::from_row)?;
+    
+    rows.collect()
+}
+
+// Its exceptions and occurrence links go with it (task_recurrences_delete trigger)
+pub fn delete_task_recurrence(conn: &rusqlite::Connection, task_id: TaskId) -> rusqlite::Result<usize> {
+    let sql = include_str!("../db/sql/delete_task_recurrence.sql");
+    conn.execute(sql, [&task_id])
+}
+
+pub fn set_recurrence_generated_through(conn: &rusqlite::Connection, task_id: TaskId, day: chrono::NaiveDate) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/set_recurrence_generated_through.sql");
+    conn.execute(sql, rusqlite::params![&task_id, &day])?;
+    Ok(())
+}
+
+pub fn set_recurrence_exception(conn: &rusqlite::Connection, exception: &crate::structs::recurrence::RecurrenceException) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/set_recurrence_exception.sql");
+    conn.execute(sql, rusqlite::params![
+        &exception.series_id,
+        &exception.occurrence_on,
+        &exception.kind,
+        &exception.deadline,
+        &exception.created_at,
+    ])?;
+    Ok(())
+}
+
+pub fn get_recurrence_exceptions(
+    conn: &rusqlite::Connection,
+    series_id: TaskId,
+) -> rusqlite::Result<Vec<crate::structs::recurrence::RecurrenceException>> {
+    use crate::structs::recurrence::RecurrenceException;
+    
+    let sql = include_str!("../db/sql/get_recurrence_exceptions.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map([&series_id], RecurrenceException::from_row)?;
+    
+    rows.collect()
+}
+
+pub fn add_recurrence_occurrence(
+    conn: &rusqlite::Connection,
+    series_id: TaskId,
+    occurrence_on: chrono::NaiveDate,
+    task_id: TaskId,
+) -> rusqlite::Result<()> {
+    let sql = include_str!("../db/sql/add_recurrence_occurrence.sql");
+    conn.execute(sql, rusqlite::params![&series_id, &occurrence_on, &task_id])?;
+    Ok(())
+}
+
+// Tasks made for the series' occurrences from `from` on, through `through` when given
+pub fn get_recurrence_occurrence_tasks(
+    conn: &rusqlite::Connection,
+    series_id: TaskId,
+    from: chrono::NaiveDate,
+    through: Option<chrono::NaiveDate>,
+) -> rusqlite::Result<Vec<TaskId>> {
+    let sql = include_str!("../db/sql/get_recurrence_occurrence_tasks.sql");
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map(rusqlite::params![&series_id, &from, &through], |row| row.get(0))?;
+    
+    rows.collect()
+}
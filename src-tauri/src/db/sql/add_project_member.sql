@@ -0,0 +1 @@
+INSERT INTO project_members (project_id, identity, role) VALUES (?1, ?2, ?3)
@@ -0,0 +1 @@
+INSERT OR IGNORE INTO recurrence_occurrences (series_id, occurrence_on, task_id) VALUES (?1, ?2, ?3)
@@ -0,0 +1 @@
+INSERT INTO sync_outbox (seq, record) VALUES (?1, ?2)
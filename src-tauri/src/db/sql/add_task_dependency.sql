@@ -0,0 +1 @@
+INSERT OR IGNORE INTO task_dependencies (task_id, depends_on_id, created_at) VALUES (?1, ?2, ?3)
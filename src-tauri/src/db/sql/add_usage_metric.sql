@@ -0,0 +1,3 @@
+INSERT INTO usage_metrics (day, kind, name, count)
+VALUES (?1, ?2, ?3, ?4)
+ON CONFLICT(day, kind, name) DO UPDATE SET count = count + excluded.count
@@ -0,0 +1 @@
+UPDATE projects SET archived_at = ?2 WHERE id = ?1 AND archived_at IS NULL
@@ -0,0 +1,2 @@
+-- Hide a task from day lists: a completed one, or an open one of an archived project
+UPDATE tasks SET archived_at = ?1 WHERE id = ?2
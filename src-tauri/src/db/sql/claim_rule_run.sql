@@ -0,0 +1 @@
+INSERT OR IGNORE INTO rule_runs (rule_id, task_id, deadline, fired_at) VALUES (?1, ?2, ?3, ?4)
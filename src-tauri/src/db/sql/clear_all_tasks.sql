@@ -0,0 +1,8 @@
+-- Remove every task and the rows that hang off it
+DELETE FROM calendar_events;
+DELETE FROM task_notifications;
+DELETE FROM task_idempotency_keys;
+DELETE FROM work_sessions;
+DELETE FROM tasks;
+-- Forgotten rather than pushed as deletions, so wiping this device leaves shared projects alone
+DELETE FROM shared_project_tasks;
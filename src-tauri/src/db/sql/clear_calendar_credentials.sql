@@ -2,5 +2,6 @@ UPDATE calendar_credentials
 SET email = '', 
     access_token = '', 
     refresh_token = '', 
-    token_expiry = CURRENT_TIMESTAMP 
+    token_expiry = CURRENT_TIMESTAMP, 
+    reauth_needed_at = NULL 
 WHERE id = 1
@@ -0,0 +1 @@
+DELETE FROM changelog_origin
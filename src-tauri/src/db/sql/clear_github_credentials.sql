@@ -0,0 +1 @@
+DELETE FROM github_credentials WHERE id = 1
@@ -0,0 +1,5 @@
+-- Empty the old columns once their values are in the secrets table; where they're NOT NULL, '' stands in
+UPDATE calendar_credentials SET access_token = '', refresh_token = '' WHERE id = 1;
+UPDATE github_credentials SET token = '' WHERE id = 1;
+UPDATE webhooks SET secret = '' WHERE secret <> '';
+UPDATE settings SET http_api_token = NULL, slack_signing_secret = NULL, sync_webdav_password = NULL, sync_passphrase = NULL WHERE id = 1;
@@ -0,0 +1 @@
+DELETE FROM task_tags_stale
@@ -0,0 +1 @@
+DELETE FROM sync_conflicts WHERE resolved_at IS NOT NULL
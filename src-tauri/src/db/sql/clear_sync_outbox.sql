@@ -0,0 +1 @@
+DELETE FROM sync_outbox WHERE seq <= ?1
@@ -0,0 +1,2 @@
+-- Forget delivered notifications for a task (e.g. after its deadline moved)
+DELETE FROM task_notifications WHERE task_id = ?;
@@ -0,0 +1,3 @@
+UPDATE calendar_journal
+SET completed_at = ?1
+WHERE task_id = ?2 AND operation = ?3 AND id <= ?4 AND completed_at IS NULL
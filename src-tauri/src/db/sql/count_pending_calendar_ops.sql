@@ -0,0 +1,2 @@
+-- Unfinished journal entries still being retried
+SELECT COUNT(*) FROM calendar_journal WHERE completed_at IS NULL AND attempts < ?1
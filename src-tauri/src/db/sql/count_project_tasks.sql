@@ -0,0 +1 @@
+SELECT COUNT(*) FROM tasks WHERE project_id = ?1
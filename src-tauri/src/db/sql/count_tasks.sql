@@ -0,0 +1,8 @@
+-- Tasks matching a filter, each part left out when NULL: archived or not (?1), status (?2),
+-- project (?3) and a LIKE pattern on the title (?4)
+SELECT COUNT(*)
+FROM tasks
+WHERE (?1 IS NULL OR (archived_at IS NOT NULL) = ?1)
+  AND (?2 IS NULL OR status = ?2)
+  AND (?3 IS NULL OR project_id = ?3)
+  AND (?4 IS NULL OR title LIKE ?4 ESCAPE '\')
@@ -0,0 +1,6 @@
+-- Count tasks for a day that are not completed yet
+SELECT COUNT(*) 
+FROM tasks 
+WHERE created_at >= ?1 AND created_at < ?2 
+  AND status != 'completed'
+  AND archived_at IS NULL
@@ -0,0 +1 @@
+DELETE FROM app_lock WHERE id = 1
\ No newline at end of file
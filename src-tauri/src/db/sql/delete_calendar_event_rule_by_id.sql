@@ -0,0 +1 @@
+DELETE FROM calendar_event_rules WHERE id = ?;
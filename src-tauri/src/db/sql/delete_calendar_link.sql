@@ -0,0 +1 @@
+DELETE FROM calendar_events WHERE google_event_id = ?1
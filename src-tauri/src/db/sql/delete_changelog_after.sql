@@ -0,0 +1 @@
+DELETE FROM changelog WHERE seq > ?1
\ No newline at end of file
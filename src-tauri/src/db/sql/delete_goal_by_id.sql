@@ -0,0 +1 @@
+DELETE FROM goals WHERE id = ?;
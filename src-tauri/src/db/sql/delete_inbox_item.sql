@@ -0,0 +1 @@
+DELETE FROM inbox_items WHERE id = ?1
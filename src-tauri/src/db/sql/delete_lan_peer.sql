@@ -0,0 +1 @@
+DELETE FROM lan_peers WHERE device_id = ?1
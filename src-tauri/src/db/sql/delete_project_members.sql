@@ -0,0 +1 @@
+DELETE FROM project_members WHERE project_id = ?1
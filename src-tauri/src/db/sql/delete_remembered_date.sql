@@ -0,0 +1 @@
+DELETE FROM remembered_dates WHERE id = ?1
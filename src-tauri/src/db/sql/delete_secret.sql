@@ -0,0 +1 @@
+DELETE FROM secrets WHERE namespace = ?1 AND name = ?2
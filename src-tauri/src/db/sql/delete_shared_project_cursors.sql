@@ -0,0 +1 @@
+DELETE FROM shared_project_cursors WHERE project_id = ?1
@@ -0,0 +1 @@
+DELETE FROM shared_project_tasks WHERE project_id = ?1
@@ -0,0 +1 @@
+DELETE FROM task_recurrences WHERE task_id = ?1
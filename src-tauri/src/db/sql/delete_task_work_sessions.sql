@@ -0,0 +1 @@
+DELETE FROM work_sessions WHERE task_id = ?1
@@ -0,0 +1 @@
+DELETE FROM webhook_deliveries WHERE webhook_id = ?1
@@ -0,0 +1,2 @@
+-- Take tasks out of a project that's going away; the new updated_at lets other devices see it
+UPDATE tasks SET project_id = NULL, assignee = NULL, updated_at = ?2 WHERE project_id = ?1
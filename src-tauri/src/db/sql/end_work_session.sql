@@ -0,0 +1,4 @@
+-- Close the task's running session; idle pauses are backdated, but never to before it began
+UPDATE work_sessions
+SET ended_at = MAX(started_at, ?2), pause_reason = ?3, note = ?4
+WHERE task_id = ?1 AND ended_at IS NULL
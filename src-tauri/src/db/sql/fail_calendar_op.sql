@@ -0,0 +1,3 @@
+UPDATE calendar_journal
+SET attempts = attempts + 1, last_error = ?1
+WHERE id = ?2
@@ -0,0 +1,3 @@
+SELECT pin_hash, key_salt, idle_minutes, created_at
+FROM app_lock
+WHERE id = 1
\ No newline at end of file
@@ -0,0 +1,8 @@
+-- Last success is all-time; call and error counts are since ?1
+SELECT account,
+       MAX(CASE WHEN error_kind IS NULL THEN occurred_at END) AS last_success,
+       COUNT(CASE WHEN occurred_at >= ?1 THEN 1 END) AS calls,
+       COUNT(CASE WHEN occurred_at >= ?1 AND error_kind IS NOT NULL THEN 1 END) AS errors
+FROM calendar_api_log
+GROUP BY account
+ORDER BY account
@@ -1,3 +1,3 @@
-SELECT email, access_token, refresh_token, token_expiry 
+SELECT email, token_expiry, reauth_needed_at 
 FROM calendar_credentials 
 WHERE id = 1
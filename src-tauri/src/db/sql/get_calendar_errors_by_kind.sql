@@ -0,0 +1,5 @@
+SELECT error_kind, COUNT(*) AS count
+FROM calendar_api_log
+WHERE error_kind IS NOT NULL AND occurred_at >= ?1
+GROUP BY error_kind
+ORDER BY count DESC
@@ -1,2 +1,2 @@
--- Get Google Calendar event ID for a task
-SELECT google_event_id FROM calendar_events WHERE task_id = ?;
+-- Get the Google Calendar event made for a task; events a task was made from aren't ours to change
+SELECT google_event_id FROM calendar_events WHERE task_id = ? AND origin = 'task';
@@ -0,0 +1,3 @@
+SELECT id, name, title_prefix, enabled, created_at
+FROM calendar_event_rules
+WHERE id = ?
@@ -0,0 +1,3 @@
+SELECT (SELECT COUNT(DISTINCT task_id) FROM calendar_events WHERE origin = 'task') AS linked_tasks,
+       (SELECT COUNT(*) FROM tasks WHERE has_calendar_integration = 1) AS calendar_tasks,
+       (SELECT COUNT(*) FROM calendar_journal WHERE completed_at IS NULL) AS pending_operations
@@ -0,0 +1,3 @@
+SELECT task_id, google_event_id, origin
+FROM calendar_events
+ORDER BY id
@@ -0,0 +1,4 @@
+SELECT seq, table_name, row_id, op, old_values, new_values, clock, device_id, changed_at
+FROM changelog
+WHERE changed_at < ?1
+ORDER BY seq
@@ -0,0 +1 @@
+SELECT COALESCE(MAX(seq), 0) FROM changelog
\ No newline at end of file
@@ -0,0 +1,5 @@
+-- Local days in [?1, ?2) with at least one completion; ?3 moves UTC to the local day
+SELECT DISTINCT date(completed_at, ?3) AS day
+FROM tasks
+WHERE completed_at >= ?1 AND completed_at < ?2
+ORDER BY day
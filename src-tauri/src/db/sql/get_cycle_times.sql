@@ -0,0 +1,7 @@
+-- Minutes from creation to first start and from first start to completion, for tasks created in the range
+SELECT CASE WHEN started_at IS NOT NULL
+            THEN (julianday(started_at) - julianday(created_at)) * 1440 END AS wait_minutes,
+       CASE WHEN started_at IS NOT NULL AND completed_at IS NOT NULL
+            THEN (julianday(completed_at) - julianday(started_at)) * 1440 END AS work_minutes
+FROM tasks
+WHERE created_at >= ?1 AND created_at < ?2
@@ -0,0 +1,4 @@
+SELECT day, created, created_and_completed, completed, tracked_minutes
+FROM day_summaries
+WHERE day >= ?1 AND day <= ?2
+ORDER BY day
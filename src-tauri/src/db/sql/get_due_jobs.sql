@@ -0,0 +1,5 @@
+-- Enabled jobs whose next run time has passed
+SELECT name, schedule, enabled, last_run_at, last_error, next_run_at
+FROM jobs
+WHERE enabled = 1 AND next_run_at <= ?1
+ORDER BY next_run_at ASC
@@ -0,0 +1,6 @@
+SELECT d.id, d.webhook_id, d.event, d.payload, d.status, d.attempts, d.response_status, d.last_error,
+       d.next_attempt_at, d.created_at, d.delivered_at
+FROM webhook_deliveries d
+JOIN webhooks w ON w.id = d.webhook_id
+WHERE d.status = 'pending' AND d.next_attempt_at <= ?1 AND w.enabled = 1
+ORDER BY d.id
@@ -0,0 +1,4 @@
+SELECT task_id, source, external_id, url
+FROM external_links
+WHERE task_id = ?1 AND source = ?2
+LIMIT 1
@@ -0,0 +1,3 @@
+SELECT login, open_sealed(token), close_issues_on_complete
+FROM github_credentials
+WHERE id = 1
@@ -0,0 +1,3 @@
+SELECT id, title, metric, target, created_at, last_met_week
+FROM goals
+WHERE id = ?
@@ -0,0 +1,4 @@
+SELECT date, name
+FROM holidays
+WHERE date >= ?1 AND date <= ?2
+ORDER BY date, name
@@ -0,0 +1,3 @@
+SELECT id, text, captured_at
+FROM inbox_items
+ORDER BY captured_at, id
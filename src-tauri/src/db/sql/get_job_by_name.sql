@@ -0,0 +1,3 @@
+SELECT name, schedule, enabled, last_run_at, last_error, next_run_at
+FROM jobs
+WHERE name = ?1
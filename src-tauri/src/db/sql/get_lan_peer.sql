@@ -0,0 +1,3 @@
+SELECT device_id, name, secret, paired_at, last_synced_at
+FROM lan_peers
+WHERE device_id = ?1
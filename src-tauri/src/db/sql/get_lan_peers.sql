@@ -0,0 +1,3 @@
+SELECT device_id, name, secret, paired_at, last_synced_at
+FROM lan_peers
+ORDER BY name, device_id
@@ -0,0 +1,21 @@
+-- Secrets kept in their integration's own table, from before the secrets table.
+-- Compared after opening: the app lock seals the emptied columns too.
+SELECT namespace, name, value
+FROM (
+    SELECT 'google' AS namespace, 'access_token' AS name, open_sealed(access_token) AS value FROM calendar_credentials WHERE id = 1
+    UNION ALL
+    SELECT 'google', 'refresh_token', open_sealed(refresh_token) FROM calendar_credentials WHERE id = 1
+    UNION ALL
+    SELECT 'github', 'token', open_sealed(token) FROM github_credentials WHERE id = 1
+    UNION ALL
+    SELECT 'webhook', CAST(id AS TEXT), secret FROM webhooks
+    UNION ALL
+    SELECT 'http_api', 'token', http_api_token FROM settings WHERE id = 1
+    UNION ALL
+    SELECT 'slack', 'signing_secret', slack_signing_secret FROM settings WHERE id = 1
+    UNION ALL
+    SELECT 'sync', 'webdav_password', open_sealed(sync_webdav_password) FROM settings WHERE id = 1
+    UNION ALL
+    SELECT 'sync', 'passphrase', open_sealed(sync_passphrase) FROM settings WHERE id = 1
+)
+WHERE value <> ''
@@ -0,0 +1,5 @@
+SELECT id, title, deadline, deadline_moves, postponed_days
+FROM tasks
+WHERE deadline_moves > 0
+ORDER BY deadline_moves DESC, postponed_days DESC
+LIMIT ?1
@@ -0,0 +1,3 @@
+SELECT calendar_network_allowed, github_network_allowed, sync_network_allowed
+FROM settings
+WHERE id = 1
@@ -0,0 +1,8 @@
+-- Tasks still to be done in a project; completed and archived ones are left out
+SELECT id, title, open_sealed(notes) AS notes, status, created_at, updated_at, deadline, 
+       has_calendar_integration, calendar_email, reminder_frequency, 
+       started_at, paused_at, completed_at, notifications_enabled, estimate_minutes,
+       project_id, assignee, context, energy, calendar_privacy
+FROM tasks
+WHERE project_id = ?1 AND status != 'completed' AND archived_at IS NULL
+ORDER BY created_at
@@ -0,0 +1,11 @@
+-- Open, unarchived tasks not at reminder frequency ?1; with ?2 set, only those without reminders
+SELECT id, title, open_sealed(notes) AS notes, status, created_at, updated_at, deadline, 
+       has_calendar_integration, calendar_email, reminder_frequency, 
+       started_at, paused_at, completed_at, notifications_enabled, estimate_minutes,
+       project_id, assignee, context, energy, calendar_privacy
+FROM tasks 
+WHERE status != 'completed'
+  AND archived_at IS NULL
+  AND reminder_frequency != ?1
+  AND (?2 = 0 OR reminder_frequency = 'none')
+ORDER BY created_at
@@ -0,0 +1,12 @@
+-- Pauses with a reason in the range, per reason, with the average wait until work on the task started again
+SELECT pause_reason,
+       COUNT(*) AS pauses,
+       CAST(ROUND(AVG((julianday(resumed_at) - julianday(ended_at)) * 1440)) AS INTEGER) AS minutes_to_resume
+FROM (
+    SELECT pause_reason, ended_at,
+           LEAD(started_at) OVER (PARTITION BY task_id ORDER BY started_at) AS resumed_at
+    FROM work_sessions
+)
+WHERE pause_reason IS NOT NULL AND ended_at >= ?1 AND ended_at < ?2
+GROUP BY pause_reason
+ORDER BY pauses DESC, pause_reason
@@ -0,0 +1,4 @@
+SELECT id, task_id, operation, event_id, attempts
+FROM calendar_journal
+WHERE completed_at IS NULL AND attempts < ?1
+ORDER BY id
@@ -0,0 +1,5 @@
+SELECT COUNT(CASE WHEN deadline_moves > 0 THEN 1 END) AS postponed_tasks,
+       COALESCE(SUM(deadline_moves), 0) AS moves,
+       COALESCE(SUM(postponed_days), 0.0) AS postponed_days,
+       COUNT(CASE WHEN status != 'completed' AND julianday(deadline) < julianday('now') THEN 1 END) AS overdue_open
+FROM tasks
@@ -0,0 +1,21 @@
+-- get_productivity_stats.sql for days in the settings offset: created and completed counts come from
+-- day_summaries (?6 to ?7, YYYY-MM-DD); overdue depends on now (?4), so it's still counted from tasks
+WITH activity AS (
+    SELECT day, created, completed, 0 AS overdue, created_and_completed
+    FROM day_summaries
+    WHERE day >= ?6 AND day <= ?7
+    UNION ALL
+    SELECT date(deadline, ?5), 0, 0, 1, 0
+    FROM tasks
+    WHERE deadline >= ?1 AND deadline < ?2 AND deadline < ?4
+      AND (completed_at IS NULL OR completed_at > deadline)
+)
+SELECT CASE ?3
+           WHEN 'day' THEN day
+           WHEN 'week' THEN date(day, '-6 days', 'weekday 1')
+           ELSE strftime('%Y-%m-01', day)
+       END AS bucket,
+       SUM(created), SUM(completed), SUM(overdue), SUM(created_and_completed)
+FROM activity
+GROUP BY bucket
+ORDER BY bucket
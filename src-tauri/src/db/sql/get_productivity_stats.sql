@@ -0,0 +1,25 @@
+-- Created, completed and overdue counts per local bucket in [?1, ?2) (?3: day, week or month; ?4: now; ?5: UTC offset modifier)
+WITH activity AS (
+    SELECT created_at AS happened_at, 1 AS created, 0 AS completed, 0 AS overdue,
+           CASE WHEN status = 'completed' THEN 1 ELSE 0 END AS created_and_completed
+    FROM tasks
+    WHERE created_at >= ?1 AND created_at < ?2
+    UNION ALL
+    SELECT completed_at, 0, 1, 0, 0
+    FROM tasks
+    WHERE completed_at >= ?1 AND completed_at < ?2
+    UNION ALL
+    SELECT deadline, 0, 0, 1, 0
+    FROM tasks
+    WHERE deadline >= ?1 AND deadline < ?2 AND deadline < ?4
+      AND (completed_at IS NULL OR completed_at > deadline)
+)
+SELECT CASE ?3
+           WHEN 'day' THEN date(happened_at, ?5)
+           WHEN 'week' THEN date(happened_at, ?5, '-6 days', 'weekday 1')
+           ELSE strftime('%Y-%m-01', happened_at, ?5)
+       END AS bucket,
+       SUM(created), SUM(completed), SUM(overdue), SUM(created_and_completed)
+FROM activity
+GROUP BY bucket
+ORDER BY bucket
@@ -0,0 +1,2 @@
+SELECT id, name, share_location, open_sealed(share_key) AS share_key, role, last_seq, synced_at, created_at, archived_at
+FROM projects WHERE id = ?1
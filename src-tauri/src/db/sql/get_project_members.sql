@@ -0,0 +1,4 @@
+-- Owner first
+SELECT identity, role FROM project_members
+WHERE project_id = ?1
+ORDER BY role = 'owner' DESC, identity
@@ -0,0 +1,2 @@
+SELECT id FROM tasks WHERE project_id = ?1
+ORDER BY created_at
@@ -0,0 +1,3 @@
+-- Every linked event, made for a task or made into one, keyed by event for the calendar pull
+SELECT google_event_id, task_id, origin, event_start
+FROM calendar_events
@@ -0,0 +1,3 @@
+SELECT pushed_title, pushed_deadline, pushed_hash
+FROM calendar_events
+WHERE google_event_id = ?1 AND pushed_at IS NOT NULL
@@ -0,0 +1,4 @@
+SELECT series_id, occurrence_on, kind, deadline, created_at
+FROM recurrence_exceptions
+WHERE series_id = ?1
+ORDER BY occurrence_on
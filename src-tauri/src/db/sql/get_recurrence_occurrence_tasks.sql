@@ -0,0 +1,5 @@
+-- Tasks made for occurrences from ?2 on, through ?3 unless that's NULL
+SELECT task_id
+FROM recurrence_occurrences
+WHERE series_id = ?1 AND occurrence_on >= ?2 AND (?3 IS NULL OR occurrence_on <= ?3)
+ORDER BY occurrence_on
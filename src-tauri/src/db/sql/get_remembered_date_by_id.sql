@@ -0,0 +1,3 @@
+SELECT id, name, month, day, year, remind_days_before, reminded_for, created_at
+FROM remembered_dates
+WHERE id = ?1
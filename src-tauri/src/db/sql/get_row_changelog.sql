@@ -0,0 +1,11 @@
+SELECT seq, table_name, row_id, op,
+       CASE WHEN json_extract(old_values, '$.notes') LIKE 'sealed:%'
+            THEN json_set(old_values, '$.notes', open_sealed(json_extract(old_values, '$.notes')))
+            ELSE old_values END,
+       CASE WHEN json_extract(new_values, '$.notes') LIKE 'sealed:%'
+            THEN json_set(new_values, '$.notes', open_sealed(json_extract(new_values, '$.notes')))
+            ELSE new_values END,
+       clock, device_id, changed_at
+FROM changelog
+WHERE table_name = ?1 AND row_id = ?2
+ORDER BY seq
\ No newline at end of file
@@ -0,0 +1,3 @@
+SELECT id, name, trigger, tag, action, enabled, created_at
+FROM rules
+ORDER BY created_at
@@ -0,0 +1,3 @@
+SELECT backend, open_sealed(value)
+FROM secrets
+WHERE namespace = ?1 AND name = ?2
@@ -0,0 +1,5 @@
+-- Notes left on sessions that ended in the range, oldest first
+SELECT task_id, ended_at, note
+FROM work_sessions
+WHERE note IS NOT NULL AND ended_at >= ?1 AND ended_at < ?2
+ORDER BY ended_at
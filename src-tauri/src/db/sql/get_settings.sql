@@ -1,3 +1,20 @@
-SELECT id, dark_mode, notifications_enabled, default_reminder_frequency, calendar_integration_enabled, calendar_email, created_at, updated_at
+SELECT id, dark_mode, notifications_enabled, default_reminder_frequency, calendar_integration_enabled, calendar_email, created_at, updated_at,
+       quick_add_shortcut, idle_pause_minutes, show_task_badge,
+       http_api_enabled, http_api_port,
+       vault_path, vault_layout,
+       slack_enabled,
+       work_day_minutes, usage_metrics_enabled,
+       daily_summary_time, daily_summary_notification,
+       sync_folder, sync_device_id, sync_merged_at,
+       sync_backend, sync_webdav_url, sync_webdav_username, sync_device_name, sync_identity,
+       lan_sync_enabled,
+       utc_offset_minutes,
+       completion_effects, completion_webhook_url,
+       locale,
+       skip_weekends, skip_holidays, holiday_region,
+       calendar_work_sessions,
+       estimate_reached_percent, estimate_reached_notification,
+       calendar_privacy,
+       default_deadline_time
 FROM settings
 WHERE id = 1
@@ -0,0 +1 @@
+SELECT last_seq FROM shared_project_cursors WHERE project_id = ?1 AND device_id = ?2
@@ -0,0 +1 @@
+SELECT task_id, version FROM shared_project_tasks WHERE project_id = ?1
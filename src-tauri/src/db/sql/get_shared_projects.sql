@@ -0,0 +1,5 @@
+-- Shared projects only
+SELECT id, name, share_location, open_sealed(share_key) AS share_key, role, last_seq, synced_at, created_at, archived_at
+FROM projects
+WHERE share_location IS NOT NULL AND share_key IS NOT NULL
+ORDER BY created_at
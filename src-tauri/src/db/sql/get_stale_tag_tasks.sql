@@ -0,0 +1,4 @@
+-- Tasks whose tags need reading again, with the text they're read from
+SELECT s.task_id, t.title, open_sealed(t.notes) AS notes
+FROM task_tags_stale s
+JOIN tasks t ON t.id = s.task_id
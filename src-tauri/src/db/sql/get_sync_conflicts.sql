@@ -0,0 +1,4 @@
+SELECT id, task_id, title, remote_device, local_updated_at, remote_updated_at, winner, detected_at,
+       local_version, remote_version, resolved_at, kept
+FROM sync_conflicts
+ORDER BY resolved_at IS NOT NULL, detected_at DESC
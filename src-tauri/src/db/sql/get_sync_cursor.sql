@@ -0,0 +1 @@
+SELECT last_seq FROM sync_cursors WHERE device_id = ?1
@@ -0,0 +1,3 @@
+SELECT device_id, device_name, last_seq
+FROM sync_cursors
+ORDER BY device_name, device_id
@@ -0,0 +1 @@
+SELECT sync_last_seq FROM settings WHERE id = 1
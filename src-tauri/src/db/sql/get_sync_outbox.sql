@@ -0,0 +1 @@
+SELECT seq, record FROM sync_outbox ORDER BY seq
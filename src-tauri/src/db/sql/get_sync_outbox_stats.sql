@@ -0,0 +1 @@
+SELECT COUNT(*), COALESCE(MAX(seq), 0) FROM sync_outbox
@@ -0,0 +1 @@
+SELECT deleted_at FROM sync_tombstones WHERE task_id = ?1
@@ -0,0 +1 @@
+SELECT task_id, deleted_at FROM sync_tombstones
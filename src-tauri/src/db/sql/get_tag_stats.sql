@@ -0,0 +1,8 @@
+-- Open and completed tasks per tag; archived tasks count as neither
+SELECT tt.tag,
+       COALESCE(SUM(t.status != 'completed' AND t.archived_at IS NULL), 0) AS open_count,
+       COALESCE(SUM(t.status = 'completed' AND t.archived_at IS NULL), 0) AS completed_count
+FROM task_tags tt
+JOIN tasks t ON t.id = tt.task_id
+GROUP BY tt.tag
+ORDER BY COUNT(*) DESC, tt.tag
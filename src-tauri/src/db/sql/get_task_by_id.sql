@@ -1,5 +1,6 @@
-SELECT id, title, notes, status, 
+SELECT id, title, open_sealed(notes) AS notes, status, 
     created_at, updated_at, deadline, 
     has_calendar_integration, calendar_email, reminder_frequency, 
-    started_at, paused_at, completed_at 
+    started_at, paused_at, completed_at, notifications_enabled, estimate_minutes,
+    project_id, assignee, context, energy, calendar_privacy
 FROM tasks WHERE id = ?1
\ No newline at end of file
@@ -0,0 +1,2 @@
+-- The task's own calendar privacy; NULL when it follows settings
+SELECT calendar_privacy FROM tasks WHERE id = ?1
@@ -0,0 +1,5 @@
+-- When each task was created, which decides the day it's listed under
+SELECT created_at
+FROM tasks
+WHERE archived_at IS NULL
+ORDER BY created_at DESC
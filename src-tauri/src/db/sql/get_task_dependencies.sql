@@ -0,0 +1,3 @@
+-- Every link; graphs are walked in memory
+SELECT task_id, depends_on_id FROM task_dependencies
+ORDER BY created_at
@@ -0,0 +1 @@
+SELECT task_id FROM external_links WHERE source = ?1 AND external_id = ?2
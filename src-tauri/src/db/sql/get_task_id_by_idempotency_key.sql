@@ -0,0 +1,5 @@
+-- Keys of deleted tasks no longer count
+SELECT k.task_id
+FROM task_idempotency_keys k
+JOIN tasks t ON t.id = k.task_id
+WHERE k.idempotency_key = ?1
@@ -0,0 +1 @@
+SELECT open_sealed(notes) FROM tasks WHERE id = ?1
\ No newline at end of file
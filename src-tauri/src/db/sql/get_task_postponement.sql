@@ -0,0 +1,3 @@
+SELECT id, title, deadline, deadline_moves, postponed_days
+FROM tasks
+WHERE id = ?1
@@ -0,0 +1,3 @@
+SELECT task_id, frequency, interval, starts_on, generated_through, created_at
+FROM task_recurrences
+WHERE task_id = ?1
@@ -0,0 +1,7 @@
+-- Minutes from start to completion for tasks completed in the range, largest first
+SELECT id, title,
+       CAST(ROUND((julianday(completed_at) - julianday(started_at)) * 1440) AS INTEGER) AS minutes
+FROM tasks
+WHERE completed_at >= ?1 AND completed_at < ?2
+  AND started_at IS NOT NULL AND completed_at > started_at
+ORDER BY minutes DESC
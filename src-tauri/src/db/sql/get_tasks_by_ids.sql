@@ -0,0 +1,7 @@
+-- The ?1 in the list is widened to one placeholder per requested ID
+SELECT id, title, open_sealed(notes) AS notes, status, 
+    created_at, updated_at, deadline, 
+    has_calendar_integration, calendar_email, reminder_frequency, 
+    started_at, paused_at, completed_at, notifications_enabled, estimate_minutes,
+    project_id, assignee, context, energy, calendar_privacy
+FROM tasks WHERE id IN (?1)
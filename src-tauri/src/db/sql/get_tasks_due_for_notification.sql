@@ -0,0 +1,12 @@
+-- Open tasks with notifications enabled whose deadline falls before the given cutoff
+SELECT id, title, open_sealed(notes) AS notes, status, created_at, updated_at, deadline, 
+       has_calendar_integration, calendar_email, reminder_frequency, 
+       started_at, paused_at, completed_at, notifications_enabled, estimate_minutes,
+       project_id, assignee, context, energy, calendar_privacy
+FROM tasks 
+WHERE deadline IS NOT NULL 
+  AND deadline <= ?1 
+  AND status != 'completed' 
+  AND archived_at IS NULL
+  AND notifications_enabled = 1
+ORDER BY deadline ASC
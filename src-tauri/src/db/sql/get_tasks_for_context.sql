@@ -0,0 +1,15 @@
+-- Open tasks that fit the moment: in context ?1 (any when NULL), needing no more energy than ?2,
+-- and estimated at no more than ?3 minutes, counting unestimated tasks as ?4; unlabelled energy always fits
+SELECT id, title, open_sealed(notes) AS notes, status, created_at, updated_at, deadline,
+       has_calendar_integration, calendar_email, reminder_frequency,
+       started_at, paused_at, completed_at, notifications_enabled, estimate_minutes,
+       project_id, assignee, context, energy, calendar_privacy
+FROM tasks
+WHERE status != 'completed'
+  AND archived_at IS NULL
+  AND (?1 IS NULL OR context = ?1)
+  AND (?2 IS NULL OR energy IS NULL
+       OR CASE energy WHEN 'low' THEN 1 WHEN 'medium' THEN 2 ELSE 3 END
+          <= CASE ?2 WHEN 'low' THEN 1 WHEN 'medium' THEN 2 ELSE 3 END)
+  AND (?3 IS NULL OR COALESCE(estimate_minutes, ?4) <= ?3)
+ORDER BY deadline IS NULL, deadline, created_at
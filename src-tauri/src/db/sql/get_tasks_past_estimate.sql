@@ -0,0 +1,16 @@
+-- Ongoing tasks whose work sessions, the running one counted up to ?1, add up to at least ?2 percent of the estimate
+SELECT t.id, t.title, open_sealed(t.notes) AS notes, t.status, t.created_at, t.updated_at, t.deadline, 
+       t.has_calendar_integration, t.calendar_email, t.reminder_frequency, 
+       t.started_at, t.paused_at, t.completed_at, t.notifications_enabled, t.estimate_minutes,
+       t.project_id, t.assignee, t.context, t.energy, t.calendar_privacy,
+       s.minutes
+FROM tasks t
+JOIN (
+    SELECT task_id,
+           CAST(ROUND(SUM(julianday(COALESCE(ended_at, ?1)) - julianday(started_at)) * 1440) AS INTEGER) AS minutes
+    FROM work_sessions
+    GROUP BY task_id
+) s ON s.task_id = t.id
+WHERE t.status = 'ongoing'
+  AND t.estimate_minutes > 0
+  AND s.minutes * 100 >= t.estimate_minutes * ?2
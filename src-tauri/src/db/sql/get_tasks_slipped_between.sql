@@ -0,0 +1,9 @@
+SELECT id, title, open_sealed(notes) AS notes, status, created_at, updated_at, deadline, 
+       has_calendar_integration, calendar_email, reminder_frequency, 
+       started_at, paused_at, completed_at, notifications_enabled, estimate_minutes,
+       project_id, assignee, context, energy, calendar_privacy
+FROM tasks 
+WHERE deadline >= ?1 AND deadline < ?2 
+  AND julianday(deadline) < julianday('now')
+  AND (completed_at IS NULL OR julianday(completed_at) > julianday(deadline))
+ORDER BY deadline
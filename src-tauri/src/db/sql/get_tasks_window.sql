@@ -0,0 +1,14 @@
+-- List rows ?7 to ?7 + ?6 of the tasks count_tasks.sql counts, newest first, notes cut to ?5 characters
+SELECT id, title, NULL AS notes, status, created_at, updated_at, deadline, 
+       has_calendar_integration, calendar_email, reminder_frequency, 
+       started_at, paused_at, completed_at, notifications_enabled, estimate_minutes,
+       project_id, assignee, context, energy, calendar_privacy,
+       substr(open_sealed(notes), 1, ?5) AS notes_preview,
+       COALESCE(length(open_sealed(notes)) > ?5, 0) AS notes_truncated
+FROM tasks
+WHERE (?1 IS NULL OR (archived_at IS NOT NULL) = ?1)
+  AND (?2 IS NULL OR status = ?2)
+  AND (?3 IS NULL OR project_id = ?3)
+  AND (?4 IS NULL OR title LIKE ?4 ESCAPE '\')
+ORDER BY created_at DESC, id DESC
+LIMIT ?6 OFFSET ?7
@@ -0,0 +1,15 @@
+-- Open, unpaused tasks with local reminders: a frequency set, notifications on and no calendar event to remind instead.
+-- Calendar tasks count too while the account needs connecting again, since their events can't be kept up to date.
+SELECT id, title, open_sealed(notes) AS notes, status, created_at, updated_at, deadline, 
+       has_calendar_integration, calendar_email, reminder_frequency, 
+       started_at, paused_at, completed_at, notifications_enabled, estimate_minutes,
+       project_id, assignee, context, energy, calendar_privacy
+FROM tasks 
+WHERE deadline IS NOT NULL 
+  AND deadline > ?1 
+  AND status IN ('not-started', 'ongoing') 
+  AND notifications_enabled = 1
+  AND (has_calendar_integration = 0
+       OR EXISTS (SELECT 1 FROM calendar_credentials WHERE id = 1 AND reauth_needed_at IS NOT NULL))
+  AND reminder_frequency != 'none'
+ORDER BY deadline ASC
@@ -0,0 +1,3 @@
+SELECT id, accent_color, font_scale, density, created_at, updated_at
+FROM theme_settings
+WHERE id = 1
@@ -0,0 +1,16 @@
+-- Most relevant open tasks for a day: ongoing first, then nearest deadline; notes cut to ?3 characters
+SELECT id, title, NULL AS notes, status, created_at, updated_at, deadline, 
+       has_calendar_integration, calendar_email, reminder_frequency, 
+       started_at, paused_at, completed_at, notifications_enabled, estimate_minutes,
+       project_id, assignee, context, energy, calendar_privacy,
+       substr(open_sealed(notes), 1, ?3) AS notes_preview,
+       COALESCE(length(open_sealed(notes)) > ?3, 0) AS notes_truncated
+FROM tasks 
+WHERE created_at >= ?1 AND created_at < ?2 
+  AND status != 'completed'
+  AND archived_at IS NULL
+ORDER BY CASE status WHEN 'ongoing' THEN 0 ELSE 1 END, 
+         deadline IS NULL, 
+         deadline ASC, 
+         created_at DESC
+LIMIT ?4
\ No newline at end of file
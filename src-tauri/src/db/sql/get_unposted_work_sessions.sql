@@ -0,0 +1,5 @@
+SELECT s.id, s.task_id, t.title, s.started_at, s.ended_at
+FROM work_sessions s
+JOIN tasks t ON t.id = s.task_id
+WHERE s.ended_at IS NOT NULL AND s.posted_at IS NULL
+ORDER BY s.started_at
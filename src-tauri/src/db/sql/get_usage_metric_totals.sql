@@ -0,0 +1,5 @@
+SELECT kind, name, SUM(count) AS count
+FROM usage_metrics
+WHERE day >= ?1
+GROUP BY kind, name
+ORDER BY count DESC, name
@@ -0,0 +1,3 @@
+SELECT day, kind, name, count
+FROM usage_metrics
+ORDER BY day, kind, name
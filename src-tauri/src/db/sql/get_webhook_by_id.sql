@@ -0,0 +1,3 @@
+SELECT id, url, secret, events, enabled, created_at
+FROM webhooks
+WHERE id = ?1
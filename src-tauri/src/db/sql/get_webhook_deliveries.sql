@@ -0,0 +1,6 @@
+SELECT id, webhook_id, event, payload, status, attempts, response_status, last_error,
+       next_attempt_at, created_at, delivered_at
+FROM webhook_deliveries
+WHERE ?1 IS NULL OR webhook_id = ?1
+ORDER BY id DESC
+LIMIT ?2
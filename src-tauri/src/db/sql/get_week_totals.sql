@@ -0,0 +1,8 @@
+-- Completed tasks and their start-to-completion minutes, the measure goals are tracked against
+SELECT COUNT(*) AS completed,
+       COALESCE(SUM(CASE WHEN started_at IS NOT NULL AND completed_at > started_at
+                         THEN CAST(ROUND((julianday(completed_at) - julianday(started_at)) * 1440) AS INTEGER)
+                    END), 0) AS tracked_minutes
+FROM tasks
+WHERE completed_at >= ?1 AND completed_at < ?2
+  AND status = 'completed'
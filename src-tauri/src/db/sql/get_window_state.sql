@@ -0,0 +1,3 @@
+SELECT kind, x, y, width, height, always_on_top, updated_at
+FROM window_states
+WHERE kind = ?1
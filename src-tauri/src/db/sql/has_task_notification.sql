@@ -0,0 +1 @@
+SELECT COUNT(*) FROM task_notifications WHERE task_id = ? AND kind = ?;
@@ -0,0 +1,3 @@
+-- Keep existing links: an event already linked to another task stays with it
+INSERT OR IGNORE INTO calendar_events (task_id, google_event_id, origin, updated_at, synced_at)
+VALUES (?1, ?2, ?3, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP);
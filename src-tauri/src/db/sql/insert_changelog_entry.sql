@@ -0,0 +1,2 @@
+INSERT INTO changelog (seq, table_name, row_id, op, old_values, new_values, clock, device_id, changed_at)
+VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
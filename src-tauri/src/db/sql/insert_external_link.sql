@@ -0,0 +1,2 @@
+INSERT OR REPLACE INTO external_links (task_id, source, external_id, url)
+VALUES (?1, ?2, ?3, ?4)
@@ -0,0 +1 @@
+INSERT OR IGNORE INTO holidays (date, name, imported_at) VALUES (?1, ?2, ?3)
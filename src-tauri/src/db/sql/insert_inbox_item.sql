@@ -0,0 +1 @@
+INSERT INTO inbox_items (text, captured_at) VALUES (?1, ?2)
@@ -0,0 +1,2 @@
+INSERT INTO remembered_dates (name, month, day, year, remind_days_before, created_at)
+VALUES (?1, ?2, ?3, ?4, ?5, ?6)
@@ -0,0 +1,2 @@
+INSERT INTO sync_conflicts (task_id, title, remote_device, local_updated_at, remote_updated_at, winner, detected_at, local_version, remote_version)
+VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
@@ -0,0 +1,2 @@
+INSERT OR REPLACE INTO task_idempotency_keys (idempotency_key, task_id)
+VALUES (?1, ?2)
@@ -0,0 +1 @@
+INSERT OR IGNORE INTO task_tags (task_id, tag) VALUES (?1, ?2)
@@ -0,0 +1 @@
+INSERT INTO webhooks (url, secret, events, enabled, created_at) VALUES (?1, '', ?2, 1, ?3)
@@ -0,0 +1,2 @@
+INSERT INTO webhook_deliveries (webhook_id, event, payload, status, next_attempt_at, created_at)
+VALUES (?1, ?2, ?3, 'pending', ?4, ?4)
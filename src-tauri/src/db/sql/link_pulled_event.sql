@@ -0,0 +1,3 @@
+-- Link a task made from an event on the user's calendar
+INSERT INTO calendar_events (task_id, google_event_id, origin, event_start, updated_at, synced_at)
+VALUES (?1, ?2, 'calendar', ?3, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP)
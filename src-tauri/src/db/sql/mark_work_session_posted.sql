@@ -0,0 +1,4 @@
+UPDATE work_sessions
+SET posted_at = ?2,
+    google_event_id = ?3
+WHERE id = ?1
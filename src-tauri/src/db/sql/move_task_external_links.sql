@@ -0,0 +1 @@
+UPDATE external_links SET task_id = ?2 WHERE task_id = ?1
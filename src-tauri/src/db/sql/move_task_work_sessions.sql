@@ -0,0 +1 @@
+UPDATE work_sessions SET task_id = ?2 WHERE task_id = ?1
@@ -0,0 +1,11 @@
+-- Store every sealed value in plain text again, history included
+UPDATE tasks SET notes = open_sealed(notes) WHERE notes LIKE 'sealed:%';
+UPDATE calendar_credentials SET access_token = open_sealed(access_token), refresh_token = open_sealed(refresh_token) WHERE id = 1;
+UPDATE github_credentials SET token = open_sealed(token) WHERE id = 1;
+UPDATE settings SET sync_webdav_password = open_sealed(sync_webdav_password), sync_passphrase = open_sealed(sync_passphrase) WHERE id = 1;
+UPDATE projects SET share_key = open_sealed(share_key) WHERE share_key LIKE 'sealed:%';
+UPDATE secrets SET value = open_sealed(value) WHERE value LIKE 'sealed:%';
+UPDATE changelog SET old_values = json_set(old_values, '$.notes', open_sealed(json_extract(old_values, '$.notes')))
+WHERE table_name = 'tasks' AND json_extract(old_values, '$.notes') LIKE 'sealed:%';
+UPDATE changelog SET new_values = json_set(new_values, '$.notes', open_sealed(json_extract(new_values, '$.notes')))
+WHERE table_name = 'tasks' AND json_extract(new_values, '$.notes') LIKE 'sealed:%';
@@ -0,0 +1,22 @@
+-- Replace every column of a task with another device's copy
+UPDATE tasks
+SET title = ?2,
+    notes = seal_text(?3),
+    status = ?4,
+    created_at = ?5,
+    updated_at = ?6,
+    deadline = ?7,
+    has_calendar_integration = ?8,
+    calendar_email = ?9,
+    reminder_frequency = ?10,
+    started_at = ?11,
+    paused_at = ?12,
+    completed_at = ?13,
+    notifications_enabled = ?14,
+    estimate_minutes = ?15,
+    project_id = ?16,
+    assignee = ?17,
+    context = ?18,
+    energy = ?19,
+    calendar_privacy = ?20
+WHERE id = ?1
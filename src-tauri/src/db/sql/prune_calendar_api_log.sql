@@ -0,0 +1 @@
+DELETE FROM calendar_api_log WHERE occurred_at < ?1
@@ -0,0 +1 @@
+DELETE FROM changelog WHERE changed_at < ?1
@@ -0,0 +1,17 @@
+-- Recount every day from tasks, in the current settings offset
+DELETE FROM day_summaries;
+INSERT INTO day_summaries (day, created, created_and_completed, completed, tracked_minutes)
+SELECT day, SUM(created), SUM(created_and_completed), SUM(completed), SUM(tracked_minutes)
+FROM (
+    SELECT date(created_at, (SELECT modifier FROM day_summary_offset)) AS day,
+           1 AS created, status = 'completed' AS created_and_completed, 0 AS completed, 0 AS tracked_minutes
+    FROM tasks
+    WHERE archived_at IS NULL OR status = 'completed'
+    UNION ALL
+    SELECT date(completed_at, (SELECT modifier FROM day_summary_offset)), 0, 0, 1,
+           COALESCE(MAX(0, (strftime('%s', completed_at) - strftime('%s', started_at)) / 60), 0)
+    FROM tasks
+    WHERE completed_at IS NOT NULL
+)
+WHERE day IS NOT NULL
+GROUP BY day;
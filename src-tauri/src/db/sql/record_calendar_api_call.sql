@@ -0,0 +1,2 @@
+INSERT INTO calendar_api_log (account, operation, error_kind, message, occurred_at)
+VALUES (?1, ?2, ?3, ?4, ?5)
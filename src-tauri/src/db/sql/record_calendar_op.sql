@@ -0,0 +1,2 @@
+INSERT INTO calendar_journal (task_id, operation, event_id, created_at)
+VALUES (?1, ?2, ?3, ?4)
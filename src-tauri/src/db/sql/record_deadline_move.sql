@@ -0,0 +1,5 @@
+-- Count one deadline move; pulling a deadline in adds no postponement days
+UPDATE tasks
+SET deadline_moves = deadline_moves + 1,
+    postponed_days = postponed_days + MAX(0, julianday(?2) - julianday(?3))
+WHERE id = ?1;
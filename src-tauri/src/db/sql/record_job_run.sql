@@ -0,0 +1,6 @@
+-- Store the outcome of a job run and when it should run next
+UPDATE jobs 
+SET last_run_at = ?1,
+    last_error = ?2,
+    next_run_at = ?3
+WHERE name = ?4
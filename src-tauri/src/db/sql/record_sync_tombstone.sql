@@ -0,0 +1 @@
+INSERT OR REPLACE INTO sync_tombstones (task_id, deleted_at) VALUES (?1, ?2)
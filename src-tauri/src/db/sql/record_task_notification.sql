@@ -0,0 +1,2 @@
+-- Remember that a notification kind was delivered for a task
+INSERT OR IGNORE INTO task_notifications (task_id, kind) VALUES (?, ?);
@@ -0,0 +1,3 @@
+UPDATE webhook_deliveries
+SET status = ?1, attempts = attempts + 1, response_status = ?2, last_error = ?3, next_attempt_at = ?4, delivered_at = ?5
+WHERE id = ?6
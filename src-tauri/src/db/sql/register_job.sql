@@ -0,0 +1,2 @@
+-- Add a job definition the first time the scheduler sees it
+INSERT OR IGNORE INTO jobs (name, schedule, next_run_at) VALUES (?1, ?2, ?3);
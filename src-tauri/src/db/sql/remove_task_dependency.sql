@@ -0,0 +1 @@
+DELETE FROM task_dependencies WHERE task_id = ?1 AND depends_on_id = ?2
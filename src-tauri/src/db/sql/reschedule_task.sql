@@ -0,0 +1,3 @@
+UPDATE tasks
+SET created_at = ?1, updated_at = ?2
+WHERE id = ?3 AND status != 'completed'
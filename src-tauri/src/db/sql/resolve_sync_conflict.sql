@@ -0,0 +1 @@
+UPDATE sync_conflicts SET resolved_at = ?2, kept = ?3 WHERE id = ?1
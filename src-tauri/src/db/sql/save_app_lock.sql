@@ -0,0 +1,6 @@
+INSERT INTO app_lock (id, pin_hash, key_salt, idle_minutes, created_at)
+VALUES (1, ?1, ?2, ?3, ?4)
+ON CONFLICT(id) DO UPDATE SET
+    pin_hash = excluded.pin_hash,
+    key_salt = excluded.key_salt,
+    idle_minutes = excluded.idle_minutes
\ No newline at end of file
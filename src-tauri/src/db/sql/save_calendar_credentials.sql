@@ -1,7 +1,8 @@
 UPDATE calendar_credentials 
 SET email = ?, 
-    access_token = ?, 
-    refresh_token = ?, 
+    access_token = '', 
+    refresh_token = '', 
     token_expiry = ?, 
+    reauth_needed_at = NULL, 
     updated_at = CURRENT_TIMESTAMP 
 WHERE id = 1
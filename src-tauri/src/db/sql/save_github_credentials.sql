@@ -0,0 +1,7 @@
+INSERT INTO github_credentials (id, login, token, close_issues_on_complete, updated_at)
+VALUES (1, ?1, '', ?2, CURRENT_TIMESTAMP)
+ON CONFLICT(id) DO UPDATE SET
+    login = excluded.login,
+    token = excluded.token,
+    close_issues_on_complete = excluded.close_issues_on_complete,
+    updated_at = CURRENT_TIMESTAMP
@@ -0,0 +1,2 @@
+INSERT INTO lan_peers (device_id, name, secret, paired_at) VALUES (?1, ?2, ?3, ?4)
+ON CONFLICT(device_id) DO UPDATE SET name = excluded.name, secret = excluded.secret, paired_at = excluded.paired_at
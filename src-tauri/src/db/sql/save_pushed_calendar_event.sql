@@ -0,0 +1,9 @@
+-- Links the event too, when saved right after it was created
+INSERT INTO calendar_events (task_id, google_event_id, updated_at, synced_at, pushed_title, pushed_deadline, pushed_hash, pushed_at)
+VALUES (?1, ?2, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP, ?3, ?4, ?5, ?6)
+ON CONFLICT (google_event_id) DO UPDATE SET
+    pushed_title = excluded.pushed_title,
+    pushed_deadline = excluded.pushed_deadline,
+    pushed_hash = excluded.pushed_hash,
+    pushed_at = excluded.pushed_at,
+    synced_at = excluded.synced_at
@@ -0,0 +1,6 @@
+INSERT INTO secrets (namespace, name, backend, value, updated_at)
+VALUES (?1, ?2, ?3, seal_text(?4), ?5)
+ON CONFLICT(namespace, name) DO UPDATE SET
+    backend = excluded.backend,
+    value = excluded.value,
+    updated_at = excluded.updated_at
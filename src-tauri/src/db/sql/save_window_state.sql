@@ -0,0 +1,3 @@
+-- Insert or replace the saved geometry for a window kind
+INSERT OR REPLACE INTO window_states (kind, x, y, width, height, always_on_top, updated_at)
+VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7);
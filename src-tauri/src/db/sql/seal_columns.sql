@@ -0,0 +1,11 @@
+-- Seal every sensitive value still stored in plain text, history included; values already sealed are left alone
+UPDATE tasks SET notes = seal_text(notes) WHERE notes NOT LIKE 'sealed:%';
+UPDATE calendar_credentials SET access_token = seal_text(access_token), refresh_token = seal_text(refresh_token) WHERE id = 1;
+UPDATE github_credentials SET token = seal_text(token) WHERE id = 1;
+UPDATE settings SET sync_webdav_password = seal_text(sync_webdav_password), sync_passphrase = seal_text(sync_passphrase) WHERE id = 1;
+UPDATE projects SET share_key = seal_text(share_key) WHERE share_key NOT LIKE 'sealed:%';
+UPDATE secrets SET value = seal_text(value) WHERE value NOT LIKE 'sealed:%';
+UPDATE changelog SET old_values = json_set(old_values, '$.notes', seal_text(json_extract(old_values, '$.notes')))
+WHERE table_name = 'tasks' AND json_type(old_values, '$.notes') = 'text';
+UPDATE changelog SET new_values = json_set(new_values, '$.notes', seal_text(json_extract(new_values, '$.notes')))
+WHERE table_name = 'tasks' AND json_type(new_values, '$.notes') = 'text';
@@ -0,0 +1,10 @@
+-- Tasks whose title or notes contain ?1 (a LIKE pattern), newest first
+SELECT id, title, open_sealed(notes) AS notes, status, created_at, updated_at, deadline, 
+       has_calendar_integration, calendar_email, reminder_frequency, 
+       started_at, paused_at, completed_at, notifications_enabled, estimate_minutes,
+       project_id, assignee, context, energy, calendar_privacy
+FROM tasks 
+WHERE archived_at IS NULL
+  AND (title LIKE ?1 ESCAPE '\' OR open_sealed(notes) LIKE ?1 ESCAPE '\')
+ORDER BY created_at DESC
+LIMIT ?2
@@ -0,0 +1,2 @@
+-- Only the first refusal counts, so the time says how long reminders have been local
+UPDATE calendar_credentials SET reauth_needed_at = ?1 WHERE id = 1 AND reauth_needed_at IS NULL
@@ -0,0 +1,2 @@
+INSERT INTO changelog_origin (id, device_id) VALUES (1, ?1)
+ON CONFLICT(id) DO UPDATE SET device_id = excluded.device_id
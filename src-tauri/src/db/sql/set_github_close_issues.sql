@@ -0,0 +1,3 @@
+UPDATE github_credentials
+SET close_issues_on_complete = ?1, updated_at = CURRENT_TIMESTAMP
+WHERE id = 1
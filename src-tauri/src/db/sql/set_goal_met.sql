@@ -0,0 +1 @@
+UPDATE goals SET last_met_week = ?2 WHERE id = ?1;
@@ -0,0 +1,5 @@
+-- Change when a job runs; next_run_at follows the new schedule
+UPDATE jobs
+SET schedule = ?1,
+    next_run_at = ?2
+WHERE name = ?3
@@ -0,0 +1 @@
+UPDATE lan_peers SET last_synced_at = ?2 WHERE device_id = ?1
@@ -0,0 +1 @@
+UPDATE projects SET share_location = ?2, share_key = seal_text(?3) WHERE id = ?1
@@ -0,0 +1 @@
+UPDATE projects SET last_seq = ?2, synced_at = ?3 WHERE id = ?1
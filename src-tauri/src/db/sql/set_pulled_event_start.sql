@@ -0,0 +1,3 @@
+UPDATE calendar_events
+SET event_start = ?2, synced_at = CURRENT_TIMESTAMP
+WHERE google_event_id = ?1 AND origin = 'calendar'
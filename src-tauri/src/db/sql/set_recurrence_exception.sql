@@ -0,0 +1,7 @@
+-- A later exception for the same occurrence replaces the earlier one
+INSERT INTO recurrence_exceptions (series_id, occurrence_on, kind, deadline, created_at)
+VALUES (?1, ?2, ?3, ?4, ?5)
+ON CONFLICT(series_id, occurrence_on) DO UPDATE SET
+    kind = excluded.kind,
+    deadline = excluded.deadline,
+    created_at = excluded.created_at
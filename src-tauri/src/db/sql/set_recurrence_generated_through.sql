@@ -0,0 +1 @@
+UPDATE task_recurrences SET generated_through = ?2 WHERE task_id = ?1
@@ -0,0 +1 @@
+UPDATE remembered_dates SET reminded_for = ?2 WHERE id = ?1
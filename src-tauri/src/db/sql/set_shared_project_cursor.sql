@@ -0,0 +1,2 @@
+INSERT INTO shared_project_cursors (project_id, device_id, last_seq) VALUES (?1, ?2, ?3)
+ON CONFLICT(project_id, device_id) DO UPDATE SET last_seq = excluded.last_seq
@@ -0,0 +1,2 @@
+INSERT INTO shared_project_tasks (project_id, task_id, version) VALUES (?1, ?2, ?3)
+ON CONFLICT(project_id, task_id) DO UPDATE SET version = excluded.version
@@ -0,0 +1,2 @@
+INSERT INTO sync_cursors (device_id, last_seq) VALUES (?1, ?2)
+ON CONFLICT(device_id) DO UPDATE SET last_seq = excluded.last_seq
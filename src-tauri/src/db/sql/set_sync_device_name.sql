@@ -0,0 +1,2 @@
+INSERT INTO sync_cursors (device_id, last_seq, device_name) VALUES (?1, 0, ?2)
+ON CONFLICT(device_id) DO UPDATE SET device_name = excluded.device_name
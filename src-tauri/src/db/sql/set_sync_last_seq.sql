@@ -0,0 +1 @@
+UPDATE settings SET sync_last_seq = ?1 WHERE id = 1
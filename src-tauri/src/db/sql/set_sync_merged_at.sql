@@ -0,0 +1 @@
+UPDATE settings SET sync_merged_at = ?1 WHERE id = 1
@@ -0,0 +1,8 @@
+-- A changed rule counts from the task's deadline again; occurrences already made aren't made twice
+INSERT INTO task_recurrences (task_id, frequency, interval, starts_on, generated_through, created_at)
+VALUES (?1, ?2, ?3, ?4, ?4, ?5)
+ON CONFLICT(task_id) DO UPDATE SET
+    frequency = excluded.frequency,
+    interval = excluded.interval,
+    starts_on = excluded.starts_on,
+    generated_through = MAX(generated_through, excluded.generated_through)
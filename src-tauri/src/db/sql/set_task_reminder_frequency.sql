@@ -0,0 +1 @@
+UPDATE tasks SET reminder_frequency = ?1, updated_at = ?2 WHERE id = ?3
@@ -0,0 +1,4 @@
+-- Open a session unless the task already has one running
+INSERT INTO work_sessions (task_id, started_at)
+SELECT ?1, ?2
+WHERE NOT EXISTS (SELECT 1 FROM work_sessions WHERE task_id = ?1 AND ended_at IS NULL)
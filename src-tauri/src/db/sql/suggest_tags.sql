@@ -0,0 +1,6 @@
+-- Tags starting with ?1 (a LIKE pattern), most used first
+SELECT tag, task_count
+FROM tag_usage
+WHERE tag LIKE ?1 ESCAPE '\'
+ORDER BY task_count DESC, tag
+LIMIT ?2
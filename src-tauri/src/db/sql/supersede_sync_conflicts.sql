@@ -0,0 +1,2 @@
+UPDATE sync_conflicts SET resolved_at = ?3, kept = 'superseded'
+WHERE task_id = ?1 AND resolved_at IS NULL AND detected_at < ?2
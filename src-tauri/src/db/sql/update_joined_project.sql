@@ -0,0 +1,2 @@
+-- Name and role as the owner's manifest has them
+UPDATE projects SET name = ?2, role = ?3 WHERE id = ?1
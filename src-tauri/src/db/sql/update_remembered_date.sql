@@ -0,0 +1,9 @@
+-- A moved date is reminded of again
+UPDATE remembered_dates
+SET name = ?2,
+    month = ?3,
+    day = ?4,
+    year = ?5,
+    remind_days_before = ?6,
+    reminded_for = CASE WHEN month = ?3 AND day = ?4 THEN reminded_for END
+WHERE id = ?1
@@ -1,3 +1,7 @@
--- Insert or replace calendar event for a task
-INSERT OR REPLACE INTO calendar_events (task_id, google_event_id, updated_at, synced_at) 
-VALUES (?, ?, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP);
+-- Link a calendar event to a task; an existing link keeps what was last pushed
+INSERT INTO calendar_events (task_id, google_event_id, updated_at, synced_at) 
+VALUES (?1, ?2, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP)
+ON CONFLICT (google_event_id) DO UPDATE SET
+    task_id = excluded.task_id,
+    updated_at = excluded.updated_at,
+    synced_at = excluded.synced_at;
@@ -0,0 +1,15 @@
+-- Calendar side effects owed by task changes; written in the same transaction as the change
+-- and replayed at startup until the call goes through
+
+CREATE TABLE IF NOT EXISTS calendar_journal (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    task_id BLOB NOT NULL,
+    operation VARCHAR(20) NOT NULL,
+    event_id VARCHAR(255),
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    created_at DATETIME NOT NULL,
+    completed_at DATETIME
+);
+
+CREATE INDEX IF NOT EXISTS idx_calendar_journal_completed_at ON calendar_journal(completed_at);
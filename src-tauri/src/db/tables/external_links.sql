@@ -0,0 +1,14 @@
+-- External links table - ties tasks to items in other services (e.g. GitHub issues)
+
+CREATE TABLE IF NOT EXISTS external_links (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    task_id BLOB NOT NULL,
+    source VARCHAR(20) NOT NULL,
+    external_id VARCHAR(255) NOT NULL,
+    url TEXT,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (source, external_id),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_external_links_task_id ON external_links(task_id);
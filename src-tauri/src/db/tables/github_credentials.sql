@@ -0,0 +1,10 @@
+-- GitHub personal access token (single row, present only while connected)
+
+CREATE TABLE IF NOT EXISTS github_credentials (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    login VARCHAR(255) NOT NULL,
+    token TEXT NOT NULL,
+    close_issues_on_complete BOOLEAN NOT NULL DEFAULT 0,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
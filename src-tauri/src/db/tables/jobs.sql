@@ -0,0 +1,10 @@
+-- Background jobs run by the scheduler (definitions seeded from code, state persisted)
+
+CREATE TABLE IF NOT EXISTS jobs (
+    name VARCHAR(50) PRIMARY KEY,
+    schedule VARCHAR(50) NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT 1,
+    last_run_at DATETIME,
+    last_error TEXT,
+    next_run_at DATETIME NOT NULL
+);
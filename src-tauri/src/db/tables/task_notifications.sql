@@ -0,0 +1,9 @@
+-- Desktop notifications already delivered for a task (prevents repeats)
+
+CREATE TABLE IF NOT EXISTS task_notifications (
+    task_id BLOB NOT NULL,
+    kind VARCHAR(20) NOT NULL,
+    sent_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (task_id, kind),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
@@ -0,0 +1,15 @@
+
+-- Single row table (id always = 1) for appearance tokens shared by all windows
+CREATE TABLE IF NOT EXISTS theme_settings (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    accent_color VARCHAR(7) NOT NULL DEFAULT '#6366f1',
+    font_scale REAL NOT NULL DEFAULT 1.0,
+    density VARCHAR(20) NOT NULL DEFAULT 'comfortable' CHECK (
+        density IN ('compact', 'comfortable', 'spacious')
+    ),
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Insert default theme row (ignore if already exists)
+INSERT OR IGNORE INTO theme_settings (id) VALUES (1);
@@ -0,0 +1,11 @@
+-- Remembered geometry and pinning for detached windows (focus window, today widget)
+
+CREATE TABLE IF NOT EXISTS window_states (
+    kind VARCHAR(50) PRIMARY KEY,
+    x REAL NOT NULL,
+    y REAL NOT NULL,
+    width REAL NOT NULL,
+    height REAL NOT NULL,
+    always_on_top BOOLEAN NOT NULL DEFAULT 1,
+    updated_at DATETIME NOT NULL
+);
@@ -1,34 +1,25 @@
 extern crate proc_macro;
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, DeriveInput, Data, Fields, LitStr};
+use syn::punctuated::Punctuated;
+use syn::token::Comma;
+use syn::{parse_macro_input, DeriveInput, Data, Field, Fields, Ident, LitStr};
 
 #[proc_macro_derive(Insertable, attributes(table_name))]
 pub fn insertable_derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    let struct_name = input.ident;
+    let struct_name = &input.ident;
 
     // Get table_name from #[table_name = "tasks"] attribute
-    let mut table_name = struct_name.to_string().to_lowercase();
-    for attr in input.attrs.iter() {
-        if attr.path().is_ident("table_name") {
-            if let syn::Meta::NameValue(nv) = &attr.meta {
-                if let syn::Expr::Lit(expr_lit) = &nv.value {
-                    if let syn::Lit::Str(litstr) = &expr_lit.lit {
-                        table_name = litstr.value();
-                    }
-                }
-            }
-        }
-    }
+    let table_name = match table_name(&input) {
+        Ok(table_name) => table_name,
+        Err(e) => return e.to_compile_error().into(),
+    };
 
     // Collect field names
-    let field_idents: Vec<_> = match input.data {
-        Data::Struct(ref data_struct) => match data_struct.fields {
-            Fields::Named(ref fields_named) => fields_named.named.iter().map(|f| f.ident.as_ref().unwrap()).collect(),
-            _ => panic!("Insertable only works on structs with named fields"),
-        },
-        _ => panic!("Insertable only works on structs"),
+    let field_idents: Vec<_> = match named_fields(&input, "Insertable") {
+        Ok(fields) => fields.iter().map(|f| f.ident.as_ref().unwrap()).collect(),
+        Err(e) => return e.to_compile_error().into(),
     };
 
     let columns: Vec<LitStr> = field_idents.iter()
@@ -58,15 +49,12 @@ pub fn insertable_derive(input: TokenStream) -> TokenStream {
 #[proc_macro_derive(Queryable)]
 pub fn queryable_derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    let struct_name = input.ident;
+    let struct_name = &input.ident;
 
     // Collect field names
-    let field_idents: Vec<_> = match input.data {
-        Data::Struct(ref data_struct) => match data_struct.fields {
-            Fields::Named(ref fields_named) => fields_named.named.iter().map(|f| f.ident.as_ref().unwrap()).collect(),
-            _ => panic!("Queryable only works on structs with named fields"),
-        },
-        _ => panic!("Queryable only works on structs"),
+    let field_idents: Vec<_> = match named_fields(&input, "Queryable") {
+        Ok(fields) => fields.iter().map(|f| f.ident.as_ref().unwrap()).collect(),
+        Err(e) => return e.to_compile_error().into(),
     };
 
     let field_count = field_idents.len();
@@ -90,35 +78,18 @@ pub fn queryable_derive(input: TokenStream) -> TokenStream {
 #[proc_macro_derive(Updatable, attributes(table_name))]
 pub fn updatable_derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    let struct_name = input.ident;
+    let struct_name = &input.ident;
 
     // Get table_name from #[table_name = "tasks"] attribute
-    let mut table_name = struct_name.to_string().to_lowercase();
-    for attr in input.attrs.iter() {
-        if attr.path().is_ident("table_name") {
-            if let syn::Meta::NameValue(nv) = &attr.meta {
-                if let syn::Expr::Lit(expr_lit) = &nv.value {
-                    if let syn::Lit::Str(litstr) = &expr_lit.lit {
-                        table_name = litstr.value();
-                    }
-                }
-            }
-        }
-    }
+    let table_name = match table_name(&input) {
+        Ok(table_name) => table_name,
+        Err(e) => return e.to_compile_error().into(),
+    };
 
     // Collect field names and types
-    let fields: Vec<_> = match input.data {
-        Data::Struct(ref data_struct) => match data_struct.fields {
-            Fields::Named(ref fields_named) => {
-                fields_named.named.iter().map(|f| {
-                    let ident = f.ident.as_ref().unwrap();
-                    let ty = &f.ty;
-                    (ident, ty)
-                }).collect()
-            },
-            _ => panic!("Updatable only works on structs with named fields"),
-        },
-        _ => panic!("Updatable only works on structs"),
+    let fields: Vec<_> = match named_fields(&input, "Updatable") {
+        Ok(fields) => fields.iter().map(|f| (f.ident.as_ref().unwrap(), &f.ty)).collect(),
+        Err(e) => return e.to_compile_error().into(),
     };
 
     let field_pushes = fields.iter().map(|(field_ident, field_ty)| {
@@ -174,3 +145,129 @@ pub fn updatable_derive(input: TokenStream) -> TokenStream {
 
     TokenStream::from(expanded)
 }
+
+// Text column for an enum of plain variants: each is stored as its kebab-case name, or #[db(rename = "...")].
+// Reading anything else is an error, never a fallback variant.
+#[proc_macro_derive(DbEnum, attributes(db))]
+pub fn db_enum_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let enum_name = &input.ident;
+
+    let variants = match unit_variants(&input) {
+        Ok(variants) => variants,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let idents: Vec<&Ident> = variants.iter().map(|(ident, _)| *ident).collect();
+    let values: Vec<&LitStr> = variants.iter().map(|(_, value)| value).collect();
+    let type_name = LitStr::new(&enum_name.to_string(), enum_name.span());
+
+    let expanded = quote! {
+        impl #enum_name {
+            // Every stored value, in declaration order
+            pub const DB_VALUES: &'static [&'static str] = &[#(#values),*];
+
+            pub fn as_db_str(&self) -> &'static str {
+                match self {
+                    #( #enum_name::#idents => #values, )*
+                }
+            }
+
+            pub fn from_db_str(value: &str) -> Option<Self> {
+                match value {
+                    #( #values => Some(#enum_name::#idents), )*
+                    _ => None,
+                }
+            }
+        }
+
+        impl rusqlite::types::ToSql for #enum_name {
+            fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+                Ok(rusqlite::types::ToSqlOutput::from(self.as_db_str()))
+            }
+        }
+
+        impl rusqlite::types::FromSql for #enum_name {
+            fn column_result(value: rusqlite::types::ValueRef<'_>) -> rusqlite::types::FromSqlResult<Self> {
+                let value = value.as_str()?;
+                #enum_name::from_db_str(value).ok_or_else(|| {
+                    rusqlite::types::FromSqlError::Other(format!("Invalid {} value '{}'", #type_name, value).into())
+                })
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+// Table from #[table_name = "tasks"], defaulting to the lowercased struct name
+fn table_name(input: &DeriveInput) -> syn::Result<String> {
+    let mut table_name = input.ident.to_string().to_lowercase();
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("table_name")) {
+        let value = match &attr.meta {
+            syn::Meta::NameValue(nv) => match &nv.value {
+                syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(litstr), .. }) => litstr.value(),
+                other => return Err(syn::Error::new_spanned(other, "table_name must be a string literal")),
+            },
+            _ => return Err(syn::Error::new_spanned(attr, "expected #[table_name = \"...\"]")),
+        };
+        if value.is_empty() {
+            return Err(syn::Error::new_spanned(attr, "table_name must not be empty"));
+        }
+        table_name = value;
+    }
+    Ok(table_name)
+}
+
+fn named_fields<'a>(input: &'a DeriveInput, derive: &str) -> syn::Result<&'a Punctuated<Field, Comma>> {
+    match &input.data {
+        Data::Struct(data_struct) => match &data_struct.fields {
+            Fields::Named(fields_named) => Ok(&fields_named.named),
+            _ => Err(syn::Error::new_spanned(&input.ident, format!("{} only works on structs with named fields", derive))),
+        },
+        _ => Err(syn::Error::new_spanned(&input.ident, format!("{} only works on structs", derive))),
+    }
+}
+
+// Variants with the value each is stored as; two variants can't share one
+fn unit_variants(input: &DeriveInput) -> syn::Result<Vec<(&Ident, LitStr)>> {
+    let Data::Enum(data_enum) = &input.data else {
+        return Err(syn::Error::new_spanned(&input.ident, "DbEnum only works on enums"));
+    };
+
+    let mut variants: Vec<(&Ident, LitStr)> = Vec::new();
+    for variant in &data_enum.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(variant, "DbEnum variants can't have fields"));
+        }
+
+        let mut value = LitStr::new(&kebab_case(&variant.ident.to_string()), variant.ident.span());
+        for attr in variant.attrs.iter().filter(|attr| attr.path().is_ident("db")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    value = meta.value()?.parse()?;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected #[db(rename = \"...\")]"))
+                }
+            })?;
+        }
+
+        if variants.iter().any(|(_, other)| other.value() == value.value()) {
+            return Err(syn::Error::new_spanned(variant, format!("another variant is already stored as \"{}\"", value.value())));
+        }
+        variants.push((&variant.ident, value));
+    }
+    Ok(variants)
+}
+
+// As serde's kebab-case: a dash before each capital but the first, then all lowercase
+fn kebab_case(name: &str) -> String {
+    let mut kebab = String::new();
+    for (i, c) in name.char_indices() {
+        if i > 0 && c.is_uppercase() {
+            kebab.push('-');
+        }
+        kebab.extend(c.to_lowercase());
+    }
+    kebab
+}
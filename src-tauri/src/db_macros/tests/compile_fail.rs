@@ -0,0 +1,6 @@
+// Shapes and attributes the derives must reject with a readable error instead of bad code
+#[test]
+fn rejected_inputs() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,36 @@
+// DbEnum values through an in-memory database, the way the app stores its enum columns
+use db_macros::DbEnum;
+use rusqlite::Connection;
+
+#[derive(Debug, Clone, PartialEq, DbEnum)]
+enum Frequency {
+    None,
+    Hourly,
+    #[db(rename = "every-3-hours")]
+    Every3Hours,
+    NotStarted,
+}
+
+fn stored(conn: &Connection, value: &str) -> rusqlite::Result<Frequency> {
+    conn.query_row("SELECT ?1", [value], |row| row.get(0))
+}
+
+#[test]
+fn variants_are_stored_as_kebab_case_or_their_rename() {
+    assert_eq!(Frequency::DB_VALUES, ["none", "hourly", "every-3-hours", "not-started"]);
+    assert_eq!(Frequency::NotStarted.as_db_str(), "not-started");
+    assert_eq!(Frequency::from_db_str("every-3-hours"), Some(Frequency::Every3Hours));
+    assert_eq!(Frequency::from_db_str("Hourly"), None);
+}
+
+#[test]
+fn values_round_trip_and_unknown_ones_are_errors() {
+    let conn = Connection::open_in_memory().unwrap();
+    for value in [Frequency::None, Frequency::Hourly, Frequency::Every3Hours, Frequency::NotStarted] {
+        let read: Frequency = conn.query_row("SELECT ?1", [&value], |row| row.get(0)).unwrap();
+        assert_eq!(read, value);
+    }
+
+    assert!(stored(&conn, "weekly").is_err());
+    assert!(conn.query_row("SELECT 3", [], |row| row.get::<_, Frequency>(0)).is_err());
+}
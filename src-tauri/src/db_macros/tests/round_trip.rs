@@ -0,0 +1,186 @@
+// Round-trips derived structs through an in-memory database, the way the app's db module uses them
+use db_macros::{Insertable, Queryable, Updatable};
+use proptest::prelude::*;
+use rusqlite::Connection;
+
+// Same traits as the app's db module; Updatable's derive refers to crate::db::Updatable
+mod db {
+    pub trait Insertable {
+        fn table_name() -> &'static str;
+        fn columns_values(&self) -> Vec<(&'static str, &dyn rusqlite::ToSql)>;
+    }
+
+    pub trait Updatable {
+        fn table_name() -> &'static str;
+        fn update_columns_values(&self) -> Vec<(&'static str, &dyn rusqlite::ToSql)>;
+    }
+}
+use db::{Insertable, Updatable};
+
+#[derive(Debug, Clone, PartialEq, Insertable, Queryable)]
+#[table_name = "items"]
+struct Item {
+    id: i64,
+    title: String,
+    notes: Option<String>,
+    estimate_minutes: Option<i32>,
+    done: bool,
+}
+
+// No attribute: the table is the lowercased struct name
+#[derive(Insertable)]
+struct Tag {
+    name: String,
+}
+
+#[derive(Updatable)]
+#[table_name = "items"]
+struct ItemUpdate {
+    title: Option<String>,
+    // Some(None) clears the column, None leaves it alone
+    notes: Option<Option<String>>,
+    estimate_minutes: Option<Option<i32>>,
+    done: bool,
+}
+
+fn open() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute_batch(
+        "CREATE TABLE items (
+            id INTEGER PRIMARY KEY,
+            title TEXT NOT NULL,
+            notes TEXT,
+            estimate_minutes INTEGER,
+            done INTEGER NOT NULL
+        );",
+    ).unwrap();
+    conn
+}
+
+fn insert<T: Insertable>(conn: &Connection, value: &T) {
+    let cols_vals = value.columns_values();
+    let columns: Vec<&str> = cols_vals.iter().map(|(col, _)| *col).collect();
+    let placeholders = vec!["?"; cols_vals.len()].join(", ");
+    let values: Vec<&dyn rusqlite::ToSql> = cols_vals.iter().map(|(_, v)| *v).collect();
+
+    let sql = format!("INSERT INTO {} ({}) VALUES ({})", T::table_name(), columns.join(", "), placeholders);
+    conn.execute(&sql, &values[..]).unwrap();
+}
+
+fn update<T: Updatable>(conn: &Connection, id: i64, value: &T) {
+    let cols_vals = value.update_columns_values();
+    let set_clauses: Vec<String> = cols_vals.iter().map(|(col, _)| format!("{} = ?", col)).collect();
+    let mut values: Vec<&dyn rusqlite::ToSql> = cols_vals.iter().map(|(_, v)| *v).collect();
+    values.push(&id);
+
+    let sql = format!("UPDATE {} SET {} WHERE id = ?", T::table_name(), set_clauses.join(", "));
+    conn.execute(&sql, &values[..]).unwrap();
+}
+
+fn load(conn: &Connection, id: i64) -> Item {
+    conn.query_row(
+        "SELECT id, title, notes, estimate_minutes, done FROM items WHERE id = ?1",
+        [id],
+        Item::from_row,
+    ).unwrap()
+}
+
+fn item() -> Item {
+    Item {
+        id: 1,
+        title: "Write report".to_string(),
+        notes: Some("Draft first".to_string()),
+        estimate_minutes: Some(45),
+        done: false,
+    }
+}
+
+#[test]
+fn table_name_comes_from_the_attribute_or_the_struct() {
+    assert_eq!(<Item as Insertable>::table_name(), "items");
+    assert_eq!(<ItemUpdate as Updatable>::table_name(), "items");
+    assert_eq!(Tag::table_name(), "tag");
+}
+
+#[test]
+fn insertable_lists_every_field_in_order() {
+    let columns: Vec<&str> = item().columns_values().iter().map(|(col, _)| *col).collect();
+    assert_eq!(columns, ["id", "title", "notes", "estimate_minutes", "done"]);
+}
+
+#[test]
+fn none_is_stored_as_null() {
+    let conn = open();
+    let item = Item { notes: None, estimate_minutes: None, ..item() };
+    insert(&conn, &item);
+
+    let notes_is_null: bool = conn.query_row("SELECT notes IS NULL FROM items WHERE id = 1", [], |row| row.get(0)).unwrap();
+    assert!(notes_is_null);
+    assert_eq!(load(&conn, 1), item);
+}
+
+#[test]
+fn updatable_skips_none_and_clears_nested_none() {
+    let conn = open();
+    insert(&conn, &item());
+
+    let changes = ItemUpdate {
+        title: None,
+        notes: Some(None),
+        estimate_minutes: Some(Some(90)),
+        done: true,
+    };
+    let columns: Vec<&str> = changes.update_columns_values().iter().map(|(col, _)| *col).collect();
+    assert_eq!(columns, ["notes", "estimate_minutes", "done"]);
+
+    update(&conn, 1, &changes);
+    assert_eq!(load(&conn, 1), Item {
+        notes: None,
+        estimate_minutes: Some(90),
+        done: true,
+        ..item()
+    });
+}
+
+#[test]
+fn non_option_fields_are_always_updated() {
+    let changes = ItemUpdate { title: None, notes: None, estimate_minutes: None, done: false };
+    let columns: Vec<&str> = changes.update_columns_values().iter().map(|(col, _)| *col).collect();
+    assert_eq!(columns, ["done"]);
+}
+
+fn arb_item() -> impl Strategy<Value = Item> {
+    (any::<i64>(), ".*", proptest::option::of(".*"), any::<Option<i32>>(), any::<bool>())
+        .prop_map(|(id, title, notes, estimate_minutes, done)| Item { id, title, notes, estimate_minutes, done })
+}
+
+proptest! {
+    #[test]
+    fn insert_then_from_row_round_trips(item in arb_item()) {
+        let conn = open();
+        insert(&conn, &item);
+        prop_assert_eq!(load(&conn, item.id), item);
+    }
+
+    #[test]
+    fn update_matches_applying_the_changes_by_hand(
+        item in arb_item(),
+        title in proptest::option::of(".*"),
+        notes in proptest::option::of(proptest::option::of(".*")),
+        estimate_minutes in any::<Option<Option<i32>>>(),
+        done in any::<bool>(),
+    ) {
+        let conn = open();
+        insert(&conn, &item);
+
+        let expected = Item {
+            id: item.id,
+            title: title.clone().unwrap_or(item.title.clone()),
+            notes: notes.clone().unwrap_or(item.notes.clone()),
+            estimate_minutes: estimate_minutes.unwrap_or(item.estimate_minutes),
+            done,
+        };
+        update(&conn, item.id, &ItemUpdate { title, notes, estimate_minutes, done });
+        prop_assert_eq!(load(&conn, item.id), expected);
+    }
+}
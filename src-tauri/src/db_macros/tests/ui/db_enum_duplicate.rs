@@ -0,0 +1,10 @@
+use db_macros::DbEnum;
+
+#[derive(DbEnum)]
+enum Status {
+    NotStarted,
+    #[db(rename = "not-started")]
+    Fresh,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use db_macros::DbEnum;
+
+#[derive(DbEnum)]
+enum Reminder {
+    None,
+    Every { minutes: i64 },
+}
+
+fn main() {}
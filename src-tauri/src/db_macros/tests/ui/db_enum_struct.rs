@@ -0,0 +1,8 @@
+use db_macros::DbEnum;
+
+#[derive(DbEnum)]
+struct Status {
+    name: String,
+}
+
+fn main() {}
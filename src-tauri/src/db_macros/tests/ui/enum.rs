@@ -0,0 +1,9 @@
+use db_macros::Insertable;
+
+#[derive(Insertable)]
+enum Status {
+    NotStarted,
+    Completed,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use db_macros::Insertable;
+
+#[derive(Insertable)]
+#[table_name = ""]
+struct Task {
+    title: String,
+}
+
+fn main() {}
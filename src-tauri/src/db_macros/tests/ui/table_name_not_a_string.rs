@@ -0,0 +1,9 @@
+use db_macros::Updatable;
+
+#[derive(Updatable)]
+#[table_name = 5]
+struct TaskUpdate {
+    title: Option<String>,
+}
+
+fn main() {}
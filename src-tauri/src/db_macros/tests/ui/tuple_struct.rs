@@ -0,0 +1,6 @@
+use db_macros::Queryable;
+
+#[derive(Queryable)]
+struct Pair(i64, String);
+
+fn main() {}
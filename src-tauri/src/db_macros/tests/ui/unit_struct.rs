@@ -0,0 +1,6 @@
+use db_macros::Updatable;
+
+#[derive(Updatable)]
+struct Nothing;
+
+fn main() {}
@@ -0,0 +1,110 @@
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, Url};
+use tauri_plugin_deep_link::DeepLinkExt;
+use crate::db::Database;
+use crate::services::{metrics_service, task_service};
+use crate::structs::dto::QuickAddData;
+use crate::structs::task_struct::TaskId;
+use crate::window_manager;
+use tracing::{info, warn, error};
+
+pub const SCHEME: &str = "myhandler";
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct NavigatePayload {
+    route: String,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ConfirmCompletePayload {
+    task_id: TaskId,
+}
+
+// Link that opens a task in the app (used in calendar event descriptions)
+pub fn task_url(task_id: &str) -> String {
+    format!("{}://task/{}", SCHEME, task_id)
+}
+
+// Register the URL scheme and route incoming links
+pub fn init_deep_links(app: &AppHandle) {
+    // Windows and Linux only pick up the scheme once it is registered at runtime
+    #[cfg(any(windows, target_os = "linux"))]
+    if let Err(e) = app.deep_link().register_all() {
+        error!("Failed to register deep link scheme: {}", e);
+    }
+    
+    let handle = app.clone();
+    app.deep_link().on_open_url(move |event| {
+        for url in event.urls() {
+            handle_url(&handle, &url);
+        }
+    });
+    
+    // App may have been launched by a link
+    if let Ok(Some(urls)) = app.deep_link().get_current() {
+        for url in urls {
+            handle_url(app, &url);
+        }
+    }
+}
+
+// Supported links:
+//   myhandler://task/{id}            open a task
+//   myhandler://task/{id}/complete   open a task and ask whether to complete it; any page or app
+//                                    can open a link, so it never changes a task by itself
+//   myhandler://new?text=...         quick-add a task from text
+pub fn handle_url(app: &AppHandle, url: &Url) {
+    info!("Handling deep link: {}", url);
+    
+    if url.scheme() != SCHEME {
+        warn!("Ignoring link with unknown scheme: {}", url.scheme());
+        return;
+    }
+    metrics_service::record_feature("deep-link");
+    
+    let segments: Vec<&str> = url.path_segments()
+        .map(|segments| segments.filter(|s| !s.is_empty()).collect())
+        .unwrap_or_default();
+    
+    let result = match (url.host_str(), segments.as_slice()) {
+        (Some("task"), [id]) => {
+            navigate(app, format!("/task/{}", id));
+            Ok(())
+        }
+        (Some("task"), [id, "complete"]) => id.parse::<TaskId>().map(|id| {
+            navigate(app, format!("/task/{}", id));
+            let _ = app.emit("deep-link-confirm-complete", ConfirmCompletePayload { task_id: id });
+        }),
+        (Some("new"), []) => {
+            let text = url.query_pairs()
+                .find(|(key, _)| key == "text" || key == "title")
+                .map(|(_, value)| value.into_owned())
+                .unwrap_or_default();
+            create_task(app, text)
+        }
+        _ => Err(format!("Unsupported link: {}", url)),
+    };
+    
+    if let Err(e) = result {
+        error!("Failed to handle deep link: {}", e);
+    }
+}
+
+fn navigate(app: &AppHandle, route: String) {
+    if let Some(window) = app.get_webview_window(window_manager::MAIN_WINDOW) {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+    let _ = app.emit("deep-link-navigate", NavigatePayload { route });
+}
+
+fn create_task(app: &AppHandle, text: String) -> Result<(), String> {
+    let db = app.try_state::<Database>()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    let task = task_service::quick_add_task(QuickAddData { text }, &db, app)?;
+    navigate(app, format!("/task/{}", task.id));
+    Ok(())
+}
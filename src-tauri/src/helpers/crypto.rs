@@ -0,0 +1,132 @@
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use rand::RngCore;
+use sha2::Sha256;
+
+pub const SALT_LENGTH: usize = 16;
+const KEY_LENGTH: usize = 32;
+const NONCE_LENGTH: usize = 12;
+// OWASP's 2023 recommendation for PBKDF2-HMAC-SHA256
+const PBKDF2_ROUNDS: u32 = 600_000;
+// First byte of every sealed payload, so the format can change later
+const FORMAT_VERSION: u8 = 1;
+// Start of a text column sealed in place, followed by the sealed payload in hex
+pub const SEALED_PREFIX: &str = "sealed:";
+
+/// Key derived from a passphrase; every device with the same passphrase and salt gets the same key
+pub struct SecretKey([u8; KEY_LENGTH]);
+
+impl SecretKey {
+    /// Random key, for secrets that are stored rather than typed
+    pub fn random() -> Self {
+        let mut key = [0u8; KEY_LENGTH];
+        rand::thread_rng().fill_bytes(&mut key);
+        SecretKey(key)
+    }
+    
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let key = bytes.try_into()
+            .map_err(|_| format!("Secret key must be {} bytes", KEY_LENGTH))?;
+        Ok(SecretKey(key))
+    }
+    
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+pub fn random_salt() -> [u8; SALT_LENGTH] {
+    let mut salt = [0u8; SALT_LENGTH];
+    rand::thread_rng().fill_bytes(&mut salt);
+    salt
+}
+
+/// Slow on purpose: derive once per sync, not per payload
+pub fn derive_key(passphrase: &str, salt: &[u8]) -> SecretKey {
+    let mut key = [0u8; KEY_LENGTH];
+    pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, PBKDF2_ROUNDS, &mut key);
+    SecretKey(key)
+}
+
+/// Argon2id hash of an app lock PIN in PHC form, with its own salt and parameters
+pub fn hash_pin(pin: &str) -> Result<String, String> {
+    let salt = SaltString::encode_b64(&random_salt())
+        .map_err(|e| format!("Failed to hash PIN: {}", e))?;
+    Argon2::default().hash_password(pin.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| format!("Failed to hash PIN: {}", e))
+}
+
+pub fn verify_pin(pin: &str, hash: &str) -> Result<bool, String> {
+    let hash = PasswordHash::new(hash)
+        .map_err(|e| format!("Invalid PIN hash: {}", e))?;
+    Ok(Argon2::default().verify_password(pin.as_bytes(), &hash).is_ok())
+}
+
+/// Key for data sealed under a PIN; `salt` must not be the one in the PIN's hash
+pub fn derive_pin_key(pin: &str, salt: &[u8]) -> Result<SecretKey, String> {
+    let mut key = [0u8; KEY_LENGTH];
+    Argon2::default().hash_password_into(pin.as_bytes(), salt, &mut key)
+        .map_err(|e| format!("Failed to derive key: {}", e))?;
+    Ok(SecretKey(key))
+}
+
+/// Version byte, random nonce, then the ChaCha20-Poly1305 ciphertext
+pub fn seal(key: &SecretKey, plaintext: &[u8]) -> Result<Vec<u8>, String> {
+    let mut nonce = [0u8; NONCE_LENGTH];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&key.0));
+    let ciphertext = cipher.encrypt(Nonce::from_slice(&nonce), plaintext)
+        .map_err(|e| format!("Failed to encrypt data: {}", e))?;
+    
+    let mut sealed = Vec::with_capacity(1 + NONCE_LENGTH + ciphertext.len());
+    sealed.push(FORMAT_VERSION);
+    sealed.extend_from_slice(&nonce);
+    sealed.extend_from_slice(&ciphertext);
+    Ok(sealed)
+}
+
+/// Fails on a wrong key as well as on tampered or truncated data
+pub fn open(key: &SecretKey, sealed: &[u8]) -> Result<Vec<u8>, String> {
+    if sealed.len() < 1 + NONCE_LENGTH || sealed[0] != FORMAT_VERSION {
+        return Err("Unrecognized encrypted data".to_string());
+    }
+    let (nonce, ciphertext) = sealed[1..].split_at(NONCE_LENGTH);
+    
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&key.0));
+    cipher.decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|e| format!("Failed to decrypt data: {}", e))
+}
+
+/// Text as stored in a sealed column: the prefix, then the sealed bytes in hex
+pub fn seal_text(key: &SecretKey, text: &str) -> Result<String, String> {
+    Ok(format!("{}{}", SEALED_PREFIX, to_hex(&seal(key, text.as_bytes())?)))
+}
+
+/// Text that isn't sealed comes back unchanged
+pub fn open_text(key: &SecretKey, text: &str) -> Result<String, String> {
+    let Some(hex) = text.strip_prefix(SEALED_PREFIX) else {
+        return Ok(text.to_string());
+    };
+    String::from_utf8(open(key, &from_hex(hex)?)?)
+        .map_err(|_| "Sealed text isn't valid UTF-8".to_string())
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+pub fn from_hex(hex: &str) -> Result<Vec<u8>, String> {
+    if hex.len() % 2 != 0 {
+        return Err("Invalid hex string".to_string());
+    }
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| std::str::from_utf8(pair).ok()
+            .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+            .ok_or_else(|| "Invalid hex string".to_string()))
+        .collect()
+}
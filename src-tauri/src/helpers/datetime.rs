@@ -0,0 +1,175 @@
+use std::fmt;
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+
+// Local date-times without an offset; seconds and fractions are optional
+const LOCAL_FORMATS: [&str; 4] = [
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%d %H:%M",
+];
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+// Why a date field was rejected; `field` is the payload key the UI can point at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DateTimeError {
+    Empty { field: &'static str },
+    Unrecognized { field: &'static str, value: String },
+    // Looks like a date but names a day or instant that doesn't exist
+    OutOfRange { field: &'static str, value: String },
+}
+
+impl fmt::Display for DateTimeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DateTimeError::Empty { field } => write!(f, "Invalid {}: value is empty", field),
+            DateTimeError::Unrecognized { field, value } => write!(
+                f,
+                "Invalid {}: '{}' is not an ISO 8601 date or datetime, or epoch milliseconds",
+                field, value
+            ),
+            DateTimeError::OutOfRange { field, value } => write!(f, "Invalid {}: '{}' is out of range", field, value),
+        }
+    }
+}
+
+impl std::error::Error for DateTimeError {}
+
+// Services report errors as strings
+impl From<DateTimeError> for String {
+    fn from(err: DateTimeError) -> Self {
+        err.to_string()
+    }
+}
+
+/// Parse a date field sent by a client into UTC. Accepts RFC 3339, local date-times with a `T`
+/// or a space, date-only values (midnight) and epoch milliseconds; anything without its own
+/// offset is read at `offset`.
+pub fn parse_datetime(field: &'static str, value: &str, offset: FixedOffset) -> Result<DateTime<Utc>, DateTimeError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(DateTimeError::Empty { field });
+    }
+    let out_of_range = || DateTimeError::OutOfRange { field, value: value.to_string() };
+    
+    if let Ok(date_time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(date_time.with_timezone(&Utc));
+    }
+    
+    if is_epoch_millis(value) {
+        return value.parse::<i64>().ok()
+            .and_then(DateTime::from_timestamp_millis)
+            .ok_or_else(out_of_range);
+    }
+    
+    let local = LOCAL_FORMATS.iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .or_else(|| NaiveDate::parse_from_str(value, DATE_FORMAT).ok().map(|date| date.and_time(NaiveTime::MIN)));
+    match local {
+        Some(local) => offset.from_local_datetime(&local)
+            .single()
+            .map(|date_time| date_time.with_timezone(&Utc))
+            .ok_or_else(out_of_range),
+        // Right shape, impossible day (2026-02-30)
+        None if looks_like_date(value) => Err(out_of_range()),
+        None => Err(DateTimeError::Unrecognized { field, value: value.to_string() }),
+    }
+}
+
+/// Parse a deadline field: like `parse_datetime`, except a date-only value is due at `default_time`
+/// (from settings) that day instead of at midnight, which would make it due before the day starts.
+pub fn parse_deadline(
+    field: &'static str,
+    value: &str,
+    offset: FixedOffset,
+    default_time: NaiveTime,
+) -> Result<DateTime<Utc>, DateTimeError> {
+    let value = value.trim();
+    match NaiveDate::parse_from_str(value, DATE_FORMAT) {
+        Ok(day) => deadline_on(day, default_time, &offset)
+            .ok_or_else(|| DateTimeError::OutOfRange { field, value: value.to_string() }),
+        Err(_) => parse_datetime(field, value, offset),
+    }
+}
+
+/// Deadline for a day without a time of day; every date-only deadline (payloads, CLI, imports) goes
+/// through here. None when `zone` skips that time on that day.
+pub fn deadline_on<Tz: TimeZone>(day: NaiveDate, time: NaiveTime, zone: &Tz) -> Option<DateTime<Utc>> {
+    zone.from_local_datetime(&day.and_time(time))
+        .earliest()
+        .map(|date_time| date_time.with_timezone(&Utc))
+}
+
+/// The offset written in an RFC 3339 value, if it has one
+pub fn explicit_offset(value: &str) -> Option<FixedOffset> {
+    DateTime::parse_from_rfc3339(value.trim()).ok().map(|date_time| *date_time.offset())
+}
+
+fn is_epoch_millis(value: &str) -> bool {
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn looks_like_date(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    bytes.len() >= 10
+        && bytes[..4].iter().all(u8::is_ascii_digit)
+        && bytes[4] == b'-'
+        && bytes[5..7].iter().all(u8::is_ascii_digit)
+        && bytes[7] == b'-'
+        && bytes[8..10].iter().all(u8::is_ascii_digit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn utc(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+    
+    fn plus_five() -> FixedOffset {
+        FixedOffset::east_opt(5 * 3600).unwrap()
+    }
+    
+    #[test]
+    fn rfc3339_keeps_its_own_offset() {
+        let parsed = parse_datetime("deadline", "2026-03-10T15:00:00-08:00", plus_five()).unwrap();
+        assert_eq!(parsed, utc("2026-03-10T23:00:00Z"));
+    }
+    
+    #[test]
+    fn local_forms_are_read_at_the_offset() {
+        for value in ["2026-03-10T15:00:00", "2026-03-10 15:00", "2026-03-10 15:00:00.250"] {
+            let parsed = parse_datetime("deadline", value, plus_five()).unwrap();
+            assert_eq!(parsed.format("%H:%M").to_string(), "10:00", "{}", value);
+        }
+        let midnight = parse_datetime("deadline", "2026-03-10", plus_five()).unwrap();
+        assert_eq!(midnight, utc("2026-03-09T19:00:00Z"));
+    }
+    
+    #[test]
+    fn date_only_deadlines_get_the_default_time() {
+        let six_pm = NaiveTime::from_hms_opt(18, 0, 0).unwrap();
+        let parsed = parse_deadline("deadline", " 2026-03-10 ", plus_five(), six_pm).unwrap();
+        assert_eq!(parsed, utc("2026-03-10T13:00:00Z"));
+        let timed = parse_deadline("deadline", "2026-03-10T09:30", plus_five(), six_pm).unwrap();
+        assert_eq!(timed, utc("2026-03-10T04:30:00Z"));
+    }
+    
+    #[test]
+    fn epoch_millis_are_utc() {
+        let parsed = parse_datetime("createdAt", "1773154800000", plus_five()).unwrap();
+        assert_eq!(parsed, utc("2026-03-10T15:00:00Z"));
+    }
+    
+    #[test]
+    fn errors_name_the_field() {
+        assert_eq!(parse_datetime("deadline", "  ", plus_five()), Err(DateTimeError::Empty { field: "deadline" }));
+        assert!(matches!(parse_datetime("deadline", "next tuesday", plus_five()), Err(DateTimeError::Unrecognized { .. })));
+        assert_eq!(
+            parse_datetime("createdAt", "2026-02-30", plus_five()),
+            Err(DateTimeError::OutOfRange { field: "createdAt", value: "2026-02-30".to_string() })
+        );
+    }
+}
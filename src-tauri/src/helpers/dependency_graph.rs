@@ -0,0 +1,136 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use crate::structs::dependency::TaskDependency;
+use crate::structs::task_struct::TaskId;
+
+// Why a link between two tasks was refused
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DependencyError {
+    SelfDependency { task_id: TaskId },
+    // The chain already there, each task depending on the next: from the task that would be
+    // depended on to the one that would depend on it
+    Cycle { path: Vec<TaskId> },
+}
+
+// Start of the error a refused link reaches the UI with, followed by the loop it would close, e.g.
+// "DEPENDENCY_CYCLE: <id> -> <id> -> <id>"; matched like calendar_service::REAUTH_NEEDED
+pub const DEPENDENCY_CYCLE: &str = "DEPENDENCY_CYCLE";
+
+impl DependencyError {
+    // The loop the link would close, from the task depended on back to it
+    pub fn cycle(&self) -> Vec<TaskId> {
+        match self {
+            DependencyError::SelfDependency { task_id } => vec![*task_id, *task_id],
+            DependencyError::Cycle { path } => path.iter().chain(path.first()).copied().collect(),
+        }
+    }
+}
+
+impl fmt::Display for DependencyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DependencyError::SelfDependency { task_id } => write!(f, "Task {} can't depend on itself", task_id),
+            DependencyError::Cycle { .. } => write!(f, "Link would create a dependency cycle: {}", join_path(&self.cycle())),
+        }
+    }
+}
+
+impl std::error::Error for DependencyError {}
+
+// Services report errors as strings; this one keeps its kind and path in a form the UI can parse
+impl From<DependencyError> for String {
+    fn from(err: DependencyError) -> Self {
+        format!("{}: {}", DEPENDENCY_CYCLE, join_path(&err.cycle()))
+    }
+}
+
+fn join_path(path: &[TaskId]) -> String {
+    path.iter().map(TaskId::to_string).collect::<Vec<_>>().join(" -> ")
+}
+
+// Refuse `link` when `links` already make its task a dependency of the task it would depend on
+pub fn check_link(links: &[TaskDependency], link: TaskDependency) -> Result<(), DependencyError> {
+    if link.task_id == link.depends_on_id {
+        return Err(DependencyError::SelfDependency { task_id: link.task_id });
+    }
+    
+    let mut depends_on: HashMap<TaskId, Vec<TaskId>> = HashMap::new();
+    for existing in links {
+        depends_on.entry(existing.task_id).or_default().push(existing.depends_on_id);
+    }
+    
+    // Breadth first from the task depended on, remembering how each task was reached
+    let mut came_from: HashMap<TaskId, TaskId> = HashMap::new();
+    let mut queue = VecDeque::from([link.depends_on_id]);
+    while let Some(current) = queue.pop_front() {
+        if current == link.task_id {
+            let mut path = vec![current];
+            let mut step = current;
+            while let Some(previous) = came_from.get(&step) {
+                path.push(*previous);
+                step = *previous;
+            }
+            path.reverse();
+            return Err(DependencyError::Cycle { path });
+        }
+        for next in depends_on.get(&current).into_iter().flatten() {
+            if *next != link.depends_on_id && !came_from.contains_key(next) {
+                came_from.insert(*next, current);
+                queue.push_back(*next);
+            }
+        }
+    }
+    Ok(())
+}
+
+// Tasks linked to `roots` through any number of links, in either direction; roots included
+pub fn connected(links: &[TaskDependency], roots: &[TaskId]) -> HashSet<TaskId> {
+    let mut neighbours: HashMap<TaskId, Vec<TaskId>> = HashMap::new();
+    for link in links {
+        neighbours.entry(link.task_id).or_default().push(link.depends_on_id);
+        neighbours.entry(link.depends_on_id).or_default().push(link.task_id);
+    }
+    
+    let mut seen: HashSet<TaskId> = roots.iter().copied().collect();
+    let mut queue: VecDeque<TaskId> = roots.iter().copied().collect();
+    while let Some(current) = queue.pop_front() {
+        for next in neighbours.get(&current).into_iter().flatten() {
+            if seen.insert(*next) {
+                queue.push_back(*next);
+            }
+        }
+    }
+    seen
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+    
+    fn id(n: u128) -> TaskId {
+        TaskId::from(Uuid::from_u128(n))
+    }
+    
+    fn link(task: u128, depends_on: u128) -> TaskDependency {
+        TaskDependency { task_id: id(task), depends_on_id: id(depends_on) }
+    }
+    
+    #[test]
+    fn links_closing_a_loop_are_refused() {
+        let links = [link(1, 2), link(2, 3)];
+        assert_eq!(check_link(&links, link(3, 1)), Err(DependencyError::Cycle { path: vec![id(1), id(2), id(3)] }));
+        assert_eq!(check_link(&links, link(2, 2)), Err(DependencyError::SelfDependency { task_id: id(2) }));
+        assert_eq!(check_link(&links, link(1, 3)), Ok(()));
+        
+        let refused: String = check_link(&links, link(3, 1)).unwrap_err().into();
+        assert_eq!(refused, format!("DEPENDENCY_CYCLE: {} -> {} -> {} -> {}", id(1), id(2), id(3), id(1)));
+    }
+    
+    #[test]
+    fn connected_follows_links_both_ways() {
+        let links = [link(1, 2), link(3, 2), link(4, 5)];
+        let expected: HashSet<TaskId> = [id(1), id(2), id(3)].into_iter().collect();
+        assert_eq!(connected(&links, &[id(3)]), expected);
+    }
+}
@@ -0,0 +1,170 @@
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Weekday};
+use crate::structs::settings::Locale;
+
+/// Dates, times and week labels for generated text (exports, notes, notifications), in the conventions of the locale from settings.
+/// File names, front matter and API fields stay ISO so they sort and parse the same everywhere.
+#[derive(Debug, Clone, Copy)]
+pub struct Formatter {
+    locale: Locale,
+}
+
+// Names are indexed from January and from Monday
+struct Names {
+    months: [&'static str; 12],
+    short_months: [&'static str; 12],
+    weekdays: [&'static str; 7],
+    week: &'static str,
+}
+
+const ENGLISH: Names = Names {
+    months: ["January", "February", "March", "April", "May", "June", "July", "August", "September", "October", "November", "December"],
+    short_months: ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"],
+    weekdays: ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"],
+    week: "Week",
+};
+
+const GERMAN: Names = Names {
+    months: ["Januar", "Februar", "März", "April", "Mai", "Juni", "Juli", "August", "September", "Oktober", "November", "Dezember"],
+    short_months: ["Jan.", "Feb.", "März", "Apr.", "Mai", "Juni", "Juli", "Aug.", "Sept.", "Okt.", "Nov.", "Dez."],
+    weekdays: ["Montag", "Dienstag", "Mittwoch", "Donnerstag", "Freitag", "Samstag", "Sonntag"],
+    week: "KW",
+};
+
+const FRENCH: Names = Names {
+    months: ["janvier", "février", "mars", "avril", "mai", "juin", "juillet", "août", "septembre", "octobre", "novembre", "décembre"],
+    short_months: ["janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.", "nov.", "déc."],
+    weekdays: ["lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche"],
+    week: "Semaine",
+};
+
+const SPANISH: Names = Names {
+    months: ["enero", "febrero", "marzo", "abril", "mayo", "junio", "julio", "agosto", "septiembre", "octubre", "noviembre", "diciembre"],
+    short_months: ["ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sept", "oct", "nov", "dic"],
+    weekdays: ["lunes", "martes", "miércoles", "jueves", "viernes", "sábado", "domingo"],
+    week: "Semana",
+};
+
+impl Formatter {
+    pub fn new(locale: Locale) -> Self {
+        Formatter { locale }
+    }
+    
+    fn names(&self) -> &'static Names {
+        match self.locale {
+            Locale::EnUs | Locale::EnGb => &ENGLISH,
+            Locale::De => &GERMAN,
+            Locale::Fr => &FRENCH,
+            Locale::Es => &SPANISH,
+        }
+    }
+    
+    /// First day of the week: Sunday in the US, Monday elsewhere
+    pub fn week_start(&self) -> Weekday {
+        match self.locale {
+            Locale::EnUs => Weekday::Sun,
+            _ => Weekday::Mon,
+        }
+    }
+    
+    /// Day with its weekday and year, e.g. "Saturday, October 17, 2026" or "Samstag, 17. Oktober 2026"
+    pub fn long_date(&self, day: NaiveDate) -> String {
+        let names = self.names();
+        let weekday = names.weekdays[day.weekday().num_days_from_monday() as usize];
+        let month = names.months[day.month0() as usize];
+        match self.locale {
+            Locale::EnUs => format!("{}, {} {}, {}", weekday, month, day.day(), day.year()),
+            Locale::EnGb => format!("{}, {} {} {}", weekday, day.day(), month, day.year()),
+            Locale::De => format!("{}, {}. {} {}", weekday, day.day(), month, day.year()),
+            Locale::Fr => format!("{} {} {} {}", weekday, day.day(), month, day.year()),
+            Locale::Es => format!("{}, {} de {} de {}", weekday, day.day(), month, day.year()),
+        }
+    }
+    
+    /// Day and month only, e.g. "Oct 17" or "17 oct."
+    pub fn short_date(&self, day: NaiveDate) -> String {
+        let month = self.names().short_months[day.month0() as usize];
+        match self.locale {
+            Locale::EnUs => format!("{} {}", month, day.day()),
+            Locale::De => format!("{}. {}", day.day(), month),
+            Locale::EnGb | Locale::Fr | Locale::Es => format!("{} {}", day.day(), month),
+        }
+    }
+    
+    /// All-digit date, e.g. "10/17/2026", "17/10/2026" or "17.10.2026"
+    pub fn numeric_date(&self, day: NaiveDate) -> String {
+        match self.locale {
+            Locale::EnUs => format!("{}/{}/{}", day.month(), day.day(), day.year()),
+            Locale::De => format!("{:02}.{:02}.{}", day.day(), day.month(), day.year()),
+            Locale::EnGb | Locale::Fr | Locale::Es => format!("{:02}/{:02}/{}", day.day(), day.month(), day.year()),
+        }
+    }
+    
+    /// Clock time: 12-hour in the US, 24-hour elsewhere
+    pub fn time(&self, time: NaiveTime) -> String {
+        match self.locale {
+            Locale::EnUs => {
+                let (pm, hour) = time.hour12();
+                format!("{}:{:02} {}", hour, time.minute(), if pm { "PM" } else { "AM" })
+            }
+            _ => format!("{:02}:{:02}", time.hour(), time.minute()),
+        }
+    }
+    
+    /// Short date and time in the same year, e.g. "Oct 17, 2:30 PM"
+    pub fn short_date_time(&self, moment: NaiveDateTime) -> String {
+        let separator = if self.locale == Locale::Fr { " " } else { ", " };
+        format!("{}{}{}", self.short_date(moment.date()), separator, self.time(moment.time()))
+    }
+    
+    /// Numeric date and time, e.g. "10/17/2026 2:30 PM"
+    pub fn date_time(&self, moment: NaiveDateTime) -> String {
+        format!("{} {}", self.numeric_date(moment.date()), self.time(moment.time()))
+    }
+    
+    /// Heading for the week starting on `first_day`; ISO week numbers where weeks start on Monday
+    pub fn week_label(&self, first_day: NaiveDate) -> String {
+        let last_day = first_day + Duration::days(6);
+        let range = format!("{} – {}", self.short_date(first_day), self.short_date(last_day));
+        match self.week_start() {
+            Weekday::Mon => {
+                let week = first_day.iso_week();
+                format!("{} {}, {} ({})", self.names().week, week.week(), week.year(), range)
+            }
+            // Only US weeks start on Sunday; those have no common numbering
+            _ => format!("Week of {}, {}", range, last_day.year()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn day(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+    
+    #[test]
+    fn dates_follow_the_locale() {
+        let date = day("2026-03-07");
+        assert_eq!(Formatter::new(Locale::EnUs).long_date(date), "Saturday, March 7, 2026");
+        assert_eq!(Formatter::new(Locale::EnGb).long_date(date), "Saturday, 7 March 2026");
+        assert_eq!(Formatter::new(Locale::De).long_date(date), "Samstag, 7. März 2026");
+        assert_eq!(Formatter::new(Locale::Es).long_date(date), "sábado, 7 de marzo de 2026");
+        assert_eq!(Formatter::new(Locale::EnUs).numeric_date(date), "3/7/2026");
+        assert_eq!(Formatter::new(Locale::De).numeric_date(date), "07.03.2026");
+    }
+    
+    #[test]
+    fn us_times_are_twelve_hour() {
+        let moment = day("2026-03-07").and_hms_opt(0, 5, 0).unwrap();
+        assert_eq!(Formatter::new(Locale::EnUs).short_date_time(moment), "Mar 7, 12:05 AM");
+        assert_eq!(Formatter::new(Locale::Fr).short_date_time(moment), "7 mars 00:05");
+    }
+    
+    #[test]
+    fn week_labels_follow_the_week_start() {
+        assert_eq!(Formatter::new(Locale::EnGb).week_label(day("2026-03-02")), "Week 10, 2026 (2 Mar – 8 Mar)");
+        assert_eq!(Formatter::new(Locale::EnUs).week_label(day("2026-12-27")), "Week of Dec 27 – Jan 2, 2027");
+    }
+}
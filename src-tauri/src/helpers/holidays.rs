@@ -0,0 +1,259 @@
+use std::collections::HashSet;
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use crate::structs::settings::HolidayRegion;
+
+// Longer events in an ICS file are vacations or mistakes, not holidays
+const MAX_EVENT_DAYS: i64 = 31;
+
+// How a region moves a holiday that falls on a weekend
+#[derive(Clone, Copy)]
+enum Substitute {
+    None,
+    // Saturday to Friday, Sunday to Monday (US federal)
+    NearestWeekday,
+    // The next weekday that isn't already a holiday (UK bank holidays)
+    NextFreeWeekday,
+}
+
+/// Public holidays of a bundled regional list in `year`, with weekend substitutes, in date order.
+/// National holidays only; days off that differ by state or region need an ICS import.
+pub fn region_holidays(region: HolidayRegion, year: i32) -> Vec<(NaiveDate, String)> {
+    let date = |month, day| NaiveDate::from_ymd_opt(year, month, day);
+    let easter = easter_sunday(year);
+    let after_easter = |days| easter.map(|easter| easter + Duration::days(days));
+    let nth = |month, weekday, n| NaiveDate::from_weekday_of_month_opt(year, month, weekday, n);
+    let last = |month, weekday| last_weekday_of_month(year, month, weekday);
+    
+    let (holidays, substitute) = match region {
+        HolidayRegion::Us => (vec![
+            (date(1, 1), "New Year's Day"),
+            (nth(1, Weekday::Mon, 3), "Martin Luther King Jr. Day"),
+            (nth(2, Weekday::Mon, 3), "Washington's Birthday"),
+            (last(5, Weekday::Mon), "Memorial Day"),
+            (date(6, 19), "Juneteenth"),
+            (date(7, 4), "Independence Day"),
+            (nth(9, Weekday::Mon, 1), "Labor Day"),
+            (nth(10, Weekday::Mon, 2), "Columbus Day"),
+            (date(11, 11), "Veterans Day"),
+            (nth(11, Weekday::Thu, 4), "Thanksgiving Day"),
+            (date(12, 25), "Christmas Day"),
+        ], Substitute::NearestWeekday),
+        HolidayRegion::Gb => (vec![
+            (date(1, 1), "New Year's Day"),
+            (after_easter(-2), "Good Friday"),
+            (after_easter(1), "Easter Monday"),
+            (nth(5, Weekday::Mon, 1), "Early May bank holiday"),
+            (last(5, Weekday::Mon), "Spring bank holiday"),
+            (last(8, Weekday::Mon), "Summer bank holiday"),
+            (date(12, 25), "Christmas Day"),
+            (date(12, 26), "Boxing Day"),
+        ], Substitute::NextFreeWeekday),
+        HolidayRegion::De => (vec![
+            (date(1, 1), "Neujahr"),
+            (after_easter(-2), "Karfreitag"),
+            (after_easter(1), "Ostermontag"),
+            (date(5, 1), "Tag der Arbeit"),
+            (after_easter(39), "Christi Himmelfahrt"),
+            (after_easter(50), "Pfingstmontag"),
+            (date(10, 3), "Tag der Deutschen Einheit"),
+            (date(12, 25), "1. Weihnachtstag"),
+            (date(12, 26), "2. Weihnachtstag"),
+        ], Substitute::None),
+        HolidayRegion::Fr => (vec![
+            (date(1, 1), "Jour de l'an"),
+            (after_easter(1), "Lundi de Pâques"),
+            (date(5, 1), "Fête du Travail"),
+            (date(5, 8), "Victoire 1945"),
+            (after_easter(39), "Ascension"),
+            (after_easter(50), "Lundi de Pentecôte"),
+            (date(7, 14), "Fête nationale"),
+            (date(8, 15), "Assomption"),
+            (date(11, 1), "Toussaint"),
+            (date(11, 11), "Armistice 1918"),
+            (date(12, 25), "Noël"),
+        ], Substitute::None),
+        HolidayRegion::Es => (vec![
+            (date(1, 1), "Año Nuevo"),
+            (date(1, 6), "Epifanía del Señor"),
+            (after_easter(-2), "Viernes Santo"),
+            (date(5, 1), "Fiesta del Trabajo"),
+            (date(8, 15), "Asunción de la Virgen"),
+            (date(10, 12), "Fiesta Nacional de España"),
+            (date(11, 1), "Todos los Santos"),
+            (date(12, 6), "Día de la Constitución"),
+            (date(12, 8), "Inmaculada Concepción"),
+            (date(12, 25), "Natividad del Señor"),
+        ], Substitute::None),
+    };
+    
+    let mut days: Vec<(NaiveDate, String)> = holidays.into_iter()
+        .filter_map(|(date, name)| date.map(|date| (date, name.to_string())))
+        .collect();
+    
+    // Substitutes are added next to the holiday itself, which stays listed on its own date
+    let mut taken: HashSet<NaiveDate> = days.iter().map(|(date, _)| *date).collect();
+    let mut substitutes = Vec::new();
+    for (date, name) in &days {
+        let moved = match (substitute, date.weekday()) {
+            (Substitute::NearestWeekday, Weekday::Sat) => Some((*date - Duration::days(1), "observed")),
+            (Substitute::NearestWeekday, Weekday::Sun) => Some((*date + Duration::days(1), "observed")),
+            (Substitute::NextFreeWeekday, Weekday::Sat | Weekday::Sun) => date.iter_days()
+                .skip(1)
+                .find(|day| !is_weekend(*day) && !taken.contains(day))
+                .map(|day| (day, "substitute day")),
+            _ => None,
+        };
+        if let Some((moved, label)) = moved {
+            taken.insert(moved);
+            substitutes.push((moved, format!("{} ({})", name, label)));
+        }
+    }
+    days.extend(substitutes);
+    days.sort();
+    days
+}
+
+pub fn is_weekend(day: NaiveDate) -> bool {
+    matches!(day.weekday(), Weekday::Sat | Weekday::Sun)
+}
+
+/// Events of an ICS calendar as (day, summary), one entry per day of a multi-day event.
+/// Recurring events count on their first date only; holiday feeds list each year's dates separately.
+pub fn parse_ics_holidays(content: &str) -> Result<Vec<(NaiveDate, String)>, String> {
+    // Long lines are folded onto continuation lines that start with a space or tab
+    let mut lines: Vec<String> = Vec::new();
+    for line in content.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    if lines.first().map(|line| line.trim()) != Some("BEGIN:VCALENDAR") {
+        return Err("Not an ICS calendar file".to_string());
+    }
+    
+    let mut holidays = Vec::new();
+    let mut event: Option<(Option<NaiveDate>, Option<NaiveDate>, Option<String>)> = None;
+    for line in &lines {
+        let Some((property, value)) = line.split_once(':') else {
+            continue;
+        };
+        let name = property.split(';').next().unwrap_or(property).to_ascii_uppercase();
+        match (name.as_str(), value.trim(), event.as_mut()) {
+            ("BEGIN", "VEVENT", _) => event = Some((None, None, None)),
+            ("END", "VEVENT", Some(_)) => {
+                if let Some((Some(start), end, summary)) = event.take() {
+                    let summary = summary.filter(|s| !s.is_empty()).unwrap_or_else(|| "Holiday".to_string());
+                    // DTEND is exclusive; events without one, or ending the day they start, cover one day
+                    let days = end.map_or(1, |end| (end - start).num_days().max(1));
+                    if days > MAX_EVENT_DAYS {
+                        return Err(format!("Event '{}' on {} lasts {} days; holidays can't be longer than {}", summary, start, days, MAX_EVENT_DAYS));
+                    }
+                    holidays.extend(start.iter_days().take(days as usize).map(|day| (day, summary.clone())));
+                }
+                event = None;
+            }
+            ("DTSTART", value, Some((start, _, _))) => *start = Some(ics_date(value)?),
+            ("DTEND", value, Some((_, end, _))) => *end = Some(ics_date(value)?),
+            ("SUMMARY", value, Some((_, _, summary))) => *summary = Some(ics_unescape(value)),
+            _ => {}
+        }
+    }
+    
+    if holidays.is_empty() {
+        return Err("No events found in the calendar file".to_string());
+    }
+    Ok(holidays)
+}
+
+// Date part of a DATE (20261225) or DATE-TIME (20261225T090000Z) value
+fn ics_date(value: &str) -> Result<NaiveDate, String> {
+    value.get(..8)
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y%m%d").ok())
+        .ok_or_else(|| format!("Invalid date in calendar file: {}", value))
+}
+
+fn ics_unescape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => out.push(' '),
+            Some(escaped) => out.push(escaped),
+            None => {}
+        }
+    }
+    out.trim().to_string()
+}
+
+// Anonymous Gregorian algorithm
+fn easter_sunday(year: i32) -> Option<NaiveDate> {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+}
+
+fn last_weekday_of_month(year: i32, month: u32, weekday: Weekday) -> Option<NaiveDate> {
+    let next_month = match month {
+        12 => NaiveDate::from_ymd_opt(year + 1, 1, 1),
+        month => NaiveDate::from_ymd_opt(year, month + 1, 1),
+    }?;
+    let last = next_month.pred_opt()?;
+    Some(last - Duration::days(last.weekday().days_since(weekday) as i64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn day(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+    
+    fn dates(region: HolidayRegion, year: i32) -> Vec<NaiveDate> {
+        region_holidays(region, year).into_iter().map(|(date, _)| date).collect()
+    }
+    
+    #[test]
+    fn easter_based_holidays() {
+        assert_eq!(easter_sunday(2026), Some(day("2026-04-05")));
+        assert_eq!(easter_sunday(2027), Some(day("2027-03-28")));
+        assert!(dates(HolidayRegion::De, 2026).contains(&day("2026-05-14")));
+    }
+    
+    #[test]
+    fn weekend_holidays_get_substitutes() {
+        // July 4th 2026 is a Saturday; observed on Friday
+        assert!(dates(HolidayRegion::Us, 2026).contains(&day("2026-07-03")));
+        // Christmas 2027 is a Saturday and Boxing Day a Sunday; both move past the weekend
+        let gb = dates(HolidayRegion::Gb, 2027);
+        assert!(gb.contains(&day("2027-12-27")) && gb.contains(&day("2027-12-28")));
+        assert_eq!(last_weekday_of_month(2026, 5, Weekday::Mon), Some(day("2026-05-25")));
+    }
+    
+    #[test]
+    fn ics_events_cover_each_day() {
+        let ics = "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nDTSTART;VALUE=DATE:20261224\r\nDTEND;VALUE=DATE:20261227\r\nSUMMARY:Office\r\n  closed\\, holidays\r\nEND:VEVENT\r\nBEGIN:VEVENT\r\nDTSTART:20270101T000000Z\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+        let holidays = parse_ics_holidays(ics).unwrap();
+        assert_eq!(holidays.len(), 4);
+        assert_eq!(holidays[0], (day("2026-12-24"), "Office closed, holidays".to_string()));
+        assert_eq!(holidays[3], (day("2027-01-01"), "Holiday".to_string()));
+        assert!(parse_ics_holidays("not a calendar").is_err());
+    }
+}
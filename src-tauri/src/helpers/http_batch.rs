@@ -0,0 +1,108 @@
+// Bodies for Google's batch endpoint: several API calls sent as one multipart/mixed request, each
+// answered in its own part of the multipart/mixed response.
+
+// One call in a batch; `path` is what would follow the host, e.g. "/calendar/v3/...". An empty
+// body sends none, as for a GET
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchPart {
+    pub method: &'static str,
+    pub path: String,
+    pub body: String,
+}
+
+// The answer to one call in a batch
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchReply {
+    pub status: u16,
+    pub body: String,
+}
+
+// Multipart body for `parts`; each is numbered by its position so the reply can be matched to it
+pub fn encode(boundary: &str, parts: &[BatchPart]) -> String {
+    let mut body = String::new();
+    for (i, part) in parts.iter().enumerate() {
+        body.push_str(&format!("--{}\r\n", boundary));
+        body.push_str("Content-Type: application/http\r\n");
+        body.push_str(&format!("Content-ID: <item{}>\r\n\r\n", i + 1));
+        body.push_str(&format!("{} {}\r\n", part.method, part.path));
+        if !part.body.is_empty() {
+            body.push_str("Content-Type: application/json\r\n\r\n");
+            body.push_str(&part.body);
+            body.push_str("\r\n");
+        }
+    }
+    body.push_str(&format!("--{}--\r\n", boundary));
+    body
+}
+
+// Boundary from a multipart Content-Type header
+pub fn boundary(content_type: &str) -> Option<&str> {
+    content_type.split(';')
+        .filter_map(|param| param.trim().strip_prefix("boundary="))
+        .map(|boundary| boundary.trim_matches('"'))
+        .find(|boundary| !boundary.is_empty())
+}
+
+// Replies to a batch of `count` calls, by the position of the call; None where Google left one out
+pub fn decode(boundary: &str, body: &str, count: usize) -> Result<Vec<Option<BatchReply>>, String> {
+    let body = body.replace("\r\n", "\n");
+    let delimiter = format!("--{}", boundary);
+    let mut replies = vec![None; count];
+    
+    // Before the first delimiter is preamble, and the closing one starts with "--"
+    for part in body.split(delimiter.as_str()).skip(1).filter(|part| !part.starts_with("--")) {
+        let (headers, response) = part.trim_start_matches('\n').split_once("\n\n")
+            .ok_or_else(|| "Batch reply part has no body".to_string())?;
+        let index = headers.lines()
+            .filter_map(|line| line.split_once(':'))
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-id"))
+            .and_then(|(_, value)| value.trim().strip_prefix("<response-item")?.strip_suffix('>')?.parse::<usize>().ok())
+            .filter(|n| (1..=count).contains(n))
+            .ok_or_else(|| format!("Batch reply part has no known Content-ID: {}", headers.trim()))?;
+        
+        let (head, reply_body) = response.split_once("\n\n").unwrap_or((response, ""));
+        let status = head.lines().next()
+            .and_then(|line| line.split_whitespace().nth(1))
+            .and_then(|code| code.parse::<u16>().ok())
+            .ok_or_else(|| format!("Batch reply part has no status line: {}", head.trim()))?;
+        replies[index - 1] = Some(BatchReply { status, body: reply_body.trim().to_string() });
+    }
+    Ok(replies)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn encode_numbers_the_parts() {
+        let parts = [
+            BatchPart { method: "PATCH", path: "/calendar/v3/calendars/primary/events/a".to_string(), body: "{}".to_string() },
+            BatchPart { method: "PATCH", path: "/calendar/v3/calendars/primary/events/b".to_string(), body: "{\"x\":1}".to_string() },
+        ];
+        let body = encode("b0", &parts);
+        assert!(body.starts_with("--b0\r\nContent-Type: application/http\r\nContent-ID: <item1>\r\n\r\nPATCH /calendar/v3/calendars/primary/events/a\r\n"));
+        assert!(body.contains("Content-ID: <item2>\r\n\r\nPATCH /calendar/v3/calendars/primary/events/b\r\nContent-Type: application/json\r\n\r\n{\"x\":1}\r\n"));
+        assert!(body.ends_with("--b0--\r\n"));
+        
+        let get = BatchPart { method: "GET", path: "/calendar/v3/calendars/primary/events/c".to_string(), body: String::new() };
+        assert_eq!(
+            encode("b1", &[get]),
+            "--b1\r\nContent-Type: application/http\r\nContent-ID: <item1>\r\n\r\nGET /calendar/v3/calendars/primary/events/c\r\n--b1--\r\n",
+        );
+    }
+    
+    #[test]
+    fn decode_matches_replies_by_content_id() {
+        let body = "--batch_x\r\nContent-Type: application/http\r\nContent-ID: <response-item2>\r\n\r\n\
+            HTTP/1.1 404 Not Found\r\nContent-Type: application/json\r\n\r\n{\"error\":{}}\r\n\
+            --batch_x\r\nContent-Type: application/http\r\nContent-ID: <response-item1>\r\n\r\n\
+            HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\r\n{\"id\":\"a\"}\r\n--batch_x--\r\n";
+        let replies = decode(boundary("multipart/mixed; boundary=batch_x").unwrap(), body, 3).unwrap();
+        assert_eq!(replies, vec![
+            Some(BatchReply { status: 200, body: "{\"id\":\"a\"}".to_string() }),
+            Some(BatchReply { status: 404, body: "{\"error\":{}}".to_string() }),
+            None,
+        ]);
+    }
+}
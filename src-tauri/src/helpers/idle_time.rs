@@ -0,0 +1,51 @@
+// Seconds since the last keyboard/mouse input, or None when the platform can't tell us
+
+#[cfg(target_os = "windows")]
+pub fn get_idle_seconds() -> Option<u64> {
+    use windows_sys::Win32::System::SystemInformation::GetTickCount;
+    use windows_sys::Win32::UI::Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO};
+    
+    let mut info = LASTINPUTINFO {
+        cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32,
+        dwTime: 0,
+    };
+    
+    // SAFETY: `info` is a valid LASTINPUTINFO with cbSize set as the API requires
+    if unsafe { GetLastInputInfo(&mut info) } == 0 {
+        return None;
+    }
+    
+    let now = unsafe { GetTickCount() };
+    Some(u64::from(now.wrapping_sub(info.dwTime)) / 1000)
+}
+
+#[cfg(target_os = "macos")]
+pub fn get_idle_seconds() -> Option<u64> {
+    // HIDIdleTime is reported in nanoseconds
+    let output = std::process::Command::new("ioreg")
+        .args(["-c", "IOHIDSystem", "-d", "4"])
+        .output()
+        .ok()?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    
+    let line = text.lines().find(|line| line.contains("\"HIDIdleTime\""))?;
+    let nanos: u64 = line.rsplit('=').next()?.trim().parse().ok()?;
+    Some(nanos / 1_000_000_000)
+}
+
+#[cfg(target_os = "linux")]
+pub fn get_idle_seconds() -> Option<u64> {
+    // X11 only; xprintidle reports milliseconds and is absent on most Wayland setups
+    let output = std::process::Command::new("xprintidle").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    
+    let millis: u64 = String::from_utf8_lossy(&output.stdout).trim().parse().ok()?;
+    Some(millis / 1000)
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+pub fn get_idle_seconds() -> Option<u64> {
+    None
+}
@@ -0,0 +1,21 @@
+// Text matched with SQL LIKE; the queries pair it with ESCAPE '\'
+
+// `text` with the LIKE wildcards and the escape character escaped, so it matches only itself
+pub fn escape_like(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn wildcards_match_only_themselves() {
+        assert_eq!(escape_like("50% off_now"), "50\\% off\\_now");
+    }
+    
+    #[test]
+    fn backslash_is_escaped_first() {
+        assert_eq!(escape_like("a\\%"), "a\\\\\\%");
+    }
+}
@@ -1 +1,14 @@
-pub mod parse_date;
\ No newline at end of file
+pub mod parse_date;
+pub mod datetime;
+pub mod format;
+pub mod holidays;
+pub mod idle_time;
+pub mod crypto;
+pub mod yearly;
+pub mod reminder_plan;
+pub mod template;
+pub mod tags;
+pub mod dependency_graph;
+pub mod http_batch;
+pub mod recurrence;
+pub mod like;
\ No newline at end of file
@@ -1,18 +1,124 @@
-use chrono::{DateTime, Utc};
-
-/// Parse ISO 8601 datetime string and return start and end of day timestamps
-pub fn parse_date_range(date_str: &str) -> Result<(DateTime<Utc>, DateTime<Utc>), String> {
-    // Parse ISO 8601 datetime string and get the date
-    let date_time = date_str.parse::<DateTime<Utc>>()
-        .map_err(|e| format!("Invalid datetime format: {}", e))?;
-    
-    // Get date at start of day (00:00:00) and end of day (23:59:59)
-    let start_of_day = date_time.date_naive().and_hms_opt(0, 0, 0)
-        .ok_or("Failed to create start of day")?
-        .and_utc();
-    let end_of_day = date_time.date_naive().and_hms_opt(23, 59, 59)
-        .ok_or("Failed to create end of day")?
-        .and_utc();
-    
-    Ok((start_of_day, end_of_day))
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveTime, Offset, TimeZone, Utc, Weekday};
+use crate::helpers::datetime::{explicit_offset, parse_datetime};
+
+/// Offset from UTC in minutes, as sent by clients or stored in settings
+pub fn offset_from_minutes(minutes: i32) -> Result<FixedOffset, String> {
+    minutes.checked_mul(60)
+        .and_then(FixedOffset::east_opt)
+        .ok_or_else(|| format!("Invalid UTC offset: {} minutes", minutes))
+}
+
+/// Parse the date in payload field `field` and return the start of its day and the start of the next one.
+/// Days are cut at `offset`; without one, at the offset written in the value itself, else UTC.
+pub fn parse_date_range(field: &'static str, date_str: &str, offset: Option<FixedOffset>) -> Result<(DateTime<Utc>, DateTime<Utc>), String> {
+    let (date, offset) = local_date(field, date_str, offset)?;
+    let start_of_day = start_of(date, offset)?;
+    
+    // Half-open: the end is the next day's 00:00, so no sub-second timestamp falls between days
+    Ok((start_of_day, start_of_day + Duration::days(1)))
+}
+
+/// Parse the date in payload field `field` and return the start of its Monday and of the Monday after
+pub fn parse_week_range(field: &'static str, date_str: &str, offset: Option<FixedOffset>) -> Result<(DateTime<Utc>, DateTime<Utc>), String> {
+    parse_week_range_from(field, date_str, offset, Weekday::Mon)
+}
+
+/// Like parse_week_range, for weeks that start on `first_day`
+pub fn parse_week_range_from(field: &'static str, date_str: &str, offset: Option<FixedOffset>, first_day: Weekday) -> Result<(DateTime<Utc>, DateTime<Utc>), String> {
+    let (date, offset) = local_date(field, date_str, offset)?;
+    let days_into_week = date.weekday().days_since(first_day) as i64;
+    let start = start_of(date - Duration::days(days_into_week), offset)?;
+    
+    Ok((start, start + Duration::days(7)))
+}
+
+/// Calendar day a moment falls on in `offset`
+pub fn local_day(moment: DateTime<Utc>, offset: FixedOffset) -> NaiveDate {
+    moment.with_timezone(&offset).date_naive()
+}
+
+/// Last calendar day of a half-open range from parse_date_range or parse_week_range
+pub fn last_day(end: DateTime<Utc>, offset: FixedOffset) -> NaiveDate {
+    local_day(end - Duration::days(1), offset)
+}
+
+// Calendar date of the moment in `offset`, or in the value's own offset
+fn local_date(field: &'static str, date_str: &str, offset: Option<FixedOffset>) -> Result<(NaiveDate, FixedOffset), String> {
+    let offset = offset.or_else(|| explicit_offset(date_str)).unwrap_or(Utc.fix());
+    let moment = parse_datetime(field, date_str, offset)?;
+    
+    Ok((local_day(moment, offset), offset))
+}
+
+fn start_of(date: NaiveDate, offset: FixedOffset) -> Result<DateTime<Utc>, String> {
+    offset.from_local_datetime(&date.and_time(NaiveTime::MIN))
+        .single()
+        .map(|start| start.with_timezone(&Utc))
+        .ok_or_else(|| "Failed to create start of day".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn utc(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+    
+    #[test]
+    fn day_range_is_half_open() {
+        let (start, end) = parse_date_range("date", "2026-03-10T15:00:00Z", None).unwrap();
+        assert_eq!(start, utc("2026-03-10T00:00:00Z"));
+        assert_eq!(end, utc("2026-03-11T00:00:00Z"));
+    }
+    
+    #[test]
+    fn day_range_follows_the_given_offset() {
+        // 21:30 UTC is already the next day at UTC+5
+        let plus_five = offset_from_minutes(300).unwrap();
+        let (start, end) = parse_date_range("date", "2026-03-10T21:30:00Z", Some(plus_five)).unwrap();
+        assert_eq!(start, utc("2026-03-10T19:00:00Z"));
+        assert_eq!(end, utc("2026-03-11T19:00:00Z"));
+        
+        // and still the same day at UTC-8
+        let minus_eight = offset_from_minutes(-480).unwrap();
+        let (start, _) = parse_date_range("date", "2026-03-11T05:00:00Z", Some(minus_eight)).unwrap();
+        assert_eq!(start, utc("2026-03-10T08:00:00Z"));
+    }
+    
+    #[test]
+    fn day_range_falls_back_to_the_strings_offset() {
+        let (start, _) = parse_date_range("date", "2026-03-10T23:30:00-08:00", None).unwrap();
+        assert_eq!(start, utc("2026-03-10T08:00:00Z"));
+    }
+    
+    #[test]
+    fn week_range_starts_on_the_local_monday() {
+        // Monday 00:30 at UTC+2 is still Sunday in UTC
+        let plus_two = offset_from_minutes(120).unwrap();
+        let (start, end) = parse_week_range("week", "2026-03-08T22:30:00Z", Some(plus_two)).unwrap();
+        assert_eq!(start, utc("2026-03-08T22:00:00Z"));
+        assert_eq!(end, utc("2026-03-15T22:00:00Z"));
+        assert_eq!(last_day(end, plus_two), NaiveDate::from_ymd_opt(2026, 3, 15).unwrap());
+    }
+    
+    #[test]
+    fn week_range_can_start_on_sunday() {
+        let (start, end) = parse_week_range_from("week", "2026-03-08", None, Weekday::Sun).unwrap();
+        assert_eq!(start, utc("2026-03-08T00:00:00Z"));
+        assert_eq!(end, utc("2026-03-15T00:00:00Z"));
+    }
+    
+    #[test]
+    fn date_only_values_name_their_own_day() {
+        let minus_eight = offset_from_minutes(-480).unwrap();
+        let (start, _) = parse_date_range("date", "2026-03-10", Some(minus_eight)).unwrap();
+        assert_eq!(start, utc("2026-03-10T08:00:00Z"));
+    }
+    
+    #[test]
+    fn offset_outside_a_day_is_rejected() {
+        assert!(offset_from_minutes(24 * 60).is_err());
+        assert!(offset_from_minutes(i32::MAX).is_err());
+    }
 }
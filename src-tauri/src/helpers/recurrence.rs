@@ -0,0 +1,111 @@
+use chrono::{DateTime, Days, Months, NaiveDate, Utc};
+use crate::structs::recurrence::{ExceptionKind, RecurrenceException, RecurrenceFrequency};
+
+/// An occurrence to make a task for; `deadline` is set when an exception moved it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Occurrence {
+    pub day: NaiveDate,
+    pub deadline: Option<DateTime<Utc>>,
+}
+
+/// Day of the `n`th occurrence, the first being 0. Counted from the first each time, so a monthly
+/// series on the 31st is back on the 31st after a shorter month.
+pub fn nth_day(starts_on: NaiveDate, frequency: RecurrenceFrequency, interval: u32, n: u32) -> Option<NaiveDate> {
+    let steps = n.checked_mul(interval)?;
+    match frequency {
+        RecurrenceFrequency::Daily => starts_on.checked_add_days(Days::new(steps as u64)),
+        RecurrenceFrequency::Weekly => starts_on.checked_add_days(Days::new(steps as u64 * 7)),
+        RecurrenceFrequency::Monthly => starts_on.checked_add_months(Months::new(steps)),
+    }
+}
+
+fn days(starts_on: NaiveDate, frequency: RecurrenceFrequency, interval: u32) -> impl Iterator<Item = NaiveDate> {
+    (0..).map_while(move |n| nth_day(starts_on, frequency, interval, n))
+}
+
+/// Whether the series has an occurrence on `day`
+pub fn is_occurrence(starts_on: NaiveDate, frequency: RecurrenceFrequency, interval: u32, day: NaiveDate) -> bool {
+    days(starts_on, frequency, interval)
+        .take_while(|occurrence| *occurrence <= day)
+        .any(|occurrence| occurrence == day)
+}
+
+/// Occurrences after `after` up to `through`, with the exceptions applied: skipped ones are left out,
+/// rescheduled ones carry their deadline, and none come from the earliest end of the series on
+pub fn occurrences_between(
+    starts_on: NaiveDate,
+    frequency: RecurrenceFrequency,
+    interval: u32,
+    after: NaiveDate,
+    through: NaiveDate,
+    exceptions: &[RecurrenceException],
+) -> Vec<Occurrence> {
+    let ends_on = exceptions.iter()
+        .filter(|exception| exception.kind == ExceptionKind::EndSeries)
+        .map(|exception| exception.occurrence_on)
+        .min();
+    
+    days(starts_on, frequency, interval)
+        .skip_while(|day| *day <= after)
+        .take_while(|day| *day <= through && !ends_on.is_some_and(|end| *day >= end))
+        .filter_map(|day| {
+            let exception = exceptions.iter().find(|exception| exception.occurrence_on == day);
+            match exception.map(|exception| exception.kind) {
+                Some(ExceptionKind::Skip) => None,
+                Some(ExceptionKind::Reschedule) => Some(Occurrence { day, deadline: exception.and_then(|exception| exception.deadline) }),
+                _ => Some(Occurrence { day, deadline: None }),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::task_struct::TaskId;
+    
+    fn day(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+    
+    fn exception(on: &str, kind: ExceptionKind, deadline: Option<DateTime<Utc>>) -> RecurrenceException {
+        RecurrenceException {
+            series_id: TaskId::from(uuid::Uuid::nil()),
+            occurrence_on: day(on),
+            kind,
+            deadline,
+            created_at: Utc::now(),
+        }
+    }
+    
+    #[test]
+    fn monthly_series_keep_their_day_after_short_months() {
+        let start = day("2026-01-31");
+        let monthly = |n| nth_day(start, RecurrenceFrequency::Monthly, 1, n);
+        assert_eq!(monthly(1), Some(day("2026-02-28")));
+        assert_eq!(monthly(2), Some(day("2026-03-31")));
+        assert_eq!(nth_day(start, RecurrenceFrequency::Weekly, 2, 1), Some(day("2026-02-14")));
+        
+        assert!(is_occurrence(start, RecurrenceFrequency::Monthly, 1, day("2026-04-30")));
+        assert!(!is_occurrence(start, RecurrenceFrequency::Monthly, 1, day("2026-04-29")));
+        assert!(!is_occurrence(start, RecurrenceFrequency::Daily, 3, day("2026-02-01")));
+    }
+    
+    #[test]
+    fn exceptions_skip_move_and_end_occurrences() {
+        let moved = "2026-03-05T17:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let exceptions = [
+            exception("2026-03-03", ExceptionKind::Skip, None),
+            exception("2026-03-04", ExceptionKind::Reschedule, Some(moved)),
+            exception("2026-03-06", ExceptionKind::EndSeries, None),
+        ];
+        let occurrences = occurrences_between(
+            day("2026-03-01"), RecurrenceFrequency::Daily, 1, day("2026-03-01"), day("2026-03-31"), &exceptions,
+        );
+        assert_eq!(occurrences, vec![
+            Occurrence { day: day("2026-03-02"), deadline: None },
+            Occurrence { day: day("2026-03-04"), deadline: Some(moved) },
+            Occurrence { day: day("2026-03-05"), deadline: None },
+        ]);
+    }
+}
@@ -0,0 +1,71 @@
+use chrono::{DateTime, Utc};
+use crate::structs::reminder_frequency::ReminderFrequency;
+
+// When to remind about a deadline, shared by the Google Calendar reminders and local notifications
+// so both remind at the same times.
+
+// Escalating reminders come hourly in this many hours before the deadline, and daily before that
+const ESCALATION_HOURS: usize = 4;
+
+// Minutes before `deadline` to remind at, nearest the deadline first, leaving out any before `from`.
+// At most `max` of them; escalating reminders then split it between the last hours and the days before.
+pub fn reminder_minutes(frequency: &ReminderFrequency, from: DateTime<Utc>, deadline: DateTime<Utc>, max: usize) -> Vec<i64> {
+    let minutes_left = (deadline - from).num_minutes();
+    let every = |step: i64, limit: usize| -> Vec<i64> {
+        (1..)
+            .map(|i| i * step)
+            .take_while(|minutes| *minutes <= minutes_left)
+            .take(limit)
+            .collect()
+    };
+    
+    match frequency {
+        ReminderFrequency::None => Vec::new(),
+        ReminderFrequency::Hourly => every(60, max),
+        ReminderFrequency::Every3Hours => every(180, max),
+        ReminderFrequency::Daily => every(1440, max),
+        ReminderFrequency::Escalating => {
+            let hourly = every(60, ESCALATION_HOURS);
+            let daily = every(1440, max);
+            // Short on room, the days keep up to half of it
+            let hours = hourly.len().min(max - daily.len().min(max / 2));
+            let days = daily.len().min(max - hours);
+            hourly.into_iter().take(hours).chain(daily.into_iter().take(days)).collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    
+    fn at(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+    
+    #[test]
+    fn fixed_frequencies_stop_at_now() {
+        let deadline = at("2026-05-10T12:00:00Z");
+        let from = deadline - Duration::minutes(150);
+        assert_eq!(reminder_minutes(&ReminderFrequency::Hourly, from, deadline, 4), vec![60, 120]);
+        assert_eq!(reminder_minutes(&ReminderFrequency::Every3Hours, from, deadline, 4), Vec::<i64>::new());
+        assert_eq!(reminder_minutes(&ReminderFrequency::Hourly, deadline + Duration::hours(1), deadline, 4), Vec::<i64>::new());
+    }
+    
+    #[test]
+    fn escalating_goes_hourly_in_the_last_hours() {
+        let deadline = at("2026-05-10T12:00:00Z");
+        let from = deadline - Duration::days(3);
+        assert_eq!(
+            reminder_minutes(&ReminderFrequency::Escalating, from, deadline, usize::MAX),
+            vec![60, 120, 180, 240, 1440, 2880, 4320],
+        );
+        // Google's limit: the nearest hours and days share it
+        assert_eq!(reminder_minutes(&ReminderFrequency::Escalating, from, deadline, 4), vec![60, 120, 1440, 2880]);
+        assert_eq!(
+            reminder_minutes(&ReminderFrequency::Escalating, deadline - Duration::hours(5), deadline, 4),
+            vec![60, 120, 180, 240],
+        );
+    }
+}
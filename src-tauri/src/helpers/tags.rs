@@ -0,0 +1,37 @@
+// Tasks have no tag field; a tag is a #word in the title or notes. Tags are compared lowercased,
+// so #Errand and #errand are one tag.
+
+// Tags in `text`, lowercased, each once, in the order they first appear
+pub fn extract_tags(text: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for word in text.split_whitespace().filter_map(|word| word.strip_prefix('#')) {
+        let tag = word.trim_end_matches(|c: char| !is_tag_char(c));
+        if tag.is_empty() || !tag.chars().all(is_tag_char) {
+            continue;
+        }
+        let tag = tag.to_lowercase();
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+pub fn is_tag_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn tags_are_lowercased_once() {
+        assert_eq!(extract_tags("Buy milk #Errand, then #home and #errand."), vec!["errand", "home"]);
+    }
+    
+    #[test]
+    fn headings_and_bare_hashes_are_not_tags() {
+        assert_eq!(extract_tags("# Notes\n## Plan\nsee issue #12 and a#b or #a/b"), vec!["12"]);
+    }
+}
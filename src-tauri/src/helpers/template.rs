@@ -0,0 +1,53 @@
+// Fills `{{NAME}}` placeholders in HTML pages; values are escaped, so text from outside can't add markup.
+// Placeholders without a value are left out, so an older or misspelt override page still renders.
+pub fn render(template: &str, values: &[(&str, &str)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            out.push_str(&rest[start..]);
+            return out;
+        };
+        let name = after[..end].trim();
+        if let Some((_, value)) = values.iter().find(|(key, _)| *key == name) {
+            out.push_str(&escape_html(value));
+        }
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+pub fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn values_are_escaped() {
+        let page = render("<p>{{ MESSAGE }}</p>", &[("MESSAGE", "<script>\"x\" & 'y'</script>")]);
+        assert_eq!(page, "<p>&lt;script&gt;&quot;x&quot; &amp; &#39;y&#39;&lt;/script&gt;</p>");
+    }
+    
+    #[test]
+    fn unknown_placeholders_are_dropped() {
+        assert_eq!(render("{{A}}-{{B}}-{{", &[("A", "1")]), "1--{{");
+    }
+}
@@ -0,0 +1,49 @@
+use chrono::{Datelike, NaiveDate};
+
+/// `month`/`day` in `year`; February 29 falls on the 28th outside leap years
+pub fn in_year(year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(year, month, day).or_else(|| {
+        if month == 2 && day == 29 {
+            NaiveDate::from_ymd_opt(year, 2, 28)
+        } else {
+            None
+        }
+    })
+}
+
+/// The first time `month`/`day` comes around on or after `from`
+pub fn next_occurrence(month: u32, day: u32, from: NaiveDate) -> Option<NaiveDate> {
+    [from.year(), from.year() + 1].into_iter()
+        .filter_map(|year| in_year(year, month, day))
+        .find(|date| *date >= from)
+}
+
+/// Whether the day exists in some year (February 29 included)
+pub fn is_valid_month_day(month: u32, day: u32) -> bool {
+    // 2000 is a leap year
+    NaiveDate::from_ymd_opt(2000, month, day).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn day(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+    
+    #[test]
+    fn next_occurrence_wraps_to_next_year() {
+        assert_eq!(next_occurrence(3, 14, day("2026-03-14")), Some(day("2026-03-14")));
+        assert_eq!(next_occurrence(3, 14, day("2026-03-15")), Some(day("2027-03-14")));
+        assert_eq!(next_occurrence(1, 2, day("2026-12-31")), Some(day("2027-01-02")));
+    }
+    
+    #[test]
+    fn leap_day_falls_back_to_the_28th() {
+        assert_eq!(next_occurrence(2, 29, day("2027-01-01")), Some(day("2027-02-28")));
+        assert_eq!(next_occurrence(2, 29, day("2027-03-01")), Some(day("2028-02-29")));
+        assert!(is_valid_month_day(2, 29));
+        assert!(!is_valid_month_day(4, 31));
+    }
+}
@@ -0,0 +1,328 @@
+use std::io::Read;
+use std::sync::{Arc, Mutex};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager};
+use sha2::Sha256;
+use tiny_http::{Header, Method, Request, Response, Server};
+use crate::db::Database;
+use crate::secrets;
+use crate::services::{metrics_service, settings_service, slack_service, task_service};
+use crate::structs::dto::{CompleteTaskData, DateQuery, PauseTaskData, TaskData, TaskRef};
+use crate::structs::reminder_frequency::ReminderFrequency;
+use crate::structs::work_session::PauseReason;
+use crate::structs::task_update::{TaskUpdate, TaskUpdateData};
+use tracing::{info, warn, error};
+
+const TOKEN_LENGTH: usize = 40;
+const MAX_BODY_BYTES: u64 = 64 * 1024;
+const SLACK_COMMAND_PATH: &str = "/slack/command";
+// Older signed requests are rejected as replays
+const SLACK_MAX_AGE_SECS: i64 = 5 * 60;
+
+// Running server, if enabled; unblocking it ends the request thread
+#[derive(Default)]
+pub struct HttpApiState(Mutex<Option<Arc<Server>>>);
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NewTask {
+    title: String,
+    #[serde(default)]
+    reminder_frequency: Option<ReminderFrequency>,
+}
+
+// Start, stop or restart the server to match settings; generates a token on first enable
+pub fn apply_settings(app: &AppHandle) -> Result<(), String> {
+    stop(app);
+    
+    let db = app.try_state::<Database>()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    let settings = db.settings()
+        .map_err(|e| format!("Failed to fetch settings: {}", e))?;
+    
+    if !settings.http_api_enabled {
+        return Ok(());
+    }
+    
+    let token = match settings_service::secret(&db, secrets::HTTP_API, "token")? {
+        Some(token) => token,
+        None => regenerate_token_in_db(&db)?,
+    };
+    
+    // Loopback only: the API must never be reachable from the network
+    let address = format!("127.0.0.1:{}", settings.http_api_port);
+    let server = Arc::new(Server::http(&address)
+        .map_err(|e| format!("Failed to start HTTP API on {}: {}", address, e))?);
+    
+    let state = app.state::<HttpApiState>();
+    *state.0.lock().map_err(|e| e.to_string())? = Some(server.clone());
+    
+    let handle = app.clone();
+    std::thread::spawn(move || {
+        for request in server.incoming_requests() {
+            handle_request(&handle, &token, request);
+        }
+    });
+    
+    info!("HTTP API listening on {}", address);
+    Ok(())
+}
+
+// New token for clients; restarts the server so the old one stops working
+pub fn regenerate_token(app: &AppHandle) -> Result<String, String> {
+    let db = app.try_state::<Database>()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    let token = regenerate_token_in_db(&db)?;
+    
+    apply_settings(app)?;
+    Ok(token)
+}
+
+fn stop(app: &AppHandle) {
+    let Some(state) = app.try_state::<HttpApiState>() else {
+        return;
+    };
+    let Ok(mut server) = state.0.lock() else {
+        return;
+    };
+    if let Some(server) = server.take() {
+        server.unblock();
+        info!("HTTP API stopped");
+    }
+}
+
+fn regenerate_token_in_db(db: &Database) -> Result<String, String> {
+    use rand::Rng;
+    
+    let token: String = rand::thread_rng()
+        .sample_iter(&rand::distributions::Alphanumeric)
+        .take(TOKEN_LENGTH)
+        .map(char::from)
+        .collect();
+    
+    let conn = db.get_connection();
+    secrets::store(&conn, secrets::HTTP_API, "token", &token)?;
+    Ok(token)
+}
+
+fn handle_request(app: &AppHandle, token: &str, mut request: Request) {
+    let (status, body) = if app.try_state::<Database>().is_some_and(|db| db.is_locked()) {
+        (423, json!({ "error": "App is locked" }))
+    } else if is_slack_command(&request) {
+        metrics_service::record_feature("slack-command");
+        // Slack can't send the bearer token; its request signature authenticates it instead
+        match slack_command(app, &mut request) {
+            Ok((status, body)) => (status, body),
+            Err((status, message)) => (status, json!({ "error": message })),
+        }
+    } else if !is_authorized(&request, token) {
+        (401, json!({ "error": "Missing or invalid bearer token" }))
+    } else {
+        metrics_service::record_feature("http-api");
+        match route(app, &mut request) {
+            Ok((status, body)) => (status, body),
+            Err((status, message)) => (status, json!({ "error": message })),
+        }
+    };
+    
+    if status >= 400 {
+        warn!("HTTP API {} {} -> {}", request.method(), request.url(), status);
+    }
+    
+    let response = Response::from_string(body.to_string())
+        .with_status_code(status)
+        .with_header(Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).unwrap());
+    if let Err(e) = request.respond(response) {
+        error!("Failed to send HTTP API response: {}", e);
+    }
+}
+
+fn is_authorized(request: &Request, token: &str) -> bool {
+    request.headers().iter()
+        .find(|header| header.field.equiv("Authorization"))
+        .and_then(|header| header.value.as_str().strip_prefix("Bearer "))
+        .map(|given| constant_time_eq(given.as_bytes(), token.as_bytes()))
+        .unwrap_or(false)
+}
+
+fn is_slack_command(request: &Request) -> bool {
+    *request.method() == Method::Post
+        && request.url().split('?').next() == Some(SLACK_COMMAND_PATH)
+}
+
+// Reached through a relay, since the server only listens on loopback
+fn slack_command(app: &AppHandle, request: &mut Request) -> RouteResult {
+    let db = app.try_state::<Database>()
+        .ok_or_else(|| (503, "Database not initialized".to_string()))?;
+    let settings = db.settings()
+        .map_err(|e| (500, format!("Failed to fetch settings: {}", e)))?;
+    
+    if !settings.slack_enabled {
+        return Err((404, "Slack commands are disabled".to_string()));
+    }
+    let secret = settings_service::secret(&db, secrets::SLACK, "signing_secret")
+        .map_err(|e| (500, e))?
+        .ok_or_else(|| (404, "Slack commands are disabled".to_string()))?;
+    
+    let body = read_body(request)?;
+    verify_slack_signature(request, &body, &secret).map_err(|e| (401, e))?;
+    ok(slack_service::handle_command(&db, app, &body))
+}
+
+// Slack signs "v0:<timestamp>:<body>" with the app's signing secret
+fn verify_slack_signature(request: &Request, body: &str, secret: &str) -> Result<(), String> {
+    let timestamp = header_value(request, "X-Slack-Request-Timestamp")
+        .ok_or("Missing Slack request timestamp")?;
+    let signature = header_value(request, "X-Slack-Signature")
+        .ok_or("Missing Slack signature")?;
+    
+    let sent_at: i64 = timestamp.parse()
+        .map_err(|_| "Invalid Slack request timestamp".to_string())?;
+    if (Utc::now().timestamp() - sent_at).abs() > SLACK_MAX_AGE_SECS {
+        return Err("Slack request is too old".to_string());
+    }
+    
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .map_err(|e| format!("Invalid signing secret: {}", e))?;
+    mac.update(format!("v0:{}:{}", timestamp, body).as_bytes());
+    let expected: String = mac.finalize().into_bytes().iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    
+    if constant_time_eq(format!("v0={}", expected).as_bytes(), signature.as_bytes()) {
+        Ok(())
+    } else {
+        Err("Invalid Slack signature".to_string())
+    }
+}
+
+fn header_value<'a>(request: &'a Request, name: &'static str) -> Option<&'a str> {
+    request.headers().iter()
+        .find(|header| header.field.equiv(name))
+        .map(|header| header.value.as_str())
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+type RouteResult = Result<(u16, Value), (u16, String)>;
+
+fn route(app: &AppHandle, request: &mut Request) -> RouteResult {
+    let db = app.try_state::<Database>()
+        .ok_or_else(|| (503, "Database not initialized".to_string()))?;
+    
+    let url = request.url().to_string();
+    let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let method = request.method().clone();
+    let now = Utc::now().to_rfc3339();
+    
+    match (&method, segments.as_slice()) {
+        (Method::Get, ["tasks"]) => {
+            let date = query_param(query, "date").unwrap_or(now);
+            ok(task_service::get_tasks_by_date(DateQuery { date, utc_offset_minutes: utc_offset(query) }, &db))
+        }
+        (Method::Get, ["tasks", "today"]) => {
+            ok(task_service::get_today_overview(DateQuery { date: now, utc_offset_minutes: utc_offset(query) }, &db))
+        }
+        (Method::Get, ["tasks", id]) => {
+            ok(task_service::get_task_by_id(task_ref(id)?, &db))
+        }
+        (Method::Post, ["tasks"]) => {
+            // Same header as most HTTP APIs; a retried POST with it returns the first task
+            let idempotency_key = header_value(request, "Idempotency-Key").map(str::to_string);
+            let body: NewTask = read_json(request)?;
+            let payload = TaskData { title: body.title, created_at: now, idempotency_key, reminder_frequency: body.reminder_frequency };
+            created(task_service::create_task(payload, &db, app))
+        }
+        (Method::Patch, ["tasks", id]) => {
+            let data: TaskUpdateData = read_json(request)?;
+            let payload = TaskUpdate { id: task_ref(id)?.id, data };
+            ok(tauri::async_runtime::block_on(task_service::update_task(payload, &db, app)))
+        }
+        (Method::Delete, ["tasks", id]) => {
+            tauri::async_runtime::block_on(task_service::delete_task(task_ref(id)?, &db, app))
+                .map(|_| (200, json!({ "id": id })))
+                .map_err(|e| (400, e))
+        }
+        (Method::Post, ["tasks", id, action]) => {
+            let payload = task_ref(id)?;
+            let result = match *action {
+                "start" => task_service::start_task(payload, &db, app),
+                "pause" => {
+                    let payload = PauseTaskData { id: payload.id, reason: pause_reason(query)?, note: query_param(query, "note") };
+                    tauri::async_runtime::block_on(task_service::pause_task(payload, &db, app))
+                }
+                "resume" => tauri::async_runtime::block_on(task_service::resume_task(payload, &db, app)),
+                "complete" => {
+                    let payload = CompleteTaskData { id: payload.id, note: query_param(query, "note") };
+                    tauri::async_runtime::block_on(task_service::complete_task(payload, &db, app))
+                }
+                _ => return Err((404, format!("Unknown action: {}", action))),
+            };
+            ok(result)
+        }
+        _ => Err((404, format!("No route for {} {}", method, path))),
+    }
+}
+
+// Malformed IDs are the client's fault, not a missing task
+fn task_ref(id: &str) -> Result<TaskRef, (u16, String)> {
+    id.parse()
+        .map(|id| TaskRef { id })
+        .map_err(|e| (400, e))
+}
+
+fn ok<T: serde::Serialize>(result: Result<T, String>) -> RouteResult {
+    respond_with(200, result)
+}
+
+fn created<T: serde::Serialize>(result: Result<T, String>) -> RouteResult {
+    respond_with(201, result)
+}
+
+fn respond_with<T: serde::Serialize>(status: u16, result: Result<T, String>) -> RouteResult {
+    let value = result.map_err(|e| (400, e))?;
+    serde_json::to_value(value)
+        .map(|body| (status, body))
+        .map_err(|e| (500, format!("Failed to serialize response: {}", e)))
+}
+
+fn read_json<T: DeserializeOwned>(request: &mut Request) -> Result<T, (u16, String)> {
+    let body = read_body(request)?;
+    serde_json::from_str(&body).map_err(|e| (400, format!("Invalid JSON body: {}", e)))
+}
+
+fn read_body(request: &mut Request) -> Result<String, (u16, String)> {
+    let mut body = String::new();
+    request.as_reader()
+        .take(MAX_BODY_BYTES)
+        .read_to_string(&mut body)
+        .map_err(|e| (400, format!("Failed to read body: {}", e)))?;
+    Ok(body)
+}
+
+fn query_param(query: &str, name: &str) -> Option<String> {
+    query.split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| urlencoding::decode(value).unwrap_or_default().to_string())
+}
+
+// `utcOffsetMinutes`; days follow settings when it's missing or not a number
+fn utc_offset(query: &str) -> Option<i32> {
+    query_param(query, "utcOffsetMinutes").and_then(|value| value.parse().ok())
+}
+
+// `reason` on a pause, e.g. `?reason=meeting`
+fn pause_reason(query: &str) -> Result<Option<PauseReason>, (u16, String)> {
+    query_param(query, "reason")
+        .map(|reason| serde_json::from_value(Value::String(reason.clone()))
+            .map_err(|_| (400, format!("Unknown pause reason: {}", reason))))
+        .transpose()
+}
@@ -0,0 +1,111 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use super::{ImportedTask, Importer};
+
+// Reads the JSON produced by REMINDERS_SCRIPT; fetch it with `read_reminders`
+pub struct AppleRemindersImporter;
+
+// JavaScript for Automation; bulk property reads keep large lists fast
+#[cfg(target_os = "macos")]
+const REMINDERS_SCRIPT: &str = r#"
+const app = Application('Reminders');
+JSON.stringify(app.lists().map(list => {
+  const r = list.reminders;
+  const ids = r.id(), names = r.name(), bodies = r.body(), due = r.dueDate(), allDay = r.allDayDueDate(),
+    created = r.creationDate(), completed = r.completed(), completedAt = r.completionDate();
+  return {
+    name: list.name(),
+    reminders: ids.map((id, i) => ({
+      id, name: names[i], body: bodies[i], dueDate: due[i], allDayDueDate: allDay[i],
+      creationDate: created[i], completed: completed[i], completionDate: completedAt[i],
+    })),
+  };
+}));
+"#;
+
+#[derive(Deserialize)]
+struct ReminderList {
+    name: String,
+    reminders: Vec<Reminder>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Reminder {
+    id: String,
+    name: String,
+    body: Option<String>,
+    due_date: Option<DateTime<Utc>>,
+    // Set instead of a due time for reminders due on a day
+    #[serde(default)]
+    all_day_due_date: Option<DateTime<Utc>>,
+    creation_date: Option<DateTime<Utc>>,
+    #[serde(default)]
+    completed: bool,
+    completion_date: Option<DateTime<Utc>>,
+}
+
+impl Importer for AppleRemindersImporter {
+    fn source(&self) -> &'static str {
+        "apple-reminders"
+    }
+    
+    fn parse(&self, content: &str) -> Result<Vec<ImportedTask>, String> {
+        let lists: Vec<ReminderList> = serde_json::from_str(content)
+            .map_err(|e| format!("Unexpected Reminders output: {}", e))?;
+        
+        let tasks = lists.into_iter()
+            .flat_map(|list| {
+                let list_name = list.name;
+                list.reminders.into_iter().map(move |reminder| {
+                    // No projects yet: keep the list name with the task
+                    let notes = match reminder.body.as_deref().map(str::trim) {
+                        Some(body) if !body.is_empty() => format!("{}\n\nReminders list: {}", body, list_name),
+                        _ => format!("Reminders list: {}", list_name),
+                    };
+                    ImportedTask {
+                        external_id: Some(reminder.id),
+                        title: reminder.name,
+                        notes: Some(notes),
+                        all_day: reminder.all_day_due_date.is_some(),
+                        deadline: reminder.due_date.or(reminder.all_day_due_date),
+                        created_at: reminder.creation_date,
+                        completed_at: reminder.completed
+                            .then(|| reminder.completion_date.unwrap_or_else(Utc::now)),
+                    }
+                })
+            })
+            .collect();
+        
+        Ok(tasks)
+    }
+}
+
+// Whether this build can read Apple Reminders
+pub const SUPPORTED: bool = cfg!(target_os = "macos");
+
+// Ask Reminders for every list; the first run shows the macOS automation prompt
+#[cfg(target_os = "macos")]
+pub fn read_reminders() -> Result<String, String> {
+    let output = std::process::Command::new("osascript")
+        .args(["-l", "JavaScript", "-e", REMINDERS_SCRIPT])
+        .output()
+        .map_err(|e| format!("Failed to run osascript: {}", e))?;
+    
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        // -1743: the user denied automation access
+        if stderr.contains("-1743") {
+            return Err("Access to Reminders was denied; allow it in System Settings > Privacy & Security > Automation".to_string());
+        }
+        return Err(format!("Failed to read Reminders: {}", stderr.trim()));
+    }
+    
+    String::from_utf8(output.stdout)
+        .map_err(|e| format!("Unexpected Reminders output: {}", e))
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn read_reminders() -> Result<String, String> {
+    Err("Apple Reminders import is only available on macOS".to_string())
+}
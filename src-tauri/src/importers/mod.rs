@@ -0,0 +1,39 @@
+use chrono::{DateTime, Utc};
+use crate::structs::data_export::ImportSource;
+
+pub mod apple_reminders;
+mod ticktick;
+mod trello;
+
+pub use apple_reminders::AppleRemindersImporter;
+pub use ticktick::TickTickImporter;
+pub use trello::TrelloImporter;
+
+// A task read from another app's export, before it is stored
+#[derive(Debug, Clone)]
+pub struct ImportedTask {
+    // ID in the source app; items imported before are skipped
+    pub external_id: Option<String>,
+    pub title: String,
+    pub notes: Option<String>,
+    pub deadline: Option<DateTime<Utc>>,
+    // The deadline names a day, not a time; stored at the default deadline time on that day
+    pub all_day: bool,
+    pub created_at: Option<DateTime<Utc>>,
+    // Set for tasks already done in the source app
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+// One implementation per supported export format
+pub trait Importer {
+    // Stored as the external link source, so re-importing the same file adds nothing
+    fn source(&self) -> &'static str;
+    fn parse(&self, content: &str) -> Result<Vec<ImportedTask>, String>;
+}
+
+pub fn importer_for(source: ImportSource) -> Box<dyn Importer> {
+    match source {
+        ImportSource::Trello => Box::new(TrelloImporter),
+        ImportSource::TickTick => Box::new(TickTickImporter),
+    }
+}
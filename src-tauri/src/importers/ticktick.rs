@@ -0,0 +1,76 @@
+use chrono::{DateTime, Utc};
+use csv::{ReaderBuilder, StringRecord};
+use super::{ImportedTask, Importer};
+
+// CSV from TickTick's "Generate backup"; a few lines of preamble come before the header
+pub struct TickTickImporter;
+
+// Status column: 0 open, 1 completed, 2 archived after completion
+const OPEN_STATUS: &str = "0";
+
+impl Importer for TickTickImporter {
+    fn source(&self) -> &'static str {
+        "ticktick"
+    }
+    
+    fn parse(&self, content: &str) -> Result<Vec<ImportedTask>, String> {
+        let header_start = content.lines()
+            .position(|line| line.contains("\"Title\"") && line.contains("\"taskId\""))
+            .ok_or("Not a TickTick backup: no header row found")?;
+        let body: String = content.lines().skip(header_start).collect::<Vec<_>>().join("\n");
+        
+        let mut reader = ReaderBuilder::new().flexible(true).from_reader(body.as_bytes());
+        let headers = reader.headers()
+            .map_err(|e| format!("Failed to read TickTick header: {}", e))?
+            .clone();
+        let column = |name: &str| headers.iter().position(|h| h == name);
+        let title = column("Title").ok_or("TickTick backup has no Title column")?;
+        let (content_col, due, all_day, created, completed, status, task_id) = (
+            column("Content"),
+            column("Due Date"),
+            column("Is All Day"),
+            column("Created Time"),
+            column("Completed Time"),
+            column("Status"),
+            column("taskId"),
+        );
+        
+        let mut tasks = Vec::new();
+        for record in reader.records() {
+            let record = record.map_err(|e| format!("Invalid TickTick row: {}", e))?;
+            let title = field(&record, Some(title)).unwrap_or_default();
+            if title.is_empty() {
+                continue;
+            }
+            
+            let is_done = field(&record, status).is_some_and(|s| s != OPEN_STATUS);
+            tasks.push(ImportedTask {
+                external_id: field(&record, task_id),
+                notes: field(&record, content_col),
+                deadline: field(&record, due).and_then(|d| parse_time(&d)),
+                all_day: field(&record, all_day).is_some_and(|value| value == "true"),
+                created_at: field(&record, created).and_then(|d| parse_time(&d)),
+                completed_at: if is_done {
+                    Some(field(&record, completed).and_then(|d| parse_time(&d)).unwrap_or_else(Utc::now))
+                } else {
+                    None
+                },
+                title,
+            });
+        }
+        
+        Ok(tasks)
+    }
+}
+
+fn field(record: &StringRecord, index: Option<usize>) -> Option<String> {
+    index.and_then(|i| record.get(i))
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+// e.g. "2024-05-01T16:00:00+0000"
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%z").ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
@@ -0,0 +1,60 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use super::{ImportedTask, Importer};
+
+// Board JSON from Trello's "Print and export" menu
+pub struct TrelloImporter;
+
+#[derive(Deserialize)]
+struct Board {
+    cards: Vec<Card>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Card {
+    id: String,
+    name: String,
+    #[serde(default)]
+    desc: String,
+    due: Option<DateTime<Utc>>,
+    #[serde(default)]
+    closed: bool,
+    #[serde(default)]
+    due_complete: bool,
+    date_last_activity: Option<DateTime<Utc>>,
+}
+
+impl Importer for TrelloImporter {
+    fn source(&self) -> &'static str {
+        "trello"
+    }
+    
+    fn parse(&self, content: &str) -> Result<Vec<ImportedTask>, String> {
+        let board: Board = serde_json::from_str(content)
+            .map_err(|e| format!("Not a Trello board export: {}", e))?;
+        
+        // Archived cards are left behind
+        let tasks = board.cards.into_iter()
+            .filter(|card| !card.closed)
+            .map(|card| ImportedTask {
+                created_at: created_at_from_id(&card.id),
+                completed_at: card.due_complete
+                    .then(|| card.date_last_activity.unwrap_or_else(Utc::now)),
+                notes: Some(card.desc).filter(|desc| !desc.trim().is_empty()),
+                deadline: card.due,
+                all_day: false,
+                title: card.name,
+                external_id: Some(card.id),
+            })
+            .collect();
+        
+        Ok(tasks)
+    }
+}
+
+// Trello IDs are Mongo ObjectIds: the first 8 hex digits are the creation time
+fn created_at_from_id(id: &str) -> Option<DateTime<Utc>> {
+    let seconds = i64::from_str_radix(id.get(..8)?, 16).ok()?;
+    DateTime::from_timestamp(seconds, 0)
+}
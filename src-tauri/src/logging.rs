@@ -0,0 +1,154 @@
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, EnvFilter};
+
+const LOG_FILE_PREFIX: &str = "myhandler";
+const LOG_FILE_SUFFIX: &str = "log";
+const MAX_LOG_FILES: usize = 7;
+const MAX_RECENT_LINES: usize = 2000;
+
+// Secrets that must never reach the log file: the value following each marker is masked
+const SECRET_MARKERS: [&str; 5] = ["access_token", "refresh_token", "client_secret", "Bearer ", "ya29."];
+const REDACTED: &str = "[REDACTED]";
+
+// Keeps the background log writer alive; flushes pending lines when dropped
+pub struct LogGuard(#[allow(dead_code)] WorkerGuard);
+
+pub fn get_log_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path().app_log_dir()
+        .map_err(|e| format!("Failed to get log directory: {:?}", e))
+}
+
+// Install the global subscriber: daily-rotated file in app-data plus stdout in debug builds
+pub fn init_logging(app: &AppHandle) -> Result<(), String> {
+    let log_dir = get_log_dir(app)?;
+    fs::create_dir_all(&log_dir)
+        .map_err(|e| format!("Failed to create log directory: {}", e))?;
+    
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(LOG_FILE_PREFIX)
+        .filename_suffix(LOG_FILE_SUFFIX)
+        .max_log_files(MAX_LOG_FILES)
+        .build(&log_dir)
+        .map_err(|e| format!("Failed to create log file appender: {}", e))?;
+    let (file_writer, guard) = tracing_appender::non_blocking(RedactingWriter(appender));
+    
+    let filter = EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| EnvFilter::new("info"));
+    let file_layer = fmt::layer()
+        .with_ansi(false)
+        .with_writer(file_writer);
+    let stdout_layer = cfg!(debug_assertions).then(|| {
+        fmt::layer().with_writer(|| RedactingWriter(std::io::stdout()))
+    });
+    
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(file_layer)
+        .with(stdout_layer)
+        .try_init()
+        .map_err(|e| format!("Failed to install log subscriber: {}", e))?;
+    
+    app.manage(LogGuard(guard));
+    tracing::info!(log_dir = %log_dir.display(), "Logging initialized");
+    Ok(())
+}
+
+// Mask the value that follows any secret marker
+pub fn redact(line: &str) -> String {
+    let mut result = line.to_string();
+    
+    for marker in SECRET_MARKERS {
+        // "Bearer " and "ya29." prefix the secret directly; keys need `=` or a quote before the value
+        let needs_separator = marker.chars().all(|c| c.is_alphanumeric() || c == '_');
+        let mut search_from = 0;
+        
+        while let Some(offset) = result[search_from..].find(marker) {
+            let marker_end = search_from + offset + marker.len();
+            
+            // Skip separators between key and value: `":"`, `=`, `: "`
+            let value_start = result[marker_end..]
+                .find(|c: char| !matches!(c, '"' | '\'' | ':' | '=' | ' '))
+                .map(|i| marker_end + i)
+                .unwrap_or(result.len());
+            let value_end = result[value_start..]
+                .find(['"', '\'', '&', ',', '}', ' ', '\n'])
+                .map(|i| value_start + i)
+                .unwrap_or(result.len());
+            
+            let separator = &result[marker_end..value_start];
+            let is_key_value = !needs_separator || separator.contains(['=', '"', '\'']);
+            
+            if is_key_value && value_end > value_start && &result[value_start..value_end] != REDACTED {
+                result.replace_range(value_start..value_end, REDACTED);
+                search_from = value_start + REDACTED.len();
+            } else {
+                search_from = value_end.max(marker_end);
+            }
+        }
+    }
+    
+    result
+}
+
+struct RedactingWriter<W: Write>(W);
+
+impl<W: Write> Write for RedactingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let text = String::from_utf8_lossy(buf);
+        self.0.write_all(redact(&text).as_bytes())?;
+        Ok(buf.len())
+    }
+    
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.0.flush()
+    }
+}
+
+// Last `lines` log lines across the rotated files, oldest first
+pub fn get_recent_logs(app: &AppHandle, lines: usize) -> Result<Vec<String>, String> {
+    let log_dir = get_log_dir(app)?;
+    let lines = lines.min(MAX_RECENT_LINES);
+    
+    let mut files: Vec<PathBuf> = fs::read_dir(&log_dir)
+        .map_err(|e| format!("Failed to read log directory: {}", e))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .map(|name| name.starts_with(LOG_FILE_PREFIX) && name.ends_with(LOG_FILE_SUFFIX))
+                .unwrap_or(false)
+        })
+        .collect();
+    // Date-stamped names sort chronologically; newest first
+    files.sort();
+    files.reverse();
+    
+    let mut collected: Vec<String> = Vec::new();
+    for file in files {
+        if collected.len() >= lines {
+            break;
+        }
+        let content = fs::read_to_string(&file)
+            .map_err(|e| format!("Failed to read log file {:?}: {}", file, e))?;
+        let remaining = lines - collected.len();
+        let mut file_lines: Vec<String> = content.lines().rev().take(remaining).map(String::from).collect();
+        collected.append(&mut file_lines);
+    }
+    
+    collected.reverse();
+    Ok(collected)
+}
+
+pub fn open_log_folder(app: &AppHandle) -> Result<(), String> {
+    let log_dir = get_log_dir(app)?;
+    opener::open(&log_dir)
+        .map_err(|e| format!("Failed to open log folder: {}", e))
+}
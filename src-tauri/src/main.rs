@@ -8,61 +8,274 @@ mod helpers;
 mod services;
 mod commands;
 mod thirdparty;
+mod tray;
+mod deep_link;
+mod window_manager;
+mod badge;
+mod perf;
+mod shutdown;
+mod secrets;
+mod startup;
+mod logging;
+mod cli;
+mod http_api;
+mod importers;
 
-use commands::{
-  create_task, 
-  get_tasks_by_date, 
-  get_tasks_by_date_not_completed, 
-  start_task, 
-  pause_task, 
-  resume_task, 
-  complete_task, 
-  delete_task, 
-  get_task_by_id,
-  update_task,
-  get_settings,
-  update_settings,
-  start_calendar_auth,
-  get_calendar_status,
-  disconnect_calendar
-};
+use tauri::Manager;
+use services::app_info_service::CommandList;
+use tracing::{info, error};
+// Every command, re-exported from its module by commands/mod.rs
+use commands::*;
+
+// Expands to the invoke handler plus the names of the commands it registers
+macro_rules! app_commands {
+  ($($command:ident),* $(,)?) => {
+    (
+      tauri::generate_handler![$($command),*] as fn(tauri::ipc::Invoke) -> bool,
+      &[$(stringify!($command)),*] as &'static [&'static str],
+    )
+  };
+}
 
 fn main() {
   // Load environment variables from .env file
   dotenv::dotenv().ok();
   
+  let context = tauri::generate_context!();
+  
+  // Headless commands (`myhandler add ...`) run against the same database without the UI
+  if let Some(code) = cli::run(&context.config().identifier) {
+    std::process::exit(code);
+  }
+  
+  // One list registers the handlers and feeds get_app_info's command list
+  let (invoke_handler, command_names) = app_commands![
+    create_task, 
+    quick_add_task,
+    get_tasks_by_date, 
+    get_tasks_by_date_not_completed, 
+    get_today_overview,
+    start_task, 
+    pause_task, 
+    resume_task, 
+    complete_task, 
+    delete_task, 
+    get_task_by_id,
+    get_task_notes,
+    get_task_history,
+    update_task,
+    get_settings,
+    update_settings,
+    get_theme,
+    update_theme,
+    start_calendar_auth,
+    get_calendar_status,
+    disconnect_calendar,
+    notify_pomodoro_finished,
+    list_jobs,
+    run_job_now,
+    get_recent_logs,
+    open_log_folder,
+    export_diagnostics,
+    resolve_idle_time,
+    get_performance_stats,
+    get_app_info,
+    get_startup_phase,
+    get_recovery_status,
+    recover_database,
+    open_focus_window,
+    open_today_widget,
+    set_window_always_on_top,
+    regenerate_http_api_token,
+    get_network_permissions,
+    set_network_permissions,
+    export_data,
+    import_data,
+    export_archive,
+    import_archive,
+    export_csv,
+    export_markdown_summary,
+    export_shared_day,
+    export_pdf_agenda,
+    connect_github,
+    get_github_status,
+    disconnect_github,
+    set_github_close_issues,
+    sync_github_issues,
+    import_tasks,
+    import_apple_reminders,
+    cancel_import,
+    open_snapshot_folder,
+    get_productivity_stats,
+    get_time_breakdown,
+    get_pause_reasons,
+    get_completion_heatmap,
+    get_cycle_time_stats,
+    generate_weekly_review,
+    get_procrastination_stats,
+    create_goal,
+    get_goals,
+    update_goal,
+    delete_goal,
+    get_goal_progress,
+    get_consistency_score,
+    get_workload_forecast,
+    get_calendar_usage_stats,
+    get_usage_metrics,
+    export_usage_metrics,
+    get_daily_summary,
+    sync_now,
+    get_sync_status,
+    list_conflicts,
+    resolve_conflict,
+    clear_sync_conflicts,
+    get_lan_devices,
+    start_lan_pairing,
+    pair_lan_device,
+    unpair_lan_device,
+    sync_lan_now,
+    list_webhooks,
+    create_webhook,
+    delete_webhook,
+    get_webhook_deliveries,
+    retry_webhook_deliveries,
+    create_rule,
+    get_rules,
+    update_rule,
+    delete_rule,
+    capture,
+    list_inbox,
+    triage_inbox_item,
+    discard_inbox_item,
+    suggest_schedule,
+    apply_schedule,
+    run_readonly_query,
+    list_holidays,
+    import_holidays,
+    clear_holidays,
+    get_lock_status,
+    unlock_app,
+    lock_app,
+    set_app_lock,
+    disable_app_lock,
+    create_project,
+    get_projects,
+    delete_project,
+    share_project,
+    join_shared_project,
+    set_project_members,
+    sync_shared_projects,
+    post_work_sessions,
+    get_tasks_for_context,
+    suggest_deadline,
+    list_remembered_dates,
+    add_remembered_date,
+    update_remembered_date,
+    delete_remembered_date,
+    merge_tasks,
+    get_connectivity,
+    check_calendar_connection,
+    get_ongoing_task,
+    search_tasks,
+    get_completed_tasks,
+    get_all_dates_with_tasks,
+    get_date_sections,
+    get_tasks_by_ids,
+    suggest_tags,
+    get_tag_stats,
+    archive_project,
+    add_task_dependency,
+    remove_task_dependency,
+    get_dependency_graph,
+    get_calendar_event_rules,
+    create_calendar_event_rule,
+    update_calendar_event_rule,
+    delete_calendar_event_rule,
+    pull_calendar_events,
+    apply_default_reminders,
+    count_tasks,
+    get_tasks_window,
+    set_task_recurrence,
+    remove_task_recurrence,
+    get_task_recurrence,
+    add_recurrence_exception
+  ];
+  
   tauri::Builder::default()
+    // Must be registered first: forwards links from a second launch to this instance
+    .plugin(tauri_plugin_single_instance::init(|app, _argv, _cwd| {
+      if let Some(window) = app.get_webview_window(window_manager::MAIN_WINDOW) {
+        let _ = window.show();
+        let _ = window.set_focus();
+      }
+    }))
+    .plugin(tauri_plugin_deep_link::init())
+    .plugin(tauri_plugin_notification::init())
+    .plugin(window_manager::shortcut_plugin())
+    .manage(services::idle_service::IdleState::default())
+    .manage(services::recovery_service::RecoveryState::default())
+    .manage(http_api::HttpApiState::default())
+    .manage(services::vault_service::VaultState::default())
+    .manage(services::lan_sync_service::LanSyncState::default())
+    .manage(startup::StartupState::default())
     .setup(|app| {
-      match db::init_db(&app.handle()) {
-        Ok(_) => {
-          println!("Database initialized successfully");
-          Ok(())
-        }
+      // Logging comes first so database and plugin setup is captured
+      if let Err(e) = logging::init_logging(app.handle()) {
+        eprintln!("Failed to initialize logging: {}", e);
+      }
+      
+      match db::init_db(app.handle()) {
+        Ok(_) => info!("Database initialized successfully"),
         Err(e) => {
-          eprintln!("Failed to initialize database: {}", e);
-          eprintln!("App will continue but database features may not work");
-          // Don't crash the app, just log the error
-          Ok(())
+          error!("Failed to initialize database: {}", e);
+          // Keep the app usable on a temporary database until the user recovers
+          if let Err(e) = services::recovery_service::enter_recovery_mode(app.handle(), &e) {
+            error!("{}", e);
+            error!("App will continue but database features may not work");
+            return Ok(());
+          }
         }
       }
+      services::lock_service::init_app_lock(app.handle());
+      
+      services::metrics_service::init_metrics(app.handle());
+      if let Err(e) = tray::init_tray(app.handle()) {
+        error!("Failed to create tray icon: {}", e);
+      }
+      badge::init_badge(app.handle());
+      services::stats_service::init_stats_cache(app.handle());
+      services::goal_service::init_goal_tracking(app.handle());
+      services::sync_service::init_sync(app.handle());
+      services::webhook_service::init_webhooks(app.handle());
+      window_manager::init_quick_add_shortcut(app.handle());
+      window_manager::init_detached_windows(app.handle());
+      deep_link::init_deep_links(app.handle());
+      if let Err(e) = http_api::apply_settings(app.handle()) {
+        error!("{}", e);
+      }
+      // Everything else runs after setup returns, so the window isn't kept waiting
+      startup::run_deferred(app.handle());
+      Ok(())
+    })
+    .manage(CommandList(command_names))
+    .invoke_handler(move |invoke| {
+      // While locked, only the lock screen's commands get through
+      let locked = invoke.message.webview().try_state::<db::Database>().is_some_and(|db| db.is_locked());
+      if locked && !services::lock_service::LOCKED_COMMANDS.contains(&invoke.message.command()) {
+        invoke.resolver.reject("App is locked");
+        return true;
+      }
+      invoke_handler(invoke)
     })
-    .invoke_handler(tauri::generate_handler![
-      create_task, 
-      get_tasks_by_date, 
-      get_tasks_by_date_not_completed, 
-      start_task, 
-      pause_task, 
-      resume_task, 
-      complete_task, 
-      delete_task, 
-      get_task_by_id,
-      update_task,
-      get_settings,
-      update_settings,
-      start_calendar_auth,
-      get_calendar_status,
-      disconnect_calendar
-    ])
-    .run(tauri::generate_context!())
-    .expect("error while running tauri application");
+    .build(context)
+    .expect("error while building tauri application")
+    .run(|app, event| match event {
+      // Keep running in the tray when the last window closes so the
+      // quick-add shortcut still works; explicit exits carry a code
+      tauri::RunEvent::ExitRequested { api, code: None, .. } => api.prevent_exit(),
+      // Quitting closes windows without CloseRequested, so save their geometry here
+      tauri::RunEvent::ExitRequested { .. } => window_manager::save_window_states(app),
+      tauri::RunEvent::Exit => shutdown::shutdown(app),
+      _ => {}
+    });
 }
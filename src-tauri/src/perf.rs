@@ -0,0 +1,93 @@
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+use serde::Serialize;
+use crate::services::metrics_service;
+use tracing::warn;
+
+// Commands slower than this are logged; usually means waiting on the DB lock or the network
+const SLOW_COMMAND_MS: f64 = 250.0;
+// Samples kept per command for percentiles
+const MAX_SAMPLES: usize = 500;
+
+static SAMPLES: OnceLock<Mutex<HashMap<&'static str, VecDeque<f64>>>> = OnceLock::new();
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandStats {
+    pub command: String,
+    pub count: usize,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub max_ms: f64,
+}
+
+// Time a synchronous command body
+pub fn timed<T>(command: &'static str, body: impl FnOnce() -> T) -> T {
+    let started = Instant::now();
+    let result = body();
+    record(command, started);
+    result
+}
+
+// Time an async command body, including time spent awaiting
+pub async fn timed_async<T>(command: &'static str, body: impl Future<Output = T>) -> T {
+    let started = Instant::now();
+    let result = body.await;
+    record(command, started);
+    result
+}
+
+fn record(command: &'static str, started: Instant) {
+    metrics_service::record_command(command);
+    
+    let elapsed_ms = started.elapsed().as_secs_f64() * 1000.0;
+    if elapsed_ms > SLOW_COMMAND_MS {
+        warn!(command, elapsed_ms, "Slow command");
+    }
+    
+    let samples = SAMPLES.get_or_init(Default::default);
+    if let Ok(mut samples) = samples.lock() {
+        let entry = samples.entry(command).or_default();
+        if entry.len() == MAX_SAMPLES {
+            entry.pop_front();
+        }
+        entry.push_back(elapsed_ms);
+    }
+}
+
+// p50/p95/max per command over the recent samples, slowest p95 first
+pub fn get_performance_stats() -> Vec<CommandStats> {
+    let Some(samples) = SAMPLES.get() else {
+        return Vec::new();
+    };
+    let Ok(samples) = samples.lock() else {
+        return Vec::new();
+    };
+    
+    let mut stats: Vec<CommandStats> = samples.iter()
+        .filter(|(_, durations)| !durations.is_empty())
+        .map(|(command, durations)| {
+            let mut sorted: Vec<f64> = durations.iter().copied().collect();
+            sorted.sort_by(f64::total_cmp);
+            
+            CommandStats {
+                command: command.to_string(),
+                count: sorted.len(),
+                p50_ms: percentile(&sorted, 0.50),
+                p95_ms: percentile(&sorted, 0.95),
+                max_ms: sorted[sorted.len() - 1],
+            }
+        })
+        .collect();
+    
+    stats.sort_by(|a, b| b.p95_ms.total_cmp(&a.p95_ms));
+    stats
+}
+
+// Nearest-rank percentile of an already sorted, non-empty slice
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
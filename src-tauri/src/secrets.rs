@@ -0,0 +1,156 @@
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use chrono::Utc;
+use rusqlite::Connection;
+use crate::db;
+use crate::helpers::crypto::{self, SecretKey, SEALED_PREFIX};
+use crate::structs::secret::SecretBackend;
+use tracing::{info, warn};
+
+// Tokens and signing secrets for every integration, stored the same way.
+// Each goes to the OS keyring when there is one; without it, it's kept in the secrets table,
+// encrypted with a device key from a file beside the database, so a copy of the database alone
+// (backup, sync, export) doesn't give it away. The secrets table records where each one is.
+
+// Namespaces, one per integration
+pub const GOOGLE: &str = "google";
+pub const GITHUB: &str = "github";
+pub const WEBHOOK: &str = "webhook";
+pub const HTTP_API: &str = "http_api";
+pub const SLACK: &str = "slack";
+pub const SYNC: &str = "sync";
+
+// Service name keyring entries are filed under
+const KEYRING_SERVICE: &str = "MyHandler";
+const KEY_FILE_NAME: &str = "secrets.key";
+
+// An in-memory database has no folder for a key file; its secrets only last as long as it does
+static MEMORY_KEY: OnceLock<SecretKey> = OnceLock::new();
+
+pub fn store(conn: &Connection, namespace: &str, key: &str, value: &str) -> Result<(), String> {
+    let now = Utc::now();
+    let saved = match keyring_entry(namespace, key).and_then(|entry| {
+        entry.set_password(value)
+            .map_err(|e| format!("Failed to write to the keyring: {}", e))
+    }) {
+        Ok(()) => db::save_secret(conn, namespace, key, SecretBackend::Keyring, None, now),
+        Err(e) => {
+            warn!("Keeping secret {}/{} in the database: {}", namespace, key, e);
+            let encrypted = crypto::to_hex(&crypto::seal(&device_key(conn)?, value.as_bytes())?);
+            db::save_secret(conn, namespace, key, SecretBackend::Database, Some(&encrypted), now)
+        }
+    };
+    saved.map_err(|e| format!("Failed to save secret {}/{}: {}", namespace, key, e))
+}
+
+pub fn get(conn: &Connection, namespace: &str, key: &str) -> Result<Option<String>, String> {
+    let stored = db::get_secret(conn, namespace, key)
+        .map_err(|e| format!("Failed to read secret {}/{}: {}", namespace, key, e))?;
+    let Some(stored) = stored else {
+        return Ok(None);
+    };
+    
+    match stored.backend {
+        SecretBackend::Keyring => match keyring_entry(namespace, key)?.get_password() {
+            Ok(value) => Ok(Some(value)),
+            // Removed from the keyring by hand, or a database restored from another device
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(format!("Failed to read secret {}/{} from the keyring: {}", namespace, key, e)),
+        },
+        SecretBackend::Database => {
+            let value = stored.value.unwrap_or_default();
+            if value.starts_with(SEALED_PREFIX) {
+                return Err(format!("Secret {}/{} can't be read while the app is locked", namespace, key));
+            }
+            let bytes = crypto::open(&device_key(conn)?, &crypto::from_hex(&value)?)?;
+            String::from_utf8(bytes)
+                .map(Some)
+                .map_err(|_| format!("Secret {}/{} isn't valid UTF-8", namespace, key))
+        }
+    }
+}
+
+pub fn delete(conn: &Connection, namespace: &str, key: &str) -> Result<(), String> {
+    let stored = db::get_secret(conn, namespace, key)
+        .map_err(|e| format!("Failed to read secret {}/{}: {}", namespace, key, e))?;
+    if stored.is_some_and(|stored| stored.backend == SecretBackend::Keyring) {
+        match keyring_entry(namespace, key)?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => {}
+            Err(e) => return Err(format!("Failed to remove secret {}/{} from the keyring: {}", namespace, key, e)),
+        }
+    }
+    
+    db::delete_secret(conn, namespace, key)
+        .map(|_| ())
+        .map_err(|e| format!("Failed to delete secret {}/{}: {}", namespace, key, e))
+}
+
+// Move secrets still in their integration's own table into the store, and empty the old columns.
+// Values sealed by the app lock can't be read while it's locked, so then nothing moves until the unlock.
+pub fn move_legacy(conn: &Connection) -> Result<usize, String> {
+    let legacy = db::get_legacy_secrets(conn)
+        .map_err(|e| format!("Failed to read stored secrets: {}", e))?;
+    if legacy.is_empty() || legacy.iter().any(|secret| secret.value.starts_with(SEALED_PREFIX)) {
+        return Ok(0);
+    }
+    
+    let tx = conn.unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    for secret in &legacy {
+        store(&tx, &secret.namespace, &secret.name, &secret.value)?;
+    }
+    db::clear_legacy_secrets(&tx)
+        .and_then(|_| tx.commit())
+        .map_err(|e| format!("Failed to clear moved secrets: {}", e))?;
+    
+    info!("Moved {} secrets into the secrets store", legacy.len());
+    Ok(legacy.len())
+}
+
+fn keyring_entry(namespace: &str, key: &str) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYRING_SERVICE, &format!("{}/{}", namespace, key))
+        .map_err(|e| format!("Failed to open keyring entry {}/{}: {}", namespace, key, e))
+}
+
+// Key for secrets kept in the database, created the first time one is
+fn device_key(conn: &Connection) -> Result<SecretKey, String> {
+    let Some(path) = key_path(conn) else {
+        return SecretKey::from_bytes(MEMORY_KEY.get_or_init(SecretKey::random).as_bytes());
+    };
+    
+    match fs::read_to_string(&path) {
+        Ok(hex) => return SecretKey::from_bytes(&crypto::from_hex(hex.trim())?),
+        Err(e) if e.kind() != ErrorKind::NotFound => {
+            return Err(format!("Failed to read {}: {}", path.display(), e));
+        }
+        Err(_) => {}
+    }
+    
+    let key = SecretKey::random();
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    match options.open(&path) {
+        Ok(mut file) => {
+            file.write_all(crypto::to_hex(key.as_bytes()).as_bytes())
+                .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+            info!("Created device key {}", path.display());
+            Ok(key)
+        }
+        // Another connection got there first
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => device_key(conn),
+        Err(e) => Err(format!("Failed to create {}: {}", path.display(), e)),
+    }
+}
+
+fn key_path(conn: &Connection) -> Option<PathBuf> {
+    conn.path()
+        .filter(|path| !path.is_empty())
+        .and_then(|path| Path::new(path).parent().map(|dir| dir.join(KEY_FILE_NAME)))
+}
@@ -0,0 +1,169 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::BufWriter;
+use chrono::{FixedOffset, NaiveDate};
+use printpdf::path::PaintMode;
+use printpdf::{
+    BuiltinFont, IndirectFontRef, Line, Mm, PdfDocument, PdfDocumentReference,
+    PdfLayerReference, Point, Rect,
+};
+use crate::db::{self, Database};
+use crate::helpers::format::Formatter;
+use crate::helpers::parse_date::{last_day, local_day, parse_date_range};
+use crate::services::settings_service;
+use crate::structs::data_export::PdfAgendaData;
+use crate::structs::task_struct::{Status, Task};
+use tracing::info;
+
+// A4 portrait; all positions in millimetres from the bottom-left corner
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const MARGIN: f32 = 18.0;
+const LINE_HEIGHT: f32 = 7.0;
+const CHECKBOX_SIZE: f32 = 3.5;
+const DEADLINE_COLUMN: f32 = PAGE_WIDTH - MARGIN - 34.0;
+// Built-in fonts can't be measured; this keeps titles clear of the deadline column
+const MAX_TITLE_CHARS: usize = 72;
+
+// Printable agenda of tasks created in the range, grouped by day; returns the task count
+pub fn export_pdf_agenda(db: &Database, payload: PdfAgendaData) -> Result<usize, String> {
+    let offset = settings_service::day_offset(db, payload.date_range.utc_offset_minutes)?;
+    let formatter = settings_service::formatter(db)?;
+    let (start, _) = parse_date_range("from", &payload.date_range.from, Some(offset))?;
+    let (_, end) = parse_date_range("to", &payload.date_range.to, Some(offset))?;
+    if start >= end {
+        return Err("Agenda range ends before it starts".to_string());
+    }
+    
+    let tasks = {
+        let sql = include_str!("../db/sql/get_tasks_created_between.sql");
+        let conn = db.get_read_connection();
+        db::query_tasks_by_date_range(&conn, start, end, sql)
+            .map_err(|e| format!("Failed to query tasks: {}", e))?
+    }; // DB lock released here
+    
+    // Grouped by the same local days the range was cut at
+    let mut days: BTreeMap<NaiveDate, Vec<&Task>> = BTreeMap::new();
+    for task in &tasks {
+        days.entry(local_day(task.created_at, offset)).or_default().push(task);
+    }
+    
+    let title = format!("Agenda {} to {}", formatter.numeric_date(local_day(start, offset)), formatter.numeric_date(last_day(end, offset)));
+    let mut pdf = AgendaPdf::new(&title, formatter, offset)?;
+    pdf.heading(&title, 16.0);
+    
+    if days.is_empty() {
+        pdf.note("No tasks in this range");
+    }
+    for (day, tasks) in &days {
+        pdf.gap();
+        pdf.heading(&formatter.long_date(*day), 12.0);
+        for task in tasks {
+            pdf.task(task);
+        }
+    }
+    
+    pdf.save(&payload.path)?;
+    info!("Exported agenda of {} tasks to {}", tasks.len(), payload.path);
+    Ok(tasks.len())
+}
+
+struct AgendaPdf {
+    doc: PdfDocumentReference,
+    layer: PdfLayerReference,
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+    formatter: Formatter,
+    // Deadlines are shown in the offset the agenda's days were cut at
+    offset: FixedOffset,
+    // Baseline of the next line
+    y: f32,
+}
+
+impl AgendaPdf {
+    fn new(title: &str, formatter: Formatter, offset: FixedOffset) -> Result<Self, String> {
+        let (doc, page, layer) = PdfDocument::new(title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Agenda");
+        let regular = doc.add_builtin_font(BuiltinFont::Helvetica)
+            .map_err(|e| format!("Failed to load PDF font: {}", e))?;
+        let bold = doc.add_builtin_font(BuiltinFont::HelveticaBold)
+            .map_err(|e| format!("Failed to load PDF font: {}", e))?;
+        let layer = doc.get_page(page).get_layer(layer);
+        
+        Ok(Self {
+            doc,
+            layer,
+            regular,
+            bold,
+            formatter,
+            offset,
+            y: PAGE_HEIGHT - MARGIN,
+        })
+    }
+    
+    // Move to the next line, starting a new page when this one is full
+    fn advance(&mut self, height: f32) -> f32 {
+        if self.y - height < MARGIN {
+            let (page, layer) = self.doc.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Agenda");
+            self.layer = self.doc.get_page(page).get_layer(layer);
+            self.y = PAGE_HEIGHT - MARGIN;
+        }
+        self.y -= height;
+        self.y
+    }
+    
+    fn gap(&mut self) {
+        self.y -= LINE_HEIGHT / 2.0;
+    }
+    
+    fn heading(&mut self, text: &str, size: f32) {
+        let y = self.advance(LINE_HEIGHT + 2.0);
+        self.layer.use_text(text, size, Mm(MARGIN), Mm(y), &self.bold);
+    }
+    
+    fn note(&mut self, text: &str) {
+        let y = self.advance(LINE_HEIGHT);
+        self.layer.use_text(text, 10.0, Mm(MARGIN), Mm(y), &self.regular);
+    }
+    
+    fn task(&mut self, task: &Task) {
+        let y = self.advance(LINE_HEIGHT);
+        
+        let (left, bottom) = (MARGIN, y - 0.5);
+        let (right, top) = (left + CHECKBOX_SIZE, bottom + CHECKBOX_SIZE);
+        self.layer.set_outline_thickness(0.6);
+        self.layer.add_rect(Rect::new(Mm(left), Mm(bottom), Mm(right), Mm(top)).with_mode(PaintMode::Stroke));
+        if task.status == Status::Completed {
+            self.stroke(&[(left, bottom), (right, top)]);
+            self.stroke(&[(left, top), (right, bottom)]);
+        }
+        
+        self.layer.use_text(shorten(&task.title), 10.0, Mm(left + CHECKBOX_SIZE + 3.0), Mm(y), &self.regular);
+        if let Some(deadline) = task.deadline {
+            let due = format!("Due {}", self.formatter.short_date_time(deadline.with_timezone(&self.offset).naive_local()));
+            self.layer.use_text(due, 9.0, Mm(DEADLINE_COLUMN), Mm(y), &self.regular);
+        }
+    }
+    
+    fn stroke(&self, points: &[(f32, f32)]) {
+        self.layer.add_line(Line {
+            points: points.iter().map(|&(x, y)| (Point::new(Mm(x), Mm(y)), false)).collect(),
+            is_closed: false,
+        });
+    }
+    
+    fn save(self, path: &str) -> Result<(), String> {
+        let file = File::create(path)
+            .map_err(|e| format!("Failed to create PDF file: {}", e))?;
+        self.doc.save(&mut BufWriter::new(file))
+            .map_err(|e| format!("Failed to write PDF file: {}", e))
+    }
+}
+
+fn shorten(title: &str) -> String {
+    let title = title.replace(['\r', '\n'], " ");
+    if title.chars().count() <= MAX_TITLE_CHARS {
+        return title;
+    }
+    let kept: String = title.chars().take(MAX_TITLE_CHARS - 1).collect();
+    format!("{}…", kept.trim_end())
+}
@@ -0,0 +1,204 @@
+use std::collections::HashSet;
+use chrono::{Duration, NaiveDate};
+use crate::db::{self, Database};
+use crate::helpers::parse_date::{last_day, local_day, parse_date_range};
+use crate::services::settings_service;
+use crate::structs::stats::{ConsistencyQuery, ConsistencyScore, ScoreComponent};
+use crate::structs::task_struct::Task;
+
+// A week of daily completions earns the full streak score
+const STREAK_TARGET_DAYS: i64 = 7;
+// Two hours of tracked work per day earns the full focus score
+const FOCUS_TARGET_MINUTES_PER_DAY: f64 = 120.0;
+const STREAK_WEIGHT: f64 = 0.4;
+const ON_TIME_WEIGHT: f64 = 0.35;
+const FOCUS_WEIGHT: f64 = 0.25;
+
+// One 0-100 score from completion streaks, deadlines met and tracked time, with each part shown
+pub fn get_consistency_score(db: &Database, payload: ConsistencyQuery) -> Result<ConsistencyScore, String> {
+    let offset = settings_service::day_offset(db, payload.range.utc_offset_minutes)?;
+    let (start, _) = parse_date_range("from", &payload.range.from, Some(offset))?;
+    let (_, end) = parse_date_range("to", &payload.range.to, Some(offset))?;
+    if start >= end {
+        return Err("Stats range ends before it starts".to_string());
+    }
+    
+    // Day summaries only hold days in the settings offset
+    let summarized = offset == settings_service::day_offset(db, None)?;
+    let (active_days, summarized_minutes, completed) = {
+        let conn = db.get_read_connection();
+        let (active_days, summarized_minutes): (Vec<String>, Option<i64>) = if summarized {
+            db::get_day_summaries(&conn, local_day(start, offset), last_day(end, offset))
+                .map(|rows| {
+                    let tracked = rows.iter().map(|row| row.tracked_minutes).sum();
+                    (rows.into_iter().filter(|row| row.completed > 0).map(|row| row.day).collect(), Some(tracked))
+                })
+        } else {
+            db::get_completion_days(&conn, start, end, offset).map(|days| (days, None))
+        }.map_err(|e| format!("Failed to compute daily completions: {}", e))?;
+        let completed = db::query_tasks_by_date_range(&conn, start, end, include_str!("../db/sql/get_tasks_completed_between.sql"))
+            .map_err(|e| format!("Failed to query tasks: {}", e))?;
+        (active_days, summarized_minutes, completed)
+    }; // DB lock released here
+    
+    let active_days: Vec<NaiveDate> = active_days.iter()
+        .filter_map(|day| NaiveDate::parse_from_str(day, "%Y-%m-%d").ok())
+        .collect();
+    let (current_streak, longest_streak) = streaks(&active_days, last_day(end, offset));
+    
+    let with_deadline: Vec<&Task> = completed.iter().filter(|task| task.deadline.is_some()).collect();
+    let on_time = with_deadline.iter().filter(|task| task.completed_at <= task.deadline).count();
+    
+    // Day summaries already hold the minutes tracked on each day
+    let tracked_minutes: i64 = summarized_minutes.unwrap_or_else(|| completed.iter()
+        .filter_map(Task::time_spent)
+        .map(|spent| spent.num_minutes())
+        .sum());
+    let range_days = (last_day(end, offset) - local_day(start, offset)).num_days() + 1;
+    
+    let streak = ScoreComponent {
+        value: current_streak as f64,
+        score: Some(streak_score(current_streak)),
+        weight: STREAK_WEIGHT,
+    };
+    let on_time = ScoreComponent {
+        value: on_time as f64,
+        score: on_time_rate(on_time, with_deadline.len()),
+        weight: ON_TIME_WEIGHT,
+    };
+    let focus = ScoreComponent {
+        value: tracked_minutes as f64,
+        score: Some(focus_score(tracked_minutes, range_days)),
+        weight: FOCUS_WEIGHT,
+    };
+    
+    Ok(ConsistencyScore {
+        score: combine(&[&streak, &on_time, &focus]),
+        current_streak,
+        longest_streak,
+        streak,
+        on_time,
+        focus,
+    })
+}
+
+// (current, longest) runs of consecutive days; the current run may end the day before `through`,
+// since that day may not be over yet
+fn streaks(days: &[NaiveDate], through: NaiveDate) -> (i64, i64) {
+    let set: HashSet<NaiveDate> = days.iter().copied().collect();
+    
+    let mut longest = 0;
+    for day in &set {
+        // Count each run once, from its first day
+        if set.contains(&(*day - Duration::days(1))) {
+            continue;
+        }
+        let mut length = 1;
+        while set.contains(&(*day + Duration::days(length))) {
+            length += 1;
+        }
+        longest = longest.max(length);
+    }
+    
+    let mut day = if set.contains(&through) { through } else { through - Duration::days(1) };
+    let mut current = 0;
+    while set.contains(&day) {
+        current += 1;
+        day -= Duration::days(1);
+    }
+    
+    (current, longest)
+}
+
+fn streak_score(current_streak: i64) -> f64 {
+    (current_streak as f64 / STREAK_TARGET_DAYS as f64).min(1.0)
+}
+
+// None when no completed task had a deadline
+fn on_time_rate(on_time: usize, with_deadline: usize) -> Option<f64> {
+    (with_deadline > 0).then(|| on_time as f64 / with_deadline as f64)
+}
+
+fn focus_score(tracked_minutes: i64, days: i64) -> f64 {
+    if days <= 0 {
+        return 0.0;
+    }
+    (tracked_minutes as f64 / days as f64 / FOCUS_TARGET_MINUTES_PER_DAY).min(1.0)
+}
+
+// Weighted average on 0-100; components without a score give their weight to the others
+fn combine(components: &[&ScoreComponent]) -> f64 {
+    let (sum, weights) = components.iter()
+        .filter_map(|component| component.score.map(|score| (score * component.weight, component.weight)))
+        .fold((0.0, 0.0), |(sum, weights), (value, weight)| (sum + value, weights + weight));
+    if weights == 0.0 {
+        0.0
+    } else {
+        sum / weights * 100.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, d).unwrap()
+    }
+    
+    fn component(score: Option<f64>, weight: f64) -> ScoreComponent {
+        ScoreComponent { value: 0.0, score, weight }
+    }
+    
+    #[test]
+    fn streaks_count_current_and_longest_runs() {
+        let days = [day(1), day(2), day(3), day(4), day(8), day(9)];
+        assert_eq!(streaks(&days, day(9)), (2, 4));
+    }
+    
+    #[test]
+    fn current_streak_survives_an_unfinished_last_day() {
+        let days = [day(7), day(8)];
+        assert_eq!(streaks(&days, day(9)), (2, 2));
+    }
+    
+    #[test]
+    fn current_streak_breaks_after_a_missed_day() {
+        let days = [day(5), day(6)];
+        assert_eq!(streaks(&days, day(9)), (0, 2));
+        assert_eq!(streaks(&[], day(9)), (0, 0));
+    }
+    
+    #[test]
+    fn streak_score_caps_at_target() {
+        assert_eq!(streak_score(0), 0.0);
+        assert!((streak_score(3) - 3.0 / 7.0).abs() < 1e-9);
+        assert_eq!(streak_score(30), 1.0);
+    }
+    
+    #[test]
+    fn on_time_rate_needs_deadlines() {
+        assert_eq!(on_time_rate(0, 0), None);
+        assert_eq!(on_time_rate(3, 4), Some(0.75));
+    }
+    
+    #[test]
+    fn focus_score_averages_over_the_range() {
+        assert_eq!(focus_score(840, 7), 1.0);
+        assert_eq!(focus_score(420, 7), 0.5);
+        assert_eq!(focus_score(100, 0), 0.0);
+    }
+    
+    #[test]
+    fn combine_weights_components() {
+        let score = combine(&[&component(Some(1.0), 0.4), &component(Some(0.5), 0.35), &component(Some(0.0), 0.25)]);
+        assert!((score - 57.5).abs() < 1e-9);
+    }
+    
+    #[test]
+    fn combine_redistributes_missing_components() {
+        let score = combine(&[&component(Some(1.0), 0.4), &component(None, 0.35), &component(Some(0.5), 0.4)]);
+        assert!((score - 75.0).abs() < 1e-9);
+        assert_eq!(combine(&[&component(None, 1.0)]), 0.0);
+    }
+}
@@ -0,0 +1,32 @@
+use tauri::AppHandle;
+use crate::db::{self, Database};
+use crate::importers::apple_reminders;
+use crate::services::legacy_service;
+use crate::structs::app_info::{AppFeatures, AppInfo};
+
+// Calendar integrations compiled into this build
+const CALENDAR_PROVIDERS: [&str; 1] = ["google"];
+
+// Names of the registered commands, managed at startup from the handler list
+pub struct CommandList(pub &'static [&'static str]);
+
+pub fn get_app_info(db: &Database, app: &AppHandle, commands: &CommandList) -> Result<AppInfo, String> {
+    let conn = db.get_connection();
+    
+    let schema_version = db::get_schema_version(&conn)
+        .map_err(|e| format!("Failed to read schema version: {}", e))?;
+    let full_text_search = db::has_fts5(&conn)
+        .map_err(|e| format!("Failed to check full text search support: {}", e))?;
+    
+    Ok(AppInfo {
+        version: app.package_info().version.to_string(),
+        schema_version,
+        features: AppFeatures {
+            calendar_providers: CALENDAR_PROVIDERS.iter().map(|p| p.to_string()).collect(),
+            full_text_search,
+            apple_reminders_import: apple_reminders::SUPPORTED,
+        },
+        commands: commands.0.iter().map(|c| c.to_string()).collect(),
+        legacy_api_version: legacy_service::LEGACY_API_VERSION,
+    })
+}
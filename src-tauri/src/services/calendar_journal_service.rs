@@ -0,0 +1,177 @@
+use std::collections::HashSet;
+use rusqlite::Connection;
+use crate::db::{self, Database};
+use crate::services::{calendar_service, connectivity_service, network_permission_service};
+use crate::structs::calendar_journal::JournalOperation;
+use crate::structs::network::NetworkFeature;
+use crate::structs::settings::CompletionEffect;
+use crate::structs::task_struct::{Status, TaskId};
+use tracing::{info, warn, error};
+
+// Entries still failing after this many attempts are given up on
+const MAX_ATTEMPTS: i32 = 10;
+
+// Record a side effect; call inside the transaction that changes the task
+pub fn record(conn: &Connection, task_id: TaskId, operation: JournalOperation, event_id: Option<&str>) -> Result<i64, String> {
+    db::record_calendar_op(conn, task_id, operation, event_id)
+        .map_err(|e| format!("Failed to record calendar side effect: {}", e))
+}
+
+// Mark an entry (and older ones it supersedes) done, or count a failed attempt
+pub fn finish(db: &Database, entry_id: i64, task_id: TaskId, operation: JournalOperation, result: &Result<(), String>) {
+    let conn = db.get_connection();
+    let outcome = match result {
+        // Event removed outside the app: nothing left to apply
+        Ok(_) => db::complete_calendar_ops(&conn, task_id, operation, entry_id),
+        Err(e) if e == "EVENT_NOT_FOUND" => db::complete_calendar_ops(&conn, task_id, operation, entry_id),
+        // Never sent, so not an attempt; it waits for the connection to come back
+        Err(e) if e == connectivity_service::OFFLINE => Ok(()),
+        // Held until the calendar account is connected again
+        Err(e) if e == calendar_service::REAUTH_NEEDED => Ok(()),
+        Err(e) => db::fail_calendar_op(&conn, entry_id, e),
+    };
+    
+    if let Err(e) = outcome {
+        error!("Failed to update calendar journal: {}", e);
+    }
+}
+
+// Entries still to be sent, e.g. while offline
+pub fn pending_count(db: &Database) -> Result<i64, String> {
+    let conn = db.get_read_connection();
+    db::count_pending_calendar_ops(&conn, MAX_ATTEMPTS)
+        .map_err(|e| format!("Failed to read calendar journal: {}", e))
+}
+
+// Apply side effects a crash or failed call left behind; every operation is safe to repeat
+pub async fn replay(db: &Database) {
+    // Without consent nothing can be sent; keep the entries for later
+    if network_permission_service::ensure_allowed(db, NetworkFeature::Calendar).is_err() {
+        return;
+    }
+    // The connectivity check replays them once we're back
+    if !connectivity_service::is_online() {
+        return;
+    }
+    
+    let entries = {
+        let conn = db.get_connection();
+        db::get_pending_calendar_ops(&conn, MAX_ATTEMPTS)
+    }; // DB lock released here
+    
+    let entries = match entries {
+        Ok(entries) => entries,
+        Err(e) => {
+            error!("Failed to read calendar journal: {}", e);
+            return;
+        }
+    };
+    
+    if entries.is_empty() {
+        return;
+    }
+    info!("Replaying {} unfinished calendar side effects", entries.len());
+    
+    // Newest entry per task and operation covers the older ones
+    let mut seen = HashSet::new();
+    for entry in entries.iter().rev() {
+        if !seen.insert((entry.task_id, entry.operation)) {
+            continue;
+        }
+        
+        let task_id = entry.task_id;
+        let result = match (entry.operation, &entry.event_id) {
+            (JournalOperation::Sync, _) => sync_task_event(db, task_id).await,
+            (JournalOperation::Delete, Some(event_id)) => calendar_service::delete_task_calendar_event(db, event_id).await,
+            (JournalOperation::Delete, None) => Ok(()),
+        };
+        
+        if let Err(e) = &result {
+            warn!("Calendar side effect for task {} failed again (attempt {}): {}", task_id, entry.attempts + 1, e);
+        }
+        finish(db, entry.id, task_id, entry.operation, &result);
+    }
+}
+
+// Send a Sync entry recorded with a new task now; a failure leaves it for the next replay
+pub async fn sync_now(db: &Database, entry_id: i64, task_id: TaskId) {
+    let result = sync_task_event(db, task_id).await;
+    if let Err(e) = &result {
+        warn!("Calendar side effect for task {} failed: {}", task_id, e);
+    }
+    finish(db, entry_id, task_id, JournalOperation::Sync, &result);
+}
+
+// Make the task's event match the task as it is now
+async fn sync_task_event(db: &Database, task_id: TaskId) -> Result<(), String> {
+    let keep_completed = db.settings()
+        .map_err(|e| format!("Failed to fetch settings: {}", e))?
+        .completion_effects
+        .contains(CompletionEffect::KeepCalendarEvent);
+    
+    let (task, event_id) = {
+        let conn = db.get_connection();
+        
+        let task = match db::get_task_by_id(&conn, task_id) {
+            Ok(task) => task,
+            // Deleting the task removed its event link; a Delete entry covers the event
+            Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(()),
+            Err(e) => return Err(format!("Failed to get task: {}", e)),
+        };
+        let event_id = db::get_task_google_event_id(&conn, task_id)
+            .map_err(|e| format!("Failed to get calendar event: {}", e))?;
+        
+        (task, event_id)
+    }; // DB lock released here
+    
+    // Completed tasks keep their event only with the keep-calendar-event completion effect
+    let wanted = task.has_calendar_integration && (task.status != Status::Completed || keep_completed);
+    
+    match (task.deadline.filter(|_| wanted), event_id) {
+        (Some(deadline), Some(event_id)) => {
+            // Paused and completed tasks keep their event without reminders
+            let reminder_frequency = match task.status {
+                Status::Paused | Status::Completed => String::new(),
+                _ => String::from(task.reminder_frequency.clone()),
+            };
+            let result = calendar_service::update_task_calendar_event(
+                db,
+                &event_id,
+                task_id,
+                &task.title,
+                Some(&calendar_service::task_event_description(&task)),
+                deadline,
+                &reminder_frequency,
+            ).await;
+            
+            if matches!(&result, Err(e) if e == "EVENT_NOT_FOUND") {
+                let conn = db.get_connection();
+                let _ = db::clear_task_google_event_id(&conn, task_id);
+            }
+            result
+        }
+        (Some(deadline), None) if task.status != Status::Completed => {
+            let event_id = calendar_service::create_task_calendar_event(
+                db,
+                task_id,
+                &task.title,
+                Some(&calendar_service::task_event_description(&task)),
+                deadline,
+                &String::from(task.reminder_frequency.clone()),
+            ).await?;
+            
+            let conn = db.get_connection();
+            db::update_task_google_event_id(&conn, task_id, &event_id)
+                .map_err(|e| format!("Failed to save calendar event: {}", e))
+        }
+        (None, Some(event_id)) => {
+            calendar_service::delete_task_calendar_event(db, &event_id).await?;
+            
+            let conn = db.get_connection();
+            db::clear_task_google_event_id(&conn, task_id)
+                .map_err(|e| format!("Failed to clear calendar event: {}", e))
+        }
+        // A kept event is only ever updated; none is created for a finished task
+        (Some(_), None) | (None, None) => Ok(()),
+    }
+}
@@ -0,0 +1,230 @@
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveTime, Utc};
+use tauri::{AppHandle, Manager};
+use uuid::timestamp::context::NoContext;
+use uuid::{Timestamp, Uuid};
+use crate::db::{self, Database};
+use crate::helpers::datetime::deadline_on;
+use crate::services::{calendar_service, event_service, rule_service, settings_service, task_service};
+use crate::structs::calendar_event::{EventOrigin, ListedEventTime};
+use crate::structs::calendar_rule::{
+    CalendarEventRule, CalendarEventRuleData, CalendarEventRuleId, CalendarEventRuleUpdate, CalendarEventRuleUpdateParsed, CalendarPullReport,
+};
+use crate::structs::rule::RuleTrigger;
+use crate::structs::task_struct::{Status, Task, TaskId};
+use crate::structs::task_update::{TaskUpdate, TaskUpdateData};
+use tracing::{info, warn};
+
+const MAX_NAME_LEN: usize = 255;
+const MAX_PREFIX_LEN: usize = 255;
+// Events further out than this are left until they come closer
+const PULL_DAYS_AHEAD: i64 = 30;
+
+// Calendar pull job: make tasks from new events the rules match, and move the deadlines of tasks whose event moved.
+// Nothing is read from Google while no rule is enabled.
+pub async fn pull_calendar_events(app: &AppHandle) -> Result<CalendarPullReport, String> {
+    let db = app.try_state::<Database>()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    let mut report = CalendarPullReport::default();
+    
+    let rules: Vec<CalendarEventRule> = list_rules(&db)?
+        .into_iter()
+        .filter(|rule| rule.enabled)
+        .collect();
+    if rules.is_empty() || calendar_service::get_credentials(&db)?.is_none() {
+        return Ok(report);
+    }
+    let deadline_time = settings_service::default_deadline_time(&db)?;
+    let reminder_frequency = settings_service::reminder_frequency(&db, None)?;
+    
+    let now = Utc::now();
+    let events = calendar_service::list_calendar_events(&db, now, now + Duration::days(PULL_DAYS_AHEAD)).await?;
+    let links = {
+        let conn = db.get_read_connection();
+        db::get_pulled_event_links(&conn)
+            .map_err(|e| format!("Failed to fetch calendar links: {}", e))?
+    }; // DB lock released here
+    
+    let mut new_events = Vec::new();
+    let mut moved_events = Vec::new();
+    for event in events {
+        if event.status.as_deref() == Some("cancelled") {
+            continue;
+        }
+        let Some(start) = event.start.as_ref().and_then(|start| event_start(start, deadline_time)) else {
+            continue;
+        };
+        match links.get(&event.id) {
+            // Events made for tasks are never turned into tasks themselves
+            Some(link) if link.origin == EventOrigin::Task => {}
+            Some(link) if link.event_start == Some(start) => {}
+            Some(link) => moved_events.push((event.id, link.task_id, link.event_start.is_some(), start)),
+            None => {
+                if let Some(title) = event.summary.as_deref().and_then(|summary| task_title(&rules, summary)) {
+                    new_events.push((event.id, title, start));
+                }
+            }
+        }
+    }
+    
+    // Task and link go in together, so an event can't end up with two tasks
+    let created = {
+        let conn = db.get_connection();
+        let tx = conn.unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        
+        let mut created = Vec::new();
+        for (event_id, title, start) in &new_events {
+            let mut task = Task::new(title, now, None, reminder_frequency.clone());
+            task.deadline = Some(*start);
+            db::insert(&tx, &task)
+                .and_then(|_| db::link_pulled_event(&tx, TaskId::from(task.id), event_id, *start))
+                .map_err(|e| format!("Failed to create task from calendar event: {}", e))?;
+            created.push(task);
+        }
+        
+        tx.commit().map_err(|e| format!("Failed to create tasks from calendar events: {}", e))?;
+        created
+    }; // DB lock released here
+    
+    for task in &created {
+        event_service::emit_task_created(app, task);
+        rule_service::run_rules(app, RuleTrigger::TaskCreated, task);
+    }
+    report.created = created.len();
+    
+    for (event_id, task_id, known_start, start) in moved_events {
+        // Links restored from an export don't know the start their deadline came from; nothing to move
+        if known_start && move_deadline(&db, app, task_id, start).await? {
+            report.moved += 1;
+        }
+        let conn = db.get_connection();
+        db::set_pulled_event_start(&conn, &event_id, start)
+            .map_err(|e| format!("Failed to record event start: {}", e))?;
+    }
+    
+    if report.created > 0 || report.moved > 0 {
+        info!("Calendar pull created {} tasks and moved {}", report.created, report.moved);
+    }
+    Ok(report)
+}
+
+// Deleted and completed tasks stay as they are
+async fn move_deadline(db: &Database, app: &AppHandle, task_id: TaskId, start: DateTime<Utc>) -> Result<bool, String> {
+    let open = {
+        let conn = db.get_read_connection();
+        db::get_tasks_by_ids(&conn, &[task_id])
+            .map_err(|e| format!("Failed to fetch task: {}", e))?
+            .pop()
+            .is_some_and(|task| task.status != Status::Completed)
+    }; // DB lock released here
+    if !open {
+        return Ok(false);
+    }
+    
+    let data = TaskUpdateData { deadline: Some(start.to_rfc3339()), ..rule_service::no_changes() };
+    match task_service::update_task(TaskUpdate { id: task_id, data }, db, app).await {
+        Ok(_) => Ok(true),
+        Err(e) => {
+            warn!("Failed to move task {} with its calendar event: {}", task_id, e);
+            Ok(false)
+        }
+    }
+}
+
+// Timed events start at their `dateTime`; all-day ones at the default deadline time on their day
+fn event_start(time: &ListedEventTime, deadline_time: NaiveTime) -> Option<DateTime<Utc>> {
+    if let Some(date_time) = &time.date_time {
+        return DateTime::parse_from_rfc3339(date_time).ok().map(|start| start.with_timezone(&Utc));
+    }
+    let day = NaiveDate::parse_from_str(time.date.as_deref()?, "%Y-%m-%d").ok()?;
+    deadline_on(day, deadline_time, &Local)
+}
+
+// The task title for an event the first matching rule takes: the title without the prefix, or all of it
+// when the prefix is all there is
+fn task_title(rules: &[CalendarEventRule], summary: &str) -> Option<String> {
+    let summary = summary.trim();
+    rules.iter().find_map(|rule| {
+        let head = summary.get(..rule.title_prefix.len())?;
+        if head.to_lowercase() != rule.title_prefix.to_lowercase() {
+            return None;
+        }
+        let rest = summary[head.len()..].trim();
+        let title = if rest.is_empty() { summary } else { rest };
+        Some(title.to_string())
+    })
+}
+
+pub fn list_rules(db: &Database) -> Result<Vec<CalendarEventRule>, String> {
+    let conn = db.get_read_connection();
+    db::get_calendar_event_rules(&conn)
+        .map_err(|e| format!("Failed to fetch calendar event rules: {}", e))
+}
+
+pub fn create_rule(db: &Database, payload: CalendarEventRuleData) -> Result<CalendarEventRule, String> {
+    let rule = CalendarEventRule {
+        id: Uuid::new_v7(Timestamp::now(NoContext)),
+        name: parse_name(&payload.name)?,
+        title_prefix: parse_prefix(&payload.title_prefix)?,
+        enabled: true,
+        created_at: Utc::now(),
+    };
+    
+    let conn = db.get_connection();
+    db::insert(&conn, &rule)
+        .map_err(|e| format!("Failed to create calendar event rule: {}", e))?;
+    
+    info!("Created calendar event rule {}", rule.id);
+    Ok(rule)
+}
+
+pub fn update_rule(db: &Database, payload: CalendarEventRuleUpdate) -> Result<CalendarEventRule, String> {
+    let data = payload.data;
+    let update = CalendarEventRuleUpdateParsed {
+        name: data.name.as_deref().map(parse_name).transpose()?,
+        title_prefix: data.title_prefix.as_deref().map(parse_prefix).transpose()?,
+        enabled: data.enabled,
+    };
+    
+    let conn = db.get_connection();
+    db::update_calendar_event_rule(&conn, &payload.id, &update)
+        .map_err(|e| match e {
+            rusqlite::Error::QueryReturnedNoRows => format!("No calendar event rule with ID {}", payload.id),
+            e => format!("Failed to update calendar event rule: {}", e),
+        })
+}
+
+// Tasks the rule already made keep their events
+pub fn delete_rule(db: &Database, payload: CalendarEventRuleId) -> Result<(), String> {
+    let conn = db.get_connection();
+    let deleted = db::delete_calendar_event_rule_by_id(&conn, &payload.id)
+        .map_err(|e| format!("Failed to delete calendar event rule: {}", e))?;
+    
+    if deleted == 0 {
+        return Err(format!("No calendar event rule with ID {}", payload.id));
+    }
+    Ok(())
+}
+
+fn parse_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Rule name cannot be empty".to_string());
+    }
+    if name.chars().count() > MAX_NAME_LEN {
+        return Err(format!("Rule name is longer than {} characters", MAX_NAME_LEN));
+    }
+    Ok(name.to_string())
+}
+
+// Leading spaces never match a trimmed title, and an empty prefix would match every event
+fn parse_prefix(prefix: &str) -> Result<String, String> {
+    let prefix = prefix.trim_start();
+    if prefix.trim().is_empty() {
+        return Err("Title prefix cannot be empty".to_string());
+    }
+    if prefix.chars().count() > MAX_PREFIX_LEN {
+        return Err(format!("Title prefix is longer than {} characters", MAX_PREFIX_LEN));
+    }
+    Ok(prefix.to_string())
+}
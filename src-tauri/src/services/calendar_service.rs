@@ -1,71 +1,193 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use tauri::{AppHandle, Manager};
 use crate::db::{self, Database};
-use crate::structs::calendar::CalendarCredentials;
+use crate::helpers::crypto;
+use crate::services::{calendar_journal_service, connectivity_service, event_service, network_permission_service};
+use crate::services::event_service::CalendarReauthPayload;
+use crate::structs::calendar_event::{CalendarPrivacy, EventOrigin, EventPatch, ListedEvent, PushedEvent};
+use crate::structs::data_export::CalendarLink;
+use crate::structs::calendar_journal::JournalOperation;
+use crate::structs::calendar::{CalendarCredentials, CalendarHealth, DisconnectCalendarData, CalendarHealthStatus, CalendarUsageQuery, CalendarUsageStats};
+use crate::structs::network::NetworkFeature;
+use crate::structs::task_struct::{Task, TaskId};
 use crate::thirdparty::calendar;
+use crate::deep_link;
 use chrono::{DateTime, Utc, Duration};
+use sha2::{Digest, Sha256};
+use tracing::{debug, info, warn, error};
 
-pub async fn start_oauth_flow(db: &Database) -> Result<CalendarCredentials, String> {
+// Edit-form saves arrive per keystroke; wait for them to settle before patching Google
+const UPDATE_QUIET_PERIOD: std::time::Duration = std::time::Duration::from_millis(1500);
+
+// Calls older than this are dropped from the API log
+const API_LOG_RETENTION_DAYS: i64 = 90;
+
+// Stands in for the title on events set to busy
+const BUSY_TITLE: &str = "Busy – MyHandler task";
+
+// Folder in app data for replacement OAuth callback pages
+const OAUTH_PAGES_DIR: &str = "oauth_pages";
+
+// Returned instead of calling Google once the refresh token was refused; the calendar journal keeps
+// the change until the account is connected again
+pub const REAUTH_NEEDED: &str = "REAUTH_NEEDED";
+
+// Latest queued update per event ID, tagged with the call that queued it
+static PENDING_UPDATES: OnceLock<Mutex<HashMap<String, (u64, QueuedEventUpdate)>>> = OnceLock::new();
+static NEXT_UPDATE_ID: AtomicU64 = AtomicU64::new(0);
+// When the refusal the UI was last told about happened, so it's asked once per refusal and run
+static REAUTH_PROMPTED: Mutex<Option<DateTime<Utc>>> = Mutex::new(None);
+
+// Everything needed to patch a task's event once its quiet period is over
+#[derive(Debug, Clone)]
+pub struct QueuedEventUpdate {
+    pub task_id: TaskId,
+    pub title: String,
+    pub description: String,
+    pub deadline: DateTime<Utc>,
+    pub reminder_frequency: String,
+    // Journal entry to mark done once the update is sent
+    pub journal_id: Option<i64>,
+}
+
+pub async fn start_oauth_flow(app: &AppHandle, db: &Database) -> Result<CalendarCredentials, String> {
+    network_permission_service::ensure_allowed(db, NetworkFeature::Calendar)?;
+    connectivity_service::ensure_online()?;
+    
+    // Callback pages in the settings locale; pages in app data/oauth_pages replace the built-in ones
+    let locale = db.settings()
+        .map(|settings| settings.locale)
+        .map_err(|e| format!("Failed to read settings: {}", e))?;
+    let override_dir = app.path().app_data_dir().ok().map(|dir| dir.join(OAUTH_PAGES_DIR));
+    let pages = calendar::OauthPages::new(locale, override_dir);
+    
     // Start OAuth flow and get credentials
-    let credentials = calendar::start_oauth_flow().await?;
+    let credentials = calendar::start_oauth_flow(&pages).await?;
     
     // Save to database
     save_credentials(db, &credentials)?;
     
+    // Before the replay, so it recreates the events that are gone
+    if let Err(e) = relink_task_events(db).await {
+        warn!("Failed to relink calendar events: {}", e);
+    }
+    
+    // Changes held back while the account needed connecting again
+    calendar_journal_service::replay(db).await;
+    
     Ok(credentials)
 }
 
 pub fn save_credentials(db: &Database, creds: &CalendarCredentials) -> Result<(), String> {
-    println!("save_credentials: Starting...");
+    debug!("save_credentials: Starting...");
     let conn = db.get_connection();
-    println!("save_credentials: Got connection, saving...");
+    debug!("save_credentials: Got connection, saving...");
     
     let result = db::save_calendar_credentials(&conn, creds)
         .map_err(|e| format!("Failed to save credentials: {}", e));
     
-    println!("save_credentials: Save completed");
+    debug!("save_credentials: Save completed");
     result
 }
 
 pub fn get_credentials(db: &Database) -> Result<Option<CalendarCredentials>, String> {
-    println!("get_credentials: Getting DB connection...");
-    let conn = db.get_connection();
-    println!("get_credentials: Got connection, querying credentials...");
-    
-    let result = db::get_calendar_credentials(&conn)
+    // Served from memory after the first call; saving or clearing them drops the copy
+    let result = db.calendar_credentials()
         .map_err(|e| format!("Failed to get credentials: {}", e));
     
-    println!("get_credentials: Query completed");
+    debug!("get_credentials: Query completed");
     result
 }
 
-pub fn disconnect_calendar(db: &Database) -> Result<(), String> {
+pub fn disconnect_calendar(db: &Database, payload: DisconnectCalendarData) -> Result<(), String> {
     let conn = db.get_connection();
     
-    db::clear_calendar_credentials(&conn)
+    db::clear_calendar_credentials(&conn, payload.clear_event_links)
         .map_err(|e| format!("Failed to disconnect calendar: {}", e))
 }
 
-// Get valid access token, refreshing if needed
+// Links kept through a disconnect: those whose event is on the calendar just connected stay, and the
+// rest are dropped with a journaled sync, so their tasks get a new event instead of a missing one
+async fn relink_task_events(db: &Database) -> Result<(), String> {
+    let links: Vec<CalendarLink> = {
+        let conn = db.get_read_connection();
+        db::get_calendar_links(&conn)
+            .map_err(|e| format!("Failed to get calendar links: {}", e))?
+    }; // DB lock released here
+    let links: Vec<CalendarLink> = links.into_iter().filter(|link| link.origin == EventOrigin::Task).collect();
+    if links.is_empty() {
+        return Ok(());
+    }
+    
+    let event_ids: Vec<String> = links.iter().map(|link| link.google_event_id.clone()).collect();
+    let result = async {
+        let access_token = get_valid_access_token(db).await?;
+        calendar::events_exist(&access_token, &event_ids).await
+    }.await;
+    record_api_call(db, "get", &result);
+    let found = result?;
+    
+    let mut dropped = 0;
+    {
+        let conn = db.get_connection();
+        let tx = conn.unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        for (link, exists) in links.iter().zip(&found) {
+            match exists {
+                Ok(true) => {}
+                Ok(false) => {
+                    db::delete_calendar_link(&tx, &link.google_event_id)
+                        .map_err(|e| format!("Failed to drop calendar link: {}", e))?;
+                    calendar_journal_service::record(&tx, TaskId::from(link.task_id), JournalOperation::Sync, None)?;
+                    dropped += 1;
+                }
+                // Left linked; an update that finds the event gone drops it then
+                Err(e) => warn!("Couldn't check calendar event {}: {}", link.google_event_id, e),
+            }
+        }
+        tx.commit().map_err(|e| format!("Failed to drop calendar links: {}", e))?;
+    } // DB lock released here
+    
+    info!("Relinked calendar events: {} kept, {} dropped", found.iter().filter(|exists| matches!(exists, Ok(true))).count(), dropped);
+    Ok(())
+}
+
+// Get valid access token, refreshing if needed; every Google call goes through here
 pub async fn get_valid_access_token(db: &Database) -> Result<String, String> {
-    println!("get_valid_access_token: Starting...");
-    println!("get_valid_access_token: Calling get_credentials...");
+    network_permission_service::ensure_allowed(db, NetworkFeature::Calendar)?;
+    connectivity_service::ensure_online()?;
+    
+    debug!("get_valid_access_token: Starting...");
+    debug!("get_valid_access_token: Calling get_credentials...");
     
     let mut creds = get_credentials(db)?
         .ok_or_else(|| "No calendar credentials found".to_string())?;
+    if creds.reauth_needed_at.is_some() {
+        return Err(REAUTH_NEEDED.to_string());
+    }
     
-    println!("get_valid_access_token: Credentials loaded successfully");
+    debug!("get_valid_access_token: Credentials loaded successfully");
     
-    println!("Credentials loaded, checking expiry...");
+    debug!("Credentials loaded, checking expiry...");
     
     // Check if token needs refresh (5 minute buffer)
     let now = Utc::now();
     let buffer = Duration::minutes(5);
     
     if creds.token_expiry - buffer < now {
-        println!("Token expired, refreshing...");
+        info!("Token expired, refreshing...");
         // Token expired or about to expire, refresh it
-        let (new_access_token, expires_in) = 
-            calendar::refresh_access_token(&creds.refresh_token).await?;
-        println!("Token refresh completed");
+        let (new_access_token, expires_in) = match calendar::refresh_access_token(&creds.refresh_token).await {
+            Ok(token) => token,
+            Err(e) if e == calendar::INVALID_GRANT => {
+                mark_reauth_needed(db)?;
+                return Err(REAUTH_NEEDED.to_string());
+            }
+            Err(e) => return Err(e),
+        };
+        info!("Token refresh completed");
         
         // Update credentials
         creds.access_token = new_access_token.clone();
@@ -76,74 +198,544 @@ pub async fn get_valid_access_token(db: &Database) -> Result<String, String> {
         
         Ok(new_access_token)
     } else {
-        println!("Token still valid, using existing one");
+        info!("Token still valid, using existing one");
         Ok(creds.access_token)
     }
 }
 
+// Connection health for the settings page, so a broken connection shows before an edit fails.
+// Refreshes the token to prove the refresh token still works, then checks the new one and its scopes.
+pub async fn check_calendar_connection(db: &Database) -> Result<CalendarHealth, String> {
+    let mut health = CalendarHealth {
+        status: CalendarHealthStatus::Disconnected,
+        email: None,
+        token_expiry: None,
+        refresh_ok: None,
+        granted_scopes: Vec::new(),
+        missing_scopes: Vec::new(),
+        problems: Vec::new(),
+        checked_at: Utc::now(),
+    };
+    let Some(mut creds) = get_credentials(db)? else {
+        return Ok(health);
+    };
+    health.email = Some(creds.email.clone());
+    health.token_expiry = Some(creds.token_expiry);
+    if creds.reauth_needed_at.is_some() {
+        health.refresh_ok = Some(false);
+        health.problems.push("Google no longer accepts this connection; reconnect Google Calendar".to_string());
+        return Ok(health);
+    }
+    
+    if let Err(e) = network_permission_service::ensure_allowed(db, NetworkFeature::Calendar) {
+        health.status = CalendarHealthStatus::Degraded;
+        health.problems.push(e);
+        return Ok(health);
+    }
+    if connectivity_service::ensure_online().is_err() {
+        health.status = CalendarHealthStatus::Offline;
+        return Ok(health);
+    }
+    
+    match calendar::refresh_access_token(&creds.refresh_token).await {
+        Ok((access_token, expires_in)) => {
+            creds.access_token = access_token;
+            creds.token_expiry = Utc::now() + Duration::seconds(expires_in);
+            save_credentials(db, &creds)?;
+            health.token_expiry = Some(creds.token_expiry);
+            health.refresh_ok = Some(true);
+        }
+        Err(e) if e == calendar::INVALID_GRANT => {
+            mark_reauth_needed(db)?;
+            health.refresh_ok = Some(false);
+            health.problems.push("Google no longer accepts this connection; reconnect Google Calendar".to_string());
+            return Ok(health);
+        }
+        Err(e) => {
+            warn!("Calendar health check: {}", e);
+            health.refresh_ok = Some(false);
+            health.problems.push("The connection can't be renewed; reconnect Google Calendar".to_string());
+        }
+    }
+    
+    // A token that expired and couldn't be renewed has nothing left to check
+    if creds.token_expiry > Utc::now() {
+        let result = calendar::get_token_info(&creds.access_token).await;
+        record_api_call(db, "check", &result);
+        match result {
+            Ok(info) => {
+                health.granted_scopes = info.scope.split_whitespace().map(String::from).collect();
+                health.missing_scopes = calendar::required_scopes().into_iter()
+                    .filter(|scope| !health.granted_scopes.iter().any(|granted| granted == scope))
+                    .map(String::from)
+                    .collect();
+                if !health.missing_scopes.is_empty() {
+                    health.problems.push("Calendar access wasn't fully granted; reconnect and allow every permission".to_string());
+                }
+                // Google's own count is what the token actually has left
+                if let Some(secs) = info.expires_in.as_deref().and_then(|secs| secs.parse::<i64>().ok()) {
+                    health.token_expiry = Some(Utc::now() + Duration::seconds(secs));
+                }
+                if let Some(email) = info.email.as_deref() {
+                    if !creds.email.is_empty() && !email.eq_ignore_ascii_case(&creds.email) {
+                        health.problems.push(format!("The token belongs to {}, not {}; reconnect Google Calendar", email, creds.email));
+                    }
+                }
+            }
+            Err(e) => health.problems.push(e),
+        }
+    } else {
+        health.problems.push("The access token has expired".to_string());
+    }
+    
+    health.status = if health.problems.is_empty() {
+        CalendarHealthStatus::Healthy
+    } else {
+        CalendarHealthStatus::Degraded
+    };
+    Ok(health)
+}
+
+// Stop calendar calls until the account is connected again; calendar tasks get local reminders meanwhile
+fn mark_reauth_needed(db: &Database) -> Result<(), String> {
+    let conn = db.get_connection();
+    let first = db::set_calendar_reauth_needed(&conn, Utc::now())
+        .map_err(|e| format!("Failed to record the calendar connection: {}", e))?;
+    if first {
+        warn!("Google refused the refresh token; calendar sync stopped until the account is connected again");
+    }
+    Ok(())
+}
+
+// Calendar reauth job: tell the UI the account has to be connected again, once per refusal and run
+pub fn prompt_reauth(app: &AppHandle) -> Result<(), String> {
+    let Some(db) = app.try_state::<Database>() else {
+        return Ok(());
+    };
+    let Some(creds) = get_credentials(&db)? else {
+        return Ok(());
+    };
+    let Some(since) = creds.reauth_needed_at else {
+        return Ok(());
+    };
+    {
+        let mut prompted = REAUTH_PROMPTED.lock().unwrap_or_else(|e| e.into_inner());
+        if *prompted == Some(since) {
+            return Ok(());
+        }
+        *prompted = Some(since);
+    }
+    
+    event_service::emit_calendar_reauth_needed(app, &CalendarReauthPayload { email: creds.email, since });
+    Ok(())
+}
+
+// Event description: task notes followed by a link back into the app
+pub fn task_event_description(task: &Task) -> String {
+    let link = task_link(TaskId::from(task.id));
+    
+    match task.notes.as_deref() {
+        Some(notes) if !notes.is_empty() => format!("{}\n\n{}", notes, link),
+        _ => link,
+    }
+}
+
+fn task_link(task_id: TaskId) -> String {
+    format!("Open in My Handler: {}", deep_link::task_url(&task_id.to_string()))
+}
+
+// Every event sent for a task passes through here: title and description are cut down to what
+// the task's calendar privacy, or else the one in settings, lets Google see
+fn event_content(db: &Database, task_id: TaskId, title: &str, description: Option<&str>) -> Result<(String, Option<String>), String> {
+    let default_privacy = db.settings()
+        .map_err(|e| format!("Failed to fetch settings: {}", e))?
+        .calendar_privacy;
+    let privacy = {
+        let conn = db.get_read_connection();
+        db::get_task_calendar_privacy(&conn, task_id)
+            .map_err(|e| format!("Failed to get calendar privacy: {}", e))?
+    }; // DB lock released here
+    
+    Ok(match privacy.unwrap_or(default_privacy) {
+        CalendarPrivacy::Full => (title.to_string(), description.map(str::to_string)),
+        CalendarPrivacy::NoNotes => (title.to_string(), Some(task_link(task_id))),
+        CalendarPrivacy::Busy => (BUSY_TITLE.to_string(), None),
+    })
+}
+
 // Create calendar event for a task
 pub async fn create_task_calendar_event(
     db: &Database,
+    task_id: TaskId,
     title: &str,
     notes: Option<&str>,
     deadline: DateTime<Utc>,
     reminder_frequency: &str,
 ) -> Result<String, String> {
-    println!("Getting access token for calendar...");
-    let access_token = get_valid_access_token(db).await?;
-    println!("Access token obtained, creating event...");
-    
-    let result = calendar::create_calendar_event(
-        &access_token,
-        title,
-        notes,
-        deadline,
-        reminder_frequency,
-    ).await;
+    let result = async {
+        let (title, notes) = event_content(db, task_id, title, notes)?;
+        debug!("Getting access token for calendar...");
+        let access_token = get_valid_access_token(db).await?;
+        debug!("Access token obtained, creating event...");
+        
+        let event_id = calendar::create_calendar_event(
+            &access_token,
+            &title,
+            notes.as_deref(),
+            deadline,
+            reminder_frequency,
+        ).await?;
+        Ok::<_, String>((event_id, pushed_event(title, notes.as_deref(), deadline, reminder_frequency)))
+    }.await;
+    record_api_call(db, "create", &result);
     
-    match &result {
-        Ok(event_id) => println!("Successfully created calendar event: {}", event_id),
-        Err(e) => eprintln!("Failed to create calendar event: {}", e),
+    match result {
+        Ok((event_id, pushed)) => {
+            info!("Successfully created calendar event: {}", event_id);
+            save_pushed(db, task_id, &event_id, &pushed);
+            Ok(event_id)
+        }
+        Err(e) => {
+            error!("Failed to create calendar event: {}", e);
+            Err(e)
+        }
     }
+}
+
+// Past event for finished work; not tied to a task's own event
+pub async fn create_work_session_event(
+    db: &Database,
+    task_id: TaskId,
+    summary: &str,
+    description: &str,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<String, String> {
+    let result = async {
+        let (summary, description) = event_content(db, task_id, summary, Some(description))?;
+        let access_token = get_valid_access_token(db).await?;
+        calendar::create_past_event(&access_token, &summary, description.as_deref(), start, end).await
+    }.await;
+    record_api_call(db, "create", &result);
+    
+    result
+}
+
+// Events on the user's calendar in the range, for the calendar event rules
+pub async fn list_calendar_events(db: &Database, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<ListedEvent>, String> {
+    let result = async {
+        let access_token = get_valid_access_token(db).await?;
+        calendar::list_events(&access_token, from, to).await
+    }.await;
+    record_api_call(db, "list", &result);
     
     result
 }
 
-// Update calendar event
+// Update calendar event now; supersedes any queued update for it
 pub async fn update_task_calendar_event(
     db: &Database,
     event_id: &str,
+    task_id: TaskId,
     title: &str,
     notes: Option<&str>,
     deadline: DateTime<Utc>,
     reminder_frequency: &str,
 ) -> Result<(), String> {
-    println!("Updating calendar event: {}", event_id);
-    let access_token = get_valid_access_token(db).await?;
+    cancel_queued_update(event_id);
+    patch_calendar_event(db, event_id, task_id, title, notes, deadline, reminder_frequency).await
+}
+
+// Update many events now, batched into as few requests as Google allows; supersedes queued updates
+// for them. One result per update, in order; journal entries and vanished events are handled here.
+pub async fn update_task_calendar_events(db: &Database, updates: &[(String, QueuedEventUpdate)]) -> Vec<Result<(), String>> {
+    let mut results: Vec<Result<(), String>> = Vec::with_capacity(updates.len());
+    let mut patches = Vec::new();
+    let mut sending = Vec::new();
+    for (event_id, update) in updates {
+        cancel_queued_update(event_id);
+        let (title, notes) = match event_content(db, update.task_id, &update.title, Some(&update.description)) {
+            Ok(content) => content,
+            Err(e) => {
+                results.push(Err(e));
+                continue;
+            }
+        };
+        let pushed = pushed_event(title, notes.as_deref(), update.deadline, &update.reminder_frequency);
+        if last_pushed(db, event_id).as_ref() == Some(&pushed) {
+            debug!("Calendar event {} is up to date, not updating", event_id);
+            results.push(Ok(()));
+            continue;
+        }
+        
+        patches.push(EventPatch {
+            event_id: event_id.clone(),
+            title: pushed.title.clone(),
+            notes,
+            deadline: update.deadline,
+            reminder_frequency: update.reminder_frequency.clone(),
+        });
+        sending.push((results.len(), pushed));
+        results.push(Ok(()));
+    }
+    
+    if !patches.is_empty() {
+        info!("Updating {} calendar events in batches", patches.len());
+        let sent = match get_valid_access_token(db).await {
+            Ok(access_token) => calendar::update_calendar_events(&access_token, &patches).await,
+            Err(e) => Err(e),
+        };
+        let sent = sent.unwrap_or_else(|e| patches.iter().map(|_| Err(e.clone())).collect());
+        
+        for ((index, pushed), result) in sending.into_iter().zip(sent) {
+            record_api_call(db, "update", &result);
+            let (event_id, update) = &updates[index];
+            match &result {
+                Ok(_) => save_pushed(db, update.task_id, event_id, &pushed),
+                Err(e) => warn!("Failed to update calendar event {}: {}", event_id, e),
+            }
+            results[index] = result;
+        }
+    }
+    
+    for ((_, update), result) in updates.iter().zip(&results) {
+        if let Some(journal_id) = update.journal_id {
+            calendar_journal_service::finish(db, journal_id, update.task_id, JournalOperation::Sync, result);
+        }
+        if matches!(result, Err(e) if e == "EVENT_NOT_FOUND") {
+            let conn = db.get_connection();
+            let _ = db::clear_task_google_event_id(&conn, update.task_id);
+        }
+    }
+    results
+}
+
+// Queue an update; successive calls for the same event within the quiet period become one PATCH
+pub fn queue_task_calendar_update(app: &AppHandle, event_id: &str, update: QueuedEventUpdate) {
+    let update_id = NEXT_UPDATE_ID.fetch_add(1, Ordering::SeqCst);
+    if let Ok(mut pending) = pending_updates().lock() {
+        pending.insert(event_id.to_string(), (update_id, update));
+    }
     
-    let result = calendar::update_calendar_event(
-        &access_token,
+    let app = app.clone();
+    let event_id = event_id.to_string();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(UPDATE_QUIET_PERIOD).await;
+        
+        // Only the newest queued call sends; older ones were coalesced into it
+        let update = match pending_updates().lock() {
+            Ok(mut pending) => match pending.get(&event_id) {
+                Some((queued_id, _)) if *queued_id == update_id => pending.remove(&event_id).map(|(_, update)| update),
+                _ => None,
+            },
+            Err(_) => None,
+        };
+        
+        if let Some(update) = update {
+            send_queued_update(&app, &event_id, update).await;
+        }
+    });
+}
+
+// Send queued updates without waiting out the quiet period (shutdown)
+pub async fn flush_queued_updates(app: &AppHandle) {
+    let queued: Vec<(String, QueuedEventUpdate)> = match pending_updates().lock() {
+        Ok(mut pending) => pending.drain().map(|(event_id, (_, update))| (event_id, update)).collect(),
+        Err(_) => return,
+    };
+    
+    for (event_id, update) in queued {
+        send_queued_update(app, &event_id, update).await;
+    }
+}
+
+fn pending_updates() -> &'static Mutex<HashMap<String, (u64, QueuedEventUpdate)>> {
+    PENDING_UPDATES.get_or_init(Default::default)
+}
+
+fn cancel_queued_update(event_id: &str) {
+    if let Ok(mut pending) = pending_updates().lock() {
+        if pending.remove(event_id).is_some() {
+            debug!("Dropped queued update for calendar event: {}", event_id);
+        }
+    }
+}
+
+async fn send_queued_update(app: &AppHandle, event_id: &str, update: QueuedEventUpdate) {
+    let Some(db) = app.try_state::<Database>() else {
+        return;
+    };
+    
+    let result = patch_calendar_event(
+        &db,
         event_id,
-        title,
-        notes,
-        deadline,
-        reminder_frequency,
+        update.task_id,
+        &update.title,
+        Some(&update.description),
+        update.deadline,
+        &update.reminder_frequency,
     ).await;
+    if let Some(journal_id) = update.journal_id {
+        calendar_journal_service::finish(&db, journal_id, update.task_id, JournalOperation::Sync, &result);
+    }
+    
+    match result {
+        Ok(_) => {}
+        Err(e) if e == "EVENT_NOT_FOUND" => {
+            // Event was deleted externally, clear it from database to stay in sync
+            info!("Calendar event was deleted externally, clearing from database");
+            let conn = db.get_connection();
+            let _ = db::clear_task_google_event_id(&conn, update.task_id);
+        }
+        Err(e) => warn!("Failed to update calendar event: {}", e),
+    }
+}
+
+async fn patch_calendar_event(
+    db: &Database,
+    event_id: &str,
+    task_id: TaskId,
+    title: &str,
+    notes: Option<&str>,
+    deadline: DateTime<Utc>,
+    reminder_frequency: &str,
+) -> Result<(), String> {
+    let (title, notes) = event_content(db, task_id, title, notes)?;
+    let pushed = pushed_event(title, notes.as_deref(), deadline, reminder_frequency);
+    // Status toggles and edits to fields the event doesn't show would send the same event again
+    if last_pushed(db, event_id).as_ref() == Some(&pushed) {
+        debug!("Calendar event {} is up to date, not updating", event_id);
+        return Ok(());
+    }
+    
+    info!("Updating calendar event: {}", event_id);
+    let result = async {
+        let access_token = get_valid_access_token(db).await?;
+        
+        calendar::update_calendar_event(
+            &access_token,
+            event_id,
+            &pushed.title,
+            notes.as_deref(),
+            deadline,
+            reminder_frequency,
+        ).await
+    }.await;
+    record_api_call(db, "update", &result);
     
     match &result {
-        Ok(_) => println!("Successfully updated calendar event"),
-        Err(e) => eprintln!("Failed to update calendar event: {}", e),
+        Ok(_) => {
+            info!("Successfully updated calendar event");
+            save_pushed(db, task_id, event_id, &pushed);
+        }
+        Err(e) => error!("Failed to update calendar event: {}", e),
     }
     
     result
 }
 
+fn pushed_event(title: String, notes: Option<&str>, deadline: DateTime<Utc>, reminder_frequency: &str) -> PushedEvent {
+    let mut hasher = Sha256::new();
+    hasher.update(notes.unwrap_or_default().as_bytes());
+    hasher.update([0]);
+    hasher.update(reminder_frequency.as_bytes());
+    PushedEvent {
+        title,
+        deadline,
+        content_hash: crypto::to_hex(&hasher.finalize()),
+    }
+}
+
+// The cache only saves calls; when it can't be read the update is sent
+fn last_pushed(db: &Database, event_id: &str) -> Option<PushedEvent> {
+    let conn = db.get_read_connection();
+    db::get_pushed_calendar_event(&conn, event_id)
+        .unwrap_or_else(|e| {
+            warn!("Failed to read cached calendar event {}: {}", event_id, e);
+            None
+        })
+}
+
+fn save_pushed(db: &Database, task_id: TaskId, event_id: &str, pushed: &PushedEvent) {
+    let conn = db.get_connection();
+    if let Err(e) = db::save_pushed_calendar_event(&conn, task_id, event_id, pushed, Utc::now()) {
+        warn!("Failed to cache calendar event {}: {}", event_id, e);
+    }
+}
+
 // Delete calendar event
 pub async fn delete_task_calendar_event(
     db: &Database,
     event_id: &str,
 ) -> Result<(), String> {
-    let access_token = get_valid_access_token(db).await?;
+    cancel_queued_update(event_id);
+    let result = async {
+        let access_token = get_valid_access_token(db).await?;
+        calendar::delete_calendar_event(&access_token, event_id).await
+    }.await;
+    record_api_call(db, "delete", &result);
+    
+    result
+}
+
+// Linked tasks, recent API errors by kind and last successful call per account
+pub fn get_calendar_usage_stats(db: &Database, payload: CalendarUsageQuery) -> Result<CalendarUsageStats, String> {
+    if !(1..=API_LOG_RETENTION_DAYS).contains(&(payload.days as i64)) {
+        return Err(format!("Days must be between 1 and {}", API_LOG_RETENTION_DAYS));
+    }
+    let since = Utc::now() - Duration::days(payload.days as i64);
     
-    calendar::delete_calendar_event(&access_token, event_id).await
+    let conn = db.get_connection();
+    let counts = db::get_calendar_link_counts(&conn)
+        .map_err(|e| format!("Failed to count calendar links: {}", e))?;
+    let errors_by_kind = db::get_calendar_errors_by_kind(&conn, since)
+        .map_err(|e| format!("Failed to read calendar API log: {}", e))?;
+    let accounts = db::get_calendar_account_usage(&conn, since)
+        .map_err(|e| format!("Failed to read calendar API log: {}", e))?;
+    
+    Ok(CalendarUsageStats {
+        linked_tasks: counts.linked_tasks,
+        calendar_tasks: counts.calendar_tasks,
+        pending_operations: counts.pending_operations,
+        errors_by_kind,
+        accounts,
+    })
+}
+
+// Log the outcome of a Google call under the connected account; blocked calls never left the app
+fn record_api_call<T>(db: &Database, operation: &str, result: &Result<T, String>) {
+    let error = result.as_ref().err();
+    if error.is_some_and(|e| e == network_permission_service::CONSENT_REQUIRED || e == network_permission_service::PERMISSION_DENIED) {
+        return;
+    }
+    
+    let account = get_credentials(db).ok().flatten()
+        .map(|creds| creds.email)
+        .unwrap_or_default();
+    
+    let conn = db.get_connection();
+    let outcome = db::record_calendar_api_call(&conn, &account, operation, error.map(|e| error_kind(e)), error.map(String::as_str))
+        .and_then(|_| db::prune_calendar_api_log(&conn, Utc::now() - Duration::days(API_LOG_RETENTION_DAYS)));
+    if let Err(e) = outcome {
+        warn!("Failed to log calendar API call: {}", e);
+    }
+}
+
+// Bucket an error message from the Google calendar client by its cause
+fn error_kind(message: &str) -> &'static str {
+    if message == "EVENT_NOT_FOUND" {
+        return "not-found";
+    }
+    // HTTP failures read "Failed to ... event: 401 Unauthorized - <body>"
+    let status = message.split_whitespace()
+        .find_map(|word| word.parse::<u16>().ok().filter(|code| (400..600).contains(code)));
+    match status {
+        Some(401 | 403) => "auth",
+        Some(404 | 410) => "not-found",
+        Some(429) => "rate-limit",
+        Some(code) if code >= 500 => "server",
+        Some(_) => "request",
+        None if message.starts_with("No calendar credentials") => "auth",
+        None => "network",
+    }
 }
@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+use chrono::{Duration, Utc};
+use serde_json::{Map, Value};
+use crate::db::{self, Database};
+use crate::structs::changelog::{ChangeOp, ChangelogEntry, RowChange};
+use crate::structs::task_struct::TaskId;
+use tracing::info;
+
+// Older entries are dropped; undo and history views don't reach back further
+const RETENTION_DAYS: i64 = 90;
+// Entries older than this are folded into one per run of changes to a row
+const COMPACT_AFTER_DAYS: i64 = 7;
+
+type Values = Map<String, Value>;
+
+// Result of folding one change into the one before it
+enum Folded {
+    One(ChangelogEntry),
+    // The changes cancel out, e.g. a row inserted and deleted again
+    Nothing,
+    // Can't be combined, e.g. a delete followed by an insert of a different row
+    Both(ChangelogEntry, ChangelogEntry),
+}
+
+pub fn get_task_history(db: &Database, task_id: TaskId) -> Result<Vec<RowChange>, String> {
+    let conn = db.get_read_connection();
+    let entries = db::get_row_changelog(&conn, "tasks", &task_id.uuid().simple().to_string())
+        .map_err(|e| format!("Failed to fetch task history: {}", e))?;
+    
+    entries.into_iter()
+        .map(|entry| Ok(RowChange {
+            seq: entry.seq,
+            op: entry.op,
+            old_values: parse_values(entry.old_values.as_deref())?,
+            new_values: parse_values(entry.new_values.as_deref())?,
+            clock: entry.clock,
+            device_id: entry.device_id,
+            changed_at: entry.changed_at,
+        }))
+        .collect()
+}
+
+// Drop entries past retention and fold older runs of changes to the same row into one
+pub fn compact(db: &Database) -> Result<(), String> {
+    let now = Utc::now();
+    let conn = db.get_connection();
+    let tx = conn.unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    
+    let pruned = db::prune_changelog(&tx, now - Duration::days(RETENTION_DAYS))
+        .map_err(|e| format!("Failed to prune changelog: {}", e))?;
+    let entries = db::get_changelog_before(&tx, now - Duration::days(COMPACT_AFTER_DAYS))
+        .map_err(|e| format!("Failed to read changelog: {}", e))?;
+    
+    // Entries arrive in seq order, so each row's run stays in order
+    let mut rows: HashMap<(String, String), Vec<ChangelogEntry>> = HashMap::new();
+    for entry in entries {
+        rows.entry((entry.table_name.clone(), entry.row_id.clone())).or_default().push(entry);
+    }
+    
+    let mut folded_away = 0;
+    for run in rows.into_values().filter(|run| run.len() > 1) {
+        let seqs: Vec<i64> = run.iter().map(|entry| entry.seq).collect();
+        let folded = fold_run(run)?;
+        folded_away += seqs.len() - folded.len();
+        
+        for seq in seqs {
+            db::delete_changelog_entry(&tx, seq)
+                .map_err(|e| format!("Failed to compact changelog: {}", e))?;
+        }
+        for entry in &folded {
+            db::insert_changelog_entry(&tx, entry)
+                .map_err(|e| format!("Failed to compact changelog: {}", e))?;
+        }
+    }
+    
+    tx.commit().map_err(|e| format!("Failed to compact changelog: {}", e))?;
+    if pruned > 0 || folded_away > 0 {
+        info!("Changelog: pruned {} entries, folded away {}", pruned, folded_away);
+    }
+    Ok(())
+}
+
+fn fold_run(run: Vec<ChangelogEntry>) -> Result<Vec<ChangelogEntry>, String> {
+    let mut folded: Vec<ChangelogEntry> = Vec::new();
+    for entry in run {
+        match folded.pop() {
+            None => folded.push(entry),
+            Some(previous) => match fold(previous, entry)? {
+                Folded::One(entry) => folded.push(entry),
+                Folded::Nothing => {}
+                Folded::Both(previous, entry) => {
+                    folded.push(previous);
+                    folded.push(entry);
+                }
+            },
+        }
+    }
+    Ok(folded)
+}
+
+// The combined entry keeps the later one's seq, clock, device and time
+fn fold(first: ChangelogEntry, next: ChangelogEntry) -> Result<Folded, String> {
+    let first_old = parse_values(first.old_values.as_deref())?;
+    let first_new = parse_values(first.new_values.as_deref())?;
+    let next_old = parse_values(next.old_values.as_deref())?;
+    let next_new = parse_values(next.new_values.as_deref())?;
+    
+    let (op, old, new) = match (first.op, next.op) {
+        (ChangeOp::Insert, ChangeOp::Update) => (ChangeOp::Insert, None, Some(overlay(first_new, next_new))),
+        (ChangeOp::Insert, ChangeOp::Delete) => return Ok(Folded::Nothing),
+        (ChangeOp::Update, ChangeOp::Update) => {
+            // Earliest old value and latest new value of each column
+            let old = overlay(next_old, first_old);
+            let new = overlay(first_new, next_new);
+            let (old, new) = drop_unchanged(old, new);
+            if new.is_empty() {
+                return Ok(Folded::Nothing);
+            }
+            (ChangeOp::Update, Some(old), Some(new))
+        }
+        (ChangeOp::Update, ChangeOp::Delete) => (ChangeOp::Delete, Some(overlay(next_old, first_old)), None),
+        // Deleted, then brought back (e.g. by sync): an update, if anything differs
+        (ChangeOp::Delete, ChangeOp::Insert) => {
+            let (old, new) = drop_unchanged(first_old.unwrap_or_default(), next_new.unwrap_or_default());
+            if new.is_empty() {
+                return Ok(Folded::Nothing);
+            }
+            (ChangeOp::Update, Some(old), Some(new))
+        }
+        _ => return Ok(Folded::Both(first, next)),
+    };
+    
+    Ok(Folded::One(ChangelogEntry {
+        op,
+        old_values: old.map(|values| Value::Object(values).to_string()),
+        new_values: new.map(|values| Value::Object(values).to_string()),
+        ..next
+    }))
+}
+
+// `base` with every column in `top` replaced or added
+fn overlay(base: Option<Values>, top: Option<Values>) -> Values {
+    let mut values = base.unwrap_or_default();
+    values.extend(top.unwrap_or_default());
+    values
+}
+
+// Columns that ended up back at their old value aren't a change
+fn drop_unchanged(mut old: Values, mut new: Values) -> (Values, Values) {
+    let unchanged: Vec<String> = new.iter()
+        .filter(|(column, value)| old.get(*column) == Some(value))
+        .map(|(column, _)| column.clone())
+        .collect();
+    for column in unchanged {
+        old.remove(&column);
+        new.remove(&column);
+    }
+    (old, new)
+}
+
+fn parse_values(json: Option<&str>) -> Result<Option<Values>, String> {
+    json.map(|json| serde_json::from_str(json)
+            .map_err(|e| format!("Invalid changelog values: {}", e)))
+        .transpose()
+}
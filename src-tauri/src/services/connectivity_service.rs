@@ -0,0 +1,85 @@
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use tauri::{AppHandle, Manager};
+use tokio::net::TcpStream;
+use crate::db::Database;
+use crate::services::{calendar_journal_service, event_service};
+use crate::structs::network::ConnectivityStatus;
+use tracing::{info, warn};
+
+// Returned instead of calling Google while offline; the calendar journal keeps the change until we're back
+pub const OFFLINE: &str = "OFFLINE";
+
+// A TCP connect is enough to tell whether there's a way out; the second host covers DNS being down
+const PROBE_HOSTS: [&str; 2] = ["www.googleapis.com:443", "1.1.1.1:443"];
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Assumed online until a check says otherwise, so the first calls after startup aren't refused
+static STATE: OnceLock<Mutex<(bool, Option<DateTime<Utc>>)>> = OnceLock::new();
+
+pub fn is_online() -> bool {
+    state().lock().map(|state| state.0).unwrap_or(true)
+}
+
+// Call before a Google call: offline, it fails at once rather than waiting out the request timeout
+pub fn ensure_online() -> Result<(), String> {
+    if is_online() {
+        Ok(())
+    } else {
+        Err(OFFLINE.to_string())
+    }
+}
+
+pub fn get_connectivity(db: &Database) -> Result<ConnectivityStatus, String> {
+    let (online, checked_at) = state().lock()
+        .map(|state| *state)
+        .map_err(|_| "Connectivity state is unavailable".to_string())?;
+    Ok(ConnectivityStatus {
+        online,
+        checked_at,
+        pending_calendar_changes: calendar_journal_service::pending_count(db)?,
+    })
+}
+
+// Connectivity job: probe, and on a change tell the UI; back online, queued calendar changes are sent
+pub async fn check(app: &AppHandle) -> Result<(), String> {
+    let online = probe().await;
+    let changed = {
+        let mut state = state().lock()
+            .map_err(|_| "Connectivity state is unavailable".to_string())?;
+        let changed = state.0 != online;
+        *state = (online, Some(Utc::now()));
+        changed
+    };
+    if !changed {
+        return Ok(());
+    }
+    
+    if online {
+        info!("Back online");
+    } else {
+        warn!("Offline: calendar changes will be queued");
+    }
+    let Some(db) = app.try_state::<Database>() else {
+        return Ok(());
+    };
+    event_service::emit_connectivity_changed(app, &get_connectivity(&db)?);
+    if online {
+        calendar_journal_service::replay(&db).await;
+    }
+    Ok(())
+}
+
+async fn probe() -> bool {
+    for host in PROBE_HOSTS {
+        if let Ok(Ok(_)) = tokio::time::timeout(PROBE_TIMEOUT, TcpStream::connect(host)).await {
+            return true;
+        }
+    }
+    false
+}
+
+fn state() -> &'static Mutex<(bool, Option<DateTime<Utc>>)> {
+    STATE.get_or_init(|| Mutex::new((true, None)))
+}
@@ -0,0 +1,281 @@
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use chrono::Utc;
+use rusqlite::Connection;
+use tauri::AppHandle;
+use uuid::Uuid;
+use crate::db::{self, Database};
+use crate::services::import_service::ImportProgress;
+use crate::structs::data_export::{ArchiveManifest, CalendarLink, DataExport, ImportMode, ImportSummary};
+use crate::structs::settings::SettingsUpdateParsed;
+use crate::structs::task_struct::TaskId;
+use crate::structs::theme::ThemeUpdateParsed;
+use tracing::info;
+use zip::write::SimpleFileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+// Bump when the document layout changes; older files must stay importable
+pub const EXPORT_FORMAT_VERSION: u32 = 1;
+// Bump when archive entries change
+pub const ARCHIVE_FORMAT_VERSION: u32 = 1;
+const ARCHIVE_MANIFEST: &str = "manifest.json";
+const ARCHIVE_DATA: &str = "data.json";
+
+// Write every task, the settings, theme and calendar links to `path` as JSON
+pub fn export_data(db: &Database, app: &AppHandle, path: &str) -> Result<(), String> {
+    let export = {
+        let conn = db.get_connection();
+        build_export(&conn, app)?
+    }; // DB lock released here
+    
+    let json = serde_json::to_string_pretty(&export)
+        .map_err(|e| format!("Failed to serialize export: {}", e))?;
+    fs::write(path, json)
+        .map_err(|e| format!("Failed to write export file: {}", e))?;
+    
+    info!("Exported {} tasks to {}", export.tasks.len(), path);
+    Ok(())
+}
+
+// Load a file written by export_data; all or nothing
+pub fn import_data(db: &Database, app: &AppHandle, path: &str, mode: ImportMode) -> Result<ImportSummary, String> {
+    let json = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read import file: {}", e))?;
+    let export: DataExport = serde_json::from_str(&json)
+        .map_err(|e| format!("Invalid export file: {}", e))?;
+    
+    let summary = apply_import(db, app, &export, mode)?;
+    info!(
+        "Imported {} ({:?}): {} new, {} updated, {} skipped",
+        path, mode, summary.imported, summary.updated, summary.skipped
+    );
+    Ok(summary)
+}
+
+// Zip holding a manifest and the export_data document, for moving everything to a new machine
+pub fn export_archive(db: &Database, app: &AppHandle, path: &str) -> Result<(), String> {
+    let export = {
+        let conn = db.get_connection();
+        build_export(&conn, app)?
+    }; // DB lock released here
+    
+    let manifest = ArchiveManifest {
+        format_version: ARCHIVE_FORMAT_VERSION,
+        app_version: export.app_version.clone(),
+        created_at: Utc::now(),
+        entries: vec![ARCHIVE_DATA.to_string()],
+    };
+    let manifest_json = serde_json::to_string_pretty(&manifest)
+        .map_err(|e| format!("Failed to serialize archive manifest: {}", e))?;
+    let data_json = serde_json::to_string_pretty(&export)
+        .map_err(|e| format!("Failed to serialize export: {}", e))?;
+    
+    let file = File::create(path)
+        .map_err(|e| format!("Failed to create archive: {}", e))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default();
+    
+    for (name, content) in [(ARCHIVE_MANIFEST, manifest_json), (ARCHIVE_DATA, data_json)] {
+        zip.start_file(name, options)
+            .map_err(|e| format!("Failed to add {} to archive: {}", name, e))?;
+        zip.write_all(content.as_bytes())
+            .map_err(|e| format!("Failed to write {} to archive: {}", name, e))?;
+    }
+    
+    zip.finish()
+        .map_err(|e| format!("Failed to finish archive: {}", e))?;
+    
+    info!("Exported archive of {} tasks to {}", export.tasks.len(), path);
+    Ok(())
+}
+
+// Replace everything with an archive's contents; all or nothing
+pub fn import_archive(db: &Database, app: &AppHandle, path: &str) -> Result<ImportSummary, String> {
+    let file = File::open(path)
+        .map_err(|e| format!("Failed to open archive: {}", e))?;
+    let mut zip = ZipArchive::new(file)
+        .map_err(|e| format!("Invalid archive: {}", e))?;
+    
+    let manifest: ArchiveManifest = serde_json::from_str(&read_entry(&mut zip, ARCHIVE_MANIFEST)?)
+        .map_err(|e| format!("Invalid archive manifest: {}", e))?;
+    if manifest.format_version > ARCHIVE_FORMAT_VERSION {
+        return Err(format!(
+            "Archive format {} is newer than this app supports ({}); update the app first",
+            manifest.format_version, ARCHIVE_FORMAT_VERSION
+        ));
+    }
+    if !manifest.entries.iter().any(|entry| entry == ARCHIVE_DATA) {
+        return Err("Archive has no data entry".to_string());
+    }
+    
+    let export: DataExport = serde_json::from_str(&read_entry(&mut zip, ARCHIVE_DATA)?)
+        .map_err(|e| format!("Invalid archive data: {}", e))?;
+    
+    let summary = apply_import(db, app, &export, ImportMode::Replace)?;
+    info!("Imported archive {} from app {}: {} tasks", path, manifest.app_version, summary.imported);
+    Ok(summary)
+}
+
+fn read_entry(zip: &mut ZipArchive<File>, name: &str) -> Result<String, String> {
+    let mut entry = zip.by_name(name)
+        .map_err(|e| format!("Archive is missing {}: {}", name, e))?;
+    let mut content = String::new();
+    entry.read_to_string(&mut content)
+        .map_err(|e| format!("Failed to read {} from archive: {}", name, e))?;
+    Ok(content)
+}
+
+fn apply_import(db: &Database, app: &AppHandle, export: &DataExport, mode: ImportMode) -> Result<ImportSummary, String> {
+    if export.format_version > EXPORT_FORMAT_VERSION {
+        return Err(format!(
+            "Export format {} is newer than this app supports ({}); update the app first",
+            export.format_version, EXPORT_FORMAT_VERSION
+        ));
+    }
+    
+    let mut progress = ImportProgress::new(app, export.tasks.len());
+    let conn = db.get_connection();
+    let tx = conn.unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    
+    // An error (including cancellation) drops the transaction, rolling everything back
+    let summary = match mode {
+        ImportMode::Merge => merge_tasks(&tx, export, &mut progress)?,
+        ImportMode::Replace => replace_all(&tx, export, &mut progress)?,
+    };
+    
+    tx.commit().map_err(|e| format!("Failed to import data: {}", e))?;
+    Ok(summary)
+}
+
+pub fn build_export(conn: &Connection, app: &AppHandle) -> Result<DataExport, String> {
+    let tasks = db::get_all_tasks(conn)
+        .map_err(|e| format!("Failed to fetch tasks: {}", e))?;
+    let settings = db::get_settings(conn)
+        .map_err(|e| format!("Failed to fetch settings: {}", e))?;
+    let theme = db::get_theme(conn)
+        .map_err(|e| format!("Failed to fetch theme: {}", e))?;
+    let calendar_links = db::get_calendar_links(conn)
+        .map_err(|e| format!("Failed to fetch calendar links: {}", e))?;
+    
+    Ok(DataExport {
+        format_version: EXPORT_FORMAT_VERSION,
+        app_version: app.package_info().version.to_string(),
+        exported_at: Utc::now(),
+        tasks,
+        settings,
+        theme,
+        calendar_links,
+    })
+}
+
+fn merge_tasks(conn: &Connection, export: &DataExport, progress: &mut ImportProgress) -> Result<ImportSummary, String> {
+    let links = calendar_links_by_task(export);
+    let mut summary = ImportSummary::default();
+    
+    for task in &export.tasks {
+        progress.advance()?;
+        let task_id = TaskId::from(task.id);
+        
+        // Same ID means the same task copied between machines; the later edit wins
+        match db::get_task_by_id(conn, task_id) {
+            Ok(existing) if existing.updated_at >= task.updated_at => {
+                summary.skipped += 1;
+                continue;
+            }
+            Ok(_) => {
+                db::delete_task_by_id(conn, task_id)
+                    .map_err(|e| format!("Failed to replace task {}: {}", task_id, e))?;
+                db::clear_task_google_event_id(conn, task_id)
+                    .map_err(|e| format!("Failed to replace task {}: {}", task_id, e))?;
+                summary.updated += 1;
+            }
+            Err(rusqlite::Error::QueryReturnedNoRows) => summary.imported += 1,
+            Err(e) => return Err(format!("Failed to look up task {}: {}", task_id, e)),
+        }
+        
+        db::insert(conn, task)
+            .map_err(|e| format!("Failed to import task {}: {}", task_id, e))?;
+        if let Some(link) = links.get(&task.id) {
+            db::insert_calendar_link(conn, task_id, &link.google_event_id, link.origin)
+                .map_err(|e| format!("Failed to import calendar link for {}: {}", task_id, e))?;
+        }
+    }
+    
+    Ok(summary)
+}
+
+fn replace_all(conn: &Connection, export: &DataExport, progress: &mut ImportProgress) -> Result<ImportSummary, String> {
+    db::clear_all_tasks(conn)
+        .map_err(|e| format!("Failed to clear tasks: {}", e))?;
+    
+    let links = calendar_links_by_task(export);
+    for task in &export.tasks {
+        progress.advance()?;
+        let task_id = TaskId::from(task.id);
+        db::insert(conn, task)
+            .map_err(|e| format!("Failed to import task {}: {}", task_id, e))?;
+        if let Some(link) = links.get(&task.id) {
+            db::insert_calendar_link(conn, task_id, &link.google_event_id, link.origin)
+                .map_err(|e| format!("Failed to import calendar link for {}: {}", task_id, e))?;
+        }
+    }
+    
+    // HTTP API, Slack, vault, sync, LAN sync, usage metrics, time zone, webhook and calendar settings belong to this machine and stay as they are
+    let settings = &export.settings;
+    db::update_settings(conn, &SettingsUpdateParsed {
+        dark_mode: Some(settings.dark_mode),
+        notifications_enabled: Some(settings.notifications_enabled),
+        default_reminder_frequency: Some(settings.default_reminder_frequency.clone()),
+        quick_add_shortcut: Some(settings.quick_add_shortcut.clone()),
+        idle_pause_minutes: Some(settings.idle_pause_minutes),
+        show_task_badge: Some(settings.show_task_badge),
+        http_api_enabled: None,
+        http_api_port: None,
+        vault_path: None,
+        vault_layout: None,
+        slack_enabled: None,
+        work_day_minutes: Some(settings.work_day_minutes),
+        usage_metrics_enabled: None,
+        daily_summary_time: Some(settings.daily_summary_time.clone()),
+        daily_summary_notification: Some(settings.daily_summary_notification),
+        sync_folder: None,
+        sync_backend: None,
+        sync_webdav_url: None,
+        sync_webdav_username: None,
+        sync_device_name: None,
+        sync_identity: None,
+        lan_sync_enabled: None,
+        utc_offset_minutes: None,
+        completion_effects: Some(settings.completion_effects.clone()),
+        completion_webhook_url: None,
+        locale: Some(settings.locale),
+        skip_weekends: Some(settings.skip_weekends),
+        skip_holidays: Some(settings.skip_holidays),
+        holiday_region: Some(settings.holiday_region),
+        calendar_work_sessions: None,
+        estimate_reached_percent: Some(settings.estimate_reached_percent),
+        estimate_reached_notification: Some(settings.estimate_reached_notification),
+        calendar_privacy: Some(settings.calendar_privacy),
+        default_deadline_time: Some(settings.default_deadline_time.clone()),
+    }).map_err(|e| format!("Failed to import settings: {}", e))?;
+    
+    let theme = &export.theme;
+    db::update_theme(conn, &ThemeUpdateParsed {
+        accent_color: Some(theme.accent_color.clone()),
+        font_scale: Some(theme.font_scale),
+        density: Some(theme.density.clone()),
+    }).map_err(|e| format!("Failed to import theme: {}", e))?;
+    
+    Ok(ImportSummary {
+        imported: export.tasks.len(),
+        ..Default::default()
+    })
+}
+
+fn calendar_links_by_task(export: &DataExport) -> HashMap<Uuid, &CalendarLink> {
+    export.calendar_links.iter()
+        .map(|link| (link.task_id, link))
+        .collect()
+}
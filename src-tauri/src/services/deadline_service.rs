@@ -0,0 +1,80 @@
+use chrono::{Duration, Utc};
+use crate::db::{self, Database};
+use crate::services::{holiday_service, stats_service};
+use crate::structs::dto::TaskRef;
+use crate::structs::stats::DeadlineSuggestion;
+use crate::structs::task_struct::Status;
+
+// Tasks created this far back set the usual pace
+const HISTORY_DAYS: i64 = 180;
+// A postponement moves the deadline by at least a day
+const MIN_SLIP_DAYS: f64 = 1.0;
+
+// A realistic new deadline for a task being postponed: no sooner than the work it likely still needs at the
+// usual pace, and pushed back about as far as earlier moves fell short. Whole days keep the deadline's time of day.
+pub fn suggest_deadline(db: &Database, payload: TaskRef) -> Result<DeadlineSuggestion, String> {
+    let now = Utc::now();
+    let (task, postponement, totals, cycle_times) = {
+        let conn = db.get_read_connection();
+        let task = db::get_task_by_id(&conn, payload.id)
+            .map_err(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => format!("Task {} not found", payload.id),
+                e => format!("Failed to get task: {}", e),
+            })?;
+        let postponement = db::get_task_postponement(&conn, payload.id)
+            .map_err(|e| format!("Failed to read postponements: {}", e))?;
+        let totals = db::get_postponement_totals(&conn)
+            .map_err(|e| format!("Failed to compute postponement stats: {}", e))?;
+        let cycle_times = db::get_cycle_times(&conn, now - Duration::days(HISTORY_DAYS), now)
+            .map_err(|e| format!("Failed to compute cycle times: {}", e))?;
+        (task, postponement, totals, cycle_times)
+    }; // DB lock released here
+    
+    if task.status == Status::Completed {
+        return Err("Task is already completed".to_string());
+    }
+    let deadline = task.deadline.ok_or_else(|| "Task has no deadline to postpone".to_string())?;
+    
+    // Clock changes can put a start before creation; those would skew the medians
+    let waits = stats_service::duration_stats(cycle_times.iter().filter_map(|row| row.wait_minutes).filter(|m| *m >= 0.0).collect());
+    let work = stats_service::duration_stats(cycle_times.iter().filter_map(|row| row.work_minutes).filter(|m| *m >= 0.0).collect());
+    
+    // Time already waited or worked on the task counts against the medians
+    let at_usual_pace = work.median_minutes.map(|work_minutes| {
+        let remaining = match task.started_at {
+            Some(started_at) => work_minutes - (now - started_at).num_minutes() as f64,
+            None => {
+                let waited = (now - task.created_at).num_minutes() as f64;
+                (waits.median_minutes.unwrap_or(0.0) - waited).max(0.0) + work_minutes
+            }
+        };
+        remaining.max(0.0) as i64
+    });
+    let remaining_minutes = [at_usual_pace, task.estimate_minutes.map(i64::from)].into_iter().flatten().max();
+    
+    // The task's own moves say most about it; without any, how far moves usually go
+    let slip_days = if postponement.deadline_moves > 0 {
+        postponement.postponed_days / postponement.deadline_moves as f64
+    } else if totals.moves > 0 {
+        totals.postponed_days / totals.moves as f64
+    } else {
+        0.0
+    }.max(MIN_SLIP_DAYS);
+    
+    let mut earliest = (deadline + Duration::minutes((slip_days * 1440.0).round() as i64)).max(now);
+    if let Some(minutes) = remaining_minutes {
+        earliest = earliest.max(now + Duration::minutes(minutes));
+    }
+    let days = ((earliest - deadline).num_minutes() as f64 / 1440.0).ceil() as i64;
+    let suggested_deadline = holiday_service::shift_to_working_day(db, deadline + Duration::days(days.max(1)))?;
+    
+    Ok(DeadlineSuggestion {
+        task_id: payload.id,
+        current_deadline: deadline,
+        suggested_deadline,
+        remaining_minutes,
+        slip_days,
+        deadline_moves: postponement.deadline_moves,
+        sample_size: work.count,
+    })
+}
@@ -0,0 +1,107 @@
+use std::collections::HashSet;
+use chrono::Utc;
+use crate::db::{self, Database};
+use crate::helpers::dependency_graph;
+use crate::structs::dependency::{DependencyEdge, DependencyGraph, DependencyGraphQuery, DependencyNode, TaskDependency};
+use crate::structs::task_struct::{Status, TaskId};
+use tracing::info;
+
+// Links are checked against the rest inside the write transaction, so two at once can't close a loop
+pub fn add_task_dependency(db: &Database, payload: TaskDependency) -> Result<(), String> {
+    let conn = db.get_connection();
+    let tx = conn.unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    
+    let found = db::get_tasks_by_ids(&tx, &[payload.task_id, payload.depends_on_id])
+        .map_err(|e| format!("Failed to get tasks: {}", e))?;
+    for id in [payload.task_id, payload.depends_on_id] {
+        if !found.iter().any(|task| TaskId::from(task.id) == id) {
+            return Err(format!("Task {} not found", id));
+        }
+    }
+    
+    let links = db::get_task_dependencies(&tx)
+        .map_err(|e| format!("Failed to get dependencies: {}", e))?;
+    dependency_graph::check_link(&links, payload)?;
+    
+    let added = db::add_task_dependency(&tx, payload.task_id, payload.depends_on_id, Utc::now())
+        .map_err(|e| format!("Failed to add dependency: {}", e))?;
+    tx.commit().map_err(|e| format!("Failed to add dependency: {}", e))?;
+    
+    if added {
+        info!("Task {} now depends on {}", payload.task_id, payload.depends_on_id);
+    }
+    Ok(())
+}
+
+pub fn remove_task_dependency(db: &Database, payload: TaskDependency) -> Result<(), String> {
+    let conn = db.get_connection();
+    let removed = db::remove_task_dependency(&conn, payload.task_id, payload.depends_on_id)
+        .map_err(|e| format!("Failed to remove dependency: {}", e))?;
+    
+    if removed == 0 {
+        return Err(format!("Task {} doesn't depend on {}", payload.task_id, payload.depends_on_id));
+    }
+    Ok(())
+}
+
+// Tasks and links for the blocked/blocking view around one task or across a project
+pub fn get_dependency_graph(db: &Database, payload: DependencyGraphQuery) -> Result<DependencyGraph, String> {
+    let conn = db.get_read_connection();
+    let links = db::get_task_dependencies(&conn)
+        .map_err(|e| format!("Failed to get dependencies: {}", e))?;
+    
+    let ids: HashSet<TaskId> = match (payload.root_id, payload.project_id) {
+        (Some(root_id), None) => dependency_graph::connected(&links, &[root_id]),
+        (None, Some(project_id)) => {
+            let project_tasks = db::get_project_task_ids(&conn, &project_id)
+                .map_err(|e| format!("Failed to get project tasks: {}", e))?;
+            // Tasks outside the project show when a project task is linked to them directly
+            let mut ids: HashSet<TaskId> = project_tasks.iter().copied().collect();
+            for link in &links {
+                if project_tasks.contains(&link.task_id) || project_tasks.contains(&link.depends_on_id) {
+                    ids.extend([link.task_id, link.depends_on_id]);
+                }
+            }
+            ids
+        }
+        _ => return Err("Give either a root task or a project".to_string()),
+    };
+    
+    let mut tasks = db::get_tasks_by_ids(&conn, &ids.into_iter().collect::<Vec<_>>())
+        .map_err(|e| format!("Failed to get tasks: {}", e))?;
+    tasks.sort_by_key(|task| task.created_at);
+    if let Some(root_id) = payload.root_id {
+        if !tasks.iter().any(|task| TaskId::from(task.id) == root_id) {
+            return Err(format!("Task {} not found", root_id));
+        }
+    }
+    
+    let completed: HashSet<TaskId> = tasks.iter()
+        .filter(|task| task.status == Status::Completed)
+        .map(|task| TaskId::from(task.id))
+        .collect();
+    let included: HashSet<TaskId> = tasks.iter().map(|task| TaskId::from(task.id)).collect();
+    let edges: Vec<DependencyEdge> = links.into_iter()
+        .filter(|link| included.contains(&link.task_id) && included.contains(&link.depends_on_id))
+        .map(|link| DependencyEdge {
+            task_id: link.task_id,
+            depends_on_id: link.depends_on_id,
+            satisfied: completed.contains(&link.depends_on_id),
+        })
+        .collect();
+    
+    let nodes = tasks.into_iter()
+        .map(|task| {
+            let id = TaskId::from(task.id);
+            DependencyNode {
+                id,
+                blocked: edges.iter().any(|edge| edge.task_id == id && !edge.satisfied),
+                blocking: edges.iter().any(|edge| edge.depends_on_id == id && !edge.satisfied),
+                title: task.title,
+                status: task.status,
+            }
+        })
+        .collect();
+    Ok(DependencyGraph { nodes, edges })
+}
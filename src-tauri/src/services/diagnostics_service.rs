@@ -0,0 +1,96 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Write;
+use serde::Serialize;
+use tracing::info;
+use tauri::AppHandle;
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+use crate::db::{self, Database};
+use crate::logging;
+use crate::structs::settings::Settings;
+
+const DIAGNOSTIC_LOG_LINES: usize = 2000;
+
+// Tables whose sizes help reproduce bug reports; credentials are counted but never dumped
+const COUNTED_TABLES: [&str; 5] = ["tasks", "calendar_events", "calendar_credentials", "task_notifications", "jobs"];
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DiagnosticsReport {
+    app_version: String,
+    os: String,
+    os_version: String,
+    arch: String,
+    generated_at: String,
+    schema_version: i64,
+    row_counts: BTreeMap<String, i64>,
+    settings: Settings,
+}
+
+// Write a zip with recent logs and an environment/database summary to `path`
+pub fn export_diagnostics(db: &Database, app: &AppHandle, path: &str) -> Result<(), String> {
+    let report = build_report(db, app)?;
+    let report_json = serde_json::to_string_pretty(&report)
+        .map_err(|e| format!("Failed to serialize diagnostics: {}", e))?;
+    
+    // Log lines are redacted when written; redact again in case of older files
+    let logs = logging::get_recent_logs(app, DIAGNOSTIC_LOG_LINES)
+        .unwrap_or_else(|e| vec![format!("Logs unavailable: {}", e)])
+        .iter()
+        .map(|line| logging::redact(line))
+        .collect::<Vec<_>>()
+        .join("\n");
+    
+    let file = File::create(path)
+        .map_err(|e| format!("Failed to create diagnostics file: {}", e))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default();
+    
+    for (name, content) in [("diagnostics.json", report_json), ("logs.txt", logs)] {
+        zip.start_file(name, options)
+            .map_err(|e| format!("Failed to add {} to diagnostics: {}", name, e))?;
+        zip.write_all(content.as_bytes())
+            .map_err(|e| format!("Failed to write {} to diagnostics: {}", name, e))?;
+    }
+    
+    zip.finish()
+        .map_err(|e| format!("Failed to finish diagnostics file: {}", e))?;
+    
+    info!("Diagnostics exported to {}", path);
+    Ok(())
+}
+
+fn build_report(db: &Database, app: &AppHandle) -> Result<DiagnosticsReport, String> {
+    let conn = db.get_connection();
+    
+    let schema_version = db::get_schema_version(&conn)
+        .map_err(|e| format!("Failed to read schema version: {}", e))?;
+    
+    let mut row_counts = BTreeMap::new();
+    for table_name in COUNTED_TABLES {
+        let count = db::count_rows(&conn, table_name)
+            .map_err(|e| format!("Failed to count rows in {}: {}", table_name, e))?;
+        row_counts.insert(table_name.to_string(), count);
+    }
+    
+    let mut settings = db::get_settings(&conn)
+        .map_err(|e| format!("Failed to fetch settings: {}", e))?;
+    // Personal data, not needed for debugging
+    settings.calendar_email = None;
+    
+    drop(conn);
+    
+    let os = os_info::get();
+    
+    Ok(DiagnosticsReport {
+        app_version: app.package_info().version.to_string(),
+        os: os.os_type().to_string(),
+        os_version: os.version().to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        generated_at: chrono::Utc::now().to_rfc3339(),
+        schema_version,
+        row_counts,
+        settings,
+    })
+}
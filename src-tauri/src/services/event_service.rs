@@ -0,0 +1,128 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use crate::structs::app_lock::LockStatus;
+use crate::structs::goal::GoalProgress;
+use crate::structs::network::ConnectivityStatus;
+use crate::structs::overview::DailySummary;
+use crate::structs::task_struct::{Task, TaskId};
+use tracing::error;
+
+pub const TASK_CREATED: &str = "task-created";
+pub const TASK_UPDATED: &str = "task-updated";
+pub const TASK_STATUS_CHANGED: &str = "task-status-changed";
+pub const TASK_DELETED: &str = "task-deleted";
+
+// The events below aren't about a task's lifecycle
+
+// Asks the user whether to keep time spent idle
+pub const IDLE_TIME_RETURNED: &str = "idle-time-returned";
+// Items handled so far by the running import
+pub const IMPORT_PROGRESS: &str = "import-progress";
+// A weekly goal reached its target
+pub const GOAL_MET: &str = "goal-met";
+// The end-of-day summary job ran
+pub const DAILY_SUMMARY: &str = "daily-summary";
+// The app locked or unlocked; the payload is the lock status
+pub const APP_LOCK_CHANGED: &str = "app-lock-changed";
+// A running task's tracked time reached the estimate threshold in settings
+pub const ESTIMATE_REACHED: &str = "estimate-reached";
+// The connectivity check found the app went offline or came back
+pub const CONNECTIVITY_CHANGED: &str = "connectivity-changed";
+// Google refused the calendar connection; the UI asks to connect the account again
+pub const CALENDAR_REAUTH_NEEDED: &str = "calendar-reauth-needed";
+
+// All task lifecycle events, for listeners that react to any change
+pub const TASK_EVENTS: [&str; 4] = [TASK_CREATED, TASK_UPDATED, TASK_STATUS_CHANGED, TASK_DELETED];
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskDeletedPayload {
+    pub id: TaskId,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdleReturnedPayload {
+    pub task_ids: Vec<TaskId>,
+    pub idle_since: DateTime<Utc>,
+    pub returned_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EstimateReachedPayload {
+    pub task: Task,
+    pub tracked_minutes: i64,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarReauthPayload {
+    pub email: String,
+    // Calendar tasks have had local reminders since then
+    pub since: DateTime<Utc>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportProgressPayload {
+    pub done: usize,
+    pub total: usize,
+}
+
+// Events are broadcast to every window and to Rust listeners (tray, badges).
+// Call these only after the DB lock is released: Rust listeners may query the database.
+pub fn emit_task_created(app: &AppHandle, task: &Task) {
+    emit(app, TASK_CREATED, task);
+}
+
+pub fn emit_task_updated(app: &AppHandle, task: &Task) {
+    emit(app, TASK_UPDATED, task);
+}
+
+pub fn emit_task_status_changed(app: &AppHandle, task: &Task) {
+    emit(app, TASK_STATUS_CHANGED, task);
+}
+
+pub fn emit_task_deleted(app: &AppHandle, id: TaskId) {
+    emit(app, TASK_DELETED, TaskDeletedPayload { id });
+}
+
+pub fn emit_idle_time_returned(app: &AppHandle, payload: IdleReturnedPayload) {
+    emit(app, IDLE_TIME_RETURNED, payload);
+}
+
+pub fn emit_import_progress(app: &AppHandle, done: usize, total: usize) {
+    emit(app, IMPORT_PROGRESS, ImportProgressPayload { done, total });
+}
+
+pub fn emit_goal_met(app: &AppHandle, progress: &GoalProgress) {
+    emit(app, GOAL_MET, progress);
+}
+
+pub fn emit_daily_summary(app: &AppHandle, summary: &DailySummary) {
+    emit(app, DAILY_SUMMARY, summary);
+}
+
+pub fn emit_app_lock_changed(app: &AppHandle, status: &LockStatus) {
+    emit(app, APP_LOCK_CHANGED, status);
+}
+
+pub fn emit_estimate_reached(app: &AppHandle, payload: &EstimateReachedPayload) {
+    emit(app, ESTIMATE_REACHED, payload);
+}
+
+pub fn emit_connectivity_changed(app: &AppHandle, status: &ConnectivityStatus) {
+    emit(app, CONNECTIVITY_CHANGED, status);
+}
+
+pub fn emit_calendar_reauth_needed(app: &AppHandle, payload: &CalendarReauthPayload) {
+    emit(app, CALENDAR_REAUTH_NEEDED, payload);
+}
+
+fn emit<S: Serialize + Clone>(app: &AppHandle, event: &str, payload: S) {
+    if let Err(e) = app.emit(event, payload) {
+        error!("Failed to emit '{}' event: {}", event, e);
+    }
+}
@@ -0,0 +1,321 @@
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use chrono::{DateTime, Datelike, Duration, FixedOffset, Utc};
+use crate::db::{self, Database};
+use crate::helpers::format::Formatter;
+use crate::helpers::parse_date::{local_day, parse_date_range, parse_week_range_from};
+use crate::services::settings_service;
+use crate::structs::data_export::{
+    CsvColumn, CsvExportData, MarkdownSummary, MarkdownSummaryData, SharedDay, SharedDayData, SharedDayFormat,
+    SharedDaySnapshot, SharedTask, SummaryPeriod,
+};
+use crate::structs::task_struct::{Task, TaskId};
+use tracing::info;
+
+// Write tasks created in the range to a CSV file, one row at a time; returns the row count
+pub fn export_csv(db: &Database, payload: CsvExportData) -> Result<usize, String> {
+    let offset = settings_service::day_offset(db, payload.range.utc_offset_minutes)?;
+    let (start, _) = parse_date_range("from", &payload.range.from, Some(offset))?;
+    let (_, end) = parse_date_range("to", &payload.range.to, Some(offset))?;
+    if start >= end {
+        return Err("Export range ends before it starts".to_string());
+    }
+    
+    let columns = if payload.columns.is_empty() {
+        CsvColumn::ALL.to_vec()
+    } else {
+        payload.columns
+    };
+    
+    let file = File::create(&payload.path)
+        .map_err(|e| format!("Failed to create CSV file: {}", e))?;
+    let mut writer = BufWriter::new(file);
+    
+    let header: Vec<&str> = columns.iter().map(|column| column.header()).collect();
+    writeln!(writer, "{}", header.join(","))
+        .map_err(|e| format!("Failed to write CSV file: {}", e))?;
+    
+    let rows = {
+        let conn = db.get_read_connection();
+        db::for_each_task_created_between(&conn, start, end, |task| {
+            let fields: Vec<String> = columns.iter()
+                .map(|column| csv_field(&column_value(task, *column)))
+                .collect();
+            writeln!(writer, "{}", fields.join(","))
+        }).map_err(|e| format!("Failed to export tasks: {}", e))?
+    }; // DB lock released here
+    
+    writer.flush()
+        .map_err(|e| format!("Failed to write CSV file: {}", e))?;
+    
+    info!("Exported {} tasks to {}", rows, payload.path);
+    Ok(rows)
+}
+
+// Work-log style report of a day or week; written to `folder` when one is given
+pub fn export_markdown_summary(db: &Database, payload: MarkdownSummaryData) -> Result<MarkdownSummary, String> {
+    let offset = settings_service::day_offset(db, payload.utc_offset_minutes)?;
+    let formatter = settings_service::formatter(db)?;
+    let (day_start, day_end) = parse_date_range("date", &payload.date, Some(offset))?;
+    let day = local_day(day_start, offset);
+    let (start, end, heading, file_name) = match payload.period {
+        SummaryPeriod::Day => (
+            day_start,
+            day_end,
+            formatter.long_date(day),
+            format!("myhandler-{}.md", day.format("%Y-%m-%d")),
+        ),
+        SummaryPeriod::Week => {
+            let (week_start, week_end) = parse_week_range_from("date", &payload.date, Some(offset), formatter.week_start())?;
+            let first_day = local_day(week_start, offset);
+            // File names keep ISO weeks; the week's fourth day is in the same ISO week whichever day it starts on
+            let week = (first_day + Duration::days(3)).iso_week();
+            (
+                week_start,
+                week_end,
+                formatter.week_label(first_day),
+                format!("myhandler-{}-W{:02}.md", week.year(), week.week()),
+            )
+        }
+    };
+    
+    let (completed, carried_over, notes) = {
+        let conn = db.get_read_connection();
+        let completed = db::query_tasks_by_date_range(&conn, start, end, include_str!("../db/sql/get_tasks_completed_between.sql"))
+            .map_err(|e| format!("Failed to query tasks: {}", e))?;
+        let carried_over = db::query_tasks_by_date_range(&conn, start, end, include_str!("../db/sql/get_tasks_by_date_not_completed.sql"))
+            .map_err(|e| format!("Failed to query tasks: {}", e))?;
+        let notes = db::get_session_notes(&conn, start, end)
+            .map_err(|e| format!("Failed to get session notes: {}", e))?;
+        (completed, carried_over, notes)
+    }; // DB lock released here
+    
+    // Stamped with when the session ended: the time on a day, day and time across a week
+    let mut session_notes: HashMap<TaskId, Vec<String>> = HashMap::new();
+    for note in notes {
+        let ended_at = note.ended_at.with_timezone(&offset).naive_local();
+        let stamp = match payload.period {
+            SummaryPeriod::Day => formatter.time(ended_at.time()),
+            SummaryPeriod::Week => formatter.short_date_time(ended_at),
+        };
+        session_notes.entry(note.task_id).or_default().push(format!("_{}_ {}", stamp, note.note));
+    }
+    
+    let markdown = render_summary(&heading, &completed, &carried_over, &session_notes);
+    
+    let path = match payload.folder {
+        Some(folder) => {
+            let path = Path::new(&folder).join(file_name);
+            fs::write(&path, &markdown)
+                .map_err(|e| format!("Failed to write summary: {}", e))?;
+            info!("Summary written to {:?}", path);
+            Some(path.display().to_string())
+        }
+        None => None,
+    };
+    
+    Ok(MarkdownSummary { markdown, path })
+}
+
+// Static snapshot of a day's tasks to hand to someone without an account; written to `folder` when one is given
+pub fn export_shared_day(db: &Database, payload: SharedDayData) -> Result<SharedDay, String> {
+    let offset = settings_service::day_offset(db, payload.utc_offset_minutes)?;
+    let formatter = settings_service::formatter(db)?;
+    let (start, end) = parse_date_range("date", &payload.date, Some(offset))?;
+    
+    let tasks = {
+        let sql = include_str!("../db/sql/get_tasks_by_date.sql");
+        let conn = db.get_read_connection();
+        db::query_tasks_by_date_range(&conn, start, end, sql)
+            .map_err(|e| format!("Failed to query tasks: {}", e))?
+    }; // DB lock released here
+    
+    // Listed in the order they were added, as on a plan
+    let snapshot = SharedDaySnapshot {
+        date: local_day(start, offset).format("%Y-%m-%d").to_string(),
+        generated_at: Utc::now(),
+        tasks: tasks.into_iter().rev()
+            .map(|task| SharedTask {
+                notes: task.notes.filter(|notes| payload.include_notes && !notes.trim().is_empty()),
+                status: String::from(task.status),
+                title: task.title,
+                deadline: task.deadline,
+                estimate_minutes: task.estimate_minutes,
+                started_at: task.started_at,
+                completed_at: task.completed_at,
+            })
+            .collect(),
+    };
+    
+    let (content, extension) = match payload.format {
+        SharedDayFormat::Json => (
+            serde_json::to_string_pretty(&snapshot)
+                .map_err(|e| format!("Failed to serialize shared day: {}", e))?,
+            "json",
+        ),
+        SharedDayFormat::Html => (render_shared_day(&snapshot, formatter, offset), "html"),
+    };
+    
+    let path = match payload.folder {
+        Some(folder) => {
+            let path = Path::new(&folder).join(format!("myhandler-shared-{}.{}", snapshot.date, extension));
+            fs::write(&path, &content)
+                .map_err(|e| format!("Failed to write shared day: {}", e))?;
+            info!("Shared day written to {:?}", path);
+            Some(path.display().to_string())
+        }
+        None => None,
+    };
+    
+    Ok(SharedDay { content, path })
+}
+
+// Inline styles and no scripts, so it opens the same anywhere, even from a mail attachment.
+// Times are shown in the offset the day was cut at.
+fn render_shared_day(snapshot: &SharedDaySnapshot, formatter: Formatter, offset: FixedOffset) -> String {
+    let heading = chrono::NaiveDate::parse_from_str(&snapshot.date, "%Y-%m-%d")
+        .map(|day| formatter.long_date(day))
+        .unwrap_or_else(|_| snapshot.date.clone());
+    
+    let mut out = String::new();
+    let _ = writeln!(out, "<!DOCTYPE html>");
+    let _ = writeln!(out, "<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">");
+    let _ = writeln!(out, "<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">");
+    let _ = writeln!(out, "<title>Plan for {}</title>", html_escape(&heading));
+    let _ = writeln!(out, "<style>body{{font-family:system-ui,sans-serif;max-width:40rem;margin:2rem auto;padding:0 1rem;color:#222}}li{{margin:.6rem 0}}.done{{text-decoration:line-through;color:#777}}.meta{{color:#666;font-size:.85em}}blockquote{{margin:.3rem 0 0 1rem;color:#444;white-space:pre-wrap}}</style>");
+    let _ = writeln!(out, "</head>\n<body>");
+    let _ = writeln!(out, "<h1>Plan for {}</h1>", html_escape(&heading));
+    
+    if snapshot.tasks.is_empty() {
+        let _ = writeln!(out, "<p>Nothing planned.</p>");
+    } else {
+        let _ = writeln!(out, "<ul>");
+        for task in &snapshot.tasks {
+            let class = if task.completed_at.is_some() { " class=\"done\"" } else { "" };
+            let _ = write!(out, "<li><span{}>{}</span>", class, html_escape(&task.title));
+            
+            let mut meta = vec![task.status.replace('-', " ")];
+            if let Some(minutes) = task.estimate_minutes {
+                meta.push(format!("estimate {}", format_duration(Duration::minutes(minutes.into()))));
+            }
+            if let Some(deadline) = task.deadline {
+                meta.push(format!("due {}", formatter.short_date_time(deadline.with_timezone(&offset).naive_local())));
+            }
+            let _ = write!(out, " <span class=\"meta\">{}</span>", html_escape(&meta.join(" · ")));
+            
+            if let Some(notes) = &task.notes {
+                let _ = write!(out, "<blockquote>{}</blockquote>", html_escape(notes));
+            }
+            let _ = writeln!(out, "</li>");
+        }
+        let _ = writeln!(out, "</ul>");
+    }
+    
+    let _ = writeln!(out, "<p class=\"meta\">Shared from MyHandler on {}. Read-only snapshot.</p>", formatter.date_time(snapshot.generated_at.with_timezone(&offset).naive_local()));
+    let _ = writeln!(out, "</body>\n</html>");
+    out
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+fn render_summary(heading: &str, completed: &[Task], carried_over: &[Task], session_notes: &HashMap<TaskId, Vec<String>>) -> String {
+    let mut out = format!("# Work log: {}\n", heading);
+    
+    let _ = write!(out, "\n## Completed ({})\n\n", completed.len());
+    if completed.is_empty() {
+        out.push_str("Nothing completed.\n");
+    }
+    for task in completed {
+        let spent = task.time_spent().map(|d| format!(" ({})", format_duration(d))).unwrap_or_default();
+        let _ = writeln!(out, "- [x] {}{}", task.title, spent);
+        push_notes(&mut out, task);
+        push_session_notes(&mut out, task, session_notes);
+    }
+    
+    // Tasks are listed newest first by the query; keep the order they were added in
+    let _ = write!(out, "\n## Carried over ({})\n\n", carried_over.len());
+    if carried_over.is_empty() {
+        out.push_str("Nothing left open.\n");
+    }
+    for task in carried_over.iter().rev() {
+        let _ = writeln!(out, "- [ ] {} _{}_", task.title, String::from(task.status.clone()));
+        push_notes(&mut out, task);
+        push_session_notes(&mut out, task, session_notes);
+    }
+    
+    let total: Duration = completed.iter().filter_map(Task::time_spent).sum();
+    let _ = write!(out, "\n## Time spent\n\n");
+    let _ = writeln!(out, "{} on {} completed tasks (start to finish).", format_duration(total), completed.len());
+    
+    out
+}
+
+// Pauses aren't stored, so this is wall-clock time from first start to completion
+fn format_duration(duration: Duration) -> String {
+    let minutes = duration.num_minutes();
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{}m", m),
+        (h, 0) => format!("{}h", h),
+        (h, m) => format!("{}h {}m", h, m),
+    }
+}
+
+fn push_notes(out: &mut String, task: &Task) {
+    let Some(notes) = task.notes.as_deref().filter(|n| !n.trim().is_empty()) else {
+        return;
+    };
+    for line in notes.lines() {
+        let _ = writeln!(out, "  > {}", line);
+    }
+}
+
+fn push_session_notes(out: &mut String, task: &Task, session_notes: &HashMap<TaskId, Vec<String>>) {
+    for note in session_notes.get(&TaskId::from(task.id)).into_iter().flatten() {
+        let _ = writeln!(out, "  - {}", note);
+    }
+}
+
+fn column_value(task: &Task, column: CsvColumn) -> String {
+    match column {
+        CsvColumn::Id => task.id.to_string(),
+        CsvColumn::Title => task.title.clone(),
+        CsvColumn::Notes => task.notes.clone().unwrap_or_default(),
+        CsvColumn::Status => String::from(task.status.clone()),
+        CsvColumn::CreatedAt => format_time(Some(task.created_at)),
+        CsvColumn::UpdatedAt => format_time(Some(task.updated_at)),
+        CsvColumn::Deadline => format_time(task.deadline),
+        CsvColumn::StartedAt => format_time(task.started_at),
+        CsvColumn::PausedAt => format_time(task.paused_at),
+        CsvColumn::CompletedAt => format_time(task.completed_at),
+        CsvColumn::ReminderFrequency => String::from(task.reminder_frequency.clone()),
+        CsvColumn::CalendarIntegration => task.has_calendar_integration.to_string(),
+    }
+}
+
+fn format_time(time: Option<DateTime<Utc>>) -> String {
+    time.map(|t| t.to_rfc3339()).unwrap_or_default()
+}
+
+// RFC 4180 quoting; leading formula characters are prefixed so spreadsheets show them as text
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+    
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
@@ -0,0 +1,192 @@
+use chrono::Utc;
+use tauri::AppHandle;
+use crate::db::{self, Database, insert};
+use crate::services::{event_service, network_permission_service, settings_service};
+use crate::structs::github::{GithubCredentials, GithubIssue, GithubStatus, GithubSyncSummary};
+use crate::structs::network::NetworkFeature;
+use crate::structs::reminder_frequency::ReminderFrequency;
+use crate::structs::task_struct::{Task, TaskId, Status};
+use crate::structs::task_update::TaskUpdateParsed;
+use crate::thirdparty::github;
+use tracing::{info, warn};
+
+// `source` value in external_links
+pub const GITHUB_SOURCE: &str = "github";
+
+// Verify the personal access token and store it
+pub async fn connect_github(db: &Database, token: &str, close_issues_on_complete: bool) -> Result<GithubStatus, String> {
+    network_permission_service::ensure_allowed(db, NetworkFeature::Github)?;
+    
+    let token = token.trim();
+    if token.is_empty() {
+        return Err("GitHub token cannot be empty".to_string());
+    }
+    
+    let user = github::get_authenticated_user(token).await?;
+    
+    let creds = GithubCredentials {
+        login: user.login,
+        token: token.to_string(),
+        close_issues_on_complete,
+    };
+    let conn = db.get_connection();
+    db::save_github_credentials(&conn, &creds)
+        .map_err(|e| format!("Failed to save GitHub credentials: {}", e))?;
+    
+    info!("Connected GitHub account {}", creds.login);
+    Ok(creds.into())
+}
+
+pub fn get_github_status(db: &Database) -> Result<Option<GithubStatus>, String> {
+    Ok(get_credentials(db)?.map(GithubStatus::from))
+}
+
+pub fn disconnect_github(db: &Database) -> Result<(), String> {
+    let conn = db.get_connection();
+    
+    db::clear_github_credentials(&conn)
+        .map_err(|e| format!("Failed to disconnect GitHub: {}", e))
+}
+
+pub fn set_close_issues_on_complete(db: &Database, enabled: bool) -> Result<GithubStatus, String> {
+    {
+        let conn = db.get_connection();
+        db::set_github_close_issues(&conn, enabled)
+            .map_err(|e| format!("Failed to update GitHub settings: {}", e))?;
+    } // DB lock released here
+    
+    get_github_status(db)?.ok_or_else(|| "GitHub is not connected".to_string())
+}
+
+// Create a task per assigned open issue, or refresh the one already linked to it
+pub async fn sync_github_issues(db: &Database, app: &AppHandle) -> Result<GithubSyncSummary, String> {
+    network_permission_service::ensure_allowed(db, NetworkFeature::Github)?;
+    
+    let creds = get_credentials(db)?
+        .ok_or_else(|| "GitHub is not connected".to_string())?;
+    let issues = github::list_assigned_issues(&creds.token).await?;
+    // Before the lock: settings may be read through the same connection
+    let reminder_frequency = settings_service::reminder_frequency(db, None)?;
+    
+    let mut summary = GithubSyncSummary::default();
+    for issue in issues.iter().filter(|issue| issue.pull_request.is_none()) {
+        match sync_issue(db, issue, &reminder_frequency)? {
+            SyncOutcome::Created(task) => {
+                summary.created += 1;
+                event_service::emit_task_created(app, &task);
+            }
+            SyncOutcome::Updated(task) => {
+                summary.updated += 1;
+                event_service::emit_task_updated(app, &task);
+            }
+            SyncOutcome::Unchanged => summary.unchanged += 1,
+        }
+    }
+    
+    info!(
+        "GitHub sync: {} created, {} updated, {} unchanged",
+        summary.created, summary.updated, summary.unchanged
+    );
+    Ok(summary)
+}
+
+// Close the linked issue after its task was completed, if the user asked for that
+pub async fn close_linked_issue(db: &Database, task_id: TaskId) {
+    let (creds, link) = {
+        let conn = db.get_connection();
+        let creds = db::get_github_credentials(&conn).ok().flatten();
+        let link = db::get_external_link_by_task(&conn, task_id, GITHUB_SOURCE).ok().flatten();
+        (creds, link)
+    }; // DB lock released here
+    
+    let (Some(creds), Some(link)) = (creds, link) else {
+        return;
+    };
+    if !creds.close_issues_on_complete {
+        return;
+    }
+    if let Err(e) = network_permission_service::ensure_allowed(db, NetworkFeature::Github) {
+        warn!("Not closing GitHub issue {}: {}", link.external_id, e);
+        return;
+    }
+    
+    if let Err(e) = github::close_issue(&creds.token, &link.external_id).await {
+        warn!("{}", e);
+    }
+}
+
+fn get_credentials(db: &Database) -> Result<Option<GithubCredentials>, String> {
+    let conn = db.get_connection();
+    
+    db::get_github_credentials(&conn)
+        .map_err(|e| format!("Failed to get GitHub credentials: {}", e))
+}
+
+enum SyncOutcome {
+    Created(Task),
+    Updated(Task),
+    Unchanged,
+}
+
+fn sync_issue(db: &Database, issue: &GithubIssue, reminder_frequency: &ReminderFrequency) -> Result<SyncOutcome, String> {
+    let external_id = issue.external_id();
+    let notes = issue_notes(issue);
+    
+    let conn = db.get_connection();
+    let linked_task = match db::get_task_id_by_external_id(&conn, GITHUB_SOURCE, &external_id)
+        .map_err(|e| format!("Failed to look up GitHub link: {}", e))?
+    {
+        Some(task_id) => match db::get_task_by_id(&conn, task_id) {
+            Ok(task) => Some(task),
+            // Task deleted locally: leave it deleted
+            Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(SyncOutcome::Unchanged),
+            Err(e) => return Err(format!("Failed to get task: {}", e)),
+        },
+        None => None,
+    };
+    
+    match linked_task {
+        Some(task) if task.status == Status::Completed => Ok(SyncOutcome::Unchanged),
+        Some(task) if task.title == issue.title && task.notes.as_deref() == Some(notes.as_str()) => Ok(SyncOutcome::Unchanged),
+        Some(task) => {
+            let update = TaskUpdateParsed {
+                title: Some(issue.title.clone()),
+                notes: Some(Some(notes)),
+                deadline: None,
+                has_calendar_integration: None,
+                calendar_email: None,
+                reminder_frequency: None,
+                notifications_enabled: None,
+                estimate_minutes: None,
+                project_id: None,
+                assignee: None,
+                context: None,
+                energy: None,
+                calendar_privacy: None,
+                updated_at: Utc::now(),
+            };
+            let task = db::update_task(&conn, task.id.into(), &update)
+                .map_err(|e| format!("Failed to update task from {}: {}", external_id, e))?;
+            Ok(SyncOutcome::Updated(task))
+        }
+        None => {
+            let task = Task::new(&issue.title, Utc::now(), Some(&notes), reminder_frequency.clone());
+            let tx = conn.unchecked_transaction()
+                .map_err(|e| format!("Failed to start transaction: {}", e))?;
+            insert(&tx, &task)
+                .map_err(|e| format!("Failed to insert task for {}: {}", external_id, e))?;
+            db::insert_external_link(&tx, task.id.into(), GITHUB_SOURCE, &external_id, Some(&issue.html_url))
+                .map_err(|e| format!("Failed to link task to {}: {}", external_id, e))?;
+            tx.commit().map_err(|e| format!("Failed to insert task for {}: {}", external_id, e))?;
+            Ok(SyncOutcome::Created(task))
+        }
+    }
+}
+
+// Link first so it stays visible however long the description is
+fn issue_notes(issue: &GithubIssue) -> String {
+    match issue.body.as_deref().map(str::trim) {
+        Some(body) if !body.is_empty() => format!("{}\n\n{}", issue.html_url, body),
+        _ => issue.html_url.clone(),
+    }
+}
@@ -0,0 +1,148 @@
+use chrono::Utc;
+use tauri::{AppHandle, Listener, Manager};
+use uuid::timestamp::context::NoContext;
+use uuid::{Timestamp, Uuid};
+use crate::db::{self, Database};
+use crate::helpers::parse_date::{local_day, parse_week_range};
+use crate::services::{event_service, settings_service};
+use crate::structs::goal::{Goal, GoalData, GoalId, GoalMetric, GoalProgress, GoalProgressQuery, GoalUpdate, GoalUpdateParsed, WeekTotals};
+use tracing::{info, error};
+
+// Announce goals as they are met; completing a task is a status change
+pub fn init_goal_tracking(app: &AppHandle) {
+    let handle = app.clone();
+    app.listen_any(event_service::TASK_STATUS_CHANGED, move |_| {
+        // Off the emitting thread: progress queries the database
+        let handle = handle.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            if let Err(e) = announce_met_goals(&handle) {
+                error!("Failed to check goals: {}", e);
+            }
+        });
+    });
+}
+
+pub fn create_goal(db: &Database, payload: GoalData) -> Result<Goal, String> {
+    validate(&payload.title, payload.target)?;
+    
+    let goal = Goal {
+        id: Uuid::new_v7(Timestamp::now(NoContext)),
+        title: payload.title.trim().to_string(),
+        metric: payload.metric,
+        target: payload.target,
+        created_at: Utc::now(),
+        last_met_week: None,
+    };
+    
+    let conn = db.get_connection();
+    db::insert(&conn, &goal)
+        .map_err(|e| format!("Failed to create goal: {}", e))?;
+    
+    info!("Created goal {}", goal.id);
+    Ok(goal)
+}
+
+pub fn get_goals(db: &Database) -> Result<Vec<Goal>, String> {
+    let conn = db.get_read_connection();
+    db::get_goals(&conn)
+        .map_err(|e| format!("Failed to fetch goals: {}", e))
+}
+
+pub fn update_goal(db: &Database, payload: GoalUpdate) -> Result<Goal, String> {
+    let conn = db.get_connection();
+    let current = db::get_goal_by_id(&conn, &payload.id)
+        .map_err(|e| format!("Failed to get goal: {}", e))?;
+    
+    let data = payload.data;
+    validate(data.title.as_deref().unwrap_or(&current.title), data.target.unwrap_or(current.target))?;
+    
+    let update = GoalUpdateParsed {
+        title: data.title.map(|title| title.trim().to_string()),
+        metric: data.metric,
+        target: data.target,
+    };
+    db::update_goal(&conn, &payload.id, &update)
+        .map_err(|e| format!("Failed to update goal: {}", e))
+}
+
+pub fn delete_goal(db: &Database, payload: GoalId) -> Result<(), String> {
+    let conn = db.get_connection();
+    let deleted = db::delete_goal_by_id(&conn, &payload.id)
+        .map_err(|e| format!("Failed to delete goal: {}", e))?;
+    
+    if deleted == 0 {
+        return Err(format!("No goal with ID {}", payload.id));
+    }
+    Ok(())
+}
+
+// Progress of every goal over the week containing the given moment
+pub fn get_goal_progress(db: &Database, payload: GoalProgressQuery) -> Result<Vec<GoalProgress>, String> {
+    let offset = settings_service::day_offset(db, payload.utc_offset_minutes)?;
+    let (start, end) = parse_week_range("week", &payload.week, Some(offset))?;
+    
+    let conn = db.get_read_connection();
+    let goals = db::get_goals(&conn)
+        .map_err(|e| format!("Failed to fetch goals: {}", e))?;
+    let totals = db::get_week_totals(&conn, start, end)
+        .map_err(|e| format!("Failed to compute goal progress: {}", e))?;
+    
+    Ok(goals.into_iter().map(|goal| progress(goal, &totals)).collect())
+}
+
+// Emit goal-met once per goal and week
+fn announce_met_goals(app: &AppHandle) -> Result<(), String> {
+    let db = app.state::<Database>();
+    let offset = settings_service::day_offset(&db, None)?;
+    let (start, end) = parse_week_range("week", &Utc::now().to_rfc3339(), Some(offset))?;
+    let week = local_day(start, offset).format("%Y-%m-%d").to_string();
+    
+    let newly_met: Vec<GoalProgress> = {
+        let conn = db.get_connection();
+        let goals = db::get_goals(&conn)
+            .map_err(|e| format!("Failed to fetch goals: {}", e))?;
+        let totals = db::get_week_totals(&conn, start, end)
+            .map_err(|e| format!("Failed to compute goal progress: {}", e))?;
+        
+        let mut newly_met = Vec::new();
+        for goal in goals.into_iter().filter(|goal| goal.last_met_week.as_deref() != Some(week.as_str())) {
+            let progress = progress(goal, &totals);
+            if progress.met {
+                db::set_goal_met(&conn, &progress.goal.id, &week)
+                    .map_err(|e| format!("Failed to record met goal: {}", e))?;
+                newly_met.push(progress);
+            }
+        }
+        newly_met
+    }; // DB lock released here
+    
+    for progress in &newly_met {
+        info!("Goal {} met for week of {}", progress.goal.id, week);
+        event_service::emit_goal_met(app, progress);
+    }
+    Ok(())
+}
+
+fn progress(goal: Goal, totals: &WeekTotals) -> GoalProgress {
+    let current = match goal.metric {
+        GoalMetric::CompletedTasks => totals.completed,
+        GoalMetric::TrackedMinutes => totals.tracked_minutes,
+    };
+    
+    GoalProgress {
+        current,
+        percentage: (current as f64 * 100.0 / goal.target as f64).min(100.0),
+        met: current >= goal.target,
+        goal,
+    }
+}
+
+fn validate(title: &str, target: i64) -> Result<(), String> {
+    if title.trim().is_empty() {
+        return Err("Goal title can't be empty".to_string());
+    }
+    if target <= 0 {
+        return Err("Goal target must be greater than zero".to_string());
+    }
+    Ok(())
+}
@@ -0,0 +1,132 @@
+use std::fs;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use crate::db::{self, Database};
+use crate::helpers::holidays::{parse_ics_holidays, region_holidays};
+use crate::helpers::parse_date::local_day;
+use crate::services::settings_service;
+use crate::structs::holiday::{
+    HolidayImport, HolidayImportData, HolidayRangeQuery, HolidaySource, ListedHoliday, WorkingCalendar, MAX_SKIP_DAYS,
+};
+use tracing::info;
+
+// Listing more than this is almost certainly a mistyped year
+const MAX_LIST_DAYS: i64 = 3 * 366;
+
+// Days off between `from` and `to` (and far enough past `to` to shift a date off them), per settings.
+// Call before taking the connection: settings are read through it.
+pub fn working_calendar(db: &Database, from: NaiveDate, to: NaiveDate) -> Result<WorkingCalendar, String> {
+    let settings = db.settings()
+        .map_err(|e| format!("Failed to fetch settings: {}", e))?;
+    
+    let mut calendar = WorkingCalendar {
+        skip_weekends: settings.skip_weekends,
+        ..WorkingCalendar::default()
+    };
+    if !settings.skip_holidays {
+        return Ok(calendar);
+    }
+    
+    let to = to + Duration::days(MAX_SKIP_DAYS);
+    let imported = {
+        let conn = db.get_read_connection();
+        db::get_holidays_between(&conn, from, to)
+            .map_err(|e| format!("Failed to fetch holidays: {}", e))?
+    }; // DB lock released here
+    calendar.holidays.extend(imported.into_iter().map(|holiday| holiday.date));
+    
+    if let Some(region) = settings.holiday_region {
+        for year in from.year()..=to.year() {
+            calendar.holidays.extend(region_holidays(region, year).into_iter()
+                .map(|(date, _)| date)
+                .filter(|date| (from..=to).contains(date)));
+        }
+    }
+    Ok(calendar)
+}
+
+// The same time on the next working day in the settings offset; unchanged when skipping is off
+pub fn shift_to_working_day(db: &Database, moment: DateTime<Utc>) -> Result<DateTime<Utc>, String> {
+    let offset = settings_service::day_offset(db, None)?;
+    let day = local_day(moment, offset);
+    let calendar = working_calendar(db, day, day)?;
+    Ok(calendar.shift_to_working_day(moment, offset))
+}
+
+// Imported and regional holidays in the range, whether or not skipping is on
+pub fn list_holidays(db: &Database, payload: HolidayRangeQuery) -> Result<Vec<ListedHoliday>, String> {
+    let from = parse_day("from", &payload.from)?;
+    let to = parse_day("to", &payload.to)?;
+    if from > to {
+        return Err("Holiday range ends before it starts".to_string());
+    }
+    if (to - from).num_days() > MAX_LIST_DAYS {
+        return Err(format!("Holiday range can't be longer than {} days", MAX_LIST_DAYS));
+    }
+    
+    let region = db.settings()
+        .map_err(|e| format!("Failed to fetch settings: {}", e))?
+        .holiday_region;
+    let imported = {
+        let conn = db.get_read_connection();
+        db::get_holidays_between(&conn, from, to)
+            .map_err(|e| format!("Failed to fetch holidays: {}", e))?
+    }; // DB lock released here
+    
+    let mut holidays: Vec<ListedHoliday> = imported.into_iter()
+        .map(|holiday| ListedHoliday { date: holiday.date, name: holiday.name, source: HolidaySource::Imported })
+        .collect();
+    if let Some(region) = region {
+        for year in from.year()..=to.year() {
+            holidays.extend(region_holidays(region, year).into_iter()
+                .filter(|(date, _)| (from..=to).contains(date))
+                .map(|(date, name)| ListedHoliday { date, name, source: HolidaySource::Region }));
+        }
+    }
+    holidays.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.name.cmp(&b.name)));
+    Ok(holidays)
+}
+
+// Add every event in an ICS file as a holiday; importing the same file again adds nothing
+pub fn import_holidays(db: &Database, payload: HolidayImportData) -> Result<HolidayImport, String> {
+    let content = fs::read_to_string(&payload.path)
+        .map_err(|e| format!("Failed to read {}: {}", payload.path, e))?;
+    let holidays = parse_ics_holidays(&content)?;
+    
+    let added = {
+        let conn = db.get_connection();
+        let tx = conn.unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        
+        if payload.replace {
+            db::delete_holidays(&tx)
+                .map_err(|e| format!("Failed to clear holidays: {}", e))?;
+        }
+        let now = Utc::now();
+        let mut added = 0;
+        for (date, name) in &holidays {
+            if db::insert_holiday(&tx, *date, name, now)
+                .map_err(|e| format!("Failed to import holiday on {}: {}", date, e))?
+            {
+                added += 1;
+            }
+        }
+        
+        tx.commit().map_err(|e| format!("Failed to import holidays: {}", e))?;
+        added
+    }; // DB lock released here
+    
+    info!("Imported {} holidays from {}", added, payload.path);
+    Ok(HolidayImport { added, skipped: holidays.len() - added })
+}
+
+// Remove every imported holiday; the regional list is turned off in settings instead
+pub fn clear_holidays(db: &Database) -> Result<usize, String> {
+    let conn = db.get_connection();
+    db::delete_holidays(&conn)
+        .map_err(|e| format!("Failed to clear holidays: {}", e))
+}
+
+fn parse_day(field: &str, value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+        .map_err(|_| format!("Invalid {} '{}': expected YYYY-MM-DD", field, value))
+}
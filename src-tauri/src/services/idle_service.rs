@@ -0,0 +1,91 @@
+use std::sync::Mutex;
+use chrono::{Duration, Utc};
+use tauri::{AppHandle, Manager};
+use crate::db::{self, Database};
+use crate::helpers::idle_time;
+use crate::services::{event_service, task_service};
+use crate::structs::dto::{IdleResolutionData, TaskRef};
+use crate::structs::task_struct::{Task, TaskId};
+use tracing::info;
+
+// Tasks auto-paused during the current idle period
+struct IdleSession {
+    task_ids: Vec<TaskId>,
+    idle_since: chrono::DateTime<Utc>,
+}
+
+#[derive(Default)]
+pub struct IdleState(Mutex<Option<IdleSession>>);
+
+// Auto-pause ongoing tasks once the user has been idle past the configured threshold,
+// and ask them about the idle time when they come back
+pub async fn check_idle(app: &AppHandle) -> Result<(), String> {
+    let db = app.try_state::<Database>()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    let state = app.state::<IdleState>();
+    
+    // Unsupported platform or missing helper tool: nothing to do
+    let Some(idle_secs) = idle_time::get_idle_seconds() else {
+        return Ok(());
+    };
+    
+    let settings = db.settings()
+        .map_err(|e| format!("Failed to fetch settings: {}", e))?;
+    let threshold_secs = i64::from(settings.idle_pause_minutes) * 60;
+    
+    let is_idle = threshold_secs > 0 && idle_secs as i64 >= threshold_secs;
+    let has_session = state.0.lock().map_err(|e| e.to_string())?.is_some();
+    
+    if is_idle && !has_session {
+        let tasks = {
+            let conn = db.get_connection();
+            db::get_ongoing_tasks(&conn)
+                .map_err(|e| format!("Failed to get ongoing tasks: {}", e))?
+        }; // DB lock released here
+        
+        if tasks.is_empty() {
+            return Ok(());
+        }
+        
+        let idle_since = Utc::now() - Duration::seconds(idle_secs as i64);
+        let mut task_ids = Vec::new();
+        for task in tasks {
+            let task_id = TaskId::from(task.id);
+            task_service::pause_task_at(task_id, idle_since, None, None, &db, app).await?;
+            info!("Auto-paused task {} after {}s idle", task_id, idle_secs);
+            task_ids.push(task_id);
+        }
+        
+        *state.0.lock().map_err(|e| e.to_string())? = Some(IdleSession { task_ids, idle_since });
+    } else if !is_idle {
+        let session = state.0.lock().map_err(|e| e.to_string())?.take();
+        if let Some(session) = session {
+            event_service::emit_idle_time_returned(app, event_service::IdleReturnedPayload {
+                task_ids: session.task_ids,
+                idle_since: session.idle_since,
+                returned_at: Utc::now(),
+            });
+        }
+    }
+    
+    Ok(())
+}
+
+// Keeping idle time resumes the tasks as if they were never paused;
+// discarding leaves them paused at the moment the user went idle
+pub async fn resolve_idle_time(payload: IdleResolutionData, db: &Database, app: &AppHandle) -> Result<Vec<Task>, String> {
+    let mut tasks = Vec::new();
+    
+    for task_id in payload.task_ids {
+        let task = if payload.keep {
+            task_service::resume_task(TaskRef { id: task_id }, db, app).await?
+        } else {
+            let conn = db.get_connection();
+            db::get_task_by_id(&conn, task_id)
+                .map_err(|e| format!("Failed to get task: {}", e))?
+        };
+        tasks.push(task);
+    }
+    
+    Ok(tasks)
+}
@@ -0,0 +1,178 @@
+use std::collections::HashSet;
+use std::fs;
+use std::sync::atomic::{AtomicBool, Ordering};
+use chrono::{FixedOffset, NaiveTime, Utc};
+use tauri::{AppHandle, Manager};
+use crate::db::{self, Database, insert};
+use crate::helpers::datetime::deadline_on;
+use crate::helpers::parse_date::local_day;
+use crate::importers::{self, apple_reminders, AppleRemindersImporter, ImportedTask, Importer};
+use crate::services::{event_service, settings_service};
+use crate::structs::data_export::{ImportSummary, TaskImportData};
+use crate::structs::reminder_frequency::ReminderFrequency;
+use crate::structs::task_struct::{Status, Task};
+use tracing::info;
+
+// Tasks written per transaction; keeps the DB lock short so the UI stays responsive
+const IMPORT_BATCH_SIZE: usize = 200;
+// Items between import-progress events
+const PROGRESS_INTERVAL: usize = 50;
+pub const IMPORT_CANCELLED: &str = "IMPORT_CANCELLED";
+
+// Only one import runs at a time, so a single flag is enough to cancel it
+static IMPORT_RUNNING: AtomicBool = AtomicBool::new(false);
+static CANCEL_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+// Clears the running flag however the import ends
+struct RunningImport;
+
+impl Drop for RunningImport {
+    fn drop(&mut self) {
+        IMPORT_RUNNING.store(false, Ordering::SeqCst);
+    }
+}
+
+// Counts handled items for import-progress events and checks for cancellation
+pub struct ImportProgress<'a> {
+    app: &'a AppHandle,
+    done: usize,
+    total: usize,
+}
+
+impl<'a> ImportProgress<'a> {
+    pub fn new(app: &'a AppHandle, total: usize) -> Self {
+        event_service::emit_import_progress(app, 0, total);
+        Self { app, done: 0, total }
+    }
+    
+    // Call before each item; the error makes the caller drop (roll back) its open transaction
+    pub fn advance(&mut self) -> Result<(), String> {
+        if CANCEL_REQUESTED.load(Ordering::SeqCst) {
+            return Err(IMPORT_CANCELLED.to_string());
+        }
+        
+        self.done += 1;
+        if self.done % PROGRESS_INTERVAL == 0 || self.done == self.total {
+            event_service::emit_import_progress(self.app, self.done, self.total);
+        }
+        Ok(())
+    }
+}
+
+// Run an import on a blocking thread so the command doesn't hold up the UI
+pub async fn run_in_background<T, F>(app: AppHandle, job: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce(&Database, &AppHandle) -> Result<T, String> + Send + 'static,
+{
+    if IMPORT_RUNNING.swap(true, Ordering::SeqCst) {
+        return Err("Another import is already running".to_string());
+    }
+    let running = RunningImport;
+    CANCEL_REQUESTED.store(false, Ordering::SeqCst);
+    
+    tauri::async_runtime::spawn_blocking(move || {
+        let _running = running;
+        let db = app.state::<Database>();
+        job(&db, &app)
+    })
+    .await
+    .map_err(|e| format!("Import task failed: {}", e))?
+}
+
+// Stop the running import; batches already committed stay imported
+pub fn cancel_import() -> Result<(), String> {
+    if !IMPORT_RUNNING.load(Ordering::SeqCst) {
+        return Err("No import is running".to_string());
+    }
+    
+    CANCEL_REQUESTED.store(true, Ordering::SeqCst);
+    info!("Import cancellation requested");
+    Ok(())
+}
+
+// Read another app's export and add its tasks; items imported before are skipped
+pub fn import_tasks(db: &Database, app: &AppHandle, payload: TaskImportData) -> Result<ImportSummary, String> {
+    let content = fs::read_to_string(&payload.path)
+        .map_err(|e| format!("Failed to read import file: {}", e))?;
+    
+    let importer = importers::importer_for(payload.source);
+    let tasks = importer.parse(&content)?;
+    info!("Parsed {} tasks from {} export", tasks.len(), importer.source());
+    
+    store_tasks(db, app, importer.source(), &tasks)
+}
+
+// Import every Reminders list (macOS only)
+pub fn import_apple_reminders(db: &Database, app: &AppHandle) -> Result<ImportSummary, String> {
+    let output = apple_reminders::read_reminders()?;
+    
+    let importer = AppleRemindersImporter;
+    let tasks = importer.parse(&output)?;
+    info!("Read {} reminders", tasks.len());
+    
+    store_tasks(db, app, importer.source(), &tasks)
+}
+
+// Shared import pipeline: de-duplicates by external ID and commits in batches
+pub fn store_tasks(db: &Database, app: &AppHandle, source: &str, tasks: &[ImportedTask]) -> Result<ImportSummary, String> {
+    let mut summary = ImportSummary::default();
+    let mut seen = HashSet::new();
+    let mut progress = ImportProgress::new(app, tasks.len());
+    // Before the lock: settings may be read through the same connection
+    let offset = settings_service::day_offset(db, None)?;
+    let deadline_time = settings_service::default_deadline_time(db)?;
+    let reminder_frequency = settings_service::reminder_frequency(db, None)?;
+    
+    for batch in tasks.chunks(IMPORT_BATCH_SIZE) {
+        let conn = db.get_connection();
+        let tx = conn.unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        
+        for imported in batch {
+            progress.advance()?;
+            
+            if let Some(external_id) = &imported.external_id {
+                let already_linked = db::get_task_id_by_external_id(&tx, source, external_id)
+                    .map_err(|e| format!("Failed to look up {} item {}: {}", source, external_id, e))?
+                    .is_some();
+                if already_linked || !seen.insert(external_id.clone()) {
+                    summary.skipped += 1;
+                    continue;
+                }
+            }
+            
+            let task = to_task(imported, offset, deadline_time, &reminder_frequency);
+            insert(&tx, &task)
+                .map_err(|e| format!("Failed to import '{}': {}", imported.title, e))?;
+            if let Some(external_id) = &imported.external_id {
+                db::insert_external_link(&tx, task.id.into(), source, external_id, None)
+                    .map_err(|e| format!("Failed to link '{}': {}", imported.title, e))?;
+            }
+            summary.imported += 1;
+        }
+        
+        tx.commit().map_err(|e| format!("Failed to import tasks: {}", e))?;
+    } // DB lock released between batches
+    
+    info!("Imported {} {} tasks, skipped {}", summary.imported, source, summary.skipped);
+    Ok(summary)
+}
+
+fn to_task(imported: &ImportedTask, offset: FixedOffset, deadline_time: NaiveTime, reminder_frequency: &ReminderFrequency) -> Task {
+    let created_at = imported.created_at.unwrap_or_else(Utc::now);
+    let mut task = Task::new(&imported.title, created_at, imported.notes.as_deref(), reminder_frequency.clone());
+    // All-day deadlines come as the start of the day, which has already passed once the day begins
+    task.deadline = match imported.deadline {
+        Some(deadline) if imported.all_day => deadline_on(local_day(deadline, offset), deadline_time, &offset).or(Some(deadline)),
+        deadline => deadline,
+    };
+    
+    if let Some(completed_at) = imported.completed_at {
+        task.status = Status::Completed;
+        task.completed_at = Some(completed_at);
+        task.updated_at = completed_at.max(created_at);
+    }
+    
+    task
+}
@@ -0,0 +1,99 @@
+use chrono::Utc;
+use tauri::AppHandle;
+use crate::db::{self, Database, insert};
+use crate::helpers::datetime::{parse_datetime, parse_deadline};
+use crate::services::{event_service, rule_service, settings_service};
+use crate::structs::inbox::{CaptureData, InboxItem, InboxItemRef, TriageData};
+use crate::structs::rule::RuleTrigger;
+use crate::structs::task_struct::{normalize_context, Energy, Task};
+use tracing::info;
+
+// Generous: captures are often pasted or dictated
+const MAX_CAPTURE_CHARS: usize = 10_000;
+
+// Store text as-is with no date; nothing else is asked for, so capture never waits on a decision
+pub fn capture(db: &Database, payload: CaptureData) -> Result<InboxItem, String> {
+    let text = payload.text.trim();
+    if text.is_empty() {
+        return Err("Nothing to capture".to_string());
+    }
+    if text.chars().count() > MAX_CAPTURE_CHARS {
+        return Err(format!("Capture is longer than {} characters", MAX_CAPTURE_CHARS));
+    }
+    
+    let conn = db.get_connection();
+    db::insert_inbox_item(&conn, text, Utc::now())
+        .map_err(|e| format!("Failed to capture: {}", e))
+}
+
+pub fn list_inbox(db: &Database) -> Result<Vec<InboxItem>, String> {
+    let conn = db.get_read_connection();
+    db::get_inbox_items(&conn)
+        .map_err(|e| format!("Failed to fetch inbox: {}", e))
+}
+
+// Turn a capture into a scheduled task; the capture is removed in the same transaction
+pub fn triage_inbox_item(db: &Database, app: &AppHandle, payload: TriageData) -> Result<Task, String> {
+    let fields = payload.task_fields;
+    
+    // Before the lock: settings may be read through the same connection
+    let offset = settings_service::day_offset(db, None)?;
+    let deadline_time = settings_service::default_deadline_time(db)?;
+    let reminder_frequency = settings_service::reminder_frequency(db, None)?;
+    let created_at = parse_datetime("createdAt", &fields.created_at, offset)?;
+    let deadline = fields.deadline.as_deref()
+        .map(|deadline| parse_deadline("deadline", deadline, offset, deadline_time))
+        .transpose()?;
+    let estimate_minutes = match fields.estimate_minutes {
+        Some(minutes) if minutes < 0 => return Err("Estimate can't be negative".to_string()),
+        minutes => minutes.filter(|minutes| *minutes > 0),
+    };
+    let context = fields.context.as_deref().and_then(normalize_context);
+    let energy = fields.energy.as_deref()
+        .map(str::trim)
+        .filter(|energy| !energy.is_empty())
+        .map(str::parse::<Energy>)
+        .transpose()?;
+    
+    let task = {
+        let conn = db.get_connection();
+        let tx = conn.unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        
+        let item = db::get_inbox_item(&tx, payload.id)
+            .map_err(|e| format!("Failed to get inbox item: {}", e))?
+            .ok_or_else(|| format!("No inbox item with ID {}", payload.id))?;
+        
+        let title = fields.title.as_deref().map(str::trim).filter(|title| !title.is_empty()).unwrap_or(&item.text);
+        let notes = fields.notes.as_deref().map(str::trim).filter(|notes| !notes.is_empty());
+        let mut task = Task::new(title, created_at, notes, reminder_frequency);
+        task.deadline = deadline;
+        task.estimate_minutes = estimate_minutes;
+        task.context = context;
+        task.energy = energy;
+        
+        insert(&tx, &task).map_err(|e| format!("Failed to insert task: {}", e))?;
+        db::delete_inbox_item(&tx, item.id)
+            .map_err(|e| format!("Failed to remove inbox item: {}", e))?;
+        
+        tx.commit().map_err(|e| format!("Failed to triage inbox item: {}", e))?;
+        task
+    }; // DB lock released here
+    
+    info!("Triaged inbox item {} into task {}", payload.id, task.id);
+    event_service::emit_task_created(app, &task);
+    rule_service::run_rules(app, RuleTrigger::TaskCreated, &task);
+    Ok(task)
+}
+
+// Drop a capture that isn't worth a task
+pub fn discard_inbox_item(db: &Database, payload: InboxItemRef) -> Result<(), String> {
+    let conn = db.get_connection();
+    let deleted = db::delete_inbox_item(&conn, payload.id)
+        .map_err(|e| format!("Failed to discard inbox item: {}", e))?;
+    
+    if deleted == 0 {
+        return Err(format!("No inbox item with ID {}", payload.id));
+    }
+    Ok(())
+}
@@ -0,0 +1,524 @@
+use std::collections::HashMap;
+use std::io::Read;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use chrono::{DateTime, Duration, Utc};
+use mdns_sd::{ServiceDaemon, ServiceEvent, ServiceInfo};
+use rand::Rng;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use tiny_http::{Header, Method, Request, Response, Server};
+use crate::db::{self, Database};
+use crate::helpers::crypto::{self, SecretKey};
+use crate::services::{recovery_service, sync_service};
+use crate::structs::settings::Settings;
+use crate::structs::sync::{
+    LanDevice, LanEnvelope, LanPairRequest, LanPairResponse, LanPairingCode, LanPeer, LanSnapshot,
+    LanSnapshotRequest, SyncReport,
+};
+use tracing::{info, warn, error};
+
+const SERVICE_TYPE: &str = "_myhandler-sync._tcp.local.";
+const PAIR_PATH: &str = "/pair";
+const SNAPSHOT_PATH: &str = "/snapshot";
+// No 0/O or 1/I, so a code read off the other screen is typed right
+const PAIRING_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+const PAIRING_CODE_LENGTH: usize = 10;
+const PAIRING_MINUTES: i64 = 5;
+// Wrong codes close pairing, so guessing needs a new code shown on this device
+const MAX_PAIRING_ATTEMPTS: u32 = 3;
+// Older requests are rejected as replays
+const MAX_REQUEST_AGE_SECS: i64 = 5 * 60;
+const MAX_REQUEST_BYTES: u64 = 64 * 1024;
+
+// Server, mDNS advertisement and what it has found, while LAN sync is enabled
+#[derive(Default)]
+pub struct LanSyncState {
+    running: Mutex<Option<Running>>,
+    // Devices advertising on the network, by device ID
+    discovered: Arc<Mutex<HashMap<String, Discovered>>>,
+    pairing: Mutex<Option<Pairing>>,
+}
+
+struct Running {
+    server: Arc<Server>,
+    daemon: ServiceDaemon,
+}
+
+#[derive(Clone)]
+struct Discovered {
+    name: String,
+    address: SocketAddr,
+}
+
+// Code shown on this device while another one pairs with it
+struct Pairing {
+    code: String,
+    expires_at: DateTime<Utc>,
+    failed_attempts: u32,
+}
+
+type RouteResult = Result<LanEnvelope, (u16, String)>;
+
+// Start or stop advertising and serving to match settings
+pub fn apply_settings(app: &AppHandle) -> Result<(), String> {
+    stop(app);
+    
+    // The recovery database's task list must not reach other devices
+    if recovery_service::is_active(app) {
+        return Ok(());
+    }
+    let settings = load_settings(app)?;
+    if !settings.lan_sync_enabled {
+        return Ok(());
+    }
+    
+    // Any port: peers find it through mDNS
+    let server = Arc::new(Server::http("0.0.0.0:0")
+        .map_err(|e| format!("Failed to start LAN sync server: {}", e))?);
+    let port = server.server_addr().to_ip()
+        .map(|address| address.port())
+        .ok_or_else(|| "LAN sync server has no port".to_string())?;
+    
+    let daemon = ServiceDaemon::new()
+        .map_err(|e| format!("Failed to start mDNS: {}", e))?;
+    let name = sync_service::device_name(&settings);
+    let host_name = format!("{}.local.", settings.sync_device_id);
+    let service = ServiceInfo::new(SERVICE_TYPE, &settings.sync_device_id, &host_name, "", port, &[("name", name.as_str())][..])
+        .map_err(|e| format!("Invalid mDNS service: {}", e))?
+        .enable_addr_auto();
+    daemon.register(service)
+        .map_err(|e| format!("Failed to advertise LAN sync: {}", e))?;
+    let events = daemon.browse(SERVICE_TYPE)
+        .map_err(|e| format!("Failed to browse for LAN sync devices: {}", e))?;
+    
+    let state = app.state::<LanSyncState>();
+    let discovered = state.discovered.clone();
+    let own_id = settings.sync_device_id.clone();
+    // Ends when the daemon shuts down
+    std::thread::spawn(move || {
+        while let Ok(event) = events.recv() {
+            match event {
+                ServiceEvent::ServiceResolved(info) => {
+                    let Some(device_id) = device_id_of(info.get_fullname()) else {
+                        continue;
+                    };
+                    if device_id == own_id {
+                        continue;
+                    }
+                    // Prefer IPv4: IPv6 link-local addresses need a scope to connect
+                    let addresses = info.get_addresses();
+                    let Some(ip) = addresses.iter().find(|ip| ip.is_ipv4()).or(addresses.iter().next()) else {
+                        continue;
+                    };
+                    let name = info.get_property_val_str("name").unwrap_or(&device_id).to_string();
+                    if let Ok(mut discovered) = discovered.lock() {
+                        discovered.insert(device_id, Discovered { name, address: SocketAddr::new(*ip, info.get_port()) });
+                    }
+                }
+                ServiceEvent::ServiceRemoved(_, fullname) => {
+                    if let (Some(device_id), Ok(mut discovered)) = (device_id_of(&fullname), discovered.lock()) {
+                        discovered.remove(&device_id);
+                    }
+                }
+                _ => {}
+            }
+        }
+    });
+    
+    *state.running.lock().map_err(|e| e.to_string())? = Some(Running { server: server.clone(), daemon });
+    
+    let handle = app.clone();
+    std::thread::spawn(move || {
+        for request in server.incoming_requests() {
+            handle_request(&handle, request);
+        }
+    });
+    
+    info!("LAN sync listening on port {}", port);
+    Ok(())
+}
+
+pub fn stop(app: &AppHandle) {
+    let Some(state) = app.try_state::<LanSyncState>() else {
+        return;
+    };
+    if let Ok(mut discovered) = state.discovered.lock() {
+        discovered.clear();
+    }
+    if let Ok(mut pairing) = state.pairing.lock() {
+        *pairing = None;
+    }
+    let Ok(mut running) = state.running.lock() else {
+        return;
+    };
+    if let Some(running) = running.take() {
+        running.server.unblock();
+        if let Err(e) = running.daemon.shutdown() {
+            warn!("Failed to stop mDNS: {}", e);
+        }
+        info!("LAN sync stopped");
+    }
+}
+
+// Paired devices and unpaired ones currently on the network
+pub fn get_lan_devices(app: &AppHandle) -> Result<Vec<LanDevice>, String> {
+    let peers = {
+        let db = app.state::<Database>();
+        let conn = db.get_connection();
+        db::get_lan_peers(&conn)
+            .map_err(|e| format!("Failed to fetch paired devices: {}", e))?
+    }; // DB lock released here
+    let mut discovered = discovered_devices(app)?;
+    
+    let mut devices: Vec<LanDevice> = peers.into_iter()
+        .map(|peer| LanDevice {
+            online: discovered.remove(&peer.device_id).is_some(),
+            device_id: peer.device_id,
+            name: peer.name,
+            paired: true,
+            paired_at: Some(peer.paired_at),
+            last_synced_at: peer.last_synced_at,
+        })
+        .collect();
+    devices.extend(discovered.into_iter().map(|(device_id, found)| LanDevice {
+        device_id,
+        name: found.name,
+        paired: false,
+        online: true,
+        paired_at: None,
+        last_synced_at: None,
+    }));
+    Ok(devices)
+}
+
+// Code to type on the other device; replaces any earlier one
+pub fn start_lan_pairing(app: &AppHandle) -> Result<LanPairingCode, String> {
+    let state = app.state::<LanSyncState>();
+    if state.running.lock().map_err(|e| e.to_string())?.is_none() {
+        return Err("Turn on LAN sync before pairing".to_string());
+    }
+    
+    let mut rng = rand::thread_rng();
+    let code: String = (0..PAIRING_CODE_LENGTH)
+        .map(|_| PAIRING_ALPHABET[rng.gen_range(0..PAIRING_ALPHABET.len())] as char)
+        .collect();
+    let expires_at = Utc::now() + Duration::minutes(PAIRING_MINUTES);
+    
+    *state.pairing.lock().map_err(|e| e.to_string())? = Some(Pairing {
+        code: code.clone(),
+        expires_at,
+        failed_attempts: 0,
+    });
+    Ok(LanPairingCode { code, expires_at })
+}
+
+// Pair with a device on the network using the code it shows
+pub async fn pair_lan_device(app: &AppHandle, device_id: &str, code: &str) -> Result<LanDevice, String> {
+    let settings = load_settings(app)?;
+    if !settings.lan_sync_enabled {
+        return Err("Turn on LAN sync before pairing".to_string());
+    }
+    let found = discovered_devices(app)?.remove(device_id)
+        .ok_or_else(|| "Device is not on the local network".to_string())?;
+    
+    // Deliberately slow, so kept off the async workers
+    let (code, responder_id, requester_id) = (normalize_code(code), device_id.to_string(), settings.sync_device_id.clone());
+    let key = tauri::async_runtime::spawn_blocking(move || pairing_key(&code, &responder_id, &requester_id))
+        .await
+        .map_err(|e| format!("Failed to derive pairing key: {}", e))?;
+    let secret = SecretKey::random();
+    let request = LanPairRequest {
+        device_id: settings.sync_device_id.clone(),
+        name: sync_service::device_name(&settings),
+        secret: crypto::to_hex(secret.as_bytes()),
+        sent_at: Utc::now(),
+    };
+    let envelope = post(found.address, PAIR_PATH, LanEnvelope {
+        device_id: settings.sync_device_id.clone(),
+        sealed: seal_json(&key, &request)?,
+    }).await?;
+    let response: LanPairResponse = open_json(&key, &envelope.sealed)?;
+    if response.device_id != device_id || !is_fresh(response.sent_at) {
+        return Err("Unexpected pairing response".to_string());
+    }
+    
+    let paired_at = Utc::now();
+    let db = app.state::<Database>();
+    let conn = db.get_connection();
+    db::save_lan_peer(&conn, device_id, &response.name, &request.secret, paired_at)
+        .map_err(|e| format!("Failed to save paired device: {}", e))?;
+    info!("Paired with {} over LAN", response.name);
+    
+    Ok(LanDevice {
+        device_id: device_id.to_string(),
+        name: response.name,
+        paired: true,
+        online: true,
+        paired_at: Some(paired_at),
+        last_synced_at: None,
+    })
+}
+
+// The other device rejects this one from then on, as its secret no longer matches anything here
+pub fn unpair_lan_device(app: &AppHandle, device_id: &str) -> Result<(), String> {
+    let db = app.state::<Database>();
+    let conn = db.get_connection();
+    let removed = db::delete_lan_peer(&conn, device_id)
+        .map_err(|e| format!("Failed to unpair device: {}", e))?;
+    if removed == 0 {
+        return Err(format!("Device {} is not paired", device_id));
+    }
+    Ok(())
+}
+
+// Merge the task lists of paired devices that are online; each device pulls for itself
+pub async fn sync_lan_now(app: &AppHandle) -> Result<SyncReport, String> {
+    let settings = load_settings(app)?;
+    if !settings.lan_sync_enabled || recovery_service::is_active(app) {
+        return Ok(SyncReport::default());
+    }
+    let peers = {
+        let db = app.state::<Database>();
+        let conn = db.get_connection();
+        db::get_lan_peers(&conn)
+            .map_err(|e| format!("Failed to fetch paired devices: {}", e))?
+    }; // DB lock released here
+    let discovered = discovered_devices(app)?;
+    
+    let mut report = SyncReport::default();
+    for peer in peers {
+        let Some(found) = discovered.get(&peer.device_id) else {
+            continue;
+        };
+        // One unreachable device shouldn't hold up the rest
+        match pull_from(app, &settings.sync_device_id, &peer, found.address).await {
+            Ok(pulled) => {
+                report.applied += pulled.applied;
+                report.skipped += pulled.skipped;
+                report.conflicts += pulled.conflicts;
+            }
+            Err(e) => warn!("LAN sync with {} failed: {}", peer.name, e),
+        }
+    }
+    Ok(report)
+}
+
+async fn pull_from(app: &AppHandle, own_id: &str, peer: &LanPeer, address: SocketAddr) -> Result<SyncReport, String> {
+    let key = peer_key(peer)?;
+    let request = LanSnapshotRequest {
+        device_id: own_id.to_string(),
+        sent_at: Utc::now(),
+    };
+    let envelope = post(address, SNAPSHOT_PATH, LanEnvelope {
+        device_id: own_id.to_string(),
+        sealed: seal_json(&key, &request)?,
+    }).await?;
+    let snapshot: LanSnapshot = open_json(&key, &envelope.sealed)?;
+    if snapshot.device_id != peer.device_id || !is_fresh(snapshot.sent_at) {
+        return Err("Unexpected snapshot".to_string());
+    }
+    
+    let report = sync_service::merge_snapshot(app, peer.device_id.clone(), snapshot.records)?;
+    
+    let db = app.state::<Database>();
+    let conn = db.get_connection();
+    db::set_lan_peer_synced(&conn, &peer.device_id, Utc::now())
+        .map_err(|e| format!("Failed to save sync time: {}", e))?;
+    Ok(report)
+}
+
+fn handle_request(app: &AppHandle, mut request: Request) {
+    let result = if *request.method() != Method::Post {
+        Err((405, "Only POST is supported".to_string()))
+    } else if app.try_state::<Database>().is_some_and(|db| db.is_locked()) {
+        // Notes can't be read or sealed until the app is unlocked
+        Err((423, "App is locked".to_string()))
+    } else {
+        let path = request.url().split('?').next().unwrap_or_default().to_string();
+        match read_envelope(&mut request) {
+            Ok(envelope) if path == PAIR_PATH => pair_incoming(app, envelope),
+            Ok(envelope) if path == SNAPSHOT_PATH => snapshot_incoming(app, envelope),
+            Ok(_) => Err((404, format!("No route for {}", path))),
+            Err(e) => Err(e),
+        }
+    };
+    
+    let (status, body) = match result {
+        Ok(envelope) => (200, serde_json::to_string(&envelope).unwrap_or_default()),
+        Err((status, message)) => {
+            warn!("LAN sync request from {:?} -> {}: {}", request.remote_addr(), status, message);
+            (status, serde_json::json!({ "error": message }).to_string())
+        }
+    };
+    let response = Response::from_string(body)
+        .with_status_code(status)
+        .with_header(Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).unwrap());
+    if let Err(e) = request.respond(response) {
+        error!("Failed to send LAN sync response: {}", e);
+    }
+}
+
+fn pair_incoming(app: &AppHandle, envelope: LanEnvelope) -> RouteResult {
+    let state = app.state::<LanSyncState>();
+    let code = match &*state.pairing.lock().map_err(|e| (500, e.to_string()))? {
+        Some(pairing) if pairing.expires_at > Utc::now() => pairing.code.clone(),
+        _ => return Err((403, "Not pairing; show a pairing code first".to_string())),
+    };
+    let settings = load_settings(app).map_err(|e| (500, e))?;
+    
+    let key = pairing_key(&code, &settings.sync_device_id, &envelope.device_id);
+    let request: LanPairRequest = match open_json(&key, &envelope.sealed) {
+        Ok(request) => request,
+        Err(_) => {
+            if let Ok(mut pairing) = state.pairing.lock() {
+                let closed = pairing.as_mut().map_or(true, |pairing| {
+                    pairing.failed_attempts += 1;
+                    pairing.failed_attempts >= MAX_PAIRING_ATTEMPTS
+                });
+                if closed {
+                    *pairing = None;
+                }
+            }
+            return Err((403, "Wrong pairing code".to_string()));
+        }
+    };
+    if request.device_id != envelope.device_id || !is_fresh(request.sent_at) {
+        return Err((400, "Invalid pairing request".to_string()));
+    }
+    let secret = crypto::from_hex(&request.secret).map_err(|e| (400, e))?;
+    SecretKey::from_bytes(&secret).map_err(|e| (400, e))?;
+    
+    {
+        let db = app.state::<Database>();
+        let conn = db.get_connection();
+        db::save_lan_peer(&conn, &request.device_id, &request.name, &request.secret, Utc::now())
+            .map_err(|e| (500, format!("Failed to save paired device: {}", e)))?;
+    } // DB lock released here
+    // A code pairs one device
+    if let Ok(mut pairing) = state.pairing.lock() {
+        *pairing = None;
+    }
+    info!("Paired with {} over LAN", request.name);
+    
+    let response = LanPairResponse {
+        device_id: settings.sync_device_id.clone(),
+        name: sync_service::device_name(&settings),
+        sent_at: Utc::now(),
+    };
+    Ok(LanEnvelope {
+        device_id: settings.sync_device_id,
+        sealed: seal_json(&key, &response).map_err(|e| (500, e))?,
+    })
+}
+
+fn snapshot_incoming(app: &AppHandle, envelope: LanEnvelope) -> RouteResult {
+    let db = app.state::<Database>();
+    let peer = {
+        let conn = db.get_connection();
+        db::get_lan_peer(&conn, &envelope.device_id)
+            .map_err(|e| (500, format!("Failed to fetch paired device: {}", e)))?
+    }; // DB lock released here
+    let peer = peer.ok_or_else(|| (403, "Device is not paired".to_string()))?;
+    
+    let key = peer_key(&peer).map_err(|e| (500, e))?;
+    let request: LanSnapshotRequest = open_json(&key, &envelope.sealed).map_err(|e| (403, e))?;
+    if request.device_id != peer.device_id || !is_fresh(request.sent_at) {
+        return Err((403, "Stale or mismatched snapshot request".to_string()));
+    }
+    
+    let settings = load_settings(app).map_err(|e| (500, e))?;
+    let snapshot = LanSnapshot {
+        device_id: settings.sync_device_id.clone(),
+        name: sync_service::device_name(&settings),
+        records: sync_service::snapshot_records(&db).map_err(|e| (500, e))?,
+        sent_at: Utc::now(),
+    };
+    Ok(LanEnvelope {
+        device_id: settings.sync_device_id,
+        sealed: seal_json(&key, &snapshot).map_err(|e| (500, e))?,
+    })
+}
+
+fn read_envelope(request: &mut Request) -> Result<LanEnvelope, (u16, String)> {
+    let mut body = String::new();
+    request.as_reader()
+        .take(MAX_REQUEST_BYTES)
+        .read_to_string(&mut body)
+        .map_err(|e| (400, format!("Failed to read body: {}", e)))?;
+    serde_json::from_str(&body).map_err(|e| (400, format!("Invalid JSON body: {}", e)))
+}
+
+async fn post(address: SocketAddr, path: &str, envelope: LanEnvelope) -> Result<LanEnvelope, String> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .user_agent("MyHandler")
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+    let response = client.post(format!("http://{}{}", address, path))
+        .json(&envelope)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach {}: {}", address, e))?;
+    
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("{} answered {}: {}", address, status, body));
+    }
+    response.json().await
+        .map_err(|e| format!("Invalid response from {}: {}", address, e))
+}
+
+// Both devices derive it from the code; the device IDs keep one code from fitting any other pair
+fn pairing_key(code: &str, responder_id: &str, requester_id: &str) -> SecretKey {
+    let salt = format!("myhandler-lan-pair:{}:{}", responder_id, requester_id);
+    crypto::derive_key(code, salt.as_bytes())
+}
+
+fn peer_key(peer: &LanPeer) -> Result<SecretKey, String> {
+    SecretKey::from_bytes(&crypto::from_hex(&peer.secret)?)
+}
+
+fn seal_json<T: Serialize>(key: &SecretKey, value: &T) -> Result<String, String> {
+    let json = serde_json::to_vec(value)
+        .map_err(|e| format!("Failed to serialize LAN sync data: {}", e))?;
+    Ok(crypto::to_hex(&crypto::seal(key, &json)?))
+}
+
+fn open_json<T: DeserializeOwned>(key: &SecretKey, sealed: &str) -> Result<T, String> {
+    let json = crypto::open(key, &crypto::from_hex(sealed)?)?;
+    serde_json::from_slice(&json)
+        .map_err(|e| format!("Invalid LAN sync data: {}", e))
+}
+
+fn is_fresh(sent_at: DateTime<Utc>) -> bool {
+    (Utc::now() - sent_at).num_seconds().abs() <= MAX_REQUEST_AGE_SECS
+}
+
+// Codes are shown grouped and upper case; accept them typed either way
+fn normalize_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+// Instance name of "<device id>._myhandler-sync._tcp.local."
+fn device_id_of(fullname: &str) -> Option<String> {
+    fullname.strip_suffix(SERVICE_TYPE)?.strip_suffix('.').map(str::to_string)
+}
+
+fn discovered_devices(app: &AppHandle) -> Result<HashMap<String, Discovered>, String> {
+    let state = app.state::<LanSyncState>();
+    let discovered = state.discovered.lock().map_err(|e| e.to_string())?;
+    Ok(discovered.clone())
+}
+
+fn load_settings(app: &AppHandle) -> Result<Settings, String> {
+    let db = app.try_state::<Database>()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    db.settings()
+        .map_err(|e| format!("Failed to fetch settings: {}", e))
+}
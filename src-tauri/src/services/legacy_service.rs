@@ -0,0 +1,100 @@
+use std::collections::HashSet;
+use std::sync::{Mutex, OnceLock};
+use chrono::{Duration, NaiveDate, NaiveTime, Utc};
+use crate::db::{self, Database};
+use crate::helpers::like::escape_like;
+use crate::helpers::parse_date::local_day;
+use crate::services::settings_service;
+use crate::structs::legacy::{DateSection, LegacySearchQuery};
+use crate::structs::task_struct::Task;
+use tracing::warn;
+
+// Commands from builds that registered everything in main.rs, answered for frontends still built
+// against those invoke names. They adapt the old payloads and results around the same queries the
+// commands modules use; nothing new should call them.
+
+// Bumped when an alias is added or dropped, or changes what it returns; reported in app info
+pub const LEGACY_API_VERSION: u32 = 1;
+
+const SEARCH_LIMIT: i64 = 100;
+const COMPLETED_LIMIT: i64 = 500;
+
+static REPORTED: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+
+// The old app tracked one task at a time; with several going, the one started first
+pub fn get_ongoing_task(db: &Database) -> Result<Option<Task>, String> {
+    deprecated("get_ongoing_task");
+    let conn = db.get_read_connection();
+    db::get_ongoing_tasks(&conn)
+        .map(|tasks| tasks.into_iter().next())
+        .map_err(|e| format!("Failed to query tasks: {}", e))
+}
+
+pub fn search_tasks(db: &Database, payload: LegacySearchQuery) -> Result<Vec<Task>, String> {
+    deprecated("search_tasks");
+    let query = payload.query.trim();
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+    
+    let pattern = format!("%{}%", escape_like(query));
+    let conn = db.get_read_connection();
+    db::search_tasks(&conn, &pattern, SEARCH_LIMIT)
+        .map_err(|e| format!("Failed to search tasks: {}", e))
+}
+
+pub fn get_completed_tasks(db: &Database) -> Result<Vec<Task>, String> {
+    deprecated("get_completed_tasks");
+    let conn = db.get_read_connection();
+    db::get_completed_tasks(&conn, COMPLETED_LIMIT)
+        .map_err(|e| format!("Failed to query tasks: {}", e))
+}
+
+// Sent as the start of each day, which the old frontend parses into a Date
+pub fn get_all_dates_with_tasks(db: &Database) -> Result<Vec<String>, String> {
+    deprecated("get_all_dates_with_tasks");
+    Ok(date_sections(db)?.into_iter().map(|section| section.date.to_rfc3339()).collect())
+}
+
+pub fn get_date_sections(db: &Database) -> Result<Vec<DateSection>, String> {
+    deprecated("get_date_sections");
+    date_sections(db)
+}
+
+// Days with tasks, newest first, in the settings offset like the day views
+fn date_sections(db: &Database) -> Result<Vec<DateSection>, String> {
+    let offset = settings_service::day_offset(db, None)?;
+    let formatter = settings_service::formatter(db)?;
+    let created = {
+        let conn = db.get_read_connection();
+        db::get_task_created_times(&conn)
+            .map_err(|e| format!("Failed to query tasks: {}", e))?
+    }; // DB lock released here
+    
+    let today = local_day(Utc::now(), offset);
+    let mut days: Vec<NaiveDate> = created.into_iter().map(|at| local_day(at, offset)).collect();
+    days.dedup();
+    
+    days.into_iter()
+        .map(|day| {
+            let date = day.and_time(NaiveTime::MIN)
+                .and_local_timezone(offset)
+                .single()
+                .ok_or_else(|| format!("Invalid day {}", day))?;
+            Ok(DateSection {
+                date,
+                label: formatter.long_date(day),
+                is_today: day == today,
+                is_yesterday: day == today - Duration::days(1),
+            })
+        })
+        .collect()
+}
+
+// Logged once per command per run, so it shows which old frontend is still in use
+fn deprecated(command: &'static str) {
+    let mut reported = REPORTED.get_or_init(Default::default).lock().unwrap_or_else(|e| e.into_inner());
+    if reported.insert(command) {
+        warn!("Legacy command {} called; it's only kept for older frontends", command);
+    }
+}
@@ -0,0 +1,230 @@
+use chrono::Utc;
+use tauri::{AppHandle, Manager};
+use crate::db::{self, Database};
+use crate::helpers::crypto::{self, SecretKey};
+use crate::helpers::idle_time;
+use crate::secrets;
+use crate::services::event_service;
+use crate::structs::app_lock::{AppLock, AppLockData, LockStatus, UnlockData};
+use tracing::{error, info, warn};
+
+const MIN_PIN_LENGTH: usize = 4;
+const MAX_IDLE_MINUTES: u32 = 24 * 60;
+
+// What the lock screen calls; every other command is rejected while the app is locked
+pub const LOCKED_COMMANDS: [&str; 5] = ["get_lock_status", "unlock_app", "get_theme", "get_startup_phase", "get_recovery_status"];
+
+// The app starts locked when a lock is set; called once the database is open, and again after it's replaced
+pub fn init_app_lock(app: &AppHandle) {
+    let Some(db) = app.try_state::<Database>() else {
+        return;
+    };
+    let lock = {
+        let conn = db.get_connection();
+        db::get_app_lock(&conn)
+    }; // DB lock released here
+    
+    db.set_sealing_key(None);
+    match lock {
+        Ok(lock) => db.set_lock_enabled(lock.is_some()),
+        Err(e) => error!("Failed to read app lock: {}", e),
+    }
+}
+
+pub fn get_lock_status(db: &Database) -> Result<LockStatus, String> {
+    let lock = load_lock(db)?;
+    Ok(LockStatus {
+        enabled: lock.is_some(),
+        locked: db.is_locked(),
+        idle_minutes: lock.map(|lock| lock.idle_minutes),
+    })
+}
+
+// Set a PIN, or change it and the idle time; notes and credentials are sealed with a key derived from it
+pub async fn set_app_lock(app: &AppHandle, payload: AppLockData) -> Result<LockStatus, String> {
+    let db = app.try_state::<Database>()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    if payload.pin.chars().count() < MIN_PIN_LENGTH {
+        return Err(format!("PIN must be at least {} characters", MIN_PIN_LENGTH));
+    }
+    if payload.idle_minutes > MAX_IDLE_MINUTES {
+        return Err(format!("Idle time can't be more than {} minutes", MAX_IDLE_MINUTES));
+    }
+    if db.is_locked() {
+        return Err("Unlock the app first".to_string());
+    }
+    
+    let existing = load_lock(&db)?;
+    if let Some(existing) = &existing {
+        let current_pin = payload.current_pin.ok_or_else(|| "Enter the current PIN to change the lock".to_string())?;
+        if !check_pin(current_pin, existing.pin_hash.clone()).await? {
+            return Err("Wrong PIN".to_string());
+        }
+    }
+    
+    let pin = payload.pin;
+    let (pin_hash, key_salt, key) = tauri::async_runtime::spawn_blocking(move || -> Result<_, String> {
+        let salt = crypto::random_salt();
+        Ok((crypto::hash_pin(&pin)?, crypto::to_hex(&salt), crypto::derive_pin_key(&pin, &salt)?))
+    })
+        .await
+        .map_err(|e| format!("Failed to set the PIN: {}", e))??;
+    
+    let had_lock = existing.is_some();
+    let lock = AppLock {
+        pin_hash,
+        key_salt,
+        idle_minutes: payload.idle_minutes,
+        created_at: existing.map_or_else(Utc::now, |lock| lock.created_at),
+    };
+    
+    {
+        let conn = db.get_connection();
+        let tx = conn.unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        
+        // Values sealed under the old PIN are opened with its key before the new one seals them
+        if had_lock {
+            db::open_sealed_columns(&tx)
+                .map_err(|e| format!("Failed to open sealed values: {}", e))?;
+        }
+        // Swapped while the connection is held, so no write is sealed with the wrong key
+        let previous = db.set_sealing_key(Some(key));
+        let result = db::seal_columns(&tx)
+            .and_then(|_| db::save_app_lock(&tx, &lock))
+            .and_then(|_| tx.commit());
+        if let Err(e) = result {
+            db.restore_sealing_key(previous);
+            return Err(format!("Failed to set the app lock: {}", e));
+        }
+    } // DB lock released here
+    db.set_lock_enabled(true);
+    
+    info!("App lock {}", if had_lock { "changed" } else { "set" });
+    changed(app, &db)
+}
+
+// Remove the lock and store notes and credentials in plain text again
+pub async fn disable_app_lock(app: &AppHandle, payload: UnlockData) -> Result<LockStatus, String> {
+    let db = app.try_state::<Database>()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    let lock = load_lock(&db)?
+        .ok_or_else(|| "The app has no lock".to_string())?;
+    if db.is_locked() {
+        return Err("Unlock the app first".to_string());
+    }
+    if !check_pin(payload.pin, lock.pin_hash).await? {
+        return Err("Wrong PIN".to_string());
+    }
+    
+    {
+        let conn = db.get_connection();
+        let tx = conn.unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        db::open_sealed_columns(&tx)
+            .and_then(|_| db::delete_app_lock(&tx))
+            .and_then(|_| tx.commit())
+            .map_err(|e| format!("Failed to remove the app lock: {}", e))?;
+        db.set_sealing_key(None);
+        db.set_lock_enabled(false);
+    } // DB lock released here
+    
+    info!("App lock removed");
+    changed(app, &db)
+}
+
+pub async fn unlock_app(app: &AppHandle, payload: UnlockData) -> Result<LockStatus, String> {
+    let db = app.try_state::<Database>()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    let lock = load_lock(&db)?
+        .ok_or_else(|| "The app has no lock".to_string())?;
+    if !db.is_locked() {
+        return get_lock_status(&db);
+    }
+    
+    let pin = payload.pin;
+    let key = tauri::async_runtime::spawn_blocking(move || -> Result<Option<SecretKey>, String> {
+        if !crypto::verify_pin(&pin, &lock.pin_hash)? {
+            return Ok(None);
+        }
+        crypto::derive_pin_key(&pin, &crypto::from_hex(&lock.key_salt)?).map(Some)
+    })
+        .await
+        .map_err(|e| format!("Failed to check the PIN: {}", e))??;
+    let Some(key) = key else {
+        warn!("Unlock attempt with a wrong PIN");
+        return Err("Wrong PIN".to_string());
+    };
+    
+    {
+        let conn = db.get_connection();
+        db.set_sealing_key(Some(key));
+        // Writes made while locked (CLI, deep links) couldn't be sealed then
+        let tx = conn.unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        db::seal_columns(&tx)
+            .and_then(|_| tx.commit())
+            .map_err(|e| format!("Failed to seal notes and credentials: {}", e))?;
+        
+        // Credentials sealed before the secrets store can only move now that they open
+        if let Err(e) = secrets::move_legacy(&conn) {
+            warn!("Failed to move secrets into the secrets store: {}", e);
+        }
+    } // DB lock released here
+    
+    info!("App unlocked");
+    changed(app, &db)
+}
+
+pub fn lock_app(app: &AppHandle) -> Result<LockStatus, String> {
+    let db = app.try_state::<Database>()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    if load_lock(&db)?.is_none() {
+        return Err("The app has no lock".to_string());
+    }
+    
+    db.set_sealing_key(None);
+    info!("App locked");
+    changed(app, &db)
+}
+
+// Auto-lock job: lock once there's been no input for the lock's idle time
+pub fn lock_if_idle(app: &AppHandle) -> Result<(), String> {
+    let db = app.try_state::<Database>()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    if db.is_locked() {
+        return Ok(());
+    }
+    let Some(lock) = load_lock(&db)? else {
+        return Ok(());
+    };
+    
+    // Unsupported platform or missing helper tool: only locking by hand works
+    let Some(idle_secs) = idle_time::get_idle_seconds() else {
+        return Ok(());
+    };
+    if lock.idle_minutes > 0 && idle_secs >= u64::from(lock.idle_minutes) * 60 {
+        info!("Locking after {}s idle", idle_secs);
+        lock_app(app)?;
+    }
+    Ok(())
+}
+
+fn load_lock(db: &Database) -> Result<Option<AppLock>, String> {
+    let conn = db.get_read_connection();
+    db::get_app_lock(&conn)
+        .map_err(|e| format!("Failed to read app lock: {}", e))
+}
+
+// Argon2 is slow on purpose, so kept off the async workers
+async fn check_pin(pin: String, pin_hash: String) -> Result<bool, String> {
+    tauri::async_runtime::spawn_blocking(move || crypto::verify_pin(&pin, &pin_hash))
+        .await
+        .map_err(|e| format!("Failed to check the PIN: {}", e))?
+}
+
+fn changed(app: &AppHandle, db: &Database) -> Result<LockStatus, String> {
+    let status = get_lock_status(db)?;
+    event_service::emit_app_lock_changed(app, &status);
+    Ok(status)
+}
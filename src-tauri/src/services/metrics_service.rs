@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+use std::fs;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use chrono::{Duration, Utc};
+use tauri::{AppHandle, Manager};
+use crate::db::{self, Database};
+use crate::services::recovery_service;
+use crate::structs::metrics::{MetricKind, UsageMetricCount, UsageMetrics, UsageMetricsExportData, UsageMetricsQuery};
+use tracing::{info, warn};
+
+// Mirrors the usage_metrics_enabled setting; nothing is counted while it is off
+static ENABLED: AtomicBool = AtomicBool::new(false);
+// Counts not yet written to the database, flushed by the scheduler and on exit
+static PENDING: OnceLock<Mutex<HashMap<(MetricKind, &'static str), i64>>> = OnceLock::new();
+
+// Load the opt-in setting; the recovery database never collects
+pub fn init_metrics(app: &AppHandle) {
+    if recovery_service::is_active(app) {
+        return;
+    }
+    let Some(db) = app.try_state::<Database>() else {
+        return;
+    };
+    match db.settings() {
+        Ok(settings) => set_enabled(settings.usage_metrics_enabled),
+        Err(e) => warn!("Failed to load usage metrics setting: {}", e),
+    }
+}
+
+pub fn set_enabled(enabled: bool) {
+    let was_enabled = ENABLED.swap(enabled, Ordering::SeqCst);
+    // Turning it off drops anything counted but not yet stored
+    if was_enabled && !enabled {
+        if let Ok(mut pending) = pending().lock() {
+            pending.clear();
+        }
+    }
+}
+
+pub fn record_command(name: &'static str) {
+    record(MetricKind::Command, name);
+}
+
+pub fn record_feature(name: &'static str) {
+    record(MetricKind::Feature, name);
+}
+
+fn record(kind: MetricKind, name: &'static str) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    if let Ok(mut pending) = pending().lock() {
+        *pending.entry((kind, name)).or_default() += 1;
+    }
+}
+
+// Add pending counts to today's rows
+pub fn flush(db: &Database) -> Result<(), String> {
+    let counts: Vec<((MetricKind, &'static str), i64)> = match pending().lock() {
+        Ok(mut pending) => pending.drain().collect(),
+        Err(_) => return Ok(()),
+    };
+    if counts.is_empty() {
+        return Ok(());
+    }
+    
+    let day = Utc::now().format("%Y-%m-%d").to_string();
+    let conn = db.get_connection();
+    let tx = conn.unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    for ((kind, name), count) in counts {
+        db::add_usage_metric(&tx, &day, kind.as_str(), name, count)
+            .map_err(|e| format!("Failed to store usage metrics: {}", e))?;
+    }
+    tx.commit().map_err(|e| format!("Failed to store usage metrics: {}", e))
+}
+
+// Totals over the last `days` days, today included
+pub fn get_usage_metrics(db: &Database, payload: UsageMetricsQuery) -> Result<UsageMetrics, String> {
+    if payload.days == 0 {
+        return Err("Days must be at least 1".to_string());
+    }
+    flush(db)?;
+    
+    let since = (Utc::now() - Duration::days(payload.days as i64 - 1)).format("%Y-%m-%d").to_string();
+    let totals = {
+        let conn = db.get_connection();
+        db::get_usage_metric_totals(&conn, &since)
+            .map_err(|e| format!("Failed to read usage metrics: {}", e))?
+    }; // DB lock released here
+    
+    let mut metrics = UsageMetrics {
+        enabled: ENABLED.load(Ordering::SeqCst),
+        commands: Vec::new(),
+        features: Vec::new(),
+    };
+    for total in totals {
+        let count = UsageMetricCount { name: total.name, count: total.count };
+        if total.kind == MetricKind::Feature.as_str() {
+            metrics.features.push(count);
+        } else {
+            metrics.commands.push(count);
+        }
+    }
+    
+    Ok(metrics)
+}
+
+// Write every stored day to a JSON file the user picked; nothing is ever sent anywhere
+pub fn export_usage_metrics(db: &Database, payload: UsageMetricsExportData) -> Result<usize, String> {
+    flush(db)?;
+    
+    let rows = {
+        let conn = db.get_connection();
+        db::get_usage_metrics(&conn)
+            .map_err(|e| format!("Failed to read usage metrics: {}", e))?
+    }; // DB lock released here
+    
+    let json = serde_json::to_string_pretty(&rows)
+        .map_err(|e| format!("Failed to serialize usage metrics: {}", e))?;
+    fs::write(&payload.path, json)
+        .map_err(|e| format!("Failed to write usage metrics file: {}", e))?;
+    
+    info!("Exported {} usage metric rows to {}", rows.len(), payload.path);
+    Ok(rows.len())
+}
+
+fn pending() -> &'static Mutex<HashMap<(MetricKind, &'static str), i64>> {
+    PENDING.get_or_init(Default::default)
+}
@@ -1,3 +1,45 @@
 pub mod task_service;
 pub mod settings_service;
 pub mod calendar_service;
+pub mod notification_service;
+pub mod event_service;
+pub mod scheduler_service;
+pub mod diagnostics_service;
+pub mod idle_service;
+pub mod app_info_service;
+pub mod recovery_service;
+pub mod network_permission_service;
+pub mod calendar_journal_service;
+pub mod data_service;
+pub mod export_service;
+pub mod github_service;
+pub mod import_service;
+pub mod snapshot_service;
+pub mod vault_service;
+pub mod slack_service;
+pub mod agenda_service;
+pub mod stats_service;
+pub mod goal_service;
+pub mod analytics;
+pub mod metrics_service;
+pub mod sync_service;
+pub mod sync_backend;
+pub mod lan_sync_service;
+pub mod changelog_service;
+pub mod webhook_service;
+pub mod rule_service;
+pub mod inbox_service;
+pub mod schedule_service;
+pub mod query_service;
+pub mod holiday_service;
+pub mod lock_service;
+pub mod project_service;
+pub mod work_session_service;
+pub mod deadline_service;
+pub mod remembered_date_service;
+pub mod connectivity_service;
+pub mod legacy_service;
+pub mod tag_service;
+pub mod dependency_service;
+pub mod calendar_rule_service;
+pub mod recurrence_service;
@@ -0,0 +1,37 @@
+use crate::db::{self, Database};
+use crate::structs::network::{NetworkFeature, NetworkPermissions, NetworkPermissionsUpdate};
+use tracing::{info, warn};
+
+// Returned instead of making the call; the UI asks the user and retries
+pub const CONSENT_REQUIRED: &str = "NETWORK_CONSENT_REQUIRED";
+pub const PERMISSION_DENIED: &str = "NETWORK_PERMISSION_DENIED";
+
+pub fn get_network_permissions(db: &Database) -> Result<NetworkPermissions, String> {
+    let conn = db.get_connection();
+    
+    db::get_network_permissions(&conn)
+        .map_err(|e| format!("Failed to fetch network permissions: {}", e))
+}
+
+pub fn set_network_permissions(db: &Database, data: NetworkPermissionsUpdate) -> Result<NetworkPermissions, String> {
+    let conn = db.get_connection();
+    
+    db::update_settings(&conn, &data)
+        .map_err(|e| format!("Failed to update network permissions: {}", e))?;
+    info!("Network permissions updated: {:?}", data);
+    
+    db::get_network_permissions(&conn)
+        .map_err(|e| format!("Failed to fetch network permissions: {}", e))
+}
+
+// Call before any outbound request a feature makes
+pub fn ensure_allowed(db: &Database, feature: NetworkFeature) -> Result<(), String> {
+    match get_network_permissions(db)?.get(feature) {
+        Some(true) => Ok(()),
+        Some(false) => {
+            warn!("Blocked network call for '{}': permission denied", feature.name());
+            Err(PERMISSION_DENIED.to_string())
+        }
+        None => Err(CONSENT_REQUIRED.to_string()),
+    }
+}
@@ -0,0 +1,282 @@
+use chrono::{Duration, Local, Utc};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+use crate::db::{self, Database};
+use crate::helpers::format::Formatter;
+use crate::helpers::parse_date::local_day;
+use crate::helpers::reminder_plan;
+use crate::services::{event_service, remembered_date_service, settings_service, task_service};
+use crate::services::event_service::EstimateReachedPayload;
+use crate::structs::dto::DateQuery;
+use crate::structs::task_struct::{Task, TaskId};
+use tracing::error;
+
+// How far ahead of a deadline the "due soon" notification fires
+const DUE_SOON_WINDOW_MINUTES: i64 = 15;
+// A reminder missed by more than this (app closed, machine asleep) is skipped rather than shown late
+const REMINDER_GRACE_MINUTES: i64 = 30;
+// Completed task titles named in the end-of-day notification
+const SUMMARY_HIGHLIGHTS: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NotificationKind {
+    DueSoon,
+    Overdue,
+    PomodoroFinished,
+    EstimateReached,
+    Reminder,
+}
+
+impl NotificationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationKind::DueSoon => "due-soon",
+            NotificationKind::Overdue => "overdue",
+            NotificationKind::PomodoroFinished => "pomodoro-finished",
+            NotificationKind::EstimateReached => "estimate-reached",
+            NotificationKind::Reminder => "reminder",
+        }
+    }
+}
+
+// Notify about tasks whose deadline is approaching or has passed (scheduler job)
+pub fn check_deadlines(app: &AppHandle) -> Result<(), String> {
+    let db = match app.try_state::<Database>() {
+        Some(db) => db,
+        None => return Ok(()), // Database not initialized, nothing to check
+    };
+    
+    let settings = db.settings()
+        .map_err(|e| format!("Failed to fetch settings: {}", e))?;
+    if !settings.notifications_enabled {
+        return Ok(());
+    }
+    let formatter = Formatter::new(settings.locale);
+    
+    let conn = db.get_connection();
+    let now = Utc::now();
+    let cutoff = now + Duration::minutes(DUE_SOON_WINDOW_MINUTES);
+    let tasks = db::get_tasks_due_for_notification(&conn, cutoff)
+        .map_err(|e| format!("Failed to query due tasks: {}", e))?;
+    
+    for task in tasks {
+        let deadline = match task.deadline {
+            Some(deadline) => deadline,
+            None => continue,
+        };
+        let kind = if deadline <= now {
+            NotificationKind::Overdue
+        } else {
+            NotificationKind::DueSoon
+        };
+        
+        let task_id = TaskId::from(task.id);
+        let already_sent = db::has_task_notification(&conn, task_id, kind.as_str())
+            .map_err(|e| format!("Failed to check notification history: {}", e))?;
+        if already_sent {
+            continue;
+        }
+        
+        show_task_notification(app, &task, kind, formatter);
+        db::record_task_notification(&conn, task_id, kind.as_str())
+            .map_err(|e| format!("Failed to record notification: {}", e))?;
+    }
+    
+    // Reminders on each task's frequency, at the same times its calendar event would remind
+    let tasks = db::get_tasks_with_reminders(&conn, now)
+        .map_err(|e| format!("Failed to query task reminders: {}", e))?;
+    let since = now - Duration::minutes(REMINDER_GRACE_MINUTES);
+    for task in tasks {
+        let Some(deadline) = task.deadline else {
+            continue;
+        };
+        // Latest reminder time that has come; an earlier one missed in the same check isn't worth a second popup
+        let Some(minutes) = reminder_plan::reminder_minutes(&task.reminder_frequency, since, deadline, usize::MAX)
+            .into_iter()
+            .find(|minutes| deadline - Duration::minutes(*minutes) <= now)
+        else {
+            continue;
+        };
+        
+        let task_id = TaskId::from(task.id);
+        let key = format!("{}:{}", NotificationKind::Reminder.as_str(), minutes);
+        let already_sent = db::has_task_notification(&conn, task_id, &key)
+            .map_err(|e| format!("Failed to check notification history: {}", e))?;
+        if already_sent {
+            continue;
+        }
+        
+        show_task_notification(app, &task, NotificationKind::Reminder, formatter);
+        db::record_task_notification(&conn, task_id, &key)
+            .map_err(|e| format!("Failed to record notification: {}", e))?;
+    }
+    
+    Ok(())
+}
+
+// Running tasks whose tracked time reached the estimate threshold in settings (scheduler job): the UI
+// is told once per task so it can suggest completing it; a notification is optional
+pub fn check_estimates(app: &AppHandle) -> Result<(), String> {
+    let Some(db) = app.try_state::<Database>() else {
+        return Ok(());
+    };
+    
+    let settings = db.settings()
+        .map_err(|e| format!("Failed to fetch settings: {}", e))?;
+    if settings.estimate_reached_percent == 0 {
+        return Ok(());
+    }
+    let kind = NotificationKind::EstimateReached;
+    
+    let reached = {
+        let conn = db.get_connection();
+        let tasks = db::get_tasks_past_estimate(&conn, Utc::now(), settings.estimate_reached_percent)
+            .map_err(|e| format!("Failed to query tracked time: {}", e))?;
+        
+        let mut reached = Vec::new();
+        for (task, tracked_minutes) in tasks {
+            let task_id = TaskId::from(task.id);
+            let already_sent = db::has_task_notification(&conn, task_id, kind.as_str())
+                .map_err(|e| format!("Failed to check notification history: {}", e))?;
+            if already_sent {
+                continue;
+            }
+            db::record_task_notification(&conn, task_id, kind.as_str())
+                .map_err(|e| format!("Failed to record notification: {}", e))?;
+            reached.push(EstimateReachedPayload { task, tracked_minutes });
+        }
+        reached
+    }; // DB lock released here
+    
+    let notify = settings.notifications_enabled && settings.estimate_reached_notification;
+    let formatter = Formatter::new(settings.locale);
+    for payload in &reached {
+        event_service::emit_estimate_reached(app, payload);
+        if notify && payload.task.notifications_enabled {
+            show_task_notification(app, &payload.task, kind, formatter);
+        }
+    }
+    Ok(())
+}
+
+// Notify that a pomodoro for a task has ended (timer runs in the frontend)
+pub fn notify_pomodoro_finished(app: &AppHandle, db: &Database, task_id: TaskId) -> Result<(), String> {
+    let settings = db.settings()
+        .map_err(|e| format!("Failed to fetch settings: {}", e))?;
+    let conn = db.get_connection();
+    let task = db::get_task_by_id(&conn, task_id)
+        .map_err(|e| format!("Failed to get task by ID: {}", e))?;
+    
+    if settings.notifications_enabled && task.notifications_enabled {
+        show_task_notification(app, &task, NotificationKind::PomodoroFinished, Formatter::new(settings.locale));
+    }
+    
+    Ok(())
+}
+
+// End-of-day summary (scheduler job): always sent to the UI, shown as a notification if enabled
+pub fn send_daily_summary(app: &AppHandle) -> Result<(), String> {
+    let Some(db) = app.try_state::<Database>() else {
+        return Ok(());
+    };
+    
+    let summary = task_service::get_daily_summary(DateQuery { date: Utc::now().to_rfc3339(), utc_offset_minutes: None }, &db)?;
+    let settings = db.settings()
+        .map_err(|e| format!("Failed to fetch settings: {}", e))?;
+    
+    event_service::emit_daily_summary(app, &summary);
+    if !settings.notifications_enabled || !settings.daily_summary_notification {
+        return Ok(());
+    }
+    
+    let tracked = match (summary.tracked_minutes / 60, summary.tracked_minutes % 60) {
+        (0, m) => format!("{}m", m),
+        (h, m) => format!("{}h {}m", h, m),
+    };
+    let mut body = format!("{} done, {} remaining, {} tracked", summary.completed_count, summary.remaining_count, tracked);
+    let highlights: Vec<&str> = summary.completed.iter()
+        .take(SUMMARY_HIGHLIGHTS)
+        .map(|task| task.title.as_str())
+        .collect();
+    if !highlights.is_empty() {
+        body.push_str(&format!("\nDone: {}", highlights.join(", ")));
+    }
+    
+    if let Err(e) = app.notification()
+        .builder()
+        .title("Today's summary")
+        .body(body)
+        .show()
+    {
+        error!("Failed to show daily summary notification: {}", e);
+    }
+    Ok(())
+}
+
+// Birthdays and the like entering their reminder window (scheduler job); each is notified once a year
+pub fn check_remembered_dates(app: &AppHandle) -> Result<(), String> {
+    let Some(db) = app.try_state::<Database>() else {
+        return Ok(());
+    };
+    
+    let settings = db.settings()
+        .map_err(|e| format!("Failed to fetch settings: {}", e))?;
+    if !settings.notifications_enabled {
+        return Ok(());
+    }
+    let today = local_day(Utc::now(), settings_service::day_offset(&db, None)?);
+    
+    for upcoming in remembered_date_service::due_reminders(&db, today)? {
+        if upcoming.date.reminded_for == Some(upcoming.next_on) {
+            continue;
+        }
+        
+        let when = match upcoming.days_until {
+            0 => "Today".to_string(),
+            1 => "Tomorrow".to_string(),
+            days => format!("In {} days", days),
+        };
+        let body = match upcoming.years {
+            Some(years) => format!("{}\n{} ({} years)", upcoming.date.name, when, years),
+            None => format!("{}\n{}", upcoming.date.name, when),
+        };
+        if let Err(e) = app.notification()
+            .builder()
+            .title("Coming up")
+            .body(body)
+            .show()
+        {
+            error!("Failed to show reminder for date {}: {}", upcoming.date.id, e);
+        }
+        remembered_date_service::mark_reminded(&db, &upcoming)?;
+    }
+    Ok(())
+}
+
+fn show_task_notification(app: &AppHandle, task: &Task, kind: NotificationKind, formatter: Formatter) {
+    let title = match kind {
+        NotificationKind::DueSoon => "Task due soon",
+        NotificationKind::Overdue => "Task overdue",
+        NotificationKind::PomodoroFinished => "Pomodoro finished",
+        NotificationKind::EstimateReached => "Consider completing",
+        NotificationKind::Reminder => "Task reminder",
+    };
+    // Shown on this machine, so in its own time zone
+    let deadline = task.deadline.map(|deadline| deadline.with_timezone(&Local).naive_local());
+    let body = match (kind, deadline) {
+        (NotificationKind::DueSoon, Some(deadline)) => format!("{}\nDue at {}", task.title, formatter.time(deadline.time())),
+        (NotificationKind::Overdue, Some(deadline)) => format!("{}\nWas due {}", task.title, formatter.short_date_time(deadline)),
+        (NotificationKind::Reminder, Some(deadline)) => format!("{}\nDue {}", task.title, formatter.short_date_time(deadline)),
+        (NotificationKind::EstimateReached, _) => format!("{}\nTracked time reached the estimate", task.title),
+        _ => task.title.clone(),
+    };
+    
+    if let Err(e) = app.notification()
+        .builder()
+        .title(title)
+        .body(body)
+        .show()
+    {
+        error!("Failed to show {} notification for task {}: {}", kind.as_str(), task.id, e);
+    }
+}
@@ -0,0 +1,750 @@
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use chrono::Utc;
+use tauri::{AppHandle, Manager};
+use uuid::{Timestamp, Uuid};
+use crate::db::{self, Database};
+use crate::helpers::crypto::{self, SecretKey};
+use crate::services::sync_backend::{FolderBackend, SyncBackend, WebDavBackend};
+use crate::secrets;
+use crate::services::{calendar_journal_service, calendar_service, event_service, network_permission_service, recovery_service, settings_service, sync_service};
+use crate::structs::calendar_journal::JournalOperation;
+use crate::structs::network::NetworkFeature;
+use crate::structs::project::{
+    ArchiveMode, ArchiveProjectData, ArchiveProjectSummary, JoinProjectData, Project, ProjectData, ProjectDetail, ProjectId,
+    ProjectInvite, ProjectManifest, ProjectMember, ProjectMembersData, ProjectRole, ShareProjectData,
+};
+use crate::structs::settings::Settings;
+use crate::structs::sync::{Change, ChangeRecord, Changeset, DeviceInfo, SealedChangeset, SyncReport, WebDavAccount};
+use crate::structs::task_struct::{Task, TaskId};
+use tracing::{error, info, warn};
+
+// Invites look like myhandler-share:<project id>:<key hex>
+const INVITE_PREFIX: &str = "myhandler-share:";
+const MAX_NAME_LENGTH: usize = 100;
+
+// One shared project sync at a time, so a changeset is never pushed twice
+static SYNC_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+// Each project is synced through its own location, apart from this person's own devices
+enum ShareLocation {
+    Folder(FolderBackend),
+    WebDav(WebDavBackend),
+}
+
+// Who this person is to the people they share projects with
+pub fn identity(settings: &Settings) -> String {
+    settings.sync_identity.clone().unwrap_or_else(|| sync_service::device_name(settings))
+}
+
+pub fn create_project(db: &Database, payload: ProjectData) -> Result<ProjectDetail, String> {
+    let project = Project {
+        id: Uuid::new_v7(Timestamp::now(uuid::timestamp::context::NoContext)),
+        name: checked_name(&payload.name, "Project name")?,
+        share_location: None,
+        share_key: None,
+        role: ProjectRole::Owner,
+        last_seq: 0,
+        synced_at: None,
+        created_at: Utc::now(),
+        archived_at: None,
+    };
+    
+    let conn = db.get_connection();
+    db::insert(&conn, &project)
+        .map_err(|e| format!("Failed to create project: {}", e))?;
+    info!("Created project {}", project.id);
+    Ok(ProjectDetail { project, members: Vec::new(), task_count: 0 })
+}
+
+pub fn get_projects(db: &Database) -> Result<Vec<ProjectDetail>, String> {
+    let conn = db.get_read_connection();
+    let projects = db::get_projects(&conn)
+        .map_err(|e| format!("Failed to fetch projects: {}", e))?;
+    
+    projects.into_iter()
+        .map(|project| detail(&conn, project))
+        .collect()
+}
+
+// Delete the project here; its tasks stay, outside any project. Other members keep their copies.
+pub fn delete_project(db: &Database, app: &AppHandle, payload: ProjectId) -> Result<(), String> {
+    let now = Utc::now();
+    let tasks = {
+        let conn = db.get_connection();
+        let tx = conn.unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        
+        let tasks = db::get_project_tasks(&tx, &payload.id)
+            .map_err(|e| format!("Failed to fetch project tasks: {}", e))?;
+        db::detach_project_tasks(&tx, &payload.id, now)
+            .map_err(|e| format!("Failed to take tasks out of the project: {}", e))?;
+        if db::delete_project(&tx, &payload.id).map_err(|e| format!("Failed to delete project: {}", e))? == 0 {
+            return Err(format!("Project {} not found", payload.id));
+        }
+        
+        tx.commit().map_err(|e| format!("Failed to delete project: {}", e))?;
+        tasks
+    }; // DB lock released here
+    
+    info!("Deleted project {}, {} tasks kept", payload.id, tasks.len());
+    for task in tasks {
+        event_service::emit_task_updated(app, &Task { project_id: None, assignee: None, updated_at: now, ..task });
+    }
+    Ok(())
+}
+
+// Archive a project, deciding in the same transaction what happens to its open tasks.
+// Tasks moved to the inbox are deleted; their calendar events, and those of archived tasks, are
+// journaled and removed afterwards.
+pub async fn archive_project(db: &Database, app: &AppHandle, payload: ArchiveProjectData) -> Result<ArchiveProjectSummary, String> {
+    let now = Utc::now();
+    let (summary, archived, events) = {
+        let conn = db.get_connection();
+        let tx = conn.unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    
+        let project = db::get_project_by_id(&tx, &payload.id)
+            .map_err(|e| format!("Failed to fetch project: {}", e))?
+            .ok_or_else(|| format!("Project {} not found", payload.id))?;
+        if project.archived_at.is_some() {
+            return Err(format!("Project {} is already archived", project.name));
+        }
+    
+        let open = db::get_open_project_tasks(&tx, &payload.id)
+            .map_err(|e| format!("Failed to fetch project tasks: {}", e))?;
+        let total = db::count_project_tasks(&tx, &payload.id)
+            .map_err(|e| format!("Failed to count project tasks: {}", e))?;
+    
+        let mut summary = ArchiveProjectSummary {
+            project_id: payload.id,
+            mode: payload.mode,
+            archived_tasks: Vec::new(),
+            moved_tasks: Vec::new(),
+            inbox_items: Vec::new(),
+            completed_count: total - open.len() as i64,
+        };
+        let mut archived = Vec::new();
+        let mut events = Vec::new();
+        match payload.mode {
+            ArchiveMode::Block if !open.is_empty() => {
+                return Err(format!("Project {} still has {} open tasks", project.name, open.len()));
+            }
+            ArchiveMode::Block => {}
+            ArchiveMode::Archive => {
+                for task in open {
+                    let task_id = TaskId::from(task.id);
+                    db::archive_task(&tx, task_id, now)
+                        .map_err(|e| format!("Failed to archive task: {}", e))?;
+                    // An archived task keeps no event; the journal entry holds the ID until it's deleted
+                    let event_id = db::get_task_google_event_id(&tx, task_id)
+                        .map_err(|e| format!("Failed to get calendar event: {}", e))?;
+                    if let Some(event_id) = event_id {
+                        db::clear_task_google_event_id(&tx, task_id)
+                            .map_err(|e| format!("Failed to clear calendar event: {}", e))?;
+                        let journal_id = calendar_journal_service::record(&tx, task_id, JournalOperation::Delete, Some(&event_id))?;
+                        events.push((task_id, event_id, journal_id));
+                    }
+                    summary.archived_tasks.push(task_id);
+                    archived.push(task);
+                }
+            }
+            ArchiveMode::Inbox => {
+                for task in open {
+                    let task_id = TaskId::from(task.id);
+                    let text = match task.notes.as_deref().map(str::trim).filter(|notes| !notes.is_empty()) {
+                        Some(notes) => format!("{}\n\n{}", task.title, notes),
+                        None => task.title.clone(),
+                    };
+                    let item = db::insert_inbox_item(&tx, &text, now)
+                        .map_err(|e| format!("Failed to move task to the inbox: {}", e))?;
+    
+                    let event_id = db::get_task_google_event_id(&tx, task_id)
+                        .map_err(|e| format!("Failed to get calendar event: {}", e))?;
+                    db::delete_task_by_id(&tx, task_id)
+                        .map_err(|e| format!("Failed to delete task: {}", e))?;
+                    if let Some(event_id) = event_id {
+                        let journal_id = calendar_journal_service::record(&tx, task_id, JournalOperation::Delete, Some(&event_id))?;
+                        events.push((task_id, event_id, journal_id));
+                    }
+    
+                    summary.moved_tasks.push(task_id);
+                    summary.inbox_items.push(item);
+                }
+            }
+        }
+    
+        db::archive_project(&tx, &payload.id, now)
+            .map_err(|e| format!("Failed to archive project: {}", e))?;
+        tx.commit().map_err(|e| format!("Failed to archive project: {}", e))?;
+        (summary, archived, events)
+    }; // DB lock released here
+    
+    info!(
+        "Archived project {}: {} tasks archived, {} moved to the inbox, {} completed kept",
+        payload.id, summary.archived_tasks.len(), summary.moved_tasks.len(), summary.completed_count,
+    );
+    
+    for (task_id, event_id, journal_id) in events {
+        let result = calendar_service::delete_task_calendar_event(db, &event_id).await;
+        if let Err(e) = &result {
+            warn!("Failed to delete calendar event {}: {}", event_id, e);
+        }
+        calendar_journal_service::finish(db, journal_id, task_id, JournalOperation::Delete, &result);
+    }
+    for task in archived {
+        event_service::emit_task_updated(app, &task);
+    }
+    for task_id in &summary.moved_tasks {
+        event_service::emit_task_deleted(app, *task_id);
+    }
+    Ok(summary)
+}
+
+// Start syncing the project through `location` and return the invite for the other members
+pub async fn share_project(app: &AppHandle, payload: ShareProjectData) -> Result<ProjectInvite, String> {
+    let db = app.try_state::<Database>()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    let location = payload.location.trim().to_string();
+    if location.is_empty() {
+        return Err("Choose a folder or WebDAV URL to share the project through".to_string());
+    }
+    let settings = db.settings()
+        .map_err(|e| format!("Failed to fetch settings: {}", e))?;
+    
+    let (project, members) = load_project(&db, &payload.id)?;
+    if project.role != ProjectRole::Owner {
+        return Err("Only the project's owner can share it".to_string());
+    }
+    if let (Some(shared_at), Some(key)) = (&project.share_location, &project.share_key) {
+        if *shared_at != location {
+            return Err(format!("Project is already shared at {}", shared_at));
+        }
+        return Ok(ProjectInvite { invite: invite(&project.id, key), location });
+    }
+    
+    // The owner leads the member list; anyone added before sharing keeps their role
+    let owner = identity(&settings);
+    let mut members: Vec<ProjectMember> = members.into_iter()
+        .filter(|member| member.identity != owner)
+        .collect();
+    members.insert(0, ProjectMember { identity: owner, role: ProjectRole::Owner });
+    
+    let key = SecretKey::random();
+    let manifest = ProjectManifest {
+        project_id: project.id,
+        name: project.name.clone(),
+        members: members.clone(),
+        updated_at: Utc::now(),
+    };
+    // Written before the project counts as shared, so a location that can't be used fails here
+    match open_location(&db, &location)? {
+        ShareLocation::Folder(backend) => publish(&backend, &manifest, &key, true).await?,
+        ShareLocation::WebDav(backend) => publish(&backend, &manifest, &key, true).await?,
+    }
+    
+    let key = crypto::to_hex(key.as_bytes());
+    {
+        let conn = db.get_connection();
+        let tx = conn.unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        db::set_project_share(&tx, &project.id, &location, &key)
+            .and_then(|_| db::set_project_members(&tx, &project.id, &members))
+            .and_then(|_| tx.commit())
+            .map_err(|e| format!("Failed to share project: {}", e))?;
+    } // DB lock released here
+    
+    info!("Shared project {} at {}", project.id, location);
+    sync_soon(app.clone());
+    Ok(ProjectInvite { invite: invite(&project.id, &key), location })
+}
+
+// Join a project someone shared; their member list must already name this person
+pub async fn join_shared_project(app: &AppHandle, payload: JoinProjectData) -> Result<ProjectDetail, String> {
+    let db = app.try_state::<Database>()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    let (project_id, key_hex) = parse_invite(&payload.invite)?;
+    let key = SecretKey::from_bytes(&crypto::from_hex(&key_hex)?)?;
+    let location = payload.location.trim().to_string();
+    if location.is_empty() {
+        return Err("Choose where this device sees the shared folder".to_string());
+    }
+    let settings = db.settings()
+        .map_err(|e| format!("Failed to fetch settings: {}", e))?;
+    
+    let existing = {
+        let conn = db.get_read_connection();
+        db::get_project_by_id(&conn, &project_id)
+            .map_err(|e| format!("Failed to fetch project: {}", e))?
+    }; // DB lock released here
+    if existing.is_some() {
+        return Err("This project is already on this device".to_string());
+    }
+    
+    let manifest = match open_location(&db, &location)? {
+        ShareLocation::Folder(backend) => fetch_manifest(&backend, &key).await?,
+        ShareLocation::WebDav(backend) => fetch_manifest(&backend, &key).await?,
+    };
+    let manifest = manifest
+        .filter(|manifest| manifest.project_id == project_id)
+        .ok_or_else(|| format!("The invite's project isn't shared at {}", location))?;
+    let me = identity(&settings);
+    let role = manifest.members.iter()
+        .find(|member| member.identity == me)
+        .map(|member| member.role)
+        .ok_or_else(|| format!("Ask the project's owner to add '{}' to its members first", me))?;
+    
+    let project = Project {
+        id: project_id,
+        name: manifest.name,
+        share_location: Some(location),
+        share_key: Some(key_hex),
+        role,
+        last_seq: 0,
+        synced_at: None,
+        created_at: Utc::now(),
+        archived_at: None,
+    };
+    {
+        let conn = db.get_connection();
+        let tx = conn.unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        db::insert(&tx, &project)
+            .and_then(|_| db::set_project_members(&tx, &project.id, &manifest.members))
+            .and_then(|_| tx.commit())
+            .map_err(|e| format!("Failed to join project: {}", e))?;
+    } // DB lock released here
+    
+    info!("Joined shared project {}", project.id);
+    // The project is joined either way; a failed first sync is retried by the scheduled one
+    let running = SYNC_LOCK.lock().await;
+    if let Err(e) = sync_project(app, &project).await {
+        warn!("First sync of shared project {} failed: {}", project.id, e);
+    }
+    drop(running);
+    
+    let (project, _) = load_project(&db, &project_id)?;
+    let conn = db.get_read_connection();
+    detail(&conn, project)
+}
+
+// Replace who else is in the project; only the owner can
+pub async fn set_project_members(app: &AppHandle, payload: ProjectMembersData) -> Result<ProjectDetail, String> {
+    let db = app.try_state::<Database>()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    let settings = db.settings()
+        .map_err(|e| format!("Failed to fetch settings: {}", e))?;
+    let (project, _) = load_project(&db, &payload.id)?;
+    if project.role != ProjectRole::Owner {
+        return Err("Only the project's owner can change its members".to_string());
+    }
+    
+    let owner = identity(&settings);
+    let mut members = vec![ProjectMember { identity: owner.clone(), role: ProjectRole::Owner }];
+    let mut seen = HashSet::from([owner]);
+    for member in payload.members {
+        let identity = checked_name(&member.identity, "Member name")?;
+        if member.role == ProjectRole::Owner {
+            return Err("A project has only one owner".to_string());
+        }
+        if !seen.insert(identity.clone()) {
+            return Err(format!("'{}' is in the member list more than once", identity));
+        }
+        members.push(ProjectMember { identity, role: member.role });
+    }
+    
+    if let (Some(location), Some(key)) = (&project.share_location, &project.share_key) {
+        let key = SecretKey::from_bytes(&crypto::from_hex(key)?)?;
+        let manifest = ProjectManifest {
+            project_id: project.id,
+            name: project.name.clone(),
+            members: members.clone(),
+            updated_at: Utc::now(),
+        };
+        match open_location(&db, location)? {
+            ShareLocation::Folder(backend) => publish(&backend, &manifest, &key, false).await?,
+            ShareLocation::WebDav(backend) => publish(&backend, &manifest, &key, false).await?,
+        }
+    }
+    
+    let conn = db.get_connection();
+    let tx = conn.unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    db::set_project_members(&tx, &project.id, &members)
+        .and_then(|_| tx.commit())
+        .map_err(|e| format!("Failed to save project members: {}", e))?;
+    info!("Project {} now has {} members", project.id, members.len());
+    detail(&conn, project)
+}
+
+// A task in a project with members can only be assigned to one of them
+pub fn check_assignee(conn: &rusqlite::Connection, project_id: Option<Uuid>, assignee: &str) -> Result<(), String> {
+    let project_id = project_id.ok_or_else(|| "Only tasks in a project can be assigned".to_string())?;
+    if db::get_project_by_id(conn, &project_id)
+        .map_err(|e| format!("Failed to fetch project: {}", e))?
+        .is_none()
+    {
+        return Err(format!("Project {} not found", project_id));
+    }
+    let members = db::get_project_members(conn, &project_id)
+        .map_err(|e| format!("Failed to fetch project members: {}", e))?;
+    if !members.is_empty() && !members.iter().any(|member| member.identity == assignee) {
+        return Err(format!("'{}' isn't a member of the project", assignee));
+    }
+    Ok(())
+}
+
+// Push this person's changes to every shared project and merge the other members'
+pub async fn sync_shared_projects(app: &AppHandle) -> Result<SyncReport, String> {
+    if recovery_service::is_active(app) {
+        return Ok(SyncReport::default());
+    }
+    let db = app.try_state::<Database>()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    let _running = SYNC_LOCK.lock().await;
+    
+    let projects = {
+        let conn = db.get_read_connection();
+        db::get_shared_projects(&conn)
+            .map_err(|e| format!("Failed to fetch shared projects: {}", e))?
+    }; // DB lock released here
+    
+    // One unreachable location doesn't hold up the others
+    let mut report = SyncReport::default();
+    let mut failed = Vec::new();
+    for project in projects {
+        match sync_project(app, &project).await {
+            Ok(synced) => {
+                report.applied += synced.applied;
+                report.skipped += synced.skipped;
+                report.conflicts += synced.conflicts;
+            }
+            Err(e) => {
+                warn!("Shared project {} failed to sync: {}", project.id, e);
+                failed.push(format!("{}: {}", project.name, e));
+            }
+        }
+    }
+    
+    if !failed.is_empty() {
+        return Err(failed.join("; "));
+    }
+    Ok(report)
+}
+
+fn sync_soon(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = sync_shared_projects(&app).await {
+            error!("Shared project sync failed: {}", e);
+        }
+    });
+}
+
+async fn sync_project(app: &AppHandle, project: &Project) -> Result<SyncReport, String> {
+    let (Some(location), Some(key)) = (&project.share_location, &project.share_key) else {
+        return Ok(SyncReport::default());
+    };
+    let key = SecretKey::from_bytes(&crypto::from_hex(key)?)?;
+    
+    let db = app.state::<Database>();
+    match open_location(&db, location)? {
+        ShareLocation::Folder(backend) => sync_with(app, project, &backend, &key).await,
+        ShareLocation::WebDav(backend) => sync_with(app, project, &backend, &key).await,
+    }
+}
+
+async fn sync_with<B: SyncBackend>(app: &AppHandle, project: &Project, backend: &B, key: &SecretKey) -> Result<SyncReport, String> {
+    let db = app.state::<Database>();
+    let settings = db.settings()
+        .map_err(|e| format!("Failed to fetch settings: {}", e))?;
+    let me = identity(&settings);
+    let device_id = settings.sync_device_id.clone();
+    
+    // Everyone but the owner takes name, members and their own role from the owner's manifest
+    let mut role = project.role;
+    if role != ProjectRole::Owner {
+        let manifest = fetch_manifest(backend, key).await?
+            .filter(|manifest| manifest.project_id == project.id)
+            .ok_or_else(|| "The project is no longer shared at its location".to_string())?;
+        role = manifest.members.iter()
+            .find(|member| member.identity == me)
+            .map(|member| member.role)
+            .ok_or_else(|| format!("'{}' was removed from the project", me))?;
+        
+        let conn = db.get_connection();
+        let tx = conn.unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        db::update_joined_project(&tx, &project.id, &manifest.name, role)
+            .and_then(|_| db::set_project_members(&tx, &project.id, &manifest.members))
+            .and_then(|_| tx.commit())
+            .map_err(|e| format!("Failed to update project members: {}", e))?;
+    } // DB lock released here
+    
+    let members: HashMap<String, ProjectRole> = {
+        let conn = db.get_read_connection();
+        db::get_project_members(&conn, &project.id)
+            .map_err(|e| format!("Failed to fetch project members: {}", e))?
+            .into_iter()
+            .map(|member| (member.identity, member.role))
+            .collect()
+    }; // DB lock released here
+    
+    // A viewer's edits stay on their own devices
+    if role.can_edit() {
+        push_changes(&db, project, backend, key, &device_id).await?;
+    }
+    
+    let info = DeviceInfo {
+        device_id: device_id.clone(),
+        name: sync_service::device_name(&settings),
+        last_seen_at: Utc::now(),
+        identity: Some(me),
+    };
+    backend.register_device(&device_id, sync_service::encode(&info, Some(key))?).await?;
+    
+    let mut report = SyncReport::default();
+    for other in backend.device_ids().await? {
+        if other == device_id {
+            continue;
+        }
+        let cursor = {
+            let conn = db.get_read_connection();
+            db::get_shared_project_cursor(&conn, &project.id, &other)
+                .map_err(|e| format!("Failed to read sync cursor: {}", e))?
+        }; // DB lock released here
+        let changesets = backend.pull(&other, cursor.unwrap_or(0)).await?;
+        let Some(last_seq) = changesets.last().map(|changeset| changeset.seq) else {
+            continue;
+        };
+        
+        let owner = match backend.device_info(&other).await? {
+            Some(data) => sync_service::decode::<DeviceInfo>(&data, Some(key)).ok().and_then(|info| info.identity),
+            None => None,
+        };
+        let can_edit = owner.as_ref()
+            .and_then(|owner| members.get(owner))
+            .is_some_and(|role| role.can_edit());
+        
+        let mut records = Vec::new();
+        for changeset in changesets {
+            let changeset: Changeset = sync_service::decode(&changeset.data, Some(key))?;
+            if changeset.device_id != other {
+                return Err(format!("Changeset from {} found in {}'s folder", changeset.device_id, other));
+            }
+            records.extend(changeset.records);
+        }
+        
+        // Changes from viewers and from devices of people no longer in the project are passed over for good
+        let received = records.len();
+        let records = if can_edit { project_records(&db, &project.id, records)? } else { Vec::new() };
+        if records.len() < received {
+            warn!("Ignored {} changes to shared project {} from device {}", received - records.len(), project.id, other);
+        }
+        report.skipped += received - records.len();
+        
+        // Versions as received: a newer local copy kept by the merge differs from them and is pushed next time
+        let versions: Vec<(Uuid, Option<_>)> = records.iter()
+            .map(|record| match &record.change {
+                Change::Upsert { task } => (task.id, Some(task.updated_at)),
+                Change::Delete { task_id } => (*task_id, None),
+            })
+            .collect();
+        if !records.is_empty() {
+            let merged = sync_service::merge_snapshot(app, other.clone(), records)?;
+            report.applied += merged.applied;
+            report.skipped += merged.skipped;
+            report.conflicts += merged.conflicts;
+        }
+        
+        let conn = db.get_connection();
+        let tx = conn.unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        for (task_id, version) in versions {
+            match version {
+                Some(version) => db::set_shared_project_version(&tx, &project.id, &task_id, version),
+                None => db::delete_shared_project_version(&tx, &project.id, &task_id),
+            }
+            .map_err(|e| format!("Failed to record shared task version: {}", e))?;
+        }
+        db::set_shared_project_cursor(&tx, &project.id, &other, last_seq)
+            .and_then(|_| tx.commit())
+            .map_err(|e| format!("Failed to save sync cursor: {}", e))?;
+    }
+    
+    let conn = db.get_connection();
+    let last_seq = db::get_project_by_id(&conn, &project.id)
+        .map_err(|e| format!("Failed to fetch project: {}", e))?
+        .map_or(project.last_seq, |project| project.last_seq);
+    db::set_project_synced(&conn, &project.id, last_seq, Utc::now())
+        .map_err(|e| format!("Failed to save sync time: {}", e))?;
+    Ok(report)
+}
+
+// Send the project's tasks that changed, joined or left it since the last push
+async fn push_changes<B: SyncBackend>(
+    db: &Database,
+    project: &Project,
+    backend: &B,
+    key: &SecretKey,
+    device_id: &str,
+) -> Result<(), String> {
+    let now = Utc::now();
+    let (changed, removed, last_seq) = {
+        let conn = db.get_read_connection();
+        let tasks = db::get_project_tasks(&conn, &project.id)
+            .map_err(|e| format!("Failed to fetch project tasks: {}", e))?;
+        let versions = db::get_shared_project_versions(&conn, &project.id)
+            .map_err(|e| format!("Failed to read shared task versions: {}", e))?;
+        let last_seq = db::get_project_by_id(&conn, &project.id)
+            .map_err(|e| format!("Failed to fetch project: {}", e))?
+            .map_or(project.last_seq, |project| project.last_seq);
+        
+        let current: HashSet<Uuid> = tasks.iter().map(|task| task.id).collect();
+        // Deleted here or moved to another project: gone for the other members too
+        let mut removed = Vec::new();
+        for task_id in versions.keys().filter(|task_id| !current.contains(task_id)) {
+            let deleted_at = db::get_sync_tombstone(&conn, task_id)
+                .map_err(|e| format!("Failed to read deleted task: {}", e))?;
+            removed.push((*task_id, deleted_at.unwrap_or(now)));
+        }
+        let changed: Vec<Task> = tasks.into_iter()
+            .filter(|task| versions.get(&task.id) != Some(&task.updated_at))
+            .collect();
+        (changed, removed, last_seq)
+    }; // DB lock released here
+    if changed.is_empty() && removed.is_empty() {
+        return Ok(());
+    }
+    
+    let pushed: Vec<(Uuid, Option<_>)> = changed.iter()
+        .map(|task| (task.id, Some(task.updated_at)))
+        .chain(removed.iter().map(|(task_id, _)| (*task_id, None)))
+        .collect();
+    // Follows the clock but never repeats or goes back
+    let first_seq = now.timestamp_micros().max(last_seq + 1);
+    let records: Vec<ChangeRecord> = changed.into_iter()
+        .map(|task| (task.updated_at, Change::Upsert { task: Box::new(task) }))
+        .chain(removed.into_iter().map(|(task_id, deleted_at)| (deleted_at, Change::Delete { task_id })))
+        .enumerate()
+        .map(|(index, (recorded_at, change))| ChangeRecord {
+            seq: first_seq + index as i64,
+            device_id: device_id.to_string(),
+            recorded_at,
+            change,
+        })
+        .collect();
+    let last_seq = first_seq + records.len() as i64 - 1;
+    
+    let changeset = Changeset { device_id: device_id.to_string(), records };
+    backend.push(device_id, SealedChangeset { seq: last_seq, data: sync_service::encode(&changeset, Some(key))? }).await?;
+    
+    let conn = db.get_connection();
+    let tx = conn.unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    for (task_id, version) in &pushed {
+        match version {
+            Some(version) => db::set_shared_project_version(&tx, &project.id, task_id, *version),
+            None => db::delete_shared_project_version(&tx, &project.id, task_id),
+        }
+        .map_err(|e| format!("Failed to record shared task version: {}", e))?;
+    }
+    db::set_project_synced(&tx, &project.id, last_seq, now)
+        .and_then(|_| tx.commit())
+        .map_err(|e| format!("Failed to save sync state: {}", e))?;
+    info!("Pushed {} changes to shared project {}", pushed.len(), project.id);
+    Ok(())
+}
+
+// Only the project's own tasks: a change can't pull in a task from outside it, or touch one that isn't in it here
+fn project_records(db: &Database, project_id: &Uuid, records: Vec<ChangeRecord>) -> Result<Vec<ChangeRecord>, String> {
+    let conn = db.get_read_connection();
+    let mut accepted = Vec::with_capacity(records.len());
+    for record in records {
+        let (task_id, in_project) = match &record.change {
+            Change::Upsert { task } => (task.id, task.project_id.as_ref() == Some(project_id)),
+            Change::Delete { task_id } => (*task_id, true),
+        };
+        let local = match db::get_task_by_id(&conn, task_id.into()) {
+            Ok(task) => Some(task),
+            Err(rusqlite::Error::QueryReturnedNoRows) => None,
+            Err(e) => return Err(format!("Failed to fetch task {}: {}", task_id, e)),
+        };
+        if in_project && local.map_or(true, |local| local.project_id.as_ref() == Some(project_id)) {
+            accepted.push(record);
+        }
+    }
+    Ok(accepted)
+}
+
+// Write the manifest; a new share refuses a location some other project already uses
+async fn publish<B: SyncBackend>(backend: &B, manifest: &ProjectManifest, key: &SecretKey, new_share: bool) -> Result<(), String> {
+    if new_share && backend.read_manifest().await?.is_some() {
+        return Err("Another project is already shared there; choose an empty folder".to_string());
+    }
+    backend.write_manifest(sync_service::encode(manifest, Some(key))?).await
+}
+
+// None when nothing is shared at the location; a manifest the key can't open belongs to another project
+async fn fetch_manifest<B: SyncBackend>(backend: &B, key: &SecretKey) -> Result<Option<ProjectManifest>, String> {
+    Ok(backend.read_manifest().await?
+        .and_then(|data| sync_service::decode(&data, Some(key)).ok()))
+}
+
+// URLs are WebDAV folders, signed in to with the account from the sync settings; anything else is a local folder
+fn open_location(db: &Database, location: &str) -> Result<ShareLocation, String> {
+    if !(location.starts_with("https://") || location.starts_with("http://")) {
+        return Ok(ShareLocation::Folder(FolderBackend::new(PathBuf::from(location))));
+    }
+    
+    network_permission_service::ensure_allowed(db, NetworkFeature::Sync)?;
+    let settings = db.settings()
+        .map_err(|e| format!("Failed to fetch settings: {}", e))?;
+    Ok(ShareLocation::WebDav(WebDavBackend::new(WebDavAccount {
+        url: location.trim_end_matches('/').to_string(),
+        username: settings.sync_webdav_username,
+        password: settings_service::secret(db, secrets::SYNC, "webdav_password")?,
+    })))
+}
+
+fn load_project(db: &Database, id: &Uuid) -> Result<(Project, Vec<ProjectMember>), String> {
+    let conn = db.get_read_connection();
+    let project = db::get_project_by_id(&conn, id)
+        .map_err(|e| format!("Failed to fetch project: {}", e))?
+        .ok_or_else(|| format!("Project {} not found", id))?;
+    let members = db::get_project_members(&conn, id)
+        .map_err(|e| format!("Failed to fetch project members: {}", e))?;
+    Ok((project, members))
+}
+
+fn detail(conn: &rusqlite::Connection, project: Project) -> Result<ProjectDetail, String> {
+    let members = db::get_project_members(conn, &project.id)
+        .map_err(|e| format!("Failed to fetch project members: {}", e))?;
+    let task_count = db::count_project_tasks(conn, &project.id)
+        .map_err(|e| format!("Failed to count project tasks: {}", e))?;
+    Ok(ProjectDetail { project, members, task_count })
+}
+
+fn invite(project_id: &Uuid, key: &str) -> String {
+    format!("{}{}:{}", INVITE_PREFIX, project_id, key)
+}
+
+fn parse_invite(invite: &str) -> Result<(Uuid, String), String> {
+    let (project_id, key) = invite.trim()
+        .strip_prefix(INVITE_PREFIX)
+        .and_then(|rest| rest.split_once(':'))
+        .ok_or_else(|| "Not a shared project invite".to_string())?;
+    let project_id = Uuid::parse_str(project_id)
+        .map_err(|_| "Not a shared project invite".to_string())?;
+    Ok((project_id, key.to_string()))
+}
+
+fn checked_name(value: &str, field: &str) -> Result<String, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(format!("{} can't be empty", field));
+    }
+    if value.chars().count() > MAX_NAME_LENGTH {
+        return Err(format!("{} can't be longer than {} characters", field, MAX_NAME_LENGTH));
+    }
+    Ok(value.to_string())
+}
@@ -0,0 +1,38 @@
+use std::time::Duration;
+use crate::db::{self, Database};
+use crate::structs::query::{QueryData, QueryResult};
+use tracing::info;
+
+const MAX_SQL_CHARS: usize = 10_000;
+const MAX_ROWS: usize = 1_000;
+// Runaway queries (cross joins, deep recursion) are stopped after this
+const QUERY_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Query console: one SELECT on a sandboxed read-only connection, so custom reports can't change data
+pub fn run_readonly_query(db: &Database, payload: QueryData) -> Result<QueryResult, String> {
+    let sql = payload.sql.trim();
+    if sql.is_empty() {
+        return Err("Query is empty".to_string());
+    }
+    if sql.chars().count() > MAX_SQL_CHARS {
+        return Err(format!("Query is longer than {} characters", MAX_SQL_CHARS));
+    }
+    
+    let conn = db.open_query_connection()
+        .map_err(|e| format!("Failed to open query connection: {}", e))?;
+    let result = db::run_readonly_query(&conn, sql, MAX_ROWS, QUERY_TIMEOUT)
+        .map_err(|e| match e {
+            rusqlite::Error::InvalidQuery => "Only SELECT queries can be run".to_string(),
+            rusqlite::Error::MultipleStatement => "Run one statement at a time".to_string(),
+            rusqlite::Error::SqliteFailure(err, _) if err.code == rusqlite::ErrorCode::AuthorizationForStatementDenied => {
+                "Only SELECT queries can be run".to_string()
+            }
+            rusqlite::Error::SqliteFailure(err, _) if err.code == rusqlite::ErrorCode::OperationInterrupted => {
+                format!("Query took longer than {} seconds", QUERY_TIMEOUT.as_secs())
+            }
+            e => format!("Query failed: {}", e),
+        })?;
+    
+    info!("Query console returned {} rows", result.rows.len());
+    Ok(result)
+}
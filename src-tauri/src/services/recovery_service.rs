@@ -0,0 +1,143 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use chrono::Utc;
+use rusqlite::{Connection, ErrorCode};
+use tauri::{AppHandle, Emitter, Manager};
+use crate::db::{self, Database};
+use crate::error::DbError;
+use crate::services::lock_service;
+use crate::structs::recovery::{RecoveryAction, RecoveryStatus};
+use tracing::{info, warn, error};
+
+pub const RECOVERY_REQUIRED_EVENT: &str = "database-recovery-required";
+pub const RECOVERED_EVENT: &str = "database-recovered";
+
+// Backups live next to the database; newest file name sorts last
+const BACKUP_DIR: &str = "backups";
+const BACKUP_EXTENSION: &str = "db";
+
+// Set while the app runs on the in-memory fallback database
+#[derive(Default)]
+pub struct RecoveryState(Mutex<Option<RecoveryStatus>>);
+
+// Manage an in-memory database so commands keep working, and tell the UI what happened
+pub fn enter_recovery_mode(app: &AppHandle, init_error: &DbError) -> Result<(), String> {
+    let fallback = Database::open_in_memory()
+        .map_err(|e| format!("Failed to open fallback database: {}", e))?;
+    app.manage(fallback);
+    
+    let status = RecoveryStatus {
+        error: init_error.to_string(),
+        corrupted: is_corruption(init_error),
+        latest_backup: find_latest_backup(app)
+            .map(|path| path.display().to_string()),
+    };
+    warn!("Running on a temporary in-memory database: {}", status.error);
+    
+    let state = app.state::<RecoveryState>();
+    *state.0.lock().map_err(|e| e.to_string())? = Some(status.clone());
+    
+    if let Err(e) = app.emit(RECOVERY_REQUIRED_EVENT, status) {
+        error!("Failed to emit '{}' event: {}", RECOVERY_REQUIRED_EVENT, e);
+    }
+    
+    Ok(())
+}
+
+pub fn is_active(app: &AppHandle) -> bool {
+    app.try_state::<RecoveryState>()
+        .and_then(|state| state.0.lock().ok().map(|status| status.is_some()))
+        .unwrap_or(false)
+}
+
+pub fn get_recovery_status(app: &AppHandle) -> Result<Option<RecoveryStatus>, String> {
+    let state = app.state::<RecoveryState>();
+    let status = state.0.lock().map_err(|e| e.to_string())?;
+    Ok(status.clone())
+}
+
+// Replace the broken database file and switch the app over to it
+pub fn recover_database(app: &AppHandle, action: RecoveryAction) -> Result<(), String> {
+    if !is_active(app) {
+        return Err("Database is not in recovery mode".to_string());
+    }
+    
+    let db_path = db::get_db_path(app)
+        .map_err(|e| format!("Failed to get database path: {}", e))?;
+    
+    let backup = match action {
+        RecoveryAction::RestoreBackup => Some(
+            find_latest_backup(app).ok_or_else(|| "No backup available".to_string())?
+        ),
+        RecoveryAction::Recreate => None,
+    };
+    
+    set_aside(&db_path)?;
+    
+    if let Some(backup) = &backup {
+        fs::copy(backup, &db_path)
+            .map_err(|e| format!("Failed to restore backup {:?}: {}", backup, e))?;
+        info!("Restored database from backup {:?}", backup);
+    }
+    
+    let conn = Connection::open(&db_path)
+        .map_err(|e| format!("Failed to open recovered database: {}", e))?;
+    db::prepare_file_connection(&conn)
+        .map_err(|e| format!("Failed to initialize recovered database: {}", e))?;
+    
+    let db = app.state::<Database>();
+    db.replace_connection(conn, &db_path)
+        .map_err(|e| format!("Failed to initialize recovered database: {}", e))?;
+    // The recovered file may have an app lock; it starts locked like on launch
+    lock_service::init_app_lock(app);
+    
+    let state = app.state::<RecoveryState>();
+    *state.0.lock().map_err(|e| e.to_string())? = None;
+    
+    info!("Database recovered");
+    if let Err(e) = app.emit(RECOVERED_EVENT, ()) {
+        error!("Failed to emit '{}' event: {}", RECOVERED_EVENT, e);
+    }
+    
+    Ok(())
+}
+
+fn is_corruption(error: &DbError) -> bool {
+    match error {
+        DbError::Sqlite(e) => matches!(
+            e.sqlite_error_code(),
+            Some(ErrorCode::DatabaseCorrupt) | Some(ErrorCode::NotADatabase)
+        ),
+        _ => false,
+    }
+}
+
+fn find_latest_backup(app: &AppHandle) -> Option<PathBuf> {
+    let db_path = db::get_db_path(app).ok()?;
+    let backup_dir = db_path.parent()?.join(BACKUP_DIR);
+    
+    fs::read_dir(backup_dir).ok()?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some(BACKUP_EXTENSION))
+        .max()
+}
+
+// Keep the broken file (and its WAL/SHM) for inspection instead of deleting it
+fn set_aside(db_path: &Path) -> Result<(), String> {
+    let suffix = format!("corrupt-{}", Utc::now().format("%Y%m%d%H%M%S"));
+    
+    for extension in ["", "-wal", "-shm"] {
+        let path = PathBuf::from(format!("{}{}", db_path.display(), extension));
+        if !path.exists() {
+            continue;
+        }
+        
+        let target = PathBuf::from(format!("{}.{}", path.display(), suffix));
+        fs::rename(&path, &target)
+            .map_err(|e| format!("Failed to move {:?} aside: {}", path, e))?;
+        info!("Moved {:?} to {:?}", path, target);
+    }
+    
+    Ok(())
+}
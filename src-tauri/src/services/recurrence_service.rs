@@ -0,0 +1,227 @@
+use chrono::{Duration, FixedOffset, NaiveDate, Utc};
+use tauri::{AppHandle, Manager};
+use crate::db::{self, Database};
+use crate::helpers::datetime::{deadline_on, parse_datetime};
+use crate::helpers::parse_date::local_day;
+use crate::helpers::recurrence;
+use crate::services::{calendar_journal_service, event_service, rule_service, settings_service, task_service};
+use crate::structs::calendar_journal::JournalOperation;
+use crate::structs::dto::TaskRef;
+use crate::structs::recurrence::{
+    ExceptionKind, RecurrenceData, RecurrenceException, RecurrenceExceptionData, RecurrenceView, TaskRecurrence,
+};
+use crate::structs::rule::RuleTrigger;
+use crate::structs::task_struct::{Status, Task, TaskId};
+use crate::structs::task_update::{TaskUpdate, TaskUpdateData};
+use tracing::info;
+
+// Further apart than this is better kept as separate tasks
+const MAX_INTERVAL: u32 = 366;
+// Occurrences are made this far ahead, so they're listed and on the calendar before their day
+const DAYS_AHEAD: i64 = 7;
+
+// Repeat a task from its deadline on; setting it again replaces the rule
+pub fn set_task_recurrence(db: &Database, payload: RecurrenceData) -> Result<TaskRecurrence, String> {
+    let interval = payload.interval.unwrap_or(1);
+    if interval == 0 || interval > MAX_INTERVAL {
+        return Err(format!("Interval must be between 1 and {}", MAX_INTERVAL));
+    }
+    let offset = settings_service::day_offset(db, None)?;
+    
+    let conn = db.get_connection();
+    let task = db::get_task_by_id(&conn, payload.task_id)
+        .map_err(|e| match e {
+            rusqlite::Error::QueryReturnedNoRows => format!("Task {} not found", payload.task_id),
+            e => format!("Failed to get task: {}", e),
+        })?;
+    let Some(deadline) = task.deadline else {
+        return Err("Give the task a deadline before making it repeat".to_string());
+    };
+    
+    let recurrence = db::set_task_recurrence(&conn, payload.task_id, payload.frequency, interval, local_day(deadline, offset), Utc::now())
+        .and_then(|_| db::get_task_recurrence(&conn, payload.task_id))
+        .map_err(|e| format!("Failed to save recurrence: {}", e))?
+        .ok_or_else(|| format!("Task {} doesn't repeat", payload.task_id))?;
+    
+    info!("Task {} repeats {:?} every {}", payload.task_id, recurrence.frequency, recurrence.interval);
+    Ok(recurrence)
+}
+
+// Occurrences already made stay as they are
+pub fn remove_task_recurrence(db: &Database, payload: TaskRef) -> Result<(), String> {
+    let conn = db.get_connection();
+    let deleted = db::delete_task_recurrence(&conn, payload.id)
+        .map_err(|e| format!("Failed to remove recurrence: {}", e))?;
+    
+    if deleted == 0 {
+        return Err(format!("Task {} doesn't repeat", payload.id));
+    }
+    Ok(())
+}
+
+pub fn get_task_recurrence(db: &Database, payload: TaskRef) -> Result<Option<RecurrenceView>, String> {
+    let conn = db.get_read_connection();
+    let Some(recurrence) = db::get_task_recurrence(&conn, payload.id)
+        .map_err(|e| format!("Failed to get recurrence: {}", e))?
+    else {
+        return Ok(None);
+    };
+    let exceptions = db::get_recurrence_exceptions(&conn, payload.id)
+        .map_err(|e| format!("Failed to get recurrence exceptions: {}", e))?;
+    
+    Ok(Some(RecurrenceView { recurrence, exceptions }))
+}
+
+// Skip, move or end the series at one occurrence. Occurrences not made yet follow it when they are;
+// ones already made are deleted or moved through the task service, so their calendar events follow too.
+// Completed occurrences are left alone.
+pub async fn add_recurrence_exception(db: &Database, app: &AppHandle, payload: RecurrenceExceptionData) -> Result<RecurrenceException, String> {
+    let offset = settings_service::day_offset(db, None)?;
+    let deadline = match (payload.kind, payload.deadline.as_deref()) {
+        (ExceptionKind::Reschedule, Some(deadline)) => Some(parse_datetime("deadline", deadline, offset)?),
+        (ExceptionKind::Reschedule, None) => return Err("Give the occurrence its new deadline".to_string()),
+        _ => None,
+    };
+    
+    let (exception, made) = {
+        let conn = db.get_connection();
+        let tx = conn.unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        
+        let recurrence = db::get_task_recurrence(&tx, payload.series_id)
+            .map_err(|e| format!("Failed to get recurrence: {}", e))?
+            .ok_or_else(|| format!("Task {} doesn't repeat", payload.series_id))?;
+        // The first occurrence is the task itself, edited or deleted as any other
+        if payload.occurrence_on <= recurrence.starts_on
+            || !recurrence::is_occurrence(recurrence.starts_on, recurrence.frequency, recurrence.interval, payload.occurrence_on)
+        {
+            return Err(format!("Task {} has no later occurrence on {}", payload.series_id, payload.occurrence_on));
+        }
+        
+        let exception = RecurrenceException {
+            series_id: payload.series_id,
+            occurrence_on: payload.occurrence_on,
+            kind: payload.kind,
+            deadline,
+            created_at: Utc::now(),
+        };
+        db::set_recurrence_exception(&tx, &exception)
+            .map_err(|e| format!("Failed to save recurrence exception: {}", e))?;
+        
+        let through = (exception.kind != ExceptionKind::EndSeries).then_some(exception.occurrence_on);
+        let made = db::get_recurrence_occurrence_tasks(&tx, exception.series_id, exception.occurrence_on, through)
+            .and_then(|ids| db::get_tasks_by_ids(&tx, &ids))
+            .map_err(|e| format!("Failed to get occurrences: {}", e))?;
+        
+        tx.commit().map_err(|e| format!("Failed to save recurrence exception: {}", e))?;
+        (exception, made)
+    }; // DB lock released here
+    
+    for task in made.into_iter().filter(|task| task.status != Status::Completed) {
+        let id = TaskId::from(task.id);
+        match exception.kind {
+            ExceptionKind::Reschedule => {
+                let data = TaskUpdateData { deadline: exception.deadline.map(|deadline| deadline.to_rfc3339()), ..rule_service::no_changes() };
+                task_service::update_task(TaskUpdate { id, data }, db, app).await?;
+            }
+            ExceptionKind::Skip | ExceptionKind::EndSeries => task_service::delete_task(TaskRef { id }, db, app).await?,
+        }
+    }
+    
+    info!("Recorded {:?} for task {} on {}", exception.kind, exception.series_id, exception.occurrence_on);
+    Ok(exception)
+}
+
+// Recurrence job: make tasks for the occurrences in the coming days. Each gets the series' settings and
+// deadline time, and calendar tasks have their event pushed as they're made.
+pub async fn generate_occurrences(app: &AppHandle) -> Result<(), String> {
+    let db = app.try_state::<Database>()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    let offset = settings_service::day_offset(&db, None)?;
+    let through = local_day(Utc::now(), offset) + Duration::days(DAYS_AHEAD);
+    
+    let recurrences = {
+        let conn = db.get_read_connection();
+        db::get_task_recurrences(&conn)
+            .map_err(|e| format!("Failed to get recurrences: {}", e))?
+    }; // DB lock released here
+    
+    let mut created = Vec::new();
+    for recurrence in recurrences.iter().filter(|recurrence| recurrence.generated_through < through) {
+        created.extend(generate(&db, recurrence, through, offset)?);
+    }
+    
+    for (task, journal_id) in &created {
+        event_service::emit_task_created(app, task);
+        rule_service::run_rules(app, RuleTrigger::TaskCreated, task);
+        if let Some(journal_id) = journal_id {
+            calendar_journal_service::sync_now(&db, *journal_id, TaskId::from(task.id)).await;
+        }
+    }
+    
+    if !created.is_empty() {
+        info!("Made {} occurrences of repeating tasks", created.len());
+    }
+    Ok(())
+}
+
+// One series' occurrences through `through`, made and recorded in one transaction so none is made twice
+fn generate(db: &Database, recurrence: &TaskRecurrence, through: NaiveDate, offset: FixedOffset) -> Result<Vec<(Task, Option<i64>)>, String> {
+    let conn = db.get_connection();
+    let tx = conn.unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    
+    let series = db::get_task_by_id(&tx, recurrence.task_id)
+        .map_err(|e| format!("Failed to get task: {}", e))?;
+    // Without a deadline there's no time of day to give the occurrences; they're made once it has one
+    let Some(series_deadline) = series.deadline else {
+        return Ok(Vec::new());
+    };
+    let time = series_deadline.with_timezone(&offset).time();
+    let exceptions = db::get_recurrence_exceptions(&tx, recurrence.task_id)
+        .map_err(|e| format!("Failed to get recurrence exceptions: {}", e))?;
+    
+    let now = Utc::now();
+    let mut created = Vec::new();
+    let occurrences = recurrence::occurrences_between(
+        recurrence.starts_on,
+        recurrence.frequency,
+        recurrence.interval,
+        recurrence.generated_through,
+        through,
+        &exceptions,
+    );
+    for occurrence in occurrences {
+        let deadline = occurrence.deadline.or_else(|| deadline_on(occurrence.day, time, &offset));
+        let task = Task {
+            deadline,
+            has_calendar_integration: series.has_calendar_integration,
+            calendar_email: series.calendar_email.clone(),
+            notifications_enabled: series.notifications_enabled,
+            estimate_minutes: series.estimate_minutes,
+            project_id: series.project_id,
+            assignee: series.assignee.clone(),
+            context: series.context.clone(),
+            energy: series.energy,
+            calendar_privacy: series.calendar_privacy,
+            ..Task::new(&series.title, now, series.notes.as_deref(), series.reminder_frequency.clone())
+        };
+        let task_id = TaskId::from(task.id);
+        db::insert(&tx, &task)
+            .and_then(|_| db::add_recurrence_occurrence(&tx, recurrence.task_id, occurrence.day, task_id))
+            .map_err(|e| format!("Failed to make occurrence of task {}: {}", recurrence.task_id, e))?;
+        
+        // Journaled with the insert so the event is still pushed if the app stops first
+        let journal_id = if task.has_calendar_integration && task.deadline.is_some() {
+            Some(calendar_journal_service::record(&tx, task_id, JournalOperation::Sync, None)?)
+        } else {
+            None
+        };
+        created.push((task, journal_id));
+    }
+    
+    db::set_recurrence_generated_through(&tx, recurrence.task_id, through)
+        .map_err(|e| format!("Failed to record occurrences: {}", e))?;
+    tx.commit().map_err(|e| format!("Failed to make occurrences: {}", e))?;
+    Ok(created)
+}
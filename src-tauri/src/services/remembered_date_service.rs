@@ -0,0 +1,125 @@
+use chrono::{Datelike, NaiveDate, Utc};
+use crate::db::{self, Database};
+use crate::helpers::parse_date::local_day;
+use crate::helpers::yearly::{is_valid_month_day, next_occurrence};
+use crate::services::settings_service;
+use crate::structs::remembered_date::{RememberedDate, RememberedDateData, RememberedDateId, RememberedDateUpdate, UpcomingDate};
+use tracing::info;
+
+const MAX_NAME_LENGTH: usize = 255;
+// Further ahead than this is better kept as a task
+const MAX_REMIND_DAYS: u32 = 60;
+
+pub fn add_remembered_date(db: &Database, payload: RememberedDateData) -> Result<UpcomingDate, String> {
+    let payload = validate(payload)?;
+    let today = today(db)?;
+    
+    let date = {
+        let conn = db.get_connection();
+        db::insert_remembered_date(&conn, &payload)
+            .and_then(|id| db::get_remembered_date_by_id(&conn, id))
+            .map_err(|e| format!("Failed to add date: {}", e))?
+    }; // DB lock released here
+    
+    info!("Added remembered date {}", date.id);
+    upcoming(date, today)
+}
+
+pub fn update_remembered_date(db: &Database, payload: RememberedDateUpdate) -> Result<UpcomingDate, String> {
+    let data = validate(payload.data)?;
+    let today = today(db)?;
+    
+    let date = {
+        let conn = db.get_connection();
+        let updated = db::update_remembered_date(&conn, payload.id, &data)
+            .map_err(|e| format!("Failed to update date: {}", e))?;
+        if updated == 0 {
+            return Err(format!("Date {} doesn't exist", payload.id));
+        }
+        db::get_remembered_date_by_id(&conn, payload.id)
+            .map_err(|e| format!("Failed to update date: {}", e))?
+    }; // DB lock released here
+    
+    upcoming(date, today)
+}
+
+pub fn delete_remembered_date(db: &Database, payload: RememberedDateId) -> Result<(), String> {
+    let conn = db.get_connection();
+    let deleted = db::delete_remembered_date(&conn, payload.id)
+        .map_err(|e| format!("Failed to delete date: {}", e))?;
+    
+    if deleted == 0 {
+        return Err(format!("Date {} doesn't exist", payload.id));
+    }
+    Ok(())
+}
+
+// Every remembered date, soonest first, counted from today in the settings offset
+pub fn list_remembered_dates(db: &Database) -> Result<Vec<UpcomingDate>, String> {
+    let today = today(db)?;
+    upcoming_from(db, today)
+}
+
+// Dates coming around within their reminder window of `day`, soonest first
+pub fn due_reminders(db: &Database, day: NaiveDate) -> Result<Vec<UpcomingDate>, String> {
+    Ok(upcoming_from(db, day)?
+        .into_iter()
+        .filter(|upcoming| upcoming.days_until <= upcoming.date.remind_days_before as i64)
+        .collect())
+}
+
+pub fn mark_reminded(db: &Database, upcoming: &UpcomingDate) -> Result<(), String> {
+    let conn = db.get_connection();
+    db::set_remembered_date_reminded(&conn, upcoming.date.id, upcoming.next_on)
+        .map_err(|e| format!("Failed to record reminder: {}", e))
+}
+
+fn upcoming_from(db: &Database, day: NaiveDate) -> Result<Vec<UpcomingDate>, String> {
+    let dates = {
+        let conn = db.get_read_connection();
+        db::get_remembered_dates(&conn)
+            .map_err(|e| format!("Failed to fetch dates: {}", e))?
+    }; // DB lock released here
+    
+    let mut upcoming = dates.into_iter()
+        .map(|date| upcoming(date, day))
+        .collect::<Result<Vec<_>, _>>()?;
+    upcoming.sort_by(|a, b| a.days_until.cmp(&b.days_until).then_with(|| a.date.name.cmp(&b.date.name)));
+    Ok(upcoming)
+}
+
+fn upcoming(date: RememberedDate, day: NaiveDate) -> Result<UpcomingDate, String> {
+    let next_on = next_occurrence(date.month, date.day, day)
+        .ok_or_else(|| format!("Date {} has an invalid day {}/{}", date.id, date.month, date.day))?;
+    Ok(UpcomingDate {
+        days_until: (next_on - day).num_days(),
+        years: date.year.map(|year| next_on.year() - year).filter(|years| *years >= 0),
+        next_on,
+        date,
+    })
+}
+
+fn today(db: &Database) -> Result<NaiveDate, String> {
+    let offset = settings_service::day_offset(db, None)?;
+    Ok(local_day(Utc::now(), offset))
+}
+
+fn validate(mut payload: RememberedDateData) -> Result<RememberedDateData, String> {
+    payload.name = payload.name.trim().to_string();
+    if payload.name.is_empty() {
+        return Err("Name cannot be empty".to_string());
+    }
+    if payload.name.chars().count() > MAX_NAME_LENGTH {
+        return Err(format!("Name can't be longer than {} characters", MAX_NAME_LENGTH));
+    }
+    if !is_valid_month_day(payload.month, payload.day) {
+        return Err(format!("Invalid date: month {}, day {}", payload.month, payload.day));
+    }
+    if payload.year.is_some_and(|year| !(1..=9999).contains(&year)) {
+        return Err("Year must be between 1 and 9999".to_string());
+    }
+    if payload.remind_days() > MAX_REMIND_DAYS {
+        return Err(format!("Reminders can be at most {} days ahead", MAX_REMIND_DAYS));
+    }
+    Ok(payload)
+}
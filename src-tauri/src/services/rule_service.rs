@@ -0,0 +1,246 @@
+use chrono::{Duration, Utc};
+use tauri::{AppHandle, Manager};
+use uuid::timestamp::context::NoContext;
+use uuid::{Timestamp, Uuid};
+use crate::db::{self, Database};
+use crate::helpers::tags;
+use crate::services::{event_service, holiday_service, task_service};
+use crate::structs::rule::{Rule, RuleAction, RuleData, RuleId, RuleTrigger, RuleUpdate, RuleUpdateParsed};
+use crate::structs::reminder_frequency::ReminderFrequency;
+use crate::structs::task_struct::{Task, TaskId};
+use crate::structs::task_update::{TaskUpdate, TaskUpdateData};
+use tracing::{info, warn, error};
+
+const MAX_NAME_LEN: usize = 255;
+const MAX_TAG_LEN: usize = 64;
+// Follow-ups and postponements further out than a year are almost certainly typos
+const MAX_DAYS: i64 = 365;
+
+// Run the task's matching rules in the background; a failing rule never fails the change that triggered it.
+// Tasks and edits made by rules don't trigger rules themselves, so rules can't feed each other.
+pub fn run_rules(app: &AppHandle, trigger: RuleTrigger, task: &Task) {
+    let app = app.clone();
+    let task = task.clone();
+    tauri::async_runtime::spawn(async move {
+        let rules = {
+            let db = app.state::<Database>();
+            let conn = db.get_read_connection();
+            db::get_rules(&conn)
+        };
+        let rules = match rules {
+            Ok(rules) => rules,
+            Err(e) => {
+                error!("Failed to fetch rules: {}", e);
+                return;
+            }
+        };
+        
+        for rule in rules.iter().filter(|rule| rule.enabled && rule.trigger == trigger && matches_tag(rule, &task)) {
+            apply(&app, rule, &task).await;
+        }
+    });
+}
+
+// Rules job: fire deadline-passed rules for open tasks whose deadline is behind us
+pub async fn run_deadline_rules(app: &AppHandle) -> Result<(), String> {
+    let due: Vec<(Rule, Task)> = {
+        let db = app.try_state::<Database>()
+            .ok_or_else(|| "Database not initialized".to_string())?;
+        let conn = db.get_connection();
+        
+        let rules: Vec<Rule> = db::get_rules(&conn)
+            .map_err(|e| format!("Failed to fetch rules: {}", e))?
+            .into_iter()
+            .filter(|rule| rule.enabled && rule.trigger == RuleTrigger::DeadlinePassed)
+            .collect();
+        if rules.is_empty() {
+            return Ok(());
+        }
+        let overdue = db::get_open_tasks_due_by(&conn, Utc::now())
+            .map_err(|e| format!("Failed to fetch overdue tasks: {}", e))?;
+        
+        let mut due = Vec::new();
+        for rule in &rules {
+            for task in overdue.iter().filter(|task| matches_tag(rule, task)) {
+                let Some(deadline) = task.deadline else {
+                    continue;
+                };
+                // Claimed before running, so a crash mid-action can't repeat it
+                if db::claim_rule_run(&conn, &rule.id, TaskId::from(task.id), deadline)
+                    .map_err(|e| format!("Failed to record rule run: {}", e))?
+                {
+                    due.push((rule.clone(), task.clone()));
+                }
+            }
+        }
+        due
+    }; // DB lock released here
+    
+    for (rule, task) in &due {
+        apply(app, rule, task).await;
+    }
+    Ok(())
+}
+
+async fn apply(app: &AppHandle, rule: &Rule, task: &Task) {
+    info!("Rule '{}' ({}) firing for task {}", rule.name, rule.id, task.id);
+    let db = app.state::<Database>();
+    
+    let result = match &rule.action {
+        RuleAction::CreateFollowUp { title, in_days } => {
+            let title = title.clone().unwrap_or_else(|| format!("Follow up: {}", task.title));
+            // Tasks are planned by their created date, so the follow-up shows up on that day
+            holiday_service::shift_to_working_day(&db, Utc::now() + Duration::days(*in_days))
+                .and_then(|planned_at| task_service::add_task(&title, planned_at, None, &db))
+                .map(|follow_up| event_service::emit_task_created(app, &follow_up))
+        }
+        RuleAction::SetReminder { frequency } => {
+            let data = TaskUpdateData { reminder_frequency: Some(frequency.clone()), ..no_changes() };
+            task_service::update_task(TaskUpdate { id: task.id.into(), data }, &db, app).await.map(|_| ())
+        }
+        RuleAction::PostponeDeadline { days } => async {
+            // From now when it already passed, so the new deadline isn't overdue too
+            let from = task.deadline.map_or(Utc::now(), |deadline| deadline.max(Utc::now()));
+            let deadline = holiday_service::shift_to_working_day(&db, from + Duration::days(*days))?;
+            let data = TaskUpdateData { deadline: Some(deadline.to_rfc3339()), ..no_changes() };
+            task_service::update_task(TaskUpdate { id: task.id.into(), data }, &db, app).await.map(|_| ())
+        }.await,
+    };
+    
+    if let Err(e) = result {
+        warn!("Rule '{}' failed for task {}: {}", rule.name, task.id, e);
+    }
+}
+
+// An update that leaves every field as it is, to set just the ones an action changes
+pub fn no_changes() -> TaskUpdateData {
+    TaskUpdateData {
+        title: None,
+        notes: None,
+        deadline: None,
+        has_calendar_integration: None,
+        calendar_email: None,
+        reminder_frequency: None,
+        notifications_enabled: None,
+        estimate_minutes: None,
+        project_id: None,
+        assignee: None,
+        context: None,
+        energy: None,
+        calendar_privacy: None,
+    }
+}
+
+fn matches_tag(rule: &Rule, task: &Task) -> bool {
+    let Some(tag) = &rule.tag else {
+        return true;
+    };
+    let text = format!("{} {}", task.title, task.notes.as_deref().unwrap_or(""));
+    tags::extract_tags(&text).contains(&tag.to_lowercase())
+}
+
+pub fn list_rules(db: &Database) -> Result<Vec<Rule>, String> {
+    let conn = db.get_read_connection();
+    db::get_rules(&conn)
+        .map_err(|e| format!("Failed to fetch rules: {}", e))
+}
+
+pub fn create_rule(db: &Database, payload: RuleData) -> Result<Rule, String> {
+    validate_action(&payload.action)?;
+    
+    let rule = Rule {
+        id: Uuid::new_v7(Timestamp::now(NoContext)),
+        name: parse_name(&payload.name)?,
+        trigger: payload.trigger,
+        tag: payload.tag.as_deref().map(parse_tag).transpose()?.flatten(),
+        action: payload.action,
+        enabled: true,
+        created_at: Utc::now(),
+    };
+    
+    let conn = db.get_connection();
+    db::insert(&conn, &rule)
+        .map_err(|e| format!("Failed to create rule: {}", e))?;
+    
+    info!("Created rule {}", rule.id);
+    Ok(rule)
+}
+
+pub fn update_rule(db: &Database, payload: RuleUpdate) -> Result<Rule, String> {
+    let data = payload.data;
+    if let Some(action) = &data.action {
+        validate_action(action)?;
+    }
+    
+    let update = RuleUpdateParsed {
+        name: data.name.as_deref().map(parse_name).transpose()?,
+        trigger: data.trigger,
+        tag: data.tag.as_deref().map(parse_tag).transpose()?,
+        action: data.action,
+        enabled: data.enabled,
+    };
+    
+    let conn = db.get_connection();
+    db::update_rule(&conn, &payload.id, &update)
+        .map_err(|e| match e {
+            rusqlite::Error::QueryReturnedNoRows => format!("No rule with ID {}", payload.id),
+            e => format!("Failed to update rule: {}", e),
+        })
+}
+
+pub fn delete_rule(db: &Database, payload: RuleId) -> Result<(), String> {
+    let conn = db.get_connection();
+    let deleted = db::delete_rule_by_id(&conn, &payload.id)
+        .map_err(|e| format!("Failed to delete rule: {}", e))?;
+    
+    if deleted == 0 {
+        return Err(format!("No rule with ID {}", payload.id));
+    }
+    Ok(())
+}
+
+fn parse_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Rule name cannot be empty".to_string());
+    }
+    if name.chars().count() > MAX_NAME_LEN {
+        return Err(format!("Rule name is longer than {} characters", MAX_NAME_LEN));
+    }
+    Ok(name.to_string())
+}
+
+// "#errand" and "errand" are the same tag; empty means no tag
+fn parse_tag(tag: &str) -> Result<Option<String>, String> {
+    let tag = tag.trim();
+    let tag = tag.strip_prefix('#').unwrap_or(tag);
+    if tag.is_empty() {
+        return Ok(None);
+    }
+    if tag.len() > MAX_TAG_LEN || !tag.chars().all(tags::is_tag_char) {
+        return Err(format!("Invalid tag '{}': use letters, digits, '-' and '_'", tag));
+    }
+    Ok(Some(tag.to_string()))
+}
+
+fn validate_action(action: &RuleAction) -> Result<(), String> {
+    match action {
+        RuleAction::CreateFollowUp { title, in_days } => {
+            if title.as_deref().is_some_and(|title| title.trim().is_empty()) {
+                return Err("Follow-up title cannot be empty".to_string());
+            }
+            if !(0..=MAX_DAYS).contains(in_days) {
+                return Err(format!("Follow-up must be 0 to {} days out", MAX_DAYS));
+            }
+        }
+        RuleAction::SetReminder { frequency } => {
+            frequency.parse::<ReminderFrequency>()?;
+        }
+        RuleAction::PostponeDeadline { days } => {
+            if !(1..=MAX_DAYS).contains(days) {
+                return Err(format!("Deadline can be postponed by 1 to {} days", MAX_DAYS));
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,159 @@
+use std::collections::HashSet;
+use chrono::{Duration, Utc};
+use tauri::AppHandle;
+use crate::db::{self, Database};
+use crate::helpers::parse_date::{local_day, parse_date_range};
+use crate::services::{event_service, holiday_service, settings_service};
+use crate::structs::schedule::{ApplyScheduleData, ScheduleDay, SchedulePlan, ScheduleQuery, ScheduleReason, ScheduleSuggestion};
+use crate::structs::task_struct::{Task, TaskId};
+use tracing::info;
+
+const MAX_HORIZON_DAYS: u32 = 60;
+// Counted for tasks without an estimate, so they still take up room
+const DEFAULT_ESTIMATE_MINUTES: i64 = 30;
+
+// Propose days for overdue and carried-over tasks: earliest deadline first, each on the first day
+// with room left under the working day from settings, and no later than its deadline if possible.
+// Weekends and holidays get no room when settings say to skip them.
+pub fn suggest_schedule(db: &Database, payload: ScheduleQuery) -> Result<SchedulePlan, String> {
+    if !(1..=MAX_HORIZON_DAYS).contains(&payload.horizon_days) {
+        return Err(format!("Schedule must cover 1 to {} days", MAX_HORIZON_DAYS));
+    }
+    
+    // Before the lock: settings may be read through the same connection
+    let offset = settings_service::day_offset(db, payload.utc_offset_minutes)?;
+    let capacity_minutes = db.settings()
+        .map_err(|e| format!("Failed to fetch settings: {}", e))?
+        .work_day_minutes as i64;
+    
+    let now = Utc::now();
+    let today = local_day(now, offset);
+    let (today_start, _) = parse_date_range("date", &now.to_rfc3339(), Some(offset))?;
+    let horizon_end = today_start + Duration::days(payload.horizon_days as i64);
+    let calendar = holiday_service::working_calendar(db, today, today + Duration::days(payload.horizon_days as i64))?;
+    
+    let tasks = {
+        let conn = db.get_read_connection();
+        db::get_open_tasks_planned_before(&conn, horizon_end)
+            .map_err(|e| format!("Failed to query tasks: {}", e))?
+    }; // DB lock released here
+    
+    let mut days: Vec<ScheduleDay> = today.iter_days()
+        .take(payload.horizon_days as usize)
+        .map(|day| ScheduleDay {
+            date: day.format("%Y-%m-%d").to_string(),
+            working: calendar.is_working_day(day),
+            capacity_minutes: if calendar.is_working_day(day) { capacity_minutes } else { 0 },
+            planned_minutes: 0,
+            suggested_minutes: 0,
+        })
+        .collect();
+    
+    // Tasks already on a day in the horizon keep their day and use up its room
+    let mut candidates: Vec<(Task, ScheduleReason)> = Vec::new();
+    for task in tasks {
+        let reason = if task.deadline.is_some_and(|deadline| deadline < now) {
+            ScheduleReason::Overdue
+        } else if local_day(task.created_at, offset) < today {
+            ScheduleReason::CarriedOver
+        } else {
+            let index = (local_day(task.created_at, offset) - today).num_days() as usize;
+            if let Some(day) = days.get_mut(index) {
+                day.planned_minutes += minutes_for(&task);
+            }
+            continue;
+        };
+        candidates.push((task, reason));
+    }
+    // Overdue deadlines sort before upcoming ones; tasks without a deadline go last, oldest first
+    candidates.sort_by_key(|(task, _)| (task.deadline.is_none(), task.deadline, task.created_at));
+    
+    let mut suggestions = Vec::new();
+    let mut unplaced = Vec::new();
+    for (task, reason) in candidates {
+        let minutes = minutes_for(&task);
+        // Last day index the deadline allows; none for passed deadlines or no deadline
+        let last_index = task.deadline
+            .map(|deadline| (local_day(deadline, offset) - today).num_days())
+            .filter(|index| *index >= 0);
+        
+        // An empty working day takes a task bigger than the working day rather than leave it unplaced
+        let fits = |day: &ScheduleDay| {
+            let used = day.planned_minutes + day.suggested_minutes;
+            day.working && (used == 0 || used + minutes <= day.capacity_minutes)
+        };
+        let before_deadline = days.iter()
+            .enumerate()
+            .position(|(index, day)| last_index.map_or(true, |last| index as i64 <= last) && fits(day));
+        let (index, after_deadline) = match before_deadline {
+            Some(index) => (index, false),
+            None => match days.iter().position(fits) {
+                Some(index) => (index, true),
+                None => {
+                    unplaced.push(TaskId::from(task.id));
+                    continue;
+                }
+            },
+        };
+        
+        let day = &mut days[index];
+        day.suggested_minutes += minutes;
+        suggestions.push(ScheduleSuggestion {
+            task_id: TaskId::from(task.id),
+            title: task.title,
+            date: day.date.clone(),
+            reason,
+            deadline: task.deadline,
+            minutes,
+            estimated: task.estimate_minutes.is_some(),
+            after_deadline,
+        });
+    }
+    
+    Ok(SchedulePlan { suggestions, days, unplaced })
+}
+
+// Move the accepted tasks to their days in one transaction; none move if any can't
+pub fn apply_schedule(db: &Database, app: &AppHandle, payload: ApplyScheduleData) -> Result<Vec<Task>, String> {
+    if payload.items.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut seen = HashSet::new();
+    if let Some(item) = payload.items.iter().find(|item| !seen.insert(item.task_id)) {
+        return Err(format!("Task {} is in the schedule more than once", item.task_id));
+    }
+    
+    let offset = settings_service::day_offset(db, payload.utc_offset_minutes)?;
+    let planned: Vec<(TaskId, _)> = payload.items.iter()
+        .map(|item| parse_date_range("date", &item.date, Some(offset)).map(|(start, _)| (item.task_id, start)))
+        .collect::<Result<_, _>>()?;
+    
+    let tasks = {
+        let conn = db.get_connection();
+        let tx = conn.unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        
+        let mut tasks = Vec::with_capacity(planned.len());
+        for (task_id, planned_at) in planned {
+            let task = db::reschedule_task(&tx, task_id, planned_at)
+                .map_err(|e| match e {
+                    rusqlite::Error::QueryReturnedNoRows => format!("Task {} doesn't exist or is already completed", task_id),
+                    e => format!("Failed to reschedule task {}: {}", task_id, e),
+                })?;
+            tasks.push(task);
+        }
+        
+        tx.commit().map_err(|e| format!("Failed to apply schedule: {}", e))?;
+        tasks
+    }; // DB lock released here
+    
+    info!("Applied schedule for {} tasks", tasks.len());
+    for task in &tasks {
+        event_service::emit_task_updated(app, task);
+    }
+    Ok(tasks)
+}
+
+fn minutes_for(task: &Task) -> i64 {
+    task.estimate_minutes.map_or(DEFAULT_ESTIMATE_MINUTES, |minutes| minutes as i64)
+}
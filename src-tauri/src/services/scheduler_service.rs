@@ -0,0 +1,324 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use chrono::Utc;
+use tauri::{AppHandle, Manager};
+use crate::db::{self, Database};
+use crate::services::{calendar_rule_service, calendar_service, changelog_service, connectivity_service, idle_service, lan_sync_service, lock_service, metrics_service, notification_service, project_service, recurrence_service, rule_service, snapshot_service, sync_service, webhook_service, work_session_service};
+use crate::structs::job::{Job, Schedule};
+use tracing::error;
+
+// How often the scheduler looks for due jobs
+const TICK_INTERVAL_SECS: u64 = 30;
+
+// Set on app exit: no new job runs start once this is true
+static STOPPING: AtomicBool = AtomicBool::new(false);
+// Jobs currently executing, so shutdown can wait for them
+static RUNNING_JOBS: AtomicUsize = AtomicUsize::new(0);
+
+// Jobs known to the scheduler; state for each lives in the `jobs` table
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JobKind {
+    DeadlineNotifications,
+    Maintenance,
+    IdleDetection,
+    JsonSnapshot,
+    UsageMetrics,
+    DailySummary,
+    Sync,
+    LanSync,
+    WebhookDelivery,
+    Rules,
+    AppLock,
+    SharedProjects,
+    WorkSessions,
+    RememberedDates,
+    EstimateChecks,
+    Connectivity,
+    CalendarReauth,
+    CalendarPull,
+    Recurrence,
+}
+
+impl JobKind {
+    pub const ALL: [JobKind; 19] = [
+        JobKind::DeadlineNotifications,
+        JobKind::Maintenance,
+        JobKind::IdleDetection,
+        JobKind::JsonSnapshot,
+        JobKind::UsageMetrics,
+        JobKind::DailySummary,
+        JobKind::Sync,
+        JobKind::LanSync,
+        JobKind::WebhookDelivery,
+        JobKind::Rules,
+        JobKind::AppLock,
+        JobKind::SharedProjects,
+        JobKind::WorkSessions,
+        JobKind::RememberedDates,
+        JobKind::EstimateChecks,
+        JobKind::Connectivity,
+        JobKind::CalendarReauth,
+        JobKind::CalendarPull,
+        JobKind::Recurrence,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            JobKind::DeadlineNotifications => "deadline-notifications",
+            JobKind::Maintenance => "maintenance",
+            JobKind::IdleDetection => "idle-detection",
+            JobKind::JsonSnapshot => "json-snapshot",
+            JobKind::UsageMetrics => "usage-metrics",
+            JobKind::DailySummary => "daily-summary",
+            JobKind::Sync => "sync",
+            JobKind::LanSync => "lan-sync",
+            JobKind::WebhookDelivery => "webhook-delivery",
+            JobKind::Rules => "rules",
+            JobKind::AppLock => "app-lock",
+            JobKind::SharedProjects => "shared-projects",
+            JobKind::WorkSessions => "work-sessions",
+            JobKind::RememberedDates => "remembered-dates",
+            JobKind::EstimateChecks => "estimate-checks",
+            JobKind::Connectivity => "connectivity",
+            JobKind::CalendarReauth => "calendar-reauth",
+            JobKind::CalendarPull => "calendar-pull",
+            JobKind::Recurrence => "recurrence",
+        }
+    }
+
+    pub fn default_schedule(&self) -> &'static str {
+        match self {
+            JobKind::DeadlineNotifications => "every:60",
+            JobKind::Maintenance => "daily:03:00",
+            JobKind::IdleDetection => "every:30",
+            // Unchanged data is skipped, so frequent runs cost little
+            JobKind::JsonSnapshot => "every:21600",
+            // Stores counts kept in memory; nothing to do while metrics are off
+            JobKind::UsageMetrics => "every:600",
+            // Moved to the time in settings by set_daily_summary_time
+            JobKind::DailySummary => "daily:18:00",
+            // Picks up other devices' changes; nothing to do while sync is off
+            JobKind::Sync => "every:300",
+            // Pulls from paired devices that are online; nothing to do while LAN sync is off
+            JobKind::LanSync => "every:300",
+            // Retries deliveries that failed; new events are sent as they happen
+            JobKind::WebhookDelivery => "every:60",
+            // Deadline-passed rules; the other triggers run as tasks change
+            JobKind::Rules => "every:60",
+            // Locks after the idle time of the app lock; nothing to do without one
+            JobKind::AppLock => "every:30",
+            // Exchanges changes with the people projects are shared with; nothing to do without shared projects
+            JobKind::SharedProjects => "every:300",
+            // Puts the day's work on the calendar; only marks sessions skipped while posting is off
+            JobKind::WorkSessions => "daily:23:30",
+            // Birthdays and the like coming up; each is notified once a year
+            JobKind::RememberedDates => "daily:09:00",
+            // Running tasks past the estimate threshold in settings; nothing to do while it's 0
+            JobKind::EstimateChecks => "every:60",
+            // Flips online/offline; calendar calls fail fast while offline and are sent once it's back
+            JobKind::Connectivity => "every:30",
+            // Asks for the calendar account again once Google refused it; nothing to do otherwise
+            JobKind::CalendarReauth => "every:60",
+            // Makes tasks from events the calendar event rules match; nothing to do without enabled rules
+            JobKind::CalendarPull => "every:900",
+            // Makes the coming week's occurrences of repeating tasks; nothing to do without any
+            JobKind::Recurrence => "every:3600",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        JobKind::ALL.iter().copied().find(|kind| kind.name() == name)
+    }
+}
+
+// Seed job definitions and spawn the scheduler loop
+pub fn start_scheduler(app: AppHandle) {
+    if let Err(e) = register_jobs(&app) {
+        error!("Failed to register scheduler jobs: {}", e);
+        return;
+    }
+    
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(TICK_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+            if STOPPING.load(Ordering::SeqCst) {
+                break;
+            }
+            if let Err(e) = run_due_jobs(&app).await {
+                error!("Scheduler tick failed: {}", e);
+            }
+        }
+    });
+}
+
+// Stop starting jobs and wait up to `timeout` for running ones; false if some were still running
+pub fn stop_scheduler(timeout: Duration) -> bool {
+    STOPPING.store(true, Ordering::SeqCst);
+    
+    let deadline = Instant::now() + timeout;
+    while RUNNING_JOBS.load(Ordering::SeqCst) > 0 {
+        if Instant::now() >= deadline {
+            return false;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    
+    true
+}
+
+fn register_jobs(app: &AppHandle) -> Result<(), String> {
+    let db = app.try_state::<Database>()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    let conn = db.get_connection();
+    
+    let now = Utc::now();
+    for kind in JobKind::ALL {
+        // Validates the built-in schedule string as well
+        let schedule = Schedule::parse(kind.default_schedule())?;
+        db::register_job(&conn, kind.name(), kind.default_schedule(), schedule.next_after(now))
+            .map_err(|e| format!("Failed to register job '{}': {}", kind.name(), e))?;
+    }
+    
+    let settings = db::get_settings(&conn)
+        .map_err(|e| format!("Failed to fetch settings: {}", e))?;
+    set_job_time(&conn, JobKind::DailySummary, &settings.daily_summary_time)
+}
+
+// Run the end-of-day summary at `time` (local HH:MM) from now on
+pub fn set_daily_summary_time(db: &Database, time: &str) -> Result<(), String> {
+    let conn = db.get_connection();
+    set_job_time(&conn, JobKind::DailySummary, time)
+}
+
+// Reschedule a daily job; left alone when the time is unchanged so a pending run isn't skipped
+fn set_job_time(conn: &rusqlite::Connection, kind: JobKind, time: &str) -> Result<(), String> {
+    let schedule = Schedule::parse(&format!("daily:{}", time))?;
+    let job = db::get_job_by_name(conn, kind.name())
+        .map_err(|e| format!("Failed to get job '{}': {}", kind.name(), e))?;
+    if Schedule::parse(&job.schedule).ok().as_ref() == Some(&schedule) {
+        return Ok(());
+    }
+    
+    db::set_job_schedule(conn, kind.name(), &schedule.to_string(), schedule.next_after(Utc::now()))
+        .map_err(|e| format!("Failed to reschedule job '{}': {}", kind.name(), e))
+}
+
+async fn run_due_jobs(app: &AppHandle) -> Result<(), String> {
+    let due_jobs = {
+        let db = app.try_state::<Database>()
+            .ok_or_else(|| "Database not initialized".to_string())?;
+        // Jobs wait for the app to be unlocked: they'd see sealed notes and credentials
+        if db.is_locked() {
+            return Ok(());
+        }
+        let conn = db.get_connection();
+        db::get_due_jobs(&conn, Utc::now())
+            .map_err(|e| format!("Failed to query due jobs: {}", e))?
+    }; // DB lock released here
+    
+    for job in due_jobs {
+        let _ = execute_job(app, &job).await;
+    }
+    
+    Ok(())
+}
+
+// Run a job and persist its last-run/last-error state
+async fn execute_job(app: &AppHandle, job: &Job) -> Result<(), String> {
+    if STOPPING.load(Ordering::SeqCst) {
+        return Err("Scheduler is shutting down".to_string());
+    }
+    
+    let started_at = Utc::now();
+    
+    RUNNING_JOBS.fetch_add(1, Ordering::SeqCst);
+    let result = match JobKind::from_name(&job.name) {
+        Some(kind) => run_job(app, kind).await,
+        None => Err(format!("Unknown job: {}", job.name)),
+    };
+    RUNNING_JOBS.fetch_sub(1, Ordering::SeqCst);
+    
+    if let Err(ref e) = result {
+        error!("Job '{}' failed: {}", job.name, e);
+    }
+    
+    let next_run_at = Schedule::parse(&job.schedule)
+        .map(|schedule| schedule.next_after(started_at))
+        .unwrap_or_else(|_| started_at + chrono::Duration::hours(24));
+    
+    let db = app.try_state::<Database>()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    let conn = db.get_connection();
+    db::record_job_run(&conn, &job.name, started_at, result.as_ref().err().map(|e| e.as_str()), next_run_at)
+        .map_err(|e| format!("Failed to record job run: {}", e))?;
+    
+    result
+}
+
+async fn run_job(app: &AppHandle, kind: JobKind) -> Result<(), String> {
+    match kind {
+        JobKind::DeadlineNotifications => notification_service::check_deadlines(app),
+        JobKind::Maintenance => run_maintenance(app),
+        JobKind::IdleDetection => idle_service::check_idle(app).await,
+        JobKind::JsonSnapshot => snapshot_service::write_snapshot(app),
+        JobKind::UsageMetrics => flush_usage_metrics(app),
+        JobKind::DailySummary => notification_service::send_daily_summary(app),
+        JobKind::Sync => sync_service::sync_now(app).await.map(|_| ()),
+        JobKind::LanSync => lan_sync_service::sync_lan_now(app).await.map(|_| ()),
+        JobKind::WebhookDelivery => webhook_service::deliver_due(app).await,
+        JobKind::Rules => rule_service::run_deadline_rules(app).await,
+        JobKind::AppLock => lock_service::lock_if_idle(app),
+        JobKind::SharedProjects => project_service::sync_shared_projects(app).await.map(|_| ()),
+        JobKind::WorkSessions => work_session_service::post_work_sessions(app).await.map(|_| ()),
+        JobKind::RememberedDates => notification_service::check_remembered_dates(app),
+        JobKind::EstimateChecks => notification_service::check_estimates(app),
+        JobKind::Connectivity => connectivity_service::check(app).await,
+        JobKind::CalendarReauth => calendar_service::prompt_reauth(app),
+        JobKind::CalendarPull => calendar_rule_service::pull_calendar_events(app).await.map(|_| ()),
+        JobKind::Recurrence => recurrence_service::generate_occurrences(app).await,
+    }
+}
+
+fn flush_usage_metrics(app: &AppHandle) -> Result<(), String> {
+    let db = app.try_state::<Database>()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    metrics_service::flush(&db)
+}
+
+fn run_maintenance(app: &AppHandle) -> Result<(), String> {
+    let db = app.try_state::<Database>()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    changelog_service::compact(&db)?;
+    webhook_service::prune_deliveries(&db)?;
+    
+    let conn = db.get_connection();
+    conn.execute_batch("PRAGMA optimize;")
+        .map_err(|e| format!("Failed to optimize database: {}", e))
+}
+
+pub fn list_jobs(db: &Database) -> Result<Vec<Job>, String> {
+    let conn = db.get_connection();
+    
+    db::get_jobs(&conn)
+        .map_err(|e| format!("Failed to list jobs: {}", e))
+}
+
+// Run a job immediately, outside its schedule
+pub async fn run_job_now(app: &AppHandle, name: &str) -> Result<Job, String> {
+    let job = {
+        let db = app.try_state::<Database>()
+            .ok_or_else(|| "Database not initialized".to_string())?;
+        let conn = db.get_connection();
+        db::get_job_by_name(&conn, name)
+            .map_err(|e| format!("Failed to get job '{}': {}", name, e))?
+    }; // DB lock released here
+    
+    execute_job(app, &job).await?;
+    
+    let db = app.try_state::<Database>()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    let conn = db.get_connection();
+    db::get_job_by_name(&conn, name)
+        .map_err(|e| format!("Failed to get job '{}': {}", name, e))
+}
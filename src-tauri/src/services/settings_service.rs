@@ -1,19 +1,99 @@
+use chrono::{FixedOffset, NaiveTime};
 use crate::db::{self, Database};
+use crate::helpers::format::Formatter;
+use crate::helpers::parse_date::offset_from_minutes;
+use crate::secrets;
+use crate::services::stats_service;
+use crate::structs::reminder_frequency::ReminderFrequency;
 use crate::structs::settings::{Settings, SettingsUpdateData};
+use crate::structs::theme::{Theme, ThemeUpdateData};
 
 pub fn get_settings(db: &Database) -> Result<Settings, String> {
-    let conn = db.get_connection();
-    
-    db::get_settings(&conn)
+    db.settings()
         .map_err(|e| format!("Failed to fetch settings: {}", e))
 }
 
+// A secret set through settings (HTTP API token, Slack signing secret, sync password and passphrase);
+// these are kept in the secrets store rather than on the settings row
+pub fn secret(db: &Database, namespace: &str, name: &str) -> Result<Option<String>, String> {
+    let conn = db.get_read_connection();
+    secrets::get(&conn, namespace, name)
+}
+
+// Offset task days start at: the one the request sent, else the one in settings
+pub fn day_offset(db: &Database, requested: Option<i32>) -> Result<FixedOffset, String> {
+    let minutes = match requested {
+        Some(minutes) => minutes,
+        None => get_settings(db)?.utc_offset_minutes,
+    };
+    offset_from_minutes(minutes)
+}
+
+// Reminder frequency a new task gets: the one the request sent, else the default in settings
+pub fn reminder_frequency(db: &Database, requested: Option<ReminderFrequency>) -> Result<ReminderFrequency, String> {
+    match requested {
+        Some(frequency) => Ok(frequency),
+        None => Ok(get_settings(db)?.default_reminder_frequency),
+    }
+}
+
+// Time of day a deadline sent as a bare date gets, in the day offset
+pub fn default_deadline_time(db: &Database) -> Result<NaiveTime, String> {
+    let time = get_settings(db)?.default_deadline_time;
+    NaiveTime::parse_from_str(&time, "%H:%M")
+        .map_err(|_| format!("Invalid default deadline time in settings: {}", time))
+}
+
+// Date and time formatting in the locale from settings
+pub fn formatter(db: &Database) -> Result<Formatter, String> {
+    Ok(Formatter::new(get_settings(db)?.locale))
+}
+
 pub fn update_settings(db: &Database, data: SettingsUpdateData) -> Result<Settings, String> {
     // Parse and validate the update data
-    let parsed = data.parse()?;
+    let (parsed, secret_changes) = data.parse()?;
+    // Day summaries are cut at the settings offset, so moving it recounts them
+    let offset_changed = match parsed.utc_offset_minutes {
+        Some(minutes) => minutes != get_settings(db)?.utc_offset_minutes,
+        None => false,
+    };
     
     // Update settings in database
     let conn = db.get_connection();
-    db::update_settings(&conn, &parsed)
-        .map_err(|e| format!("Failed to update settings: {}", e))
+    let tx = conn.unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    let settings = db::update_settings(&tx, &parsed)
+        .map_err(|e| format!("Failed to update settings: {}", e))?;
+    for (namespace, name, value) in secret_changes.changes() {
+        match value {
+            Some(value) => secrets::store(&tx, namespace, name, value)?,
+            None => secrets::delete(&tx, namespace, name)?,
+        }
+    }
+    if offset_changed {
+        db::rebuild_day_summaries(&tx)
+            .map_err(|e| format!("Failed to recount day summaries: {}", e))?;
+    }
+    tx.commit().map_err(|e| format!("Failed to update settings: {}", e))?;
+    if offset_changed {
+        stats_service::clear_cache();
+    }
+    
+    Ok(settings)
+}
+
+pub fn get_theme(db: &Database) -> Result<Theme, String> {
+    let conn = db.get_connection();
+    
+    db::get_theme(&conn)
+        .map_err(|e| format!("Failed to fetch theme: {}", e))
+}
+
+pub fn update_theme(db: &Database, data: ThemeUpdateData) -> Result<Theme, String> {
+    // Validate accent color, font scale and density
+    let parsed = data.parse()?;
+    
+    let conn = db.get_connection();
+    db::update_theme(&conn, &parsed)
+        .map_err(|e| format!("Failed to update theme: {}", e))
 }
@@ -0,0 +1,84 @@
+use chrono::Utc;
+use serde::Serialize;
+use tauri::AppHandle;
+use crate::db::Database;
+use crate::services::task_service;
+use crate::structs::dto::{DateQuery, TaskData};
+use crate::structs::task_struct::Status;
+use tracing::info;
+
+const HELP: &str = "Usage: `add <title>` to create a task, `today` to list today's tasks";
+
+// Reply body Slack shows in the channel; ephemeral replies are only seen by whoever ran the command
+#[derive(Serialize)]
+pub struct SlackReply {
+    pub response_type: &'static str,
+    pub text: String,
+}
+
+// Run a slash command from Slack's form-encoded request body
+pub fn handle_command(db: &Database, app: &AppHandle, body: &str) -> Result<SlackReply, String> {
+    let input = form_value(body, "text").unwrap_or_default();
+    let user = form_value(body, "user_name").unwrap_or_default();
+    let (action, rest) = input.trim().split_once(' ').unwrap_or((input.trim(), ""));
+    info!("Slack command '{}' from {}", action, user);
+    
+    let text = match action.to_lowercase().as_str() {
+        "add" => add_task(db, app, rest.trim())?,
+        "" | "today" | "list" => list_today(db)?,
+        _ => HELP.to_string(),
+    };
+    
+    Ok(SlackReply {
+        response_type: "ephemeral",
+        text,
+    })
+}
+
+fn add_task(db: &Database, app: &AppHandle, title: &str) -> Result<String, String> {
+    if title.is_empty() {
+        return Ok(HELP.to_string());
+    }
+    
+    let payload = TaskData {
+        title: title.to_string(),
+        created_at: Utc::now().to_rfc3339(),
+        idempotency_key: None,
+        reminder_frequency: None,
+    };
+    let task = task_service::create_task(payload, db, app)?;
+    Ok(format!("Added: {}", escape(&task.title)))
+}
+
+fn list_today(db: &Database) -> Result<String, String> {
+    let tasks = task_service::get_tasks_by_date(DateQuery { date: Utc::now().to_rfc3339(), utc_offset_minutes: None }, db)?;
+    if tasks.is_empty() {
+        return Ok("No tasks for today".to_string());
+    }
+    
+    let open = tasks.iter().filter(|item| item.task.status != Status::Completed).count();
+    let mut text = format!("*Today* ({} of {} open)", open, tasks.len());
+    for task in tasks.iter().map(|item| &item.task) {
+        let mark = match task.status {
+            Status::Completed => "✓",
+            Status::Ongoing => "▸",
+            Status::Paused => "⏸",
+            Status::NotStarted => "•",
+        };
+        text.push_str(&format!("\n{} {}", mark, escape(&task.title)));
+    }
+    
+    Ok(text)
+}
+
+fn form_value(body: &str, name: &str) -> Option<String> {
+    body.split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| urlencoding::decode(&value.replace('+', " ")).unwrap_or_default().to_string())
+}
+
+// Slack treats these as control characters in message text
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
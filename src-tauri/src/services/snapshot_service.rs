@@ -0,0 +1,113 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use chrono::Utc;
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
+use crate::db::{self, Database};
+use crate::services::{data_service, recovery_service};
+use tracing::{debug, info, warn};
+
+// Human-readable copies of the data, next to the database and separate from raw backups
+const SNAPSHOT_DIR: &str = "snapshots";
+const SNAPSHOT_PREFIX: &str = "snapshot-";
+// Oldest snapshots beyond this are deleted
+const MAX_SNAPSHOTS: usize = 30;
+
+// Write a JSON snapshot in the export format, unless nothing changed since the last one
+pub fn write_snapshot(app: &AppHandle) -> Result<(), String> {
+    // The fallback database is empty; a snapshot of it would only push out real ones
+    if recovery_service::is_active(app) {
+        return Ok(());
+    }
+    
+    let db = app.try_state::<Database>()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    let export = {
+        let conn = db.get_connection();
+        data_service::build_export(&conn, app)?
+    }; // DB lock released here
+    
+    let dir = get_snapshot_dir(app)?;
+    fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create snapshot folder: {}", e))?;
+    
+    let value = serde_json::to_value(&export)
+        .map_err(|e| format!("Failed to serialize snapshot: {}", e))?;
+    let snapshots = list_snapshots(&dir);
+    if let Some(latest) = snapshots.last() {
+        if same_content(latest, &value) {
+            debug!("Data unchanged since {:?}, skipping snapshot", latest);
+            return Ok(());
+        }
+    }
+    
+    let path = dir.join(format!("{}{}.json", SNAPSHOT_PREFIX, Utc::now().format("%Y%m%d-%H%M%S")));
+    let json = serde_json::to_string_pretty(&value)
+        .map_err(|e| format!("Failed to serialize snapshot: {}", e))?;
+    fs::write(&path, json)
+        .map_err(|e| format!("Failed to write snapshot: {}", e))?;
+    info!("Wrote snapshot {:?}", path);
+    
+    prune(&list_snapshots(&dir));
+    Ok(())
+}
+
+pub fn open_snapshot_folder(app: &AppHandle) -> Result<(), String> {
+    let dir = get_snapshot_dir(app)?;
+    fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create snapshot folder: {}", e))?;
+    opener::open(&dir)
+        .map_err(|e| format!("Failed to open snapshot folder: {}", e))
+}
+
+fn get_snapshot_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let db_path = db::get_db_path(app)
+        .map_err(|e| format!("Failed to get database path: {}", e))?;
+    db_path.parent()
+        .map(|dir| dir.join(SNAPSHOT_DIR))
+        .ok_or_else(|| "Database path has no parent folder".to_string())
+}
+
+// Oldest first; timestamped names sort chronologically
+fn list_snapshots(dir: &Path) -> Vec<PathBuf> {
+    let mut snapshots: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| {
+            entries.filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|path| {
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| name.starts_with(SNAPSHOT_PREFIX) && name.ends_with(".json"))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    snapshots.sort();
+    snapshots
+}
+
+// Compare everything except the export timestamp
+fn same_content(path: &Path, current: &Value) -> bool {
+    let Ok(previous) = fs::read_to_string(path) else {
+        return false;
+    };
+    let Ok(mut previous) = serde_json::from_str::<Value>(&previous) else {
+        return false;
+    };
+    
+    let mut current = current.clone();
+    for value in [&mut previous, &mut current] {
+        if let Some(object) = value.as_object_mut() {
+            object.remove("exportedAt");
+        }
+    }
+    previous == current
+}
+
+fn prune(snapshots: &[PathBuf]) {
+    let excess = snapshots.len().saturating_sub(MAX_SNAPSHOTS);
+    for path in &snapshots[..excess] {
+        if let Err(e) = fs::remove_file(path) {
+            warn!("Failed to remove old snapshot {:?}: {}", path, e);
+        }
+    }
+}
@@ -0,0 +1,409 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, OnceLock};
+use chrono::{Datelike, Duration, Months, NaiveDate, Utc};
+use tauri::{AppHandle, Listener};
+use crate::db::{self, Database};
+use crate::helpers::parse_date::{last_day, local_day, parse_date_range, parse_week_range_from};
+use crate::services::{event_service, holiday_service, settings_service};
+use crate::structs::stats::{
+    CompletionHeatmap, CycleTimeQuery, CycleTimeStats, DurationStats, Granularity, HeatmapDay, HeatmapQuery, ProductivityBucket,
+    PauseReasonEntry, PauseReasonQuery, PauseReasonStats, ProcrastinationStats, ProductivityStatsQuery, TimeBreakdown, TimeBreakdownEntry, TimeBreakdownQuery, TimeGroupBy, WeeklyReview, WeeklyReviewQuery,
+    WorkloadDay, WorkloadForecast, WorkloadForecastQuery,
+};
+use crate::structs::task_struct::{Status, Task, TaskId};
+use crate::structs::work_session::SessionNote;
+
+// Enough for daily buckets over a few years; keeps a typo'd range from building a huge reply
+const MAX_BUCKETS: usize = 1500;
+
+// Forecasts further out than this are mostly empty days
+const MAX_FORECAST_DAYS: u32 = 366;
+
+// Most-postponed tasks listed by get_procrastination_stats
+const MOST_POSTPONED_LIMIT: i64 = 10;
+
+// Heatmap per year, with the UTC day it was computed on; cleared whenever tasks change
+static HEATMAP_CACHE: OnceLock<Mutex<HashMap<i32, (NaiveDate, CompletionHeatmap)>>> = OnceLock::new();
+
+// Drop cached stats on every task change
+pub fn init_stats_cache(app: &AppHandle) {
+    for event in event_service::TASK_EVENTS {
+        app.listen_any(event, |_| clear_cache());
+    }
+}
+
+// For changes that bypass task events (imports)
+pub fn clear_cache() {
+    if let Ok(mut cache) = heatmap_cache().lock() {
+        cache.clear();
+    }
+}
+
+// Created vs completed and overdue counts per bucket, with quiet buckets filled in for charts
+pub fn get_productivity_stats(db: &Database, payload: ProductivityStatsQuery) -> Result<Vec<ProductivityBucket>, String> {
+    let offset = settings_service::day_offset(db, payload.range.utc_offset_minutes)?;
+    let (start, _) = parse_date_range("from", &payload.range.from, Some(offset))?;
+    let (_, end) = parse_date_range("to", &payload.range.to, Some(offset))?;
+    if start >= end {
+        return Err("Stats range ends before it starts".to_string());
+    }
+    
+    // Day summaries only hold days in the settings offset
+    let summarized = offset == settings_service::day_offset(db, None)?;
+    let rows = {
+        let conn = db.get_read_connection();
+        let rows = if summarized {
+            db::get_productivity_from_summaries(&conn, start, end, local_day(start, offset), last_day(end, offset), payload.granularity, offset)
+        } else {
+            db::get_productivity_stats(&conn, start, end, payload.granularity, offset)
+        };
+        rows.map_err(|e| format!("Failed to compute productivity stats: {}", e))?
+    }; // DB lock released here
+    
+    let mut rows: HashMap<String, _> = rows.into_iter()
+        .map(|row| (row.bucket.clone(), row))
+        .collect();
+    
+    let mut buckets = Vec::new();
+    let mut day = bucket_start(local_day(start, offset), payload.granularity);
+    while day <= last_day(end, offset) {
+        if buckets.len() == MAX_BUCKETS {
+            return Err(format!("Range has more than {} buckets; use a coarser granularity", MAX_BUCKETS));
+        }
+        
+        let key = day.format("%Y-%m-%d").to_string();
+        let (created, completed, overdue, created_and_completed) = rows.remove(&key)
+            .map(|row| (row.created, row.completed, row.overdue, row.created_and_completed))
+            .unwrap_or_default();
+        
+        buckets.push(ProductivityBucket {
+            start: key,
+            created,
+            completed,
+            overdue,
+            completion_rate: (created > 0).then(|| created_and_completed as f64 / created as f64),
+        });
+        day = next_bucket(day, payload.granularity);
+    }
+    
+    Ok(buckets)
+}
+
+// Where tracked time went in the range. Time is start-to-completion of completed tasks,
+// the same measure the Markdown summary uses; there are no per-session records.
+pub fn get_time_breakdown(db: &Database, payload: TimeBreakdownQuery) -> Result<TimeBreakdown, String> {
+    let offset = settings_service::day_offset(db, payload.range.utc_offset_minutes)?;
+    let (start, _) = parse_date_range("from", &payload.range.from, Some(offset))?;
+    let (_, end) = parse_date_range("to", &payload.range.to, Some(offset))?;
+    if start >= end {
+        return Err("Stats range ends before it starts".to_string());
+    }
+    
+    match payload.group_by {
+        TimeGroupBy::Task => {}
+        TimeGroupBy::Project | TimeGroupBy::Tag => {
+            return Err("Tasks have no projects or tags to group by yet".to_string());
+        }
+    }
+    
+    let (rows, session_notes) = {
+        let conn = db.get_read_connection();
+        let rows = db::get_task_time(&conn, start, end)
+            .map_err(|e| format!("Failed to compute time breakdown: {}", e))?;
+        let session_notes = db::get_session_notes(&conn, start, end)
+            .map_err(|e| format!("Failed to get session notes: {}", e))?;
+        (rows, session_notes)
+    }; // DB lock released here
+    
+    let mut notes_by_task: HashMap<TaskId, Vec<SessionNote>> = HashMap::new();
+    for note in session_notes {
+        notes_by_task.entry(note.task_id).or_default().push(note);
+    }
+    
+    let total_minutes: i64 = rows.iter().map(|row| row.minutes).sum();
+    let entries = rows.into_iter()
+        .map(|row| TimeBreakdownEntry {
+            key: row.task_id.to_string(),
+            label: row.title,
+            minutes: row.minutes,
+            percentage: if total_minutes > 0 {
+                row.minutes as f64 * 100.0 / total_minutes as f64
+            } else {
+                0.0
+            },
+            notes: notes_by_task.remove(&TaskId::from(row.task_id)).unwrap_or_default(),
+        })
+        .collect();
+    
+    Ok(TimeBreakdown { total_minutes, entries })
+}
+
+// What paused tasks in the range, for the analytics views
+pub fn get_pause_reasons(db: &Database, payload: PauseReasonQuery) -> Result<PauseReasonStats, String> {
+    let offset = settings_service::day_offset(db, payload.range.utc_offset_minutes)?;
+    let (start, _) = parse_date_range("from", &payload.range.from, Some(offset))?;
+    let (_, end) = parse_date_range("to", &payload.range.to, Some(offset))?;
+    if start >= end {
+        return Err("Stats range ends before it starts".to_string());
+    }
+    
+    let rows = {
+        let conn = db.get_read_connection();
+        db::get_pause_reasons(&conn, start, end)
+            .map_err(|e| format!("Failed to compute pause reasons: {}", e))?
+    }; // DB lock released here
+    
+    let total_pauses: i64 = rows.iter().map(|row| row.pauses).sum();
+    let reasons = rows.into_iter()
+        .map(|row| PauseReasonEntry {
+            reason: row.reason,
+            pauses: row.pauses,
+            percentage: if total_pauses > 0 {
+                row.pauses as f64 * 100.0 / total_pauses as f64
+            } else {
+                0.0
+            },
+            minutes_to_resume: row.minutes_to_resume,
+        })
+        .collect();
+    
+    Ok(PauseReasonStats { total_pauses, reasons })
+}
+
+// Completed count for every day of the year, GitHub-contribution style; days are in the settings offset
+pub fn get_completion_heatmap(db: &Database, payload: HeatmapQuery) -> Result<CompletionHeatmap, String> {
+    let today = Utc::now().date_naive();
+    if let Ok(cache) = heatmap_cache().lock() {
+        if let Some((computed_on, heatmap)) = cache.get(&payload.year) {
+            if *computed_on == today {
+                return Ok(heatmap.clone());
+            }
+        }
+    }
+    
+    let first_day = NaiveDate::from_ymd_opt(payload.year, 1, 1)
+        .ok_or_else(|| format!("Invalid year: {}", payload.year))?;
+    let next_year = NaiveDate::from_ymd_opt(payload.year + 1, 1, 1)
+        .ok_or_else(|| format!("Invalid year: {}", payload.year))?;
+    
+    let rows = {
+        let conn = db.get_read_connection();
+        db::get_day_summaries(&conn, first_day, next_year - Duration::days(1))
+            .map_err(|e| format!("Failed to compute completion heatmap: {}", e))?
+    }; // DB lock released here
+    
+    let counts: HashMap<String, i64> = rows.into_iter()
+        .map(|row| (row.day, row.completed))
+        .collect();
+    let days: Vec<HeatmapDay> = first_day.iter_days()
+        .take_while(|day| *day < next_year)
+        .map(|day| {
+            let date = day.format("%Y-%m-%d").to_string();
+            let count = counts.get(&date).copied().unwrap_or(0);
+            HeatmapDay { date, count }
+        })
+        .collect();
+    
+    let heatmap = CompletionHeatmap {
+        year: payload.year,
+        total: days.iter().map(|day| day.count).sum(),
+        max_count: days.iter().map(|day| day.count).max().unwrap_or(0),
+        days,
+    };
+    
+    if let Ok(mut cache) = heatmap_cache().lock() {
+        cache.insert(payload.year, (today, heatmap.clone()));
+    }
+    Ok(heatmap)
+}
+
+// Average and median time from creation to start and from start to completion.
+// Tasks have no priority or project yet, so there is no breakdown by either.
+pub fn get_cycle_time_stats(db: &Database, payload: CycleTimeQuery) -> Result<CycleTimeStats, String> {
+    let offset = settings_service::day_offset(db, payload.range.utc_offset_minutes)?;
+    let (start, _) = parse_date_range("from", &payload.range.from, Some(offset))?;
+    let (_, end) = parse_date_range("to", &payload.range.to, Some(offset))?;
+    if start >= end {
+        return Err("Stats range ends before it starts".to_string());
+    }
+    
+    let rows = {
+        let conn = db.get_read_connection();
+        db::get_cycle_times(&conn, start, end)
+            .map_err(|e| format!("Failed to compute cycle times: {}", e))?
+    }; // DB lock released here
+    
+    // Clock changes can put a start before creation; those would skew the averages
+    let waits = rows.iter().filter_map(|row| row.wait_minutes).filter(|m| *m >= 0.0).collect();
+    let work = rows.iter().filter_map(|row| row.work_minutes).filter(|m| *m >= 0.0).collect();
+    
+    Ok(CycleTimeStats {
+        created_to_start: duration_stats(waits),
+        start_to_completion: duration_stats(work),
+    })
+}
+
+// What got done in the week, what slipped, and what should carry over to the next one
+pub fn generate_weekly_review(db: &Database, payload: WeeklyReviewQuery) -> Result<WeeklyReview, String> {
+    let offset = settings_service::day_offset(db, payload.utc_offset_minutes)?;
+    let formatter = settings_service::formatter(db)?;
+    let (start, end) = parse_week_range_from("week", &payload.week, Some(offset), formatter.week_start())?;
+    
+    let (completed, slipped_deadlines, touched_unfinished) = {
+        let conn = db.get_read_connection();
+        let query = |sql| db::query_tasks_by_date_range(&conn, start, end, sql)
+            .map_err(|e| format!("Failed to query tasks: {}", e));
+        (
+            query(include_str!("../db/sql/get_tasks_completed_between.sql"))?,
+            query(include_str!("../db/sql/get_tasks_slipped_between.sql"))?,
+            query(include_str!("../db/sql/get_tasks_touched_not_completed.sql"))?,
+        )
+    }; // DB lock released here
+    
+    let tracked_minutes = completed.iter()
+        .filter_map(Task::time_spent)
+        .map(|spent| spent.num_minutes())
+        .sum();
+    
+    let mut seen = HashSet::new();
+    let mut carry_overs: Vec<Task> = slipped_deadlines.iter()
+        .chain(&touched_unfinished)
+        .filter(|task| task.status != Status::Completed && seen.insert(task.id))
+        .cloned()
+        .collect();
+    // No deadline sorts last
+    carry_overs.sort_by_key(|task| (task.deadline.is_none(), task.deadline));
+    
+    Ok(WeeklyReview {
+        label: formatter.week_label(local_day(start, offset)),
+        week_start: local_day(start, offset).format("%Y-%m-%d").to_string(),
+        week_end: last_day(end, offset).format("%Y-%m-%d").to_string(),
+        completed,
+        slipped_deadlines,
+        touched_unfinished,
+        tracked_minutes,
+        carry_overs,
+    })
+}
+
+// Deadline slippage across all tasks; moves are counted since postponement tracking was added
+pub fn get_procrastination_stats(db: &Database) -> Result<ProcrastinationStats, String> {
+    let (totals, most_postponed) = {
+        let conn = db.get_read_connection();
+        let totals = db::get_postponement_totals(&conn)
+            .map_err(|e| format!("Failed to compute postponement stats: {}", e))?;
+        let most_postponed = db::get_most_postponed(&conn, MOST_POSTPONED_LIMIT)
+            .map_err(|e| format!("Failed to query postponed tasks: {}", e))?;
+        (totals, most_postponed)
+    }; // DB lock released here
+    
+    Ok(ProcrastinationStats {
+        postponed_tasks: totals.postponed_tasks,
+        total_moves: totals.moves,
+        average_postponement_days: (totals.moves > 0).then(|| totals.postponed_days / totals.moves as f64),
+        overdue_open: totals.overdue_open,
+        most_postponed,
+    })
+}
+
+// Estimated work due each day against the working day from settings, to spot days that need rescheduling
+pub fn get_workload_forecast(db: &Database, payload: WorkloadForecastQuery) -> Result<WorkloadForecast, String> {
+    if !(1..=MAX_FORECAST_DAYS).contains(&payload.days) {
+        return Err(format!("Forecast must cover 1 to {} days", MAX_FORECAST_DAYS));
+    }
+    
+    // Forecast days are UTC days; deadlines are spread over them as stored
+    let today = Utc::now().date_naive();
+    let last_day = today + Duration::days(payload.days as i64 - 1);
+    let end = last_day.and_hms_milli_opt(23, 59, 59, 999).ok_or("Invalid date")?.and_utc();
+    
+    let capacity_minutes = db.settings()
+        .map_err(|e| format!("Failed to fetch settings: {}", e))?
+        .work_day_minutes as i64;
+    let calendar = holiday_service::working_calendar(db, today, last_day)?;
+    let tasks = {
+        let conn = db.get_read_connection();
+        db::get_open_tasks_due_by(&conn, end)
+            .map_err(|e| format!("Failed to query tasks: {}", e))?
+    }; // DB lock released here
+    
+    let mut days: Vec<WorkloadDay> = today.iter_days()
+        .take(payload.days as usize)
+        .map(|day| WorkloadDay {
+            date: day.format("%Y-%m-%d").to_string(),
+            working: calendar.is_working_day(day),
+            planned_minutes: 0,
+            capacity_minutes: if calendar.is_working_day(day) { capacity_minutes } else { 0 },
+            task_ids: Vec::new(),
+            unestimated: 0,
+            overloaded: false,
+        })
+        .collect();
+    
+    for task in &tasks {
+        let Some(deadline) = task.deadline else {
+            continue;
+        };
+        let index = (deadline.date_naive() - today).num_days().max(0) as usize;
+        let Some(day) = days.get_mut(index) else {
+            continue;
+        };
+        day.task_ids.push(task.id);
+        match task.estimate_minutes {
+            Some(minutes) => day.planned_minutes += minutes as i64,
+            None => day.unestimated += 1,
+        }
+    }
+    
+    // Anything due on a skipped weekend or holiday needs moving too
+    for day in &mut days {
+        day.overloaded = day.planned_minutes > day.capacity_minutes || (!day.working && !day.task_ids.is_empty());
+    }
+    
+    Ok(WorkloadForecast {
+        overloaded_days: days.iter().filter(|day| day.overloaded).count(),
+        days,
+    })
+}
+
+pub fn duration_stats(mut minutes: Vec<f64>) -> DurationStats {
+    if minutes.is_empty() {
+        return DurationStats { count: 0, average_minutes: None, median_minutes: None };
+    }
+    
+    minutes.sort_by(f64::total_cmp);
+    let count = minutes.len();
+    let middle = count / 2;
+    let median = if count % 2 == 0 {
+        (minutes[middle - 1] + minutes[middle]) / 2.0
+    } else {
+        minutes[middle]
+    };
+    
+    DurationStats {
+        count,
+        average_minutes: Some(minutes.iter().sum::<f64>() / count as f64),
+        median_minutes: Some(median),
+    }
+}
+
+fn heatmap_cache() -> &'static Mutex<HashMap<i32, (NaiveDate, CompletionHeatmap)>> {
+    HEATMAP_CACHE.get_or_init(Default::default)
+}
+
+// Must match the bucketing in get_productivity_stats.sql
+fn bucket_start(day: NaiveDate, granularity: Granularity) -> NaiveDate {
+    match granularity {
+        Granularity::Day => day,
+        Granularity::Week => day - Duration::days(day.weekday().num_days_from_monday() as i64),
+        Granularity::Month => NaiveDate::from_ymd_opt(day.year(), day.month(), 1).unwrap_or(day),
+    }
+}
+
+fn next_bucket(day: NaiveDate, granularity: Granularity) -> NaiveDate {
+    match granularity {
+        Granularity::Day => day + Duration::days(1),
+        Granularity::Week => day + Duration::days(7),
+        Granularity::Month => day.checked_add_months(Months::new(1)).unwrap_or(NaiveDate::MAX),
+    }
+}
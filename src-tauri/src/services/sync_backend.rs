@@ -0,0 +1,215 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::structs::sync::{SealedChangeset, WebDavAccount};
+use crate::thirdparty::webdav;
+use tracing::warn;
+
+// Same layout on every backend:
+//   sync-key.json                      salt and passphrase check, if encrypted
+//   project.json                       members of a shared project, sealed with its key
+//   devices/<device id>.device         who each device is
+//   changes/<device id>/<seq>.changes  one file per push, named by its last record's seq
+const KEY_FILE: &str = "sync-key.json";
+const MANIFEST_FILE: &str = "project.json";
+const DEVICES_DIR: &str = "devices";
+const DEVICE_EXTENSION: &str = "device";
+const CHANGES_DIR: &str = "changes";
+const CHANGESET_EXTENSION: &str = "changes";
+
+// Where devices exchange changesets. Each device only writes its own files,
+// so the storage never has to merge concurrent writes to the same file.
+pub trait SyncBackend {
+    // Store one of this device's changesets
+    async fn push(&self, device_id: &str, changeset: SealedChangeset) -> Result<(), String>;
+    // Changesets a device pushed after `after_seq`, oldest first
+    async fn pull(&self, device_id: &str, after_seq: i64) -> Result<Vec<SealedChangeset>, String>;
+    async fn register_device(&self, device_id: &str, info: Vec<u8>) -> Result<(), String>;
+    // Every registered device, this one included
+    async fn device_ids(&self) -> Result<Vec<String>, String>;
+    async fn device_info(&self, device_id: &str) -> Result<Option<Vec<u8>>, String>;
+    async fn read_key_file(&self) -> Result<Option<Vec<u8>>, String>;
+    async fn write_key_file(&self, data: Vec<u8>) -> Result<(), String>;
+    // Only at shared project locations
+    async fn read_manifest(&self) -> Result<Option<Vec<u8>>, String>;
+    async fn write_manifest(&self, data: Vec<u8>) -> Result<(), String>;
+}
+
+// Zero-padded so names sort in seq order
+fn changeset_name(seq: i64) -> String {
+    format!("{:020}.{}", seq, CHANGESET_EXTENSION)
+}
+
+fn changeset_seq(name: &str) -> Option<i64> {
+    name.strip_suffix(CHANGESET_EXTENSION)?.strip_suffix('.')?.parse().ok()
+}
+
+// Folder kept in step across devices by another tool (Dropbox, Syncthing)
+pub struct FolderBackend {
+    root: PathBuf,
+}
+
+impl FolderBackend {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+}
+
+impl SyncBackend for FolderBackend {
+    async fn push(&self, device_id: &str, changeset: SealedChangeset) -> Result<(), String> {
+        let path = self.root.join(CHANGES_DIR).join(device_id).join(changeset_name(changeset.seq));
+        write_file(&path, &changeset.data)
+    }
+    
+    async fn pull(&self, device_id: &str, after_seq: i64) -> Result<Vec<SealedChangeset>, String> {
+        let dir = self.root.join(CHANGES_DIR).join(device_id);
+        let mut seqs: Vec<i64> = list_dir(&dir)?.iter()
+            .filter_map(|name| changeset_seq(name))
+            .filter(|seq| *seq > after_seq)
+            .collect();
+        seqs.sort_unstable();
+        
+        let mut changesets = Vec::new();
+        for seq in seqs {
+            let path = dir.join(changeset_name(seq));
+            match fs::read(&path) {
+                Ok(data) => changesets.push(SealedChangeset { seq, data }),
+                // Later ones must wait too, or the cursor would move past this one
+                Err(e) => {
+                    warn!("Sync changeset {:?} not readable yet: {}", path, e);
+                    break;
+                }
+            }
+        }
+        Ok(changesets)
+    }
+    
+    async fn register_device(&self, device_id: &str, info: Vec<u8>) -> Result<(), String> {
+        let path = self.root.join(DEVICES_DIR).join(format!("{}.{}", device_id, DEVICE_EXTENSION));
+        write_file(&path, &info)
+    }
+    
+    async fn device_ids(&self) -> Result<Vec<String>, String> {
+        Ok(list_dir(&self.root.join(DEVICES_DIR))?.iter()
+            .filter_map(|name| name.strip_suffix(DEVICE_EXTENSION)?.strip_suffix('.').map(str::to_string))
+            .collect())
+    }
+    
+    async fn device_info(&self, device_id: &str) -> Result<Option<Vec<u8>>, String> {
+        read_file(&self.root.join(DEVICES_DIR).join(format!("{}.{}", device_id, DEVICE_EXTENSION)))
+    }
+    
+    async fn read_key_file(&self) -> Result<Option<Vec<u8>>, String> {
+        read_file(&self.root.join(KEY_FILE))
+    }
+    
+    async fn write_key_file(&self, data: Vec<u8>) -> Result<(), String> {
+        write_file(&self.root.join(KEY_FILE), &data)
+    }
+    
+    async fn read_manifest(&self) -> Result<Option<Vec<u8>>, String> {
+        read_file(&self.root.join(MANIFEST_FILE))
+    }
+    
+    async fn write_manifest(&self, data: Vec<u8>) -> Result<(), String> {
+        write_file(&self.root.join(MANIFEST_FILE), &data)
+    }
+}
+
+// Written beside the target and renamed, so the sync tool never uploads half a file
+fn write_file(path: &Path, data: &[u8]) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create sync folder {:?}: {}", dir, e))?;
+    }
+    let temp = path.with_extension("tmp");
+    fs::write(&temp, data)
+        .map_err(|e| format!("Failed to write {:?}: {}", temp, e))?;
+    fs::rename(&temp, path)
+        .map_err(|e| format!("Failed to write {:?}: {}", path, e))
+}
+
+fn read_file(path: &Path) -> Result<Option<Vec<u8>>, String> {
+    match fs::read(path) {
+        Ok(data) => Ok(Some(data)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("Failed to read {:?}: {}", path, e)),
+    }
+}
+
+fn list_dir(dir: &Path) -> Result<Vec<String>, String> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to read sync folder {:?}: {}", dir, e)),
+    };
+    Ok(entries.flatten()
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .collect())
+}
+
+// Folder on a WebDAV server (Nextcloud, ownCloud, most NAS boxes)
+pub struct WebDavBackend {
+    account: WebDavAccount,
+}
+
+impl WebDavBackend {
+    pub fn new(account: WebDavAccount) -> Self {
+        Self { account }
+    }
+}
+
+impl SyncBackend for WebDavBackend {
+    async fn push(&self, device_id: &str, changeset: SealedChangeset) -> Result<(), String> {
+        let path = format!("{}/{}/{}", CHANGES_DIR, device_id, changeset_name(changeset.seq));
+        webdav::put(&self.account, &path, changeset.data).await
+    }
+    
+    async fn pull(&self, device_id: &str, after_seq: i64) -> Result<Vec<SealedChangeset>, String> {
+        let dir = format!("{}/{}", CHANGES_DIR, device_id);
+        let mut seqs: Vec<i64> = webdav::list(&self.account, &dir).await?.iter()
+            .filter_map(|name| changeset_seq(name))
+            .filter(|seq| *seq > after_seq)
+            .collect();
+        seqs.sort_unstable();
+        
+        let mut changesets = Vec::new();
+        for seq in seqs {
+            let path = format!("{}/{}", dir, changeset_name(seq));
+            let data = webdav::get(&self.account, &path).await?
+                .ok_or_else(|| format!("Sync changeset {} disappeared from the server", path))?;
+            changesets.push(SealedChangeset { seq, data });
+        }
+        Ok(changesets)
+    }
+    
+    async fn register_device(&self, device_id: &str, info: Vec<u8>) -> Result<(), String> {
+        let path = format!("{}/{}.{}", DEVICES_DIR, device_id, DEVICE_EXTENSION);
+        webdav::put(&self.account, &path, info).await
+    }
+    
+    async fn device_ids(&self) -> Result<Vec<String>, String> {
+        Ok(webdav::list(&self.account, DEVICES_DIR).await?.iter()
+            .filter_map(|name| name.strip_suffix(DEVICE_EXTENSION)?.strip_suffix('.').map(str::to_string))
+            .collect())
+    }
+    
+    async fn device_info(&self, device_id: &str) -> Result<Option<Vec<u8>>, String> {
+        webdav::get(&self.account, &format!("{}/{}.{}", DEVICES_DIR, device_id, DEVICE_EXTENSION)).await
+    }
+    
+    async fn read_key_file(&self) -> Result<Option<Vec<u8>>, String> {
+        webdav::get(&self.account, KEY_FILE).await
+    }
+    
+    async fn write_key_file(&self, data: Vec<u8>) -> Result<(), String> {
+        webdav::put(&self.account, KEY_FILE, data).await
+    }
+    
+    async fn read_manifest(&self) -> Result<Option<Vec<u8>>, String> {
+        webdav::get(&self.account, MANIFEST_FILE).await
+    }
+    
+    async fn write_manifest(&self, data: Vec<u8>) -> Result<(), String> {
+        webdav::put(&self.account, MANIFEST_FILE, data).await
+    }
+}
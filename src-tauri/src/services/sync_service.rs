@@ -0,0 +1,773 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use tauri::{AppHandle, Listener, Manager};
+use uuid::Uuid;
+use crate::db::{self, Database};
+use crate::helpers::crypto::{self, SecretKey};
+use crate::services::sync_backend::{FolderBackend, SyncBackend, WebDavBackend};
+use crate::secrets;
+use crate::services::{event_service, network_permission_service, recovery_service, settings_service};
+use crate::structs::network::NetworkFeature;
+use crate::structs::changelog::ChangeOp;
+use crate::structs::settings::{Settings, SyncBackendKind};
+use crate::structs::sync::{
+    Change, ChangeRecord, Changeset, ConflictResolution, ConflictWinner, DeviceInfo, SealedChangeset,
+    SyncConflictDetail, SyncKeyFile, SyncReport, SyncStatus, WebDavAccount,
+};
+use crate::structs::task_struct::Task;
+use tracing::{info, warn, error};
+
+// Encrypted into the key file; decrypting it proves the passphrase is the one other devices use
+const KEY_CHECK: &[u8] = b"myhandler-sync";
+
+// Task events and delete events both carry the task ID
+#[derive(Deserialize)]
+struct ChangedTask {
+    id: Uuid,
+}
+
+#[derive(Default)]
+struct SyncLog {
+    last_seq: i64,
+    // Version last queued per task; one action can emit several events
+    written: HashMap<Uuid, DateTime<Utc>>,
+    // Versions written by a merge (None: deleted), whose events must not be queued as local changes
+    merged: HashMap<Uuid, Option<DateTime<Utc>>>,
+}
+
+// Held while queueing, so seqs are handed out in order
+static LOG: OnceLock<Mutex<SyncLog>> = OnceLock::new();
+// One push or sync at a time, so a changeset is never sent twice
+static SYNC_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+// Error from the last sync, for get_sync_status
+static LAST_ERROR: Mutex<Option<String>> = Mutex::new(None);
+
+enum BackendConfig {
+    Folder(PathBuf),
+    WebDav(WebDavAccount),
+}
+
+struct SyncConfig {
+    backend: BackendConfig,
+    device_id: String,
+    device_name: String,
+    passphrase: Option<String>,
+    merged_at: Option<DateTime<Utc>>,
+}
+
+// One device's changesets fetched from the backend
+struct Incoming {
+    device_id: String,
+    name: Option<String>,
+    // None when the records didn't come from the device's change log
+    last_seq: Option<i64>,
+    records: Vec<ChangeRecord>,
+}
+
+// A change from another device that was written here, announced once the DB lock is released
+enum Applied {
+    Created(Task),
+    Updated(Task),
+    Deleted(Uuid),
+}
+
+// Queue every local task change for other devices and sync at startup, if configured
+pub fn init_sync(app: &AppHandle) {
+    if let Some(db) = app.try_state::<Database>() {
+        let conn = db.get_connection();
+        match db::get_sync_last_seq(&conn) {
+            Ok(seq) => {
+                if let Ok(mut log) = lock_log() {
+                    log.last_seq = seq;
+                }
+            }
+            Err(e) => warn!("Failed to load sync sequence: {}", e),
+        }
+    }
+    
+    for event in event_service::TASK_EVENTS {
+        let handle = app.clone();
+        app.listen_any(event, move |event| {
+            let Ok(changed) = serde_json::from_str::<ChangedTask>(event.payload()) else {
+                return;
+            };
+            // Off the emitting thread: queueing queries the database
+            let handle = handle.clone();
+            tauri::async_runtime::spawn_blocking(move || {
+                match queue_task(&handle, changed.id) {
+                    Ok(true) => push_soon(handle),
+                    Ok(false) => {}
+                    Err(e) => error!("Failed to queue change for sync: {}", e),
+                }
+            });
+        });
+    }
+    
+    sync_soon(app.clone());
+}
+
+// Queue every task (the location may be new to this device) and sync, after sync settings change
+pub fn apply_settings(app: &AppHandle) -> Result<(), String> {
+    queue_all_tasks(app)?;
+    sync_soon(app.clone());
+    Ok(())
+}
+
+// Imports bypass task events, so their tasks are queued in one pass
+pub fn queue_all_tasks(app: &AppHandle) -> Result<(), String> {
+    let Some(config) = load_config(app)? else {
+        return Ok(());
+    };
+    
+    let db = app.state::<Database>();
+    let tasks = {
+        let conn = db.get_connection();
+        db::get_all_tasks(&conn)
+            .map_err(|e| format!("Failed to fetch tasks: {}", e))?
+    }; // DB lock released here
+    
+    {
+        let mut log = lock_log()?;
+        let conn = db.get_connection();
+        for task in tasks {
+            log.written.insert(task.id, task.updated_at);
+            queue(&conn, &mut log, &config.device_id, Change::Upsert { task: Box::new(task) })?;
+        }
+    }
+    
+    push_soon(app.clone());
+    Ok(())
+}
+
+// Push queued changes, then merge what other devices pushed; the newer edit of a task wins
+pub async fn sync_now(app: &AppHandle) -> Result<SyncReport, String> {
+    let result = run(app, true).await;
+    if let Ok(mut last_error) = LAST_ERROR.lock() {
+        *last_error = result.as_ref().err().cloned();
+    }
+    result
+}
+
+pub fn get_sync_status(db: &Database) -> Result<SyncStatus, String> {
+    let settings = db.settings()
+        .map_err(|e| format!("Failed to fetch settings: {}", e))?;
+    let conn = db.get_connection();
+    let (pending_changes, _) = db::get_sync_outbox_stats(&conn)
+        .map_err(|e| format!("Failed to count queued sync changes: {}", e))?;
+    let devices = db::get_sync_devices(&conn)
+        .map_err(|e| format!("Failed to fetch sync devices: {}", e))?;
+    let encrypted = secrets::get(&conn, secrets::SYNC, "passphrase")?.is_some();
+    
+    let device_name = device_name(&settings);
+    let enabled = match settings.sync_backend {
+        SyncBackendKind::Folder => settings.sync_folder.is_some(),
+        SyncBackendKind::WebDav => settings.sync_webdav_url.is_some(),
+    };
+    Ok(SyncStatus {
+        enabled,
+        backend: settings.sync_backend,
+        device_id: settings.sync_device_id,
+        device_name,
+        encrypted,
+        last_synced_at: settings.sync_merged_at,
+        pending_changes,
+        last_error: LAST_ERROR.lock().ok().and_then(|last_error| last_error.clone()),
+        devices,
+    })
+}
+
+// Open conflicts first, each with both versions of the task
+pub fn list_conflicts(db: &Database) -> Result<Vec<SyncConflictDetail>, String> {
+    let conn = db.get_connection();
+    let conflicts = db::get_sync_conflicts(&conn)
+        .map_err(|e| format!("Failed to fetch sync conflicts: {}", e))?;
+    
+    conflicts.into_iter()
+        .map(|conflict| Ok(SyncConflictDetail {
+            local: parse_version(conflict.local_version.as_deref())?,
+            remote: parse_version(conflict.remote_version.as_deref())?,
+            conflict,
+        }))
+        .collect()
+}
+
+// Write the chosen version as a new edit, so it also wins on every other device
+pub async fn resolve_conflict(app: &AppHandle, resolution: ConflictResolution) -> Result<Task, String> {
+    // Not in the middle of a merge that could overwrite the choice
+    let _running = SYNC_LOCK.lock().await;
+    
+    let (task, created) = {
+        let db = app.state::<Database>();
+        let conn = db.get_connection();
+        let conflict = db::get_sync_conflict(&conn, resolution.id)
+            .map_err(|e| format!("Failed to fetch sync conflict: {}", e))?
+            .ok_or_else(|| format!("Sync conflict {} not found", resolution.id))?;
+        if conflict.resolved_at.is_some() {
+            return Err(format!("Sync conflict {} is already resolved", resolution.id));
+        }
+        
+        let version = match resolution.keep {
+            ConflictWinner::Local => conflict.local_version.as_deref(),
+            ConflictWinner::Remote => conflict.remote_version.as_deref(),
+        };
+        let mut task = parse_version(version)?
+            .ok_or_else(|| format!("Sync conflict {} has no saved versions", resolution.id))?;
+        let now = Utc::now();
+        task.updated_at = now;
+        
+        let tx = conn.unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        let created = match db::get_task_by_id(&tx, task.id.into()) {
+            Ok(_) => false,
+            Err(rusqlite::Error::QueryReturnedNoRows) => true,
+            Err(e) => return Err(format!("Failed to fetch task {}: {}", task.id, e)),
+        };
+        if created {
+            db::insert(&tx, &task)
+                .map_err(|e| format!("Failed to restore task: {}", e))?;
+        } else {
+            db::overwrite_task(&tx, &task)
+                .map_err(|e| format!("Failed to update task: {}", e))?;
+        }
+        db::resolve_sync_conflict(&tx, resolution.id, resolution.keep.as_str(), now)
+            .and_then(|_| db::supersede_sync_conflicts(&tx, &task.id, now))
+            .map_err(|e| format!("Failed to resolve sync conflict: {}", e))?;
+        tx.commit().map_err(|e| format!("Failed to resolve sync conflict: {}", e))?;
+        (task, created)
+    }; // DB lock released here
+    
+    // Announced as a local edit, so it's queued for other devices
+    if created {
+        event_service::emit_task_created(app, &task);
+    } else {
+        event_service::emit_task_updated(app, &task);
+    }
+    Ok(task)
+}
+
+pub fn clear_sync_conflicts(db: &Database) -> Result<usize, String> {
+    let conn = db.get_connection();
+    db::clear_sync_conflicts(&conn)
+        .map_err(|e| format!("Failed to clear sync conflicts: {}", e))
+}
+
+// Every task and deletion on this device, for a device that syncs with it directly
+pub fn snapshot_records(db: &Database) -> Result<Vec<ChangeRecord>, String> {
+    let settings = db.settings()
+        .map_err(|e| format!("Failed to fetch settings: {}", e))?;
+    let conn = db.get_connection();
+    let tasks = db::get_all_tasks(&conn)
+        .map_err(|e| format!("Failed to fetch tasks: {}", e))?;
+    let tombstones = db::get_sync_tombstones(&conn)
+        .map_err(|e| format!("Failed to fetch deleted tasks: {}", e))?;
+    
+    let device_id = settings.sync_device_id;
+    let record = |recorded_at, change| ChangeRecord { seq: 0, device_id: device_id.clone(), recorded_at, change };
+    Ok(tasks.into_iter()
+        .map(|task| record(task.updated_at, Change::Upsert { task: Box::new(task) }))
+        .chain(tombstones.into_iter().map(|(task_id, deleted_at)| record(deleted_at, Change::Delete { task_id })))
+        .collect())
+}
+
+// Merge another device's snapshot the same way as changes pulled from a backend
+pub fn merge_snapshot(app: &AppHandle, device_id: String, records: Vec<ChangeRecord>) -> Result<SyncReport, String> {
+    if recovery_service::is_active(app) {
+        return Err("Sync is paused while the recovery database is open".to_string());
+    }
+    let settings = app.state::<Database>().settings()
+        .map_err(|e| format!("Failed to fetch settings: {}", e))?;
+    
+    let incoming = Incoming { device_id, name: None, last_seq: None, records };
+    merge(app, &settings.sync_device_id, settings.sync_merged_at, vec![incoming])
+}
+
+fn parse_version(json: Option<&str>) -> Result<Option<Task>, String> {
+    json.map(|json| serde_json::from_str(json)
+            .map_err(|e| format!("Invalid saved task version: {}", e)))
+        .transpose()
+}
+
+fn sync_soon(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = sync_now(&app).await {
+            error!("Sync failed: {}", e);
+        }
+    });
+}
+
+// Send queued changes without waiting for the next scheduled sync
+fn push_soon(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = run(&app, false).await {
+            warn!("Sync push failed, will retry on the next sync: {}", e);
+        }
+    });
+}
+
+async fn run(app: &AppHandle, pull: bool) -> Result<SyncReport, String> {
+    let Some(config) = load_config(app)? else {
+        return Ok(SyncReport::default());
+    };
+    let _running = SYNC_LOCK.lock().await;
+    
+    match &config.backend {
+        BackendConfig::Folder(root) => {
+            sync_with(app, &config, &FolderBackend::new(root.clone()), pull).await
+        }
+        BackendConfig::WebDav(account) => {
+            network_permission_service::ensure_allowed(&app.state::<Database>(), NetworkFeature::Sync)?;
+            // Task titles and notes must never reach the server readable
+            if config.passphrase.is_none() {
+                return Err("Set a sync passphrase before syncing over WebDAV".to_string());
+            }
+            sync_with(app, &config, &WebDavBackend::new(account.clone()), pull).await
+        }
+    }
+}
+
+async fn sync_with<B: SyncBackend>(app: &AppHandle, config: &SyncConfig, backend: &B, pull: bool) -> Result<SyncReport, String> {
+    let key = unlock(backend, config).await?;
+    push_queued(app, config, backend, key.as_ref()).await?;
+    if !pull {
+        return Ok(SyncReport::default());
+    }
+    
+    let info = DeviceInfo {
+        device_id: config.device_id.clone(),
+        name: config.device_name.clone(),
+        last_seen_at: Utc::now(),
+        identity: None,
+    };
+    backend.register_device(&config.device_id, encode(&info, key.as_ref())?).await?;
+    
+    let mut incoming = Vec::new();
+    for device_id in backend.device_ids().await? {
+        if device_id == config.device_id {
+            continue;
+        }
+        let cursor = {
+            let db = app.state::<Database>();
+            let conn = db.get_connection();
+            db::get_sync_cursor(&conn, &device_id)
+                .map_err(|e| format!("Failed to read sync cursor: {}", e))?
+        }; // DB lock released here
+        
+        let name = match backend.device_info(&device_id).await? {
+            Some(data) => decode::<DeviceInfo>(&data, key.as_ref()).ok().map(|info| info.name),
+            None => None,
+        };
+        let changesets = backend.pull(&device_id, cursor.unwrap_or(0)).await?;
+        let last_seq = changesets.last().map(|changeset| changeset.seq).or(cursor).unwrap_or(0);
+        
+        let mut records = Vec::new();
+        for changeset in changesets {
+            let changeset: Changeset = decode(&changeset.data, key.as_ref())?;
+            if changeset.device_id != device_id {
+                return Err(format!("Changeset from {} found in {}'s folder", changeset.device_id, device_id));
+            }
+            records.extend(changeset.records);
+        }
+        incoming.push(Incoming { device_id, name, last_seq: Some(last_seq), records });
+    }
+    
+    merge(app, &config.device_id, config.merged_at, incoming)
+}
+
+// Key for this sync location, creating the key file on first use; None when unencrypted
+async fn unlock<B: SyncBackend>(backend: &B, config: &SyncConfig) -> Result<Option<SecretKey>, String> {
+    let key_file = backend.read_key_file().await?;
+    let Some(passphrase) = config.passphrase.clone() else {
+        if key_file.is_some() {
+            return Err("Sync data is encrypted; set the passphrase your other devices use".to_string());
+        }
+        return Ok(None);
+    };
+    
+    let key_file = match key_file {
+        Some(data) => Some(serde_json::from_slice::<SyncKeyFile>(&data)
+            .map_err(|e| format!("Invalid sync key file: {}", e))?),
+        None => None,
+    };
+    let salt = match &key_file {
+        Some(key_file) => crypto::from_hex(&key_file.salt)?,
+        None => crypto::random_salt().to_vec(),
+    };
+    
+    // Deliberately slow, so kept off the async workers
+    let derive_salt = salt.clone();
+    let key = tauri::async_runtime::spawn_blocking(move || crypto::derive_key(&passphrase, &derive_salt))
+        .await
+        .map_err(|e| format!("Failed to derive sync key: {}", e))?;
+    
+    match key_file {
+        Some(key_file) => {
+            let check = crypto::open(&key, &crypto::from_hex(&key_file.check)?).ok();
+            if check.as_deref() != Some(KEY_CHECK) {
+                return Err("Sync passphrase doesn't match the one your other devices use".to_string());
+            }
+        }
+        None => {
+            let key_file = SyncKeyFile {
+                salt: crypto::to_hex(&salt),
+                check: crypto::to_hex(&crypto::seal(&key, KEY_CHECK)?),
+            };
+            let data = serde_json::to_vec_pretty(&key_file)
+                .map_err(|e| format!("Failed to serialize sync key file: {}", e))?;
+            backend.write_key_file(data).await?;
+            info!("Encrypted sync set up");
+        }
+    }
+    Ok(Some(key))
+}
+
+async fn push_queued<B: SyncBackend>(app: &AppHandle, config: &SyncConfig, backend: &B, key: Option<&SecretKey>) -> Result<(), String> {
+    let queued = {
+        let db = app.state::<Database>();
+        let conn = db.get_connection();
+        db::get_sync_outbox(&conn)
+            .map_err(|e| format!("Failed to read queued sync changes: {}", e))?
+    }; // DB lock released here
+    let Some(&(last_seq, _)) = queued.last() else {
+        return Ok(());
+    };
+    
+    let records = queued.iter()
+        .filter_map(|(seq, record)| match serde_json::from_str::<ChangeRecord>(record) {
+            Ok(record) => Some(record),
+            Err(e) => {
+                warn!("Dropping unreadable queued sync change {}: {}", seq, e);
+                None
+            }
+        })
+        .collect();
+    let changeset = Changeset {
+        device_id: config.device_id.clone(),
+        records,
+    };
+    backend.push(&config.device_id, SealedChangeset { seq: last_seq, data: encode(&changeset, key)? }).await?;
+    
+    let db = app.state::<Database>();
+    let conn = db.get_connection();
+    db::clear_sync_outbox(&conn, last_seq)
+        .map_err(|e| format!("Failed to clear queued sync changes: {}", e))?;
+    info!("Pushed {} sync changes", queued.len());
+    Ok(())
+}
+
+pub fn encode<T: serde::Serialize>(value: &T, key: Option<&SecretKey>) -> Result<Vec<u8>, String> {
+    let json = serde_json::to_vec(value)
+        .map_err(|e| format!("Failed to serialize sync data: {}", e))?;
+    match key {
+        Some(key) => crypto::seal(key, &json),
+        None => Ok(json),
+    }
+}
+
+pub fn decode<T: serde::de::DeserializeOwned>(data: &[u8], key: Option<&SecretKey>) -> Result<T, String> {
+    let json = match key {
+        Some(key) => crypto::open(key, data)?,
+        None => data.to_vec(),
+    };
+    serde_json::from_slice(&json)
+        .map_err(|e| format!("Invalid sync data: {}", e))
+}
+
+fn merge(
+    app: &AppHandle,
+    local_device_id: &str,
+    merged_at: Option<DateTime<Utc>>,
+    incoming: Vec<Incoming>,
+) -> Result<SyncReport, String> {
+    let mut report = SyncReport::default();
+    let mut applied = Vec::new();
+    {
+        let db = app.state::<Database>();
+        let conn = db.get_connection();
+        let tx = conn.unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        
+        for device in incoming {
+            // The changelog credits these writes to the device that made them
+            db::set_changelog_origin(&tx, &device.device_id)
+                .map_err(|e| format!("Failed to record change origin: {}", e))?;
+            for record in device.records {
+                if let Some(change) = apply_record(&tx, local_device_id, merged_at, record, &mut report)? {
+                    applied.push(change);
+                }
+            }
+            if let Some(name) = &device.name {
+                db::set_sync_device_name(&tx, &device.device_id, name)
+                    .map_err(|e| format!("Failed to save sync device: {}", e))?;
+            }
+            if let Some(last_seq) = device.last_seq {
+                db::set_sync_cursor(&tx, &device.device_id, last_seq)
+                    .map_err(|e| format!("Failed to save sync cursor: {}", e))?;
+            }
+        }
+        
+        db::clear_changelog_origin(&tx)
+            .map_err(|e| format!("Failed to record change origin: {}", e))?;
+        db::set_sync_merged_at(&tx, Utc::now())
+            .map_err(|e| format!("Failed to save sync time: {}", e))?;
+        tx.commit().map_err(|e| format!("Failed to merge sync changes: {}", e))?;
+    } // DB lock released here
+    
+    if !applied.is_empty() {
+        let mut log = lock_log()?;
+        for change in &applied {
+            match change {
+                Applied::Created(task) | Applied::Updated(task) => log.merged.insert(task.id, Some(task.updated_at)),
+                Applied::Deleted(id) => log.merged.insert(*id, None),
+            };
+        }
+    }
+    for change in applied {
+        match change {
+            Applied::Created(task) => event_service::emit_task_created(app, &task),
+            Applied::Updated(task) => event_service::emit_task_updated(app, &task),
+            Applied::Deleted(id) => event_service::emit_task_deleted(app, id.into()),
+        }
+    }
+    
+    if report.applied > 0 || report.conflicts > 0 {
+        info!("Sync merged {} changes ({} skipped, {} conflicts)", report.applied, report.skipped, report.conflicts);
+    }
+    Ok(report)
+}
+
+// None when sync is off, or on the recovery database (its empty task list must not spread)
+fn load_config(app: &AppHandle) -> Result<Option<SyncConfig>, String> {
+    if recovery_service::is_active(app) {
+        return Ok(None);
+    }
+    
+    let db = app.try_state::<Database>()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    let settings = db.settings()
+        .map_err(|e| format!("Failed to fetch settings: {}", e))?;
+    
+    let device_name = device_name(&settings);
+    let backend = match settings.sync_backend {
+        SyncBackendKind::Folder => settings.sync_folder.map(|folder| BackendConfig::Folder(PathBuf::from(folder))),
+        SyncBackendKind::WebDav => match settings.sync_webdav_url {
+            Some(url) => Some(BackendConfig::WebDav(WebDavAccount {
+                url,
+                username: settings.sync_webdav_username,
+                password: settings_service::secret(&db, secrets::SYNC, "webdav_password")?,
+            })),
+            None => None,
+        },
+    };
+    let Some(backend) = backend else {
+        return Ok(None);
+    };
+    Ok(Some(SyncConfig {
+        backend,
+        device_id: settings.sync_device_id,
+        device_name,
+        passphrase: settings_service::secret(&db, secrets::SYNC, "passphrase")?,
+        merged_at: settings.sync_merged_at,
+    }))
+}
+
+// Name other devices show for this one
+pub fn device_name(settings: &Settings) -> String {
+    settings.sync_device_name.clone().unwrap_or_else(default_device_name)
+}
+
+fn default_device_name() -> String {
+    std::env::var("COMPUTERNAME")
+        .or_else(|_| std::env::var("HOSTNAME"))
+        .unwrap_or_else(|_| "Unnamed device".to_string())
+}
+
+fn lock_log() -> Result<std::sync::MutexGuard<'static, SyncLog>, String> {
+    LOG.get_or_init(Default::default).lock().map_err(|e| e.to_string())
+}
+
+// Queue the task's current state (or its deletion); false when there was nothing new to send
+fn queue_task(app: &AppHandle, task_id: Uuid) -> Result<bool, String> {
+    if recovery_service::is_active(app) {
+        return Ok(false);
+    }
+    let config = load_config(app)?;
+    
+    let db = app.state::<Database>();
+    let task = {
+        let conn = db.get_connection();
+        match db::get_task_by_id(&conn, task_id.into()) {
+            Ok(task) => Some(task),
+            Err(rusqlite::Error::QueryReturnedNoRows) => None,
+            Err(e) => return Err(format!("Failed to fetch task {}: {}", task_id, e)),
+        }
+    }; // DB lock released here
+    
+    let mut log = lock_log()?;
+    let version = task.as_ref().map(|task| task.updated_at);
+    if log.merged.get(&task_id) == Some(&version) {
+        log.merged.remove(&task_id);
+        return Ok(false);
+    }
+    
+    let conn = db.get_connection();
+    match task {
+        Some(task) => {
+            let Some(config) = config else {
+                return Ok(false);
+            };
+            if log.written.get(&task.id) == Some(&task.updated_at) {
+                return Ok(false);
+            }
+            log.written.insert(task.id, task.updated_at);
+            queue(&conn, &mut log, &config.device_id, Change::Upsert { task: Box::new(task) })?;
+        }
+        None => {
+            // Keeps an older edit from another device from bringing the task back;
+            // recorded even without a backend, since LAN peers ask for deletions too
+            db::record_sync_tombstone(&conn, &task_id, Utc::now())
+                .map_err(|e| format!("Failed to record deleted task: {}", e))?;
+            log.written.remove(&task_id);
+            let Some(config) = config else {
+                return Ok(false);
+            };
+            queue(&conn, &mut log, &config.device_id, Change::Delete { task_id })?;
+        }
+    }
+    Ok(true)
+}
+
+fn queue(conn: &rusqlite::Connection, log: &mut SyncLog, device_id: &str, change: Change) -> Result<(), String> {
+    let now = Utc::now();
+    // Follows the clock but never repeats or goes back, even across restarts
+    let seq = now.timestamp_micros().max(log.last_seq + 1);
+    
+    let record = ChangeRecord {
+        seq,
+        device_id: device_id.to_string(),
+        recorded_at: now,
+        change,
+    };
+    let json = serde_json::to_string(&record)
+        .map_err(|e| format!("Failed to serialize sync change: {}", e))?;
+    
+    db::add_sync_outbox(conn, seq, &json)
+        .and_then(|_| db::set_sync_last_seq(conn, seq))
+        .map_err(|e| format!("Failed to queue sync change: {}", e))?;
+    log.last_seq = seq;
+    Ok(())
+}
+
+// The title before this device's own renames since the last merge; None when it wasn't renamed here
+fn title_before_local_renames(
+    conn: &rusqlite::Connection,
+    local_device_id: &str,
+    merged_at: Option<DateTime<Utc>>,
+    local: &Task,
+) -> Result<Option<String>, String> {
+    let history = db::get_row_changelog(conn, "tasks", &local.id.simple().to_string())
+        .map_err(|e| format!("Failed to read task history: {}", e))?;
+    
+    let base_title = history.into_iter()
+        .filter(|entry| entry.device_id == local_device_id && entry.op == ChangeOp::Update)
+        .filter(|entry| merged_at.map_or(true, |merged_at| entry.changed_at > merged_at))
+        .filter_map(|entry| entry.old_values)
+        .filter_map(|old_values| serde_json::from_str::<serde_json::Value>(&old_values).ok())
+        .find_map(|old_values| old_values.get("title")?.as_str().map(str::to_string));
+    // Renamed and renamed back is no rename
+    Ok(base_title.filter(|title| *title != local.title))
+}
+
+fn apply_record(
+    conn: &rusqlite::Connection,
+    local_device_id: &str,
+    merged_at: Option<DateTime<Utc>>,
+    record: ChangeRecord,
+    report: &mut SyncReport,
+) -> Result<Option<Applied>, String> {
+    let task_id = match &record.change {
+        Change::Upsert { task } => task.id,
+        Change::Delete { task_id } => *task_id,
+    };
+    let local = match db::get_task_by_id(conn, task_id.into()) {
+        Ok(task) => Some(task),
+        Err(rusqlite::Error::QueryReturnedNoRows) => None,
+        Err(e) => return Err(format!("Failed to fetch task {}: {}", task_id, e)),
+    };
+    
+    match (record.change, local) {
+        (Change::Upsert { task }, None) => {
+            let task = *task;
+            let deleted_at = db::get_sync_tombstone(conn, &task.id)
+                .map_err(|e| format!("Failed to read deleted task: {}", e))?;
+            if deleted_at.is_some_and(|deleted_at| deleted_at >= task.updated_at) {
+                report.skipped += 1;
+                return Ok(None);
+            }
+            
+            db::insert(conn, &task)
+                .map_err(|e| format!("Failed to insert synced task: {}", e))?;
+            report.applied += 1;
+            Ok(Some(Applied::Created(task)))
+        }
+        (Change::Upsert { task }, Some(local)) => {
+            let task = *task;
+            if task.updated_at == local.updated_at {
+                report.skipped += 1;
+                return Ok(None);
+            }
+            
+            // Last write wins; the device ID breaks exact ties the same way on every device
+            let remote_wins = (task.updated_at, record.device_id.as_str()) > (local.updated_at, local_device_id);
+            let winner = if remote_wins { ConflictWinner::Remote } else { ConflictWinner::Local };
+            
+            // Titles merge on their own: a side that didn't rename the task takes the other's title
+            let base_title = title_before_local_renames(conn, local_device_id, merged_at, &local)?;
+            let renamed_here = base_title.is_some();
+            let renamed_there = task.title != base_title.unwrap_or_else(|| local.title.clone());
+            if renamed_here && renamed_there && task.title != local.title {
+                // Renamed differently on both: the winner applies for now, both versions wait for the user
+                db::insert_sync_conflict(conn, &local, &task, &record.device_id, winner)
+                    .map_err(|e| format!("Failed to record sync conflict: {}", e))?;
+                report.conflicts += 1;
+            }
+            
+            let merged = match (remote_wins, renamed_here, renamed_there) {
+                (true, true, false) => Task { title: local.title.clone(), ..task },
+                (true, _, _) => task,
+                (false, false, true) => Task { title: task.title, ..local },
+                (false, _, _) => {
+                    report.skipped += 1;
+                    return Ok(None);
+                }
+            };
+            db::overwrite_task(conn, &merged)
+                .map_err(|e| format!("Failed to update synced task: {}", e))?;
+            if remote_wins {
+                db::supersede_sync_conflicts(conn, &merged.id, merged.updated_at)
+                    .map_err(|e| format!("Failed to update sync conflicts: {}", e))?;
+            }
+            report.applied += 1;
+            Ok(Some(Applied::Updated(merged)))
+        }
+        (Change::Delete { task_id }, local) => {
+            // An edit made here after the delete keeps the task
+            if local.as_ref().is_some_and(|local| local.updated_at > record.recorded_at) {
+                report.skipped += 1;
+                return Ok(None);
+            }
+            
+            db::record_sync_tombstone(conn, &task_id, record.recorded_at)
+                .map_err(|e| format!("Failed to record deleted task: {}", e))?;
+            if local.is_none() {
+                return Ok(None);
+            }
+            db::delete_task_by_id(conn, task_id.into())
+                .map_err(|e| format!("Failed to delete synced task: {}", e))?;
+            report.applied += 1;
+            Ok(Some(Applied::Deleted(task_id)))
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use crate::db::{self, Database};
+use crate::helpers::like::escape_like;
+use crate::helpers::tags;
+use crate::structs::tag::{TagPrefix, TagStats, TagSuggestion};
+use tracing::debug;
+
+const SUGGESTION_LIMIT: i64 = 10;
+
+// Tags starting with the prefix, most used first, for auto-complete
+pub fn suggest_tags(db: &Database, payload: TagPrefix) -> Result<Vec<TagSuggestion>, String> {
+    let prefix = payload.prefix.trim();
+    let prefix = prefix.strip_prefix('#').unwrap_or(prefix).to_lowercase();
+    // Nothing else can be in a tag; '%' would otherwise match anything
+    if !prefix.chars().all(tags::is_tag_char) {
+        return Ok(Vec::new());
+    }
+    refresh(db)?;
+    
+    let pattern = format!("{}%", escape_like(&prefix));
+    let conn = db.get_read_connection();
+    db::suggest_tags(&conn, &pattern, SUGGESTION_LIMIT)
+        .map_err(|e| format!("Failed to fetch tags: {}", e))
+}
+
+// Open and completed tasks per tag, most used first
+pub fn get_tag_stats(db: &Database) -> Result<Vec<TagStats>, String> {
+    refresh(db)?;
+    
+    let conn = db.get_read_connection();
+    db::get_tag_stats(&conn)
+        .map_err(|e| format!("Failed to fetch tag stats: {}", e))
+}
+
+// Read the tags of tasks changed since the last tag query. Sealed notes can't be read while
+// the app is locked, so then those tasks wait for the next query after the unlock.
+fn refresh(db: &Database) -> Result<(), String> {
+    if db.is_locked() {
+        return Ok(());
+    }
+    
+    let conn = db.get_connection();
+    let tx = conn.unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    let stale = db::get_stale_tag_tasks(&tx)
+        .map_err(|e| format!("Failed to read task tags: {}", e))?;
+    if stale.is_empty() {
+        return Ok(());
+    }
+    
+    for task in &stale {
+        let text = format!("{} {}", task.title, task.notes.as_deref().unwrap_or(""));
+        db::replace_task_tags(&tx, task.task_id, &tags::extract_tags(&text))
+            .map_err(|e| format!("Failed to save task tags: {}", e))?;
+    }
+    db::clear_stale_tag_tasks(&tx)
+        .and_then(|_| tx.commit())
+        .map_err(|e| format!("Failed to save task tags: {}", e))?;
+    
+    debug!("Read tags of {} changed tasks", stale.len());
+    Ok(())
+}
@@ -1,231 +1,682 @@
-use chrono::Utc;
-use crate::services::calendar_service;
+use std::collections::{HashMap, HashSet};
+use chrono::{DateTime, Utc};
+use tauri::AppHandle;
+use uuid::Uuid;
+use crate::services::{calendar_journal_service, calendar_service, event_service, github_service, project_service, remembered_date_service, rule_service, settings_service, vault_service};
 use crate::db::{self, Database, insert};
-use crate::structs::task_struct::{Task, Status};
-use crate::helpers::parse_date::parse_date_range;
-use crate::structs::dto::{TaskData, DateQuery, TaskId};
+use crate::structs::calendar_event::CalendarPrivacy;
+use crate::structs::calendar_journal::JournalOperation;
+use crate::structs::work_session::PauseReason;
+use crate::structs::reminder_frequency::{DefaultRemindersReport, ReminderFrequency, ReminderScope};
+use crate::structs::task_struct::{normalize_context, Energy, Task, TaskFilter, TaskId, TaskListItem, Status};
+use crate::helpers::datetime::{parse_datetime, parse_deadline};
+use crate::helpers::parse_date::{local_day, parse_date_range};
+use crate::structs::dto::{TaskData, DateQuery, TaskRef, TaskIds, QuickAddData, ContextQuery, MergeTasksData, PauseTaskData, CompleteTaskData, ApplyDefaultRemindersData, TaskWindowQuery};
+use crate::structs::overview::{DailySummary, TodayOverview};
+use crate::structs::rule::RuleTrigger;
+use crate::structs::settings::CompletionEffect;
+use crate::thirdparty::webhook;
+use tracing::{debug, info, warn, error};
 
-pub fn create_task(payload: TaskData, db: &Database) -> Result<Task, String> {
-    // Parse ISO 8601 datetime string
-    let created_at = payload.created_at.parse::<chrono::DateTime<Utc>>()
-        .map_err(|e| format!("Invalid datetime format: {}", e))?;
+// Number of tasks listed in the day overview (tray menu, widgets)
+const OVERVIEW_TOP_TASKS: i64 = 3;
+// Characters of notes sent with each task in list views
+const NOTES_PREVIEW_CHARS: i64 = 280;
+// Clients send a UUID; anything much longer is not a key
+const MAX_IDEMPOTENCY_KEY_LEN: usize = 128;
+// What an unestimated task is taken to need when fitting tasks into free time, as the scheduler does
+const UNESTIMATED_MINUTES: i32 = 30;
+// One placeholder per ID; well under SQLite's limit on them
+const MAX_BATCH_IDS: usize = 500;
+// A line or two about the stretch of work, not a second set of task notes
+const MAX_SESSION_NOTE_CHARS: usize = 280;
+// Rows a virtualized list can ask for at once; a screenful or a few
+const MAX_WINDOW_ROWS: i64 = 500;
+
+pub fn create_task(payload: TaskData, db: &Database, app: &AppHandle) -> Result<Task, String> {
+    let offset = settings_service::day_offset(db, None)?;
+    let created_at = parse_datetime("createdAt", &payload.created_at, offset)?;
+    let reminder_frequency = settings_service::reminder_frequency(db, payload.reminder_frequency)?;
+    let task = Task::new(&payload.title, created_at, None, reminder_frequency);
+    
+    let Some(key) = payload.idempotency_key.as_deref().map(str::trim).filter(|key| !key.is_empty()) else {
+        {
+            let conn = db.get_connection();
+            insert(&conn, &task).map_err(|e| format!("Failed to insert task: {}", e))?;
+        } // DB lock released here
+        event_service::emit_task_created(app, &task);
+        rule_service::run_rules(app, RuleTrigger::TaskCreated, &task);
+        return Ok(task);
+    };
+    if key.len() > MAX_IDEMPOTENCY_KEY_LEN {
+        return Err(format!("Idempotency key is longer than {} characters", MAX_IDEMPOTENCY_KEY_LEN));
+    }
+    
+    // Lookup and insert share the write lock, so a second submission waits and then finds the first
+    {
+        let conn = db.get_connection();
+        let tx = conn.unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        
+        if let Some(task_id) = db::get_task_id_by_idempotency_key(&tx, key)
+            .map_err(|e| format!("Failed to look up idempotency key: {}", e))?
+        {
+            info!("Task for idempotency key {} already exists: {}", key, task_id);
+            return db::get_task_by_id(&tx, task_id)
+                .map_err(|e| format!("Failed to get task by ID: {}", e));
+        }
+        
+        insert(&tx, &task).map_err(|e| format!("Failed to insert task: {}", e))?;
+        db::insert_task_idempotency_key(&tx, key, task.id.into())
+            .map_err(|e| format!("Failed to record idempotency key: {}", e))?;
+        
+        tx.commit().map_err(|e| format!("Failed to insert task: {}", e))?;
+    } // DB lock released here
+    
+    event_service::emit_task_created(app, &task);
+    rule_service::run_rules(app, RuleTrigger::TaskCreated, &task);
+    Ok(task)
+}
+
+// Insert a new task without emitting events; used directly by the CLI, which has no AppHandle
+pub fn add_task(title: &str, created_at: DateTime<Utc>, deadline: Option<DateTime<Utc>>, db: &Database) -> Result<Task, String> {
+    // Before the lock: settings may be read through the same connection
+    let reminder_frequency = settings_service::reminder_frequency(db, None)?;
+    let mut task = Task::new(title, created_at, None, reminder_frequency);
+    task.deadline = deadline;
     
     // Use the global database connection
     let conn = db.get_connection();
-
-    let task = Task::new(&payload.title, created_at, None);
     insert(&conn, &task).map_err(|e| format!("Failed to insert task: {}", e))?;
     
     Ok(task)
 }
 
-pub fn get_tasks_by_date(payload: DateQuery, db: &Database) -> Result<Vec<Task>, String> {
-    let (start_of_day, end_of_day) = parse_date_range(&payload.date)?;
+// Create a task from free text typed into the quick-add window
+pub fn quick_add_task(payload: QuickAddData, db: &Database, app: &AppHandle) -> Result<Task, String> {
+    let title = payload.text.trim();
+    if title.is_empty() {
+        return Err("Task title cannot be empty".to_string());
+    }
     
-    let sql = include_str!("../db/sql/get_tasks_by_date.sql");
-    let conn = db.get_connection();
-    let tasks = db::query_tasks_by_date_range(&conn, start_of_day, end_of_day, sql)
+    let task = add_task(title, Utc::now(), None, db)?;
+    
+    event_service::emit_task_created(app, &task);
+    rule_service::run_rules(app, RuleTrigger::TaskCreated, &task);
+    Ok(task)
+}
+
+pub fn get_tasks_by_date(payload: DateQuery, db: &Database) -> Result<Vec<TaskListItem>, String> {
+    let offset = settings_service::day_offset(db, payload.utc_offset_minutes)?;
+    let (start_of_day, end_of_day) = parse_date_range("date", &payload.date, Some(offset))?;
+    
+    let sql = include_str!("../db/sql/get_task_list_by_date.sql");
+    let conn = db.get_read_connection();
+    let tasks = db::query_task_list_by_date_range(&conn, start_of_day, end_of_day, NOTES_PREVIEW_CHARS, sql)
         .map_err(|e| format!("Failed to query tasks: {}", e))?;
     
     Ok(tasks)
 }
 
-pub fn get_tasks_by_date_not_completed(payload: DateQuery, db: &Database) -> Result<Vec<Task>, String> {
-    let (start_of_day, end_of_day) = parse_date_range(&payload.date)?;
+pub fn get_tasks_by_date_not_completed(payload: DateQuery, db: &Database) -> Result<Vec<TaskListItem>, String> {
+    let offset = settings_service::day_offset(db, payload.utc_offset_minutes)?;
+    let (start_of_day, end_of_day) = parse_date_range("date", &payload.date, Some(offset))?;
     
-    let sql = include_str!("../db/sql/get_tasks_by_date_not_completed.sql");
-    let conn = db.get_connection();
-    let tasks = db::query_tasks_by_date_range(&conn, start_of_day, end_of_day, sql)
+    let sql = include_str!("../db/sql/get_task_list_by_date_not_completed.sql");
+    let conn = db.get_read_connection();
+    let tasks = db::query_task_list_by_date_range(&conn, start_of_day, end_of_day, NOTES_PREVIEW_CHARS, sql)
         .map_err(|e| format!("Failed to query tasks: {}", e))?;
     
     Ok(tasks)
 }
 
-pub fn start_task(payload: TaskId, db: &Database) -> Result<Task, String> {
-    let conn = db.get_connection();
+// How many rows a virtualized list has, so it can size itself before fetching any
+pub fn count_tasks(payload: TaskFilter, db: &Database) -> Result<i64, String> {
+    let conn = db.get_read_connection();
+    db::count_tasks(&conn, &payload)
+        .map_err(|e| format!("Failed to count tasks: {}", e))
+}
+
+// The rows of a virtualized list in view; the list asks again as it scrolls
+pub fn get_tasks_window(payload: TaskWindowQuery, db: &Database) -> Result<Vec<TaskListItem>, String> {
+    if payload.offset < 0 {
+        return Err("Offset can't be negative".to_string());
+    }
+    if !(1..=MAX_WINDOW_ROWS).contains(&payload.limit) {
+        return Err(format!("Limit must be between 1 and {}", MAX_WINDOW_ROWS));
+    }
+    
+    let conn = db.get_read_connection();
+    db::get_tasks_window(&conn, &payload.filter, NOTES_PREVIEW_CHARS, payload.offset, payload.limit)
+        .map_err(|e| format!("Failed to query tasks: {}", e))
+}
+
+// "What can I do now": open tasks in a context that need no more energy and time than there is
+pub fn get_tasks_for_context(payload: ContextQuery, db: &Database) -> Result<Vec<Task>, String> {
+    if payload.available_minutes.is_some_and(|minutes| minutes <= 0) {
+        return Err("Available time must be more than 0 minutes".to_string());
+    }
+    let context = match payload.context.as_deref() {
+        Some(context) => Some(normalize_context(context).ok_or_else(|| "Context can't be empty".to_string())?),
+        None => None,
+    };
+    let max_energy = payload.max_energy.as_deref().map(str::parse::<Energy>).transpose()?;
+    
+    let conn = db.get_read_connection();
+    db::get_tasks_for_context(&conn, context.as_deref(), max_energy, payload.available_minutes, UNESTIMATED_MINUTES)
+        .map_err(|e| format!("Failed to query tasks: {}", e))
+}
+
+pub fn get_today_overview(payload: DateQuery, db: &Database) -> Result<TodayOverview, String> {
+    let offset = settings_service::day_offset(db, payload.utc_offset_minutes)?;
+    let (start_of_day, end_of_day) = parse_date_range("date", &payload.date, Some(offset))?;
     
-    db::update_task_status(&conn, &payload.id, Status::Ongoing)
-        .map_err(|e| format!("Failed to start task: {}", e))
+    // Day summaries only hold days in the settings offset
+    let summarized = offset == settings_service::day_offset(db, None)?;
+    let remembered_dates = remembered_date_service::due_reminders(db, local_day(start_of_day, offset))?;
+    
+    let conn = db.get_read_connection();
+    let remaining_count = if summarized {
+        let day = local_day(start_of_day, offset);
+        db::get_day_summaries(&conn, day, day)
+            .map(|rows| rows.first().map_or(0, |row| row.created - row.created_and_completed))
+    } else {
+        db::count_tasks_by_date_not_completed(&conn, start_of_day, end_of_day)
+    }.map_err(|e| format!("Failed to count tasks: {}", e))?;
+    let top_tasks = db::get_top_tasks_by_date(&conn, start_of_day, end_of_day, NOTES_PREVIEW_CHARS, OVERVIEW_TOP_TASKS)
+        .map_err(|e| format!("Failed to query tasks: {}", e))?;
+    
+    Ok(TodayOverview {
+        remaining_count,
+        top_tasks,
+        remembered_dates,
+    })
 }
 
-pub fn pause_task(payload: TaskId, db: &Database) -> Result<Task, String> {
-    let (task, event_id) = {
+// Completed today vs still open among today's tasks
+pub fn get_daily_summary(payload: DateQuery, db: &Database) -> Result<DailySummary, String> {
+    let offset = settings_service::day_offset(db, payload.utc_offset_minutes)?;
+    let (start_of_day, end_of_day) = parse_date_range("date", &payload.date, Some(offset))?;
+    
+    let (completed, remaining) = {
+        let conn = db.get_read_connection();
+        let completed = db::query_tasks_by_date_range(&conn, start_of_day, end_of_day, include_str!("../db/sql/get_tasks_completed_between.sql"))
+            .map_err(|e| format!("Failed to query tasks: {}", e))?;
+        let remaining = db::query_tasks_by_date_range(&conn, start_of_day, end_of_day, include_str!("../db/sql/get_tasks_by_date_not_completed.sql"))
+            .map_err(|e| format!("Failed to query tasks: {}", e))?;
+        (completed, remaining)
+    }; // DB lock released here
+    
+    Ok(DailySummary {
+        date: local_day(start_of_day, offset),
+        completed_count: completed.len(),
+        remaining_count: remaining.len(),
+        tracked_minutes: completed.iter().filter_map(Task::time_spent).map(|spent| spent.num_minutes()).sum(),
+        completed,
+        remaining,
+    })
+}
+
+pub fn start_task(payload: TaskRef, db: &Database, app: &AppHandle) -> Result<Task, String> {
+    let task = {
         let conn = db.get_connection();
         
-        let task = db::update_task_status(&conn, &payload.id, Status::Paused)
+        db::update_task_status(&conn, payload.id, Status::Ongoing)
+            .map_err(|e| format!("Failed to start task: {}", e))?
+    }; // DB lock released here
+    
+    event_service::emit_task_status_changed(app, &task);
+    Ok(task)
+}
+
+pub async fn pause_task(payload: PauseTaskData, db: &Database, app: &AppHandle) -> Result<Task, String> {
+    let note = session_note(payload.note)?;
+    pause_task_at(payload.id, Utc::now(), payload.reason, note.as_deref(), db, app).await
+}
+
+// Pause with an explicit pause time, e.g. when the user went idle before we noticed
+pub async fn pause_task_at(
+    task_id: TaskId,
+    paused_at: DateTime<Utc>,
+    reason: Option<PauseReason>,
+    note: Option<&str>,
+    db: &Database,
+    app: &AppHandle,
+) -> Result<Task, String> {
+    let (task, event_id, journal_id) = {
+        let conn = db.get_connection();
+        let tx = conn.unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        
+        let task = db::pause_task_at(&tx, task_id, paused_at, reason, note)
             .map_err(|e| format!("Failed to pause task: {}", e))?;
         
-        let event_id = db::get_task_google_event_id(&conn, &payload.id)
+        let event_id = db::get_task_google_event_id(&tx, task_id)
             .map_err(|e| format!("Failed to get calendar event: {}", e))?;
         
-        (task, event_id)
+        let journal_id = journal_event_sync(&tx, task_id, &event_id, &task)?;
+        
+        tx.commit().map_err(|e| format!("Failed to pause task: {}", e))?;
+        (task, event_id, journal_id)
     }; // DB lock released here
     
     // If task has calendar event and deadline, remove reminders (pause alarms)
-    if let Some(event_id) = event_id {
-        if let Some(deadline) = task.deadline {
-            println!("Pausing calendar reminders for task: {}", task.id);
-            match tokio::runtime::Runtime::new()
-                .map_err(|e| format!("Failed to create runtime: {}", e))?
-                .block_on(calendar_service::update_task_calendar_event(
-                    db,
-                    &event_id,
-                    &task.title,
-                    task.notes.as_deref(),
-                    deadline,
-                    "", // Empty reminder_frequency to remove all reminders
-                )) {
-                Ok(_) => println!("Calendar reminders paused"),
-                Err(e) if e == "EVENT_NOT_FOUND" => {
-                    println!("Calendar event was deleted externally, clearing from database");
-                    let conn = db.get_connection();
-                    let _ = db::clear_task_google_event_id(&conn, &payload.id);
-                }
-                Err(e) => eprintln!("Warning: Failed to pause calendar reminders: {}", e),
+    if let (Some(event_id), Some(deadline), Some(journal_id)) = (event_id, task.deadline, journal_id) {
+        info!("Pausing calendar reminders for task: {}", task.id);
+        let result = calendar_service::update_task_calendar_event(
+            db,
+            &event_id,
+            task_id,
+            &task.title,
+            Some(&calendar_service::task_event_description(&task)),
+            deadline,
+            "", // Empty reminder_frequency to remove all reminders
+        ).await;
+        match &result {
+            Ok(_) => info!("Calendar reminders paused"),
+            Err(e) if e == "EVENT_NOT_FOUND" => {
+                info!("Calendar event was deleted externally, clearing from database");
+                let conn = db.get_connection();
+                let _ = db::clear_task_google_event_id(&conn, task_id);
             }
+            Err(e) => warn!("Failed to pause calendar reminders: {}", e),
         }
+        calendar_journal_service::finish(db, journal_id, task_id, JournalOperation::Sync, &result);
     }
     
+    event_service::emit_task_status_changed(app, &task);
     Ok(task)
 }
 
-pub fn resume_task(payload: TaskId, db: &Database) -> Result<Task, String> {
-    let (task, event_id) = {
+pub async fn resume_task(payload: TaskRef, db: &Database, app: &AppHandle) -> Result<Task, String> {
+    let (task, event_id, journal_id) = {
         let conn = db.get_connection();
+        let tx = conn.unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
         
-        let task = db::update_task_status(&conn, &payload.id, Status::Ongoing)
+        let task = db::update_task_status(&tx, payload.id, Status::Ongoing)
             .map_err(|e| format!("Failed to resume task: {}", e))?;
         
-        let event_id = db::get_task_google_event_id(&conn, &payload.id)
+        let event_id = db::get_task_google_event_id(&tx, payload.id)
             .map_err(|e| format!("Failed to get calendar event: {}", e))?;
         
-        (task, event_id)
+        let journal_id = journal_event_sync(&tx, payload.id, &event_id, &task)?;
+        
+        tx.commit().map_err(|e| format!("Failed to resume task: {}", e))?;
+        (task, event_id, journal_id)
     }; // DB lock released here
     
     // If task has calendar event and deadline, restore reminders
-    if let Some(event_id) = event_id {
-        if let Some(deadline) = task.deadline {
-            println!("Resuming calendar reminders for task: {}", task.id);
-            let reminder_freq_str = String::from(task.reminder_frequency.clone());
-            match tokio::runtime::Runtime::new()
-                .map_err(|e| format!("Failed to create runtime: {}", e))?
-                .block_on(calendar_service::update_task_calendar_event(
-                    db,
-                    &event_id,
-                    &task.title,
-                    task.notes.as_deref(),
-                    deadline,
-                    &reminder_freq_str, // Restore reminders from task settings
-                )) {
-                Ok(_) => println!("Calendar reminders resumed"),
-                Err(e) if e == "EVENT_NOT_FOUND" => {
-                    println!("Calendar event was deleted externally, clearing from database");
-                    let conn = db.get_connection();
-                    let _ = db::clear_task_google_event_id(&conn, &payload.id);
-                }
-                Err(e) => eprintln!("Warning: Failed to resume calendar reminders: {}", e),
+    if let (Some(event_id), Some(deadline), Some(journal_id)) = (event_id, task.deadline, journal_id) {
+        info!("Resuming calendar reminders for task: {}", task.id);
+        let reminder_freq_str = String::from(task.reminder_frequency.clone());
+        let result = calendar_service::update_task_calendar_event(
+            db,
+            &event_id,
+            payload.id,
+            &task.title,
+            Some(&calendar_service::task_event_description(&task)),
+            deadline,
+            &reminder_freq_str, // Restore reminders from task settings
+        ).await;
+        match &result {
+            Ok(_) => info!("Calendar reminders resumed"),
+            Err(e) if e == "EVENT_NOT_FOUND" => {
+                info!("Calendar event was deleted externally, clearing from database");
+                let conn = db.get_connection();
+                let _ = db::clear_task_google_event_id(&conn, payload.id);
             }
+            Err(e) => warn!("Failed to resume calendar reminders: {}", e),
         }
+        calendar_journal_service::finish(db, journal_id, payload.id, JournalOperation::Sync, &result);
     }
     
+    event_service::emit_task_status_changed(app, &task);
     Ok(task)
 }
 
-pub fn complete_task(payload: TaskId, db: &Database) -> Result<Task, String> {
-    let (task, event_id) = {
+pub async fn complete_task(payload: CompleteTaskData, db: &Database, app: &AppHandle) -> Result<Task, String> {
+    let note = session_note(payload.note)?;
+    let task = finish_task(payload.id, note.as_deref(), db, Some(app)).await?;
+    
+    event_service::emit_task_status_changed(app, &task);
+    rule_service::run_rules(app, RuleTrigger::TaskCompleted, &task);
+    Ok(task)
+}
+
+// Mark a task completed and run the completion effects without emitting events.
+// Effects that need the app (the vault log) are skipped when there is none (CLI).
+pub async fn finish_task(task_id: TaskId, note: Option<&str>, db: &Database, app: Option<&AppHandle>) -> Result<Task, String> {
+    let effects = db.settings()
+        .map_err(|e| format!("Failed to fetch settings: {}", e))?
+        .completion_effects;
+    let keep_event = effects.contains(CompletionEffect::KeepCalendarEvent);
+    
+    let (task, event_id, journal_id) = {
         let conn = db.get_connection();
+        let tx = conn.unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        
+        // Close the running session first so the note lands on it; a paused task has none to take it
+        if note.is_some() {
+            db::end_work_session(&tx, task_id, Utc::now(), None, note)
+                .map_err(|e| format!("Failed to end work session: {}", e))?;
+        }
         
-        let task = db::update_task_status(&conn, &payload.id, Status::Completed)
+        let task = db::update_task_status(&tx, task_id, Status::Completed)
             .map_err(|e| format!("Failed to complete task: {}", e))?;
         
-        let event_id = db::get_task_google_event_id(&conn, &payload.id)
+        if effects.contains(CompletionEffect::Archive) {
+            db::archive_task(&tx, task_id, Utc::now())
+                .map_err(|e| format!("Failed to archive task: {}", e))?;
+        }
+        
+        let event_id = db::get_task_google_event_id(&tx, task_id)
             .map_err(|e| format!("Failed to get calendar event: {}", e))?;
         
-        (task, event_id)
+        let journal_id = match &event_id {
+            Some(_) if keep_event => journal_event_sync(&tx, task_id, &event_id, &task)?,
+            Some(event_id) => Some(calendar_journal_service::record(&tx, task_id, JournalOperation::Delete, Some(event_id))?),
+            None => None,
+        };
+        
+        tx.commit().map_err(|e| format!("Failed to complete task: {}", e))?;
+        (task, event_id, journal_id)
     }; // DB lock released here
     
-    // If task has calendar event, delete it (task is completed)
-    if let Some(event_id) = event_id {
-        println!("Deleting calendar event for completed task: {}", task.id);
-        if let Err(e) = tokio::runtime::Runtime::new()
-            .map_err(|e| format!("Failed to create runtime: {}", e))?
-            .block_on(calendar_service::delete_task_calendar_event(db, &event_id)) 
-        {
-            eprintln!("Warning: Failed to delete calendar event: {}", e);
+    if let (Some(event_id), Some(journal_id)) = (event_id, journal_id) {
+        if keep_event {
+            keep_calendar_event(db, &task, &event_id, journal_id).await;
         } else {
-            // Clear event ID from database
-            let conn = db.get_connection();
-            let _ = db::clear_task_google_event_id(&conn, &payload.id);
+            delete_calendar_event(db, &task, &event_id, journal_id).await;
+        }
+    }
+    
+    github_service::close_linked_issue(db, task_id).await;
+    
+    for effect in effects.iter() {
+        let result = match effect {
+            // Handled in the transaction and calendar step above
+            CompletionEffect::Archive | CompletionEffect::KeepCalendarEvent => Ok(()),
+            CompletionEffect::LogToDailyNote => match app {
+                Some(app) => vault_service::log_completion(app, &task),
+                None => Ok(()),
+            },
+            CompletionEffect::Webhook => post_completion_webhook(db, &task).await,
+        };
+        // The task is completed either way; a failed effect only gets logged
+        if let Err(e) = result {
+            warn!("Completion effect '{}' failed for task {}: {}", effect.as_str(), task.id, e);
         }
     }
     
     Ok(task)
 }
 
-pub fn delete_task(payload: TaskId, db: &Database) -> Result<(), String> {
-    // Scope 1: Get calendar event ID and release lock
-    let event_id = {
+// Default: the event has served its purpose
+async fn delete_calendar_event(db: &Database, task: &Task, event_id: &str, journal_id: i64) {
+    let task_id = TaskId::from(task.id);
+    info!("Deleting calendar event for completed task: {}", task.id);
+    let result = calendar_service::delete_task_calendar_event(db, event_id).await;
+    if let Err(e) = &result {
+        warn!("Failed to delete calendar event: {}", e);
+    } else {
+        // Clear event ID from database
         let conn = db.get_connection();
+        let _ = db::clear_task_google_event_id(&conn, task_id);
+    }
+    calendar_journal_service::finish(db, journal_id, task_id, JournalOperation::Delete, &result);
+}
+
+// Keep-calendar-event effect: the event stays as a record, with its reminders removed
+async fn keep_calendar_event(db: &Database, task: &Task, event_id: &str, journal_id: i64) {
+    let Some(deadline) = task.deadline else {
+        return;
+    };
+    let task_id = TaskId::from(task.id);
+    info!("Keeping calendar event for completed task: {}", task.id);
+    let result = calendar_service::update_task_calendar_event(
+        db,
+        event_id,
+        task_id,
+        &task.title,
+        Some(&calendar_service::task_event_description(task)),
+        deadline,
+        "", // No reminders for a finished task
+    ).await;
+    if matches!(&result, Err(e) if e == "EVENT_NOT_FOUND") {
+        let conn = db.get_connection();
+        let _ = db::clear_task_google_event_id(&conn, task_id);
+    } else if let Err(e) = &result {
+        warn!("Failed to remove reminders from calendar event: {}", e);
+    }
+    calendar_journal_service::finish(db, journal_id, task_id, JournalOperation::Sync, &result);
+}
+
+async fn post_completion_webhook(db: &Database, task: &Task) -> Result<(), String> {
+    let url = db.settings()
+        .map_err(|e| format!("Failed to fetch settings: {}", e))?
+        .completion_webhook_url
+        .ok_or("No webhook URL is set")?;
+    
+    webhook::post_json(&url, &serde_json::json!({
+        "event": "task.completed",
+        "task": task,
+    })).await
+}
+
+// Undo a completion: back to not-started with its timestamps cleared
+pub fn reopen_task(task_id: TaskId, db: &Database, app: &AppHandle) -> Result<Task, String> {
+    let task = {
+        let conn = db.get_connection();
+        db::update_task_status(&conn, task_id, Status::NotStarted)
+            .map_err(|e| format!("Failed to reopen task: {}", e))?
+    }; // DB lock released here
+    
+    event_service::emit_task_status_changed(app, &task);
+    Ok(task)
+}
+
+pub async fn delete_task(payload: TaskRef, db: &Database, app: &AppHandle) -> Result<(), String> {
+    // Delete from database, journaling the event removal in the same transaction
+    let (event_id, journal_id) = {
+        let conn = db.get_connection();
+        let tx = conn.unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        
+        let event_id = db::get_task_google_event_id(&tx, payload.id)
+            .map_err(|e| format!("Failed to get calendar event: {}", e))?;
+        
+        let deleted = db::delete_task_by_id(&tx, payload.id)
+            .map_err(|e| format!("Failed to delete task: {}", e))?;
+        if deleted == 0 {
+            return Err("Task not found".to_string());
+        }
         
-        db::get_task_google_event_id(&conn, &payload.id)
-            .map_err(|e| format!("Failed to get calendar event: {}", e))?
+        let journal_id = match &event_id {
+            Some(event_id) => Some(calendar_journal_service::record(&tx, payload.id, JournalOperation::Delete, Some(event_id))?),
+            None => None,
+        };
+        
+        tx.commit().map_err(|e| format!("Failed to delete task: {}", e))?;
+        (event_id, journal_id)
     }; // DB lock released here
     
     // Delete calendar event from Google if exists
-    if let Some(event_id) = event_id {
-        println!("Deleting calendar event: {}", event_id);
-        if let Err(e) = tokio::runtime::Runtime::new()
-            .map_err(|e| format!("Failed to create runtime: {}", e))?
-            .block_on(calendar_service::delete_task_calendar_event(db, &event_id)) 
-        {
-            eprintln!("Warning: Failed to delete calendar event: {}", e);
+    if let (Some(event_id), Some(journal_id)) = (event_id, journal_id) {
+        info!("Deleting calendar event: {}", event_id);
+        let result = calendar_service::delete_task_calendar_event(db, &event_id).await;
+        if let Err(e) = &result {
+            warn!("Failed to delete calendar event: {}", e);
         }
+        calendar_journal_service::finish(db, journal_id, payload.id, JournalOperation::Delete, &result);
     }
     
-    // Scope 2: Delete from database (get fresh connection)
-    let deleted = {
+    event_service::emit_task_deleted(app, payload.id);
+    Ok(())
+}
+
+// Fold the source task into the target: notes appended, the earlier deadline kept, work sessions and
+// external links moved over, then the source deleted, all in one transaction. The source's calendar
+// event goes with it; the target's is updated when its deadline or notes changed.
+pub async fn merge_tasks(payload: MergeTasksData, db: &Database, app: &AppHandle) -> Result<Task, String> {
+    use crate::structs::task_update::TaskUpdateParsed;
+    
+    if payload.source_id == payload.target_id {
+        return Err("Can't merge a task into itself".to_string());
+    }
+    
+    let (merged, source_event_id, target_event_id, delete_journal_id, sync_journal_id) = {
         let conn = db.get_connection();
-        db::delete_task_by_id(&conn, &payload.id)
-            .map_err(|e| format!("Failed to delete task: {}", e))?
-    };
+        let tx = conn.unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        
+        let source = db::get_task_by_id(&tx, payload.source_id)
+            .map_err(|e| format!("Failed to get task {}: {}", payload.source_id, e))?;
+        let target = db::get_task_by_id(&tx, payload.target_id)
+            .map_err(|e| format!("Failed to get task {}: {}", payload.target_id, e))?;
+        let source_event_id = db::get_task_google_event_id(&tx, payload.source_id)
+            .map_err(|e| format!("Failed to get calendar event: {}", e))?;
+        let target_event_id = db::get_task_google_event_id(&tx, payload.target_id)
+            .map_err(|e| format!("Failed to get calendar event: {}", e))?;
+        
+        let notes = match (target.notes.clone(), source.notes.clone()) {
+            (Some(target_notes), Some(source_notes)) => Some(format!("{}\n\n{}", target_notes, source_notes)),
+            (target_notes, source_notes) => target_notes.or(source_notes),
+        };
+        let deadline = match (target.deadline, source.deadline) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        let update_data = TaskUpdateParsed {
+            title: None,
+            notes: (notes != target.notes).then_some(notes),
+            deadline: (deadline != target.deadline).then_some(deadline),
+            has_calendar_integration: None,
+            calendar_email: None,
+            reminder_frequency: None,
+            notifications_enabled: None,
+            estimate_minutes: target.estimate_minutes.is_none().then_some(source.estimate_minutes),
+            project_id: None,
+            assignee: None,
+            context: target.context.is_none().then_some(source.context.clone()),
+            energy: target.energy.is_none().then_some(source.energy),
+            calendar_privacy: target.calendar_privacy.is_none().then_some(source.calendar_privacy),
+            updated_at: Utc::now(),
+        };
+        let merged = db::update_task(&tx, payload.target_id, &update_data)
+            .map_err(|e| format!("Failed to update task: {}", e))?;
+        if merged.deadline != target.deadline {
+            db::clear_task_notifications(&tx, payload.target_id)
+                .map_err(|e| format!("Failed to clear notifications: {}", e))?;
+        }
+        
+        // A running source stops here; its sessions count as work on the target
+        db::end_work_session(&tx, payload.source_id, Utc::now(), None, None)
+            .and_then(|_| db::move_task_work_sessions(&tx, payload.source_id, payload.target_id))
+            .and_then(|_| db::move_task_external_links(&tx, payload.source_id, payload.target_id))
+            .map_err(|e| format!("Failed to move task history: {}", e))?;
+        db::delete_task_by_id(&tx, payload.source_id)
+            .map_err(|e| format!("Failed to delete task: {}", e))?;
+        
+        let delete_journal_id = match &source_event_id {
+            Some(event_id) => Some(calendar_journal_service::record(&tx, payload.source_id, JournalOperation::Delete, Some(event_id))?),
+            None => None,
+        };
+        let changed = merged.deadline != target.deadline || merged.notes != target.notes;
+        let sync_journal_id = if changed {
+            journal_event_sync(&tx, payload.target_id, &target_event_id, &merged)?
+        } else {
+            None
+        };
+        
+        tx.commit().map_err(|e| format!("Failed to merge tasks: {}", e))?;
+        (merged, source_event_id, target_event_id, delete_journal_id, sync_journal_id)
+    }; // DB lock released here
     
-    if deleted == 0 {
-        Err("Task not found".to_string())
-    } else {
-        Ok(())
+    info!("Merged task {} into {}", payload.source_id, payload.target_id);
+    
+    if let (Some(event_id), Some(journal_id)) = (source_event_id, delete_journal_id) {
+        let result = calendar_service::delete_task_calendar_event(db, &event_id).await;
+        if let Err(e) = &result {
+            warn!("Failed to delete calendar event: {}", e);
+        }
+        calendar_journal_service::finish(db, journal_id, payload.source_id, JournalOperation::Delete, &result);
+    }
+    if let (Some(event_id), Some(deadline), Some(_)) = (target_event_id, merged.deadline, sync_journal_id) {
+        calendar_service::queue_task_calendar_update(app, &event_id, calendar_service::QueuedEventUpdate {
+            task_id: payload.target_id,
+            title: merged.title.clone(),
+            description: calendar_service::task_event_description(&merged),
+            deadline,
+            reminder_frequency: String::from(merged.reminder_frequency.clone()),
+            journal_id: sync_journal_id,
+        });
     }
+    
+    event_service::emit_task_deleted(app, payload.source_id);
+    event_service::emit_task_updated(app, &merged);
+    Ok(merged)
 }
 
-pub fn get_task_by_id(payload: TaskId, db: &Database) -> Result<Task, String> {
-    let conn = db.get_connection();
+pub fn get_task_by_id(payload: TaskRef, db: &Database) -> Result<Task, String> {
+    let conn = db.get_read_connection();
     
-    db::get_task_by_id(&conn, &payload.id)
+    db::get_task_by_id(&conn, payload.id)
         .map_err(|e| format!("Failed to get task by ID: {}", e))
 }
 
-pub async fn update_task(payload: crate::structs::task_update::TaskUpdate, db: &Database) -> Result<Task, String> {
+// Tasks in the order asked for, once each; IDs without a task are left out
+pub fn get_tasks_by_ids(payload: TaskIds, db: &Database) -> Result<Vec<Task>, String> {
+    if payload.ids.len() > MAX_BATCH_IDS {
+        return Err(format!("Can't fetch more than {} tasks at once", MAX_BATCH_IDS));
+    }
+    let mut seen = HashSet::new();
+    let ids: Vec<TaskId> = payload.ids.into_iter().filter(|id| seen.insert(*id)).collect();
+    
+    let tasks = {
+        let conn = db.get_read_connection();
+        db::get_tasks_by_ids(&conn, &ids)
+            .map_err(|e| format!("Failed to get tasks: {}", e))?
+    }; // DB lock released here
+    
+    let mut by_id: HashMap<TaskId, Task> = tasks.into_iter().map(|task| (TaskId::from(task.id), task)).collect();
+    Ok(ids.iter().filter_map(|id| by_id.remove(id)).collect())
+}
+
+pub fn get_task_notes(payload: TaskRef, db: &Database) -> Result<Option<String>, String> {
+    let conn = db.get_read_connection();
+    
+    db::get_task_notes(&conn, payload.id)
+        .map_err(|e| format!("Failed to get task notes: {}", e))
+}
+
+pub async fn update_task(payload: crate::structs::task_update::TaskUpdate, db: &Database, app: &AppHandle) -> Result<Task, String> {
     use crate::structs::task_update::TaskUpdateParsed;
     
-    println!("Updating task: {:?}", payload.id);
+    info!("Updating task: {}", payload.id);
+    
+    // Before the lock: settings may be read through the same connection
+    let offset = settings_service::day_offset(db, None)?;
+    let deadline_time = settings_service::default_deadline_time(db)?;
     
     // Scope 1: Get current state and update task in DB
-    let (_current_task, current_event_id, updated_task, calendar_enabled, new_deadline, reminder_freq_for_event) = {
+    let (_current_task, current_event_id, updated_task, calendar_enabled, new_deadline, reminder_freq_for_event, journal_id) = {
         let conn = db.get_connection();
+        let tx = conn.unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
         
         // Get current task and calendar event
-        let current_task = db::get_task_by_id(&conn, &payload.id)
+        let current_task = db::get_task_by_id(&tx, payload.id)
             .map_err(|e| format!("Failed to get current task: {}", e))?;
-        let current_event_id = db::get_task_google_event_id(&conn, &payload.id)
+        let current_event_id = db::get_task_google_event_id(&tx, payload.id)
             .map_err(|e| format!("Failed to get calendar event: {}", e))?;
         
-        println!("Current task found, has event: {}", current_event_id.is_some());
+        debug!("Current task found, has event: {}", current_event_id.is_some());
         
         // Parse deadline if provided
         let deadline = if let Some(ref deadline_str) = payload.data.deadline {
-            Some(Some(
-                deadline_str.parse::<chrono::DateTime<chrono::Utc>>()
-                    .map_err(|e| format!("Invalid deadline format: {}", e))?
-            ))
+            Some(Some(parse_deadline("deadline", deadline_str, offset, deadline_time)?))
         } else {
             None
         };
@@ -248,9 +699,52 @@ pub async fn update_task(payload: crate::structs::task_update::TaskUpdate, db: &
             }
         });
         
-        // Get reminder frequency for later use (before moving payload.data)
-        let default_freq = String::from(current_task.reminder_frequency.clone());
-        let reminder_freq_for_event = payload.data.reminder_frequency.clone().unwrap_or(default_freq);
+        let estimate_minutes = match payload.data.estimate_minutes {
+            Some(minutes) if minutes < 0 => return Err("Estimate can't be negative".to_string()),
+            Some(minutes) => Some((minutes > 0).then_some(minutes)),
+            None => None,
+        };
+        
+        let project_id = match payload.data.project_id.as_deref().map(str::trim) {
+            Some("") => Some(None),
+            Some(id) => Some(Some(Uuid::parse_str(id).map_err(|e| format!("Invalid project ID '{}': {}", id, e))?)),
+            None => None,
+        };
+        if let Some(Some(id)) = &project_id {
+            let project = db::get_project_by_id(&tx, id)
+                .map_err(|e| format!("Failed to fetch project: {}", e))?
+                .ok_or_else(|| format!("Project {} not found", id))?;
+            if project.archived_at.is_some() {
+                return Err(format!("Project {} is archived", project.name));
+            }
+        }
+        // Moving to another project drops the assignee unless a new one comes with it
+        let assignee = match (project_id, payload.data.assignee) {
+            (Some(None), _) => Some(None),
+            (Some(project), None) if project != current_task.project_id => Some(None),
+            (_, assignee) => assignee.map(|assignee| Some(assignee.trim().to_string()).filter(|a| !a.is_empty())),
+        };
+        if let Some(Some(assignee)) = &assignee {
+            project_service::check_assignee(&tx, project_id.unwrap_or(current_task.project_id), assignee)?;
+        }
+        
+        let context = payload.data.context.as_deref().map(normalize_context);
+        let energy = match payload.data.energy.as_deref().map(str::trim) {
+            Some("") => Some(None),
+            Some(energy) => Some(Some(energy.parse::<Energy>()?)),
+            None => None,
+        };
+        let calendar_privacy = match payload.data.calendar_privacy.as_deref().map(str::trim) {
+            Some("") => Some(None),
+            Some(privacy) => Some(Some(privacy.parse::<CalendarPrivacy>()?)),
+            None => None,
+        };
+        
+        let reminder_frequency = payload.data.reminder_frequency.as_deref()
+            .map(str::parse::<ReminderFrequency>)
+            .transpose()?;
+        // Get reminder frequency for later use (before moving it into the update)
+        let reminder_freq_for_event = String::from(reminder_frequency.clone().unwrap_or(current_task.reminder_frequency.clone()));
         
         let update_data = TaskUpdateParsed {
             title: payload.data.title,
@@ -258,67 +752,91 @@ pub async fn update_task(payload: crate::structs::task_update::TaskUpdate, db: &
             deadline,
             has_calendar_integration: payload.data.has_calendar_integration,
             calendar_email,
-            reminder_frequency: payload.data.reminder_frequency,
+            reminder_frequency,
+            notifications_enabled: payload.data.notifications_enabled,
+            estimate_minutes,
+            project_id,
+            assignee,
+            context,
+            energy,
+            calendar_privacy,
             updated_at: chrono::Utc::now(),
         };
         
-        let updated_task = db::update_task(&conn, &payload.id, &update_data)
+        let updated_task = db::update_task(&tx, payload.id, &update_data)
             .map_err(|e| format!("Failed to update task: {}", e))?;
         
-        println!("Task updated in DB");
+        // Deadline moved: due/overdue notifications should fire again for the new time
+        if deadline.is_some() && updated_task.deadline != current_task.deadline {
+            let _ = db::clear_task_notifications(&tx, payload.id);
+            
+            // Setting a first deadline isn't a move
+            if let (Some(old), Some(new)) = (current_task.deadline, updated_task.deadline) {
+                db::record_deadline_move(&tx, payload.id, new, old)
+                    .map_err(|e| format!("Failed to record deadline move: {}", e))?;
+            }
+        }
+        
+        info!("Task updated in DB");
         
         // Calculate calendar state
         let calendar_enabled = payload.data.has_calendar_integration.unwrap_or(current_task.has_calendar_integration);
         let new_deadline = if let Some(Some(d)) = deadline { Some(d) } else { current_task.deadline };
         
-        (current_task, current_event_id, updated_task, calendar_enabled, new_deadline, reminder_freq_for_event)
+        // Journal the calendar change with the edit so a crash can't lose it
+        let journal_id = if calendar_enabled && new_deadline.is_some() {
+            Some(calendar_journal_service::record(&tx, payload.id, JournalOperation::Sync, None)?)
+        } else if !calendar_enabled {
+            match &current_event_id {
+                Some(event_id) => Some(calendar_journal_service::record(&tx, payload.id, JournalOperation::Delete, Some(event_id))?),
+                None => None,
+            }
+        } else {
+            None
+        };
+        
+        tx.commit().map_err(|e| format!("Failed to update task: {}", e))?;
+        (current_task, current_event_id, updated_task, calendar_enabled, new_deadline, reminder_freq_for_event, journal_id)
     }; // Connection dropped here!
     
-    println!("Calendar enabled: {}, has deadline: {}", calendar_enabled, new_deadline.is_some());
+    debug!("Calendar enabled: {}, has deadline: {}", calendar_enabled, new_deadline.is_some());
     
-    if calendar_enabled && new_deadline.is_some() {
+    if let (true, Some(new_deadline)) = (calendar_enabled, new_deadline) {
         if let Some(existing_event_id) = current_event_id {
-            // Event already exists, try to UPDATE it
-            println!("Updating existing calendar event: {}", existing_event_id);
-            match calendar_service::update_task_calendar_event(
-                db,
-                &existing_event_id,
-                &updated_task.title,
-                updated_task.notes.as_deref(),
-                new_deadline.unwrap(),
-                &reminder_freq_for_event,
-            ).await {
-                Ok(_) => {
-                    println!("Calendar event updated successfully");
-                }
-                Err(e) if e == "EVENT_NOT_FOUND" => {
-                    // Event was deleted externally, clear it from database to stay in sync
-                    println!("Calendar event was deleted externally, clearing from database");
-                    let conn = db.get_connection();
-                    let _ = db::clear_task_google_event_id(&conn, &payload.id);
-                }
-                Err(e) => {
-                    eprintln!("Warning: Failed to update calendar event: {}", e);
-                }
-            }
+            // Event already exists; edits come in bursts, so coalesce them into one UPDATE
+            info!("Queueing update for calendar event: {}", existing_event_id);
+            calendar_service::queue_task_calendar_update(app, &existing_event_id, calendar_service::QueuedEventUpdate {
+                task_id: payload.id,
+                title: updated_task.title.clone(),
+                description: calendar_service::task_event_description(&updated_task),
+                deadline: new_deadline,
+                reminder_frequency: reminder_freq_for_event.clone(),
+                journal_id,
+            });
         } else {
             // No event exists, CREATE new one
-            println!("Creating new calendar event...");
-            match calendar_service::create_task_calendar_event(
+            info!("Creating new calendar event...");
+            let result = calendar_service::create_task_calendar_event(
                 db,
+                payload.id,
                 &updated_task.title,
-                updated_task.notes.as_deref(),
-                new_deadline.unwrap(),
+                Some(&calendar_service::task_event_description(&updated_task)),
+                new_deadline,
                 &reminder_freq_for_event,
-            ).await {
+            ).await;
+            if let Some(journal_id) = journal_id {
+                let outcome = result.as_ref().map(|_| ()).map_err(|e| e.clone());
+                calendar_journal_service::finish(db, journal_id, payload.id, JournalOperation::Sync, &outcome);
+            }
+            match result {
                 Ok(event_id) => {
-                    println!("Calendar event created: {}", event_id);
+                    info!("Calendar event created: {}", event_id);
                     // Save event ID in calendar_events table (get fresh connection)
                     let conn = db.get_connection();
-                    let _ = db::update_task_google_event_id(&conn, &payload.id, &event_id);
+                    let _ = db::update_task_google_event_id(&conn, payload.id, &event_id);
                 }
                 Err(e) => {
-                    eprintln!("Failed to create calendar event: {}", e);
+                    error!("Failed to create calendar event: {}", e);
                     return Err(format!("Failed to create calendar event: {}", e));
                 }
             }
@@ -326,16 +844,107 @@ pub async fn update_task(payload: crate::structs::task_update::TaskUpdate, db: &
     } else if !calendar_enabled && current_event_id.is_some() {
         // Calendar disabled, delete existing event
         if let Some(event_id) = current_event_id {
-            if let Err(e) = calendar_service::delete_task_calendar_event(db, &event_id).await {
-                eprintln!("Warning: Failed to delete calendar event: {}", e);
+            let result = calendar_service::delete_task_calendar_event(db, &event_id).await;
+            if let Err(e) = &result {
+                warn!("Failed to delete calendar event: {}", e);
+            }
+            // The journal entry keeps the event ID for a retry
+            {
+                let conn = db.get_connection();
+                let _ = db::clear_task_google_event_id(&conn, payload.id);
+            } // DB lock released here
+            if let Some(journal_id) = journal_id {
+                calendar_journal_service::finish(db, journal_id, payload.id, JournalOperation::Delete, &result);
             }
-            let conn = db.get_connection();
-            let _ = db::clear_task_google_event_id(&conn, &payload.id);
         }
     }
     
     // Return refreshed task (get fresh connection)
-    let conn = db.get_connection();
-    db::get_task_by_id(&conn, &payload.id)
-        .map_err(|e| format!("Failed to get updated task: {}", e))
+    let task = {
+        let conn = db.get_connection();
+        db::get_task_by_id(&conn, payload.id)
+            .map_err(|e| format!("Failed to get updated task: {}", e))?
+    }; // DB lock released here
+    
+    event_service::emit_task_updated(app, &task);
+    Ok(task)
+}
+
+// Give open tasks the default reminder frequency from settings, and their calendar events the
+// reminders to match in batch requests
+pub async fn apply_default_reminders(payload: ApplyDefaultRemindersData, db: &Database, app: &AppHandle) -> Result<DefaultRemindersReport, String> {
+    let frequency = settings_service::get_settings(db)?.default_reminder_frequency;
+    let now = Utc::now();
+    
+    let (tasks, updates) = {
+        let conn = db.get_connection();
+        let tx = conn.unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        
+        let mut tasks = db::get_open_tasks_for_reminder_default(&tx, &frequency, payload.scope == ReminderScope::Unset)
+            .map_err(|e| format!("Failed to get tasks: {}", e))?;
+        let mut updates = Vec::new();
+        for task in &mut tasks {
+            let task_id = TaskId::from(task.id);
+            db::set_task_reminder_frequency(&tx, task_id, &frequency, now)
+                .map_err(|e| format!("Failed to update task: {}", e))?;
+            task.reminder_frequency = frequency.clone();
+            task.updated_at = now;
+            
+            // Paused tasks keep their event without reminders
+            let Some(deadline) = task.deadline.filter(|_| task.has_calendar_integration && task.status != Status::Paused) else {
+                continue;
+            };
+            let Some(event_id) = db::get_task_google_event_id(&tx, task_id)
+                .map_err(|e| format!("Failed to get calendar event: {}", e))?
+            else {
+                continue;
+            };
+            // Journaled with the change so events a failed batch missed are retried
+            let journal_id = calendar_journal_service::record(&tx, task_id, JournalOperation::Sync, None)?;
+            updates.push((event_id, calendar_service::QueuedEventUpdate {
+                task_id,
+                title: task.title.clone(),
+                description: calendar_service::task_event_description(task),
+                deadline,
+                reminder_frequency: String::from(frequency.clone()),
+                journal_id: Some(journal_id),
+            }));
+        }
+        
+        tx.commit().map_err(|e| format!("Failed to update tasks: {}", e))?;
+        (tasks, updates)
+    }; // DB lock released here
+    
+    info!("Applied reminder frequency {} to {} tasks", frequency.as_db_str(), tasks.len());
+    for task in &tasks {
+        event_service::emit_task_updated(app, task);
+    }
+    
+    let results = calendar_service::update_task_calendar_events(db, &updates).await;
+    let calendar_failed = results.iter().filter(|result| result.is_err()).count();
+    Ok(DefaultRemindersReport {
+        updated: tasks.len(),
+        calendar_updated: results.len() - calendar_failed,
+        calendar_failed,
+    })
+}
+
+// Kept on one line so it reads as a list item in reports; blank is no note
+fn session_note(note: Option<String>) -> Result<Option<String>, String> {
+    let Some(note) = note.map(|note| note.split_whitespace().collect::<Vec<_>>().join(" ")).filter(|note| !note.is_empty()) else {
+        return Ok(None);
+    };
+    if note.chars().count() > MAX_SESSION_NOTE_CHARS {
+        return Err(format!("Session notes are limited to {} characters", MAX_SESSION_NOTE_CHARS));
+    }
+    Ok(Some(note))
+}
+
+// Journal a reminder update when the task has an event to update
+fn journal_event_sync(conn: &rusqlite::Connection, task_id: TaskId, event_id: &Option<String>, task: &Task) -> Result<Option<i64>, String> {
+    match (event_id, task.deadline) {
+        (Some(_), Some(_)) => calendar_journal_service::record(conn, task_id, JournalOperation::Sync, None).map(Some),
+        _ => Ok(None),
+    }
 }
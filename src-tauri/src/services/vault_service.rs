@@ -0,0 +1,433 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Mutex};
+use chrono::{DateTime, Duration, Local, NaiveDate, Utc};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Deserialize;
+use tauri::{AppHandle, Listener, Manager};
+use uuid::Uuid;
+use crate::db::{self, Database};
+use crate::helpers::format::Formatter;
+use crate::services::{event_service, metrics_service, recovery_service, task_service};
+use crate::structs::dto::CompleteTaskData;
+use crate::structs::settings::VaultLayout;
+use crate::structs::task_struct::{Status, Task, TaskId};
+use tracing::{info, warn, error};
+
+const NOTE_EXTENSION: &str = "md";
+// Front-matter key that marks a note as ours; notes without it are never touched
+const MARKER_KEY: &str = "myhandler";
+// Completion log notes live in a subfolder the mirror neither watches nor cleans up
+const LOG_FOLDER: &str = "Completed";
+
+// Watcher for the vault folder while the mirror is on; dropping it ends the watch thread
+#[derive(Default)]
+pub struct VaultState(Mutex<Option<RecommendedWatcher>>);
+
+// Task events and delete events both carry the task ID
+#[derive(Deserialize)]
+struct ChangedTask {
+    id: Uuid,
+}
+
+struct VaultConfig {
+    folder: PathBuf,
+    layout: VaultLayout,
+    formatter: Formatter,
+}
+
+// Mirror every task change into the vault folder and start watching it, if configured
+pub fn init_vault(app: &AppHandle) {
+    for event in event_service::TASK_EVENTS {
+        let handle = app.clone();
+        app.listen_any(event, move |event| {
+            let Ok(changed) = serde_json::from_str::<ChangedTask>(event.payload()) else {
+                return;
+            };
+            // Off the emitting thread: rendering queries the database
+            let handle = handle.clone();
+            tauri::async_runtime::spawn_blocking(move || {
+                if let Err(e) = mirror_task(&handle, changed.id) {
+                    error!("Failed to update vault: {}", e);
+                }
+            });
+        });
+    }
+    
+    if let Err(e) = apply_settings(app) {
+        error!("{}", e);
+    }
+}
+
+// Rewrite the vault and restart the watcher to match settings
+pub fn apply_settings(app: &AppHandle) -> Result<(), String> {
+    stop(app);
+    
+    let Some(config) = load_config(app)? else {
+        return Ok(());
+    };
+    fs::create_dir_all(&config.folder)
+        .map_err(|e| format!("Failed to create vault folder {:?}: {}", config.folder, e))?;
+    mirror_all(app, &config)?;
+    
+    // Started after the full rewrite so our own writes aren't read back
+    let watcher = start_watcher(app, &config.folder)?;
+    let state = app.state::<VaultState>();
+    *state.0.lock().map_err(|e| e.to_string())? = Some(watcher);
+    
+    info!("Mirroring tasks to vault {:?}", config.folder);
+    Ok(())
+}
+
+// Changes that bypass task events (imports) need a full rewrite
+pub fn refresh_vault(app: &AppHandle) {
+    let result = load_config(app).and_then(|config| match config {
+        Some(config) => mirror_all(app, &config),
+        None => Ok(()),
+    });
+    if let Err(e) = result {
+        error!("Failed to refresh vault: {}", e);
+    }
+}
+
+// Log-to-daily-note completion effect: one line per completed task in that local day's log note
+pub fn log_completion(app: &AppHandle, task: &Task) -> Result<(), String> {
+    let Some(config) = load_config(app)? else {
+        warn!("Not logging completion of task {}: no vault folder is set", task.id);
+        return Ok(());
+    };
+    
+    let completed_at = task.completed_at.unwrap_or_else(Utc::now).with_timezone(&Local);
+    let day = completed_at.date_naive();
+    let folder = config.folder.join(LOG_FOLDER);
+    fs::create_dir_all(&folder)
+        .map_err(|e| format!("Failed to create log folder {:?}: {}", folder, e))?;
+    
+    let path = day_note_path(&folder, day);
+    let mut content = match read_mirror_note(&path) {
+        Some(content) => content,
+        // write_note would refuse anyway; say why here
+        None if path.exists() => {
+            warn!("Not logging completion to {:?}: it was not written by the vault mirror", path);
+            return Ok(());
+        }
+        None => render_log_note(day, config.formatter),
+    };
+    if !content.ends_with('\n') {
+        content.push('\n');
+    }
+    content.push_str(&format!("- {} {}\n", config.formatter.time(completed_at.time()), task.title.replace(['\r', '\n'], " ")));
+    
+    write_note(&path, &content)
+}
+
+fn stop(app: &AppHandle) {
+    let Some(state) = app.try_state::<VaultState>() else {
+        return;
+    };
+    let Ok(mut watcher) = state.0.lock() else {
+        return;
+    };
+    if watcher.take().is_some() {
+        info!("Vault mirror stopped");
+    }
+}
+
+// None when the mirror is off, or on the recovery database (an empty mirror would delete every note)
+fn load_config(app: &AppHandle) -> Result<Option<VaultConfig>, String> {
+    if recovery_service::is_active(app) {
+        return Ok(None);
+    }
+    
+    let db = app.try_state::<Database>()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    let settings = db.settings()
+        .map_err(|e| format!("Failed to fetch settings: {}", e))?;
+    
+    Ok(settings.vault_path.map(|path| VaultConfig {
+        folder: PathBuf::from(path),
+        layout: settings.vault_layout,
+        formatter: Formatter::new(settings.locale),
+    }))
+}
+
+fn mirror_task(app: &AppHandle, task_id: Uuid) -> Result<(), String> {
+    let Some(config) = load_config(app)? else {
+        return Ok(());
+    };
+    
+    let db = app.state::<Database>();
+    let task = {
+        let conn = db.get_connection();
+        match db::get_task_by_id(&conn, task_id.into()) {
+            Ok(task) => Some(task),
+            Err(rusqlite::Error::QueryReturnedNoRows) => None,
+            Err(e) => return Err(format!("Failed to fetch task {}: {}", task_id, e)),
+        }
+    }; // DB lock released here
+    
+    match (config.layout, task) {
+        (VaultLayout::Task, Some(task)) => write_note(&task_note_path(&config.folder, task.id), &render_task_note(&task, config.formatter)),
+        (VaultLayout::Task, None) => remove_note(&task_note_path(&config.folder, task_id)),
+        (VaultLayout::Day, Some(task)) => {
+            let day = task.created_at.date_naive();
+            let tasks = tasks_for_day(&db, day)?;
+            write_note(&day_note_path(&config.folder, day), &render_day_note(day, &tasks, config.formatter))
+        }
+        // A deleted task no longer says which day it was on
+        (VaultLayout::Day, None) => mirror_all(app, &config),
+    }
+}
+
+// Write every note and remove ours that no longer match a task or day
+fn mirror_all(app: &AppHandle, config: &VaultConfig) -> Result<(), String> {
+    let db = app.state::<Database>();
+    let tasks = {
+        let conn = db.get_connection();
+        db::get_all_tasks(&conn)
+            .map_err(|e| format!("Failed to fetch tasks: {}", e))?
+    }; // DB lock released here
+    
+    let mut notes = BTreeMap::new();
+    match config.layout {
+        VaultLayout::Task => {
+            for task in &tasks {
+                notes.insert(task_note_path(&config.folder, task.id), render_task_note(task, config.formatter));
+            }
+        }
+        VaultLayout::Day => {
+            let mut days: BTreeMap<NaiveDate, Vec<Task>> = BTreeMap::new();
+            for task in tasks {
+                days.entry(task.created_at.date_naive()).or_default().push(task);
+            }
+            for (day, tasks) in &days {
+                notes.insert(day_note_path(&config.folder, *day), render_day_note(*day, tasks, config.formatter));
+            }
+        }
+    }
+    
+    for (path, content) in &notes {
+        write_note(path, content)?;
+    }
+    
+    let keep: HashSet<&PathBuf> = notes.keys().collect();
+    let entries = fs::read_dir(&config.folder)
+        .map_err(|e| format!("Failed to read vault folder {:?}: {}", config.folder, e))?;
+    for path in entries.filter_map(|entry| entry.ok().map(|e| e.path())) {
+        if is_note(&path) && !keep.contains(&path) {
+            remove_note(&path)?;
+        }
+    }
+    
+    Ok(())
+}
+
+// Vault day files cover UTC days
+fn tasks_for_day(db: &Database, day: NaiveDate) -> Result<Vec<Task>, String> {
+    let start = day.and_hms_opt(0, 0, 0).ok_or("Invalid date")?.and_utc();
+    let end = start + Duration::days(1);
+    
+    let sql = include_str!("../db/sql/get_tasks_created_between.sql");
+    let conn = db.get_connection();
+    db::query_tasks_by_date_range(&conn, start, end, sql)
+        .map_err(|e| format!("Failed to query tasks: {}", e))
+}
+
+fn day_note_path(folder: &Path, day: NaiveDate) -> PathBuf {
+    folder.join(format!("{}.{}", day.format("%Y-%m-%d"), NOTE_EXTENSION))
+}
+
+fn task_note_path(folder: &Path, task_id: Uuid) -> PathBuf {
+    folder.join(format!("{}.{}", task_id, NOTE_EXTENSION))
+}
+
+fn render_day_note(day: NaiveDate, tasks: &[Task], formatter: Formatter) -> String {
+    let mut out = String::from("---\n");
+    out.push_str(&format!("date: {}\n", day.format("%Y-%m-%d")));
+    out.push_str(&format!("{}: day\n", MARKER_KEY));
+    out.push_str("---\n\n");
+    out.push_str(&format!("# {}\n\n", formatter.long_date(day)));
+    
+    for task in tasks {
+        out.push_str(&checkbox_line(task, formatter));
+        out.push('\n');
+    }
+    
+    out
+}
+
+fn render_log_note(day: NaiveDate, formatter: Formatter) -> String {
+    let mut out = String::from("---\n");
+    out.push_str(&format!("date: {}\n", day.format("%Y-%m-%d")));
+    out.push_str(&format!("{}: log\n", MARKER_KEY));
+    out.push_str("---\n\n");
+    out.push_str(&format!("# Completed {}\n\n", formatter.long_date(day)));
+    out
+}
+
+fn render_task_note(task: &Task, formatter: Formatter) -> String {
+    let mut out = String::from("---\n");
+    out.push_str(&format!("id: {}\n", task.id));
+    out.push_str(&format!("title: {}\n", yaml_string(&task.title)));
+    out.push_str(&format!("aliases: [{}]\n", yaml_string(&task.title)));
+    out.push_str(&format!("status: {}\n", String::from(task.status.clone())));
+    out.push_str(&format!("created: {}\n", task.created_at.to_rfc3339()));
+    push_time(&mut out, "deadline", task.deadline);
+    push_time(&mut out, "completed", task.completed_at);
+    out.push_str(&format!("{}: task\n", MARKER_KEY));
+    out.push_str("---\n\n");
+    out.push_str(&checkbox_line(task, formatter));
+    out.push('\n');
+    
+    if let Some(notes) = task.notes.as_deref().map(str::trim).filter(|n| !n.is_empty()) {
+        out.push('\n');
+        out.push_str(notes);
+        out.push('\n');
+    }
+    
+    out
+}
+
+// The Obsidian block ID (^id) ties the line back to its task when the checkbox is toggled
+fn checkbox_line(task: &Task, formatter: Formatter) -> String {
+    let mark = if task.status == Status::Completed { 'x' } else { ' ' };
+    let due = task.deadline
+        .map(|d| format!(" (due {})", formatter.date_time(d.with_timezone(&Local).naive_local())))
+        .unwrap_or_default();
+    format!("- [{}] {}{} ^{}", mark, task.title.replace(['\r', '\n'], " "), due, task.id)
+}
+
+fn push_time(out: &mut String, key: &str, time: Option<DateTime<Utc>>) {
+    if let Some(time) = time {
+        out.push_str(&format!("{}: {}\n", key, time.to_rfc3339()));
+    }
+}
+
+// A JSON string is also a valid double-quoted YAML scalar
+fn yaml_string(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+// (task ID, checked) for every checkbox line carrying a block ID
+fn parse_checkboxes(content: &str) -> Vec<(Uuid, bool)> {
+    content.lines()
+        .filter_map(|line| {
+            let rest = line.trim_start().strip_prefix("- [")?;
+            let checked = match rest.chars().next()? {
+                ' ' => false,
+                'x' | 'X' => true,
+                _ => return None,
+            };
+            rest[1..].strip_prefix(']')?;
+            let (_, id) = line.trim_end().rsplit_once(" ^")?;
+            Uuid::parse_str(id).ok().map(|id| (id, checked))
+        })
+        .collect()
+}
+
+fn start_watcher(app: &AppHandle, folder: &Path) -> Result<RecommendedWatcher, String> {
+    let (sender, receiver) = mpsc::channel::<notify::Result<Event>>();
+    let mut watcher = notify::recommended_watcher(sender)
+        .map_err(|e| format!("Failed to create vault watcher: {}", e))?;
+    watcher.watch(folder, RecursiveMode::NonRecursive)
+        .map_err(|e| format!("Failed to watch vault folder {:?}: {}", folder, e))?;
+    
+    let handle = app.clone();
+    std::thread::spawn(move || {
+        // Ends when the watcher is dropped and closes the channel
+        for event in receiver {
+            match event {
+                Ok(event) if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) => {
+                    for path in event.paths.iter().filter(|path| is_note(path)) {
+                        import_checkbox_changes(&handle, path);
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => warn!("Vault watcher error: {}", e),
+            }
+        }
+    });
+    
+    Ok(watcher)
+}
+
+// Complete or reopen tasks whose checkbox was toggled in the editor; other edits are ignored
+fn import_checkbox_changes(app: &AppHandle, path: &Path) {
+    let Some(content) = read_mirror_note(path) else {
+        return;
+    };
+    let db = app.state::<Database>();
+    
+    for (task_id, checked) in parse_checkboxes(&content) {
+        let id = TaskId::from(task_id);
+        let task = {
+            let conn = db.get_connection();
+            db::get_task_by_id(&conn, id)
+        }; // DB lock released here
+        
+        // Deleted since the note was written
+        let Ok(task) = task else {
+            continue;
+        };
+        let completed = task.status == Status::Completed;
+        
+        let result = if checked && !completed {
+            info!("Completing task {} from vault note {:?}", id, path);
+            tauri::async_runtime::block_on(task_service::complete_task(CompleteTaskData { id, note: None }, &db, app)).map(|_| ())
+        } else if !checked && completed {
+            info!("Reopening task {} from vault note {:?}", id, path);
+            task_service::reopen_task(id, &db, app).map(|_| ())
+        } else {
+            continue;
+        };
+        metrics_service::record_feature("vault-checkbox");
+        
+        if let Err(e) = result {
+            warn!("Failed to apply vault change for task {}: {}", task_id, e);
+        }
+    }
+}
+
+fn is_note(path: &Path) -> bool {
+    path.extension().and_then(|ext| ext.to_str()) == Some(NOTE_EXTENSION)
+}
+
+// Content of a note we wrote, or None for the user's own notes
+fn read_mirror_note(path: &Path) -> Option<String> {
+    let content = fs::read_to_string(path).ok()?;
+    let front_matter = content.strip_prefix("---\n")?.split("\n---").next()?;
+    front_matter.lines()
+        .any(|line| line.split_once(':').map(|(key, _)| key.trim()) == Some(MARKER_KEY))
+        .then_some(content)
+}
+
+// Skips unchanged notes (no watcher churn) and never overwrites the user's own notes.
+// Written to a temp file and renamed so the watcher never reads a half-written note.
+fn write_note(path: &Path, content: &str) -> Result<(), String> {
+    if path.exists() {
+        match read_mirror_note(path) {
+            Some(existing) if existing == content => return Ok(()),
+            Some(_) => {}
+            None => {
+                warn!("Not overwriting {:?}: it was not written by the vault mirror", path);
+                return Ok(());
+            }
+        }
+    }
+    
+    let temp = path.with_extension("tmp");
+    fs::write(&temp, content)
+        .map_err(|e| format!("Failed to write vault note {:?}: {}", temp, e))?;
+    fs::rename(&temp, path)
+        .map_err(|e| format!("Failed to write vault note {:?}: {}", path, e))
+}
+
+// Only removes notes we wrote; a missing note is fine
+fn remove_note(path: &Path) -> Result<(), String> {
+    if read_mirror_note(path).is_none() {
+        return Ok(());
+    }
+    fs::remove_file(path)
+        .map_err(|e| format!("Failed to remove vault note {:?}: {}", path, e))
+}
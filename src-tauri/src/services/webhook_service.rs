@@ -0,0 +1,234 @@
+use chrono::{Duration, Utc};
+use serde_json::{json, Value};
+use tauri::{AppHandle, Listener, Manager};
+use crate::db::{self, Database};
+use crate::services::{event_service, recovery_service};
+use crate::structs::webhook::{
+    DeliveryStatus, Webhook, WebhookData, WebhookDelivery, WebhookDeliveryQuery, WebhookEvent, WebhookEvents, WebhookIdData,
+};
+use crate::thirdparty::webhook;
+use tracing::{info, warn, error};
+
+const SECRET_LENGTH: usize = 32;
+// After this many attempts a delivery is marked failed and left in the log
+const MAX_ATTEMPTS: i32 = 8;
+// Wait before retry n is BASE_RETRY_SECS * 2^(n-1): 30s, 1m, 2m ..., so the last comes about an hour after the first
+const BASE_RETRY_SECS: i64 = 30;
+const DEFAULT_LOG_LIMIT: i64 = 50;
+const MAX_LOG_LIMIT: i64 = 500;
+// Finished deliveries are kept this long for the log
+const LOG_RETENTION_DAYS: i64 = 30;
+
+// One delivery pass at a time, so a pending row is never sent twice
+static DELIVERY_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+// Queue a delivery to every subscribed webhook on task created/completed/deleted, then send it
+pub fn init_webhooks(app: &AppHandle) {
+    let lifecycle = [
+        (event_service::TASK_CREATED, WebhookEvent::Created),
+        (event_service::TASK_STATUS_CHANGED, WebhookEvent::Completed),
+        (event_service::TASK_DELETED, WebhookEvent::Deleted),
+    ];
+    
+    for (name, event) in lifecycle {
+        let handle = app.clone();
+        app.listen_any(name, move |emitted| {
+            // The recovery database's tasks must not leave the device
+            if recovery_service::is_active(&handle) {
+                return;
+            }
+            let Ok(data) = serde_json::from_str::<Value>(emitted.payload()) else {
+                return;
+            };
+            // Any status change fires the same event; only completions are sent
+            if event == WebhookEvent::Completed && data.get("status").and_then(Value::as_str) != Some("completed") {
+                return;
+            }
+            
+            // Off the emitting thread: queueing queries the database
+            let handle = handle.clone();
+            tauri::async_runtime::spawn(async move {
+                match enqueue(&handle, event, data) {
+                    Ok(0) => {}
+                    Ok(_) => {
+                        if let Err(e) = deliver_due(&handle).await {
+                            error!("Failed to deliver webhooks: {}", e);
+                        }
+                    }
+                    Err(e) => error!("Failed to queue webhook deliveries: {}", e),
+                }
+            });
+        });
+    }
+}
+
+// Store a delivery for each enabled webhook subscribed to the event; returns how many
+fn enqueue(app: &AppHandle, event: WebhookEvent, data: Value) -> Result<usize, String> {
+    let db = app.try_state::<Database>()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    let conn = db.get_connection();
+    
+    let webhooks: Vec<Webhook> = db::get_webhooks(&conn)
+        .map_err(|e| format!("Failed to fetch webhooks: {}", e))?
+        .into_iter()
+        .filter(|webhook| webhook.enabled && webhook.events.contains(event))
+        .collect();
+    if webhooks.is_empty() {
+        return Ok(0);
+    }
+    
+    let payload = json!({
+        "event": event.name(),
+        "occurredAt": Utc::now(),
+        "data": data,
+    }).to_string();
+    
+    let tx = conn.unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    for webhook in &webhooks {
+        db::insert_webhook_delivery(&tx, webhook.id, event, &payload)
+            .map_err(|e| format!("Failed to queue delivery for webhook {}: {}", webhook.id, e))?;
+    }
+    tx.commit()
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+    
+    Ok(webhooks.len())
+}
+
+// Send every pending delivery whose next attempt is due; failures are rescheduled with backoff
+pub async fn deliver_due(app: &AppHandle) -> Result<(), String> {
+    let _running = DELIVERY_LOCK.lock().await;
+    
+    let (due, webhooks) = {
+        let db = app.try_state::<Database>()
+            .ok_or_else(|| "Database not initialized".to_string())?;
+        let conn = db.get_connection();
+        let due = db::get_due_webhook_deliveries(&conn, Utc::now())
+            .map_err(|e| format!("Failed to fetch due webhook deliveries: {}", e))?;
+        let webhooks = db::get_webhooks(&conn)
+            .map_err(|e| format!("Failed to fetch webhooks: {}", e))?;
+        (due, webhooks)
+    }; // DB lock released here
+    
+    for delivery in due {
+        let Some(target) = webhooks.iter().find(|webhook| webhook.id == delivery.webhook_id) else {
+            continue;
+        };
+        let result = webhook::post_signed(
+            &target.url,
+            &target.secret,
+            delivery.event.name(),
+            delivery.id,
+            &delivery.payload,
+        ).await;
+        record_attempt(app, &delivery, result)?;
+    }
+    Ok(())
+}
+
+fn record_attempt(
+    app: &AppHandle,
+    delivery: &WebhookDelivery,
+    result: Result<u16, webhook::DeliveryError>,
+) -> Result<(), String> {
+    let db = app.try_state::<Database>()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    let conn = db.get_connection();
+    let attempts = delivery.attempts + 1;
+    
+    let outcome = match result {
+        Ok(status) => db::record_webhook_attempt(&conn, delivery.id, DeliveryStatus::Delivered, Some(status), None, Utc::now()),
+        Err(e) if attempts >= MAX_ATTEMPTS => {
+            error!("Giving up on webhook delivery {} after {} attempts: {}", delivery.id, attempts, e.message);
+            db::record_webhook_attempt(&conn, delivery.id, DeliveryStatus::Failed, e.status, Some(&e.message), Utc::now())
+        }
+        Err(e) => {
+            let next_attempt_at = Utc::now() + Duration::seconds(BASE_RETRY_SECS << (attempts - 1));
+            warn!("Webhook delivery {} failed, retrying at {}: {}", delivery.id, next_attempt_at, e.message);
+            db::record_webhook_attempt(&conn, delivery.id, DeliveryStatus::Pending, e.status, Some(&e.message), next_attempt_at)
+        }
+    };
+    outcome.map_err(|e| format!("Failed to record webhook delivery {}: {}", delivery.id, e))
+}
+
+pub fn list_webhooks(db: &Database) -> Result<Vec<Webhook>, String> {
+    let conn = db.get_read_connection();
+    db::get_webhooks(&conn)
+        .map_err(|e| format!("Failed to fetch webhooks: {}", e))
+}
+
+pub fn create_webhook(db: &Database, payload: WebhookData) -> Result<Webhook, String> {
+    let url = payload.url.trim();
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err("Webhook URL must start with http:// or https://".to_string());
+    }
+    
+    let events = if payload.events.is_empty() {
+        WebhookEvent::ALL.to_vec()
+    } else {
+        payload.events.iter()
+            .map(|name| name.parse())
+            .collect::<Result<Vec<WebhookEvent>, String>>()?
+    };
+    
+    let secret = match payload.secret.as_deref().map(str::trim) {
+        Some("") => return Err("Webhook secret must not be empty".to_string()),
+        Some(secret) => secret.to_string(),
+        None => generate_secret(),
+    };
+    
+    let conn = db.get_connection();
+    let id = db::insert_webhook(&conn, url, &secret, &WebhookEvents::new(events))
+        .map_err(|e| format!("Failed to create webhook: {}", e))?;
+    
+    info!("Created webhook {}", id);
+    db::get_webhook_by_id(&conn, id)
+        .map_err(|e| format!("Failed to fetch webhook: {}", e))
+}
+
+pub fn delete_webhook(db: &Database, payload: WebhookIdData) -> Result<(), String> {
+    let conn = db.get_connection();
+    let tx = conn.unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    
+    let deleted = db::delete_webhook(&tx, payload.id)
+        .map_err(|e| format!("Failed to delete webhook: {}", e))?;
+    if deleted == 0 {
+        return Err(format!("Webhook {} not found", payload.id));
+    }
+    tx.commit()
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+    
+    info!("Deleted webhook {}", payload.id);
+    Ok(())
+}
+
+// Delivery log, newest first
+pub fn get_webhook_deliveries(db: &Database, payload: WebhookDeliveryQuery) -> Result<Vec<WebhookDelivery>, String> {
+    let limit = payload.limit.unwrap_or(DEFAULT_LOG_LIMIT).clamp(1, MAX_LOG_LIMIT);
+    
+    let conn = db.get_read_connection();
+    db::get_webhook_deliveries(&conn, payload.webhook_id, limit)
+        .map_err(|e| format!("Failed to fetch webhook deliveries: {}", e))
+}
+
+// Maintenance: drop old delivered and failed rows
+pub fn prune_deliveries(db: &Database) -> Result<(), String> {
+    let conn = db.get_connection();
+    let pruned = db::prune_webhook_deliveries(&conn, Utc::now() - Duration::days(LOG_RETENTION_DAYS))
+        .map_err(|e| format!("Failed to prune webhook deliveries: {}", e))?;
+    if pruned > 0 {
+        info!("Pruned {} webhook deliveries", pruned);
+    }
+    Ok(())
+}
+
+fn generate_secret() -> String {
+    use rand::Rng;
+    
+    rand::thread_rng()
+        .sample_iter(&rand::distributions::Alphanumeric)
+        .take(SECRET_LENGTH)
+        .map(char::from)
+        .collect()
+}
@@ -0,0 +1,76 @@
+use chrono::Local;
+use tauri::{AppHandle, Manager};
+use crate::db::{self, Database};
+use crate::deep_link;
+use crate::helpers::format::Formatter;
+use crate::services::{calendar_service, settings_service};
+use crate::structs::work_session::{WorkSession, WorkSessionReport};
+use tracing::info;
+
+// A quick start and pause isn't worth a calendar entry
+const MIN_SESSION_MINUTES: i64 = 5;
+
+// End-of-day job: put finished work sessions on Google Calendar as past events ("Worked on X").
+// Sessions that end while posting is off, or the calendar isn't connected, are skipped rather than kept for later.
+pub async fn post_work_sessions(app: &AppHandle) -> Result<WorkSessionReport, String> {
+    let db = app.try_state::<Database>()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    let enabled = db.settings()
+        .map_err(|e| format!("Failed to fetch settings: {}", e))?
+        .calendar_work_sessions;
+    let formatter = settings_service::formatter(&db)?;
+    let sessions = {
+        let conn = db.get_read_connection();
+        db::get_unposted_work_sessions(&conn)
+            .map_err(|e| format!("Failed to fetch work sessions: {}", e))?
+    }; // DB lock released here
+    
+    let mut report = WorkSessionReport::default();
+    if sessions.is_empty() {
+        return Ok(report);
+    }
+    let posting = enabled && calendar_service::get_credentials(&db)?.is_some();
+    
+    // A failed post leaves that session and the ones after it for the next run
+    for session in sessions {
+        let minutes = (session.ended_at - session.started_at).num_minutes();
+        let event_id = if posting && minutes >= MIN_SESSION_MINUTES {
+            Some(calendar_service::create_work_session_event(
+                &db,
+                session.task_id,
+                &format!("Worked on {}", session.title),
+                &description(&session, formatter),
+                session.started_at,
+                session.ended_at,
+            ).await?)
+        } else {
+            None
+        };
+        
+        let conn = db.get_connection();
+        db::mark_work_session_posted(&conn, session.id, chrono::Utc::now(), event_id.as_deref())
+            .map_err(|e| format!("Failed to record work session: {}", e))?;
+        if event_id.is_some() {
+            report.posted += 1;
+        } else {
+            report.skipped += 1;
+        }
+    }
+    
+    if report.posted > 0 {
+        info!("Posted {} work sessions to the calendar", report.posted);
+    }
+    Ok(report)
+}
+
+// "14:00–15:10" on this machine's clock, then a link back into the app
+fn description(session: &WorkSession, formatter: Formatter) -> String {
+    let start = session.started_at.with_timezone(&Local).time();
+    let end = session.ended_at.with_timezone(&Local).time();
+    format!(
+        "{}–{}\n\nOpen in My Handler: {}",
+        formatter.time(start),
+        formatter.time(end),
+        deep_link::task_url(&session.task_id.to_string()),
+    )
+}
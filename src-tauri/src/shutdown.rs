@@ -0,0 +1,84 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use crate::db::{self, Database};
+use crate::services::{calendar_service, lan_sync_service, metrics_service, recovery_service, scheduler_service};
+use tracing::{info, warn, error};
+
+// Written on a clean exit and removed at startup; missing means the last session crashed
+const CLEAN_SHUTDOWN_MARKER: &str = "clean-shutdown";
+// How long exit waits for in-flight background jobs
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(5);
+
+fn marker_path(app: &AppHandle) -> Option<PathBuf> {
+    db::get_db_path(app).ok()
+        .and_then(|path| path.parent().map(|dir| dir.join(CLEAN_SHUTDOWN_MARKER)))
+}
+
+// Call after init_db: consumes the marker and checks the database if it is missing
+pub fn check_previous_shutdown(app: &AppHandle) {
+    let Some(marker) = marker_path(app) else {
+        return;
+    };
+    
+    if marker.exists() {
+        let _ = fs::remove_file(&marker);
+        return;
+    }
+    
+    warn!("Previous session did not shut down cleanly, checking database");
+    let Some(db) = app.try_state::<Database>() else {
+        return;
+    };
+    let conn = db.get_connection();
+    match db::quick_check(&conn) {
+        Ok(result) if result == "ok" => info!("Database check passed"),
+        Ok(result) => error!("Database check reported problems: {}", result),
+        Err(e) => error!("Failed to check database: {}", e),
+    }
+}
+
+// Stop background jobs, send queued calendar updates, flush the WAL and leave the clean-shutdown marker
+pub fn shutdown(app: &AppHandle) {
+    info!("Shutting down");
+    
+    if !scheduler_service::stop_scheduler(DRAIN_TIMEOUT) {
+        warn!("Background jobs still running after {:?}, exiting anyway", DRAIN_TIMEOUT);
+    }
+    
+    // Tells paired devices on the network this one is gone
+    lan_sync_service::stop(app);
+    
+    // Edits still in their quiet period would otherwise never reach the calendar
+    let flushed = tauri::async_runtime::block_on(
+        tokio::time::timeout(DRAIN_TIMEOUT, calendar_service::flush_queued_updates(app))
+    );
+    if flushed.is_err() {
+        warn!("Queued calendar updates still pending after {:?}, exiting anyway", DRAIN_TIMEOUT);
+    }
+    
+    // The real database never opened; the next start must try (and check) it again
+    if recovery_service::is_active(app) {
+        return;
+    }
+    
+    if let Some(db) = app.try_state::<Database>() {
+        if let Err(e) = metrics_service::flush(&db) {
+            warn!("{}", e);
+        }
+        
+        let conn = db.get_connection();
+        if let Err(e) = db::checkpoint(&conn) {
+            // Not clean: leave the marker out so the next start runs a check
+            error!("Failed to checkpoint database: {}", e);
+            return;
+        }
+    }
+    
+    if let Some(marker) = marker_path(app) {
+        if let Err(e) = fs::write(&marker, chrono::Utc::now().to_rfc3339()) {
+            error!("Failed to write clean shutdown marker: {}", e);
+        }
+    }
+}
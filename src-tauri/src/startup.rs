@@ -0,0 +1,98 @@
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+use crate::db::Database;
+use crate::services::{calendar_journal_service, calendar_service, lan_sync_service, recovery_service, scheduler_service, vault_service};
+use crate::shutdown;
+use crate::structs::startup::{StartupPhase, StartupProgress};
+use tracing::{info, warn, error};
+
+pub const STARTUP_PHASE_EVENT: &str = "startup-phase";
+
+// Latest phase reached, for windows that load after its event was sent
+#[derive(Default)]
+pub struct StartupState(Mutex<Option<StartupProgress>>);
+
+// Run what the first window doesn't need (database check, calendar token,
+// reconciliation, job scheduling) in the background, reporting each phase
+pub fn run_deferred(app: &AppHandle) {
+    let handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        for phase in StartupPhase::ALL {
+            enter_phase(&handle, phase);
+            run_phase(&handle, phase).await;
+        }
+        info!("Startup finished");
+    });
+}
+
+pub fn get_startup_phase(app: &AppHandle) -> Result<Option<StartupProgress>, String> {
+    let state = app.state::<StartupState>();
+    let progress = state.0.lock().map_err(|e| e.to_string())?;
+    Ok(progress.clone())
+}
+
+fn enter_phase(app: &AppHandle, phase: StartupPhase) {
+    let progress = StartupProgress {
+        phase,
+        step: StartupPhase::ALL.iter().position(|p| *p == phase).unwrap_or(0) + 1,
+        total: StartupPhase::ALL.len(),
+    };
+    
+    if let Ok(mut current) = app.state::<StartupState>().0.lock() {
+        *current = Some(progress.clone());
+    }
+    if let Err(e) = app.emit(STARTUP_PHASE_EVENT, progress) {
+        error!("Failed to emit '{}' event: {}", STARTUP_PHASE_EVENT, e);
+    }
+}
+
+async fn run_phase(app: &AppHandle, phase: StartupPhase) {
+    match phase {
+        StartupPhase::CheckingDatabase => {
+            // Nothing to check on the fallback database
+            if recovery_service::is_active(app) {
+                return;
+            }
+            let handle = app.clone();
+            let checked = tauri::async_runtime::spawn_blocking(move || {
+                shutdown::check_previous_shutdown(&handle);
+            }).await;
+            if let Err(e) = checked {
+                error!("Database check did not finish: {}", e);
+            }
+        }
+        StartupPhase::Calendar => check_calendar_credentials(app).await,
+        StartupPhase::Reconciling => {
+            // Finish calendar calls that a crash or failed request left behind
+            if let Some(db) = app.try_state::<Database>() {
+                calendar_journal_service::replay(&db).await;
+            }
+            vault_service::init_vault(app);
+            if let Err(e) = lan_sync_service::apply_settings(app) {
+                error!("{}", e);
+            }
+        }
+        // Registers jobs, including the periodic snapshot that backs up the data
+        StartupPhase::BackgroundJobs => scheduler_service::start_scheduler(app.clone()),
+        StartupPhase::Ready => {}
+    }
+}
+
+// Refresh an expired token now rather than on the first calendar call
+async fn check_calendar_credentials(app: &AppHandle) {
+    let Some(db) = app.try_state::<Database>() else {
+        return;
+    };
+    match calendar_service::get_credentials(&db) {
+        Ok(Some(_)) => {}
+        Ok(None) => return,
+        Err(e) => {
+            warn!("{}", e);
+            return;
+        }
+    }
+    
+    if let Err(e) = calendar_service::get_valid_access_token(&db).await {
+        warn!("Calendar credentials could not be refreshed: {}", e);
+    }
+}
@@ -0,0 +1,21 @@
+use serde::Serialize;
+
+// Backend capabilities, so the UI can feature-detect after partial upgrades
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppInfo {
+    pub version: String,
+    pub schema_version: i64,
+    pub features: AppFeatures,
+    pub commands: Vec<String>,
+    // Version of the old command names still answered (legacy_service)
+    pub legacy_api_version: u32,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppFeatures {
+    pub calendar_providers: Vec<String>,
+    pub full_text_search: bool,
+    pub apple_reminders_import: bool,
+}
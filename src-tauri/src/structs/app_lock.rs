@@ -0,0 +1,36 @@
+use chrono::{DateTime, Utc};
+use db_macros::Queryable;
+use serde::{Deserialize, Serialize};
+
+// The stored lock; never sent to the frontend
+#[derive(Debug, Clone, Queryable)]
+pub struct AppLock {
+    pub pin_hash: String,
+    pub key_salt: String,
+    pub idle_minutes: u32,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppLockData {
+    pub pin: String,
+    // Required to change an existing lock
+    pub current_pin: Option<String>,
+    // 0 never locks on its own
+    pub idle_minutes: u32,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnlockData {
+    pub pin: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockStatus {
+    pub enabled: bool,
+    pub locked: bool,
+    pub idle_minutes: Option<u32>,
+}
@@ -7,4 +7,15 @@ pub struct CalendarCredentials {
     pub access_token: String,
     pub refresh_token: String,
     pub token_expiry: DateTime<Utc>,
+    // Google refused the refresh token; nothing is sent until the account is connected again
+    #[serde(default)]
+    pub reauth_needed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisconnectCalendarData {
+    // Forget which event belongs to which task; connecting again then makes new events for them
+    #[serde(default)]
+    pub clear_event_links: bool,
 }
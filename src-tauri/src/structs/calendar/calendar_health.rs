@@ -0,0 +1,28 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CalendarHealthStatus {
+    Healthy,
+    // Connected, but the next change may fail; problems says why
+    Degraded,
+    Disconnected,
+    // Couldn't be checked; nothing is known to be wrong
+    Offline,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarHealth {
+    pub status: CalendarHealthStatus,
+    pub email: Option<String>,
+    // Of the access token in use after the check
+    pub token_expiry: Option<DateTime<Utc>>,
+    // None when the refresh wasn't tried
+    pub refresh_ok: Option<bool>,
+    pub granted_scopes: Vec<String>,
+    pub missing_scopes: Vec<String>,
+    pub problems: Vec<String>,
+    pub checked_at: DateTime<Utc>,
+}
@@ -0,0 +1,46 @@
+use chrono::{DateTime, Utc};
+use db_macros::Queryable;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+pub struct CalendarUsageQuery {
+    // Window for call and error counts
+    pub days: u32,
+}
+
+#[derive(Debug, Queryable)]
+pub struct CalendarLinkCounts {
+    pub linked_tasks: i64,
+    pub calendar_tasks: i64,
+    pub pending_operations: i64,
+}
+
+#[derive(Debug, Serialize, Queryable)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarErrorCount {
+    // e.g. "auth", "rate-limit", "server", "network"
+    pub kind: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Serialize, Queryable)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarAccountUsage {
+    pub account: String,
+    pub last_success: Option<DateTime<Utc>>,
+    pub calls: i64,
+    pub errors: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarUsageStats {
+    // Tasks with a Google Calendar event
+    pub linked_tasks: i64,
+    // Tasks with calendar sync turned on; more than linked_tasks means events are missing
+    pub calendar_tasks: i64,
+    // Journaled calendar changes not yet applied
+    pub pending_operations: i64,
+    pub errors_by_kind: Vec<CalendarErrorCount>,
+    pub accounts: Vec<CalendarAccountUsage>,
+}
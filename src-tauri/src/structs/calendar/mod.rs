@@ -1,3 +1,7 @@
 pub mod calendar_credentials;
+pub mod calendar_health;
+pub mod calendar_usage;
 
-pub use calendar_credentials::CalendarCredentials;
+pub use calendar_credentials::{CalendarCredentials, DisconnectCalendarData};
+pub use calendar_health::{CalendarHealth, CalendarHealthStatus};
+pub use calendar_usage::{CalendarAccountUsage, CalendarErrorCount, CalendarLinkCounts, CalendarUsageQuery, CalendarUsageStats};
@@ -1,4 +1,115 @@
+use std::str::FromStr;
+use chrono::{DateTime, Utc};
+use db_macros::Queryable;
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
 use serde::{Deserialize, Serialize};
+use crate::structs::task_struct::TaskId;
+
+// How much of a task its calendar event shows
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum CalendarPrivacy {
+    // Title, notes and a link back to the task
+    Full,
+    // Title and link, without the notes
+    NoNotes,
+    // A placeholder title and nothing else; the calendar only shows the time as taken
+    Busy,
+}
+
+impl CalendarPrivacy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CalendarPrivacy::Full => "full",
+            CalendarPrivacy::NoNotes => "no-notes",
+            CalendarPrivacy::Busy => "busy",
+        }
+    }
+}
+
+impl FromStr for CalendarPrivacy {
+    type Err = String;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "full" => Ok(CalendarPrivacy::Full),
+            "no-notes" => Ok(CalendarPrivacy::NoNotes),
+            "busy" => Ok(CalendarPrivacy::Busy),
+            _ => Err(format!("Invalid calendar privacy '{}': expected full, no-notes or busy", s)),
+        }
+    }
+}
+
+impl ToSql for CalendarPrivacy {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.as_str()))
+    }
+}
+
+impl FromSql for CalendarPrivacy {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        value.as_str().and_then(|s| s.parse().map_err(|_| FromSqlError::InvalidType))
+    }
+}
+
+// Which side of a task-to-event link came first
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum EventOrigin {
+    // Made by the app for the task, and kept in step with it
+    #[default]
+    Task,
+    // On the user's calendar already; the task was made from it by a calendar event rule
+    Calendar,
+}
+
+impl ToSql for EventOrigin {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        let s = match self {
+            EventOrigin::Task => "task",
+            EventOrigin::Calendar => "calendar",
+        };
+        Ok(ToSqlOutput::from(s))
+    }
+}
+
+impl FromSql for EventOrigin {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        value.as_str().and_then(|s| match s {
+            "task" => Ok(EventOrigin::Task),
+            "calendar" => Ok(EventOrigin::Calendar),
+            _ => Err(FromSqlError::InvalidType),
+        })
+    }
+}
+
+// A linked event as the calendar pull sees it
+#[derive(Debug, Clone, Queryable)]
+pub struct PulledEventLink {
+    pub google_event_id: String,
+    pub task_id: TaskId,
+    pub origin: EventOrigin,
+    pub event_start: Option<DateTime<Utc>>,
+}
+
+// What an event was last updated with; an update that would send the same is skipped
+#[derive(Debug, Clone, PartialEq, Queryable)]
+pub struct PushedEvent {
+    pub title: String,
+    pub deadline: DateTime<Utc>,
+    // Of the description and reminder frequency
+    pub content_hash: String,
+}
+
+// A task event to patch as one of a batch
+#[derive(Debug, Clone)]
+pub struct EventPatch {
+    pub event_id: String,
+    pub title: String,
+    pub notes: Option<String>,
+    pub deadline: DateTime<Utc>,
+    pub reminder_frequency: String,
+}
 
 #[derive(Serialize)]
 pub struct CalendarEvent {
@@ -34,3 +145,36 @@ pub struct ReminderOverride {
 pub struct EventResponse {
     pub id: String,
 }
+
+// One page of events from the user's calendar
+// An event looked up by ID; only whether it was cancelled matters
+#[derive(Deserialize)]
+pub struct FetchedEvent {
+    #[serde(default)]
+    pub status: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct EventList {
+    #[serde(default)]
+    pub items: Vec<ListedEvent>,
+    #[serde(rename = "nextPageToken")]
+    pub next_page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct ListedEvent {
+    pub id: String,
+    // Missing on events the user can only see as busy
+    pub summary: Option<String>,
+    pub status: Option<String>,
+    pub start: Option<ListedEventTime>,
+}
+
+// `date_time` for timed events, `date` for all-day ones
+#[derive(Deserialize)]
+pub struct ListedEventTime {
+    #[serde(rename = "dateTime")]
+    pub date_time: Option<String>,
+    pub date: Option<String>,
+}
@@ -0,0 +1,46 @@
+use db_macros::Queryable;
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+use crate::structs::task_struct::TaskId;
+
+// Side effect a task change owes the calendar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JournalOperation {
+    // Bring the task's event in line with the task: create, update or remove it
+    Sync,
+    // Remove a specific event, e.g. after its task was deleted
+    Delete,
+}
+
+impl JournalOperation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JournalOperation::Sync => "sync",
+            JournalOperation::Delete => "delete",
+        }
+    }
+}
+
+impl ToSql for JournalOperation {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.as_str()))
+    }
+}
+
+impl FromSql for JournalOperation {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        value.as_str().and_then(|s| match s {
+            "sync" => Ok(JournalOperation::Sync),
+            "delete" => Ok(JournalOperation::Delete),
+            _ => Err(FromSqlError::InvalidType),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Queryable)]
+pub struct JournalEntry {
+    pub id: i64,
+    pub task_id: TaskId,
+    pub operation: JournalOperation,
+    pub event_id: Option<String>,
+    pub attempts: i32,
+}
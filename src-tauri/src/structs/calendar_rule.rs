@@ -0,0 +1,60 @@
+use chrono::{DateTime, Utc};
+use db_macros::{Insertable, Queryable, Updatable};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::db::Insertable;
+
+// Events on the user's calendar whose title starts with `title_prefix` become tasks, due when the event starts
+#[derive(Debug, Clone, Serialize, Insertable, Queryable)]
+#[serde(rename_all = "camelCase")]
+#[table_name = "calendar_event_rules"]
+pub struct CalendarEventRule {
+    pub id: Uuid,
+    pub name: String,
+    pub title_prefix: String,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarEventRuleData {
+    pub name: String,
+    pub title_prefix: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarEventRuleUpdateData {
+    pub name: Option<String>,
+    pub title_prefix: Option<String>,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Deserialize)]
+pub struct CalendarEventRuleUpdate {
+    pub id: Uuid,
+    pub data: CalendarEventRuleUpdateData,
+}
+
+#[derive(Updatable)]
+#[table_name = "calendar_event_rules"]
+pub struct CalendarEventRuleUpdateParsed {
+    pub name: Option<String>,
+    pub title_prefix: Option<String>,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Deserialize)]
+pub struct CalendarEventRuleId {
+    pub id: Uuid,
+}
+
+// What one calendar pull did
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarPullReport {
+    pub created: usize,
+    // Tasks whose event moved, given the new start as their deadline
+    pub moved: usize,
+}
@@ -0,0 +1,64 @@
+use chrono::{DateTime, Utc};
+use db_macros::Queryable;
+use rusqlite::types::{FromSql, FromSqlError, ToSql, ToSqlOutput, ValueRef};
+use rusqlite::Result as RusqliteResult;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeOp {
+    Insert,
+    Update,
+    Delete,
+}
+
+impl ToSql for ChangeOp {
+    fn to_sql(&self) -> RusqliteResult<ToSqlOutput<'_>> {
+        let s = match self {
+            ChangeOp::Insert => "insert",
+            ChangeOp::Update => "update",
+            ChangeOp::Delete => "delete",
+        };
+        Ok(ToSqlOutput::from(s))
+    }
+}
+
+impl FromSql for ChangeOp {
+    fn column_result(value: ValueRef<'_>) -> Result<Self, FromSqlError> {
+        String::column_result(value).and_then(|s| match s.as_str() {
+            "insert" => Ok(ChangeOp::Insert),
+            "update" => Ok(ChangeOp::Update),
+            "delete" => Ok(ChangeOp::Delete),
+            _ => Err(FromSqlError::InvalidType),
+        })
+    }
+}
+
+// One row of the changelog table, written by triggers on tracked tables
+#[derive(Debug, Clone, Queryable)]
+pub struct ChangelogEntry {
+    pub seq: i64,
+    pub table_name: String,
+    pub row_id: String,
+    pub op: ChangeOp,
+    // JSON objects of the changed columns; None before an insert and after a delete
+    pub old_values: Option<String>,
+    pub new_values: Option<String>,
+    pub clock: i64,
+    pub device_id: String,
+    pub changed_at: DateTime<Utc>,
+}
+
+// A changelog entry for history views, with its values parsed
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RowChange {
+    pub seq: i64,
+    pub op: ChangeOp,
+    pub old_values: Option<Map<String, Value>>,
+    pub new_values: Option<Map<String, Value>>,
+    pub clock: i64,
+    pub device_id: String,
+    pub changed_at: DateTime<Utc>,
+}
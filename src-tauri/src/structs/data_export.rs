@@ -0,0 +1,243 @@
+use chrono::{DateTime, Utc};
+use db_macros::Queryable;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::structs::calendar_event::EventOrigin;
+use crate::structs::settings::Settings;
+use crate::structs::task_struct::Task;
+use crate::structs::theme::Theme;
+
+// Versioned JSON document written by export_data and read by import_data
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataExport {
+    pub format_version: u32,
+    pub app_version: String,
+    pub exported_at: DateTime<Utc>,
+    pub tasks: Vec<Task>,
+    pub settings: Settings,
+    pub theme: Theme,
+    pub calendar_links: Vec<CalendarLink>,
+}
+
+// Which Google Calendar event belongs to which task
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarLink {
+    pub task_id: Uuid,
+    pub google_event_id: String,
+    // Older exports only had events made for tasks
+    #[serde(default)]
+    pub origin: EventOrigin,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ImportMode {
+    // Keep local data; tasks with the same ID take whichever side was edited last
+    Merge,
+    // Drop local tasks and take tasks, settings and theme from the file
+    Replace,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportSummary {
+    pub imported: usize,
+    pub updated: usize,
+    pub skipped: usize,
+}
+
+#[derive(Deserialize)]
+pub struct DataExportData {
+    pub path: String,
+}
+
+#[derive(Deserialize)]
+pub struct DataImportData {
+    pub path: String,
+    pub mode: ImportMode,
+}
+
+// Read first from an archive; lists its entries so older apps can refuse newer layouts
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveManifest {
+    pub format_version: u32,
+    pub app_version: String,
+    pub created_at: DateTime<Utc>,
+    pub entries: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub struct ArchiveData {
+    pub path: String,
+}
+
+// Task fields that can be picked for a CSV export, in the order given
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CsvColumn {
+    Id,
+    Title,
+    Notes,
+    Status,
+    CreatedAt,
+    UpdatedAt,
+    Deadline,
+    StartedAt,
+    PausedAt,
+    CompletedAt,
+    ReminderFrequency,
+    CalendarIntegration,
+}
+
+impl CsvColumn {
+    pub const ALL: [CsvColumn; 12] = [
+        CsvColumn::Id,
+        CsvColumn::Title,
+        CsvColumn::Notes,
+        CsvColumn::Status,
+        CsvColumn::CreatedAt,
+        CsvColumn::UpdatedAt,
+        CsvColumn::Deadline,
+        CsvColumn::StartedAt,
+        CsvColumn::PausedAt,
+        CsvColumn::CompletedAt,
+        CsvColumn::ReminderFrequency,
+        CsvColumn::CalendarIntegration,
+    ];
+    
+    pub fn header(&self) -> &'static str {
+        match self {
+            CsvColumn::Id => "id",
+            CsvColumn::Title => "title",
+            CsvColumn::Notes => "notes",
+            CsvColumn::Status => "status",
+            CsvColumn::CreatedAt => "created_at",
+            CsvColumn::UpdatedAt => "updated_at",
+            CsvColumn::Deadline => "deadline",
+            CsvColumn::StartedAt => "started_at",
+            CsvColumn::PausedAt => "paused_at",
+            CsvColumn::CompletedAt => "completed_at",
+            CsvColumn::ReminderFrequency => "reminder_frequency",
+            CsvColumn::CalendarIntegration => "calendar_integration",
+        }
+    }
+}
+
+// Inclusive range of task creation days, as ISO 8601 datetimes; days start at the given offset
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DateRangeData {
+    pub from: String,
+    pub to: String,
+    #[serde(default)]
+    pub utc_offset_minutes: Option<i32>,
+}
+
+// An empty column list exports every column
+#[derive(Deserialize)]
+pub struct CsvExportData {
+    pub range: DateRangeData,
+    pub columns: Vec<CsvColumn>,
+    pub path: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PdfAgendaData {
+    pub date_range: DateRangeData,
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SummaryPeriod {
+    Day,
+    // Monday to Sunday around the given date
+    Week,
+}
+
+// Without a folder the summary is only returned
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarkdownSummaryData {
+    pub date: String,
+    pub period: SummaryPeriod,
+    pub folder: Option<String>,
+    #[serde(default)]
+    pub utc_offset_minutes: Option<i32>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarkdownSummary {
+    pub markdown: String,
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SharedDayFormat {
+    // Single self-contained page
+    Html,
+    Json,
+}
+
+// Without a folder the snapshot is only returned, e.g. for the clipboard
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedDayData {
+    pub date: String,
+    pub format: SharedDayFormat,
+    pub folder: Option<String>,
+    // Notes can hold private details, so they're left out unless asked for
+    #[serde(default)]
+    pub include_notes: bool,
+    #[serde(default)]
+    pub utc_offset_minutes: Option<i32>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedDay {
+    pub content: String,
+    pub path: Option<String>,
+}
+
+// Read-only view of a day's plan for someone else; no IDs, accounts or settings
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedDaySnapshot {
+    // YYYY-MM-DD
+    pub date: String,
+    pub generated_at: DateTime<Utc>,
+    pub tasks: Vec<SharedTask>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedTask {
+    pub title: String,
+    pub notes: Option<String>,
+    pub status: String,
+    pub deadline: Option<DateTime<Utc>>,
+    pub estimate_minutes: Option<i32>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+// Other apps whose exports can be imported as tasks
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportSource {
+    Trello,
+    TickTick,
+}
+
+#[derive(Deserialize)]
+pub struct TaskImportData {
+    pub source: ImportSource,
+    pub path: String,
+}
@@ -0,0 +1,49 @@
+use db_macros::Queryable;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::structs::task_struct::{Status, TaskId};
+
+// `task_id` can't be done before `depends_on_id` is
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Queryable)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskDependency {
+    pub task_id: TaskId,
+    pub depends_on_id: TaskId,
+}
+
+// One of the two: the tasks linked to a task, directly or through others, or a project's tasks
+// with whatever they're linked to outside the project
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyGraphQuery {
+    pub root_id: Option<TaskId>,
+    pub project_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyNode {
+    pub id: TaskId,
+    pub title: String,
+    pub status: Status,
+    // Waits on a task that isn't completed yet
+    pub blocked: bool,
+    // Some task waits on this one
+    pub blocking: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyEdge {
+    pub task_id: TaskId,
+    pub depends_on_id: TaskId,
+    // The task depended on is completed
+    pub satisfied: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyGraph {
+    pub nodes: Vec<DependencyNode>,
+    pub edges: Vec<DependencyEdge>,
+}
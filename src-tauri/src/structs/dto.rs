@@ -1,19 +1,133 @@
 use serde::Deserialize;
+use crate::structs::reminder_frequency::{ReminderFrequency, ReminderScope};
+use crate::structs::task_struct::{TaskFilter, TaskId};
+use crate::structs::work_session::PauseReason;
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TaskData {
     pub title: String,
     pub created_at: String,
+    // Client-generated per submission; a retry with the same key returns the task already created
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
+    // Left out, the task gets the default reminder frequency from settings
+    #[serde(default)]
+    pub reminder_frequency: Option<ReminderFrequency>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickAddData {
+    pub text: String,
 }
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DateQuery {
     pub date: String,
+    // Minutes east of UTC where the client's days start; settings decide when left out
+    #[serde(default)]
+    pub utc_offset_minutes: Option<i32>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContextQuery {
+    // Any context when left out
+    #[serde(default)]
+    pub context: Option<String>,
+    // low, medium or high; tasks without an energy label always fit
+    #[serde(default)]
+    pub max_energy: Option<String>,
+    #[serde(default)]
+    pub available_minutes: Option<i32>,
+}
+
+// Payload naming a single task; a malformed ID is rejected while deserializing
+#[derive(Deserialize)]
+pub struct TaskRef {
+    pub id: TaskId,
+}
+
+// Payload naming several tasks, fetched in one query
+#[derive(Deserialize)]
+pub struct TaskIds {
+    pub ids: Vec<TaskId>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PauseTaskData {
+    pub id: TaskId,
+    // Left out when the user didn't say why
+    #[serde(default)]
+    pub reason: Option<PauseReason>,
+    // Kept on the session the pause ends
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct CompleteTaskData {
+    pub id: TaskId,
+    // Kept on the session the completion ends
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+// Two tasks to merge: the source is folded into the target and deleted
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeTasksData {
+    pub source_id: TaskId,
+    pub target_id: TaskId,
+}
+
+#[derive(Deserialize)]
+pub struct LogQuery {
+    pub lines: usize,
+}
+
+#[derive(Deserialize)]
+pub struct DiagnosticsExportData {
+    pub path: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdleResolutionData {
+    pub task_ids: Vec<TaskId>,
+    pub keep: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LanDeviceId {
+    pub device_id: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LanPairData {
+    pub device_id: String,
+    // Shown on the other device by start_lan_pairing
+    pub code: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyDefaultRemindersData {
+    #[serde(default)]
+    pub scope: ReminderScope,
 }
 
+// Rows `offset` to `offset + limit` of the tasks the filter matches, for a virtualized list
 #[derive(Deserialize)]
-pub struct TaskId {
-    pub id: String,
+#[serde(rename_all = "camelCase")]
+pub struct TaskWindowQuery {
+    #[serde(default)]
+    pub filter: TaskFilter,
+    pub offset: i64,
+    pub limit: i64,
 }
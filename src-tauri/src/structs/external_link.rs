@@ -0,0 +1,13 @@
+use db_macros::Queryable;
+use serde::Serialize;
+use uuid::Uuid;
+
+// A task's counterpart in another service, e.g. source "github", external_id "owner/repo#12"
+#[derive(Debug, Clone, Serialize, Queryable)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalLink {
+    pub task_id: Uuid,
+    pub source: String,
+    pub external_id: String,
+    pub url: Option<String>,
+}
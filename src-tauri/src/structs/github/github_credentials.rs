@@ -0,0 +1,39 @@
+use db_macros::Queryable;
+use serde::{Deserialize, Serialize};
+
+// Stored token; never sent to the frontend
+#[derive(Debug, Clone, Queryable)]
+pub struct GithubCredentials {
+    pub login: String,
+    pub token: String,
+    pub close_issues_on_complete: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GithubStatus {
+    pub login: String,
+    pub close_issues_on_complete: bool,
+}
+
+impl From<GithubCredentials> for GithubStatus {
+    fn from(creds: GithubCredentials) -> Self {
+        GithubStatus {
+            login: creds.login,
+            close_issues_on_complete: creds.close_issues_on_complete,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GithubConnectData {
+    pub token: String,
+    #[serde(default)]
+    pub close_issues_on_complete: bool,
+}
+
+#[derive(Deserialize)]
+pub struct GithubCloseIssuesData {
+    pub enabled: bool,
+}
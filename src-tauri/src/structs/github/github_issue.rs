@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+pub struct GithubUser {
+    pub login: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GithubRepository {
+    pub full_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GithubIssue {
+    pub number: u64,
+    pub title: String,
+    pub body: Option<String>,
+    pub html_url: String,
+    pub repository: GithubRepository,
+    // Present when the "issue" is a pull request
+    pub pull_request: Option<serde_json::Value>,
+}
+
+impl GithubIssue {
+    // Stable ID used for the task link, e.g. "owner/repo#12"
+    pub fn external_id(&self) -> String {
+        format!("{}#{}", self.repository.full_name, self.number)
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GithubSyncSummary {
+    pub created: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+}
@@ -0,0 +1,5 @@
+pub mod github_credentials;
+pub mod github_issue;
+
+pub use github_credentials::{GithubConnectData, GithubCloseIssuesData, GithubCredentials, GithubStatus};
+pub use github_issue::{GithubIssue, GithubSyncSummary, GithubUser};
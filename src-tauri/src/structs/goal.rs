@@ -0,0 +1,109 @@
+use chrono::{DateTime, Utc};
+use db_macros::{Insertable, Queryable, Updatable};
+use rusqlite::types::{FromSql, FromSqlError, ToSql, ToSqlOutput, ValueRef};
+use rusqlite::Result as RusqliteResult;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::db::Insertable;
+
+// What a goal counts over its week
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum GoalMetric {
+    CompletedTasks,
+    // Start to completion of completed tasks, as in the time stats
+    TrackedMinutes,
+}
+
+impl ToSql for GoalMetric {
+    fn to_sql(&self) -> RusqliteResult<ToSqlOutput<'_>> {
+        let s = match self {
+            GoalMetric::CompletedTasks => "completed-tasks",
+            GoalMetric::TrackedMinutes => "tracked-minutes",
+        };
+        Ok(ToSqlOutput::from(s))
+    }
+}
+
+impl FromSql for GoalMetric {
+    fn column_result(value: ValueRef<'_>) -> Result<Self, FromSqlError> {
+        String::column_result(value).and_then(|s| match s.as_str() {
+            "completed-tasks" => Ok(GoalMetric::CompletedTasks),
+            "tracked-minutes" => Ok(GoalMetric::TrackedMinutes),
+            _ => Err(FromSqlError::InvalidType),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Insertable, Queryable)]
+#[serde(rename_all = "camelCase")]
+#[table_name = "goals"]
+pub struct Goal {
+    pub id: Uuid,
+    pub title: String,
+    pub metric: GoalMetric,
+    pub target: i64,
+    pub created_at: DateTime<Utc>,
+    // Monday (YYYY-MM-DD) of the last week the goal was met, so it's announced once a week
+    pub last_met_week: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalData {
+    pub title: String,
+    pub metric: GoalMetric,
+    pub target: i64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalUpdateData {
+    pub title: Option<String>,
+    pub metric: Option<GoalMetric>,
+    pub target: Option<i64>,
+}
+
+#[derive(Deserialize)]
+pub struct GoalUpdate {
+    pub id: String,
+    pub data: GoalUpdateData,
+}
+
+#[derive(Updatable)]
+#[table_name = "goals"]
+pub struct GoalUpdateParsed {
+    pub title: Option<String>,
+    pub metric: Option<GoalMetric>,
+    pub target: Option<i64>,
+}
+
+#[derive(Deserialize)]
+pub struct GoalId {
+    pub id: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalProgressQuery {
+    // Any moment in the week; weeks run Monday to Sunday
+    pub week: String,
+    #[serde(default)]
+    pub utc_offset_minutes: Option<i32>,
+}
+
+#[derive(Debug, Queryable)]
+pub struct WeekTotals {
+    pub completed: i64,
+    pub tracked_minutes: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalProgress {
+    pub goal: Goal,
+    pub current: i64,
+    // 0-100, capped
+    pub percentage: f64,
+    pub met: bool,
+}
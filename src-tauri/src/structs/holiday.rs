@@ -0,0 +1,88 @@
+use std::collections::HashSet;
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, Utc};
+use db_macros::Queryable;
+use serde::{Deserialize, Serialize};
+use crate::helpers::holidays::is_weekend;
+
+// A date stretch of holidays longer than this is left as it is rather than skipped
+pub const MAX_SKIP_DAYS: i64 = 31;
+
+// Imported from an ICS file
+#[derive(Debug, Clone, Serialize, Queryable)]
+#[serde(rename_all = "camelCase")]
+pub struct Holiday {
+    pub date: NaiveDate,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum HolidaySource {
+    Imported,
+    // The bundled list for the region in settings
+    Region,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListedHoliday {
+    pub date: NaiveDate,
+    pub name: String,
+    pub source: HolidaySource,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HolidayImportData {
+    // Path to an .ics file
+    pub path: String,
+    // Drop previously imported holidays first
+    #[serde(default)]
+    pub replace: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HolidayImport {
+    pub added: usize,
+    // Already imported with the same date and name
+    pub skipped: usize,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HolidayRangeQuery {
+    // YYYY-MM-DD, both included
+    pub from: String,
+    pub to: String,
+}
+
+// Which days scheduling may use; every day is a working day when skipping is off in settings
+#[derive(Debug, Clone, Default)]
+pub struct WorkingCalendar {
+    pub skip_weekends: bool,
+    pub holidays: HashSet<NaiveDate>,
+}
+
+impl WorkingCalendar {
+    pub fn is_working_day(&self, day: NaiveDate) -> bool {
+        if self.skip_weekends && is_weekend(day) {
+            return false;
+        }
+        !self.holidays.contains(&day)
+    }
+    
+    /// `day` itself when it's a working day, else the next one
+    pub fn next_working_day(&self, day: NaiveDate) -> NaiveDate {
+        day.iter_days()
+            .take(MAX_SKIP_DAYS as usize + 1)
+            .find(|day| self.is_working_day(*day))
+            .unwrap_or(day)
+    }
+    
+    /// Move a moment to the same time on the next working day, with days cut at `offset`
+    pub fn shift_to_working_day(&self, moment: DateTime<Utc>, offset: FixedOffset) -> DateTime<Utc> {
+        let day = moment.with_timezone(&offset).date_naive();
+        moment + Duration::days((self.next_working_day(day) - day).num_days())
+    }
+}
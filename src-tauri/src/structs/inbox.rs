@@ -0,0 +1,47 @@
+use chrono::{DateTime, Utc};
+use db_macros::Queryable;
+use serde::{Deserialize, Serialize};
+
+// Text captured without a date, waiting to be triaged into a task
+#[derive(Debug, Clone, Serialize, Queryable)]
+#[serde(rename_all = "camelCase")]
+pub struct InboxItem {
+    pub id: i64,
+    pub text: String,
+    pub captured_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureData {
+    pub text: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InboxItemRef {
+    pub id: i64,
+}
+
+// Fields of the task a capture becomes; only the day is required
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TriageTaskFields {
+    // The captured text when left out
+    pub title: Option<String>,
+    // Day the task is planned for, like TaskData.created_at
+    pub created_at: String,
+    pub deadline: Option<String>,
+    pub notes: Option<String>,
+    pub estimate_minutes: Option<i32>,
+    pub context: Option<String>,
+    // low, medium or high
+    pub energy: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TriageData {
+    pub id: i64,
+    pub task_fields: TriageTaskFields,
+}
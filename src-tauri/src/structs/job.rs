@@ -0,0 +1,74 @@
+use chrono::{DateTime, Duration, Local, NaiveTime, TimeZone, Utc};
+use db_macros::Queryable;
+use serde::{Deserialize, Serialize};
+
+// Persisted scheduler job state
+#[derive(Debug, Clone, Serialize, Queryable)]
+#[serde(rename_all = "camelCase")]
+pub struct Job {
+    pub name: String,
+    pub schedule: String,
+    pub enabled: bool,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub next_run_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize)]
+pub struct JobName {
+    pub name: String,
+}
+
+// When a job runs: "every:<seconds>" or "daily:<HH:MM>" (local time)
+#[derive(Debug, Clone, PartialEq)]
+pub enum Schedule {
+    Every(i64),
+    Daily(NaiveTime),
+}
+
+impl Schedule {
+    pub fn parse(schedule: &str) -> Result<Self, String> {
+        match schedule.split_once(':') {
+            Some(("every", secs)) => {
+                let secs = secs.parse::<i64>()
+                    .map_err(|e| format!("Invalid interval in schedule '{}': {}", schedule, e))?;
+                if secs <= 0 {
+                    return Err(format!("Interval must be positive in schedule '{}'", schedule));
+                }
+                Ok(Schedule::Every(secs))
+            }
+            Some(("daily", time)) => {
+                let time = NaiveTime::parse_from_str(time, "%H:%M")
+                    .map_err(|e| format!("Invalid time in schedule '{}': {}", schedule, e))?;
+                Ok(Schedule::Daily(time))
+            }
+            _ => Err(format!("Unsupported schedule: {}", schedule)),
+        }
+    }
+
+    // Next run strictly after the given instant
+    pub fn next_after(&self, after: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            Schedule::Every(secs) => after + Duration::seconds(*secs),
+            Schedule::Daily(time) => {
+                let local_after = after.with_timezone(&Local);
+                // Today or tomorrow, or the day after if tomorrow's time falls in a DST gap
+                local_after.date_naive().iter_days().take(3)
+                    .filter_map(|date| Local.from_local_datetime(&date.and_time(*time)).earliest())
+                    .find(|candidate| *candidate > local_after)
+                    .map(|candidate| candidate.with_timezone(&Utc))
+                    // Only at the end of the calendar; try again in a day
+                    .unwrap_or(after + Duration::days(1))
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Schedule {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Schedule::Every(secs) => write!(f, "every:{}", secs),
+            Schedule::Daily(time) => write!(f, "daily:{}", time.format("%H:%M")),
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+
+// Payloads and results of the old main.rs commands, in the shapes those frontends expect
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LegacySearchQuery {
+    pub query: String,
+}
+
+// One day in the old sidebar
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DateSection {
+    // Start of the day in the settings offset
+    pub date: DateTime<FixedOffset>,
+    pub label: String,
+    pub is_today: bool,
+    pub is_yesterday: bool,
+}
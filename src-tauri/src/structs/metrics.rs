@@ -0,0 +1,63 @@
+use db_macros::Queryable;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MetricKind {
+    // A Tauri command invoked by the UI
+    Command,
+    // Something used outside the UI: HTTP API, Slack, vault edits, deep links
+    Feature,
+}
+
+impl MetricKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MetricKind::Command => "command",
+            MetricKind::Feature => "feature",
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct UsageMetricsQuery {
+    pub days: u32,
+}
+
+#[derive(Deserialize)]
+pub struct UsageMetricsExportData {
+    pub path: String,
+}
+
+// Stored count for one day; also the export format
+#[derive(Debug, Serialize, Queryable)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageMetricRow {
+    // YYYY-MM-DD (UTC)
+    pub day: String,
+    pub kind: String,
+    pub name: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Queryable)]
+pub struct UsageMetricTotal {
+    pub kind: String,
+    pub name: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageMetricCount {
+    pub name: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageMetrics {
+    pub enabled: bool,
+    // Most used first
+    pub commands: Vec<UsageMetricCount>,
+    pub features: Vec<UsageMetricCount>,
+}
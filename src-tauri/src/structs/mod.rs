@@ -2,5 +2,39 @@ pub mod task_struct;
 pub mod dto;
 pub mod task_update;
 pub mod settings;
+pub mod theme;
 pub mod calendar;
 pub mod calendar_event;
+pub mod overview;
+pub mod job;
+pub mod app_info;
+pub mod recovery;
+pub mod window_state;
+pub mod network;
+pub mod calendar_journal;
+pub mod data_export;
+pub mod external_link;
+pub mod github;
+pub mod stats;
+pub mod goal;
+pub mod metrics;
+pub mod sync;
+pub mod changelog;
+pub mod startup;
+pub mod webhook;
+pub mod rule;
+pub mod inbox;
+pub mod schedule;
+pub mod query;
+pub mod holiday;
+pub mod app_lock;
+pub mod project;
+pub mod work_session;
+pub mod secret;
+pub mod remembered_date;
+pub mod legacy;
+pub mod tag;
+pub mod dependency;
+pub mod calendar_rule;
+pub mod reminder_frequency;
+pub mod recurrence;
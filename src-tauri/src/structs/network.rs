@@ -0,0 +1,60 @@
+use chrono::{DateTime, Utc};
+use db_macros::{Queryable, Updatable};
+use serde::{Deserialize, Serialize};
+
+// Features that make outbound network calls and need the user's consent first
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NetworkFeature {
+    Calendar,
+    Github,
+    Sync,
+}
+
+impl NetworkFeature {
+    pub fn name(&self) -> &'static str {
+        match self {
+            NetworkFeature::Calendar => "calendar",
+            NetworkFeature::Github => "github",
+            NetworkFeature::Sync => "sync",
+        }
+    }
+}
+
+// None: not asked yet, Some(false): declined, Some(true): allowed
+#[derive(Debug, Clone, Serialize, Queryable)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkPermissions {
+    pub calendar_network_allowed: Option<bool>,
+    pub github_network_allowed: Option<bool>,
+    pub sync_network_allowed: Option<bool>,
+}
+
+impl NetworkPermissions {
+    pub fn get(&self, feature: NetworkFeature) -> Option<bool> {
+        match feature {
+            NetworkFeature::Calendar => self.calendar_network_allowed,
+            NetworkFeature::Github => self.github_network_allowed,
+            NetworkFeature::Sync => self.sync_network_allowed,
+        }
+    }
+}
+
+// Omitted fields keep their current answer
+#[derive(Debug, Deserialize, Updatable)]
+#[serde(rename_all = "camelCase")]
+#[table_name = "settings"]
+pub struct NetworkPermissionsUpdate {
+    pub calendar_network_allowed: Option<bool>,
+    pub github_network_allowed: Option<bool>,
+    pub sync_network_allowed: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectivityStatus {
+    pub online: bool,
+    // None until the first check
+    pub checked_at: Option<DateTime<Utc>>,
+    // Calendar changes waiting to be sent
+    pub pending_calendar_changes: i64,
+}
@@ -0,0 +1,27 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+use crate::structs::remembered_date::UpcomingDate;
+use crate::structs::task_struct::{Task, TaskListItem};
+
+// Aggregate of a day's open work, used by the tray and widgets
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TodayOverview {
+    pub remaining_count: i64,
+    pub top_tasks: Vec<TaskListItem>,
+    // Birthdays and the like within their reminder window
+    pub remembered_dates: Vec<UpcomingDate>,
+}
+
+// End of a day: what got done, what is left and time tracked on the finished tasks
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailySummary {
+    pub date: NaiveDate,
+    pub completed_count: usize,
+    pub remaining_count: usize,
+    // Start to completion of the day's completed tasks
+    pub tracked_minutes: i64,
+    pub completed: Vec<Task>,
+    pub remaining: Vec<Task>,
+}
@@ -0,0 +1,172 @@
+use chrono::{DateTime, Utc};
+use db_macros::{Insertable, Queryable};
+use rusqlite::types::{FromSql, FromSqlError, ToSql, ToSqlOutput, ValueRef};
+use rusqlite::Result as RusqliteResult;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::db::Insertable;
+use crate::structs::inbox::InboxItem;
+use crate::structs::task_struct::TaskId;
+
+// What a member may do with a shared project's tasks
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ProjectRole {
+    // Created the project; the only one who changes its members
+    Owner,
+    Editor,
+    // Sees the tasks; changes made on a viewer's devices aren't shared
+    Viewer,
+}
+
+impl ProjectRole {
+    pub fn can_edit(&self) -> bool {
+        !matches!(self, ProjectRole::Viewer)
+    }
+}
+
+impl ToSql for ProjectRole {
+    fn to_sql(&self) -> RusqliteResult<ToSqlOutput<'_>> {
+        let s = match self {
+            ProjectRole::Owner => "owner",
+            ProjectRole::Editor => "editor",
+            ProjectRole::Viewer => "viewer",
+        };
+        Ok(ToSqlOutput::from(s))
+    }
+}
+
+impl FromSql for ProjectRole {
+    fn column_result(value: ValueRef<'_>) -> Result<Self, FromSqlError> {
+        String::column_result(value).and_then(|s| match s.as_str() {
+            "owner" => Ok(ProjectRole::Owner),
+            "editor" => Ok(ProjectRole::Editor),
+            "viewer" => Ok(ProjectRole::Viewer),
+            _ => Err(FromSqlError::InvalidType),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Insertable, Queryable)]
+#[serde(rename_all = "camelCase")]
+#[table_name = "projects"]
+pub struct Project {
+    pub id: Uuid,
+    pub name: String,
+    // Folder path or WebDAV URL; None until the project is shared
+    pub share_location: Option<String>,
+    // Hex; only ever leaves the device inside an invite
+    #[serde(skip)]
+    pub share_key: Option<String>,
+    // This person's role in the project
+    pub role: ProjectRole,
+    #[serde(skip)]
+    pub last_seq: i64,
+    pub synced_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub archived_at: Option<DateTime<Utc>>,
+}
+
+// Members are named by their sync identity (settings), not by device
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectMember {
+    pub identity: String,
+    pub role: ProjectRole,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectDetail {
+    #[serde(flatten)]
+    pub project: Project,
+    pub members: Vec<ProjectMember>,
+    pub task_count: i64,
+}
+
+// Written by the owner to the share location, sealed with the share key
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectManifest {
+    pub project_id: Uuid,
+    pub name: String,
+    pub members: Vec<ProjectMember>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectData {
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectId {
+    pub id: Uuid,
+}
+
+// What happens to a project's open tasks when it's archived; completed tasks stay with the project
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ArchiveMode {
+    // Archived along with the project
+    Archive,
+    // Turned back into inbox captures to be triaged again
+    Inbox,
+    // Refuse while any are open
+    Block,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveProjectData {
+    pub id: Uuid,
+    pub mode: ArchiveMode,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveProjectSummary {
+    pub project_id: Uuid,
+    pub mode: ArchiveMode,
+    pub archived_tasks: Vec<TaskId>,
+    // Tasks moved to the inbox, deleted once their capture was made
+    pub moved_tasks: Vec<TaskId>,
+    pub inbox_items: Vec<InboxItem>,
+    // Completed tasks left in the archived project
+    pub completed_count: i64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShareProjectData {
+    pub id: Uuid,
+    // Folder every member's sync tool sees, or a WebDAV folder URL
+    pub location: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JoinProjectData {
+    // From the owner's share_project
+    pub invite: String,
+    // Where this device sees the share location; a synced folder's path differs between devices
+    pub location: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectMembersData {
+    pub id: Uuid,
+    // Everyone but the owner, who stays a member
+    pub members: Vec<ProjectMember>,
+}
+
+// Sent to the people the project is shared with; holds the key, so treat it like a password
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectInvite {
+    pub invite: String,
+    pub location: String,
+}
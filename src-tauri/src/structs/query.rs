@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+pub struct QueryData {
+    pub sql: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryResult {
+    pub columns: Vec<String>,
+    // One array per row, in column order
+    pub rows: Vec<Vec<serde_json::Value>>,
+    // More rows matched than were returned
+    pub truncated: bool,
+}
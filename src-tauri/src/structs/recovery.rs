@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+// Why the app is running on the fallback database and what can be done about it
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveryStatus {
+    pub error: String,
+    pub corrupted: bool,
+    pub latest_backup: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RecoveryAction {
+    RestoreBackup,
+    Recreate,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveryRequest {
+    pub action: RecoveryAction,
+}
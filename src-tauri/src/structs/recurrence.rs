@@ -0,0 +1,80 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use db_macros::{DbEnum, Queryable};
+use serde::{Deserialize, Serialize};
+use crate::structs::task_struct::TaskId;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, DbEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum RecurrenceFrequency {
+    Daily,
+    Weekly,
+    // Same day of the month; the last day in months too short for it
+    Monthly,
+}
+
+// What happens to one occurrence of a repeating task
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, DbEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum ExceptionKind {
+    // Not made, or deleted if it already was
+    Skip,
+    // Due at its own deadline instead of the series' time
+    Reschedule,
+    // Neither this occurrence nor any later one is made
+    EndSeries,
+}
+
+// How a task repeats; the task is the first occurrence and later ones are tasks of their own
+#[derive(Debug, Clone, Serialize, Queryable)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskRecurrence {
+    pub task_id: TaskId,
+    pub frequency: RecurrenceFrequency,
+    // Every `interval` days, weeks or months
+    pub interval: u32,
+    // Local day of the task's own deadline
+    pub starts_on: NaiveDate,
+    #[serde(skip)]
+    pub generated_through: NaiveDate,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Queryable)]
+#[serde(rename_all = "camelCase")]
+pub struct RecurrenceException {
+    pub series_id: TaskId,
+    pub occurrence_on: NaiveDate,
+    pub kind: ExceptionKind,
+    // Set for Reschedule
+    pub deadline: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecurrenceData {
+    pub task_id: TaskId,
+    pub frequency: RecurrenceFrequency,
+    #[serde(default)]
+    pub interval: Option<u32>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecurrenceExceptionData {
+    pub series_id: TaskId,
+    // YYYY-MM-DD in the settings offset
+    pub occurrence_on: NaiveDate,
+    pub kind: ExceptionKind,
+    // The occurrence's new deadline; only for Reschedule
+    #[serde(default)]
+    pub deadline: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecurrenceView {
+    #[serde(flatten)]
+    pub recurrence: TaskRecurrence,
+    pub exceptions: Vec<RecurrenceException>,
+}
@@ -0,0 +1,67 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use db_macros::Queryable;
+use serde::{Deserialize, Serialize};
+
+// Reminder lead time when none is given
+pub const DEFAULT_REMIND_DAYS: u32 = 3;
+
+// A birthday, anniversary or other date that comes around every year; not a task
+#[derive(Debug, Clone, Serialize, Queryable)]
+#[serde(rename_all = "camelCase")]
+pub struct RememberedDate {
+    pub id: i64,
+    pub name: String,
+    pub month: u32,
+    pub day: u32,
+    // Year it started, if known
+    pub year: Option<i32>,
+    // Shown in the today overview and notified this many days ahead
+    pub remind_days_before: u32,
+    #[serde(skip)]
+    pub reminded_for: Option<NaiveDate>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RememberedDateData {
+    pub name: String,
+    pub month: u32,
+    pub day: u32,
+    #[serde(default)]
+    pub year: Option<i32>,
+    #[serde(default)]
+    pub remind_days_before: Option<u32>,
+}
+
+impl RememberedDateData {
+    pub fn remind_days(&self) -> u32 {
+        self.remind_days_before.unwrap_or(DEFAULT_REMIND_DAYS)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RememberedDateUpdate {
+    pub id: i64,
+    pub data: RememberedDateData,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RememberedDateId {
+    pub id: i64,
+}
+
+// A remembered date with when it next comes around
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpcomingDate {
+    #[serde(flatten)]
+    pub date: RememberedDate,
+    pub next_on: NaiveDate,
+    // 0 on the day itself
+    pub days_until: i64,
+    // Age or anniversary number on next_on, when the year is known
+    pub years: Option<i32>,
+}
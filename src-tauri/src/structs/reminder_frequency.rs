@@ -0,0 +1,53 @@
+use std::str::FromStr;
+use db_macros::DbEnum;
+use serde::{Deserialize, Serialize};
+
+// How often a task reminds before its deadline; settings hold the one new tasks start with
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, DbEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReminderFrequency {
+    #[default]
+    None,
+    Hourly,
+    #[serde(rename = "every-3-hours")]
+    #[db(rename = "every-3-hours")]
+    Every3Hours,
+    Daily,
+    // Daily, then hourly in the last hours before the deadline
+    Escalating,
+}
+
+impl From<ReminderFrequency> for String {
+    fn from(freq: ReminderFrequency) -> Self {
+        freq.as_db_str().to_string()
+    }
+}
+
+impl FromStr for ReminderFrequency {
+    type Err = String;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ReminderFrequency::from_db_str(s.trim())
+            .ok_or_else(|| format!("Invalid reminder frequency '{}': expected {}", s, ReminderFrequency::DB_VALUES.join(", ")))
+    }
+}
+
+// Open tasks the default reminder frequency from settings is applied to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReminderScope {
+    // Tasks without reminders
+    #[default]
+    Unset,
+    // Every open task
+    All,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DefaultRemindersReport {
+    pub updated: usize,
+    // Calendar events updated to match, and those Google didn't take; the journal retries those
+    pub calendar_updated: usize,
+    pub calendar_failed: usize,
+}
@@ -0,0 +1,120 @@
+use chrono::{DateTime, Utc};
+use db_macros::{Insertable, Queryable, Updatable};
+use rusqlite::types::{FromSql, FromSqlError, ToSql, ToSqlOutput, ValueRef};
+use rusqlite::Result as RusqliteResult;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::db::Insertable;
+
+// When a rule is evaluated
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum RuleTrigger {
+    TaskCreated,
+    TaskCompleted,
+    // Checked by the rules job; fires once per task and deadline
+    DeadlinePassed,
+}
+
+impl ToSql for RuleTrigger {
+    fn to_sql(&self) -> RusqliteResult<ToSqlOutput<'_>> {
+        let s = match self {
+            RuleTrigger::TaskCreated => "task-created",
+            RuleTrigger::TaskCompleted => "task-completed",
+            RuleTrigger::DeadlinePassed => "deadline-passed",
+        };
+        Ok(ToSqlOutput::from(s))
+    }
+}
+
+impl FromSql for RuleTrigger {
+    fn column_result(value: ValueRef<'_>) -> Result<Self, FromSqlError> {
+        String::column_result(value).and_then(|s| match s.as_str() {
+            "task-created" => Ok(RuleTrigger::TaskCreated),
+            "task-completed" => Ok(RuleTrigger::TaskCompleted),
+            "deadline-passed" => Ok(RuleTrigger::DeadlinePassed),
+            _ => Err(FromSqlError::InvalidType),
+        })
+    }
+}
+
+// What a rule does to the task that triggered it; stored as JSON
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum RuleAction {
+    // New task planned `in_days` from now; the title defaults to "Follow up: <task title>"
+    #[serde(rename_all = "camelCase")]
+    CreateFollowUp { title: Option<String>, in_days: i64 },
+    SetReminder { frequency: String },
+    PostponeDeadline { days: i64 },
+}
+
+impl ToSql for RuleAction {
+    fn to_sql(&self) -> RusqliteResult<ToSqlOutput<'_>> {
+        let json = serde_json::to_string(self)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        Ok(ToSqlOutput::from(json))
+    }
+}
+
+impl FromSql for RuleAction {
+    fn column_result(value: ValueRef<'_>) -> Result<Self, FromSqlError> {
+        String::column_result(value)
+            .and_then(|s| serde_json::from_str(&s).map_err(|e| FromSqlError::Other(Box::new(e))))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Insertable, Queryable)]
+#[serde(rename_all = "camelCase")]
+#[table_name = "rules"]
+pub struct Rule {
+    pub id: Uuid,
+    pub name: String,
+    pub trigger: RuleTrigger,
+    // Only tasks with this #tag in the title or notes; every task when None
+    pub tag: Option<String>,
+    pub action: RuleAction,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleData {
+    pub name: String,
+    pub trigger: RuleTrigger,
+    pub tag: Option<String>,
+    pub action: RuleAction,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleUpdateData {
+    pub name: Option<String>,
+    pub trigger: Option<RuleTrigger>,
+    // Empty clears the tag
+    pub tag: Option<String>,
+    pub action: Option<RuleAction>,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Deserialize)]
+pub struct RuleUpdate {
+    pub id: Uuid,
+    pub data: RuleUpdateData,
+}
+
+#[derive(Updatable)]
+#[table_name = "rules"]
+pub struct RuleUpdateParsed {
+    pub name: Option<String>,
+    pub trigger: Option<RuleTrigger>,
+    pub tag: Option<Option<String>>,
+    pub action: Option<RuleAction>,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Deserialize)]
+pub struct RuleId {
+    pub id: Uuid,
+}
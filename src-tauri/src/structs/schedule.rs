@@ -0,0 +1,77 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::structs::task_struct::TaskId;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleQuery {
+    // Days to plan over, today included
+    pub horizon_days: u32,
+    #[serde(default)]
+    pub utc_offset_minutes: Option<i32>,
+}
+
+// Why a task needs a new day
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ScheduleReason {
+    // Its deadline has passed
+    Overdue,
+    // Planned for a day that is over, and still open
+    CarriedOver,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleSuggestion {
+    pub task_id: TaskId,
+    pub title: String,
+    // YYYY-MM-DD, in the plan's offset
+    pub date: String,
+    pub reason: ScheduleReason,
+    pub deadline: Option<DateTime<Utc>>,
+    // Minutes counted against the day: the estimate, or a default when there is none
+    pub minutes: i64,
+    pub estimated: bool,
+    // No day with room before the deadline; placed on the first day with room after it
+    pub after_deadline: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleDay {
+    // YYYY-MM-DD
+    pub date: String,
+    // False for weekends and holidays skipped per settings; those have no capacity
+    pub working: bool,
+    pub capacity_minutes: i64,
+    // Already planned for the day, before any suggestion
+    pub planned_minutes: i64,
+    pub suggested_minutes: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchedulePlan {
+    pub suggestions: Vec<ScheduleSuggestion>,
+    pub days: Vec<ScheduleDay>,
+    // Too big for the room left on any working day in the horizon
+    pub unplaced: Vec<TaskId>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleItem {
+    pub task_id: TaskId,
+    // YYYY-MM-DD
+    pub date: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyScheduleData {
+    pub items: Vec<ScheduleItem>,
+    // Should match the offset the plan was made with
+    #[serde(default)]
+    pub utc_offset_minutes: Option<i32>,
+}
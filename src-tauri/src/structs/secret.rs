@@ -0,0 +1,47 @@
+use db_macros::Queryable;
+use rusqlite::types::{FromSql, FromSqlError, ToSql, ToSqlOutput, ValueRef};
+use rusqlite::Result as RusqliteResult;
+
+// Where a secret's value is kept
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SecretBackend {
+    // The OS keyring; the secrets row only says it's there
+    Keyring,
+    // The secrets row, encrypted with the device key
+    Database,
+}
+
+impl ToSql for SecretBackend {
+    fn to_sql(&self) -> RusqliteResult<ToSqlOutput<'_>> {
+        let s = match self {
+            SecretBackend::Keyring => "keyring",
+            SecretBackend::Database => "database",
+        };
+        Ok(ToSqlOutput::from(s))
+    }
+}
+
+impl FromSql for SecretBackend {
+    fn column_result(value: ValueRef<'_>) -> Result<Self, FromSqlError> {
+        String::column_result(value).and_then(|s| match s.as_str() {
+            "keyring" => Ok(SecretBackend::Keyring),
+            "database" => Ok(SecretBackend::Database),
+            _ => Err(FromSqlError::InvalidType),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Queryable)]
+pub struct StoredSecret {
+    pub backend: SecretBackend,
+    // Hex, and sealed on top while an app lock is set; None for the keyring
+    pub value: Option<String>,
+}
+
+// A secret still in the column its integration kept it in before the secrets table
+#[derive(Debug, Clone, Queryable)]
+pub struct LegacySecret {
+    pub namespace: String,
+    pub name: String,
+    pub value: String,
+}
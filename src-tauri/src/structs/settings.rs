@@ -3,42 +3,252 @@ use db_macros::{Queryable, Updatable};
 use rusqlite::types::{FromSql, FromSqlError, ToSql, ToSqlOutput, ValueRef};
 use rusqlite::Result as RusqliteResult;
 use serde::{Deserialize, Serialize};
+use crate::secrets;
+use crate::structs::calendar_event::CalendarPrivacy;
+use crate::structs::reminder_frequency::ReminderFrequency;
 
-// ReminderFrequency enum for settings
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+const MAX_IDLE_PAUSE_MINUTES: i32 = 240;
+const MINUTES_PER_DAY: i32 = 24 * 60;
+// Ten times the estimate is too late to be a useful nudge
+const MAX_ESTIMATE_REACHED_PERCENT: i32 = 1000;
+// UTC-12:00 to UTC+14:00 covers every time zone in use
+const MIN_UTC_OFFSET_MINUTES: i32 = -12 * 60;
+const MAX_UTC_OFFSET_MINUTES: i32 = 14 * 60;
+
+// How the Markdown vault mirror splits tasks into files
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "kebab-case")]
-pub enum ReminderFrequency {
-    None,
-    Hourly,
-    #[serde(rename = "every-3-hours")]
-    Every3Hours,
-    Daily,
+pub enum VaultLayout {
+    Day,
+    Task,
 }
 
-impl ToSql for ReminderFrequency {
+impl ToSql for VaultLayout {
     fn to_sql(&self) -> RusqliteResult<ToSqlOutput<'_>> {
         let s = match self {
-            ReminderFrequency::None => "none",
-            ReminderFrequency::Hourly => "hourly",
-            ReminderFrequency::Every3Hours => "every-3-hours",
-            ReminderFrequency::Daily => "daily",
+            VaultLayout::Day => "day",
+            VaultLayout::Task => "task",
         };
         Ok(ToSqlOutput::from(s))
     }
 }
 
-impl FromSql for ReminderFrequency {
+impl FromSql for VaultLayout {
     fn column_result(value: ValueRef<'_>) -> Result<Self, FromSqlError> {
         String::column_result(value).and_then(|s| match s.as_str() {
-            "none" => Ok(ReminderFrequency::None),
-            "hourly" => Ok(ReminderFrequency::Hourly),
-            "every-3-hours" => Ok(ReminderFrequency::Every3Hours),
-            "daily" => Ok(ReminderFrequency::Daily),
+            "day" => Ok(VaultLayout::Day),
+            "task" => Ok(VaultLayout::Task),
             _ => Err(FromSqlError::InvalidType),
         })
     }
 }
 
+// Where sync exchanges changes with other devices
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum SyncBackendKind {
+    Folder,
+    #[serde(rename = "webdav")]
+    WebDav,
+}
+
+impl ToSql for SyncBackendKind {
+    fn to_sql(&self) -> RusqliteResult<ToSqlOutput<'_>> {
+        let s = match self {
+            SyncBackendKind::Folder => "folder",
+            SyncBackendKind::WebDav => "webdav",
+        };
+        Ok(ToSqlOutput::from(s))
+    }
+}
+
+impl FromSql for SyncBackendKind {
+    fn column_result(value: ValueRef<'_>) -> Result<Self, FromSqlError> {
+        String::column_result(value).and_then(|s| match s.as_str() {
+            "folder" => Ok(SyncBackendKind::Folder),
+            "webdav" => Ok(SyncBackendKind::WebDav),
+            _ => Err(FromSqlError::InvalidType),
+        })
+    }
+}
+
+// Optional steps run when a task is completed; deleting the calendar event is the default
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum CompletionEffect {
+    // Hide the task from day lists right away
+    Archive,
+    // Keep the calendar event, without reminders, instead of deleting it
+    KeepCalendarEvent,
+    // Append a line to the day's completion log in the vault folder
+    LogToDailyNote,
+    // POST the completed task to completion_webhook_url
+    Webhook,
+}
+
+impl CompletionEffect {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CompletionEffect::Archive => "archive",
+            CompletionEffect::KeepCalendarEvent => "keep-calendar-event",
+            CompletionEffect::LogToDailyNote => "log-to-daily-note",
+            CompletionEffect::Webhook => "webhook",
+        }
+    }
+}
+
+impl std::str::FromStr for CompletionEffect {
+    type Err = String;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "archive" => Ok(CompletionEffect::Archive),
+            "keep-calendar-event" => Ok(CompletionEffect::KeepCalendarEvent),
+            "log-to-daily-note" => Ok(CompletionEffect::LogToDailyNote),
+            "webhook" => Ok(CompletionEffect::Webhook),
+            _ => Err(format!("Invalid completion effect: {}", s)),
+        }
+    }
+}
+
+// Enabled completion effects, stored comma-separated
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CompletionEffects(Vec<CompletionEffect>);
+
+impl CompletionEffects {
+    pub fn contains(&self, effect: CompletionEffect) -> bool {
+        self.0.contains(&effect)
+    }
+    
+    pub fn iter(&self) -> impl Iterator<Item = CompletionEffect> + '_ {
+        self.0.iter().copied()
+    }
+}
+
+impl ToSql for CompletionEffects {
+    fn to_sql(&self) -> RusqliteResult<ToSqlOutput<'_>> {
+        let names: Vec<&str> = self.0.iter().map(CompletionEffect::as_str).collect();
+        Ok(ToSqlOutput::from(names.join(",")))
+    }
+}
+
+impl FromSql for CompletionEffects {
+    fn column_result(value: ValueRef<'_>) -> Result<Self, FromSqlError> {
+        String::column_result(value).and_then(|s| {
+            s.split(',')
+                .filter(|name| !name.is_empty())
+                .map(|name| name.parse().map_err(|_| FromSqlError::InvalidType))
+                .collect::<Result<Vec<_>, _>>()
+                .map(CompletionEffects)
+        })
+    }
+}
+
+// Conventions for dates, times and weeks in generated text
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Locale {
+    #[serde(rename = "en-US")]
+    EnUs,
+    #[serde(rename = "en-GB")]
+    EnGb,
+    #[serde(rename = "de-DE")]
+    De,
+    #[serde(rename = "fr-FR")]
+    Fr,
+    #[serde(rename = "es-ES")]
+    Es,
+}
+
+impl Locale {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Locale::EnUs => "en-US",
+            Locale::EnGb => "en-GB",
+            Locale::De => "de-DE",
+            Locale::Fr => "fr-FR",
+            Locale::Es => "es-ES",
+        }
+    }
+}
+
+impl std::str::FromStr for Locale {
+    type Err = String;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "en-US" => Ok(Locale::EnUs),
+            "en-GB" => Ok(Locale::EnGb),
+            "de-DE" => Ok(Locale::De),
+            "fr-FR" => Ok(Locale::Fr),
+            "es-ES" => Ok(Locale::Es),
+            _ => Err(format!("Unsupported locale: {}", s)),
+        }
+    }
+}
+
+impl ToSql for Locale {
+    fn to_sql(&self) -> RusqliteResult<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.as_str()))
+    }
+}
+
+impl FromSql for Locale {
+    fn column_result(value: ValueRef<'_>) -> Result<Self, FromSqlError> {
+        String::column_result(value).and_then(|s| s.parse().map_err(|_| FromSqlError::InvalidType))
+    }
+}
+
+// Bundled public holiday list; national holidays only
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum HolidayRegion {
+    Us,
+    Gb,
+    De,
+    Fr,
+    Es,
+}
+
+impl HolidayRegion {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HolidayRegion::Us => "us",
+            HolidayRegion::Gb => "gb",
+            HolidayRegion::De => "de",
+            HolidayRegion::Fr => "fr",
+            HolidayRegion::Es => "es",
+        }
+    }
+}
+
+impl std::str::FromStr for HolidayRegion {
+    type Err = String;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "us" => Ok(HolidayRegion::Us),
+            "gb" => Ok(HolidayRegion::Gb),
+            "de" => Ok(HolidayRegion::De),
+            "fr" => Ok(HolidayRegion::Fr),
+            "es" => Ok(HolidayRegion::Es),
+            _ => Err(format!("Unsupported holiday region: {}", s)),
+        }
+    }
+}
+
+impl ToSql for HolidayRegion {
+    fn to_sql(&self) -> RusqliteResult<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.as_str()))
+    }
+}
+
+impl FromSql for HolidayRegion {
+    fn column_result(value: ValueRef<'_>) -> Result<Self, FromSqlError> {
+        String::column_result(value).and_then(|s| s.parse().map_err(|_| FromSqlError::InvalidType))
+    }
+}
+
 // Settings struct
 #[derive(Debug, Clone, Serialize, Deserialize, Queryable)]
 #[serde(rename_all = "camelCase")]
@@ -51,6 +261,58 @@ pub struct Settings {
     pub calendar_email: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub quick_add_shortcut: String,
+    pub idle_pause_minutes: i32,
+    pub show_task_badge: bool,
+    pub http_api_enabled: bool,
+    pub http_api_port: i32,
+    pub vault_path: Option<String>,
+    pub vault_layout: VaultLayout,
+    pub slack_enabled: bool,
+    // Capacity the workload forecast plans against
+    pub work_day_minutes: i32,
+    // Count command and feature use locally; never sent anywhere
+    pub usage_metrics_enabled: bool,
+    // Local HH:MM the end-of-day summary is sent
+    pub daily_summary_time: String,
+    pub daily_summary_notification: bool,
+    // Folder another tool keeps in step across devices; None turns folder sync off
+    pub sync_folder: Option<String>,
+    // Names this device's change log in the sync folder
+    pub sync_device_id: String,
+    // Last time changes from other devices were merged
+    pub sync_merged_at: Option<DateTime<Utc>>,
+    pub sync_backend: SyncBackendKind,
+    // None turns WebDAV sync off
+    pub sync_webdav_url: Option<String>,
+    pub sync_webdav_username: Option<String>,
+    // Shown to other devices; defaults to the host name
+    pub sync_device_name: Option<String>,
+    // Who this person is to the people they share projects with; defaults to the device name
+    pub sync_identity: Option<String>,
+    // Advertise this device on the local network and sync directly with paired devices
+    pub lan_sync_enabled: bool,
+    // Where task days start for callers that don't send an offset (CLI, HTTP API, tray)
+    pub utc_offset_minutes: i32,
+    pub completion_effects: CompletionEffects,
+    // Target of the webhook completion effect
+    pub completion_webhook_url: Option<String>,
+    pub locale: Locale,
+    // Scheduling and date shifting move off these days
+    pub skip_weekends: bool,
+    pub skip_holidays: bool,
+    // Holidays from this list count along with imported ones
+    pub holiday_region: Option<HolidayRegion>,
+    // Post finished work sessions to Google Calendar as past events, once a day
+    pub calendar_work_sessions: bool,
+    // Share of its estimate a running task's tracked time reaches before "consider completing"; 0 turns it off
+    pub estimate_reached_percent: i32,
+    // Also show a notification, not just tell the UI
+    pub estimate_reached_notification: bool,
+    // What calendar events show for tasks without their own choice
+    pub calendar_privacy: CalendarPrivacy,
+    // Local HH:MM given to deadlines sent as a date only
+    pub default_deadline_time: String,
 }
 
 // DTO for updating settings from frontend
@@ -60,6 +322,48 @@ pub struct SettingsUpdateData {
     pub dark_mode: Option<bool>,
     pub notifications_enabled: Option<bool>,
     pub default_reminder_frequency: Option<String>,
+    pub quick_add_shortcut: Option<String>,
+    pub idle_pause_minutes: Option<i32>,
+    pub show_task_badge: Option<bool>,
+    pub http_api_enabled: Option<bool>,
+    pub http_api_port: Option<i32>,
+    // Empty string turns the vault mirror off
+    pub vault_path: Option<String>,
+    pub vault_layout: Option<String>,
+    pub slack_enabled: Option<bool>,
+    // Empty string clears the secret
+    pub slack_signing_secret: Option<String>,
+    pub work_day_minutes: Option<i32>,
+    pub usage_metrics_enabled: Option<bool>,
+    pub daily_summary_time: Option<String>,
+    pub daily_summary_notification: Option<bool>,
+    // Empty string turns folder sync off
+    pub sync_folder: Option<String>,
+    pub sync_backend: Option<String>,
+    // Empty strings clear these
+    pub sync_webdav_url: Option<String>,
+    pub sync_webdav_username: Option<String>,
+    pub sync_webdav_password: Option<String>,
+    pub sync_passphrase: Option<String>,
+    pub sync_device_name: Option<String>,
+    pub sync_identity: Option<String>,
+    pub lan_sync_enabled: Option<bool>,
+    pub utc_offset_minutes: Option<i32>,
+    // Replaces the whole list; empty turns every optional effect off
+    pub completion_effects: Option<Vec<String>>,
+    // Empty string clears the URL
+    pub completion_webhook_url: Option<String>,
+    pub locale: Option<String>,
+    pub skip_weekends: Option<bool>,
+    pub skip_holidays: Option<bool>,
+    // Empty string means imported holidays only
+    pub holiday_region: Option<String>,
+    pub calendar_work_sessions: Option<bool>,
+    pub estimate_reached_percent: Option<i32>,
+    pub estimate_reached_notification: Option<bool>,
+    // full, no-notes or busy
+    pub calendar_privacy: Option<String>,
+    pub default_deadline_time: Option<String>,
 }
 
 // Parsed update data with Updatable derive
@@ -69,28 +373,250 @@ pub struct SettingsUpdateParsed {
     pub dark_mode: Option<bool>,
     pub notifications_enabled: Option<bool>,
     pub default_reminder_frequency: Option<ReminderFrequency>,
+    pub quick_add_shortcut: Option<String>,
+    pub idle_pause_minutes: Option<i32>,
+    pub show_task_badge: Option<bool>,
+    pub http_api_enabled: Option<bool>,
+    pub http_api_port: Option<i32>,
+    pub vault_path: Option<Option<String>>,
+    pub vault_layout: Option<VaultLayout>,
+    pub slack_enabled: Option<bool>,
+    pub work_day_minutes: Option<i32>,
+    pub usage_metrics_enabled: Option<bool>,
+    pub daily_summary_time: Option<String>,
+    pub daily_summary_notification: Option<bool>,
+    pub sync_folder: Option<Option<String>>,
+    pub sync_backend: Option<SyncBackendKind>,
+    pub sync_webdav_url: Option<Option<String>>,
+    pub sync_webdav_username: Option<Option<String>>,
+    pub sync_device_name: Option<Option<String>>,
+    pub sync_identity: Option<Option<String>>,
+    pub lan_sync_enabled: Option<bool>,
+    pub utc_offset_minutes: Option<i32>,
+    pub completion_effects: Option<CompletionEffects>,
+    pub completion_webhook_url: Option<Option<String>>,
+    pub locale: Option<Locale>,
+    pub skip_weekends: Option<bool>,
+    pub skip_holidays: Option<bool>,
+    pub holiday_region: Option<Option<HolidayRegion>>,
+    pub calendar_work_sessions: Option<bool>,
+    pub estimate_reached_percent: Option<i32>,
+    pub estimate_reached_notification: Option<bool>,
+    pub calendar_privacy: Option<CalendarPrivacy>,
+    pub default_deadline_time: Option<String>,
+}
+
+// Secrets from a settings update; they go to the secrets store, not the settings row.
+// None leaves one as it is, Some(None) removes it
+#[derive(Debug)]
+pub struct SettingsSecretsUpdate {
+    pub slack_signing_secret: Option<Option<String>>,
+    pub sync_webdav_password: Option<Option<String>>,
+    pub sync_passphrase: Option<Option<String>>,
+}
+
+impl SettingsSecretsUpdate {
+    // Namespace, name and new value of each secret the update touches
+    pub fn changes(&self) -> Vec<(&'static str, &'static str, Option<&str>)> {
+        [
+            (secrets::SLACK, "signing_secret", &self.slack_signing_secret),
+            (secrets::SYNC, "webdav_password", &self.sync_webdav_password),
+            (secrets::SYNC, "passphrase", &self.sync_passphrase),
+        ]
+        .into_iter()
+        .filter_map(|(namespace, name, value)| value.as_ref().map(|value| (namespace, name, value.as_deref())))
+        .collect()
+    }
 }
 
 impl SettingsUpdateData {
-    pub fn parse(self) -> Result<SettingsUpdateParsed, String> {
-        let default_reminder_frequency = match self.default_reminder_frequency {
-            Some(freq_str) => {
-                let freq = match freq_str.as_str() {
-                    "none" => ReminderFrequency::None,
-                    "hourly" => ReminderFrequency::Hourly,
-                    "every-3-hours" => ReminderFrequency::Every3Hours,
-                    "daily" => ReminderFrequency::Daily,
-                    _ => return Err(format!("Invalid reminder frequency: {}", freq_str)),
-                };
-                Some(freq)
+    pub fn parse(self) -> Result<(SettingsUpdateParsed, SettingsSecretsUpdate), String> {
+        let default_reminder_frequency = self.default_reminder_frequency.as_deref()
+            .map(str::parse::<ReminderFrequency>)
+            .transpose()?;
+
+        // Reject shortcuts the OS hotkey parser would not accept
+        if let Some(ref shortcut) = self.quick_add_shortcut {
+            shortcut.parse::<tauri_plugin_global_shortcut::Shortcut>()
+                .map_err(|e| format!("Invalid quick add shortcut '{}': {}", shortcut, e))?;
+        }
+
+        // 0 turns idle auto-pause off
+        if let Some(minutes) = self.idle_pause_minutes {
+            if !(0..=MAX_IDLE_PAUSE_MINUTES).contains(&minutes) {
+                return Err(format!("Idle pause minutes must be between 0 and {}", MAX_IDLE_PAUSE_MINUTES));
+            }
+        }
+
+        // Privileged ports need elevated rights
+        if let Some(port) = self.http_api_port {
+            if !(1024..=65535).contains(&port) {
+                return Err("HTTP API port must be between 1024 and 65535".to_string());
+            }
+        }
+
+        if let Some(minutes) = self.work_day_minutes {
+            if !(1..=MINUTES_PER_DAY).contains(&minutes) {
+                return Err(format!("Work day minutes must be between 1 and {}", MINUTES_PER_DAY));
+            }
+        }
+
+        if let Some(percent) = self.estimate_reached_percent {
+            if !(0..=MAX_ESTIMATE_REACHED_PERCENT).contains(&percent) {
+                return Err(format!("Estimate threshold must be between 0 and {}%", MAX_ESTIMATE_REACHED_PERCENT));
+            }
+        }
+
+        if let Some(minutes) = self.utc_offset_minutes {
+            if !(MIN_UTC_OFFSET_MINUTES..=MAX_UTC_OFFSET_MINUTES).contains(&minutes) {
+                return Err(format!("UTC offset must be between {} and {} minutes", MIN_UTC_OFFSET_MINUTES, MAX_UTC_OFFSET_MINUTES));
+            }
+        }
+
+        // Stored as given, so it must already be HH:MM
+        if let Some(ref time) = self.daily_summary_time {
+            chrono::NaiveTime::parse_from_str(time, "%H:%M")
+                .map_err(|_| format!("Invalid daily summary time '{}': expected HH:MM", time))?;
+        }
+        if let Some(ref time) = self.default_deadline_time {
+            chrono::NaiveTime::parse_from_str(time, "%H:%M")
+                .map_err(|_| format!("Invalid default deadline time '{}': expected HH:MM", time))?;
+        }
+
+        let vault_path = self.vault_path.map(|path| {
+            let path = path.trim().to_string();
+            (!path.is_empty()).then_some(path)
+        });
+        if let Some(Some(ref path)) = vault_path {
+            if !std::path::Path::new(path).is_absolute() {
+                return Err(format!("Vault folder must be an absolute path: {}", path));
+            }
+        }
+
+        let sync_folder = self.sync_folder.map(|path| {
+            let path = path.trim().to_string();
+            (!path.is_empty()).then_some(path)
+        });
+        if let Some(Some(ref path)) = sync_folder {
+            if !std::path::Path::new(path).is_absolute() {
+                return Err(format!("Sync folder must be an absolute path: {}", path));
+            }
+        }
+
+        let sync_backend = match self.sync_backend.as_deref() {
+            Some("folder") => Some(SyncBackendKind::Folder),
+            Some("webdav") => Some(SyncBackendKind::WebDav),
+            Some(backend) => return Err(format!("Invalid sync backend: {}", backend)),
+            None => None,
+        };
+
+        let sync_webdav_url = self.sync_webdav_url.map(|url| {
+            let url = url.trim().trim_end_matches('/').to_string();
+            (!url.is_empty()).then_some(url)
+        });
+        // The password and passphrase would otherwise cross the network in the clear
+        if let Some(Some(ref url)) = sync_webdav_url {
+            if !url.starts_with("https://") {
+                return Err(format!("WebDAV address must start with https://: {}", url));
+            }
+        }
+
+        let vault_layout = match self.vault_layout.as_deref() {
+            Some("day") => Some(VaultLayout::Day),
+            Some("task") => Some(VaultLayout::Task),
+            Some(layout) => return Err(format!("Invalid vault layout: {}", layout)),
+            None => None,
+        };
+
+        let calendar_privacy = self.calendar_privacy.as_deref()
+            .map(str::parse::<CalendarPrivacy>)
+            .transpose()?;
+
+        let slack_signing_secret = self.slack_signing_secret.map(|secret| {
+            let secret = secret.trim().to_string();
+            (!secret.is_empty()).then_some(secret)
+        });
+
+        let completion_effects = match self.completion_effects {
+            Some(names) => {
+                let mut effects = Vec::new();
+                for name in names {
+                    let effect = name.parse::<CompletionEffect>()?;
+                    if !effects.contains(&effect) {
+                        effects.push(effect);
+                    }
+                }
+                Some(CompletionEffects(effects))
             }
             None => None,
         };
 
-        Ok(SettingsUpdateParsed {
+        // Usually a local automation tool, so plain http is allowed
+        let completion_webhook_url = self.completion_webhook_url.map(non_empty);
+        if let Some(Some(ref url)) = completion_webhook_url {
+            if !url.starts_with("https://") && !url.starts_with("http://") {
+                return Err(format!("Webhook URL must start with http:// or https://: {}", url));
+            }
+        }
+
+        let locale = self.locale.as_deref().map(str::parse::<Locale>).transpose()?;
+        let holiday_region = self.holiday_region.map(non_empty)
+            .map(|region| region.as_deref().map(str::parse::<HolidayRegion>).transpose())
+            .transpose()?;
+
+        let sync_webdav_username = self.sync_webdav_username.map(non_empty);
+        let sync_webdav_password = self.sync_webdav_password.map(non_empty);
+        let sync_passphrase = self.sync_passphrase.map(non_empty);
+        let sync_device_name = self.sync_device_name.map(non_empty);
+        let sync_identity = self.sync_identity.map(non_empty);
+
+        let secrets = SettingsSecretsUpdate {
+            slack_signing_secret,
+            sync_webdav_password,
+            sync_passphrase,
+        };
+
+        Ok((SettingsUpdateParsed {
             dark_mode: self.dark_mode,
             notifications_enabled: self.notifications_enabled,
             default_reminder_frequency,
-        })
+            quick_add_shortcut: self.quick_add_shortcut,
+            idle_pause_minutes: self.idle_pause_minutes,
+            show_task_badge: self.show_task_badge,
+            http_api_enabled: self.http_api_enabled,
+            http_api_port: self.http_api_port,
+            vault_path,
+            vault_layout,
+            slack_enabled: self.slack_enabled,
+            work_day_minutes: self.work_day_minutes,
+            usage_metrics_enabled: self.usage_metrics_enabled,
+            daily_summary_time: self.daily_summary_time,
+            daily_summary_notification: self.daily_summary_notification,
+            sync_folder,
+            sync_backend,
+            sync_webdav_url,
+            sync_webdav_username,
+            sync_device_name,
+            sync_identity,
+            lan_sync_enabled: self.lan_sync_enabled,
+            utc_offset_minutes: self.utc_offset_minutes,
+            completion_effects,
+            completion_webhook_url,
+            locale,
+            skip_weekends: self.skip_weekends,
+            skip_holidays: self.skip_holidays,
+            holiday_region,
+            calendar_work_sessions: self.calendar_work_sessions,
+            estimate_reached_percent: self.estimate_reached_percent,
+            estimate_reached_notification: self.estimate_reached_notification,
+            calendar_privacy,
+            default_deadline_time: self.default_deadline_time,
+        }, secrets))
     }
 }
+
+// Trimmed value, None when nothing is left
+fn non_empty(value: String) -> Option<String> {
+    let value = value.trim().to_string();
+    (!value.is_empty()).then_some(value)
+}
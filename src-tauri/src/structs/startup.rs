@@ -0,0 +1,31 @@
+use serde::Serialize;
+
+// Background startup work, in the order it runs once the window is up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StartupPhase {
+    CheckingDatabase,
+    Calendar,
+    Reconciling,
+    BackgroundJobs,
+    Ready,
+}
+
+impl StartupPhase {
+    pub const ALL: [StartupPhase; 5] = [
+        StartupPhase::CheckingDatabase,
+        StartupPhase::Calendar,
+        StartupPhase::Reconciling,
+        StartupPhase::BackgroundJobs,
+        StartupPhase::Ready,
+    ];
+}
+
+// Payload of the startup-phase event: `step` counts from 1 up to `total`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupProgress {
+    pub phase: StartupPhase,
+    pub step: usize,
+    pub total: usize,
+}
@@ -0,0 +1,327 @@
+use chrono::{DateTime, Utc};
+use db_macros::Queryable;
+use rusqlite::types::{ToSql, ToSqlOutput};
+use rusqlite::Result as RusqliteResult;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::structs::data_export::DateRangeData;
+use crate::structs::task_struct::{Task, TaskId};
+use crate::structs::work_session::{PauseReason, SessionNote};
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Granularity {
+    Day,
+    // Monday to Sunday
+    Week,
+    Month,
+}
+
+// Bound into the bucketing CASE in the stats queries
+impl ToSql for Granularity {
+    fn to_sql(&self) -> RusqliteResult<ToSqlOutput<'_>> {
+        let s = match self {
+            Granularity::Day => "day",
+            Granularity::Week => "week",
+            Granularity::Month => "month",
+        };
+        Ok(ToSqlOutput::from(s))
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ProductivityStatsQuery {
+    pub range: DateRangeData,
+    pub granularity: Granularity,
+}
+
+// One aggregated row per bucket that had any activity
+#[derive(Debug, Queryable)]
+pub struct ProductivityRow {
+    pub bucket: String,
+    pub created: i64,
+    pub completed: i64,
+    pub overdue: i64,
+    pub created_and_completed: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProductivityBucket {
+    // First day of the bucket, YYYY-MM-DD
+    pub start: String,
+    pub created: i64,
+    pub completed: i64,
+    // Deadlines in the bucket that passed before the task was completed
+    pub overdue: i64,
+    // Share of the bucket's new tasks that are completed; None when none were created
+    pub completion_rate: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TimeGroupBy {
+    Task,
+    Project,
+    Tag,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeBreakdownQuery {
+    pub range: DateRangeData,
+    pub group_by: TimeGroupBy,
+}
+
+#[derive(Debug, Queryable)]
+pub struct TaskTimeRow {
+    pub task_id: Uuid,
+    pub title: String,
+    pub minutes: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeBreakdownEntry {
+    pub key: String,
+    pub label: String,
+    pub minutes: i64,
+    // Share of the total, 0-100
+    pub percentage: f64,
+    // Left on the task's sessions that ended in the range
+    pub notes: Vec<SessionNote>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeBreakdown {
+    pub total_minutes: i64,
+    pub entries: Vec<TimeBreakdownEntry>,
+}
+
+#[derive(Deserialize)]
+pub struct PauseReasonQuery {
+    pub range: DateRangeData,
+}
+
+#[derive(Debug, Queryable)]
+pub struct PauseReasonRow {
+    pub reason: PauseReason,
+    pub pauses: i64,
+    pub minutes_to_resume: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PauseReasonEntry {
+    pub reason: PauseReason,
+    pub pauses: i64,
+    // Share of the pauses with a reason, 0-100
+    pub percentage: f64,
+    // Average wait until the task was worked on again; None when none of them were yet
+    pub minutes_to_resume: Option<i64>,
+}
+
+// Pauses in the range by what caused them, most common first; pauses without a reason aren't counted
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PauseReasonStats {
+    pub total_pauses: i64,
+    pub reasons: Vec<PauseReasonEntry>,
+}
+
+#[derive(Deserialize)]
+pub struct HeatmapQuery {
+    pub year: i32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HeatmapDay {
+    // YYYY-MM-DD
+    pub date: String,
+    pub count: i64,
+}
+
+// Every day of the year in order, including days with nothing completed
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompletionHeatmap {
+    pub year: i32,
+    pub total: i64,
+    // Busiest day's count, for scaling the colors
+    pub max_count: i64,
+    pub days: Vec<HeatmapDay>,
+}
+
+#[derive(Deserialize)]
+pub struct CycleTimeQuery {
+    pub range: DateRangeData,
+}
+
+// Per task created in the range; a side is None until the task reaches that step
+#[derive(Debug, Queryable)]
+pub struct CycleTimeRow {
+    pub wait_minutes: Option<f64>,
+    pub work_minutes: Option<f64>,
+}
+
+// Row of day_summaries, kept current by triggers on tasks
+#[derive(Debug, Queryable)]
+pub struct DaySummary {
+    // YYYY-MM-DD in the settings offset
+    pub day: String,
+    pub created: i64,
+    pub created_and_completed: i64,
+    pub completed: i64,
+    pub tracked_minutes: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DurationStats {
+    pub count: usize,
+    pub average_minutes: Option<f64>,
+    pub median_minutes: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CycleTimeStats {
+    pub created_to_start: DurationStats,
+    pub start_to_completion: DurationStats,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeeklyReviewQuery {
+    // Any moment in the week; weeks start on the locale's first day of the week
+    pub week: String,
+    #[serde(default)]
+    pub utc_offset_minutes: Option<i32>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeeklyReview {
+    // Heading for the week in the locale from settings
+    pub label: String,
+    // YYYY-MM-DD
+    pub week_start: String,
+    pub week_end: String,
+    pub completed: Vec<Task>,
+    // Deadlines in the week that passed before the task was completed
+    pub slipped_deadlines: Vec<Task>,
+    // Edited during the week but still open
+    pub touched_unfinished: Vec<Task>,
+    // Start to completion of the week's completed tasks
+    pub tracked_minutes: i64,
+    // Open slipped and touched tasks, soonest deadline first
+    pub carry_overs: Vec<Task>,
+}
+
+#[derive(Debug, Serialize, Queryable)]
+#[serde(rename_all = "camelCase")]
+pub struct PostponedTask {
+    pub id: Uuid,
+    pub title: String,
+    pub deadline: Option<DateTime<Utc>>,
+    pub deadline_moves: i64,
+    // Days the deadline was pushed back, summed over every move
+    pub postponed_days: f64,
+}
+
+#[derive(Debug, Queryable)]
+pub struct PostponementTotals {
+    pub postponed_tasks: i64,
+    pub moves: i64,
+    pub postponed_days: f64,
+    pub overdue_open: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcrastinationStats {
+    // Tasks whose deadline moved at least once
+    pub postponed_tasks: i64,
+    pub total_moves: i64,
+    // Days gained per move; None when no deadline has moved
+    pub average_postponement_days: Option<f64>,
+    // Open tasks already past their deadline
+    pub overdue_open: i64,
+    pub most_postponed: Vec<PostponedTask>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeadlineSuggestion {
+    pub task_id: TaskId,
+    pub current_deadline: DateTime<Utc>,
+    // Same time of day as the current deadline, on a working day
+    pub suggested_deadline: DateTime<Utc>,
+    // Work the task likely still needs at the usual pace, or its estimate if that's more;
+    // None with neither completed tasks nor an estimate to go by
+    pub remaining_minutes: Option<i64>,
+    // Days a move of this task, or of tasks in general, has usually fallen short by
+    pub slip_days: f64,
+    pub deadline_moves: i64,
+    // Recently completed tasks the usual pace comes from
+    pub sample_size: usize,
+}
+
+#[derive(Deserialize)]
+pub struct ConsistencyQuery {
+    pub range: DateRangeData,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoreComponent {
+    // Raw input: streak days, tasks done on time, or tracked minutes
+    pub value: f64,
+    // 0-1; None when there is nothing to measure, and the weight goes to the other components
+    pub score: Option<f64>,
+    pub weight: f64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsistencyScore {
+    // 0-100
+    pub score: f64,
+    // Consecutive days with a completion, up to the end of the range
+    pub current_streak: i64,
+    pub longest_streak: i64,
+    pub streak: ScoreComponent,
+    pub on_time: ScoreComponent,
+    pub focus: ScoreComponent,
+}
+
+#[derive(Deserialize)]
+pub struct WorkloadForecastQuery {
+    // Days ahead, today included
+    pub days: u32,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkloadDay {
+    // YYYY-MM-DD
+    pub date: String,
+    // False for weekends and holidays skipped per settings; those have no capacity
+    pub working: bool,
+    // Estimated minutes of open tasks due that day; overdue tasks count on today
+    pub planned_minutes: i64,
+    pub capacity_minutes: i64,
+    pub task_ids: Vec<Uuid>,
+    // Due that day without an estimate, so not in planned_minutes
+    pub unestimated: usize,
+    pub overloaded: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkloadForecast {
+    pub days: Vec<WorkloadDay>,
+    pub overloaded_days: usize,
+}
@@ -0,0 +1,238 @@
+use chrono::{DateTime, Utc};
+use db_macros::Queryable;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::structs::settings::SyncBackendKind;
+use crate::structs::task_struct::Task;
+
+// One line of a device's change log
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeRecord {
+    // Increases with every change a device writes
+    pub seq: i64,
+    pub device_id: String,
+    pub recorded_at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub change: Change,
+}
+
+// This device's changes since its last push, sent as one unit
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Changeset {
+    pub device_id: String,
+    pub records: Vec<ChangeRecord>,
+}
+
+// A changeset as stored by a backend: JSON, encrypted when a passphrase is set
+pub struct SealedChangeset {
+    // Seq of its last record; backends return changesets in this order
+    pub seq: i64,
+    pub data: Vec<u8>,
+}
+
+// Written by each device when it syncs, so others can show its name
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceInfo {
+    pub device_id: String,
+    pub name: String,
+    pub last_seen_at: DateTime<Utc>,
+    // Person the device belongs to; sent at shared project locations
+    #[serde(default)]
+    pub identity: Option<String>,
+}
+
+// Stored unencrypted next to the changes; the check value proves a passphrase is right
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncKeyFile {
+    pub salt: String,
+    pub check: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct WebDavAccount {
+    // Folder on the server, without a trailing slash
+    pub url: String,
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum Change {
+    // Full copy of the task after the change
+    Upsert { task: Box<Task> },
+    #[serde(rename_all = "camelCase")]
+    Delete { task_id: Uuid },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictWinner {
+    Local,
+    Remote,
+}
+
+impl ConflictWinner {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConflictWinner::Local => "local",
+            ConflictWinner::Remote => "remote",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Queryable)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncConflict {
+    pub id: i64,
+    pub task_id: Uuid,
+    pub title: String,
+    pub remote_device: String,
+    pub local_updated_at: DateTime<Utc>,
+    pub remote_updated_at: DateTime<Utc>,
+    // "local" or "remote": the version applied until the conflict is resolved
+    pub winner: String,
+    pub detected_at: DateTime<Utc>,
+    // Task JSON; returned parsed by list_conflicts
+    #[serde(skip)]
+    pub local_version: Option<String>,
+    #[serde(skip)]
+    pub remote_version: Option<String>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    // "local", "remote" or "superseded"
+    pub kept: Option<String>,
+}
+
+// A conflict with both versions of the task
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncConflictDetail {
+    #[serde(flatten)]
+    pub conflict: SyncConflict,
+    pub local: Option<Task>,
+    pub remote: Option<Task>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConflictResolution {
+    pub id: i64,
+    pub keep: ConflictWinner,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncReport {
+    // Changes from other devices written to this database
+    pub applied: usize,
+    // Changes skipped because this device's copy was newer
+    pub skipped: usize,
+    // Tasks renamed differently on both sides, added to the conflict inbox
+    pub conflicts: usize,
+}
+
+// Another device this one has merged changes from
+#[derive(Debug, Serialize, Queryable)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncDevice {
+    pub device_id: String,
+    pub name: Option<String>,
+    pub last_seq: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncStatus {
+    pub enabled: bool,
+    pub backend: SyncBackendKind,
+    pub device_id: String,
+    pub device_name: String,
+    pub encrypted: bool,
+    pub last_synced_at: Option<DateTime<Utc>>,
+    // Local changes not yet pushed
+    pub pending_changes: i64,
+    // Error from the last sync attempt, cleared by a successful one
+    pub last_error: Option<String>,
+    pub devices: Vec<SyncDevice>,
+}
+
+// A device paired for direct sync over the local network
+#[derive(Debug, Queryable)]
+pub struct LanPeer {
+    pub device_id: String,
+    pub name: String,
+    // Hex; shared only between the two devices
+    pub secret: String,
+    pub paired_at: DateTime<Utc>,
+    pub last_synced_at: Option<DateTime<Utc>>,
+}
+
+// A device seen on the local network or paired before
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LanDevice {
+    pub device_id: String,
+    pub name: String,
+    pub paired: bool,
+    // Currently advertising on the local network
+    pub online: bool,
+    pub paired_at: Option<DateTime<Utc>>,
+    pub last_synced_at: Option<DateTime<Utc>>,
+}
+
+// Shown on the device being paired with, and typed in on the other one
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LanPairingCode {
+    pub code: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+// Body of every LAN sync request and response; `sealed` is hex
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LanEnvelope {
+    pub device_id: String,
+    pub sealed: String,
+}
+
+// Sealed with a key derived from the pairing code
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LanPairRequest {
+    pub device_id: String,
+    pub name: String,
+    // Hex; becomes the pair's shared secret
+    pub secret: String,
+    pub sent_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LanPairResponse {
+    pub device_id: String,
+    pub name: String,
+    pub sent_at: DateTime<Utc>,
+}
+
+// Sealed with the shared secret; the timestamp keeps an old request from being replayed
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LanSnapshotRequest {
+    pub device_id: String,
+    pub sent_at: DateTime<Utc>,
+}
+
+// Every task and deletion a device knows of
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LanSnapshot {
+    pub device_id: String,
+    pub name: String,
+    pub records: Vec<ChangeRecord>,
+    pub sent_at: DateTime<Utc>,
+}
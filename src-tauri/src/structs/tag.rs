@@ -0,0 +1,33 @@
+use db_macros::Queryable;
+use serde::{Deserialize, Serialize};
+use crate::structs::task_struct::TaskId;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagPrefix {
+    // With or without the '#'
+    pub prefix: String,
+}
+
+#[derive(Debug, Serialize, Queryable)]
+#[serde(rename_all = "camelCase")]
+pub struct TagSuggestion {
+    pub tag: String,
+    pub task_count: i64,
+}
+
+#[derive(Debug, Serialize, Queryable)]
+#[serde(rename_all = "camelCase")]
+pub struct TagStats {
+    pub tag: String,
+    pub open_count: i64,
+    pub completed_count: i64,
+}
+
+// A task whose tags need reading again
+#[derive(Debug, Queryable)]
+pub struct StaleTagTask {
+    pub task_id: TaskId,
+    pub title: String,
+    pub notes: Option<String>,
+}
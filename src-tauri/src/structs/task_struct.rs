@@ -1,72 +1,79 @@
 use db_macros::{Insertable, Queryable};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Serialize, Deserialize};
 use uuid::{Uuid, Timestamp};
-use rusqlite::types::{ToSql, ToSqlOutput, FromSql, FromSqlResult, ValueRef};
+use std::fmt;
+use std::str::FromStr;
+use rusqlite::types::{ToSql, ToSqlOutput, FromSql, FromSqlError, FromSqlResult, ValueRef};
 
 use crate::db::Insertable;
+use crate::helpers::like::escape_like;
+use crate::structs::calendar_event::CalendarPrivacy;
+use crate::structs::reminder_frequency::ReminderFrequency;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub enum ReminderFrequency {
-    #[serde(rename = "none")]
-    None,
-    #[serde(rename = "hourly")]
-    Hourly,
-    #[serde(rename = "every-3-hours")]
-    Every3Hours,
-    #[serde(rename = "daily")]
-    Daily,
+// Task UUID, parsed once where it enters the app (command payloads, URLs, CLI arguments)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct TaskId(Uuid);
+
+impl TaskId {
+    pub fn uuid(&self) -> Uuid {
+        self.0
+    }
 }
 
-impl Default for ReminderFrequency {
-    fn default() -> Self {
-        ReminderFrequency::None
+impl From<Uuid> for TaskId {
+    fn from(uuid: Uuid) -> Self {
+        TaskId(uuid)
     }
 }
 
-impl From<ReminderFrequency> for String {
-    fn from(freq: ReminderFrequency) -> Self {
-        match freq {
-            ReminderFrequency::None => "none".to_string(),
-            ReminderFrequency::Hourly => "hourly".to_string(),
-            ReminderFrequency::Every3Hours => "every-3-hours".to_string(),
-            ReminderFrequency::Daily => "daily".to_string(),
-        }
+impl FromStr for TaskId {
+    type Err = String;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Uuid::parse_str(s.trim())
+            .map(TaskId)
+            .map_err(|e| format!("Invalid task ID '{}': {}", s, e))
     }
 }
 
-impl From<&str> for ReminderFrequency {
-    fn from(s: &str) -> Self {
-        match s {
-            "hourly" => ReminderFrequency::Hourly,
-            "every-3-hours" => ReminderFrequency::Every3Hours,
-            "daily" => ReminderFrequency::Daily,
-            _ => ReminderFrequency::None,
-        }
+impl TryFrom<String> for TaskId {
+    type Error = String;
+    
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
     }
 }
 
-impl From<String> for ReminderFrequency {
-    fn from(s: String) -> Self {
-        ReminderFrequency::from(s.as_str())
+impl From<TaskId> for String {
+    fn from(id: TaskId) -> Self {
+        id.0.to_string()
     }
 }
 
-impl ToSql for ReminderFrequency {
+impl fmt::Display for TaskId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+// Stored like the tasks.id column it refers to
+impl ToSql for TaskId {
     fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
-        let s = String::from(self.clone());
-        Ok(ToSqlOutput::from(s))
+        self.0.to_sql()
     }
 }
 
-impl FromSql for ReminderFrequency {
+impl FromSql for TaskId {
     fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
-        value.as_str().map(ReminderFrequency::from)
+        Uuid::column_result(value).map(TaskId)
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub enum Status {
+    #[default]
     #[serde(rename = "not-started")]
     NotStarted,
     #[serde(rename = "ongoing")]
@@ -77,12 +84,6 @@ pub enum Status {
     Completed,
 }
 
-impl Default for Status {
-    fn default() -> Self {
-        Status::NotStarted
-    }
-}
-
 impl From<Status> for String {
     fn from(status: Status) -> Self {
         match status {
@@ -125,7 +126,57 @@ impl FromSql for Status {
     }
 }
 
-#[derive(Debug, Insertable, Queryable, Serialize, Deserialize)]
+// How much focus a task takes; ordered from least to most
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum Energy {
+    Low,
+    Medium,
+    High,
+}
+
+impl Energy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Energy::Low => "low",
+            Energy::Medium => "medium",
+            Energy::High => "high",
+        }
+    }
+}
+
+impl FromStr for Energy {
+    type Err = String;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "low" => Ok(Energy::Low),
+            "medium" => Ok(Energy::Medium),
+            "high" => Ok(Energy::High),
+            _ => Err(format!("Invalid energy '{}': expected low, medium or high", s)),
+        }
+    }
+}
+
+impl ToSql for Energy {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.as_str()))
+    }
+}
+
+impl FromSql for Energy {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        value.as_str().and_then(|s| s.parse().map_err(|_| FromSqlError::InvalidType))
+    }
+}
+
+// Context label as stored: lowercase with a leading @, e.g. "@phone"; None when nothing is left
+pub fn normalize_context(context: &str) -> Option<String> {
+    let name = context.trim().trim_start_matches('@').trim();
+    (!name.is_empty()).then(|| format!("@{}", name.to_lowercase()))
+}
+
+#[derive(Debug, Clone, Insertable, Queryable, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[table_name = "tasks"]
 pub struct Task {
@@ -142,10 +193,28 @@ pub struct Task {
     pub started_at: Option<DateTime<Utc>>,
     pub paused_at: Option<DateTime<Utc>>,
     pub completed_at: Option<DateTime<Utc>>,
+    pub notifications_enabled: bool,
+    pub estimate_minutes: Option<i32>,
+    // Shared project the task belongs to, if any
+    #[serde(default)]
+    pub project_id: Option<Uuid>,
+    // Identity of the member it's assigned to
+    #[serde(default)]
+    pub assignee: Option<String>,
+    // Where the task can be done, e.g. "@computer" or "@errand"
+    #[serde(default)]
+    pub context: Option<String>,
+    #[serde(default)]
+    pub energy: Option<Energy>,
+    // What its calendar event shows; None follows settings
+    #[serde(default)]
+    pub calendar_privacy: Option<CalendarPrivacy>,
 }
 
 impl Task {
-    pub fn new(title: &str, created_at: DateTime<Utc>, notes: Option<&str>) -> Self {
+    // Every new task starts here; callers pass the frequency from settings_service::reminder_frequency,
+    // so none of them can leave out the default in settings
+    pub fn new(title: &str, created_at: DateTime<Utc>, notes: Option<&str>, reminder_frequency: ReminderFrequency) -> Self {
         Self {
             id: Uuid::new_v7(Timestamp::now(uuid::timestamp::context::NoContext)),
             title: title.to_string(),
@@ -156,10 +225,73 @@ impl Task {
             deadline: None,
             has_calendar_integration: false,
             calendar_email: None,
-            reminder_frequency: ReminderFrequency::default(),
+            reminder_frequency,
             started_at: None,
             paused_at: None,
             completed_at: None,
+            notifications_enabled: true,
+            estimate_minutes: None,
+            project_id: None,
+            assignee: None,
+            context: None,
+            energy: None,
+            calendar_privacy: None,
+        }
+    }
+    
+    // Start to completion, pauses included; None until the task is started and completed
+    pub fn time_spent(&self) -> Option<Duration> {
+        match (self.started_at, self.completed_at) {
+            (Some(started), Some(completed)) if completed > started => Some(completed - started),
+            _ => None,
         }
     }
 }
+
+// Task as listed in day views: `task.notes` is left empty and only the start
+// of the notes comes along; get_task_notes loads the full text when it's opened
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskListItem {
+    #[serde(flatten)]
+    pub task: Task,
+    pub notes_preview: Option<String>,
+    // Notes go on past the preview
+    pub notes_truncated: bool,
+}
+
+impl TaskListItem {
+    // Task columns, then the preview and the truncated flag
+    pub fn from_row(row: &rusqlite::Row) -> rusqlite::Result<Self> {
+        Ok(TaskListItem {
+            task: Task::from_row(row)?,
+            notes_preview: row.get(20)?,
+            notes_truncated: row.get(21)?,
+        })
+    }
+}
+
+// Which tasks a virtualized list shows; a part left out matches every task
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskFilter {
+    // Only archived tasks, or only tasks that aren't
+    #[serde(default)]
+    pub archived: Option<bool>,
+    #[serde(default)]
+    pub status: Option<Status>,
+    #[serde(default)]
+    pub project_id: Option<Uuid>,
+    // Text the title contains, in any case
+    #[serde(default)]
+    pub search: Option<String>,
+}
+
+impl TaskFilter {
+    // LIKE pattern for the search text, its wildcards escaped; None when there's nothing to search for
+    pub fn title_pattern(&self) -> Option<String> {
+        let search = self.search.as_deref().map(str::trim).filter(|search| !search.is_empty())?;
+        Some(format!("%{}%", escape_like(search)))
+    }
+}
+
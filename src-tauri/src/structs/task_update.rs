@@ -1,6 +1,10 @@
 use serde::Deserialize;
 use chrono::{DateTime, Utc};
 use db_macros::Updatable;
+use uuid::Uuid;
+use crate::structs::calendar_event::CalendarPrivacy;
+use crate::structs::reminder_frequency::ReminderFrequency;
+use crate::structs::task_struct::{Energy, TaskId};
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -11,12 +15,25 @@ pub struct TaskUpdateData {
     pub has_calendar_integration: Option<bool>,
     pub calendar_email: Option<String>,
     pub reminder_frequency: Option<String>,
+    pub notifications_enabled: Option<bool>,
+    // 0 clears the estimate
+    pub estimate_minutes: Option<i32>,
+    // Empty takes the task out of its project
+    pub project_id: Option<String>,
+    // Member identity; empty unassigns
+    pub assignee: Option<String>,
+    // "@phone" or "phone"; empty clears it
+    pub context: Option<String>,
+    // low, medium or high; empty clears it
+    pub energy: Option<String>,
+    // full, no-notes or busy; empty follows settings again
+    pub calendar_privacy: Option<String>,
 }
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TaskUpdate {
-    pub id: String,
+    pub id: TaskId,
     pub data: TaskUpdateData,
 }
 
@@ -29,6 +46,13 @@ pub struct TaskUpdateParsed {
     pub deadline: Option<Option<DateTime<Utc>>>,
     pub has_calendar_integration: Option<bool>,
     pub calendar_email: Option<Option<String>>,
-    pub reminder_frequency: Option<String>,
+    pub reminder_frequency: Option<ReminderFrequency>,
+    pub notifications_enabled: Option<bool>,
+    pub estimate_minutes: Option<Option<i32>>,
+    pub project_id: Option<Option<Uuid>>,
+    pub assignee: Option<Option<String>>,
+    pub context: Option<Option<String>>,
+    pub energy: Option<Option<Energy>>,
+    pub calendar_privacy: Option<Option<CalendarPrivacy>>,
     pub updated_at: DateTime<Utc>,
 }
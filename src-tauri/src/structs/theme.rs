@@ -0,0 +1,115 @@
+use chrono::{DateTime, Utc};
+use db_macros::{Queryable, Updatable};
+use rusqlite::types::{FromSql, FromSqlError, ToSql, ToSqlOutput, ValueRef};
+use rusqlite::Result as RusqliteResult;
+use serde::{Deserialize, Serialize};
+
+const MIN_FONT_SCALE: f64 = 0.75;
+const MAX_FONT_SCALE: f64 = 1.5;
+
+// Density enum for theme settings
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Density {
+    Compact,
+    Comfortable,
+    Spacious,
+}
+
+impl ToSql for Density {
+    fn to_sql(&self) -> RusqliteResult<ToSqlOutput<'_>> {
+        let s = match self {
+            Density::Compact => "compact",
+            Density::Comfortable => "comfortable",
+            Density::Spacious => "spacious",
+        };
+        Ok(ToSqlOutput::from(s))
+    }
+}
+
+impl FromSql for Density {
+    fn column_result(value: ValueRef<'_>) -> Result<Self, FromSqlError> {
+        String::column_result(value).and_then(|s| match s.as_str() {
+            "compact" => Ok(Density::Compact),
+            "comfortable" => Ok(Density::Comfortable),
+            "spacious" => Ok(Density::Spacious),
+            _ => Err(FromSqlError::InvalidType),
+        })
+    }
+}
+
+// Theme struct
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable)]
+#[serde(rename_all = "camelCase")]
+pub struct Theme {
+    pub id: i32,
+    pub accent_color: String,
+    pub font_scale: f64,
+    pub density: Density,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// DTO for updating theme from frontend
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThemeUpdateData {
+    pub accent_color: Option<String>,
+    pub font_scale: Option<f64>,
+    pub density: Option<String>,
+}
+
+// Parsed update data with Updatable derive
+#[derive(Debug, Updatable)]
+#[table_name = "theme_settings"]
+pub struct ThemeUpdateParsed {
+    pub accent_color: Option<String>,
+    pub font_scale: Option<f64>,
+    pub density: Option<Density>,
+}
+
+impl ThemeUpdateData {
+    pub fn parse(self) -> Result<ThemeUpdateParsed, String> {
+        let accent_color = match self.accent_color {
+            Some(color) => {
+                // Accept only #RRGGBB so every window renders the same value
+                let is_hex = color.len() == 7
+                    && color.starts_with('#')
+                    && color[1..].chars().all(|c| c.is_ascii_hexdigit());
+                if !is_hex {
+                    return Err(format!("Invalid accent color: {}", color));
+                }
+                Some(color.to_lowercase())
+            }
+            None => None,
+        };
+
+        if let Some(scale) = self.font_scale {
+            if !(MIN_FONT_SCALE..=MAX_FONT_SCALE).contains(&scale) {
+                return Err(format!(
+                    "Invalid font scale: {} (must be between {} and {})",
+                    scale, MIN_FONT_SCALE, MAX_FONT_SCALE
+                ));
+            }
+        }
+
+        let density = match self.density {
+            Some(density_str) => {
+                let density = match density_str.as_str() {
+                    "compact" => Density::Compact,
+                    "comfortable" => Density::Comfortable,
+                    "spacious" => Density::Spacious,
+                    _ => return Err(format!("Invalid density: {}", density_str)),
+                };
+                Some(density)
+            }
+            None => None,
+        };
+
+        Ok(ThemeUpdateParsed {
+            accent_color,
+            font_scale: self.font_scale,
+            density,
+        })
+    }
+}
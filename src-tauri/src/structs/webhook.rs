@@ -0,0 +1,172 @@
+use chrono::{DateTime, Utc};
+use db_macros::Queryable;
+use rusqlite::types::{FromSql, FromSqlError, ToSql, ToSqlOutput, ValueRef};
+use rusqlite::Result as RusqliteResult;
+use serde::{Deserialize, Serialize};
+
+// Task lifecycle events a webhook can subscribe to
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum WebhookEvent {
+    #[serde(rename = "task.created")]
+    Created,
+    #[serde(rename = "task.completed")]
+    Completed,
+    #[serde(rename = "task.deleted")]
+    Deleted,
+}
+
+impl WebhookEvent {
+    pub const ALL: [WebhookEvent; 3] = [WebhookEvent::Created, WebhookEvent::Completed, WebhookEvent::Deleted];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            WebhookEvent::Created => "task.created",
+            WebhookEvent::Completed => "task.completed",
+            WebhookEvent::Deleted => "task.deleted",
+        }
+    }
+}
+
+impl std::str::FromStr for WebhookEvent {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        WebhookEvent::ALL.iter()
+            .copied()
+            .find(|event| event.name() == s)
+            .ok_or_else(|| format!("Invalid webhook event: {}", s))
+    }
+}
+
+impl ToSql for WebhookEvent {
+    fn to_sql(&self) -> RusqliteResult<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.name()))
+    }
+}
+
+impl FromSql for WebhookEvent {
+    fn column_result(value: ValueRef<'_>) -> Result<Self, FromSqlError> {
+        String::column_result(value).and_then(|s| s.parse().map_err(|_| FromSqlError::InvalidType))
+    }
+}
+
+// Events a webhook receives, stored comma-separated
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct WebhookEvents(Vec<WebhookEvent>);
+
+impl WebhookEvents {
+    pub fn new(events: Vec<WebhookEvent>) -> Self {
+        WebhookEvents(events)
+    }
+
+    pub fn contains(&self, event: WebhookEvent) -> bool {
+        self.0.contains(&event)
+    }
+}
+
+impl ToSql for WebhookEvents {
+    fn to_sql(&self) -> RusqliteResult<ToSqlOutput<'_>> {
+        let names: Vec<&str> = self.0.iter().map(WebhookEvent::name).collect();
+        Ok(ToSqlOutput::from(names.join(",")))
+    }
+}
+
+impl FromSql for WebhookEvents {
+    fn column_result(value: ValueRef<'_>) -> Result<Self, FromSqlError> {
+        String::column_result(value).and_then(|s| {
+            s.split(',')
+                .filter(|name| !name.is_empty())
+                .map(|name| name.parse().map_err(|_| FromSqlError::InvalidType))
+                .collect::<Result<Vec<_>, _>>()
+                .map(WebhookEvents)
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Queryable)]
+#[serde(rename_all = "camelCase")]
+pub struct Webhook {
+    pub id: i64,
+    pub url: String,
+    // Signs each delivery (HMAC-SHA256) so the receiver can check it came from here
+    pub secret: String,
+    pub events: WebhookEvents,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum DeliveryStatus {
+    Pending,
+    Delivered,
+    // Out of retries
+    Failed,
+}
+
+impl ToSql for DeliveryStatus {
+    fn to_sql(&self) -> RusqliteResult<ToSqlOutput<'_>> {
+        let s = match self {
+            DeliveryStatus::Pending => "pending",
+            DeliveryStatus::Delivered => "delivered",
+            DeliveryStatus::Failed => "failed",
+        };
+        Ok(ToSqlOutput::from(s))
+    }
+}
+
+impl FromSql for DeliveryStatus {
+    fn column_result(value: ValueRef<'_>) -> Result<Self, FromSqlError> {
+        String::column_result(value).and_then(|s| match s.as_str() {
+            "pending" => Ok(DeliveryStatus::Pending),
+            "delivered" => Ok(DeliveryStatus::Delivered),
+            "failed" => Ok(DeliveryStatus::Failed),
+            _ => Err(FromSqlError::InvalidType),
+        })
+    }
+}
+
+// One event sent, or still to be sent, to one webhook
+#[derive(Debug, Clone, Serialize, Queryable)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub webhook_id: i64,
+    pub event: WebhookEvent,
+    // The JSON body, exactly as signed and sent
+    pub payload: String,
+    pub status: DeliveryStatus,
+    pub attempts: i32,
+    // HTTP status of the last attempt; None when the receiver couldn't be reached
+    pub response_status: Option<i32>,
+    pub last_error: Option<String>,
+    pub next_attempt_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookData {
+    pub url: String,
+    // Empty subscribes to every event
+    #[serde(default)]
+    pub events: Vec<String>,
+    // Generated when left out
+    pub secret: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookIdData {
+    pub id: i64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookDeliveryQuery {
+    // All webhooks when left out
+    pub webhook_id: Option<i64>,
+    pub limit: Option<i64>,
+}
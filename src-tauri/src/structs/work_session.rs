@@ -0,0 +1,67 @@
+use chrono::{DateTime, Utc};
+use db_macros::Queryable;
+use rusqlite::types::{FromSql, FromSqlError, ToSql, ToSqlOutput, ValueRef};
+use rusqlite::Result as RusqliteResult;
+use serde::{Deserialize, Serialize};
+use crate::structs::task_struct::TaskId;
+
+// Why a task was paused, kept on the session the pause ended
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PauseReason {
+    Interrupted,
+    Blocked,
+    Meeting,
+    Break,
+}
+
+impl ToSql for PauseReason {
+    fn to_sql(&self) -> RusqliteResult<ToSqlOutput<'_>> {
+        let s = match self {
+            PauseReason::Interrupted => "interrupted",
+            PauseReason::Blocked => "blocked",
+            PauseReason::Meeting => "meeting",
+            PauseReason::Break => "break",
+        };
+        Ok(ToSqlOutput::from(s))
+    }
+}
+
+impl FromSql for PauseReason {
+    fn column_result(value: ValueRef<'_>) -> Result<Self, FromSqlError> {
+        String::column_result(value).and_then(|s| match s.as_str() {
+            "interrupted" => Ok(PauseReason::Interrupted),
+            "blocked" => Ok(PauseReason::Blocked),
+            "meeting" => Ok(PauseReason::Meeting),
+            "break" => Ok(PauseReason::Break),
+            _ => Err(FromSqlError::InvalidType),
+        })
+    }
+}
+
+// A finished stretch of work waiting to go on the calendar
+#[derive(Debug, Clone, Queryable)]
+pub struct WorkSession {
+    pub id: i64,
+    pub task_id: TaskId,
+    pub title: String,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+}
+
+// A note the user left on a session as it ended
+#[derive(Debug, Clone, Serialize, Queryable)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionNote {
+    pub task_id: TaskId,
+    pub ended_at: DateTime<Utc>,
+    pub note: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkSessionReport {
+    pub posted: usize,
+    // Too short to be worth an event, or posting was off when they ended
+    pub skipped: usize,
+}
@@ -1,6 +1,18 @@
 use reqwest::Client;
+use reqwest::header::CONTENT_TYPE;
 use chrono::{DateTime, Utc};
-use crate::structs::calendar_event::{CalendarEvent, EventDateTime, EventReminders, ReminderOverride, EventResponse};
+use uuid::Uuid;
+use crate::helpers::http_batch::{self, BatchPart, BatchReply};
+use crate::helpers::reminder_plan;
+use crate::structs::calendar_event::{CalendarEvent, EventDateTime, EventReminders, ReminderOverride, EventResponse, EventList, ListedEvent, EventPatch, FetchedEvent};
+use crate::structs::reminder_frequency::ReminderFrequency;
+use tracing::info;
+
+const MAX_POPUP_REMINDERS: usize = 4;
+// Google's largest page of events
+const EVENTS_PAGE_SIZE: &str = "250";
+// Google suggests at most this many calls per batch request
+const MAX_BATCH_CALLS: usize = 50;
 
 pub async fn create_calendar_event(
     access_token: &str,
@@ -14,83 +26,57 @@ pub async fn create_calendar_event(
         .build()
         .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
     
-    // Create reminder list based on frequency
-    let mut reminders = Vec::new();
+    let event = deadline_event(title, notes, deadline, reminder_frequency);
     
-    // Only add reminders if reminder_frequency is not empty (empty = paused/completed)
-    if !reminder_frequency.is_empty() {
-        // Calculate time until deadline
-        let now = Utc::now();
-        let duration_until_deadline = deadline.signed_duration_since(now);
-        let hours_until_deadline = duration_until_deadline.num_hours();
-        
-        // Add popup reminders from now until deadline based on frequency
-        match reminder_frequency {
-            "hourly" => {
-                // Add reminders every hour from now until deadline
-                let max_popup_reminders = 4; // Reserve 1 slot for email reminder (Google limit is 5 total)
-                let reminder_count = std::cmp::min(hours_until_deadline.max(0) as usize, max_popup_reminders);
-                
-                for i in 0..reminder_count {
-                    let minutes_before = ((i as i64 + 1) * 60) as i32; // 1h, 2h, 3h, etc. before deadline
-                    reminders.push(ReminderOverride {
-                        method: "popup".to_string(),
-                        minutes: minutes_before,
-                    });
-                }
-            }
-            "every-3-hours" => {
-                // Add reminders every 3 hours from now until deadline
-                let max_popup_reminders = 4;
-                let reminder_count = std::cmp::min((hours_until_deadline / 3).max(0) as usize, max_popup_reminders);
-                
-                for i in 0..reminder_count {
-                    let minutes_before = ((i as i64 + 1) * 180) as i32; // 3h, 6h, 9h, etc. before deadline
-                    reminders.push(ReminderOverride {
-                        method: "popup".to_string(),
-                        minutes: minutes_before,
-                    });
-                }
-            }
-            "daily" => {
-                // Add reminders every day from now until deadline
-                let days_until_deadline = duration_until_deadline.num_days();
-                let max_popup_reminders = 4;
-                let reminder_count = std::cmp::min(days_until_deadline.max(0) as usize, max_popup_reminders);
-                
-                for i in 0..reminder_count {
-                    let minutes_before = ((i as i64 + 1) * 1440) as i32; // 1 day, 2 days, etc. before deadline
-                    reminders.push(ReminderOverride {
-                        method: "popup".to_string(),
-                        minutes: minutes_before,
-                    });
-                }
-            }
-            _ => {} // "none"
-        }
-        
-        // Always add email reminder 1 hour before deadline (even if no popup reminders)
-        reminders.push(ReminderOverride {
-            method: "email".to_string(),
-            minutes: 60,
-        });
+    let response = client
+        .post("https://www.googleapis.com/calendar/v3/calendars/primary/events")
+        .bearer_auth(access_token)
+        .json(&event)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to create calendar event: {}", e))?;
+    
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_body = response.text().await.unwrap_or_default();
+        return Err(format!("Failed to create event: {} - {}", status, error_body));
     }
     
-    // Create event that ends at deadline (not extends beyond it)
+    let event_response: EventResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse event response: {}", e))?;
+    
+    Ok(event_response.id)
+}
+
+// Record of something already done: no reminders, and it keeps its own start and end
+pub async fn create_past_event(
+    access_token: &str,
+    summary: &str,
+    description: Option<&str>,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<String, String> {
+    let client = Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+    
     let event = CalendarEvent {
-        summary: title.to_string(),
-        description: notes.map(|s| s.to_string()),
+        summary: summary.to_string(),
+        description: description.map(|s| s.to_string()),
         start: EventDateTime {
-            date_time: (deadline - chrono::Duration::hours(1)).to_rfc3339(),
+            date_time: start.to_rfc3339(),
             time_zone: "UTC".to_string(),
         },
         end: EventDateTime {
-            date_time: deadline.to_rfc3339(),
+            date_time: end.to_rfc3339(),
             time_zone: "UTC".to_string(),
         },
         reminders: EventReminders {
             use_default: false,
-            overrides: reminders,
+            overrides: Vec::new(),
         },
     };
     
@@ -129,87 +115,10 @@ pub async fn update_calendar_event(
         .build()
         .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
     
-    // Create reminder list based on frequency
-    let mut reminders = Vec::new();
-    
-    // Only add reminders if reminder_frequency is not empty (empty = paused/completed)
-    if !reminder_frequency.is_empty() {
-        // Calculate time until deadline
-        let now = Utc::now();
-        let duration_until_deadline = deadline.signed_duration_since(now);
-        let hours_until_deadline = duration_until_deadline.num_hours();
-        
-        // Add popup reminders from now until deadline based on frequency
-        match reminder_frequency {
-            "hourly" => {
-                // Add reminders every hour from now until deadline
-                let max_popup_reminders = 4; // Reserve 1 slot for email reminder (Google limit is 5 total)
-                let reminder_count = std::cmp::min(hours_until_deadline.max(0) as usize, max_popup_reminders);
-                
-                for i in 0..reminder_count {
-                    let minutes_before = ((i as i64 + 1) * 60) as i32; // 1h, 2h, 3h, etc. before deadline
-                    reminders.push(ReminderOverride {
-                        method: "popup".to_string(),
-                        minutes: minutes_before,
-                    });
-                }
-            }
-            "every-3-hours" => {
-                // Add reminders every 3 hours from now until deadline
-                let max_popup_reminders = 4;
-                let reminder_count = std::cmp::min((hours_until_deadline / 3).max(0) as usize, max_popup_reminders);
-                
-                for i in 0..reminder_count {
-                    let minutes_before = ((i as i64 + 1) * 180) as i32; // 3h, 6h, 9h, etc. before deadline
-                    reminders.push(ReminderOverride {
-                        method: "popup".to_string(),
-                        minutes: minutes_before,
-                    });
-                }
-            }
-            "daily" => {
-                // Add reminders every day from now until deadline
-                let days_until_deadline = duration_until_deadline.num_days();
-                let max_popup_reminders = 4;
-                let reminder_count = std::cmp::min(days_until_deadline.max(0) as usize, max_popup_reminders);
-                
-                for i in 0..reminder_count {
-                    let minutes_before = ((i as i64 + 1) * 1440) as i32; // 1 day, 2 days, etc. before deadline
-                    reminders.push(ReminderOverride {
-                        method: "popup".to_string(),
-                        minutes: minutes_before,
-                    });
-                }
-            }
-            _ => {} // "none"
-        }
-        
-        // Always add email reminder 1 hour before deadline (even if no popup reminders)
-        reminders.push(ReminderOverride {
-            method: "email".to_string(),
-            minutes: 60,
-        });
-    }
-    
-    let event = CalendarEvent {
-        summary: title.to_string(),
-        description: notes.map(|s| s.to_string()),
-        start: EventDateTime {
-            date_time: (deadline - chrono::Duration::hours(1)).to_rfc3339(),
-            time_zone: "UTC".to_string(),
-        },
-        end: EventDateTime {
-            date_time: deadline.to_rfc3339(),
-            time_zone: "UTC".to_string(),
-        },
-        reminders: EventReminders {
-            use_default: false,
-            overrides: reminders,
-        },
-    };
+    let event = deadline_event(title, notes, deadline, reminder_frequency);
     
     let response = client
-        .patch(&format!(
+        .patch(format!(
             "https://www.googleapis.com/calendar/v3/calendars/primary/events/{}",
             event_id
         ))
@@ -223,7 +132,7 @@ pub async fn update_calendar_event(
     
     // 404 (Not Found) or 410 (Gone) means event was deleted externally
     if status.as_u16() == 404 || status.as_u16() == 410 {
-        println!("Calendar event {} not found - may have been deleted externally", event_id);
+        info!("Calendar event {} not found - may have been deleted externally", event_id);
         return Err("EVENT_NOT_FOUND".to_string());
     }
     
@@ -245,7 +154,7 @@ pub async fn delete_calendar_event(
         .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
     
     let response = client
-        .delete(&format!(
+        .delete(format!(
             "https://www.googleapis.com/calendar/v3/calendars/primary/events/{}",
             event_id
         ))
@@ -258,7 +167,7 @@ pub async fn delete_calendar_event(
     
     // 404 (Not Found) or 410 (Gone) means event already deleted - this is OK
     if status.as_u16() == 404 || status.as_u16() == 410 {
-        println!("Calendar event {} already deleted or not found", event_id);
+        info!("Calendar event {} already deleted or not found", event_id);
         return Ok(());
     }
     
@@ -269,3 +178,208 @@ pub async fn delete_calendar_event(
     
     Ok(())
 }
+
+// Events on the primary calendar overlapping the range, recurring ones expanded, in start order
+pub async fn list_events(
+    access_token: &str,
+    time_min: DateTime<Utc>,
+    time_max: DateTime<Utc>,
+) -> Result<Vec<ListedEvent>, String> {
+    let client = Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+    
+    let time_min = time_min.to_rfc3339();
+    let time_max = time_max.to_rfc3339();
+    let mut events = Vec::new();
+    let mut page_token: Option<String> = None;
+    loop {
+        let mut query = vec![
+            ("timeMin", time_min.as_str()),
+            ("timeMax", time_max.as_str()),
+            ("singleEvents", "true"),
+            ("orderBy", "startTime"),
+            ("maxResults", EVENTS_PAGE_SIZE),
+        ];
+        if let Some(token) = &page_token {
+            query.push(("pageToken", token.as_str()));
+        }
+        
+        let response = client
+            .get("https://www.googleapis.com/calendar/v3/calendars/primary/events")
+            .bearer_auth(access_token)
+            .query(&query)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to list calendar events: {}", e))?;
+        
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_body = response.text().await.unwrap_or_default();
+            return Err(format!("Failed to list events: {} - {}", status, error_body));
+        }
+        
+        let page: EventList = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse event list: {}", e))?;
+        events.extend(page.items);
+        
+        match page.next_page_token {
+            Some(token) => page_token = Some(token),
+            None => break,
+        }
+    }
+    
+    Ok(events)
+}
+
+// Task event: an hour ending at the deadline, with reminders for the frequency
+fn deadline_event(title: &str, notes: Option<&str>, deadline: DateTime<Utc>, reminder_frequency: &str) -> CalendarEvent {
+    // Create reminder list based on frequency
+    let mut reminders = Vec::new();
+    
+    // Only add reminders if reminder_frequency is not empty (empty = paused/completed)
+    if !reminder_frequency.is_empty() {
+        // Popup reminders from now until the deadline; one of Google's five is kept for the email
+        let frequency: ReminderFrequency = reminder_frequency.parse().unwrap_or_default();
+        for minutes in reminder_plan::reminder_minutes(&frequency, Utc::now(), deadline, MAX_POPUP_REMINDERS) {
+            reminders.push(ReminderOverride {
+                method: "popup".to_string(),
+                minutes: minutes as i32,
+            });
+        }
+        
+        // Always add email reminder 1 hour before deadline (even if no popup reminders)
+        reminders.push(ReminderOverride {
+            method: "email".to_string(),
+            minutes: 60,
+        });
+    }
+    
+    // Create event that ends at deadline (not extends beyond it)
+    CalendarEvent {
+        summary: title.to_string(),
+        description: notes.map(|s| s.to_string()),
+        start: EventDateTime {
+            date_time: (deadline - chrono::Duration::hours(1)).to_rfc3339(),
+            time_zone: "UTC".to_string(),
+        },
+        end: EventDateTime {
+            date_time: deadline.to_rfc3339(),
+            time_zone: "UTC".to_string(),
+        },
+        reminders: EventReminders {
+            use_default: false,
+            overrides: reminders,
+        },
+    }
+}
+
+// Patch task events through Google's batch endpoint, up to 50 to a request; one result per patch,
+// in order. A batch request that fails as a whole fails each of its patches.
+pub async fn update_calendar_events(
+    access_token: &str,
+    patches: &[EventPatch],
+) -> Result<Vec<Result<(), String>>, String> {
+    let client = Client::builder()
+        .timeout(std::time::Duration::from_secs(60))
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+    
+    let mut results = Vec::with_capacity(patches.len());
+    for chunk in patches.chunks(MAX_BATCH_CALLS) {
+        let parts = chunk.iter()
+            .map(|patch| {
+                let event = deadline_event(&patch.title, patch.notes.as_deref(), patch.deadline, &patch.reminder_frequency);
+                let body = serde_json::to_string(&event)
+                    .map_err(|e| format!("Failed to encode calendar event: {}", e))?;
+                Ok(BatchPart { method: "PATCH", path: event_path(&patch.event_id), body })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        
+        match send_batch(&client, access_token, &parts).await {
+            Ok(replies) => results.extend(chunk.iter().zip(replies).map(|(patch, reply)| match reply {
+                // 404 (Not Found) or 410 (Gone) means event was deleted externally
+                Some(reply) if reply.status == 404 || reply.status == 410 => {
+                    info!("Calendar event {} not found - may have been deleted externally", patch.event_id);
+                    Err("EVENT_NOT_FOUND".to_string())
+                }
+                Some(reply) if (200..300).contains(&reply.status) => Ok(()),
+                Some(reply) => Err(format!("Failed to update event: {} - {}", reply.status, reply.body)),
+                None => Err(format!("Calendar batch response left out event {}", patch.event_id)),
+            })),
+            Err(e) => results.extend(chunk.iter().map(|_| Err(e.clone()))),
+        }
+    }
+    Ok(results)
+}
+
+// Whether each event is still on the primary calendar, in batches like update_calendar_events.
+// Deleted events can linger as cancelled; those count as gone.
+pub async fn events_exist(
+    access_token: &str,
+    event_ids: &[String],
+) -> Result<Vec<Result<bool, String>>, String> {
+    let client = Client::builder()
+        .timeout(std::time::Duration::from_secs(60))
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+    
+    let mut results = Vec::with_capacity(event_ids.len());
+    for chunk in event_ids.chunks(MAX_BATCH_CALLS) {
+        let parts: Vec<BatchPart> = chunk.iter()
+            .map(|event_id| BatchPart { method: "GET", path: event_path(event_id), body: String::new() })
+            .collect();
+        
+        match send_batch(&client, access_token, &parts).await {
+            Ok(replies) => results.extend(chunk.iter().zip(replies).map(|(event_id, reply)| match reply {
+                Some(reply) if reply.status == 404 || reply.status == 410 => Ok(false),
+                Some(reply) if (200..300).contains(&reply.status) => {
+                    let event: FetchedEvent = serde_json::from_str(&reply.body)
+                        .map_err(|e| format!("Failed to parse event {}: {}", event_id, e))?;
+                    Ok(event.status.as_deref() != Some("cancelled"))
+                }
+                Some(reply) => Err(format!("Failed to get event: {} - {}", reply.status, reply.body)),
+                None => Err(format!("Calendar batch response left out event {}", event_id)),
+            })),
+            Err(e) => results.extend(chunk.iter().map(|_| Err(e.clone()))),
+        }
+    }
+    Ok(results)
+}
+
+fn event_path(event_id: &str) -> String {
+    format!("/calendar/v3/calendars/primary/events/{}", event_id)
+}
+
+// Replies to `parts`, by position; the error is for a batch request that failed as a whole
+async fn send_batch(client: &Client, access_token: &str, parts: &[BatchPart]) -> Result<Vec<Option<BatchReply>>, String> {
+    let boundary = format!("batch_{}", Uuid::now_v7().simple());
+    
+    let response = client
+        .post("https://www.googleapis.com/batch/calendar/v3")
+        .bearer_auth(access_token)
+        .header(CONTENT_TYPE, format!("multipart/mixed; boundary={}", boundary))
+        .body(http_batch::encode(&boundary, parts))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to send calendar batch: {}", e))?;
+    
+    let status = response.status();
+    let content_type = response.headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    let body = response.text().await
+        .map_err(|e| format!("Failed to read calendar batch response: {}", e))?;
+    if !status.is_success() {
+        return Err(format!("Failed to send calendar batch: {} - {}", status, body));
+    }
+    
+    let boundary = http_batch::boundary(&content_type)
+        .ok_or_else(|| format!("Calendar batch response isn't multipart: {}", content_type))?;
+    http_batch::decode(boundary, &body, parts.len())
+}
@@ -1,9 +1,10 @@
 use crate::structs::calendar::CalendarCredentials;
+use crate::thirdparty::calendar::oauth_pages::OauthPages;
 use chrono::{Duration, Utc};
 use reqwest::Client;
 use serde::Deserialize;
-use std::sync::{Arc, Mutex};
-use tiny_http::{Server, Response};
+use tiny_http::{Request, Server, Response};
+use tracing::{info, error};
 
 // OAuth Configuration - Replace these with your Google Cloud credentials
 const CLIENT_ID: &str = "456615259862-pmujk4iqc21hrtk90sljuuavh0i2r03t.apps.googleusercontent.com";
@@ -11,12 +12,12 @@ const CLIENT_SECRET: &str = "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";
 const REDIRECT_URI: &str = "http://localhost:3333/oauth/callback";
 const GOOGLE_AUTH_URL: &str = "https://accounts.google.com/o/oauth2/v2/auth";
 const GOOGLE_TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+const GOOGLE_TOKEN_INFO_URL: &str = "https://oauth2.googleapis.com/tokeninfo";
 const SCOPES: &str = "https://www.googleapis.com/auth/calendar.events https://www.googleapis.com/auth/userinfo.email";
 
-// Load HTML templates at compile time
-const SUCCESS_HTML: &str = include_str!("../../oauth_pages/success.html");
-const ERROR_HTML: &str = include_str!("../../oauth_pages/error.html");
-const SECURITY_ERROR_HTML: &str = include_str!("../../oauth_pages/security_error.html");
+// Returned when Google refuses the refresh token for good (revoked, expired or the password changed);
+// retrying can't help, only signing in again
+pub const INVALID_GRANT: &str = "INVALID_GRANT";
 
 #[derive(Debug, Deserialize)]
 struct TokenResponse {
@@ -27,11 +28,30 @@ struct TokenResponse {
     token_type: String,
 }
 
+// Body of a refused token request
+#[derive(Deserialize)]
+struct TokenError {
+    error: String,
+}
+
 #[derive(Deserialize)]
 struct UserInfo {
     email: String,
 }
 
+// What Google knows about an access token
+#[derive(Debug, Deserialize)]
+pub struct TokenInfo {
+    // Space separated
+    #[serde(default)]
+    pub scope: String,
+    // Seconds, sent as a string
+    #[serde(default)]
+    pub expires_in: Option<String>,
+    #[serde(default)]
+    pub email: Option<String>,
+}
+
 // Generate random state for CSRF protection
 fn generate_state() -> String {
     use rand::Rng;
@@ -42,7 +62,7 @@ fn generate_state() -> String {
         .collect()
 }
 
-pub async fn start_oauth_flow() -> Result<CalendarCredentials, String> {
+pub async fn start_oauth_flow(pages: &OauthPages) -> Result<CalendarCredentials, String> {
     // Generate auth URL with state
     let state = generate_state();
     
@@ -63,8 +83,7 @@ pub async fn start_oauth_flow() -> Result<CalendarCredentials, String> {
     let server = Server::http("127.0.0.1:3333")
         .map_err(|e| format!("Failed to start server: {}", e))?;
     
-    let code_result = Arc::new(Mutex::new(None::<String>));
-    let code_clone = code_result.clone();
+    let mut callback = None;
     
     // Wait for callback with timeout
     let timeout = std::time::Duration::from_secs(300); // 5 minutes
@@ -103,40 +122,45 @@ pub async fn start_oauth_flow() -> Result<CalendarCredentials, String> {
             
             // Check for error
             if let Some(err) = error {
-                let html = ERROR_HTML.replace("{{ERROR_MESSAGE}}", &err);
-                let response = Response::from_string(html)
-                    .with_header(tiny_http::Header::from_bytes(&b"Content-Type"[..], &b"text/html"[..]).unwrap());
-                let _ = request.respond(response);
+                let err = urlencoding::decode(&err).map(|e| e.to_string()).unwrap_or(err);
+                respond_html(request, pages.error(&err));
                 return Err(format!("Authorization error: {}", err));
             }
             
             // Verify state (CSRF protection)
             if received_state.as_deref() != Some(&state) {
-                let response = Response::from_string(SECURITY_ERROR_HTML)
-                    .with_header(tiny_http::Header::from_bytes(&b"Content-Type"[..], &b"text/html"[..]).unwrap());
-                let _ = request.respond(response);
+                respond_html(request, pages.security_error());
                 return Err("Invalid state - possible CSRF attack".to_string());
             }
             
             if let Some(auth_code) = code {
-                println!("Authorization code received!");
-                
-                // Send success page to browser
-                let response = Response::from_string(SUCCESS_HTML)
-                    .with_header(tiny_http::Header::from_bytes(&b"Content-Type"[..], &b"text/html"[..]).unwrap());
-                let _ = request.respond(response);
-                
-                *code_clone.lock().unwrap() = Some(auth_code);
+                info!("Authorization code received!");
+                callback = Some((request, auth_code));
                 break;
             }
         }
     }
     
-    let auth_code = code_result.lock().unwrap().take()
+    let (request, auth_code) = callback
         .ok_or_else(|| "No authorization code received".to_string())?;
     
-    // Exchange code for tokens
-    exchange_code_for_tokens(&auth_code).await
+    // The browser waits for the exchange, so the page can say which account was connected
+    match exchange_code_for_tokens(&auth_code).await {
+        Ok(credentials) => {
+            respond_html(request, pages.success(&credentials.email));
+            Ok(credentials)
+        }
+        Err(e) => {
+            respond_html(request, pages.error(&e));
+            Err(e)
+        }
+    }
+}
+
+fn respond_html(request: Request, html: String) {
+    let response = Response::from_string(html)
+        .with_header(tiny_http::Header::from_bytes(&b"Content-Type"[..], &b"text/html; charset=utf-8"[..]).unwrap());
+    let _ = request.respond(response);
 }
 
 async fn exchange_code_for_tokens(code: &str) -> Result<CalendarCredentials, String> {
@@ -163,7 +187,7 @@ async fn exchange_code_for_tokens(code: &str) -> Result<CalendarCredentials, Str
     if !response.status().is_success() {
         let status = response.status();
         let error_body = response.text().await.unwrap_or_default();
-        eprintln!("Token exchange failed: {} - {}", status, error_body);
+        error!("Token exchange failed: {} - {}", status, error_body);
         return Err(format!("Token exchange failed: {}", status));
     }
     
@@ -185,6 +209,7 @@ async fn exchange_code_for_tokens(code: &str) -> Result<CalendarCredentials, Str
         access_token: token_data.access_token,
         refresh_token,
         token_expiry,
+        reauth_needed_at: None,
     })
 }
 
@@ -214,7 +239,7 @@ async fn get_user_email(access_token: &str) -> Result<String, String> {
 }
 
 pub async fn refresh_access_token(refresh_token: &str) -> Result<(String, i64), String> {
-    println!("Refreshing access token...");
+    info!("Refreshing access token...");
     let client = Client::builder()
         .timeout(std::time::Duration::from_secs(30))
         .build()
@@ -237,6 +262,10 @@ pub async fn refresh_access_token(refresh_token: &str) -> Result<(String, i64),
     if !response.status().is_success() {
         let status = response.status();
         let error_body = response.text().await.unwrap_or_default();
+        if serde_json::from_str::<TokenError>(&error_body).is_ok_and(|body| body.error == "invalid_grant") {
+            error!("Refresh token refused: {}", error_body);
+            return Err(INVALID_GRANT.to_string());
+        }
         return Err(format!("Token refresh failed: {} - {}", status, error_body));
     }
     
@@ -245,6 +274,37 @@ pub async fn refresh_access_token(refresh_token: &str) -> Result<(String, i64),
         .await
         .map_err(|e| format!("Failed to parse refresh response: {}", e))?;
     
-    println!("Token refreshed successfully");
+    info!("Token refreshed successfully");
     Ok((token_data.access_token, token_data.expires_in))
 }
+
+// Scopes the app asks for; a connection missing any of them can't do everything
+pub fn required_scopes() -> Vec<&'static str> {
+    SCOPES.split_whitespace().collect()
+}
+
+// Cheapest authenticated call there is: fails for revoked or expired tokens, and lists the granted scopes
+pub async fn get_token_info(access_token: &str) -> Result<TokenInfo, String> {
+    let client = Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+    
+    let response = client
+        .get(GOOGLE_TOKEN_INFO_URL)
+        .query(&[("access_token", access_token)])
+        .send()
+        .await
+        .map_err(|e| format!("Failed to check token: {}", e))?;
+    
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_body = response.text().await.unwrap_or_default();
+        return Err(format!("Token check failed: {} - {}", status, error_body));
+    }
+    
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse token info: {}", e))
+}
@@ -1,5 +1,7 @@
 pub mod google_oauth;
+mod oauth_pages;
 mod google_calendar_api;
 
-pub use google_oauth::{start_oauth_flow, refresh_access_token};
-pub use google_calendar_api::{create_calendar_event, update_calendar_event, delete_calendar_event};
+pub use google_oauth::{start_oauth_flow, refresh_access_token, get_token_info, required_scopes, INVALID_GRANT};
+pub use oauth_pages::OauthPages;
+pub use google_calendar_api::{create_calendar_event, create_past_event, update_calendar_event, update_calendar_events, events_exist, delete_calendar_event, list_events};
@@ -0,0 +1,195 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use crate::helpers::template;
+use crate::structs::settings::Locale;
+use tracing::{info, warn};
+
+// Pages the browser shows when Google sends it back to the app.
+// A file of the same name in the override folder (app data/oauth_pages) replaces the built-in page;
+// every page gets all the placeholders below, in the locale from settings.
+
+const SUCCESS_HTML: &str = include_str!("../../oauth_pages/success.html");
+const ERROR_HTML: &str = include_str!("../../oauth_pages/error.html");
+const SECURITY_ERROR_HTML: &str = include_str!("../../oauth_pages/security_error.html");
+
+#[derive(Debug, Clone, Copy)]
+enum OauthPage {
+    Success,
+    Error,
+    SecurityError,
+}
+
+impl OauthPage {
+    fn file_name(&self) -> &'static str {
+        match self {
+            OauthPage::Success => "success.html",
+            OauthPage::Error => "error.html",
+            OauthPage::SecurityError => "security_error.html",
+        }
+    }
+    
+    fn built_in(&self) -> &'static str {
+        match self {
+            OauthPage::Success => SUCCESS_HTML,
+            OauthPage::Error => ERROR_HTML,
+            OauthPage::SecurityError => SECURITY_ERROR_HTML,
+        }
+    }
+}
+
+// Sentences that name the app are split around it, so the page can highlight the name
+struct PageText {
+    success_title: &'static str,
+    success_heading: &'static str,
+    connected: [&'static str; 2],
+    signed_in_as: &'static str,
+    success_close: &'static str,
+    error_title: &'static str,
+    failed: [&'static str; 2],
+    error_close: &'static str,
+    security_title: &'static str,
+    security_warning: &'static str,
+    security_stopped: &'static str,
+    security_close: [&'static str; 2],
+}
+
+const ENGLISH: PageText = PageText {
+    success_title: "Authorization Successful",
+    success_heading: "Authorization Successful!",
+    connected: ["Your Google Calendar has been connected to ", "."],
+    signed_in_as: "Signed in as",
+    success_close: "You can now close this window and return to the app.",
+    error_title: "Authorization Failed",
+    failed: ["There was a problem connecting your Google Calendar to ", "."],
+    error_close: "Please close this window and try again in the app.",
+    security_title: "Security Error",
+    security_warning: "Invalid state parameter detected. This could be a CSRF attack or an expired authorization session.",
+    security_stopped: "For your security, the authorization process has been stopped.",
+    security_close: ["Please close this window and try connecting your calendar again in ", "."],
+};
+
+const GERMAN: PageText = PageText {
+    success_title: "Autorisierung erfolgreich",
+    success_heading: "Autorisierung erfolgreich!",
+    connected: ["Dein Google Kalender wurde mit ", " verbunden."],
+    signed_in_as: "Angemeldet als",
+    success_close: "Du kannst dieses Fenster jetzt schließen und zur App zurückkehren.",
+    error_title: "Autorisierung fehlgeschlagen",
+    failed: ["Beim Verbinden deines Google Kalenders mit ", " ist ein Problem aufgetreten."],
+    error_close: "Bitte schließe dieses Fenster und versuche es in der App erneut.",
+    security_title: "Sicherheitsfehler",
+    security_warning: "Ungültiger state-Parameter erkannt. Das kann ein CSRF-Angriff oder eine abgelaufene Autorisierungssitzung sein.",
+    security_stopped: "Zu deiner Sicherheit wurde die Autorisierung abgebrochen.",
+    security_close: ["Bitte schließe dieses Fenster und verbinde deinen Kalender in ", " erneut."],
+};
+
+const FRENCH: PageText = PageText {
+    success_title: "Autorisation réussie",
+    success_heading: "Autorisation réussie !",
+    connected: ["Votre Google Agenda est maintenant connecté à ", "."],
+    signed_in_as: "Connecté en tant que",
+    success_close: "Vous pouvez fermer cette fenêtre et revenir à l'application.",
+    error_title: "Échec de l'autorisation",
+    failed: ["Un problème est survenu lors de la connexion de votre Google Agenda à ", "."],
+    error_close: "Veuillez fermer cette fenêtre et réessayer dans l'application.",
+    security_title: "Erreur de sécurité",
+    security_warning: "Paramètre state invalide. Il peut s'agir d'une attaque CSRF ou d'une session d'autorisation expirée.",
+    security_stopped: "Par sécurité, l'autorisation a été interrompue.",
+    security_close: ["Veuillez fermer cette fenêtre et reconnecter votre agenda dans ", "."],
+};
+
+const SPANISH: PageText = PageText {
+    success_title: "Autorización completada",
+    success_heading: "¡Autorización completada!",
+    connected: ["Tu Google Calendar se ha conectado a ", "."],
+    signed_in_as: "Conectado como",
+    success_close: "Ya puedes cerrar esta ventana y volver a la aplicación.",
+    error_title: "Error de autorización",
+    failed: ["Hubo un problema al conectar tu Google Calendar a ", "."],
+    error_close: "Cierra esta ventana y vuelve a intentarlo en la aplicación.",
+    security_title: "Error de seguridad",
+    security_warning: "Se detectó un parámetro state no válido. Puede ser un ataque CSRF o una sesión de autorización caducada.",
+    security_stopped: "Por tu seguridad, se ha detenido la autorización.",
+    security_close: ["Cierra esta ventana y vuelve a conectar tu calendario en ", "."],
+};
+
+#[derive(Debug, Clone)]
+pub struct OauthPages {
+    locale: Locale,
+    // Folder with pages replacing the built-in ones; None uses the built-in pages only
+    override_dir: Option<PathBuf>,
+}
+
+impl OauthPages {
+    pub fn new(locale: Locale, override_dir: Option<PathBuf>) -> Self {
+        OauthPages { locale, override_dir }
+    }
+    
+    fn text(&self) -> &'static PageText {
+        match self.locale {
+            Locale::EnUs | Locale::EnGb => &ENGLISH,
+            Locale::De => &GERMAN,
+            Locale::Fr => &FRENCH,
+            Locale::Es => &SPANISH,
+        }
+    }
+    
+    pub fn success(&self, email: &str) -> String {
+        self.render(OauthPage::Success, email, "")
+    }
+    
+    pub fn error(&self, message: &str) -> String {
+        self.render(OauthPage::Error, "", message)
+    }
+    
+    pub fn security_error(&self) -> String {
+        self.render(OauthPage::SecurityError, "", "")
+    }
+    
+    fn render(&self, page: OauthPage, email: &str, error_message: &str) -> String {
+        let text = self.text();
+        let lang = self.locale.as_str();
+        let values = [
+            ("LANG", lang),
+            ("APP_NAME", "MyHandler"),
+            ("EMAIL", email),
+            ("ERROR_MESSAGE", error_message),
+            ("SUCCESS_TITLE", text.success_title),
+            ("SUCCESS_HEADING", text.success_heading),
+            ("CONNECTED_BEFORE", text.connected[0]),
+            ("CONNECTED_AFTER", text.connected[1]),
+            ("SIGNED_IN_AS", text.signed_in_as),
+            ("SUCCESS_CLOSE", text.success_close),
+            ("ERROR_TITLE", text.error_title),
+            ("FAILED_BEFORE", text.failed[0]),
+            ("FAILED_AFTER", text.failed[1]),
+            ("ERROR_CLOSE", text.error_close),
+            ("SECURITY_TITLE", text.security_title),
+            ("SECURITY_WARNING", text.security_warning),
+            ("SECURITY_STOPPED", text.security_stopped),
+            ("SECURITY_CLOSE_BEFORE", text.security_close[0]),
+            ("SECURITY_CLOSE_AFTER", text.security_close[1]),
+        ];
+        template::render(&self.template(page), &values)
+    }
+    
+    // A broken override falls back to the built-in page; the browser must get something
+    fn template(&self, page: OauthPage) -> String {
+        let Some(dir) = &self.override_dir else {
+            return page.built_in().to_string();
+        };
+        let path = dir.join(page.file_name());
+        match fs::read_to_string(&path) {
+            Ok(html) => {
+                info!("Using OAuth page {}", path.display());
+                html
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => page.built_in().to_string(),
+            Err(e) => {
+                warn!("Failed to read {}, using the built-in page: {}", path.display(), e);
+                page.built_in().to_string()
+            }
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use reqwest::{Client, RequestBuilder};
+use crate::structs::github::{GithubIssue, GithubUser};
+use tracing::info;
+
+const API_BASE: &str = "https://api.github.com";
+const PAGE_SIZE: usize = 100;
+// Enough for anyone's assigned issues; stops a runaway loop on a misbehaving API
+const MAX_PAGES: usize = 10;
+
+fn client() -> Result<Client, String> {
+    Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .user_agent("MyHandler")
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))
+}
+
+fn authorized(request: RequestBuilder, token: &str) -> RequestBuilder {
+    request
+        .bearer_auth(token)
+        .header("Accept", "application/vnd.github+json")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+}
+
+// Check the token and return whose it is
+pub async fn get_authenticated_user(token: &str) -> Result<GithubUser, String> {
+    let response = authorized(client()?.get(format!("{}/user", API_BASE)), token)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach GitHub: {}", e))?;
+    
+    let status = response.status();
+    if status.as_u16() == 401 {
+        return Err("GitHub rejected the token".to_string());
+    }
+    if !status.is_success() {
+        let error_body = response.text().await.unwrap_or_default();
+        return Err(format!("Failed to verify GitHub token: {} - {}", status, error_body));
+    }
+    
+    response.json::<GithubUser>()
+        .await
+        .map_err(|e| format!("Failed to parse GitHub user: {}", e))
+}
+
+// Open issues (and pull requests) assigned to the token's user across all repositories
+pub async fn list_assigned_issues(token: &str) -> Result<Vec<GithubIssue>, String> {
+    let client = client()?;
+    let mut issues = Vec::new();
+    
+    for page in 1..=MAX_PAGES {
+        let response = authorized(client.get(format!("{}/issues", API_BASE)), token)
+            .query(&[
+                ("filter", "assigned"),
+                ("state", "open"),
+                ("per_page", &PAGE_SIZE.to_string()),
+                ("page", &page.to_string()),
+            ])
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch GitHub issues: {}", e))?;
+        
+        let status = response.status();
+        if !status.is_success() {
+            let error_body = response.text().await.unwrap_or_default();
+            return Err(format!("Failed to fetch GitHub issues: {} - {}", status, error_body));
+        }
+        
+        let batch = response.json::<Vec<GithubIssue>>()
+            .await
+            .map_err(|e| format!("Failed to parse GitHub issues: {}", e))?;
+        let done = batch.len() < PAGE_SIZE;
+        issues.extend(batch);
+        if done {
+            break;
+        }
+    }
+    
+    info!("Fetched {} assigned GitHub issues", issues.len());
+    Ok(issues)
+}
+
+// `external_id` is "owner/repo#number"
+pub async fn close_issue(token: &str, external_id: &str) -> Result<(), String> {
+    let (repo, number) = external_id.rsplit_once('#')
+        .ok_or_else(|| format!("Invalid GitHub issue ID: {}", external_id))?;
+    
+    let response = authorized(client()?.patch(format!("{}/repos/{}/issues/{}", API_BASE, repo, number)), token)
+        .json(&serde_json::json!({ "state": "closed", "state_reason": "completed" }))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to close GitHub issue: {}", e))?;
+    
+    let status = response.status();
+    if !status.is_success() {
+        let error_body = response.text().await.unwrap_or_default();
+        return Err(format!("Failed to close GitHub issue: {} - {}", status, error_body));
+    }
+    
+    info!("Closed GitHub issue {}", external_id);
+    Ok(())
+}
@@ -0,0 +1,3 @@
+mod github_api;
+
+pub use github_api::{get_authenticated_user, list_assigned_issues, close_issue};
@@ -1 +1,5 @@
 pub mod calendar;
+pub mod github;
+
+pub mod webdav;
+pub mod webhook;
\ No newline at end of file
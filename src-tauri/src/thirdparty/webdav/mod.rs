@@ -0,0 +1,3 @@
+mod webdav_api;
+
+pub use webdav_api::{get, put, list};
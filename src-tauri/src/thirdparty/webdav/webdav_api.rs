@@ -0,0 +1,145 @@
+use reqwest::{Client, Method, RequestBuilder, StatusCode};
+use crate::structs::sync::WebDavAccount;
+use tracing::debug;
+
+fn client() -> Result<Client, String> {
+    Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .user_agent("MyHandler")
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))
+}
+
+fn request(client: &Client, account: &WebDavAccount, method: Method, path: &str) -> RequestBuilder {
+    let request = client.request(method, format!("{}/{}", account.url, path));
+    match &account.username {
+        Some(username) => request.basic_auth(username, account.password.as_deref()),
+        None => request,
+    }
+}
+
+fn method(name: &'static str) -> Method {
+    Method::from_bytes(name.as_bytes()).expect("valid WebDAV method name")
+}
+
+// Upload a file, creating its folders when the server says they are missing
+pub async fn put(account: &WebDavAccount, path: &str, body: Vec<u8>) -> Result<(), String> {
+    let client = client()?;
+    let mut status = send_put(&client, account, path, body.clone()).await?;
+    
+    // Servers answer 409 (or 404) when the parent collection doesn't exist
+    if status == StatusCode::CONFLICT || status == StatusCode::NOT_FOUND {
+        make_parents(&client, account, path).await?;
+        status = send_put(&client, account, path, body).await?;
+    }
+    
+    if !status.is_success() {
+        return Err(format!("WebDAV upload of {} failed: {}", path, status));
+    }
+    Ok(())
+}
+
+async fn send_put(client: &Client, account: &WebDavAccount, path: &str, body: Vec<u8>) -> Result<StatusCode, String> {
+    let response = request(client, account, Method::PUT, path)
+        .body(body)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach WebDAV server: {}", e))?;
+    check_auth(response.status())?;
+    Ok(response.status())
+}
+
+async fn make_parents(client: &Client, account: &WebDavAccount, path: &str) -> Result<(), String> {
+    let segments: Vec<&str> = path.split('/').collect();
+    for end in 1..segments.len() {
+        let dir = segments[..end].join("/");
+        let response = request(client, account, method("MKCOL"), &format!("{}/", dir))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to reach WebDAV server: {}", e))?;
+        let status = response.status();
+        check_auth(status)?;
+        // 405: the collection already exists
+        if !status.is_success() && status != StatusCode::METHOD_NOT_ALLOWED {
+            return Err(format!("Failed to create WebDAV folder {}: {}", dir, status));
+        }
+    }
+    Ok(())
+}
+
+// File contents, or None if it doesn't exist
+pub async fn get(account: &WebDavAccount, path: &str) -> Result<Option<Vec<u8>>, String> {
+    let response = request(&client()?, account, Method::GET, path)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach WebDAV server: {}", e))?;
+    
+    let status = response.status();
+    check_auth(status)?;
+    if status == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !status.is_success() {
+        return Err(format!("WebDAV download of {} failed: {}", path, status));
+    }
+    
+    let body = response.bytes()
+        .await
+        .map_err(|e| format!("Failed to read {} from WebDAV: {}", path, e))?;
+    Ok(Some(body.to_vec()))
+}
+
+// Names of the entries directly inside a folder; empty if the folder doesn't exist
+pub async fn list(account: &WebDavAccount, dir: &str) -> Result<Vec<String>, String> {
+    let response = request(&client()?, account, method("PROPFIND"), &format!("{}/", dir))
+        .header("Depth", "1")
+        .header("Content-Type", "application/xml")
+        .body(r#"<?xml version="1.0"?><propfind xmlns="DAV:"><prop><resourcetype/></prop></propfind>"#)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach WebDAV server: {}", e))?;
+    
+    let status = response.status();
+    check_auth(status)?;
+    if status == StatusCode::NOT_FOUND {
+        return Ok(Vec::new());
+    }
+    if !status.is_success() {
+        return Err(format!("Failed to list WebDAV folder {}: {}", dir, status));
+    }
+    
+    let body = response.text()
+        .await
+        .map_err(|e| format!("Failed to read WebDAV listing: {}", e))?;
+    let own_suffix = format!("/{}", dir.trim_matches('/'));
+    
+    let names = hrefs(&body).into_iter()
+        .map(|href| urlencoding::decode(&href).map(|href| href.into_owned()).unwrap_or(href))
+        .map(|href| href.trim_end_matches('/').to_string())
+        // The listing includes the folder itself
+        .filter(|href| !href.ends_with(&own_suffix))
+        .filter_map(|href| href.rsplit('/').next().map(str::to_string))
+        .filter(|name| !name.is_empty())
+        .collect();
+    debug!("WebDAV folder {} listed", dir);
+    Ok(names)
+}
+
+fn check_auth(status: StatusCode) -> Result<(), String> {
+    if status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN {
+        return Err("WebDAV server rejected the username or password".to_string());
+    }
+    Ok(())
+}
+
+// Text of every <href> element, whatever namespace prefix the server uses
+fn hrefs(body: &str) -> Vec<String> {
+    body.split('<')
+        .filter_map(|part| part.split_once('>'))
+        .filter(|(tag, _)| {
+            let name = tag.rsplit(':').next().unwrap_or(tag);
+            name.trim().eq_ignore_ascii_case("href")
+        })
+        .map(|(_, text)| text.trim().to_string())
+        .collect()
+}
@@ -0,0 +1,3 @@
+mod webhook_api;
+
+pub use webhook_api::{post_json, post_signed, DeliveryError};
@@ -0,0 +1,71 @@
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use reqwest::Client;
+use serde::Serialize;
+use sha2::Sha256;
+
+// A failed delivery attempt; status is None when no response came back
+pub struct DeliveryError {
+    pub status: Option<u16>,
+    pub message: String,
+}
+
+fn client() -> Result<Client, String> {
+    Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .user_agent("MyHandler")
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))
+}
+
+// POST a JSON body; any 2xx counts as delivered
+pub async fn post_json<T: Serialize>(url: &str, body: &T) -> Result<(), String> {
+    let response = client()?
+        .post(url)
+        .json(body)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach webhook {}: {}", url, e))?;
+    
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("Webhook {} answered {}", url, status));
+    }
+    Ok(())
+}
+
+// POST a signed event body. The signature is `sha256=` + hex HMAC-SHA256 of "{timestamp}.{body}"
+// under the webhook's secret, so receivers can verify the sender and reject replays
+pub async fn post_signed(url: &str, secret: &str, event: &str, delivery_id: i64, body: &str) -> Result<u16, DeliveryError> {
+    let failed = |message: String| DeliveryError { status: None, message };
+    
+    let timestamp = Utc::now().timestamp();
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .map_err(|e| failed(format!("Invalid signing secret: {}", e)))?;
+    mac.update(format!("{}.{}", timestamp, body).as_bytes());
+    let signature: String = mac.finalize().into_bytes().iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    
+    let response = client()
+        .map_err(failed)?
+        .post(url)
+        .header("Content-Type", "application/json")
+        .header("X-MyHandler-Event", event)
+        .header("X-MyHandler-Delivery", delivery_id.to_string())
+        .header("X-MyHandler-Timestamp", timestamp.to_string())
+        .header("X-MyHandler-Signature", format!("sha256={}", signature))
+        .body(body.to_string())
+        .send()
+        .await
+        .map_err(|e| failed(format!("Failed to reach webhook {}: {}", url, e)))?;
+    
+    let status = response.status();
+    if !status.is_success() {
+        return Err(DeliveryError {
+            status: Some(status.as_u16()),
+            message: format!("Webhook {} answered {}", url, status),
+        });
+    }
+    Ok(status.as_u16())
+}
@@ -0,0 +1,153 @@
+use chrono::Utc;
+use tauri::menu::{Menu, MenuItem, PredefinedMenuItem};
+use tauri::tray::TrayIconBuilder;
+use tauri::{AppHandle, Emitter, Listener, Manager, Wry};
+use crate::db::Database;
+use crate::services::{event_service, task_service};
+use crate::structs::dto::{CompleteTaskData, DateQuery};
+use crate::structs::overview::TodayOverview;
+use crate::window_manager;
+use tracing::error;
+
+const TRAY_ID: &str = "main-tray";
+
+// Menu item IDs
+const SUMMARY_ID: &str = "summary";
+const COMPLETE_PREFIX: &str = "complete:";
+const QUICK_ADD_ID: &str = "quick-add";
+const SHOW_ID: &str = "show";
+const QUIT_ID: &str = "quit";
+
+// Create the tray icon with a menu built from today's tasks
+pub fn init_tray(app: &AppHandle) -> tauri::Result<()> {
+    let menu = build_menu(app)?;
+    
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("My Handler")
+        .menu(&menu)
+        .show_menu_on_left_click(true)
+        .on_menu_event(|app, event| handle_menu_event(app, event.id.as_ref()));
+    
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    
+    builder.build(app)?;
+    
+    // Keep the menu in sync with task changes made from any window
+    for event in event_service::TASK_EVENTS {
+        let handle = app.clone();
+        app.listen_any(event, move |_| {
+            // Off the emitting thread: refreshing queries the database
+            let handle = handle.clone();
+            tauri::async_runtime::spawn_blocking(move || refresh_tray(&handle));
+        });
+    }
+    
+    Ok(())
+}
+
+// Rebuild the tray menu from current task state
+pub fn refresh_tray(app: &AppHandle) {
+    let tray = match app.tray_by_id(TRAY_ID) {
+        Some(tray) => tray,
+        None => return, // Tray not created (e.g. unsupported platform)
+    };
+    
+    match build_menu(app) {
+        Ok(menu) => {
+            if let Err(e) = tray.set_menu(Some(menu)) {
+                error!("Failed to update tray menu: {}", e);
+            }
+        }
+        Err(e) => error!("Failed to build tray menu: {}", e),
+    }
+}
+
+fn load_overview(app: &AppHandle) -> Option<TodayOverview> {
+    let db = app.try_state::<Database>()?;
+    let query = DateQuery {
+        date: Utc::now().to_rfc3339(),
+        utc_offset_minutes: None,
+    };
+    
+    match task_service::get_today_overview(query, &db) {
+        Ok(overview) => Some(overview),
+        Err(e) => {
+            error!("Failed to load tray overview: {}", e);
+            None
+        }
+    }
+}
+
+fn build_menu(app: &AppHandle) -> tauri::Result<Menu<Wry>> {
+    let menu = Menu::new(app)?;
+    
+    match load_overview(app) {
+        Some(overview) => {
+            let summary = format!("{} remaining today", overview.remaining_count);
+            menu.append(&MenuItem::with_id(app, SUMMARY_ID, summary, false, None::<&str>)?)?;
+            
+            for task in overview.top_tasks.iter().map(|item| &item.task) {
+                let item = MenuItem::with_id(
+                    app,
+                    format!("{}{}", COMPLETE_PREFIX, task.id),
+                    format!("Complete \"{}\"", task.title),
+                    true,
+                    None::<&str>,
+                )?;
+                menu.append(&item)?;
+            }
+        }
+        None => {
+            menu.append(&MenuItem::with_id(app, SUMMARY_ID, "Tasks unavailable", false, None::<&str>)?)?;
+        }
+    }
+    
+    menu.append(&PredefinedMenuItem::separator(app)?)?;
+    menu.append(&MenuItem::with_id(app, QUICK_ADD_ID, "Quick add…", true, None::<&str>)?)?;
+    menu.append(&MenuItem::with_id(app, SHOW_ID, "Open My Handler", true, None::<&str>)?)?;
+    menu.append(&PredefinedMenuItem::separator(app)?)?;
+    menu.append(&MenuItem::with_id(app, QUIT_ID, "Quit", true, None::<&str>)?)?;
+    
+    Ok(menu)
+}
+
+fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window(window_manager::MAIN_WINDOW) {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+fn handle_menu_event(app: &AppHandle, id: &str) {
+    if let Some(task_id) = id.strip_prefix(COMPLETE_PREFIX) {
+        let app = app.clone();
+        let payload = match task_id.parse() {
+            Ok(id) => CompleteTaskData { id, note: None },
+            Err(e) => {
+                error!("Failed to complete task from tray: {}", e);
+                return;
+            }
+        };
+        tauri::async_runtime::spawn(async move {
+            if let Some(db) = app.try_state::<Database>() {
+                if let Err(e) = task_service::complete_task(payload, &db, &app).await {
+                    error!("Failed to complete task from tray: {}", e);
+                }
+            }
+        });
+        return;
+    }
+    
+    match id {
+        QUICK_ADD_ID => {
+            show_main_window(app);
+            // Frontend focuses its task input when it receives this
+            let _ = app.emit("tray-quick-add", ());
+        }
+        SHOW_ID => show_main_window(app),
+        QUIT_ID => app.exit(0),
+        _ => {}
+    }
+}
@@ -0,0 +1,246 @@
+use chrono::Utc;
+use tauri::{AppHandle, Listener, Manager, WebviewUrl, WebviewWindow, WebviewWindowBuilder, WindowEvent};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+use crate::db::{self, Database};
+use crate::services::event_service;
+use crate::structs::window_state::WindowState;
+use tracing::{info, error};
+
+pub const MAIN_WINDOW: &str = "main";
+pub const QUICK_ADD_WINDOW: &str = "quick-add";
+pub const TODAY_WIDGET_WINDOW: &str = "today-widget";
+// Focus windows are labelled "focus-<task id>"; geometry is shared by all of them
+const FOCUS_WINDOW_PREFIX: &str = "focus-";
+const FOCUS_WINDOW_KIND: &str = "focus";
+
+// Plugin that opens the quick-add window whenever a registered shortcut is pressed
+pub fn shortcut_plugin() -> tauri::plugin::TauriPlugin<tauri::Wry> {
+    tauri_plugin_global_shortcut::Builder::new()
+        .with_handler(|app, _shortcut, event| {
+            if event.state() == ShortcutState::Pressed {
+                toggle_quick_add_window(app);
+            }
+        })
+        .build()
+}
+
+// Register the quick-add hotkey from settings, replacing any previous one
+pub fn register_quick_add_shortcut(app: &AppHandle, shortcut: &str) -> Result<(), String> {
+    let global_shortcut = app.global_shortcut();
+    
+    global_shortcut.unregister_all()
+        .map_err(|e| format!("Failed to unregister shortcuts: {}", e))?;
+    global_shortcut.register(shortcut)
+        .map_err(|e| format!("Failed to register shortcut '{}': {}", shortcut, e))?;
+    
+    info!("Quick add shortcut registered: {}", shortcut);
+    Ok(())
+}
+
+// Register the hotkey stored in settings at startup
+pub fn init_quick_add_shortcut(app: &AppHandle) {
+    let shortcut = match app.try_state::<Database>() {
+        Some(db) => {
+            match db.settings() {
+                Ok(settings) => settings.quick_add_shortcut,
+                Err(e) => {
+                    error!("Failed to load quick add shortcut: {}", e);
+                    return;
+                }
+            }
+        }
+        None => return,
+    };
+    
+    if let Err(e) = register_quick_add_shortcut(app, &shortcut) {
+        error!("{}", e);
+    }
+}
+
+// Show the quick-add window (creating it on first use) or hide it if focused
+pub fn toggle_quick_add_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window(QUICK_ADD_WINDOW) {
+        let visible = window.is_visible().unwrap_or(false);
+        let focused = window.is_focused().unwrap_or(false);
+        
+        if visible && focused {
+            let _ = window.hide();
+        } else {
+            let _ = window.show();
+            let _ = window.set_focus();
+        }
+        return;
+    }
+    
+    let result = WebviewWindowBuilder::new(
+        app,
+        QUICK_ADD_WINDOW,
+        WebviewUrl::App("index.html#/quick-add".into()),
+    )
+    .title("Quick add")
+    .inner_size(480.0, 120.0)
+    .resizable(false)
+    .decorations(false)
+    .always_on_top(true)
+    .skip_taskbar(true)
+    .center()
+    .focused(true)
+    .build();
+    
+    if let Err(e) = result {
+        error!("Failed to create quick add window: {}", e);
+    }
+}
+
+pub fn hide_quick_add_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window(QUICK_ADD_WINDOW) {
+        let _ = window.hide();
+    }
+}
+
+// Close focus windows whose task was deleted; other updates reach windows through the event bus
+pub fn init_detached_windows(app: &AppHandle) {
+    let handle = app.clone();
+    app.listen_any(event_service::TASK_DELETED, move |event| {
+        let task_id = serde_json::from_str::<serde_json::Value>(event.payload())
+            .ok()
+            .and_then(|payload| payload["id"].as_str().map(String::from));
+        
+        if let Some(task_id) = task_id {
+            let label = format!("{}{}", FOCUS_WINDOW_PREFIX, task_id);
+            if let Some(window) = handle.get_webview_window(&label) {
+                let _ = window.close();
+            }
+        }
+    });
+}
+
+// Detached window showing a single task; reuses the window if already open
+pub fn open_focus_window(app: &AppHandle, task_id: &str) -> Result<(), String> {
+    let label = format!("{}{}", FOCUS_WINDOW_PREFIX, task_id);
+    let url = format!("index.html#/focus/{}", task_id);
+    open_detached_window(app, &label, FOCUS_WINDOW_KIND, &url, "Focus", (360.0, 220.0))
+}
+
+// Small always-on-top window with today's tasks
+pub fn open_today_widget(app: &AppHandle) -> Result<(), String> {
+    open_detached_window(app, TODAY_WIDGET_WINDOW, TODAY_WIDGET_WINDOW, "index.html#/today-widget", "Today", (300.0, 420.0))
+}
+
+// Pin or unpin a detached window and remember the choice
+pub fn set_always_on_top(app: &AppHandle, label: &str, always_on_top: bool) -> Result<(), String> {
+    let window = app.get_webview_window(label)
+        .ok_or_else(|| format!("Window '{}' not found", label))?;
+    
+    window.set_always_on_top(always_on_top)
+        .map_err(|e| format!("Failed to set always on top: {}", e))?;
+    
+    save_window_state(&window, always_on_top)
+}
+
+// Persist geometry of all open detached windows (e.g. before quitting)
+pub fn save_window_states(app: &AppHandle) {
+    for (label, window) in app.webview_windows() {
+        if window_kind(&label).is_none() {
+            continue;
+        }
+        let always_on_top = window.is_always_on_top().unwrap_or(true);
+        if let Err(e) = save_window_state(&window, always_on_top) {
+            error!("{}", e);
+        }
+    }
+}
+
+fn window_kind(label: &str) -> Option<&'static str> {
+    if label == TODAY_WIDGET_WINDOW {
+        Some(TODAY_WIDGET_WINDOW)
+    } else if label.starts_with(FOCUS_WINDOW_PREFIX) {
+        Some(FOCUS_WINDOW_KIND)
+    } else {
+        None
+    }
+}
+
+fn open_detached_window(
+    app: &AppHandle,
+    label: &str,
+    kind: &str,
+    url: &str,
+    title: &str,
+    default_size: (f64, f64),
+) -> Result<(), String> {
+    if let Some(window) = app.get_webview_window(label) {
+        let _ = window.show();
+        let _ = window.set_focus();
+        return Ok(());
+    }
+    
+    let saved = {
+        let db = app.try_state::<Database>()
+            .ok_or_else(|| "Database not initialized".to_string())?;
+        let conn = db.get_connection();
+        db::get_window_state(&conn, kind)
+            .map_err(|e| format!("Failed to load window state: {}", e))?
+    }; // DB lock released here
+    
+    let mut builder = WebviewWindowBuilder::new(app, label, WebviewUrl::App(url.into()))
+        .title(title)
+        .skip_taskbar(true)
+        .focused(true);
+    
+    builder = match &saved {
+        Some(state) => builder
+            .inner_size(state.width, state.height)
+            .position(state.x, state.y)
+            .always_on_top(state.always_on_top),
+        None => builder
+            .inner_size(default_size.0, default_size.1)
+            .center()
+            .always_on_top(true),
+    };
+    
+    let window = builder.build()
+        .map_err(|e| format!("Failed to create window '{}': {}", label, e))?;
+    
+    let handle = window.clone();
+    window.on_window_event(move |event| {
+        if let WindowEvent::CloseRequested { .. } = event {
+            let always_on_top = handle.is_always_on_top().unwrap_or(true);
+            if let Err(e) = save_window_state(&handle, always_on_top) {
+                error!("{}", e);
+            }
+        }
+    });
+    
+    Ok(())
+}
+
+fn save_window_state(window: &WebviewWindow, always_on_top: bool) -> Result<(), String> {
+    let kind = window_kind(window.label())
+        .ok_or_else(|| format!("Window '{}' is not a detached window", window.label()))?;
+    
+    let scale = window.scale_factor()
+        .map_err(|e| format!("Failed to read window scale: {}", e))?;
+    let position = window.outer_position()
+        .map_err(|e| format!("Failed to read window position: {}", e))?
+        .to_logical::<f64>(scale);
+    let size = window.inner_size()
+        .map_err(|e| format!("Failed to read window size: {}", e))?
+        .to_logical::<f64>(scale);
+    
+    let state = WindowState {
+        kind: kind.to_string(),
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+        always_on_top,
+        updated_at: Utc::now(),
+    };
+    
+    let db = window.try_state::<Database>()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    let conn = db.get_connection();
+    db::save_window_state(&conn, &state)
+        .map_err(|e| format!("Failed to save window state: {}", e))
+}
@@ -8,6 +8,7 @@ import CalendarPage from "./pages/CalendarPage";
 import FocusPage from "./pages/FocusPage";
 import SettingsPage from "./pages/SettingsPage";
 import NotFound from "./pages/NotFound";
+import { DeepLinkCompleteDialog } from "@/components/tasks";
 
 const queryClient = new QueryClient();
 
@@ -16,6 +17,7 @@ const App = () => (
     <TooltipProvider>
       <Toaster />
       <Sonner />
+      <DeepLinkCompleteDialog />
       <BrowserRouter>
         <Routes>
           <Route path="/" element={<Index />} />
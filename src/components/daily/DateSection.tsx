@@ -7,7 +7,7 @@ import { motion, AnimatePresence } from 'framer-motion';
 interface DateSectionProps {
   section: DateSectionType;
   tasks: Task[];
-  onAddTask: (title: string) => void;
+  onAddTask: (title: string, idempotencyKey: string) => void;
   onStart: (id: string) => void;
   onPause: (id: string) => void;
   onResume: (id: string) => void;
@@ -0,0 +1,55 @@
+import { useEffect, useState } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import { Task } from '@/interfaces/task';
+import { tauriCommands } from '@/lib/tauri';
+import {
+  AlertDialog,
+  AlertDialogAction,
+  AlertDialogCancel,
+  AlertDialogContent,
+  AlertDialogDescription,
+  AlertDialogFooter,
+  AlertDialogHeader,
+  AlertDialogTitle,
+} from '@/components/ui/alert-dialog';
+
+// A myhandler://task/{id}/complete link only asks; the task is completed once the user confirms
+export const DeepLinkCompleteDialog = () => {
+  const [task, setTask] = useState<Task | null>(null);
+
+  useEffect(() => {
+    const unlisten = listen<{ taskId: string }>('deep-link-confirm-complete', async (event) => {
+      const found = await tauriCommands.getTaskById(event.payload.taskId);
+      if (found && found.status !== 'completed') {
+        setTask(found);
+      }
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const handleConfirm = async () => {
+    if (task) {
+      await tauriCommands.completeTask(task.id);
+    }
+    setTask(null);
+  };
+
+  return (
+    <AlertDialog open={task !== null} onOpenChange={(open) => !open && setTask(null)}>
+      <AlertDialogContent>
+        <AlertDialogHeader>
+          <AlertDialogTitle>Complete task?</AlertDialogTitle>
+          <AlertDialogDescription>
+            A link asked to mark "{task?.title}" as completed.
+          </AlertDialogDescription>
+        </AlertDialogHeader>
+        <AlertDialogFooter>
+          <AlertDialogCancel>Cancel</AlertDialogCancel>
+          <AlertDialogAction onClick={handleConfirm}>Complete</AlertDialogAction>
+        </AlertDialogFooter>
+      </AlertDialogContent>
+    </AlertDialog>
+  );
+};
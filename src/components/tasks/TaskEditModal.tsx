@@ -42,6 +42,7 @@ const reminderOptions: { value: ReminderFrequency; label: string }[] = [
   { value: 'hourly', label: 'Every 1 hour' },
   { value: 'every-3-hours', label: 'Every 3 hours' },
   { value: 'daily', label: 'Daily' },
+  { value: 'escalating', label: 'Escalating' },
 ];
 
 export const TaskEditModal = ({
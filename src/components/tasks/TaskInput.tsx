@@ -1,11 +1,11 @@
-import { useState, KeyboardEvent } from 'react';
+import { useRef, useState, KeyboardEvent } from 'react';
 import { Input } from '@/components/ui/input';
 import { Button } from '@/components/ui/button';
 import { Plus } from 'lucide-react';
 import { motion } from 'framer-motion';
 
 interface TaskInputProps {
-  onAddTask: (title: string) => void;
+  onAddTask: (title: string, idempotencyKey: string) => void;
   placeholder?: string;
 }
 
@@ -14,11 +14,20 @@ export const TaskInput = ({
   placeholder = 'Write a task…' 
 }: TaskInputProps) => {
   const [value, setValue] = useState('');
+  // One key per draft: a double-click submits the same draft twice and the backend keeps the first
+  const draftKey = useRef(crypto.randomUUID());
+
+  const handleChange = (next: string) => {
+    if (next !== value) {
+      draftKey.current = crypto.randomUUID();
+    }
+    setValue(next);
+  };
 
   const handleSubmit = () => {
     const trimmed = value.trim();
     if (trimmed) {
-      onAddTask(trimmed);
+      onAddTask(trimmed, draftKey.current);
       setValue('');
     }
   };
@@ -37,7 +46,7 @@ export const TaskInput = ({
     >
       <Input
         value={value}
-        onChange={(e) => setValue(e.target.value)}
+        onChange={(e) => handleChange(e.target.value)}
         onKeyDown={handleKeyDown}
         placeholder={placeholder}
         className="flex-1 h-9 bg-background border-border/50 focus-visible:ring-primary/30"
@@ -3,3 +3,4 @@ export { TaskInput } from './TaskInput';
 export { TaskActions } from './TaskActions';
 export { StatusBadge } from './StatusBadge';
 export { TaskEditModal } from './TaskEditModal';
+export { DeepLinkCompleteDialog } from './DeepLinkCompleteDialog';
@@ -12,11 +12,11 @@ export const useTasks = () => {
     throw err;
   };
 
-  const addTask = useCallback(async (title: string, date: Date = new Date()): Promise<Task> => {
+  const addTask = useCallback(async (title: string, date: Date = new Date(), idempotencyKey?: string): Promise<Task> => {
     setIsLoading(true);
     setError(null);
     try {
-      const task = await tauriCommands.createTask(title.trim(), date);
+      const task = await tauriCommands.createTask(title.trim(), date, idempotencyKey);
       return task;
     } catch (err) {
       return handleError(err);
@@ -2,7 +2,7 @@ export interface Settings {
   id: number;
   darkMode: boolean;
   notificationsEnabled: boolean;
-  defaultReminderFrequency: 'none' | 'hourly' | 'every-3-hours' | 'daily';
+  defaultReminderFrequency: 'none' | 'hourly' | 'every-3-hours' | 'daily' | 'escalating';
   calendarIntegrationEnabled: boolean;
   calendarEmail: string | null;
   createdAt: Date;
@@ -12,7 +12,7 @@ export interface Settings {
 export interface SettingsUpdateData {
   darkMode?: boolean;
   notificationsEnabled?: boolean;
-  defaultReminderFrequency?: 'none' | 'hourly' | 'every-3-hours' | 'daily';
+  defaultReminderFrequency?: 'none' | 'hourly' | 'every-3-hours' | 'daily' | 'escalating';
 }
 
 export interface CalendarCredentials {
@@ -1,6 +1,6 @@
 export type TaskStatus = 'not-started' | 'ongoing' | 'paused' | 'completed';
 
-export type ReminderFrequency = 'none' | 'hourly' | 'every-3-hours' | 'daily';
+export type ReminderFrequency = 'none' | 'hourly' | 'every-3-hours' | 'daily' | 'escalating';
 
 export interface Task {
   id: string;
@@ -16,6 +16,9 @@ export interface Task {
   completedAt?: Date;
   startedAt?: Date;
   pausedAt?: Date;
+  // Set on tasks from date lists, which leave `notes` empty
+  notesPreview?: string;
+  notesTruncated?: boolean;
 }
 
 export interface TaskFormData {
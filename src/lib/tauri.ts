@@ -22,11 +22,12 @@ const parseDateSection = (section: any): DateSection => ({
 // Task Commands
 export const tauriCommands = {
   // Create a new task
-  createTask: async (title: string, taskDate: Date): Promise<Task> => {
+  createTask: async (title: string, taskDate: Date, idempotencyKey?: string): Promise<Task> => {
     const result = await invoke('create_task', { 
       payload: {
         title, 
-        createdAt: taskDate.toISOString()
+        createdAt: taskDate.toISOString(),
+        idempotencyKey
       }
     });
     return parseTask(result);
@@ -91,7 +92,7 @@ export const tauriCommands = {
   // Get tasks by date (excluding completed)
   getTasksByDateNotCompleted: async (date: Date): Promise<Task[]> => {
     const result = await invoke<any[]>('get_tasks_by_date_not_completed', { 
-      payload: { date: date.toISOString() }
+      payload: { date: date.toISOString(), utcOffsetMinutes: -date.getTimezoneOffset() }
     });
     return result.map(parseTask);
   },
@@ -99,7 +100,7 @@ export const tauriCommands = {
   // Get tasks by date
   getTasksByDate: async (date: Date): Promise<Task[]> => {
     const result = await invoke<any[]>('get_tasks_by_date', { 
-      payload: { date: date.toISOString() }
+      payload: { date: date.toISOString(), utcOffsetMinutes: -date.getTimezoneOffset() }
     });
     return result.map(parseTask);
   },
@@ -112,6 +113,13 @@ export const tauriCommands = {
     return result ? parseTask(result) : null;
   },
 
+  // Get the full notes of a task (date lists only carry a preview)
+  getTaskNotes: async (id: string): Promise<string | null> => {
+    return await invoke<string | null>('get_task_notes', { 
+      payload: { id }
+    });
+  },
+
   getOngoingTask: async (): Promise<Task | null> => {
     const result = await invoke('get_ongoing_task');
     return result ? parseTask(result) : null;
@@ -180,7 +188,8 @@ export const tauriCommands = {
     return null;
   },
 
-  disconnectCalendar: async (): Promise<void> => {
-    await invoke('disconnect_calendar');
+  // Event links are kept unless cleared, so reconnecting the same account reuses its events
+  disconnectCalendar: async (clearEventLinks = false): Promise<void> => {
+    await invoke('disconnect_calendar', { payload: { clearEventLinks } });
   },
 };
@@ -61,9 +61,9 @@ const DailyPage = () => {
   }, []);
 
   const handleAddTask = useCallback((date: Date) => {
-    return async (title: string) => {
+    return async (title: string, idempotencyKey: string) => {
       try {
-        await addTask(title, date);
+        await addTask(title, date, idempotencyKey);
         await refreshData();
         toast({
           title: 'Task added',
@@ -78,7 +78,7 @@ const SettingsPage = () => {
     updateSetting({ notificationsEnabled: enabled });
   };
 
-  const handleReminderChange = (frequency: 'none' | 'hourly' | 'every-3-hours' | 'daily') => {
+  const handleReminderChange = (frequency: 'none' | 'hourly' | 'every-3-hours' | 'daily' | 'escalating') => {
     updateSetting({ defaultReminderFrequency: frequency });
   };
 
@@ -197,7 +197,7 @@ const SettingsPage = () => {
                 </div>
                 <Select 
                   value={settings.defaultReminderFrequency} 
-                  onValueChange={(value) => handleReminderChange(value as 'none' | 'hourly' | 'every-3-hours' | 'daily')}
+                  onValueChange={(value) => handleReminderChange(value as 'none' | 'hourly' | 'every-3-hours' | 'daily' | 'escalating')}
                 >
                   <SelectTrigger className="w-40">
                     <SelectValue />
@@ -207,6 +207,7 @@ const SettingsPage = () => {
                     <SelectItem value="hourly">Every hour</SelectItem>
                     <SelectItem value="every-3-hours">Every 3 hours</SelectItem>
                     <SelectItem value="daily">Daily</SelectItem>
+                    <SelectItem value="escalating">Escalating</SelectItem>
                   </SelectContent>
                 </Select>
               </div>